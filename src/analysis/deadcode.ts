@@ -3,7 +3,7 @@
  */
 
 import { extractImports } from '../import/resolver.js'
-import { TEST_PATTERNS, isTestFile } from '../constants/index.js'
+import { TEST_PATTERNS, isDataFile, isTestFile } from '../constants/index.js'
 import { escapeRegExp } from '../utils/string-analysis.js'
 import type { Project, TreeNode } from '../types/core.js'
 import type { DeadcodeResult, DeadcodeMetrics, Finding } from '../types/analysis.js'
@@ -193,6 +193,7 @@ function findUnreachableCode(
   const unusedNodes: TreeNode[] = []
 
   for (const [filePath] of project.files) {
+    if (isDataFile(filePath)) continue
     if (!reachable.has(filePath) && !frameworkFiles.has(filePath)) {
       unusedFiles.push(filePath)
     }
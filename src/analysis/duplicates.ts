@@ -0,0 +1,142 @@
+/**
+ * Duplicate code detection - clusters functions and blocks whose normalized syntax trees hash the same
+ */
+
+import type Parser from 'tree-sitter'
+import { createHash } from 'crypto'
+import { getFileLanguage } from '../core/languages.js'
+import { IDENTIFIER_TYPES, findEnclosingScope, getDefinitionNameNode, getQualifiedName } from '../core/definitions.js'
+import { getCallableKind } from '../core/symbols.js'
+import type { TreeNode, LanguageConfig } from '../types/core.js'
+
+const DEFAULT_MIN_NODES = 30
+
+// Statement blocks are compared on their own so duplicated bodies inside different functions are found
+const BLOCK_TYPES = new Set(['statement_block', 'block', 'compound_statement', 'body_statement'])
+
+const LITERAL_PATTERN = /string|number|integer|float|char|boolean|^true$|^false$|^null$|^nil$|^none$/
+const COMMENT_PATTERN = /comment/
+
+export interface DuplicateLocation {
+  file: string
+  name?: string
+  startLine: number
+  endLine: number
+}
+
+export interface DuplicateCluster {
+  nodeCount: number
+  locations: DuplicateLocation[]
+}
+
+export interface DuplicateOptions {
+  minNodes?: number
+  pathPattern?: string
+}
+
+interface Candidate {
+  hash: string
+  size: number
+  file: string
+  node: Parser.SyntaxNode
+  language: LanguageConfig
+}
+
+/**
+ * Finds clusters of structurally identical code. Identifier names and literal values are ignored,
+ * and clusters nested entirely inside a larger reported cluster are dropped.
+ */
+export function findDuplicates(files: TreeNode[], options: DuplicateOptions = {}): DuplicateCluster[] {
+  const { minNodes = DEFAULT_MIN_NODES, pathPattern } = options
+  const byHash = new Map<string, Candidate[]>()
+
+  for (const fileNode of files) {
+    if (!fileNode.rawNode) continue
+    if (pathPattern && !fileNode.path.includes(pathPattern)) continue
+
+    const language = getFileLanguage(fileNode.path, fileNode.content)
+    if (!language) continue
+
+    hashSubtree(fileNode.rawNode, (node, hash, size) => {
+      if (size < minNodes || !isCandidate(node, language)) return
+      const group = byHash.get(hash) || []
+      group.push({ hash, size, file: fileNode.path, node, language })
+      byHash.set(hash, group)
+    })
+  }
+
+  const groups = Array.from(byHash.values())
+    .filter(group => group.length > 1)
+    .sort((a, b) => b[0]!.size - a[0]!.size)
+
+  const reported: Candidate[] = []
+  const clusters: DuplicateCluster[] = []
+
+  for (const group of groups) {
+    if (group.every(candidate => reported.some(outer => contains(outer, candidate)))) continue
+
+    reported.push(...group)
+    clusters.push({
+      nodeCount: group[0]!.size,
+      locations: group.map(toLocation),
+    })
+  }
+
+  return clusters
+}
+
+/**
+ * Hashes every subtree bottom-up, reporting each named node with its hash and named-node count
+ */
+function hashSubtree(
+  node: Parser.SyntaxNode,
+  onNode: (node: Parser.SyntaxNode, hash: string, size: number) => void,
+): { hash: string, size: number } {
+  if (IDENTIFIER_TYPES.has(node.type)) return { hash: 'id', size: 1 }
+  if (LITERAL_PATTERN.test(node.type)) return { hash: 'lit', size: 1 }
+
+  const parts: string[] = [node.type]
+  let size = 1
+
+  for (const child of node.children) {
+    if (COMMENT_PATTERN.test(child.type)) continue
+
+    if (!child.isNamed) {
+      parts.push(child.type)
+      continue
+    }
+
+    const result = hashSubtree(child, onNode)
+    parts.push(result.hash)
+    size += result.size
+  }
+
+  const hash = createHash('sha1').update(parts.join('\u0000')).digest('hex')
+  onNode(node, hash, size)
+  return { hash, size }
+}
+
+function isCandidate(node: Parser.SyntaxNode, language: LanguageConfig): boolean {
+  return BLOCK_TYPES.has(node.type) || getCallableKind(node, language) !== null
+}
+
+function contains(outer: Candidate, inner: Candidate): boolean {
+  return outer.file === inner.file
+    && outer.node.startIndex <= inner.node.startIndex
+    && outer.node.endIndex >= inner.node.endIndex
+}
+
+function toLocation(candidate: Candidate): DuplicateLocation {
+  const { node, language } = candidate
+  const nameNode = getCallableKind(node, language) ? getDefinitionNameNode(node, language) : null
+  const name = nameNode
+    ? getQualifiedName(node, nameNode)
+    : findEnclosingScope(node, language)?.name
+
+  return {
+    file: candidate.file,
+    ...(name && { name }),
+    startLine: node.startPosition.row + 1,
+    endLine: node.endPosition.row + 1,
+  }
+}
@@ -111,7 +111,7 @@ function resolveImportPath(importPath: string, currentFile: string, fileNodes: T
   return null
 }
 
-function findCircularDependencies(dependencies: Map<string, string[]>): string[][] {
+export function findCircularDependencies(dependencies: Map<string, string[]>): string[][] {
   const visited = new Set<string>()
   const recursionStack = new Set<string>()
   const cycles: string[][] = []
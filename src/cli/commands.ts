@@ -7,6 +7,7 @@ import { analyzeProject, formatAnalysisReport } from '../analysis/index.js'
 import { createProject, parseProject } from '../project/manager.js'
 import { searchCode, findUsage } from '../core/search.js'
 import { renderAnalysis, type AnalysisData } from '../constants/templates.js'
+import { portablePathReplacer } from '../output/paths.js'
 import { getLogger, type Logger } from '../utils/logger.js'
 import type { AnalysisOptions, AnalysisResult, Finding } from '../types/analysis.js'
 
@@ -97,7 +98,7 @@ export async function executeAnalysis(directory: string, options: {
       circularDependencies: metrics.structure?.circularDependencies,
     }
 
-    logger.output(JSON.stringify(renderAnalysis(analysisData, 'json'), null, 2))
+    logger.output(JSON.stringify(renderAnalysis(analysisData, 'json'), portablePathReplacer, 2))
   }
   else if (options.output === 'markdown') {
     logger.output(formatAnalysisReport(result))
@@ -5,16 +5,40 @@
 import { Command } from 'commander'
 import chalk from 'chalk'
 import { execSync } from 'child_process'
+import { readFileSync } from 'fs'
+import { relative, resolve } from 'path'
 import { analyzeProject, formatAnalysisReport } from '../analysis/index.js'
 import { analyzeErrors, partitionErrors } from '../analysis/errors.js'
 import { findDependencyModuleDirs } from '../project/monorepo.js'
-import { searchCode, findUsage } from '../core/search.js'
+import { getAllFiles, getDepthSkipped, getOutsideRootLinks, getParseFailures, getSkippedFiles, getIndexStats } from '../project/manager.js'
+import { streamSearchCode, addEnclosingPaths, addNodeTexts } from '../core/search.js'
+import { createQueryEngine, bufferQuerySource, projectQuerySource, type QuerySource } from '../core/engine.js'
+import { getChangedFiles } from '../core/git.js'
+import { parseBuffer, parseFile } from '../core/parser.js'
+import { getFileLanguage, resolveLanguageNames } from '../core/languages.js'
+import { listSymbols, countSymbols, compileKindFilter } from '../core/symbols.js'
 import { createPersistentManager, getOrCreateProject } from '../project/persistent-manager.js'
+import { getCacheFilePath, isIndexCacheEnabled, setIndexCacheEnabled } from '../project/index-cache.js'
+import { applyGlobalConfig, loadConfig } from '../project/config.js'
+import { setParseThreads } from '../core/parse-pool.js'
+import { setMaxFileSize } from '../core/file-walker.js'
+import { setMaxTrees, getTreeCacheStats } from '../core/tree-cache.js'
 import { startMCPServer } from '../mcp/server.js'
+import { getToolJsonSchemas } from '../mcp/schemas.js'
+import { searchResultsToSarif, findingsToSarif, errorsToSarif } from '../output/sarif.js'
+import { searchResultsToMarkdown } from '../output/markdown.js'
+import { COLOR_MODES, formatRipgrepLines, shouldUseColor, type ColorMode } from '../output/ripgrep.js'
+import { createJsonlWriter } from '../output/jsonl.js'
+import { formatOutlineLines } from '../output/outline.js'
+import { createProgressBar } from '../output/progress.js'
+import { createPositionResolver } from '../output/offsets.js'
+import { portablePathReplacer, toPortablePath } from '../output/paths.js'
 import { renderAnalysis, type AnalysisData, SETUP_TEMPLATE, SETUP_AUTO_SUCCESS_TEMPLATE, SETUP_AUTO_EXISTS_TEMPLATE, SETUP_AUTO_FAILED_TEMPLATE, SETUP_CLAUDE_NOT_FOUND_TEMPLATE } from '../constants/templates.js'
 import { initializeLogger, getLogger } from '../utils/logger.js'
 import { getVersion } from '../utils/version.js'
+import { decodeSource } from '../utils/encoding.js'
 import type { AnalysisOptions as CoreAnalysisOptions } from '../types/analysis.js'
+import type { TreeNode } from '../types/core.js'
 
 const persistentManager = createPersistentManager(10)
 
@@ -24,24 +48,72 @@ export function createCLI(): Command {
     .description('Tree-sitter MCP server for code analysis and search')
     .version(getVersion())
     .option('--mcp', 'Run as MCP server')
+    .option('--json-schema [tool]', 'Print the JSON Schema of every MCP tool\'s parameters and result, or only the named tool\'s, then exit')
+    .option('--watch', 'Reindex changed files while the MCP server runs (default)')
+    .option('--no-watch', 'Do not watch project files for changes')
     .option('--debug', 'Enable debug logging')
     .option('--quiet', 'Suppress non-error output')
+    .option('--no-cache', 'Bypass the on-disk index cache and re-parse every file')
+    .option('--threads <num>', 'Number of parser worker threads (default: number of logical cores)')
+    .option('--max-file-size <bytes>', 'Skip files larger than this many bytes when indexing (default: 5MB)')
+    .option('--max-trees <count>', 'Keep at most this many parse trees in memory, re-parsing others on demand; 0 for no limit (default: 5000)')
+    .hook('preAction', (command, actionCommand) => {
+      const globalOptions = command.opts()
+      setIndexCacheEnabled(globalOptions.cache !== false)
+
+      // Config file values first, so the flags below override them
+      const root = resolve(actionCommand.opts().directory || process.cwd())
+      applyGlobalConfig(loadConfig(root), root)
+
+      const threads = parseInt(globalOptions.threads)
+      if (!Number.isNaN(threads)) {
+        setParseThreads(threads)
+      }
+
+      const maxFileSize = parseInt(globalOptions.maxFileSize)
+      if (!Number.isNaN(maxFileSize)) {
+        setMaxFileSize(maxFileSize)
+      }
+
+      const maxTrees = parseInt(globalOptions.maxTrees)
+      if (!Number.isNaN(maxTrees)) {
+        setMaxTrees(maxTrees)
+      }
+    })
 
   program
     .command('search <query>')
     .description('Search for code elements with progressive content inclusion')
     .option('-d, --directory <dir>', 'Directory to search (default: current directory)')
     .option('-p, --project-id <id>', 'Optional: Project ID for persistent AST caching')
+    .option('--stdin', 'Search source read from stdin instead of a project; requires --language')
+    .option('--language <name>', 'Language of the --stdin source')
     .option('--path-pattern <pattern>', 'Optional: Filter results to files containing this text in their path')
     .option('-t, --type <types...>', 'Filter by element types (function, class, etc.)')
+    .option('-k, --kind <kind>', 'Search declarations of this kind (struct, const, fn) with optional async_, pub_ or static_ prefixes')
     .option('-m, --max-results <num>', 'Maximum number of results', '10')
     .option('--fuzzy-threshold <num>', 'Minimum fuzzy match score (0-100)', '30')
+    .option('--min-query-length <num>', 'Reject shorter queries unless --exact, --whole-word or --subword-aware is set (0 accepts any)', '2')
     .option('--exact', 'Exact match only')
+    .option('--whole-word', 'Match whole identifier words only (add finds add, not address)')
+    .option('--subword-aware', 'Treat camelCase and snake_case segments as words (user finds getUser)')
+    .option('--case-insensitive', 'Ignore case when matching names (case-sensitive by default)')
+    .option('--changed-since <ref>', 'Only search files changed since the branch diverged from this git ref')
+    .option('--no-untracked', 'With --changed-since, leave out files git does not track yet')
+    .option('--sort <order>', 'Result order: relevance, path, recency', 'relevance')
+    .option('--current-file <path>', 'Rank results in and near this file higher')
     .option('--force-content-inclusion', 'Force content inclusion even with 4+ results')
     .option('--max-content-lines <num>', 'Maximum lines for content truncation', '150')
     .option('--disable-content-inclusion', 'Disable content inclusion entirely')
+    .option('--include-node-text', 'With --output json, add the source of the declaration around each match as nodeText')
+    .option('--max-node-bytes <bytes>', 'Cut nodeText after the last statement within this many bytes', '4096')
     .option('--ignore-dirs <dirs...>', 'Additional directories to ignore (beyond default ignore list)')
-    .option('--output <format>', 'Output format (json, text)', 'json')
+    .option('--no-default-excludes', 'Also index node_modules, target, dist, build and the other default excluded directories')
+    .option('--max-depth <levels>', 'Directory levels to descend below the root, which is depth 0 (default: 15)')
+    .option('--follow-symlinks', 'Follow symlinked files and directories, indexing each real path once')
+    .option('--allow-outside-root', 'With --follow-symlinks, also follow links pointing outside the project root')
+    .option('--output <format>', 'Output format (json, text, sarif, markdown, rg, jsonl)', 'json')
+    .option('--color <when>', 'Color text and rg output: auto, always, never (auto respects NO_COLOR)', 'auto')
     .action(handleSearch)
 
   program
@@ -52,8 +124,12 @@ export function createCLI(): Command {
     .option('--path-pattern <pattern>', 'Optional: Filter results to files containing this text in their path')
     .option('-a, --analysis-types <types...>', 'Analysis types to run: quality, deadcode, structure (default: quality)', ['quality'])
     .option('--ignore-dirs <dirs...>', 'Additional directories to ignore (beyond default ignore list)')
+    .option('--no-default-excludes', 'Also index node_modules, target, dist, build and the other default excluded directories')
+    .option('--max-depth <levels>', 'Directory levels to descend below the root, which is depth 0 (default: 15)')
+    .option('--follow-symlinks', 'Follow symlinked files and directories, indexing each real path once')
+    .option('--allow-outside-root', 'With --follow-symlinks, also follow links pointing outside the project root')
     .option('--max-results <num>', 'Maximum number of findings to return', '15')
-    .option('--output <format>', 'Output format (json, text, markdown)', 'json')
+    .option('--output <format>', 'Output format (json, text, markdown, sarif)', 'json')
     .action(handleAnalysis)
 
   program
@@ -63,8 +139,12 @@ export function createCLI(): Command {
     .option('-p, --project-id <id>', 'Optional: Project ID for persistent AST caching')
     .option('--path-pattern <pattern>', 'Optional: Filter results to files containing this text in their path')
     .option('--ignore-dirs <dirs...>', 'Additional directories to ignore (beyond default ignore list)')
+    .option('--no-default-excludes', 'Also index node_modules, target, dist, build and the other default excluded directories')
+    .option('--max-depth <levels>', 'Directory levels to descend below the root, which is depth 0 (default: 15)')
+    .option('--follow-symlinks', 'Follow symlinked files and directories, indexing each real path once')
+    .option('--allow-outside-root', 'With --follow-symlinks, also follow links pointing outside the project root')
     .option('--max-results <num>', 'Maximum number of errors to return', '50')
-    .option('--output <format>', 'Output format (json, text)', 'json')
+    .option('--output <format>', 'Output format (json, text, sarif)', 'json')
     .action(handleErrors)
 
   program
@@ -76,10 +156,51 @@ export function createCLI(): Command {
     .option('--case-sensitive', 'Case sensitive search')
     .option('--exact', 'Exact match only')
     .option('--ignore-dirs <dirs...>', 'Additional directories to ignore (beyond default ignore list)')
+    .option('--no-default-excludes', 'Also index node_modules, target, dist, build and the other default excluded directories')
+    .option('--max-depth <levels>', 'Directory levels to descend below the root, which is depth 0 (default: 15)')
+    .option('--follow-symlinks', 'Follow symlinked files and directories, indexing each real path once')
+    .option('--allow-outside-root', 'With --follow-symlinks, also follow links pointing outside the project root')
     .option('-m, --max-results <num>', 'Maximum number of results', '50')
     .option('--output <format>', 'Output format (json, text)', 'json')
     .action(handleFindUsage)
 
+  program
+    .command('outline [file]')
+    .description('Print an indented outline of the symbols in a file')
+    .option('--stdin', 'Outline source read from stdin instead of a file; requires --language')
+    .option('--language <name>', 'Language of the --stdin source')
+    .option('--depth <num>', 'Nesting levels to show; 1 lists only top-level symbols (default: all)')
+    .option('--output <format>', 'Output format (text, json)', 'text')
+    .option('--color <when>', 'Color text output: auto, always, never (auto respects NO_COLOR)', 'auto')
+    .action(handleOutline)
+
+  program
+    .command('stats')
+    .description('Report index size per language, retained parse trees, cache hit rate, and memory use')
+    .option('-d, --directory <dir>', 'Directory to index (default: current directory)')
+    .option('-p, --project-id <id>', 'Optional: Project ID for persistent AST caching')
+    .option('--ignore-dirs <dirs...>', 'Additional directories to ignore (beyond default ignore list)')
+    .option('--no-default-excludes', 'Also index node_modules, target, dist, build and the other default excluded directories')
+    .option('--max-depth <levels>', 'Directory levels to descend below the root, which is depth 0 (default: 15)')
+    .option('--follow-symlinks', 'Follow symlinked files and directories, indexing each real path once')
+    .option('--allow-outside-root', 'With --follow-symlinks, also follow links pointing outside the project root')
+    .option('--output <format>', 'Output format (json, text)', 'json')
+    .action(handleStats)
+
+  program
+    .command('prebuild')
+    .description('Index a directory and write the on-disk index cache, so a later session starts from it')
+    .option('-d, --directory <dir>', 'Directory to index (default: current directory)')
+    .option('-p, --project-id <id>', 'Optional: Project ID for persistent AST caching')
+    .option('--ignore-dirs <dirs...>', 'Additional directories to ignore (beyond default ignore list)')
+    .option('--no-default-excludes', 'Also index node_modules, target, dist, build and the other default excluded directories')
+    .option('--max-depth <levels>', 'Directory levels to descend below the root, which is depth 0 (default: 15)')
+    .option('--follow-symlinks', 'Follow symlinked files and directories, indexing each real path once')
+    .option('--allow-outside-root', 'With --follow-symlinks, also follow links pointing outside the project root')
+    .option('--strict', 'Exit with status 1 when a file fails to parse or its tree has syntax errors')
+    .option('--output <format>', 'Output format (json, text)', 'json')
+    .action(handlePrebuild)
+
   program
     .command('setup')
     .description('Setup MCP integration')
@@ -94,13 +215,29 @@ export function createCLI(): Command {
 interface SearchOptions {
   directory?: string
   projectId?: string
+  stdin?: boolean
+  language?: string
   pathPattern?: string
   type?: string[]
+  kind?: string
   maxResults: string
   fuzzyThreshold: string
+  minQueryLength: string
   exact?: boolean
+  wholeWord?: boolean
+  subwordAware?: boolean
+  caseInsensitive?: boolean
+  changedSince?: string
+  untracked: boolean
+  sort: string
+  currentFile?: string
   ignoreDirs?: string[]
+  defaultExcludes: boolean
+  maxDepth?: string
+  followSymlinks?: boolean
+  allowOutsideRoot?: boolean
   output: string
+  color?: string
   debug?: boolean
   quiet?: boolean
 
@@ -108,32 +245,52 @@ interface SearchOptions {
   forceContentInclusion?: boolean
   maxContentLines?: string
   disableContentInclusion?: boolean
+  includeNodeText?: boolean
+  maxNodeBytes: string
 }
 
 async function handleSearch(query: string, options: SearchOptions): Promise<void> {
-  const logger = initializeLogger(options.debug ? 'debug' : 'info', options.quiet)
+  // Info logs share stdout with the results, so machine-readable output stays quiet
+  const logger = initializeLogger(options.debug ? 'debug' : 'info', options.quiet || ['sarif', 'markdown', 'rg', 'jsonl'].includes(options.output))
 
   try {
-    logger.info(`Searching for: ${query}`)
-
-    const project = await getOrCreateProject(persistentManager, {
-      directory: options.directory || process.cwd(),
-      languages: [],
-      ignoreDirs: options.ignoreDirs || [],
-      autoWatch: false,
-    }, options.projectId)
+    const colorMode = (options.color || 'auto') as ColorMode
+    if (!COLOR_MODES.includes(colorMode)) {
+      throw new Error(`Invalid color value: ${options.color}. Must be one of: ${COLOR_MODES.join(', ')}.`)
+    }
+    const useColor = shouldUseColor(colorMode)
+    if (!useColor) {
+      chalk.level = 0
+    }
+    else if (chalk.level === 0) {
+      chalk.level = 1
+    }
 
-    const allNodes = Array.from(project.files.values())
-    const elementNodes = Array.from(project.nodes.values()).flat()
+    logger.info(`Searching for: ${query}`)
 
-    if (project.subProjects) {
-      for (const subProject of project.subProjects) {
-        allNodes.push(...Array.from(subProject.files.values()))
-        elementNodes.push(...Array.from(subProject.nodes.values()).flat())
+    let source: QuerySource
+    if (options.stdin) {
+      if (options.changedSince !== undefined) {
+        throw new Error('--changed-since cannot be used with --stdin')
       }
+      // A single buffer, as editor plugins send for unsaved files; no project is indexed
+      source = bufferQuerySource(readStdinBuffer(options.language), process.cwd())
     }
-
-    const searchNodes = [...allNodes, ...elementNodes]
+    else {
+      const project = await getOrCreateProject(persistentManager, {
+        directory: options.directory || process.cwd(),
+        languages: [],
+        ignoreDirs: options.ignoreDirs || [],
+        useDefaultExcludes: options.defaultExcludes,
+        maxDepth: parseMaxDepth(options.maxDepth),
+        followSymlinks: options.followSymlinks,
+        allowOutsideRoot: options.allowOutsideRoot,
+        autoWatch: false,
+        useCache: true,
+      }, options.projectId, { onProgress: options.quiet ? undefined : createProgressBar() })
+      source = projectQuerySource(project)
+    }
+    const { root, files: allNodes } = source
 
     let maxResults = 10
     if (options.maxResults) {
@@ -153,6 +310,11 @@ async function handleSearch(query: string, options: SearchOptions): Promise<void
       fuzzyThreshold = parsed
     }
 
+    const minQueryLength = parseInt(options.minQueryLength)
+    if (isNaN(minQueryLength) || minQueryLength < 0) {
+      throw new Error(`Invalid min-query-length value: ${options.minQueryLength}. Must be a non-negative number.`)
+    }
+
     let maxContentLines = 150
     if (options.maxContentLines) {
       const parsed = parseInt(options.maxContentLines)
@@ -162,39 +324,110 @@ async function handleSearch(query: string, options: SearchOptions): Promise<void
       maxContentLines = parsed
     }
 
-    const results = searchCode(query, searchNodes, {
-      maxResults,
+    const maxNodeBytes = parseInt(options.maxNodeBytes)
+    if (isNaN(maxNodeBytes) || maxNodeBytes < 1) {
+      throw new Error(`Invalid max-node-bytes value: ${options.maxNodeBytes}. Must be a positive number.`)
+    }
+
+    if (options.output === 'jsonl') {
+      // Results are written as they are found, in traversal order rather than ranked
+      let searchNodes = source.nodes
+      if (options.changedSince !== undefined) {
+        const changed = getChangedFiles(root, options.changedSince, { includeUntracked: options.untracked })
+        searchNodes = searchNodes.filter(node => changed.has(node.path))
+      }
+      const writer = createJsonlWriter()
+      streamSearchCode(query, searchNodes, {
+        maxResults,
+        fuzzyThreshold,
+        minQueryLength,
+        kind: options.kind !== undefined ? compileKindFilter(options.kind) : undefined,
+        exactMatch: options.exact,
+        wholeWord: options.wholeWord,
+        subwordAware: options.subwordAware,
+        caseInsensitive: options.caseInsensitive,
+        types: options.type,
+        pathPattern: options.pathPattern,
+        forceContentInclusion: options.forceContentInclusion,
+      }, result => writer.write(result))
+      writer.end(query)
+      return
+    }
+
+    // The MCP search_code tool runs the same engine, so both return the same ranking and totals
+    const { results, totalResults, totalFiles } = await createQueryEngine(source).search(query, {
       fuzzyThreshold,
+      minQueryLength,
+      kind: options.kind,
       exactMatch: options.exact,
+      wholeWord: options.wholeWord,
+      subwordAware: options.subwordAware,
+      caseInsensitive: options.caseInsensitive,
       types: options.type,
       pathPattern: options.pathPattern,
+      changedSince: options.changedSince,
+      includeUntracked: options.untracked,
+      sort: options.sort,
+      currentFile: options.currentFile && resolve(options.currentFile),
+      limit: maxResults,
       // New content inclusion options
       forceContentInclusion: options.forceContentInclusion,
       maxContentLines,
       disableContentInclusion: options.disableContentInclusion,
     })
 
+    if (options.output === 'rg') {
+      for (const line of formatRipgrepLines(results, allNodes, { color: useColor })) {
+        logger.output(line)
+      }
+      // Like rg, exit with 1 when nothing matched
+      if (results.length === 0) {
+        process.exitCode = 1
+      }
+      return
+    }
+
+    if (options.output === 'sarif') {
+      logger.output(JSON.stringify(searchResultsToSarif(query, results, {
+        root,
+        files: allNodes,
+      }), null, 2))
+      return
+    }
+
+    if (options.output === 'markdown') {
+      logger.output(searchResultsToMarkdown(query, addEnclosingPaths(results, allNodes), {
+        root,
+        files: allNodes,
+        totalResults,
+        totalFiles,
+      }))
+      return
+    }
+
     if (options.output === 'json') {
+      const resolvePosition = createPositionResolver(allNodes)
+      const withPaths = addEnclosingPaths(results, allNodes)
       logger.output(JSON.stringify({
         query,
-        results: results.map(r => ({
+        results: (options.includeNodeText ? addNodeTexts(withPaths, allNodes, maxNodeBytes) : withPaths).map(r => ({
           name: r.node.name,
           type: r.node.type,
           path: r.node.path,
-          startLine: r.node.startLine,
-          endLine: r.node.endLine,
-          startColumn: r.node.startColumn,
-          endColumn: r.node.endColumn,
+          ...resolvePosition(r.node),
           score: r.score,
+          relevance: r.relevance,
           matches: r.matches,
+          enclosingPath: r.enclosingPath,
+          nodeText: r.nodeText,
           // New content inclusion fields
           contentIncluded: r.contentIncluded,
           content: r.content,
           contentTruncated: r.contentTruncated,
           contentLines: r.contentLines,
         })),
-        totalResults: results.length,
-      }, null, 2))
+        totalResults,
+      }, portablePathReplacer, 2))
       return
     }
 
@@ -245,7 +478,7 @@ async function handleSearch(query: string, options: SearchOptions): Promise<void
         query,
         results: [],
         totalResults: 0,
-      }, null, 2))
+      }, portablePathReplacer, 2))
     }
     else {
       logger.output(chalk.red(`Search failed: ${errorMessage}`))
@@ -261,6 +494,10 @@ interface AnalysisOptions {
   pathPattern?: string
   analysisTypes?: string[]
   ignoreDirs?: string[]
+  defaultExcludes: boolean
+  maxDepth?: string
+  followSymlinks?: boolean
+  allowOutsideRoot?: boolean
   maxResults?: string
   output?: string
   debug?: boolean
@@ -272,6 +509,10 @@ interface ErrorsOptions {
   projectId?: string
   pathPattern?: string
   ignoreDirs?: string[]
+  defaultExcludes: boolean
+  maxDepth?: string
+  followSymlinks?: boolean
+  allowOutsideRoot?: boolean
   maxResults?: string
   output?: string
   debug?: boolean
@@ -279,7 +520,7 @@ interface ErrorsOptions {
 }
 
 async function handleAnalysis(options: AnalysisOptions): Promise<void> {
-  const logger = initializeLogger(options.debug ? 'debug' : 'info', options.quiet)
+  const logger = initializeLogger(options.debug ? 'debug' : 'info', options.quiet || options.output === 'sarif')
 
   try {
     const analysisTypes = options.analysisTypes || ['quality']
@@ -287,8 +528,13 @@ async function handleAnalysis(options: AnalysisOptions): Promise<void> {
     const project = await getOrCreateProject(persistentManager, {
       directory: options.directory || process.cwd(),
       ignoreDirs: options.ignoreDirs || [],
+      useDefaultExcludes: options.defaultExcludes,
+      maxDepth: parseMaxDepth(options.maxDepth),
+      followSymlinks: options.followSymlinks,
+      allowOutsideRoot: options.allowOutsideRoot,
       autoWatch: false,
-    }, options.projectId)
+      useCache: true,
+    }, options.projectId, { onProgress: options.quiet ? undefined : createProgressBar() })
 
     const depDirs = findDependencyModuleDirs(project.config.directory, project.nodes)
     const analysisOptions: CoreAnalysisOptions = {
@@ -351,7 +597,13 @@ async function handleAnalysis(options: AnalysisOptions): Promise<void> {
     }
 
     if (options.output === 'json') {
-      logger.output(JSON.stringify(filteredResult, null, 2))
+      logger.output(JSON.stringify(filteredResult, portablePathReplacer, 2))
+    }
+    else if (options.output === 'sarif') {
+      logger.output(JSON.stringify(findingsToSarif(limitedFindings, {
+        root: project.config.directory,
+        files: getAllFiles(project),
+      }), null, 2))
     }
     else if (options.output === 'markdown') {
       logger.output(formatAnalysisReport(filteredResult))
@@ -370,7 +622,7 @@ async function handleAnalysis(options: AnalysisOptions): Promise<void> {
         findings: [],
         metrics: {},
         summary: { totalFindings: 0, criticalFindings: 0, warningFindings: 0, infoFindings: 0 },
-      }, null, 2))
+      }, portablePathReplacer, 2))
     }
     else {
       logger.output(chalk.red(`Analysis failed: ${errorMessage}`))
@@ -381,14 +633,19 @@ async function handleAnalysis(options: AnalysisOptions): Promise<void> {
 }
 
 async function handleErrors(options: ErrorsOptions): Promise<void> {
-  const logger = initializeLogger(options.debug ? 'debug' : 'info', options.quiet)
+  const logger = initializeLogger(options.debug ? 'debug' : 'info', options.quiet || options.output === 'sarif')
 
   try {
     const project = await getOrCreateProject(persistentManager, {
       directory: options.directory || process.cwd(),
       ignoreDirs: options.ignoreDirs || [],
+      useDefaultExcludes: options.defaultExcludes,
+      maxDepth: parseMaxDepth(options.maxDepth),
+      followSymlinks: options.followSymlinks,
+      allowOutsideRoot: options.allowOutsideRoot,
       autoWatch: false,
-    }, options.projectId)
+      useCache: true,
+    }, options.projectId, { onProgress: options.quiet ? undefined : createProgressBar() })
 
     logger.info(`Finding errors in ${project.config.directory} (project: ${project.id})...`)
     const result = analyzeErrors(project)
@@ -412,6 +669,14 @@ async function handleErrors(options: ErrorsOptions): Promise<void> {
     const maxResults = options.maxResults ? parseInt(options.maxResults) : 50
     const limitedErrors = filteredErrors.slice(0, maxResults)
 
+    if (options.output === 'sarif') {
+      logger.output(JSON.stringify(errorsToSarif(limitedErrors, {
+        root: project.config.directory,
+        files: getAllFiles(project),
+      }), null, 2))
+      return
+    }
+
     if (options.output === 'json') {
       const condensedErrors = limitedErrors.map(e => ({
         file: e.file,
@@ -430,7 +695,10 @@ async function handleErrors(options: ErrorsOptions): Promise<void> {
         totalSourceErrors: partitioned.sourceErrors.length,
         totalDependencyErrors: partitioned.totalDependencyErrors,
         filteredErrors: limitedErrors.length,
-      }))
+        skippedFiles: getSkippedFiles(project),
+        depthSkipped: getDepthSkipped(project),
+        outsideRootLinks: getOutsideRootLinks(project),
+      }, portablePathReplacer))
     }
     else {
       logger.output(formatErrorsReport({ ...result, errors: limitedErrors }, partitioned))
@@ -446,7 +714,7 @@ async function handleErrors(options: ErrorsOptions): Promise<void> {
         errors: [],
         summary: { totalErrors: 0, filesWithErrors: 0, missingErrors: 0, parseErrors: 0, extraErrors: 0 },
         metrics: { totalFiles: 0, totalErrorNodes: 0, errorsByType: {}, errorsByFile: {} },
-      }, null, 2))
+      }, portablePathReplacer, 2))
     }
     else {
       logger.output(chalk.red(`Error analysis failed: ${errorMessage}`))
@@ -547,6 +815,10 @@ interface FindUsageOptions {
   caseSensitive?: boolean
   exact?: boolean
   ignoreDirs?: string[]
+  defaultExcludes: boolean
+  maxDepth?: string
+  followSymlinks?: boolean
+  allowOutsideRoot?: boolean
   maxResults: string
   output: string
   debug?: boolean
@@ -565,7 +837,7 @@ async function handleFindUsage(identifier: string, options: FindUsageOptions): P
           usages: [],
           totalUsages: 0,
           displayedUsages: 0,
-        }, null, 2))
+        }, portablePathReplacer, 2))
       }
       else {
         logger.output(chalk.yellow('No identifier provided. No usages found.'))
@@ -579,26 +851,13 @@ async function handleFindUsage(identifier: string, options: FindUsageOptions): P
       directory: options.directory || process.cwd(),
       languages: [],
       ignoreDirs: options.ignoreDirs || [],
+      useDefaultExcludes: options.defaultExcludes,
+      maxDepth: parseMaxDepth(options.maxDepth),
+      followSymlinks: options.followSymlinks,
+      allowOutsideRoot: options.allowOutsideRoot,
       autoWatch: false,
-    }, options.projectId)
-
-    const allNodes = Array.from(project.files.values())
-    const elementNodes = Array.from(project.nodes.values()).flat()
-
-    if (project.subProjects) {
-      for (const subProject of project.subProjects) {
-        allNodes.push(...Array.from(subProject.files.values()))
-        elementNodes.push(...Array.from(subProject.nodes.values()).flat())
-      }
-    }
-
-    const searchNodes = [...allNodes, ...elementNodes]
-
-    const results = findUsage(identifier, searchNodes, {
-      caseSensitive: options.caseSensitive,
-      exactMatch: options.exact,
-      pathPattern: options.pathPattern,
-    })
+      useCache: true,
+    }, options.projectId, { onProgress: options.quiet ? undefined : createProgressBar() })
 
     let maxResults = 50
     if (options.maxResults) {
@@ -608,7 +867,12 @@ async function handleFindUsage(identifier: string, options: FindUsageOptions): P
       }
       maxResults = parsed
     }
-    const limitedResults = results.slice(0, maxResults)
+    const { usages: limitedResults, totalUsages } = createQueryEngine(projectQuerySource(project)).findUsage(identifier, {
+      caseSensitive: options.caseSensitive,
+      exactMatch: options.exact,
+      pathPattern: options.pathPattern,
+      maxResults,
+    })
 
     if (options.output === 'json') {
       logger.output(JSON.stringify({
@@ -623,9 +887,9 @@ async function handleFindUsage(identifier: string, options: FindUsageOptions): P
           name: result.node.name || '',
           context: result.context,
         })),
-        totalUsages: results.length,
+        totalUsages,
         displayedUsages: limitedResults.length,
-      }, null, 2))
+      }, portablePathReplacer, 2))
       return
     }
 
@@ -634,9 +898,9 @@ async function handleFindUsage(identifier: string, options: FindUsageOptions): P
       return
     }
 
-    const displayText = results.length > limitedResults.length
-      ? `Found ${results.length} usages (showing first ${limitedResults.length}):\n`
-      : `Found ${results.length} usages:\n`
+    const displayText = totalUsages > limitedResults.length
+      ? `Found ${totalUsages} usages (showing first ${limitedResults.length}):\n`
+      : `Found ${totalUsages} usages:\n`
     logger.output(chalk.cyan(displayText))
 
     for (const result of limitedResults) {
@@ -662,7 +926,7 @@ async function handleFindUsage(identifier: string, options: FindUsageOptions): P
         identifier,
         usages: [],
         totalUsages: 0,
-      }, null, 2))
+      }, portablePathReplacer, 2))
     }
     else {
       logger.output(chalk.red(`Usage search failed: ${errorMessage}`))
@@ -672,6 +936,255 @@ async function handleFindUsage(identifier: string, options: FindUsageOptions): P
   }
 }
 
+interface StatsOptions {
+  directory?: string
+  projectId?: string
+  ignoreDirs?: string[]
+  defaultExcludes: boolean
+  maxDepth?: string
+  followSymlinks?: boolean
+  allowOutsideRoot?: boolean
+  output: string
+  debug?: boolean
+  quiet?: boolean
+}
+
+async function handleStats(options: StatsOptions): Promise<void> {
+  const logger = initializeLogger(options.debug ? 'debug' : 'info', options.quiet)
+
+  try {
+    const project = await getOrCreateProject(persistentManager, {
+      directory: options.directory || process.cwd(),
+      ignoreDirs: options.ignoreDirs || [],
+      useDefaultExcludes: options.defaultExcludes,
+      maxDepth: parseMaxDepth(options.maxDepth),
+      followSymlinks: options.followSymlinks,
+      allowOutsideRoot: options.allowOutsideRoot,
+      autoWatch: false,
+      useCache: true,
+    }, options.projectId, { onProgress: options.quiet ? undefined : createProgressBar() })
+
+    const index = getIndexStats(project)
+    const treeCache = getTreeCacheStats()
+    const { heapUsed, rss } = process.memoryUsage()
+
+    if (options.output === 'json') {
+      logger.output(JSON.stringify({
+        projectId: project.id,
+        directory: project.config.directory,
+        index,
+        treeCache,
+        memory: { estimatedIndexBytes: index.estimatedMemoryBytes, heapUsedBytes: heapUsed, rssBytes: rss },
+      }, portablePathReplacer, 2))
+      return
+    }
+
+    const toMB = (bytes: number) => `${(bytes / 1024 / 1024).toFixed(1)}MB`
+
+    logger.output(chalk.cyan(`Index stats for ${project.config.directory} (project: ${project.id})\n`))
+    const languages = Object.entries(index.languages).sort(([, a], [, b]) => b.files - a.files)
+    for (const [language, entry] of languages) {
+      logger.output(`  ${chalk.bold(language.padEnd(12))} ${String(entry.files).padStart(6)} files ${String(entry.symbols).padStart(8)} symbols ${toMB(entry.bytes).padStart(9)}`)
+    }
+    logger.output(`  ${chalk.bold('total'.padEnd(12))} ${String(index.totalFiles).padStart(6)} files ${String(index.totalSymbols).padStart(8)} symbols ${toMB(index.totalBytes).padStart(9)}`)
+    logger.output('')
+    logger.output(`Parse trees: ${index.parseTrees} retained, cache hit rate ${(treeCache.hitRate * 100).toFixed(1)}%`)
+    logger.output(`Memory: ~${toMB(index.estimatedMemoryBytes)} index, ${toMB(heapUsed)} heap, ${toMB(rss)} RSS`)
+  }
+  catch (error) {
+    const errorMessage = error instanceof Error ? error.message : 'Unknown error'
+
+    if (options.output === 'json') {
+      logger.output(JSON.stringify({ error: true, message: errorMessage }, null, 2))
+    }
+    else {
+      logger.output(chalk.red(`Stats failed: ${errorMessage}`))
+    }
+
+    process.exit(1)
+  }
+}
+
+interface PrebuildOptions extends StatsOptions {
+  strict?: boolean
+}
+
+async function handlePrebuild(options: PrebuildOptions): Promise<void> {
+  const logger = initializeLogger(options.debug ? 'debug' : 'info', options.quiet)
+
+  try {
+    if (!isIndexCacheEnabled()) {
+      throw new Error('prebuild writes the index cache, so it cannot run with --no-cache')
+    }
+
+    const startTime = performance.now()
+    const project = await getOrCreateProject(persistentManager, {
+      directory: options.directory || process.cwd(),
+      ignoreDirs: options.ignoreDirs || [],
+      useDefaultExcludes: options.defaultExcludes,
+      maxDepth: parseMaxDepth(options.maxDepth),
+      followSymlinks: options.followSymlinks,
+      allowOutsideRoot: options.allowOutsideRoot,
+      autoWatch: false,
+      useCache: true,
+    }, options.projectId, { onProgress: options.quiet ? undefined : createProgressBar(), requireCacheWrite: true })
+    const durationMs = Math.round(performance.now() - startTime)
+
+    const { totalFiles, totalSymbols } = getIndexStats(project)
+    const failedFiles = getParseFailures(project)
+    const filesWithErrors = getAllFiles(project)
+      .filter(file => file.parseErrors?.length)
+      .map(file => ({ path: file.path, errors: file.parseErrors!.length }))
+    const failed = failedFiles.length > 0 || filesWithErrors.length > 0
+    const cacheFile = getCacheFilePath(project.config.directory)
+
+    if (options.output === 'json') {
+      logger.output(JSON.stringify({
+        projectId: project.id,
+        directory: project.config.directory,
+        cacheFile,
+        totalFiles,
+        totalSymbols,
+        durationMs,
+        failedFiles,
+        filesWithErrors,
+        skippedFiles: getSkippedFiles(project),
+      }, portablePathReplacer, 2))
+    }
+    else {
+      logger.output(chalk.cyan(`Prebuilt index for ${project.config.directory} (project: ${project.id})`))
+      logger.output(`  ${totalFiles} files, ${totalSymbols} symbols in ${(durationMs / 1000).toFixed(1)}s`)
+      logger.output(`  Cache: ${cacheFile}`)
+      for (const path of failedFiles) {
+        logger.output(chalk.red(`  Failed to parse ${toPortablePath(relative(project.config.directory, path))}`))
+      }
+      for (const { path, errors } of filesWithErrors) {
+        logger.output(chalk.yellow(`  ${toPortablePath(relative(project.config.directory, path))}: ${errors} syntax ${errors === 1 ? 'error' : 'errors'}`))
+      }
+    }
+
+    if (options.strict && failed) {
+      process.exit(1)
+    }
+  }
+  catch (error) {
+    const errorMessage = error instanceof Error ? error.message : 'Unknown error'
+
+    if (options.output === 'json') {
+      logger.output(JSON.stringify({ error: true, message: errorMessage }, null, 2))
+    }
+    else {
+      logger.output(chalk.red(`Prebuild failed: ${errorMessage}`))
+    }
+
+    process.exit(1)
+  }
+}
+
+interface OutlineOptions {
+  depth?: string
+  stdin?: boolean
+  language?: string
+  output: string
+  color?: string
+  debug?: boolean
+  quiet?: boolean
+}
+
+async function handleOutline(file: string | undefined, options: OutlineOptions): Promise<void> {
+  const logger = initializeLogger(options.debug ? 'debug' : 'info', options.quiet)
+
+  try {
+    const colorMode = (options.color || 'auto') as ColorMode
+    if (!COLOR_MODES.includes(colorMode)) {
+      throw new Error(`Invalid color value: ${options.color}. Must be one of: ${COLOR_MODES.join(', ')}.`)
+    }
+
+    let depth: number | undefined
+    if (options.depth !== undefined) {
+      depth = parseInt(options.depth)
+      if (isNaN(depth) || depth < 1) {
+        throw new Error(`Invalid depth value: ${options.depth}. Must be a positive number.`)
+      }
+    }
+
+    if (!options.stdin && file === undefined) {
+      throw new Error('Pass a file to outline, or --stdin with --language')
+    }
+
+    // Same extraction as the list_symbols tool, without indexing the rest of the project
+    const fileNode = options.stdin ? readStdinBuffer(options.language) : await parseFile(resolve(file!))
+    const filePath = options.stdin ? fileNode.path : resolve(file!)
+    if (!options.stdin && !getFileLanguage(filePath, fileNode.content)) {
+      throw new Error(`Unsupported file type: ${file}`)
+    }
+    if (fileNode.skipped) {
+      throw new Error(fileNode.skipReason || `Skipped ${file ?? filePath}`)
+    }
+    const symbols = listSymbols(fileNode, { depth })
+
+    if (options.output === 'json') {
+      logger.output(JSON.stringify({
+        path: filePath,
+        symbols,
+        totalSymbols: countSymbols(symbols),
+        ...fileNode.parseErrors ? { parseErrors: fileNode.parseErrors } : {},
+      }, portablePathReplacer, 2))
+      return
+    }
+
+    const useColor = shouldUseColor(colorMode)
+    if (!useColor) {
+      chalk.level = 0
+    }
+    else if (chalk.level === 0) {
+      chalk.level = 1
+    }
+    logger.output(chalk.cyan(toPortablePath(relative(process.cwd(), filePath))))
+    if (symbols.length === 0) {
+      logger.output(chalk.yellow('No symbols found'))
+    }
+    for (const line of formatOutlineLines(symbols, { color: useColor })) {
+      logger.output(`  ${line}`)
+    }
+    if (fileNode.parseErrors) {
+      logger.output(chalk.yellow(`\n${fileNode.parseErrors.length} syntax error(s); symbols inside them are missing (first at line ${fileNode.parseErrors[0]!.startLine})`))
+    }
+  }
+  catch (error) {
+    const errorMessage = error instanceof Error ? error.message : 'Unknown error'
+
+    if (options.output === 'json') {
+      logger.output(JSON.stringify({ error: true, message: errorMessage }, null, 2))
+    }
+    else {
+      logger.output(chalk.red(`Outline failed: ${errorMessage}`))
+    }
+
+    process.exit(1)
+  }
+}
+
+/**
+ * Reads all of stdin and parses it as one buffer in the named language
+ */
+function parseMaxDepth(value: string | undefined): number | undefined {
+  if (value === undefined) return undefined
+  const parsed = parseInt(value)
+  if (isNaN(parsed) || parsed < 0) {
+    throw new Error(`Invalid max-depth value: ${value}. Must be a non-negative number.`)
+  }
+  return parsed
+}
+
+function readStdinBuffer(language: string | undefined): TreeNode {
+  if (!language) {
+    throw new Error('--stdin requires --language')
+  }
+  const config = resolveLanguageNames([language])[0]!
+  return parseBuffer(decodeSource(readFileSync(0)).content, config)
+}
+
 interface SetupOptions {
   auto?: boolean
 }
@@ -720,13 +1233,33 @@ async function runAutoSetup(logger: any): Promise<void> {
 
 interface DefaultOptions {
   mcp?: boolean
+  jsonSchema?: boolean | string
+  watch?: boolean
 }
 
 function handleDefaultAction(options: DefaultOptions): void {
-  if (options.mcp || !process.stdin.isTTY) {
-    startMCPServer()
+  if (options.jsonSchema) {
+    handleJsonSchema(options.jsonSchema)
+  }
+  else if (options.mcp || !process.stdin.isTTY) {
+    startMCPServer({ watch: options.watch })
   }
   else {
     console.info('Use --help to see available commands')
   }
 }
+
+function handleJsonSchema(tool: boolean | string): void {
+  const logger = getLogger()
+  const tools = getToolJsonSchemas()
+  const selected = typeof tool === 'string' ? tools.filter(schema => schema.name === tool) : tools
+  if (selected.length === 0) {
+    logger.output(chalk.red(`Unknown tool: ${tool}. Must be one of: ${tools.map(schema => schema.name).join(', ')}.`))
+    process.exit(1)
+  }
+
+  logger.output(JSON.stringify({
+    $schema: 'https://json-schema.org/draft/2020-12/schema',
+    tools: selected,
+  }, null, 2))
+}
@@ -13,8 +13,12 @@ export const LOGIC_EXTENSIONS = {
   CPP: ['.cpp', '.cxx', '.cc', '.hpp', '.hxx'],
   RUBY: ['.rb'],
   CSHARP: ['.cs'],
-  PHP: ['.php'],
+  PHP: ['.php', '.phtml'],
   KOTLIN: ['.kt', '.kts'],
+  SWIFT: ['.swift'],
+  SCALA: ['.scala', '.sc'],
+  ELIXIR: ['.ex', '.exs'],
+  BASH: ['.sh', '.bash'],
 } as const
 
 export const FRAMEWORK_EXTENSIONS = {
@@ -59,12 +63,25 @@ export function isLogicFile(filePath: string): boolean {
   return ALL_LOGIC_EXTENSIONS.some(ext => filePath.endsWith(ext))
 }
 
+// Config and data files, which are indexed for keyPath searches but never imported
+export const DATA_EXTENSIONS = [...MARKUP_EXTENSIONS.JSON, ...MARKUP_EXTENSIONS.YAML]
+
+export function isDataFile(filePath: string): boolean {
+  return DATA_EXTENSIONS.some(ext => filePath.endsWith(ext))
+}
+
 export const TEST_PATTERNS = {
   FILE_PATTERNS: ['.test.', '.spec.'],
   DIRECTORY_PATTERNS: ['/test/', '/tests/', '__tests__', '/fixtures/'],
   NEXT_JS_SPECIAL: ['_app.', '_document.'],
 } as const
 
+// Build output and code generator output, which is rarely what a search is looking for
+export const GENERATED_PATTERNS = {
+  FILE_PATTERNS: ['.generated.', '.gen.', '.min.', '.pb.', '_pb2.'],
+  DIRECTORY_PATTERNS: ['/dist/', '/build/', '/out/', '/generated/', '/__generated__/', '/vendor/'],
+} as const
+
 export const ENCODING = {
   UTF8: 'utf-8',
 } as const
@@ -74,6 +91,11 @@ export function isTestFile(filePath: string): boolean {
     || TEST_PATTERNS.DIRECTORY_PATTERNS.some(pattern => filePath.includes(pattern))
 }
 
+export function isGeneratedFile(filePath: string): boolean {
+  return GENERATED_PATTERNS.FILE_PATTERNS.some(pattern => filePath.includes(pattern))
+    || GENERATED_PATTERNS.DIRECTORY_PATTERNS.some(pattern => filePath.includes(pattern))
+}
+
 export function getFileCategory(filePath: string): 'logic' | 'framework' | 'markup' | 'unknown' {
   if (isLogicFile(filePath)) return 'logic'
   if (isFrameworkFile(filePath)) return 'framework'
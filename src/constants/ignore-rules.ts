@@ -2,6 +2,10 @@
  * Global directory and file ignore rules - applied universally regardless of language
  */
 
+/**
+ * The default exclude set, skipped at any depth unless a walk is run with `useDefaultExcludes: false`
+ * (`--no-default-excludes`); listed in docs/cli.md, so keep the two in step
+ */
 export const GLOBAL_IGNORE_DIRS = new Set([
   // Version control
   '.git', '.svn', '.hg',
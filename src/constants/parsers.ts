@@ -17,6 +17,12 @@ export const PARSER_NAMES = {
   PHP: 'php',
   HTML: 'html',
   KOTLIN: 'kotlin',
+  SWIFT: 'swift',
+  SCALA: 'scala',
+  ELIXIR: 'elixir',
+  BASH: 'bash',
+  JSON: 'json',
+  YAML: 'yaml',
 } as const
 
 export const FUNCTION_TYPES = {
@@ -28,11 +34,18 @@ export const FUNCTION_TYPES = {
   JAVA: ['method_declaration'],
   C: ['function_definition', 'function_declarator'],
   CPP: ['function_definition', 'function_declarator'],
-  RUBY: ['method'],
-  CSHARP: ['method_declaration'],
+  RUBY: ['method', 'singleton_method'],
+  CSHARP: ['method_declaration', 'constructor_declaration'],
   PHP: ['function_definition', 'method_declaration'],
   HTML: [],
   KOTLIN: ['function_declaration'],
+  SWIFT: ['function_declaration', 'init_declaration', 'protocol_function_declaration'],
+  SCALA: ['function_definition', 'function_declaration'],
+  // Elixir defines functions and modules with macro calls; see getElixirDefinitionKind
+  ELIXIR: [],
+  BASH: ['function_definition'],
+  JSON: [],
+  YAML: [],
 } as const
 
 export const CLASS_TYPES = {
@@ -45,12 +58,269 @@ export const CLASS_TYPES = {
   C: ['struct_specifier'],
   CPP: ['class_specifier', 'struct_specifier'],
   RUBY: ['class', 'module'],
-  CSHARP: ['class_declaration', 'interface_declaration'],
-  PHP: ['class_declaration'],
+  CSHARP: ['class_declaration', 'interface_declaration', 'struct_declaration', 'record_declaration'],
+  PHP: ['class_declaration', 'interface_declaration', 'trait_declaration', 'enum_declaration'],
   HTML: [],
   KOTLIN: ['class_declaration', 'object_declaration'],
+  SWIFT: ['class_declaration', 'protocol_declaration'],
+  SCALA: ['class_definition', 'object_definition', 'trait_definition', 'package_object', 'enum_definition'],
+  ELIXIR: [],
+  BASH: [],
+  JSON: [],
+  YAML: [],
+} as const
+
+/**
+ * Decision points per language for cyclomatic complexity; each node adds one path
+ */
+export const BRANCH_TYPES = {
+  JAVASCRIPT: ['if_statement', 'for_statement', 'for_in_statement', 'while_statement', 'do_statement', 'switch_case', 'catch_clause', 'ternary_expression'],
+  TYPESCRIPT: ['if_statement', 'for_statement', 'for_in_statement', 'while_statement', 'do_statement', 'switch_case', 'catch_clause', 'ternary_expression'],
+  PYTHON: ['if_statement', 'elif_clause', 'for_statement', 'while_statement', 'except_clause', 'case_clause', 'conditional_expression', 'boolean_operator', 'if_clause'],
+  GO: ['if_statement', 'for_statement', 'expression_case', 'type_case', 'communication_case'],
+  RUST: ['if_expression', 'match_arm', 'for_expression', 'while_expression', 'loop_expression', 'try_expression'],
+  JAVA: ['if_statement', 'for_statement', 'enhanced_for_statement', 'while_statement', 'do_statement', 'switch_label', 'catch_clause', 'ternary_expression'],
+  C: ['if_statement', 'for_statement', 'while_statement', 'do_statement', 'case_statement', 'conditional_expression'],
+  CPP: ['if_statement', 'for_statement', 'for_range_loop', 'while_statement', 'do_statement', 'case_statement', 'catch_clause', 'conditional_expression'],
+  RUBY: ['if', 'elsif', 'unless', 'while', 'until', 'for', 'when', 'rescue', 'conditional', 'if_modifier', 'unless_modifier', 'while_modifier', 'until_modifier'],
+  CSHARP: ['if_statement', 'for_statement', 'foreach_statement', 'while_statement', 'do_statement', 'switch_section', 'switch_expression_arm', 'catch_clause', 'conditional_expression'],
+  PHP: ['if_statement', 'else_if_clause', 'for_statement', 'foreach_statement', 'while_statement', 'do_statement', 'case_statement', 'catch_clause', 'conditional_expression'],
+  HTML: [],
+  KOTLIN: ['if_expression', 'for_statement', 'while_statement', 'do_while_statement', 'when_entry', 'catch_block', 'conjunction_expression', 'disjunction_expression'],
+  SWIFT: ['if_statement', 'guard_statement', 'for_statement', 'while_statement', 'repeat_while_statement', 'switch_entry', 'catch_block', 'ternary_expression', 'conjunction_expression', 'disjunction_expression'],
+  SCALA: ['if_expression', 'while_expression', 'do_while_expression', 'for_expression', 'case_clause', 'catch_clause'],
+  // Each arm of a case, cond, with or multi-clause fn is a stab clause
+  ELIXIR: ['stab_clause'],
+  BASH: ['if_statement', 'elif_clause', 'for_statement', 'c_style_for_statement', 'while_statement', 'case_item', 'list'],
+  JSON: [],
+  YAML: [],
+} as const
+
+/**
+ * Import statement node types per language, read by the import graph
+ */
+export const IMPORT_TYPES = {
+  JAVASCRIPT: ['import_statement', 'export_statement', 'call_expression'],
+  TYPESCRIPT: ['import_statement', 'export_statement', 'call_expression'],
+  PYTHON: ['import_statement', 'import_from_statement'],
+  GO: [],
+  RUST: ['use_declaration', 'mod_item'],
+  JAVA: [],
+  C: [],
+  CPP: [],
+  RUBY: [],
+  CSHARP: [],
+  PHP: [],
+  HTML: [],
+  KOTLIN: [],
+  SWIFT: [],
+  SCALA: [],
+  ELIXIR: [],
+  BASH: [],
+  JSON: [],
+  YAML: [],
+} as const
+
+/**
+ * Declaration node types per language, mapped to the kind reported by definition lookups
+ */
+export const DEFINITION_KINDS = {
+  JAVASCRIPT: {
+    function_declaration: 'function',
+    generator_function_declaration: 'function',
+    class_declaration: 'class',
+    method_definition: 'method',
+    variable_declarator: 'variable',
+  },
+  TYPESCRIPT: {
+    function_declaration: 'function',
+    generator_function_declaration: 'function',
+    class_declaration: 'class',
+    abstract_class_declaration: 'class',
+    method_definition: 'method',
+    interface_declaration: 'interface',
+    type_alias_declaration: 'type',
+    enum_declaration: 'enum',
+    variable_declarator: 'variable',
+  },
+  PYTHON: {
+    function_definition: 'function',
+    class_definition: 'class',
+  },
+  GO: {
+    function_declaration: 'function',
+    method_declaration: 'method',
+    type_spec: 'type',
+    const_spec: 'const',
+    var_spec: 'variable',
+  },
+  RUST: {
+    function_item: 'function',
+    function_signature_item: 'function',
+    struct_item: 'struct',
+    enum_item: 'enum',
+    union_item: 'union',
+    trait_item: 'trait',
+    type_item: 'type',
+    const_item: 'const',
+    static_item: 'static',
+    mod_item: 'module',
+    macro_definition: 'macro',
+  },
+  JAVA: {
+    class_declaration: 'class',
+    interface_declaration: 'interface',
+    enum_declaration: 'enum',
+    record_declaration: 'record',
+    method_declaration: 'method',
+    constructor_declaration: 'constructor',
+  },
+  C: {
+    function_definition: 'function',
+    struct_specifier: 'struct',
+    union_specifier: 'union',
+    enum_specifier: 'enum',
+    type_definition: 'type',
+  },
+  CPP: {
+    function_definition: 'function',
+    class_specifier: 'class',
+    struct_specifier: 'struct',
+    union_specifier: 'union',
+    enum_specifier: 'enum',
+    namespace_definition: 'namespace',
+    type_definition: 'type',
+    alias_declaration: 'type',
+  },
+  RUBY: {
+    method: 'method',
+    singleton_method: 'method',
+    class: 'class',
+    module: 'module',
+  },
+  CSHARP: {
+    class_declaration: 'class',
+    interface_declaration: 'interface',
+    struct_declaration: 'struct',
+    enum_declaration: 'enum',
+    record_declaration: 'record',
+    method_declaration: 'method',
+    constructor_declaration: 'constructor',
+    property_declaration: 'property',
+    namespace_declaration: 'namespace',
+    file_scoped_namespace_declaration: 'namespace',
+  },
+  PHP: {
+    function_definition: 'function',
+    method_declaration: 'method',
+    class_declaration: 'class',
+    interface_declaration: 'interface',
+    trait_declaration: 'trait',
+    enum_declaration: 'enum',
+  },
+  HTML: {},
+  KOTLIN: {
+    function_declaration: 'function',
+    class_declaration: 'class',
+    object_declaration: 'object',
+    property_declaration: 'property',
+  },
+  // Classes, structs, enums, actors and extensions share `class_declaration`; see getDeclaredKind
+  SWIFT: {
+    function_declaration: 'function',
+    init_declaration: 'constructor',
+    class_declaration: 'class',
+    protocol_declaration: 'protocol',
+    protocol_function_declaration: 'method',
+    property_declaration: 'property',
+    protocol_property_declaration: 'property',
+  },
+  // `val` and `var` are outlined as properties, as in Kotlin; package objects are objects
+  SCALA: {
+    function_definition: 'function',
+    function_declaration: 'function',
+    class_definition: 'class',
+    object_definition: 'object',
+    package_object: 'object',
+    trait_definition: 'trait',
+    enum_definition: 'enum',
+    type_definition: 'type',
+    val_definition: 'property',
+    val_declaration: 'property',
+    var_definition: 'property',
+    var_declaration: 'property',
+  },
+  // `def`, `defmacro` and `defmodule` are all calls; see getElixirDefinitionKind
+  ELIXIR: {
+    call: 'function',
+  },
+  BASH: {
+    function_definition: 'function',
+    variable_assignment: 'variable',
+  },
+  JSON: {},
+  YAML: {},
+} as const
+
+/**
+ * Keywords per language that mark a declaration's modifiers, read by kind filters such as `async_fn`. Words
+ * before the declaration's name count, as do Python decorators and a JavaScript `export`.
+ */
+export const MODIFIER_KEYWORDS = {
+  JAVASCRIPT: { async: 'async', static: 'static', export: 'pub' },
+  TYPESCRIPT: { async: 'async', static: 'static', export: 'pub', public: 'pub' },
+  PYTHON: { async: 'async', staticmethod: 'static' },
+  GO: {},
+  RUST: { async: 'async', pub: 'pub' },
+  JAVA: { static: 'static', public: 'pub' },
+  C: { static: 'static' },
+  CPP: { static: 'static' },
+  RUBY: {},
+  CSHARP: { async: 'async', static: 'static', public: 'pub' },
+  PHP: { static: 'static', public: 'pub' },
+  HTML: {},
+  KOTLIN: { suspend: 'async', public: 'pub' },
+  SWIFT: { async: 'async', static: 'static', public: 'pub' },
+  SCALA: {},
+  ELIXIR: { def: 'pub', defmacro: 'pub' },
+  BASH: {},
+  JSON: {},
+  YAML: {},
 } as const
 
 export const PARSER_LIMITS = {
   KOTLIN_MAX_FILE_SIZE: 32767,
+  // Below this many files, worker startup costs more than parsing on one thread
+  PARALLEL_MIN_FILES: 200,
+  // Longer lines are cut here in the index, so minified files stay cheap to search
+  MAX_LINE_LENGTH: 1000,
+} as const
+
+export const SEARCH_LIMITS = {
+  // Largest page search_code returns; bigger limits are clamped to keep responses within MCP size limits
+  MAX_PAGE_SIZE: 500,
+  // Default cap on the UTF-8 size of each result's nodeText
+  DEFAULT_NODE_BYTES: 4096,
+  // Shortest name or fuzzy query accepted unless the call lowers it; shorter ones match almost everything
+  MIN_QUERY_LENGTH: 2,
+} as const
+
+export const STREAM_LIMITS = {
+  // Serialized size each partial-result notification aims to stay under
+  CHUNK_BYTES: 64 * 1024,
+} as const
+
+/**
+ * Points added to a result's match score (0-100) by relevance sorting. Proximity applies only when the
+ * caller names its current file and never goes below 0; of the path penalties only the larger applies.
+ */
+export const RELEVANCE_WEIGHTS = {
+  // A declaration of the symbol, rather than a use of it
+  DEFINITION: 20,
+  SAME_FILE: 15,
+  SAME_DIRECTORY: 10,
+  // Taken off SAME_DIRECTORY for each directory between the result's file and the current file
+  PER_DIRECTORY_HOP: 2,
+  TEST_PATH: -15,
+  GENERATED_PATH: -25,
 } as const
\ No newline at end of file
@@ -5,10 +5,18 @@
 export const PERSISTENCE_CONFIG = {
   MAX_PROJECTS: 10,
   PROJECT_ID_HASH_LENGTH: 8,
-  DEFAULT_WATCH_DEBOUNCE_MS: 300,
+  DEFAULT_WATCH_DEBOUNCE_MS: 200,
   LRU_CLEANUP_THRESHOLD: 0.8,
 } as const
 
+export const INDEX_CACHE_CONFIG = {
+  // Bump when the serialized node shape or extraction rules change
+  VERSION: 3,
+  DIR_NAME: 'tree-sitter-mcp',
+  DIR_ENV: 'TREE_SITTER_MCP_CACHE_DIR',
+  HASH_ALGORITHM: 'sha1',
+} as const
+
 export const PROJECT_ID_PATTERNS = {
   INVALID_CHARS: /[^a-zA-Z0-9\-_]/g,
   COLLISION_SEPARATOR: '-',
@@ -26,13 +34,15 @@ export const WATCH_CONFIG = {
     '**/coverage/**',
     '**/.nyc_output/**',
   ],
-  DEBOUNCE_MS: 300,
+  DEBOUNCE_MS: 200,
   PERSISTENT: true,
   IGNORE_INITIAL: true,
 } as const
 
 export const MEMORY_LIMITS = {
-  MAX_FILE_SIZE_BYTES: 10 * 1024 * 1024, // 10MB
+  MAX_FILE_SIZE_BYTES: 5 * 1024 * 1024, // 5MB; larger files are skipped by the directory walk
+  // Parse trees kept in memory; older ones are re-parsed from file content when needed
+  MAX_RESIDENT_TREES: 5000,
   MAX_NODES_PER_FILE: 50000,
   ESTIMATED_BYTES_PER_NODE: 100,
   ESTIMATED_BYTES_PER_FILE: 1000,
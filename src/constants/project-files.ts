@@ -31,6 +31,18 @@ export const MONOREPO_INDICATORS = [
   PROJECT_FILES.PACKAGE_MANAGERS.CARGO,
 ] as const
 
+/**
+ * Dependency and build output directories of each root type's toolchain, skipped under roots of that type
+ * on top of the global ignore list
+ */
+export const ROOT_TYPE_IGNORE_DIRS: Record<string, readonly string[]> = {
+  npm: ['node_modules', '.turbo', '.svelte-kit', '.parcel-cache', 'storybook-static'],
+  rust: ['target'],
+  go: ['vendor', 'testdata'],
+  python: ['.venv', 'venv', '__pycache__', '.eggs', 'site-packages'],
+  java: ['target', '.gradle'],
+}
+
 export function isWorkspaceFile(fileName: string): boolean {
   return WORKSPACE_FILES.includes(fileName as any)
 }
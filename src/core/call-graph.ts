@@ -0,0 +1,291 @@
+/**
+ * Call graph - links functions to the functions they call, resolved against the project's definitions
+ */
+
+import type Parser from 'tree-sitter'
+import { getFileLanguage } from './languages.js'
+import { IDENTIFIER_TYPES, getDefinitionNameNode, getQualifiedName } from './definitions.js'
+import { visitReferences } from './references.js'
+import { getCallableKind } from './symbols.js'
+import { toSourceByteOffset } from '../utils/encoding.js'
+import type { TreeNode, LanguageConfig, CallGraph, CallGraphEdge, CallGraphNode, CallerResult, EnclosingScope, ExternalCall } from '../types/core.js'
+
+const CALL_TYPES = new Set([
+  'call_expression',
+  'call',
+  'method_invocation',
+  'invocation_expression',
+  'function_call_expression',
+  'member_call_expression',
+  'scoped_call_expression',
+])
+
+// Fields holding the called name on member, attribute, and scoped callees
+const CALLEE_NAME_FIELDS = ['property', 'field', 'attribute', 'name', 'method', 'function']
+
+// Calls between these languages resolve against each other's definitions
+const JS_FAMILY = new Set(['javascript', 'typescript', 'tsx'])
+
+export interface CallGraphOptions {
+  depth?: number
+}
+
+export interface FindCallersOptions {
+  pathPattern?: string
+}
+
+interface CallSite {
+  name: string
+  line: number
+}
+
+interface Callable {
+  node: CallGraphNode
+  bareName: string
+  language: LanguageConfig
+  calls: CallSite[]
+}
+
+/**
+ * Builds caller and callee edges around a function, walking `depth` levels in each direction.
+ * Calls that match no known definition are reported in `external` instead of being dropped.
+ */
+export function buildCallGraph(symbol: string, files: TreeNode[], options: CallGraphOptions = {}): CallGraph {
+  const depth = Math.max(1, options.depth ?? 1)
+  const callables = files.flatMap(collectCallables)
+
+  const byName = new Map<string, Callable[]>()
+  for (const callable of callables) {
+    const named = byName.get(callable.bareName) || []
+    named.push(callable)
+    byName.set(callable.bareName, named)
+  }
+
+  // Prefer definitions in the calling file, then anywhere in the project in a compatible language
+  const resolveCall = (call: CallSite, caller: Callable): Callable[] => {
+    const named = byName.get(call.name) || []
+    const sameFile = named.filter(callable => callable.node.path === caller.node.path)
+    if (sameFile.length > 0) return sameFile
+    return named.filter(callable => isCompatibleLanguage(callable.language, caller.language))
+  }
+
+  const targets = callables.filter(callable => callable.node.name === symbol || callable.bareName === symbol)
+  const callees: CallGraphEdge[] = []
+  const external: ExternalCall[] = []
+
+  walkLevels(targets, depth, (caller, level) => {
+    const next: Callable[] = []
+    for (const call of caller.calls) {
+      const resolved = resolveCall(call, caller)
+      if (resolved.length === 0) {
+        external.push({ caller: caller.node, name: call.name, line: call.line, depth: level })
+        continue
+      }
+      for (const callee of resolved) {
+        callees.push({ caller: caller.node, callee: callee.node, line: call.line, depth: level })
+        next.push(callee)
+      }
+    }
+    return next
+  })
+
+  const incoming = new Map<Callable, { caller: Callable, line: number }[]>()
+  for (const caller of callables) {
+    for (const call of caller.calls) {
+      for (const callee of resolveCall(call, caller)) {
+        const edges = incoming.get(callee) || []
+        edges.push({ caller, line: call.line })
+        incoming.set(callee, edges)
+      }
+    }
+  }
+
+  const callers: CallGraphEdge[] = []
+  walkLevels(targets, depth, (callee, level) => {
+    const edges = incoming.get(callee) || []
+    for (const edge of edges) {
+      callers.push({ caller: edge.caller.node, callee: callee.node, line: edge.line, depth: level })
+    }
+    return edges.map(edge => edge.caller)
+  })
+
+  return {
+    symbol,
+    functions: targets.map(target => target.node),
+    callees,
+    callers,
+    external,
+  }
+}
+
+/**
+ * Narrows the references to a function to its call sites: the reference must be the called name of a
+ * call, so `store.save()` counts for `save` while `save` passed as an argument or read as `save.length`
+ * does not. A qualified symbol (`Store.save`) matches calls by its last segment.
+ */
+export function findCallers(symbol: string, files: TreeNode[], options: FindCallersOptions = {}): CallerResult[] {
+  const { pathPattern } = options
+  const name = symbol.split(/\.|::/).pop() || symbol
+  const results: CallerResult[] = []
+
+  for (const fileNode of files) {
+    if (!fileNode.rawNode || !fileNode.content) continue
+    if (pathPattern && !fileNode.path.includes(pathPattern)) continue
+
+    const language = getFileLanguage(fileNode.path, fileNode.content)
+    if (!language) continue
+
+    const content = fileNode.content
+    const lines = content.split('\n')
+    visitReferences(fileNode.rawNode, language, text => text === name, (node, isDefinition) => {
+      if (isDefinition || !isCalledName(node)) return
+      results.push({
+        path: fileNode.path,
+        startLine: node.startPosition.row + 1,
+        endLine: node.endPosition.row + 1,
+        startColumn: node.startPosition.column,
+        endColumn: node.endPosition.column,
+        startByte: toSourceByteOffset(content, node.startIndex, fileNode.encoding),
+        endByte: toSourceByteOffset(content, node.endIndex, fileNode.encoding),
+        context: (lines[node.startPosition.row] || '').trim(),
+        caller: findEnclosingCallable(node, language),
+      })
+    })
+  }
+
+  return results
+}
+
+// The nearest call above the identifier decides; an identifier in its arguments is not the called name
+function isCalledName(node: Parser.SyntaxNode): boolean {
+  let call = node.parent
+  while (call && !CALL_TYPES.has(call.type)) call = call.parent
+  if (!call) return false
+
+  const callee = getCallee(call)
+  const nameNode = callee ? getCalleeNameNode(callee) : null
+  return nameNode !== null && nameNode.startIndex === node.startIndex && nameNode.endIndex === node.endIndex
+}
+
+function findEnclosingCallable(node: Parser.SyntaxNode, language: LanguageConfig): EnclosingScope | undefined {
+  for (let current = node.parent; current; current = current.parent) {
+    const kind = getCallableKind(current, language)
+    const nameNode = kind ? getDefinitionNameNode(current, language) : null
+    if (kind && nameNode) {
+      return {
+        name: getQualifiedName(current, nameNode),
+        kind,
+        startLine: current.startPosition.row + 1,
+        endLine: current.endPosition.row + 1,
+      }
+    }
+  }
+  return undefined
+}
+
+/**
+ * Breadth-first walk that visits each function once; `expand` returns the neighbours for the next level
+ */
+function walkLevels(start: Callable[], depth: number, expand: (callable: Callable, level: number) => Callable[]): void {
+  const visited = new Set(start)
+  let frontier = start
+
+  for (let level = 1; level <= depth && frontier.length > 0; level++) {
+    const next: Callable[] = []
+    for (const callable of frontier) {
+      for (const neighbour of expand(callable, level)) {
+        if (!visited.has(neighbour)) {
+          visited.add(neighbour)
+          next.push(neighbour)
+        }
+      }
+    }
+    frontier = next
+  }
+}
+
+function collectCallables(fileNode: TreeNode): Callable[] {
+  if (!fileNode.rawNode) return []
+
+  const language = getFileLanguage(fileNode.path, fileNode.content)
+  if (!language) return []
+
+  const callables: Callable[] = []
+
+  function visit(node: Parser.SyntaxNode) {
+    const kind = getCallableKind(node, language!)
+    const nameNode = kind ? getDefinitionNameNode(node, language!) : null
+    if (kind && nameNode) {
+      callables.push({
+        node: {
+          name: getQualifiedName(node, nameNode),
+          kind,
+          path: fileNode.path,
+          startLine: node.startPosition.row + 1,
+          endLine: node.endPosition.row + 1,
+        },
+        bareName: nameNode.text,
+        language: language!,
+        calls: collectCallSites(node, language!),
+      })
+    }
+
+    for (const child of node.namedChildren) {
+      visit(child)
+    }
+  }
+
+  visit(fileNode.rawNode)
+  return callables
+}
+
+/**
+ * Collects calls made directly by a function; calls inside nested named functions belong to those functions
+ */
+function collectCallSites(root: Parser.SyntaxNode, language: LanguageConfig): CallSite[] {
+  const calls: CallSite[] = []
+
+  function visit(node: Parser.SyntaxNode) {
+    if (node !== root && getCallableKind(node, language)) return
+
+    if (CALL_TYPES.has(node.type)) {
+      const callee = getCallee(node)
+      const name = callee ? getCalleeNameNode(callee)?.text : undefined
+      if (name) {
+        calls.push({ name, line: node.startPosition.row + 1 })
+      }
+    }
+
+    for (const child of node.namedChildren) {
+      visit(child)
+    }
+  }
+
+  visit(root)
+  return calls
+}
+
+function getCallee(call: Parser.SyntaxNode): Parser.SyntaxNode | null {
+  return call.childForFieldName('function') || call.childForFieldName('name')
+    || call.childForFieldName('method') || call.namedChild(0)
+}
+
+/**
+ * Reduces a callee expression to the called name (`this.store.save` gives `save`, `Foo::bar` gives `bar`)
+ */
+function getCalleeNameNode(node: Parser.SyntaxNode): Parser.SyntaxNode | null {
+  if (IDENTIFIER_TYPES.has(node.type)) return node
+
+  for (const field of CALLEE_NAME_FIELDS) {
+    const child = node.childForFieldName(field)
+    if (child) return getCalleeNameNode(child)
+  }
+
+  const candidates = node.namedChildren.filter(child => !child.type.includes('argument'))
+  const last = candidates[candidates.length - 1]
+  return last ? getCalleeNameNode(last) : null
+}
+
+function isCompatibleLanguage(a: LanguageConfig, b: LanguageConfig): boolean {
+  return a.name === b.name || (JS_FAMILY.has(a.name) && JS_FAMILY.has(b.name))
+}
\ No newline at end of file
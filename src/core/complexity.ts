@@ -0,0 +1,99 @@
+/**
+ * Cyclomatic complexity - counts decision points in each function's syntax tree
+ */
+
+import type Parser from 'tree-sitter'
+import { getFileLanguage } from './languages.js'
+import { getDefinitionNameNode, getQualifiedName } from './definitions.js'
+import { getCallableKind } from './symbols.js'
+import type { TreeNode, LanguageConfig, FunctionComplexity } from '../types/core.js'
+
+// Binary expressions only branch when short-circuiting
+const LOGICAL_EXPRESSION_TYPES = new Set(['binary_expression', 'binary'])
+const LOGICAL_OPERATORS = new Set(['&&', '||', 'and', 'or'])
+
+export interface ComplexityOptions {
+  threshold?: number
+  pathPattern?: string
+}
+
+/**
+ * Reports the cyclomatic complexity of every function above `threshold`, most complex first
+ */
+export function analyzeComplexity(files: TreeNode[], options: ComplexityOptions = {}): FunctionComplexity[] {
+  const { threshold = 0, pathPattern } = options
+  const results: FunctionComplexity[] = []
+
+  for (const fileNode of files) {
+    if (!fileNode.rawNode) continue
+    if (pathPattern && !fileNode.path.includes(pathPattern)) continue
+
+    const language = getFileLanguage(fileNode.path, fileNode.content)
+    if (!language) continue
+
+    collectFunctions(fileNode.rawNode, language, (node, name) => {
+      const complexity = calculateNodeComplexity(node, language)
+      if (complexity > threshold) {
+        results.push({
+          file: fileNode.path,
+          function: name,
+          complexity,
+          startLine: node.startPosition.row + 1,
+        })
+      }
+    })
+  }
+
+  return results.sort((a, b) =>
+    b.complexity - a.complexity
+    || a.file.localeCompare(b.file)
+    || a.startLine - b.startLine,
+  )
+}
+
+/**
+ * One plus the branch nodes of a function body; nested named functions are measured on their own
+ */
+function calculateNodeComplexity(root: Parser.SyntaxNode, language: LanguageConfig): number {
+  let complexity = 1
+
+  function visit(node: Parser.SyntaxNode) {
+    if (node !== root && getCallableKind(node, language)) return
+
+    if (language.branchTypes.includes(node.type) || isLogicalExpression(node)) {
+      complexity++
+    }
+
+    for (const child of node.namedChildren) {
+      visit(child)
+    }
+  }
+
+  visit(root)
+  return complexity
+}
+
+function collectFunctions(
+  root: Parser.SyntaxNode,
+  language: LanguageConfig,
+  onFunction: (node: Parser.SyntaxNode, name: string) => void,
+): void {
+  function visit(node: Parser.SyntaxNode) {
+    const nameNode = getCallableKind(node, language) ? getDefinitionNameNode(node, language) : null
+    if (nameNode) {
+      onFunction(node, getQualifiedName(node, nameNode))
+    }
+
+    for (const child of node.namedChildren) {
+      visit(child)
+    }
+  }
+
+  visit(root)
+}
+
+function isLogicalExpression(node: Parser.SyntaxNode): boolean {
+  if (!LOGICAL_EXPRESSION_TYPES.has(node.type)) return false
+  const operator = node.childForFieldName('operator')
+  return operator !== null && LOGICAL_OPERATORS.has(operator.type)
+}
\ No newline at end of file
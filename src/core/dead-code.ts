@@ -0,0 +1,183 @@
+/**
+ * Dead code lookup - reports private functions and types that nothing else in the project references
+ */
+
+import type Parser from 'tree-sitter'
+import { getFileLanguage } from './languages.js'
+import { getDefinitionNameNode } from './definitions.js'
+import { visitReferences } from './references.js'
+import { PARSER_NAMES } from '../constants/index.js'
+import type { TreeNode, LanguageConfig, DeadSymbol } from '../types/core.js'
+
+export const DEAD_CODE_NOTE = 'References are matched by name within the project. Calls through reflection, '
+  + 'string-based dispatch, macros or code outside the project are not seen, so review each symbol before removing it.'
+
+const DEAD_CODE_KINDS = new Set(['function', 'method', 'class', 'struct', 'enum', 'trait', 'interface', 'type', 'union', 'record'])
+
+const FUNCTION_VALUE_TYPES = new Set(['arrow_function', 'function_expression', 'function'])
+
+// Called by the runtime or a test harness rather than by project code
+const ENTRY_POINT_NAMES = new Set(['main', 'init'])
+const TEST_NAME_PATTERN = /^test_/
+const RUST_KEEP_ATTRIBUTE = /^#\[(?:[\w:]+::)?(?:test|bench|no_mangle|export_name)\b|^#\[cfg\(test\)\]/
+
+// Names can be reached through strings here (getattr, obj[name], send), which the syntax tree does not show
+const DYNAMIC_LANGUAGES = new Set<string>([
+  PARSER_NAMES.JAVASCRIPT,
+  PARSER_NAMES.TYPESCRIPT,
+  PARSER_NAMES.TSX,
+  PARSER_NAMES.PYTHON,
+  PARSER_NAMES.RUBY,
+  PARSER_NAMES.PHP,
+])
+
+export interface FindDeadCodeOptions {
+  pathPattern?: string
+}
+
+interface Candidate {
+  result: DeadSymbol
+  node: Parser.SyntaxNode
+}
+
+/**
+ * Finds private functions and types with no references outside their own body. Public symbols
+ * (Rust `pub`, TypeScript `export`, capitalized Go names, ...) are skipped since other code may use them,
+ * as are entry points and tests. `pathPattern` limits where symbols are reported, not where references are counted.
+ */
+export function findDeadCode(files: TreeNode[], options: FindDeadCodeOptions = {}): DeadSymbol[] {
+  const { pathPattern } = options
+  const candidates: Candidate[] = []
+
+  for (const fileNode of files) {
+    if (!fileNode.rawNode || !fileNode.content) continue
+    if (pathPattern && !fileNode.path.includes(pathPattern)) continue
+
+    const language = getFileLanguage(fileNode.path, fileNode.content)
+    if (language) collectPrivateDefinitions(fileNode, language, candidates)
+  }
+
+  const names = new Set(candidates.map(candidate => candidate.result.name))
+  const references = new Map<string, { path: string, index: number }[]>()
+
+  for (const fileNode of names.size > 0 ? files : []) {
+    const language = fileNode.rawNode ? getFileLanguage(fileNode.path, fileNode.content) : undefined
+    if (!language) continue
+
+    visitReferences(fileNode.rawNode, language, name => names.has(name), (node, isDefinition) => {
+      if (isDefinition) return
+      const found = references.get(node.text) || []
+      found.push({ path: fileNode.path, index: node.startIndex })
+      references.set(node.text, found)
+    })
+  }
+
+  // Recursive calls and a type naming itself do not keep a symbol alive
+  return candidates
+    .filter(({ result, node }) => !(references.get(result.name) || []).some(reference =>
+      reference.path !== result.path || reference.index < node.startIndex || reference.index >= node.endIndex,
+    ))
+    .map(candidate => candidate.result)
+}
+
+function collectPrivateDefinitions(fileNode: TreeNode, language: LanguageConfig, candidates: Candidate[]): void {
+  const lines = fileNode.content!.split(/\r?\n/)
+  const confidence = DYNAMIC_LANGUAGES.has(language.name) ? 'medium' : 'high'
+
+  function visit(node: Parser.SyntaxNode) {
+    const nameNode = getDefinitionNameNode(node, language)
+    const kind = nameNode ? getKind(node, language) : undefined
+
+    if (nameNode && kind && isPrivate(node, nameNode.text, language) && !isEntryPoint(node, nameNode.text, kind, language)) {
+      candidates.push({
+        node,
+        result: {
+          name: nameNode.text,
+          kind,
+          path: fileNode.path,
+          startLine: node.startPosition.row + 1,
+          endLine: node.endPosition.row + 1,
+          startColumn: node.startPosition.column,
+          endColumn: node.endPosition.column,
+          context: (lines[node.startPosition.row] || '').trim(),
+          confidence,
+        },
+      })
+    }
+
+    for (const child of node.namedChildren) {
+      visit(child)
+    }
+  }
+
+  visit(fileNode.rawNode)
+}
+
+/**
+ * Function and type kinds only; variables holding an arrow function or function expression count as functions
+ */
+function getKind(node: Parser.SyntaxNode, language: LanguageConfig): string | undefined {
+  const kind = language.definitionKinds[node.type]
+  if (kind === 'variable') {
+    const value = node.childForFieldName('value')
+    return value && FUNCTION_VALUE_TYPES.has(value.type) ? 'function' : undefined
+  }
+  return kind && DEAD_CODE_KINDS.has(kind) ? kind : undefined
+}
+
+/**
+ * Applies each language's visibility rules; languages without a notion of private symbols report nothing
+ */
+function isPrivate(node: Parser.SyntaxNode, name: string, language: LanguageConfig): boolean {
+  switch (language.name) {
+    case PARSER_NAMES.JAVASCRIPT:
+    case PARSER_NAMES.TYPESCRIPT:
+    case PARSER_NAMES.TSX: {
+      if (node.type === 'method_definition') {
+        return node.namedChildren.some(child => child.type === 'accessibility_modifier' && child.text === 'private')
+      }
+      const declaration = node.type === 'variable_declarator' ? node.parent : node
+      return declaration?.parent?.type !== 'export_statement'
+    }
+    case PARSER_NAMES.PYTHON:
+      return name.startsWith('_') && !(name.startsWith('__') && name.endsWith('__'))
+    case PARSER_NAMES.GO:
+      return /^[a-z_]/.test(name)
+    case PARSER_NAMES.RUST:
+      return !node.namedChildren.some(child => child.type === 'visibility_modifier') && !isTraitMember(node)
+    case PARSER_NAMES.C:
+    case PARSER_NAMES.CPP:
+      return node.namedChildren.some(child => child.type === 'storage_class_specifier' && child.text === 'static')
+    case PARSER_NAMES.JAVA:
+    case PARSER_NAMES.CSHARP:
+    case PARSER_NAMES.KOTLIN:
+    case PARSER_NAMES.PHP:
+      return node.namedChildren.some(child => child.type.includes('modifier') && /\bprivate\b/.test(child.text))
+    default:
+      return false
+  }
+}
+
+/**
+ * Trait methods and trait impl methods are called through the trait
+ */
+function isTraitMember(node: Parser.SyntaxNode): boolean {
+  const owner = node.parent?.type === 'declaration_list' ? node.parent.parent : null
+  return owner?.type === 'trait_item' || (owner?.type === 'impl_item' && owner.childForFieldName('trait') !== null)
+}
+
+function isEntryPoint(node: Parser.SyntaxNode, name: string, kind: string, language: LanguageConfig): boolean {
+  if (kind === 'function' || kind === 'method') {
+    if (ENTRY_POINT_NAMES.has(name) || TEST_NAME_PATTERN.test(name)) return true
+  }
+  if (language.name !== PARSER_NAMES.RUST) return false
+
+  // `#[test]` on the item, or `#[cfg(test)]` on an enclosing module
+  for (let current: Parser.SyntaxNode | null = node; current; current = current.parent) {
+    if (current !== node && current.type !== 'mod_item') continue
+    for (let sibling = current.previousNamedSibling; sibling?.type === 'attribute_item'; sibling = sibling.previousNamedSibling) {
+      if (RUST_KEEP_ATTRIBUTE.test(sibling.text)) return true
+    }
+  }
+  return false
+}
\ No newline at end of file
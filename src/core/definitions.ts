@@ -0,0 +1,463 @@
+/**
+ * Definition lookup - resolves a symbol to its declaration sites using tree-sitter node kinds
+ */
+
+import type Parser from 'tree-sitter'
+import { resolve } from 'path'
+import { getFileLanguage } from './languages.js'
+import { toSourceByteOffset } from '../utils/encoding.js'
+import { PARSER_NAMES } from '../constants/index.js'
+import type { TreeNode, DefinitionResult, EnclosingScope, LanguageConfig } from '../types/core.js'
+
+export const IDENTIFIER_TYPES = new Set([
+  'identifier',
+  'shorthand_property_identifier',
+  'type_identifier',
+  'simple_identifier',
+  'field_identifier',
+  'property_identifier',
+  'constant',
+  'name',
+  'variable_name',
+  'operator_identifier',
+])
+
+const BODY_REQUIRED_TYPES = new Set([
+  'struct_specifier',
+  'union_specifier',
+  'enum_specifier',
+  'class_specifier',
+])
+
+const SCOPELESS_KINDS = new Set(['variable', 'const', 'static'])
+
+// Function values without a name of their own, shown as `{closure}` in enclosing paths
+const CLOSURE_TYPES = new Set([
+  'arrow_function',
+  'function',
+  'function_expression',
+  'generator_function',
+  'closure_expression',
+  'lambda',
+  'lambda_expression',
+  'lambda_literal',
+  'func_literal',
+  'anonymous_function',
+  'anonymous_function_creation_expression',
+  'anonymous_method_expression',
+])
+// Ruby blocks; other grammars use `block` for plain statement blocks
+const RUBY_CLOSURE_TYPES = new Set(['block', 'do_block'])
+const ANONYMOUS_SCOPE = '{closure}'
+
+// Swift's `class_declaration` says what it declares in a `declaration_kind` keyword
+const SWIFT_DECLARATION_KINDS: Record<string, string> = {
+  class: 'class',
+  actor: 'class',
+  struct: 'struct',
+  enum: 'enum',
+  extension: 'extension',
+}
+
+// Elixir declares with macro calls, told apart by the macro's name
+const ELIXIR_DEFINITION_KINDS = new Map([
+  ['def', 'function'],
+  ['defp', 'function'],
+  ['defmacro', 'macro'],
+  ['defmacrop', 'macro'],
+  ['defmodule', 'module'],
+])
+
+export interface FindDefinitionOptions {
+  directory?: string
+  file?: string
+  line?: number
+}
+
+interface DefinitionCandidate {
+  result: DefinitionResult
+  scopeStartLine: number
+  scopeEndLine: number
+}
+
+/**
+ * Finds declarations of a symbol across parsed file nodes.
+ * When a file and line are given, the candidate in the nearest enclosing scope wins.
+ */
+export function findDefinition(
+  symbol: string,
+  files: TreeNode[],
+  options: FindDefinitionOptions = {},
+): DefinitionResult[] {
+  const candidates: DefinitionCandidate[] = []
+
+  for (const fileNode of files) {
+    collectDefinitions(symbol, fileNode, candidates)
+  }
+
+  if (!options.file) {
+    return candidates.map(c => c.result)
+  }
+
+  const targetFile = resolve(options.directory || process.cwd(), options.file)
+  const inFile = candidates.filter(c => c.result.path === targetFile)
+
+  if (inFile.length === 0) {
+    return candidates.map(c => c.result)
+  }
+
+  if (options.line === undefined) {
+    return inFile.map(c => c.result)
+  }
+
+  const nearest = pickNearestScope(inFile, options.line)
+  return nearest ? [nearest.result] : inFile.map(c => c.result)
+}
+
+function collectDefinitions(symbol: string, fileNode: TreeNode, candidates: DefinitionCandidate[]): void {
+  if (!fileNode.rawNode || !fileNode.content) return
+
+  const language = getFileLanguage(fileNode.path, fileNode.content)
+  if (!language) return
+
+  const content = fileNode.content
+
+  function visit(node: Parser.SyntaxNode) {
+    const nameNode = getDefinitionNameNode(node, language!)
+    if (nameNode && (nameNode.text === symbol || getQualifiedName(node, nameNode) === symbol)) {
+      candidates.push(createCandidate(node, nameNode, language!.definitionKinds[node.type]!, fileNode))
+    }
+
+    for (const child of node.namedChildren) {
+      visit(child)
+    }
+  }
+
+  visit(fileNode.rawNode)
+}
+
+/**
+ * Returns the name node of a declaration, or null when the node does not declare anything
+ */
+export function getDefinitionNameNode(node: Parser.SyntaxNode, language: LanguageConfig): Parser.SyntaxNode | null {
+  if (!language.definitionKinds[node.type] || !isDefinitionSite(node)) return null
+  return getDeclarationName(node)
+}
+
+// C and C++ wrap declared names in pointer and reference declarators (`char *name(void)`)
+const WRAPPING_DECLARATORS = new Set(['pointer_declarator', 'reference_declarator'])
+const PROTOTYPE_HOLDERS = new Set(['declaration', 'field_declaration'])
+
+/**
+ * Returns the name node of a C or C++ function declared without a body: a header prototype
+ * (`int add(int a, int b);`) or a method declared in its class. Function pointer variables are not prototypes.
+ */
+export function getPrototypeNameNode(node: Parser.SyntaxNode, language: LanguageConfig): Parser.SyntaxNode | null {
+  if (language.name !== PARSER_NAMES.C && language.name !== PARSER_NAMES.CPP) return null
+  if (!PROTOTYPE_HOLDERS.has(node.type)) return null
+
+  let declarator = node.childForFieldName('declarator')
+  while (declarator && WRAPPING_DECLARATORS.has(declarator.type)) {
+    declarator = declarator.childForFieldName('declarator') || declarator.lastNamedChild
+  }
+  if (declarator?.type !== 'function_declarator') return null
+
+  const name = declarator.childForFieldName('declarator')
+  return name && name.type !== 'parenthesized_declarator' ? name : null
+}
+
+/**
+ * Returns whether a C or C++ `function_declarator` belongs to a prototype rather than heading a definition
+ */
+export function isPrototypeDeclarator(declarator: Parser.SyntaxNode, language: LanguageConfig): boolean {
+  let holder = declarator.parent
+  while (holder && WRAPPING_DECLARATORS.has(holder.type)) {
+    holder = holder.parent
+  }
+  const owner = holder ? getPrototypeNameNode(holder, language)?.parent : null
+  return !!owner && owner.startIndex === declarator.startIndex && owner.endIndex === declarator.endIndex
+}
+
+/**
+ * Returns the receiver type of a Go method (`(c *Calculator) Add` gives `Calculator`), a Kotlin
+ * extension (`fun String.shout()` gives `String`) or a Ruby class method (`def self.create` gives `self`),
+ * or null for other nodes
+ */
+export function getReceiverTypeName(node: Parser.SyntaxNode): string | null {
+  const receiver = node.childForFieldName('receiver')
+  if (!receiver) return getExtensionReceiverName(node) || getSingletonReceiverName(node)
+
+  const parameter = receiver.namedChildren.find(child => child.type === 'parameter_declaration')
+  let typeNode = parameter?.childForFieldName('type') || null
+
+  while (typeNode && typeNode.type !== 'type_identifier') {
+    if (typeNode.type === 'pointer_type' || typeNode.type === 'parenthesized_type') {
+      typeNode = typeNode.namedChild(0)
+    }
+    else if (typeNode.type === 'generic_type') {
+      typeNode = typeNode.childForFieldName('type')
+    }
+    else {
+      return null
+    }
+  }
+
+  return typeNode ? typeNode.text : null
+}
+
+/**
+ * Kotlin has no receiver field; the receiver is the type just before the `.` that precedes the name.
+ * Nullable and generic receivers keep only the type name (`List<Int>?` gives `List`).
+ */
+function getExtensionReceiverName(node: Parser.SyntaxNode): string | null {
+  if (node.type !== 'function_declaration' && node.type !== 'property_declaration') return null
+
+  const dot = node.children.findIndex(child => child.type === '.')
+  let typeNode = dot > 0 ? node.children[dot - 1] : undefined
+  while (typeNode && (typeNode.type === 'nullable_type' || typeNode.type === 'parenthesized_type')) {
+    typeNode = typeNode.namedChild(0) || undefined
+  }
+  if (typeNode?.type !== 'user_type') return null
+
+  return typeNode.namedChildren
+    .filter(child => child.type === 'type_identifier')
+    .map(child => child.text)
+    .join('.')
+}
+
+/**
+ * Ruby singleton methods keep their object (`self` or a constant), so class methods stay apart
+ * from instance methods of the same name; methods inside `class << self` count as singleton methods
+ */
+function getSingletonReceiverName(node: Parser.SyntaxNode): string | null {
+  if (node.type === 'singleton_method') return node.childForFieldName('object')?.text || null
+  if (node.type !== 'method') return null
+
+  const owner = node.parent?.type === 'body_statement' ? node.parent.parent : node.parent
+  return owner?.type === 'singleton_class' ? owner.childForFieldName('value')?.text || null : null
+}
+
+/**
+ * Returns the display name of a declaration, qualifying methods with their receiver type
+ * and appending C# generic parameters (`Map<TResult>`)
+ */
+export function getQualifiedName(node: Parser.SyntaxNode, nameNode: Parser.SyntaxNode): string {
+  const receiverType = getReceiverTypeName(node)
+  const name = receiverType ? `${receiverType}.${nameNode.text}` : nameNode.text
+  return name + getTypeParameterSuffix(node)
+}
+
+/**
+ * Returns the generic parameter list of a C# declaration (`<TKey, TValue>`), or an empty string.
+ * Only C# uses `type_parameter_list`; other grammars keep their names bare.
+ */
+export function getTypeParameterSuffix(node: Parser.SyntaxNode): string {
+  const parameters = node.namedChildren.find(child => child.type === 'type_parameter_list')
+  if (!parameters) return ''
+
+  const names = parameters.namedChildren
+    .filter(child => child.type === 'type_parameter')
+    .map(child => (child.childForFieldName('name') || child).text)
+  return `<${names.join(', ')}>`
+}
+
+/**
+ * Finds the nearest enclosing named declaration (function, class, etc.) around a syntax node
+ */
+export function findEnclosingScope(node: Parser.SyntaxNode, language: LanguageConfig): EnclosingScope | undefined {
+  const declaration = findEnclosingDeclaration(node, language)
+  if (!declaration) return undefined
+
+  return {
+    name: getQualifiedName(declaration.node, declaration.nameNode),
+    kind: declaration.kind,
+    startLine: declaration.node.startPosition.row + 1,
+    endLine: declaration.node.endPosition.row + 1,
+  }
+}
+
+/**
+ * The syntax node of the nearest enclosing named declaration, skipping variables, with its kind and name node
+ */
+export function findEnclosingDeclaration(
+  node: Parser.SyntaxNode,
+  language: LanguageConfig,
+): { node: Parser.SyntaxNode, nameNode: Parser.SyntaxNode, kind: string } | undefined {
+  let current: Parser.SyntaxNode | null = node
+  while (current) {
+    const kind = language.definitionKinds[current.type]
+    if (kind && !SCOPELESS_KINDS.has(kind)) {
+      const nameNode = getDefinitionNameNode(current, language)
+      if (nameNode) return { node: current, nameNode, kind }
+    }
+    current = current.parent
+  }
+  return undefined
+}
+
+/**
+ * Refines the kind a node type maps to when one node type declares several kinds of things
+ */
+export function getDeclaredKind(node: Parser.SyntaxNode, kind: string): string {
+  if (node.type === 'call') return getElixirDefinitionKind(node) ?? kind
+
+  const declared = node.type === 'class_declaration' ? node.childForFieldName('declaration_kind')?.text : undefined
+  return (declared && SWIFT_DECLARATION_KINDS[declared]) || kind
+}
+
+/**
+ * Returns what an Elixir `def`, `defp`, `defmacro` or `defmodule` call declares, or undefined for other calls
+ */
+export function getElixirDefinitionKind(node: Parser.SyntaxNode): string | undefined {
+  if (node.type !== 'call') return undefined
+  const target = node.childForFieldName('target')
+  return target?.type === 'identifier' ? ELIXIR_DEFINITION_KINDS.get(target.text) : undefined
+}
+
+/**
+ * Returns the name node of an Elixir definition: the module alias (`Billing.Invoice`), or the function name
+ * in its head, past any `when` guard
+ */
+export function getElixirDefinitionName(node: Parser.SyntaxNode): Parser.SyntaxNode | null {
+  if (!getElixirDefinitionKind(node)) return null
+
+  let head = node.namedChildren.find(child => child.type === 'arguments')?.firstNamedChild ?? null
+  if (head?.type === 'binary_operator' && head.childForFieldName('operator')?.text === 'when') {
+    head = head.childForFieldName('left')
+  }
+  if (head?.type === 'call') head = head.childForFieldName('target')
+  return head && (head.type === 'identifier' || head.type === 'alias') ? head : null
+}
+
+/**
+ * Joins the names of the declarations around a syntax node, outermost first, with the language's scope
+ * separator (`Calculator.add`, `billing::Invoice::total`). A declaration's own name ends the path when the
+ * node is inside it, so a method's name node gives the method's full path.
+ */
+export function getEnclosingPath(node: Parser.SyntaxNode, language: LanguageConfig): string | undefined {
+  const names: string[] = []
+  for (let current: Parser.SyntaxNode | null = node; current; current = current.parent) {
+    const name = getScopeName(current, language)
+    if (name) names.push(name)
+  }
+  return names.length > 0 ? names.reverse().join(language.scopeSeparator ?? '.') : undefined
+}
+
+function getScopeName(node: Parser.SyntaxNode, language: LanguageConfig): string | null {
+  // Keyword tokens such as `function` share their type with the expressions they start
+  if (!node.isNamed) return null
+  if (node.type === 'impl_item') return node.childForFieldName('type')?.text || null
+
+  if (CLOSURE_TYPES.has(node.type) || (language.name === PARSER_NAMES.RUBY && RUBY_CLOSURE_TYPES.has(node.type))) {
+    // `const add = () => ...` is named by its declarator
+    return node.parent?.type === 'variable_declarator' ? null : ANONYMOUS_SCOPE
+  }
+
+  const kind = language.definitionKinds[node.type]
+  if (!kind) return null
+  if (SCOPELESS_KINDS.has(kind) && !CLOSURE_TYPES.has(node.childForFieldName('value')?.type ?? '')) return null
+
+  const nameNode = getDefinitionNameNode(node, language)
+  return nameNode ? getQualifiedName(node, nameNode) : null
+}
+
+function isDefinitionSite(node: Parser.SyntaxNode): boolean {
+  if (BODY_REQUIRED_TYPES.has(node.type)) {
+    return node.childForFieldName('body') !== null
+  }
+  // Elixir calls other than definitions
+  if (node.type === 'call') return getElixirDefinitionKind(node) !== undefined
+  return true
+}
+
+function getDeclarationName(node: Parser.SyntaxNode): Parser.SyntaxNode | null {
+  if (node.type === 'call') return getElixirDefinitionName(node)
+
+  // Swift initializers are named by their keyword
+  if (node.type === 'init_declaration') return node.children.find(child => child.type === 'init') || null
+
+  // A Scala `val (a, b) = pair` binds several names, so only a plain identifier pattern names the declaration
+  if (node.type === 'val_definition' || node.type === 'var_definition') {
+    const pattern = node.childForFieldName('pattern')
+    return pattern?.type === 'identifier' ? pattern : null
+  }
+
+  const nameNode = node.childForFieldName('name')
+  // Dotted C# namespaces (`namespace Acme.Billing`) and Swift extended types (`extension Foo.Bar`) keep their full name
+  if (nameNode?.type === 'qualified_name' || nameNode?.type === 'user_type') return nameNode
+  if (nameNode) return unwrapName(nameNode)
+
+  const declarator = node.childForFieldName('declarator')
+  if (declarator) return unwrapName(declarator)
+
+  // Kotlin properties: later identifiers belong to the initializer, and destructuring declarations have no single name
+  if (node.type === 'property_declaration') {
+    const variable = node.namedChildren.find(child => child.type === 'variable_declaration')
+    return variable?.namedChildren.find(child => IDENTIFIER_TYPES.has(child.type)) || null
+  }
+
+  for (const child of node.namedChildren) {
+    if (IDENTIFIER_TYPES.has(child.type)) return child
+  }
+
+  return null
+}
+
+function unwrapName(node: Parser.SyntaxNode): Parser.SyntaxNode | null {
+  if (IDENTIFIER_TYPES.has(node.type)) return node
+
+  const inner = node.childForFieldName('name') || node.childForFieldName('declarator') || node.childForFieldName('bound_identifier')
+  if (inner) return unwrapName(inner)
+
+  return null
+}
+
+function resolveKind(node: Parser.SyntaxNode, kind: string): string {
+  if (node.type === 'variable_declarator' && node.parent?.child(0)?.type === 'const') {
+    return 'const'
+  }
+  return getDeclaredKind(node, kind)
+}
+
+function createCandidate(
+  node: Parser.SyntaxNode,
+  nameNode: Parser.SyntaxNode,
+  kind: string,
+  fileNode: TreeNode,
+): DefinitionCandidate {
+  const { path: filePath, encoding } = fileNode
+  const content = fileNode.content!
+  const scope = node.parent || node
+  const firstLine = content.substring(node.startIndex, node.endIndex).split('\n')[0] || ''
+
+  const qualifiedName = getQualifiedName(node, nameNode)
+
+  return {
+    result: {
+      name: nameNode.text,
+      ...(qualifiedName !== nameNode.text && { qualifiedName }),
+      kind: resolveKind(node, kind),
+      nodeType: node.type,
+      path: filePath,
+      startLine: node.startPosition.row + 1,
+      endLine: node.endPosition.row + 1,
+      startColumn: node.startPosition.column,
+      endColumn: node.endPosition.column,
+      startByte: toSourceByteOffset(content, node.startIndex, encoding),
+      endByte: toSourceByteOffset(content, node.endIndex, encoding),
+      context: firstLine.trim(),
+    },
+    scopeStartLine: scope.startPosition.row + 1,
+    scopeEndLine: scope.endPosition.row + 1,
+  }
+}
+
+function pickNearestScope(candidates: DefinitionCandidate[], line: number): DefinitionCandidate | undefined {
+  const visible = candidates.filter(c => c.scopeStartLine <= line && line <= c.scopeEndLine)
+
+  return visible.sort((a, b) => {
+    const spanDiff = (a.scopeEndLine - a.scopeStartLine) - (b.scopeEndLine - b.scopeStartLine)
+    if (spanDiff !== 0) return spanDiff
+    return Math.abs(a.result.startLine - line) - Math.abs(b.result.startLine - line)
+  })[0]
+}
\ No newline at end of file
@@ -0,0 +1,314 @@
+/**
+ * Query engine - the search pipeline the CLI commands and the MCP tools share, so a query returns the same
+ * results, totals and errors from either entry point
+ */
+
+import { extractAllNodes } from './parser.js'
+import { getChangedFiles } from './git.js'
+import { compileKindFilter } from './symbols.js'
+import {
+  auditSearchFiles,
+  createCandidateFilter,
+  createResultCounter,
+  createTestScopeFilter,
+  dedupResults,
+  filterByTestScope,
+  findUsage,
+  paginateResults,
+  searchWithDeadline,
+  sortResults,
+  SEARCH_DEDUP_MODES,
+  SEARCH_SORTS,
+  TEST_SCOPES,
+  FILE_MATCHERS,
+  SEARCH_MATCHERS,
+  SEARCH_MODES,
+  SCOPED_MATCHERS,
+  type CandidateFilterOptions,
+  type SearchMatcher,
+} from './search.js'
+import { getAllFiles, getAllNodes, getDepthSkipped, getGitIgnoredCount, getSkippedFiles } from '../project/manager.js'
+import { ignoresPathCase } from './path-case.js'
+import { SEARCH_LIMITS } from '../constants/index.js'
+import { createError } from '../utils/errors.js'
+import type {
+  FindUsageResult,
+  LanguageConfig,
+  LineRange,
+  Project,
+  SearchDedup,
+  SearchFileAudit,
+  SearchOptions,
+  SearchResult,
+  SearchSort,
+  SkippedFile,
+  TestScope,
+  TreeNode,
+} from '../types/core.js'
+
+/**
+ * What a query runs over: an indexed project, or a single buffer with no project
+ */
+export interface QuerySource {
+  root: string
+  files: TreeNode[]
+  /** File nodes plus their element nodes, as name and fuzzy searches read them */
+  nodes: TreeNode[]
+  skipped: SkippedFile[]
+  gitIgnored: number
+  depthSkipped: number
+  /** Whether include/exclude globs and changed-file paths match in any case */
+  ignoreCase?: boolean
+}
+
+export interface QuerySearchOptions extends Omit<SearchOptions, 'kind' | 'lineRange' | 'maxResults'> {
+  /** How the query is read: name (default), fuzzy, regex, structural, keyPath or tree-sitter */
+  matcher?: string
+  /** Declaration kind filter such as `async_fn`, name mode only */
+  kind?: string
+  languages?: LanguageConfig[]
+  include?: string[]
+  exclude?: string[]
+  /** Limits the search to files changed since this git ref */
+  changedSince?: string
+  includeUntracked?: boolean
+  /** Limits the search to one resolved file path, optionally to a span of its lines */
+  file?: string
+  lineRange?: LineRange
+  testScope?: string
+  sort?: string
+  /** Absolute, or relative to the root; results in and near it rank higher */
+  currentFile?: string
+  /** true and false stand for the default range mode and none */
+  dedup?: SearchDedup | boolean
+  offset?: number
+  /** Page size (default: 10, capped at MAX_PAGE_SIZE) */
+  limit?: number
+  /** Tallies matches per file instead of ranking and paging them */
+  countOnly?: boolean
+  /** Stops searching after this many milliseconds, returning the matches found so far */
+  timeoutMs?: number
+  signal?: AbortSignal
+}
+
+export interface QuerySearchResult {
+  /** The requested page, with content inclusion applied; empty when counting */
+  results: SearchResult[]
+  totalResults: number
+  /** Files with a match across every page */
+  totalFiles: number
+  offset: number
+  limit: number
+  hasMore: boolean
+  dedup: SearchDedup
+  totalBeforeDedup: number
+  timedOut?: boolean
+  audit: SearchFileAudit
+  /** Set instead of searching when the filters leave no indexed file, naming the filters */
+  message?: string
+  /** Matches per file in path order, when counting */
+  counts?: Map<string, number>
+}
+
+export interface QueryUsageOptions {
+  caseSensitive?: boolean
+  exactMatch?: boolean
+  pathPattern?: string
+  maxResults?: number
+}
+
+export interface QueryUsageResult {
+  /** Usages up to maxResults */
+  usages: FindUsageResult[]
+  /** Usages found before the maxResults cut */
+  totalUsages: number
+}
+
+export interface QueryEngine {
+  readonly source: QuerySource
+  search(query: string, options?: QuerySearchOptions): Promise<QuerySearchResult>
+  findUsage(identifier: string, options?: QueryUsageOptions): QueryUsageResult
+}
+
+/**
+ * The files and nodes of a project and its sub-projects, with what the walk left out
+ */
+export function projectQuerySource(project: Project): QuerySource {
+  return {
+    root: project.config.directory,
+    files: getAllFiles(project),
+    nodes: getAllNodes(project),
+    skipped: getSkippedFiles(project),
+    gitIgnored: getGitIgnoredCount(project),
+    depthSkipped: getDepthSkipped(project).length,
+    ignoreCase: ignoresPathCase(project.config),
+  }
+}
+
+/**
+ * A single parsed buffer, such as an unsaved editor buffer read from stdin
+ */
+export function bufferQuerySource(fileNode: TreeNode, root: string): QuerySource {
+  return { root, files: [fileNode], nodes: [fileNode, ...extractAllNodes(fileNode)], skipped: [], gitIgnored: 0, depthSkipped: 0 }
+}
+
+export function createQueryEngine(source: QuerySource): QueryEngine {
+  return {
+    source,
+    search: (query, options = {}) => searchSource(source, query, options),
+    findUsage: (identifier, options = {}) => findSourceUsage(source, identifier, options),
+  }
+}
+
+async function searchSource(source: QuerySource, query: string, options: QuerySearchOptions): Promise<QuerySearchResult> {
+  const {
+    matcher = 'name',
+    kind,
+    languages,
+    include = [],
+    exclude = [],
+    changedSince,
+    includeUntracked = true,
+    file,
+    lineRange,
+    testScope = 'include',
+    sort = 'relevance',
+    currentFile,
+    dedup = true,
+    offset = 0,
+    limit = 10,
+    countOnly = false,
+    timeoutMs,
+    signal,
+    ...searchOptions
+  } = options
+
+  if (!TEST_SCOPES.includes(testScope as TestScope)) {
+    throw createError('SEARCH_ERROR', `Invalid testScope: ${testScope}. Expected one of: ${TEST_SCOPES.join(', ')}`, { testScope })
+  }
+  if (!SEARCH_SORTS.includes(sort as SearchSort)) {
+    throw createError('SEARCH_ERROR', `Invalid sort: ${sort}. Expected one of: ${SEARCH_SORTS.join(', ')}`, { sort })
+  }
+  const dedupMode = dedup === true ? 'range' : dedup === false ? 'none' : dedup
+  if (!SEARCH_DEDUP_MODES.includes(dedupMode)) {
+    throw createError('SEARCH_ERROR', `Invalid dedup: ${dedup}. Expected true, false or one of: ${SEARCH_DEDUP_MODES.join(', ')}`, { dedup })
+  }
+  if (!SEARCH_MATCHERS.includes(matcher as SearchMatcher)) {
+    throw createError('SEARCH_ERROR', `Invalid mode: ${matcher}. Expected one of: ${SEARCH_MODES.join(', ')}`, { mode: matcher })
+  }
+  if ((searchOptions.wholeWord || searchOptions.subwordAware) && matcher !== 'name') {
+    throw createError('SEARCH_ERROR', `wholeWord and subwordAware apply only to name mode, not ${matcher}`, { mode: matcher })
+  }
+  if (kind !== undefined && matcher !== 'name') {
+    throw createError('SEARCH_ERROR', `kind applies only to name mode, not ${matcher}`, { mode: matcher })
+  }
+  if (searchOptions.scope !== undefined && !SCOPED_MATCHERS.has(matcher as SearchMatcher)) {
+    throw createError('SEARCH_ERROR', `scope applies only to regex, structural and tree-sitter searches, not ${matcher}`, { mode: matcher })
+  }
+  const kindFilter = kind !== undefined ? compileKindFilter(kind, languages) : undefined
+
+  const changedFiles = changedSince === undefined
+    ? undefined
+    : getChangedFiles(source.root, changedSince, { includeUntracked })
+  const candidateOptions: CandidateFilterOptions = {
+    languages,
+    include,
+    exclude,
+    files: file === undefined
+      ? changedFiles
+      : new Set(!changedFiles || changedFiles.has(file) ? [file] : []),
+    testScope: testScope as TestScope,
+    ignoreCase: source.ignoreCase,
+  }
+  const isCandidate = createCandidateFilter(source.root, candidateOptions)
+  const audit = auditSearchFiles(source.root, {
+    files: source.files,
+    skipped: source.skipped,
+    gitIgnored: source.gitIgnored,
+    depthSkipped: source.depthSkipped,
+  }, candidateOptions)
+
+  const pageOffset = Math.max(0, Math.floor(offset) || 0)
+  const pageLimit = Math.min(Math.max(0, Math.floor(limit) || 0), SEARCH_LIMITS.MAX_PAGE_SIZE)
+  const empty: QuerySearchResult = {
+    results: [],
+    totalResults: 0,
+    totalFiles: 0,
+    offset: pageOffset,
+    limit: pageLimit,
+    hasMore: false,
+    dedup: dedupMode,
+    totalBeforeDedup: 0,
+    audit,
+    ...countOnly ? { counts: new Map() } : {},
+  }
+
+  const hasFilters = (languages?.length || 0) + include.length + exclude.length > 0 || changedSince !== undefined || testScope !== 'include' || file !== undefined
+  if (hasFilters && !source.files.some(candidate => isCandidate(candidate.path))) {
+    return { ...empty, message: `No indexed files match the search filters (${describeFilters(languages, include, exclude, changedSince, testScope as TestScope, file)})` }
+  }
+
+  // Rank every match without content so the page slice, total, and content inclusion agree.
+  // The timeout covers the search alone; indexing has already finished here.
+  const deadline = timeoutMs === undefined ? Infinity : performance.now() + timeoutMs
+  const nodes = (FILE_MATCHERS.has(matcher as SearchMatcher) ? source.files : source.nodes).filter(node => isCandidate(node.path))
+
+  // Counting needs neither ranking nor a page, so each match is tallied as it is found and dropped
+  if (countOnly) {
+    const inScope = createTestScopeFilter(source.files, source.root, testScope as TestScope)
+    const counter = createResultCounter(dedupMode)
+    const { timedOut } = await searchWithDeadline(matcher as SearchMatcher, query, nodes, {
+      ...searchOptions,
+      kind: kindFilter,
+      lineRange,
+      onMatch: (node) => {
+        if (inScope(node)) counter.add(node)
+      },
+    }, deadline, signal)
+    const counts = counter.counts()
+    const totalResults = [...counts.values()].reduce((sum, count) => sum + count, 0)
+    return { ...empty, totalBeforeDedup: counter.added, ...timedOut ? { timedOut } : {}, counts, totalResults, totalFiles: counts.size }
+  }
+
+  const { value: found, timedOut } = await searchWithDeadline(matcher as SearchMatcher, query, nodes, {
+    ...searchOptions,
+    kind: kindFilter,
+    lineRange,
+    maxResults: Infinity,
+    forceContentInclusion: false,
+    disableContentInclusion: true,
+  }, deadline, signal)
+  const matched = filterByTestScope(found, source.files, source.root, testScope as TestScope)
+  const base = { ...empty, totalBeforeDedup: matched.length, ...timedOut ? { timedOut } : {} }
+
+  const ranked = dedupResults(sortResults(matched, sort as SearchSort, {
+    root: source.root,
+    currentFile,
+    files: source.files,
+  }), dedupMode)
+  const page = paginateResults(ranked, pageOffset, pageLimit, searchOptions)
+  return {
+    ...base,
+    results: page.results,
+    totalResults: page.totalResults,
+    totalFiles: new Set(ranked.map(result => result.node.path)).size,
+    hasMore: page.hasMore,
+  }
+}
+
+function findSourceUsage(source: QuerySource, identifier: string, options: QueryUsageOptions): QueryUsageResult {
+  const { maxResults = 50, ...usageOptions } = options
+  const usages = findUsage(identifier, source.nodes, usageOptions)
+  return { usages: usages.slice(0, maxResults), totalUsages: usages.length }
+}
+
+function describeFilters(languages: LanguageConfig[] | undefined, include: string[], exclude: string[], changedSince?: string, testScope: TestScope = 'include', file?: string): string {
+  const parts: string[] = []
+  if (file !== undefined) parts.push(`file: ${file}`)
+  if (languages?.length) parts.push(`language: ${languages.map(language => language.name).join(', ')}`)
+  if (include.length > 0) parts.push(`include: ${include.join(', ')}`)
+  if (exclude.length > 0) parts.push(`exclude: ${exclude.join(', ')}`)
+  if (changedSince !== undefined) parts.push(`changedSince: ${changedSince}`)
+  if (testScope !== 'include') parts.push(`testScope: ${testScope}`)
+  return parts.join('; ')
+}
\ No newline at end of file
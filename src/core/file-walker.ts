@@ -2,17 +2,65 @@
  * Simplified file walker - replaces complex FileWalker class
  */
 
-import { readdir, stat } from 'fs/promises'
-import { join, resolve, extname } from 'path'
-import { getLanguageByExtension } from './languages.js'
+import { closeSync, lstatSync, openSync, readSync, realpathSync, statSync, type Stats } from 'fs'
+import { lstat, readdir, realpath, stat } from 'fs/promises'
+import { join, resolve, relative, sep, isAbsolute } from 'path'
+import { isLanguageSelected, resolveFileLanguage } from './languages.js'
 import { getLogger } from '../utils/logger.js'
-import { isTestFile, GLOBAL_IGNORE_DIRS } from '../constants/index.js'
+import { isBinaryContent } from '../utils/encoding.js'
+import { GLOBAL_IGNORE_DIRS, MEMORY_LIMITS } from '../constants/index.js'
+import { loadGitignore, isGitIgnored, type IgnoreRule } from './gitignore.js'
+import { pathKey } from './path-case.js'
+import type { LanguageConfig, SkippedFile } from '../types/core.js'
+
+// Bytes read from the start of each file to detect binary content, a `#!` line, or header dialect hints
+const BINARY_SNIFF_BYTES = 8000
 
 export interface WalkOptions {
+  /** Directory levels to descend below the root, which is depth 0; deeper directories are not entered */
   maxDepth?: number
   ignoreDirs?: string[]
+  /** When false, the default exclude set (`GLOBAL_IGNORE_DIRS`) is not applied, leaving `ignoreDirs` and `.gitignore` rules */
+  useDefaultExcludes?: boolean
   languages?: string[]
   includeHidden?: boolean
+  respectGitignore?: boolean
+  /** When true, symlinked files and directories are walked too, each canonical path once (default: false) */
+  followSymlinks?: boolean
+  /** When true, followed symlinks may lead outside `roots`; otherwise such links are skipped */
+  allowOutsideRoot?: boolean
+  /** Directories a followed symlink may point into besides the walked one, such as an enclosing project root */
+  roots?: string[]
+  /** When true, excluded directory names and `.gitignore` rules match in any case (default: false) */
+  ignoreCase?: boolean
+  /** Files larger than this many bytes are skipped (default: the --max-file-size setting) */
+  maxFileSize?: number
+  /** Called for each file left out for being too large or binary */
+  onSkip?: (file: SkippedFile) => void
+  /** Called for each file or directory a `.gitignore` rule leaves out; an ignored directory is not entered */
+  onGitIgnored?: (path: string) => void
+  /** Called for each directory left unentered because it lies deeper than `maxDepth` */
+  onDepthLimit?: (path: string) => void
+  /** Called for each followed symlink left out for pointing outside the roots, with its canonical target */
+  onOutsideRoot?: (path: string, target: string) => void
+  /** Called for each file the walk includes, with the modification time from its stat */
+  onFileFound?: (path: string, mtimeMs: number) => void
+}
+
+/** How deep project walks descend when the project sets no `maxDepth` */
+export const DEFAULT_PROJECT_DEPTH = 15
+
+let defaultMaxFileSize: number = MEMORY_LIMITS.MAX_FILE_SIZE_BYTES
+
+/**
+ * Sets the default file size limit (used by the --max-file-size CLI option)
+ */
+export function setMaxFileSize(bytes: number): void {
+  defaultMaxFileSize = Math.max(0, Math.floor(bytes))
+}
+
+export function getMaxFileSize(): number {
+  return defaultMaxFileSize
 }
 
 export async function walkDirectory(
@@ -23,31 +71,65 @@ export async function walkDirectory(
   const {
     maxDepth = 10,
     ignoreDirs = [],
+    useDefaultExcludes = true,
     languages = [],
     includeHidden = false,
+    respectGitignore = true,
+    followSymlinks = false,
+    allowOutsideRoot = false,
+    roots = [],
+    ignoreCase = false,
+    maxFileSize = defaultMaxFileSize,
+    onSkip,
+    onGitIgnored,
+    onDepthLimit,
+    onOutsideRoot,
+    onFileFound,
   } = options
 
-  const ignoreDirSet = new Set([...GLOBAL_IGNORE_DIRS, ...ignoreDirs])
+  const ignoreDirSet = createIgnoreDirSet(useDefaultExcludes, ignoreDirs, ignoreCase)
   const files: string[] = []
+  // Canonical paths of walked directories; a symlink back into one of them is not followed again
+  const visited = new Set<string>()
+  // Canonical paths of included files, so a file reached through a symlink is not indexed twice
+  const visitedFiles = new Set<string>()
+  const canonicalRoots = followSymlinks && !allowOutsideRoot
+    ? await Promise.all([directory, ...roots].map(root => realpath(root).catch(() => resolve(root))))
+    : []
 
-  async function walk(dir: string, depth: number): Promise<void> {
-    if (depth >= maxDepth) return
-
+  async function walk(dir: string, depth: number, parentRules: IgnoreRule[]): Promise<void> {
     try {
       const entries = await readdir(dir)
+      const rules = respectGitignore && entries.includes('.gitignore')
+        ? [...parentRules, ...loadGitignore(dir, ignoreCase)]
+        : parentRules
 
       for (const entry of entries) {
         if (!includeHidden && entry.startsWith('.')) continue
 
         const fullPath = join(dir, entry)
-        const stats = await stat(fullPath)
+        let stats = await lstat(fullPath)
+        if (stats.isSymbolicLink()) {
+          const target = followSymlinks ? await followLink(fullPath) : undefined
+          if (!target) continue
+          stats = target
+        }
 
         if (stats.isDirectory()) {
           if (!includeHidden && entry.startsWith('.')) {
             continue
           }
-          if (!ignoreDirSet.has(entry)) {
-            await walk(fullPath, depth + 1)
+          if (ignoreDirSet.has(pathKey(entry, ignoreCase))) continue
+          if (isGitIgnored(fullPath, true, rules)) {
+            onGitIgnored?.(resolve(fullPath))
+            continue
+          }
+          if (depth + 1 > maxDepth) {
+            onDepthLimit?.(resolve(fullPath))
+            continue
+          }
+          if (await markVisited(fullPath)) {
+            await walk(fullPath, depth + 1, rules)
           }
         }
         else if (stats.isFile()) {
@@ -55,14 +137,23 @@ export async function walkDirectory(
             continue
           }
 
-          if (isTestFile(entry)) {
+          if (isGitIgnored(fullPath, false, rules)) {
+            onGitIgnored?.(resolve(fullPath))
             continue
           }
 
-          const language = getLanguageByExtension(extname(fullPath))
+          const language = languages.length > 0 ? detectLanguage(fullPath) : undefined
 
-          if (languages.length === 0 || (language && languages.includes(language.name))) {
+          if (languages.length === 0 || (language && isLanguageSelected(language, languages))) {
+            const reason = getSkipReason(fullPath, stats.size, maxFileSize)
+            if (reason) {
+              logger.debug(`Skipping ${reason === 'binary' ? 'binary' : 'oversized'} file: ${fullPath}`)
+              onSkip?.({ path: resolve(fullPath), reason, size: stats.size })
+              continue
+            }
+            if (followSymlinks && !(await markFileVisited(fullPath))) continue
             files.push(resolve(fullPath))
+            onFileFound?.(resolve(fullPath), stats.mtimeMs)
           }
         }
       }
@@ -72,15 +163,195 @@ export async function walkDirectory(
     }
   }
 
-  await walk(directory, 0)
+  async function markVisited(dir: string): Promise<boolean> {
+    const canonical = await realpath(dir)
+    if (visited.has(canonical)) {
+      logger.debug(`Skipping already walked directory: ${dir}`)
+      return false
+    }
+    visited.add(canonical)
+    return true
+  }
+
+  async function markFileVisited(file: string): Promise<boolean> {
+    const canonical = await realpath(file)
+    if (visitedFiles.has(canonical)) {
+      logger.debug(`Skipping already walked file: ${file}`)
+      return false
+    }
+    visitedFiles.add(canonical)
+    return true
+  }
+
+  // The stats of the link's target, or undefined for a broken link or one pointing outside the roots
+  async function followLink(link: string): Promise<Stats | undefined> {
+    let target: string
+    try {
+      target = await realpath(link)
+    }
+    catch {
+      logger.debug(`Skipping broken symlink: ${link}`)
+      return undefined
+    }
+    if (!allowOutsideRoot && !isInsideRoots(target, canonicalRoots)) {
+      logger.warn(`Skipping symlink pointing outside the project root: ${link} -> ${target}`)
+      onOutsideRoot?.(resolve(link), target)
+      return undefined
+    }
+    return stat(target)
+  }
+
+  await markVisited(directory)
+  await walk(directory, 0, [])
   return files
 }
 
-export async function findProjectFiles(directory: string, languages?: string[], ignoreDirs?: string[]): Promise<string[]> {
+function isInsideRoots(path: string, roots: string[]): boolean {
+  return roots.some(root => (path + sep).startsWith(root + sep))
+}
+
+function getSkipReason(filePath: string, size: number, maxFileSize: number): SkippedFile['reason'] | undefined {
+  if (size > maxFileSize) return 'too_large'
+  return isBinaryFile(filePath) ? 'binary' : undefined
+}
+
+function isBinaryFile(filePath: string): boolean {
+  return isBinaryContent(readHead(filePath, BINARY_SNIFF_BYTES))
+}
+
+/**
+ * Files the extension does not settle are classified from their first bytes, so `bin/deploy` with
+ * `#!/usr/bin/env bash` is shell
+ */
+function detectLanguage(filePath: string): LanguageConfig | undefined {
+  try {
+    return resolveFileLanguage(filePath, () => readHead(filePath, BINARY_SNIFF_BYTES).toString('utf-8'))?.language
+  }
+  catch {
+    return undefined
+  }
+}
+
+function readHead(filePath: string, bytes: number): Buffer {
+  const buffer = Buffer.alloc(bytes)
+  const fd = openSync(filePath, 'r')
+  try {
+    return buffer.subarray(0, readSync(fd, buffer, 0, bytes, 0))
+  }
+  finally {
+    closeSync(fd)
+  }
+}
+
+/**
+ * Checks whether a walk of the directory with these options would include the file.
+ * Used to filter watcher events so they follow the same rules as a full reindex.
+ */
+export function isWalkedFile(directory: string, filePath: string, options: WalkOptions = {}): boolean {
+  const {
+    maxDepth = 10,
+    ignoreDirs = [],
+    useDefaultExcludes = true,
+    languages = [],
+    includeHidden = false,
+    respectGitignore = true,
+    followSymlinks = false,
+    allowOutsideRoot = false,
+    roots = [],
+    ignoreCase = false,
+    maxFileSize = defaultMaxFileSize,
+  } = options
+
+  const relativePath = relative(directory, filePath)
+  if (!relativePath || relativePath.startsWith('..') || isAbsolute(relativePath)) return false
+
+  const segments = relativePath.split(sep)
+  const fileName = segments[segments.length - 1]!
+  if (segments.length - 1 > maxDepth) return false
+
+  const ignoreDirSet = createIgnoreDirSet(useDefaultExcludes, ignoreDirs, ignoreCase)
+  let rules: IgnoreRule[] = respectGitignore ? loadGitignore(directory, ignoreCase) : []
+  let current = directory
+
+  for (const segment of segments.slice(0, -1)) {
+    if (!includeHidden && segment.startsWith('.')) return false
+    if (ignoreDirSet.has(pathKey(segment, ignoreCase))) return false
+
+    current = join(current, segment)
+    if (!followSymlinks && isSymlink(current)) return false
+    if (isGitIgnored(current, true, rules)) return false
+    if (respectGitignore) rules = [...rules, ...loadGitignore(current, ignoreCase)]
+  }
+
+  if (!includeHidden && fileName.startsWith('.')) return false
+  if (isGitIgnored(filePath, false, rules)) return false
+  if (followSymlinks ? !allowOutsideRoot && !isInsideRoots(canonicalPath(filePath), [directory, ...roots].map(canonicalPath)) : isSymlink(filePath)) return false
+
+  const language = languages.length > 0 ? detectLanguage(filePath) : undefined
+  if (languages.length > 0 && !(language && isLanguageSelected(language, languages))) return false
+
+  try {
+    return !getSkipReason(filePath, statSync(filePath).size, maxFileSize)
+  }
+  catch {
+    return false
+  }
+}
+
+function createIgnoreDirSet(useDefaultExcludes: boolean, ignoreDirs: string[], ignoreCase: boolean): Set<string> {
+  return new Set([...(useDefaultExcludes ? GLOBAL_IGNORE_DIRS : []), ...ignoreDirs].map(name => pathKey(name, ignoreCase)))
+}
+
+function isSymlink(path: string): boolean {
+  try {
+    return lstatSync(path).isSymbolicLink()
+  }
+  catch {
+    return false
+  }
+}
+
+function canonicalPath(path: string): string {
+  try {
+    return realpathSync(path)
+  }
+  catch {
+    return resolve(path)
+  }
+}
+
+type ProjectWalkOptions = Pick<WalkOptions, 'maxDepth' | 'maxFileSize' | 'useDefaultExcludes' | 'followSymlinks' | 'allowOutsideRoot' | 'roots' | 'ignoreCase'>
+
+export async function findProjectFiles(
+  directory: string,
+  languages?: string[],
+  ignoreDirs?: string[],
+  options: ProjectWalkOptions & Pick<WalkOptions, 'onSkip' | 'onGitIgnored' | 'onDepthLimit' | 'onOutsideRoot' | 'onFileFound'> = {},
+): Promise<string[]> {
   return walkDirectory(directory, {
-    maxDepth: 15,
+    ...options,
+    maxDepth: options.maxDepth ?? DEFAULT_PROJECT_DEPTH,
+    includeHidden: false,
     languages,
     ignoreDirs: ignoreDirs || [],
+  })
+}
+
+/**
+ * Checks whether findProjectFiles would include the file
+ */
+export function isProjectFile(
+  directory: string,
+  filePath: string,
+  languages?: string[],
+  ignoreDirs?: string[],
+  options: ProjectWalkOptions = {},
+): boolean {
+  return isWalkedFile(directory, filePath, {
+    ...options,
+    maxDepth: options.maxDepth ?? DEFAULT_PROJECT_DEPTH,
     includeHidden: false,
+    languages,
+    ignoreDirs: ignoreDirs || [],
   })
 }
\ No newline at end of file
@@ -0,0 +1,154 @@
+/**
+ * Git integration - change sets for review-scoped searches, line blame for routing results to their authors,
+ * and file contents at other commits for comparing them
+ */
+
+import { execFileSync } from 'child_process'
+import { resolve } from 'path'
+import { createError } from '../utils/errors.js'
+import type { BlameInfo } from '../types/core.js'
+
+export interface ChangedFilesOptions {
+  /** Files git does not track yet, minus ignored ones (default: true) */
+  includeUntracked?: boolean
+}
+
+/**
+ * Returns absolute paths of files changed since `ref`, diffed from the merge base of `ref` and `HEAD` so
+ * only this branch's changes count, like a pull request diff. Uncommitted edits are included; deleted
+ * files are not, since there is nothing left to search.
+ */
+export function getChangedFiles(directory: string, ref: string, options: ChangedFilesOptions = {}): Set<string> {
+  const { includeUntracked = true } = options
+  if (ref.startsWith('-')) {
+    throw createError('SEARCH_ERROR', `Invalid git ref: ${ref}`, { ref })
+  }
+
+  try {
+    runGit(directory, ['rev-parse', '--show-toplevel'])
+  }
+  catch {
+    throw createError('PROJECT_ERROR', `${directory} is not inside a git repository, so changedSince cannot be used`, { directory })
+  }
+
+  let base: string
+  try {
+    base = runGit(directory, ['merge-base', `${ref}^{commit}`, 'HEAD']).trim()
+  }
+  catch {
+    throw createError('SEARCH_ERROR', `Unknown git ref or no common history with HEAD: ${ref}`, { ref })
+  }
+
+  // Paths relative to the project directory, so symlinked roots still match indexed paths
+  const paths = splitPaths(runGit(directory, ['diff', '--name-only', '-z', '--relative', '--no-renames', '--diff-filter=d', base, '--']))
+  if (includeUntracked) {
+    paths.push(...splitPaths(runGit(directory, ['ls-files', '--others', '--exclude-standard', '-z'])))
+  }
+
+  return new Set(paths.map(path => resolve(directory, path)))
+}
+
+/**
+ * Returns the files that differ between two commits, relative to the project directory. Renames count
+ * as a deletion and an addition, so both paths are listed.
+ */
+export function getPathsChangedBetween(directory: string, from: string, to: string): string[] {
+  try {
+    runGit(directory, ['rev-parse', '--show-toplevel'])
+  }
+  catch {
+    throw createError('PROJECT_ERROR', `${directory} is not inside a git repository, so refs cannot be compared`, { directory })
+  }
+
+  const commits = [from, to].map((ref) => {
+    if (ref.startsWith('-')) {
+      throw createError('SEARCH_ERROR', `Invalid git ref: ${ref}`, { ref })
+    }
+    try {
+      return runGit(directory, ['rev-parse', '--verify', `${ref}^{commit}`]).trim()
+    }
+    catch {
+      throw createError('SEARCH_ERROR', `Unknown git ref: ${ref}`, { ref })
+    }
+  })
+
+  return splitPaths(runGit(directory, ['diff', '--name-only', '-z', '--relative', '--no-renames', commits[0]!, commits[1]!, '--']))
+}
+
+/**
+ * Reads a file's bytes as of `ref`, given a path relative to the project directory, or undefined when
+ * the file does not exist there
+ */
+export function readFileAtRef(directory: string, ref: string, path: string): Buffer | undefined {
+  try {
+    return execFileSync('git', ['show', `${ref}:./${path}`], { cwd: directory, stdio: ['ignore', 'pipe', 'pipe'], maxBuffer: 64 * 1024 * 1024 })
+  }
+  catch {
+    return undefined
+  }
+}
+
+const DIRTY: BlameInfo = { author: null, commit: null, dirty: true }
+const SHORT_HASH_LENGTH = 7
+
+/**
+ * Returns a lookup from file and 1-based line to the line's last commit. Each file is blamed on its
+ * first lookup and reused after that; untracked and modified files report `dirty` instead.
+ */
+export function createBlameLookup(directory: string): (filePath: string, line: number) => BlameInfo {
+  try {
+    runGit(directory, ['rev-parse', '--show-toplevel'])
+  }
+  catch {
+    throw createError('PROJECT_ERROR', `${directory} is not inside a git repository, so blame cannot be used`, { directory })
+  }
+
+  const files = new Map<string, BlameInfo[] | undefined>()
+  return (filePath, line) => {
+    if (!files.has(filePath)) files.set(filePath, blameFile(directory, filePath))
+    return files.get(filePath)?.[line - 1] ?? DIRTY
+  }
+}
+
+function blameFile(directory: string, filePath: string): BlameInfo[] | undefined {
+  try {
+    if (runGit(directory, ['status', '--porcelain', '--', filePath]).trim()) return undefined
+    return parseLinePorcelain(runGit(directory, ['blame', '--line-porcelain', '--', filePath]))
+  }
+  catch {
+    // Ignored files and files outside the repository have no history to blame
+    return undefined
+  }
+}
+
+/**
+ * `--line-porcelain` repeats the full commit headers before every line, so each entry is self-contained
+ */
+function parseLinePorcelain(output: string): BlameInfo[] {
+  const lines: BlameInfo[] = []
+  let commit: string | undefined
+  let author: string | null = null
+
+  for (const row of output.split('\n')) {
+    if (row.startsWith('\t')) {
+      lines.push(commit && !/^0+$/.test(commit) ? { author, commit: commit.slice(0, SHORT_HASH_LENGTH) } : DIRTY)
+      commit = undefined
+      author = null
+    }
+    else if (commit === undefined) {
+      commit = row.split(' ')[0]
+    }
+    else if (row.startsWith('author ')) {
+      author = row.slice('author '.length)
+    }
+  }
+  return lines
+}
+
+function runGit(cwd: string, args: string[]): string {
+  return execFileSync('git', args, { cwd, encoding: 'utf-8', stdio: ['ignore', 'pipe', 'pipe'], maxBuffer: 64 * 1024 * 1024 })
+}
+
+function splitPaths(output: string): string[] {
+  return output.split('\0').filter(Boolean)
+}
\ No newline at end of file
@@ -0,0 +1,153 @@
+/**
+ * Gitignore matching - applies .gitignore rules from every directory level during a walk, and compiles path globs
+ */
+
+import { readFileSync } from 'fs'
+import { join, relative, sep } from 'path'
+
+export interface IgnoreRule {
+  base: string
+  regex: RegExp
+  negated: boolean
+  dirOnly: boolean
+}
+
+/**
+ * Parses .gitignore content into rules relative to the directory containing the file. With `ignoreCase`,
+ * as git's `core.ignorecase` on macOS and Windows, patterns match paths in any case.
+ */
+export function parseGitignore(content: string, base: string, ignoreCase = false): IgnoreRule[] {
+  const rules: IgnoreRule[] = []
+
+  for (const rawLine of content.split(/\r?\n/)) {
+    let line = rawLine.replace(/(?<!\\)\s+$/, '')
+    if (!line || line.startsWith('#')) continue
+
+    let negated = false
+    if (line.startsWith('!')) {
+      negated = true
+      line = line.substring(1)
+    }
+    else if (line.startsWith('\\!') || line.startsWith('\\#')) {
+      line = line.substring(1)
+    }
+
+    let dirOnly = false
+    if (line.endsWith('/')) {
+      dirOnly = true
+      line = line.replace(/\/+$/, '')
+    }
+    if (!line) continue
+
+    // A slash anywhere but the end anchors the pattern to the .gitignore directory
+    const anchored = line.includes('/')
+    line = line.replace(/^\//, '')
+
+    const body = globToRegex(line)
+    rules.push({
+      base,
+      regex: new RegExp(anchored ? `^${body}$` : `(?:^|/)${body}$`, ignoreCase ? 'i' : ''),
+      negated,
+      dirOnly,
+    })
+  }
+
+  return rules
+}
+
+/**
+ * Reads the .gitignore in a directory, returning no rules when it is missing or unreadable
+ */
+export function loadGitignore(directory: string, ignoreCase = false): IgnoreRule[] {
+  try {
+    return parseGitignore(readFileSync(join(directory, '.gitignore'), 'utf-8'), directory, ignoreCase)
+  }
+  catch {
+    return []
+  }
+}
+
+/**
+ * Checks a path against accumulated rules; the last matching rule wins, as in git
+ */
+export function isGitIgnored(fullPath: string, isDirectory: boolean, rules: IgnoreRule[]): boolean {
+  let ignored = false
+
+  for (const rule of rules) {
+    if (rule.dirOnly && !isDirectory) continue
+
+    const relativePath = relative(rule.base, fullPath).split(sep).join('/')
+    if (!relativePath || relativePath.startsWith('..')) continue
+
+    if (rule.regex.test(relativePath)) {
+      ignored = !rule.negated
+    }
+  }
+
+  return ignored
+}
+
+/**
+ * Compiles a glob matched against `/`-separated relative paths. As in .gitignore, a pattern without
+ * an inner slash matches at any depth, and a match on a directory covers everything beneath it.
+ */
+export function compileGlob(pattern: string, ignoreCase = false): RegExp {
+  const line = pattern.trim().replace(/^\.\//, '').replace(/\/+$/, '')
+  const anchored = line.includes('/')
+  const body = globToRegex(line.replace(/^\//, ''))
+  return new RegExp(anchored ? `^${body}(?:/|$)` : `(?:^|/)${body}(?:/|$)`, ignoreCase ? 'i' : '')
+}
+
+function globToRegex(pattern: string): string {
+  let result = ''
+  let i = 0
+
+  while (i < pattern.length) {
+    const char = pattern[i]!
+
+    if (char === '*') {
+      if (pattern[i + 1] === '*') {
+        const atSegmentStart = i === 0 || pattern[i - 1] === '/'
+        if (atSegmentStart && pattern[i + 2] === '/') {
+          result += '(?:.*/)?'
+          i += 3
+          continue
+        }
+        if (atSegmentStart && i + 2 === pattern.length) {
+          result += '.*'
+          i += 2
+          continue
+        }
+      }
+      result += '[^/]*'
+    }
+    else if (char === '?') {
+      result += '[^/]'
+    }
+    else if (char === '[') {
+      const close = pattern.indexOf(']', i + 1)
+      if (close === -1) {
+        result += '\\['
+      }
+      else {
+        const set = pattern.substring(i + 1, close).replace(/^!/, '^').replace(/\\/g, '\\\\')
+        result += `[${set}]`
+        i = close
+      }
+    }
+    else if (char === '\\' && i + 1 < pattern.length) {
+      result += escapeRegex(pattern[i + 1]!)
+      i++
+    }
+    else {
+      result += escapeRegex(char)
+    }
+    i++
+  }
+
+  return result
+}
+
+function escapeRegex(text: string): string {
+  return text.replace(/[.*+?^${}()|[\]\\/]/g, '\\$&')
+}
\ No newline at end of file
@@ -0,0 +1,116 @@
+/**
+ * Implementation lookup - lists Rust `impl Trait for Type` blocks and TypeScript `implements` clauses for a trait or interface
+ */
+
+import type Parser from 'tree-sitter'
+import type { TreeNode, ImplementationResult } from '../types/core.js'
+
+const CLASS_TYPES = new Set(['class_declaration', 'abstract_class_declaration', 'class'])
+
+// Wrappers around a type name: `Store<T>`, `fmt::Display`, `models.Store`
+const QUALIFIED_TYPES = new Set([
+  'generic_type',
+  'generic_name',
+  'scoped_type_identifier',
+  'nested_type_identifier',
+  'qualified_name',
+  'member_expression',
+  'attribute',
+])
+
+export interface FindImplementationsOptions {
+  pathPattern?: string
+}
+
+/**
+ * Finds types implementing a trait or interface. `symbol` may be qualified (`fmt::Display`), in which
+ * case the written path must end with it. Impls and classes with their own type parameters are generic.
+ */
+export function findImplementations(
+  symbol: string,
+  files: TreeNode[],
+  options: FindImplementationsOptions = {},
+): ImplementationResult[] {
+  const { pathPattern } = options
+  const results: ImplementationResult[] = []
+
+  for (const fileNode of files) {
+    if (!fileNode.rawNode || !fileNode.content) continue
+    if (pathPattern && !fileNode.path.includes(pathPattern)) continue
+
+    const lines = fileNode.content.split(/\r?\n/)
+    const visit = (node: Parser.SyntaxNode) => {
+      for (const [implementor, traitNode] of getImplementedTraits(node)) {
+        if (!matchesSymbol(traitNode, symbol)) continue
+        results.push({
+          implementor: implementor?.text ?? '<anonymous>',
+          trait: traitNode.text,
+          generic: node.childForFieldName('type_parameters') !== null,
+          path: fileNode.path,
+          startLine: node.startPosition.row + 1,
+          endLine: node.endPosition.row + 1,
+          startColumn: node.startPosition.column,
+          endColumn: node.endPosition.column,
+          context: (lines[node.startPosition.row] || '').trim(),
+        })
+      }
+
+      for (const child of node.namedChildren) {
+        visit(child)
+      }
+    }
+    visit(fileNode.rawNode)
+  }
+
+  return results
+}
+
+/**
+ * Pairs of implementing type and implemented trait node; a class may implement several interfaces.
+ * Anonymous classes have no implementing type node.
+ */
+export function getImplementedTraits(node: Parser.SyntaxNode): [Parser.SyntaxNode | null, Parser.SyntaxNode][] {
+  if (node.type === 'impl_item') {
+    const traitNode = node.childForFieldName('trait')
+    const typeNode = node.childForFieldName('type')
+    return traitNode && typeNode ? [[typeNode, traitNode]] : []
+  }
+
+  if (CLASS_TYPES.has(node.type)) {
+    const implementsClause = node.namedChildren
+      .find(child => child.type === 'class_heritage')
+      ?.namedChildren.find(child => child.type === 'implements_clause')
+    if (!implementsClause) return []
+
+    const implementor = node.childForFieldName('name')
+    return implementsClause.namedChildren
+      .filter(traitNode => !traitNode.type.includes('comment'))
+      .map(traitNode => [implementor, traitNode])
+  }
+
+  return []
+}
+
+function matchesSymbol(traitNode: Parser.SyntaxNode, symbol: string): boolean {
+  const segments = symbol.split(/::|\./)
+  if (getBaseName(traitNode) !== segments[segments.length - 1]) return false
+  if (segments.length === 1) return true
+
+  const path = traitNode.text.replace(/<[\s\S]*$/, '').replace(/\s+/g, '')
+  return path === symbol || path.endsWith(`::${symbol}`) || path.endsWith(`.${symbol}`)
+}
+
+/**
+ * The unqualified type name without type arguments (`fmt::Display` and `Store<T>` give `Display` and `Store`).
+ * Grammars name the inner node `name`, `type`, `property` or `attribute`; Java and C# generics only have children.
+ */
+export function getBaseName(node: Parser.SyntaxNode): string {
+  if (!QUALIFIED_TYPES.has(node.type)) return node.text
+
+  const inner = node.childForFieldName('name')
+    ?? node.childForFieldName('type')
+    ?? node.childForFieldName('property')
+    ?? node.childForFieldName('attribute')
+    ?? (node.type.startsWith('generic') ? node.firstNamedChild : node.lastNamedChild)
+  return inner ? getBaseName(inner) : node.text
+}
\ No newline at end of file
@@ -0,0 +1,254 @@
+/**
+ * Import graph - resolves each file's imports to project files and reports circular dependencies
+ */
+
+import { basename, dirname, join, resolve } from 'path'
+import { extractImportSpecifiers, getFileLanguage } from './languages.js'
+import { findCircularDependencies } from '../analysis/structure.js'
+import { PARSER_NAMES } from '../constants/index.js'
+import type { TreeNode, ImportSpecifier, ImportGraph, ImportEdge, ExternalImport, ImportResolution } from '../types/core.js'
+
+const SCRIPT_EXTENSIONS = ['', '.ts', '.tsx', '.js', '.jsx', '.mjs', '.cjs', '/index.ts', '/index.tsx', '/index.js', '/index.jsx']
+const RUST_ROOT_FILES = ['lib.rs', 'main.rs', 'mod.rs']
+
+export interface ImportGraphOptions {
+  file?: string
+  pathPattern?: string
+}
+
+/**
+ * Builds file-to-file import edges for the project rooted at `root`. Imports that resolve to no
+ * project file (packages, the standard library) are listed in `external`. With `file`, only that
+ * file and its transitive imports are included.
+ */
+export function buildImportGraph(files: TreeNode[], root: string, options: ImportGraphOptions = {}): ImportGraph {
+  const { file, pathPattern } = options
+  const known = new Set(files.map(fileNode => fileNode.path))
+  const edges: ImportEdge[] = []
+  const external: ExternalImport[] = []
+  const seen = new Set<string>()
+
+  for (const fileNode of files) {
+    if (!fileNode.rawNode) continue
+    if (pathPattern && !file && !fileNode.path.includes(pathPattern)) continue
+
+    const language = getFileLanguage(fileNode.path, fileNode.content)
+    if (!language) continue
+
+    for (const specifier of extractImportSpecifiers(fileNode.rawNode, language)) {
+      const targets = resolveSpecifier(specifier, fileNode.path, language.name, root, known)
+        .filter(target => target !== fileNode.path)
+
+      if (targets.length === 0) {
+        external.push({ from: fileNode.path, module: specifier.module, line: specifier.line })
+        continue
+      }
+
+      for (const target of targets) {
+        const key = `${fileNode.path}\u0000${target}\u0000${specifier.line}`
+        if (seen.has(key)) continue
+        seen.add(key)
+        edges.push({ from: fileNode.path, to: target, module: specifier.module, line: specifier.line })
+      }
+    }
+  }
+
+  const included = file ? collectTransitiveImports(file, edges) : null
+  const graphEdges = included ? edges.filter(edge => included.has(edge.from)) : edges
+  const graphFiles = included
+    ? Array.from(included)
+    : files.map(fileNode => fileNode.path).filter(path => !pathPattern || path.includes(pathPattern))
+
+  const dependencies = new Map<string, string[]>(graphFiles.map(path => [path, []]))
+  for (const edge of graphEdges) {
+    dependencies.get(edge.from)?.push(edge.to)
+  }
+
+  return {
+    files: graphFiles,
+    edges: graphEdges,
+    external: included ? external.filter(entry => included.has(entry.from)) : external,
+    cycles: findCircularDependencies(dependencies),
+  }
+}
+
+/**
+ * Resolves the imports of `fileNode` that bind `symbol` (`Cart` in `import { Cart } from './cart.js'`,
+ * `Stock` in `use crate::inventory::Stock`) by the import graph's rules. Imports of packages, the standard
+ * library or paths no project file matches come back unresolved with the module path as written.
+ */
+export function resolveImport(fileNode: TreeNode, symbol: string, files: TreeNode[], root: string): ImportResolution[] {
+  if (!fileNode.rawNode) return []
+
+  const language = getFileLanguage(fileNode.path, fileNode.content)
+  if (!language) return []
+
+  const known = new Set(files.map(file => file.path))
+  return extractImportSpecifiers(fileNode.rawNode, language)
+    .filter(specifier => specifier.names.includes(symbol) || specifier.module === symbol)
+    .map((specifier) => {
+      // Other names imported alongside may be Python submodules that resolve elsewhere
+      const [target] = resolveSpecifier({ ...specifier, names: specifier.names.filter(name => name === symbol) }, fileNode.path, language.name, root, known)
+        .filter(target => target !== fileNode.path)
+      return { module: specifier.module, line: specifier.line, resolved: target !== undefined, ...target ? { file: target } : {} }
+    })
+}
+
+function collectTransitiveImports(start: string, edges: ImportEdge[]): Set<string> {
+  const included = new Set([start])
+  const queue = [start]
+
+  while (queue.length > 0) {
+    const current = queue.shift()!
+    for (const edge of edges) {
+      if (edge.from === current && !included.has(edge.to)) {
+        included.add(edge.to)
+        queue.push(edge.to)
+      }
+    }
+  }
+
+  return included
+}
+
+function resolveSpecifier(
+  specifier: ImportSpecifier,
+  fromFile: string,
+  languageName: string,
+  root: string,
+  known: Set<string>,
+): string[] {
+  switch (languageName) {
+    case PARSER_NAMES.JAVASCRIPT:
+    case PARSER_NAMES.TYPESCRIPT:
+    case PARSER_NAMES.TSX:
+      return toList(resolveScriptImport(specifier.module, fromFile, known))
+    case PARSER_NAMES.PYTHON:
+      return resolvePythonImport(specifier, fromFile, root, known)
+    case PARSER_NAMES.RUST:
+      return toList(resolveRustImport(specifier.module, fromFile, root, known))
+    default:
+      return []
+  }
+}
+
+/**
+ * Relative specifiers only; ESM TypeScript names the emitted `.js` file, so the source extension is tried too
+ */
+function resolveScriptImport(module: string, fromFile: string, known: Set<string>): string | null {
+  if (!module.startsWith('./') && !module.startsWith('../')) return null
+
+  const target = resolve(dirname(fromFile), module)
+  const bases = [target, target.replace(/\.[cm]?js$/, '')]
+
+  for (const base of bases) {
+    for (const extension of SCRIPT_EXTENSIONS) {
+      if (known.has(base + extension)) return base + extension
+    }
+  }
+  return null
+}
+
+/**
+ * Relative imports start from the file's package; absolute ones are tried from each directory up to the root.
+ * Imported names that are submodules (`from pkg import mod`) resolve to their own files.
+ */
+function resolvePythonImport(specifier: ImportSpecifier, fromFile: string, root: string, known: Set<string>): string[] {
+  const dots = specifier.module.match(/^\.*/)![0].length
+  const parts = specifier.module.slice(dots).split('.').filter(Boolean)
+
+  const bases: string[] = []
+  if (dots > 0) {
+    let base = dirname(fromFile)
+    for (let i = 1; i < dots; i++) base = dirname(base)
+    bases.push(base)
+  }
+  else {
+    for (let dir = dirname(fromFile); dir.startsWith(root); dir = dirname(dir)) {
+      bases.push(dir)
+      if (dir === root || dir === dirname(dir)) break
+    }
+  }
+
+  for (const base of bases) {
+    const modulePath = join(base, ...parts)
+    const submodules = specifier.names
+      .map(name => findPythonModule(join(modulePath, ...name.split('.')), known))
+      .filter((path): path is string => path !== null)
+    const moduleFile = findPythonModule(modulePath, known)
+
+    const allSubmodules = specifier.names.length > 0 && submodules.length === specifier.names.length
+    const resolved = allSubmodules || !moduleFile ? submodules : [moduleFile, ...submodules]
+    if (resolved.length > 0) return resolved
+  }
+
+  return []
+}
+
+function findPythonModule(modulePath: string, known: Set<string>): string | null {
+  for (const candidate of [`${modulePath}.py`, join(modulePath, '__init__.py')]) {
+    if (known.has(candidate)) return candidate
+  }
+  return null
+}
+
+/**
+ * `crate::`, `self::` and `super::` paths map onto the module tree; the longest prefix naming a module
+ * file wins (`crate::shapes::Circle` gives `src/shapes.rs`). Other paths are external crates.
+ */
+function resolveRustImport(module: string, fromFile: string, root: string, known: Set<string>): string | null {
+  const segments = module.split('::')
+  let dir: string
+
+  if (segments[0] === 'crate') {
+    const crateRoot = findCrateRoot(fromFile, root, known)
+    if (!crateRoot) return null
+    dir = crateRoot
+    segments.shift()
+  }
+  else if (segments[0] === 'self' || segments[0] === 'super') {
+    dir = getRustModuleDir(fromFile)
+    if (segments[0] === 'self') segments.shift()
+    while (segments[0] === 'super') {
+      dir = dirname(dir)
+      segments.shift()
+    }
+  }
+  else {
+    return null
+  }
+
+  for (let length = segments.length; length > 0; length--) {
+    const modulePath = join(dir, ...segments.slice(0, length))
+    for (const candidate of [`${modulePath}.rs`, join(modulePath, 'mod.rs')]) {
+      if (known.has(candidate)) return candidate
+    }
+  }
+
+  return findRustModuleFile(dir, known)
+}
+
+function findCrateRoot(fromFile: string, root: string, known: Set<string>): string | null {
+  for (let dir = dirname(fromFile); dir.startsWith(root); dir = dirname(dir)) {
+    if (known.has(join(dir, 'lib.rs')) || known.has(join(dir, 'main.rs'))) return dir
+    if (dir === root || dir === dirname(dir)) break
+  }
+  return null
+}
+
+/**
+ * Child modules of `foo.rs` live in `foo/`; those of `mod.rs`, `lib.rs` and `main.rs` sit beside them
+ */
+function getRustModuleDir(file: string): string {
+  const name = basename(file)
+  return RUST_ROOT_FILES.includes(name) ? dirname(file) : join(dirname(file), basename(file, '.rs'))
+}
+
+function findRustModuleFile(dir: string, known: Set<string>): string | null {
+  const candidates = [`${dir}.rs`, ...RUST_ROOT_FILES.map(name => join(dir, name))]
+  return candidates.find(candidate => known.has(candidate)) ?? null
+}
+
+function toList(path: string | null): string[] {
+  return path ? [path] : []
+}
\ No newline at end of file
@@ -0,0 +1,165 @@
+/**
+ * Key paths - dotted paths to the keys of JSON and YAML mappings, matched by keyPath searches
+ */
+
+import type Parser from 'tree-sitter'
+import { PARSER_NAMES } from '../constants/index.js'
+import { createError } from '../utils/errors.js'
+import type { KeyPathLocation, KeyPathMarker, KeyPathMatch, MetavariableBinding } from '../types/core.js'
+
+// Languages whose mappings keyPath searches read
+export const KEY_PATH_LANGUAGES: ReadonlySet<string> = new Set([PARSER_NAMES.JSON, PARSER_NAMES.YAML])
+
+const MAPPING_TYPES = new Set(['object', 'block_mapping', 'flow_mapping'])
+const PAIR_TYPES = new Set(['pair', 'block_mapping_pair', 'flow_pair'])
+const SEQUENCE_TYPES = new Set(['array', 'block_sequence', 'flow_sequence'])
+// YAML nodes that hold a value together with its anchor and tag
+const YAML_NODE_TYPES = new Set(['block_node', 'flow_node'])
+const VALUE_PROPERTY_TYPES = new Set(['anchor', 'tag', 'comment'])
+
+export interface KeyPathEntry extends KeyPathMatch {
+  /** The key/value pair */
+  node: Parser.SyntaxNode
+  segments: string[]
+}
+
+/**
+ * Compiles a dotted key path such as `database.host` into a predicate over path segments. `*` stands
+ * for any one key or index and `**` for any number of them, so `**.host` finds `host` at any depth.
+ */
+export function compileKeyPath(query: string, caseInsensitive = false): (segments: string[]) => boolean {
+  const fold = (text: string) => caseInsensitive ? text.toLowerCase() : text
+  const pattern = query.trim().split('.').map(fold)
+  if (pattern.some(segment => segment === '')) {
+    throw createError('SEARCH_ERROR', `Invalid key path: ${query}. Expected dot-separated keys such as database.host`, { query })
+  }
+
+  const matchFrom = (segments: string[], at: number, from: number): boolean => {
+    if (at === pattern.length) return from === segments.length
+
+    const segment = pattern[at]!
+    if (segment === '**') {
+      for (let next = from; next <= segments.length; next++) {
+        if (matchFrom(segments, at + 1, next)) return true
+      }
+      return false
+    }
+    return from < segments.length
+      && (segment === '*' || segment === fold(segments[from]!))
+      && matchFrom(segments, at + 1, from + 1)
+  }
+
+  return segments => matchFrom(segments, 0, 0)
+}
+
+/**
+ * Lists every key of every mapping in a JSON or YAML tree in document order, with its dotted path. Later
+ * occurrences of a key within one mapping point back at the first; a YAML alias points at the anchor it
+ * names, the last one defined before it.
+ */
+export function collectKeyPaths(root: Parser.SyntaxNode): KeyPathEntry[] {
+  const entries: KeyPathEntry[] = []
+  const anchors = new Map<string, KeyPathLocation>()
+
+  const visit = (node: Parser.SyntaxNode, segments: string[]): void => {
+    if (YAML_NODE_TYPES.has(node.type)) {
+      const anchor = findMarker(node, 'anchor')
+      if (anchor) anchors.set(anchor.name, { line: anchor.line, column: anchor.column })
+    }
+
+    if (MAPPING_TYPES.has(node.type)) {
+      const seen = new Map<string, KeyPathLocation>()
+      for (const pair of node.namedChildren) {
+        const keyNode = PAIR_TYPES.has(pair.type) ? pair.childForFieldName('key') : null
+        if (!keyNode) continue
+
+        const key = readKey(keyNode)
+        const path = [...segments, key]
+        const value = pair.childForFieldName('value')
+        const anchor = value && findMarker(value, 'anchor')
+        const alias = value && findMarker(value, 'alias')
+        const aliased = alias && anchors.get(alias.name)
+        const duplicateOf = seen.get(key)
+
+        entries.push({
+          node: pair,
+          segments: path,
+          path: path.join('.'),
+          value: value ? bindValue(value) : null,
+          ...anchor ? { anchor } : {},
+          ...alias ? { alias: { ...alias, ...aliased ? { anchor: aliased } : {} } } : {},
+          ...duplicateOf ? { duplicateOf } : {},
+        })
+        if (!duplicateOf) seen.set(key, locate(keyNode))
+        if (value) visit(value, path)
+      }
+      return
+    }
+
+    if (SEQUENCE_TYPES.has(node.type)) {
+      let index = 0
+      for (const item of node.namedChildren) {
+        if (item.type === 'comment') continue
+        visit(item, [...segments, String(index++)])
+      }
+      return
+    }
+
+    for (const child of node.namedChildren) {
+      visit(child, segments)
+    }
+  }
+
+  visit(root, [])
+  return entries
+}
+
+function locate(node: Parser.SyntaxNode): KeyPathLocation {
+  return { line: node.startPosition.row + 1, column: node.startPosition.column }
+}
+
+// The anchor or alias written on a YAML value, without its `&` or `*`
+function findMarker(node: Parser.SyntaxNode, type: 'anchor' | 'alias'): KeyPathMarker | undefined {
+  const marker = node.type === type
+    ? node
+    : YAML_NODE_TYPES.has(node.type) ? node.namedChildren.find(child => child.type === type) : undefined
+  return marker && { name: marker.text.slice(1), ...locate(marker) }
+}
+
+// A YAML node's content, past its anchor and tag
+function unwrapValue(node: Parser.SyntaxNode): Parser.SyntaxNode {
+  if (!YAML_NODE_TYPES.has(node.type)) return node
+  return node.namedChildren.filter(child => !VALUE_PROPERTY_TYPES.has(child.type)).at(-1) ?? node
+}
+
+function bindValue(node: Parser.SyntaxNode): MetavariableBinding & { type: string } {
+  const value = unwrapValue(node)
+  return {
+    type: value.type,
+    text: value.text,
+    startLine: value.startPosition.row + 1,
+    endLine: value.endPosition.row + 1,
+    startColumn: value.startPosition.column,
+    endColumn: value.endPosition.column,
+  }
+}
+
+function readKey(node: Parser.SyntaxNode): string {
+  const key = unwrapValue(node)
+  switch (key.type) {
+    case 'string':
+    case 'double_quote_scalar':
+      try {
+        return String(JSON.parse(key.text))
+      }
+      catch {
+        return key.text.slice(1, -1)
+      }
+
+    case 'single_quote_scalar':
+      return key.text.slice(1, -1).replace(/''/g, '\'')
+
+    default:
+      return key.text
+  }
+}
\ No newline at end of file
@@ -3,6 +3,8 @@
  */
 
 import Parser from 'tree-sitter'
+import { createRequire } from 'module'
+import { basename, extname, isAbsolute, relative, resolve, sep } from 'path'
 import JavaScript from 'tree-sitter-javascript'
 import TypeScript from 'tree-sitter-typescript'
 import Python from 'tree-sitter-python'
@@ -17,8 +19,11 @@ import PHP from 'tree-sitter-php'
 import HTML from 'tree-sitter-html'
 import Kotlin from 'tree-sitter-kotlin'
 
-import { LOGIC_EXTENSIONS, PARSER_NAMES, FUNCTION_TYPES, CLASS_TYPES } from '../constants/index.js'
-import type { LanguageConfig, TreeSitterLanguage } from '../types/core.js'
+import { LOGIC_EXTENSIONS, MARKUP_EXTENSIONS, PARSER_NAMES, FUNCTION_TYPES, CLASS_TYPES, BRANCH_TYPES, IMPORT_TYPES, DEFINITION_KINDS, MODIFIER_KEYWORDS, isTestFile } from '../constants/index.js'
+import { compileGlob } from './gitignore.js'
+import { createError } from '../utils/errors.js'
+import { getLogger } from '../utils/logger.js'
+import type { DeclarationModifier, DisabledLanguage, GrammarRegistration, ImportSpecifier, LanguageConfig, LanguageResolution, LanguageSetup, LoadedLanguage, TreeSitterLanguage } from '../types/core.js'
 
 export const LANGUAGE_CONFIGS: LanguageConfig[] = [
   {
@@ -27,13 +32,34 @@ export const LANGUAGE_CONFIGS: LanguageConfig[] = [
     parserName: PARSER_NAMES.JAVASCRIPT,
     functionTypes: [...FUNCTION_TYPES.JAVASCRIPT],
     classTypes: [...CLASS_TYPES.JAVASCRIPT],
+    branchTypes: [...BRANCH_TYPES.JAVASCRIPT],
+    importTypes: [...IMPORT_TYPES.JAVASCRIPT],
+    definitionKinds: { ...DEFINITION_KINDS.JAVASCRIPT },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.JAVASCRIPT },
   },
   {
     name: PARSER_NAMES.TYPESCRIPT,
-    extensions: [...LOGIC_EXTENSIONS.TYPESCRIPT, '.tsx'],
+    extensions: [...LOGIC_EXTENSIONS.TYPESCRIPT],
     parserName: PARSER_NAMES.TYPESCRIPT,
     functionTypes: [...FUNCTION_TYPES.TYPESCRIPT],
     classTypes: [...CLASS_TYPES.TYPESCRIPT],
+    branchTypes: [...BRANCH_TYPES.TYPESCRIPT],
+    importTypes: [...IMPORT_TYPES.TYPESCRIPT],
+    definitionKinds: { ...DEFINITION_KINDS.TYPESCRIPT },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.TYPESCRIPT },
+  },
+  {
+    // The plain TypeScript grammar reads `<Foo>bar` as a type assertion, so JSX needs its own grammar
+    name: PARSER_NAMES.TSX,
+    extensions: [...LOGIC_EXTENSIONS.REACT_TSX],
+    parserName: PARSER_NAMES.TSX,
+    dialectOf: PARSER_NAMES.TYPESCRIPT,
+    functionTypes: [...FUNCTION_TYPES.TYPESCRIPT],
+    classTypes: [...CLASS_TYPES.TYPESCRIPT],
+    branchTypes: [...BRANCH_TYPES.TYPESCRIPT],
+    importTypes: [...IMPORT_TYPES.TYPESCRIPT],
+    definitionKinds: { ...DEFINITION_KINDS.TYPESCRIPT },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.TYPESCRIPT },
   },
   {
     name: PARSER_NAMES.PYTHON,
@@ -41,6 +67,10 @@ export const LANGUAGE_CONFIGS: LanguageConfig[] = [
     parserName: PARSER_NAMES.PYTHON,
     functionTypes: [...FUNCTION_TYPES.PYTHON],
     classTypes: [...CLASS_TYPES.PYTHON],
+    branchTypes: [...BRANCH_TYPES.PYTHON],
+    importTypes: [...IMPORT_TYPES.PYTHON],
+    definitionKinds: { ...DEFINITION_KINDS.PYTHON },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.PYTHON },
   },
   {
     name: PARSER_NAMES.GO,
@@ -48,13 +78,22 @@ export const LANGUAGE_CONFIGS: LanguageConfig[] = [
     parserName: PARSER_NAMES.GO,
     functionTypes: [...FUNCTION_TYPES.GO],
     classTypes: [...CLASS_TYPES.GO],
+    branchTypes: [...BRANCH_TYPES.GO],
+    importTypes: [...IMPORT_TYPES.GO],
+    definitionKinds: { ...DEFINITION_KINDS.GO },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.GO },
   },
   {
     name: PARSER_NAMES.RUST,
     extensions: [...LOGIC_EXTENSIONS.RUST],
     parserName: PARSER_NAMES.RUST,
+    scopeSeparator: '::',
     functionTypes: [...FUNCTION_TYPES.RUST],
     classTypes: [...CLASS_TYPES.RUST],
+    branchTypes: [...BRANCH_TYPES.RUST],
+    importTypes: [...IMPORT_TYPES.RUST],
+    definitionKinds: { ...DEFINITION_KINDS.RUST },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.RUST },
   },
   {
     name: PARSER_NAMES.JAVA,
@@ -62,6 +101,10 @@ export const LANGUAGE_CONFIGS: LanguageConfig[] = [
     parserName: PARSER_NAMES.JAVA,
     functionTypes: [...FUNCTION_TYPES.JAVA],
     classTypes: [...CLASS_TYPES.JAVA],
+    branchTypes: [...BRANCH_TYPES.JAVA],
+    importTypes: [...IMPORT_TYPES.JAVA],
+    definitionKinds: { ...DEFINITION_KINDS.JAVA },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.JAVA },
   },
   {
     name: PARSER_NAMES.C,
@@ -69,20 +112,34 @@ export const LANGUAGE_CONFIGS: LanguageConfig[] = [
     parserName: PARSER_NAMES.C,
     functionTypes: [...FUNCTION_TYPES.C],
     classTypes: [...CLASS_TYPES.C],
+    branchTypes: [...BRANCH_TYPES.C],
+    importTypes: [...IMPORT_TYPES.C],
+    definitionKinds: { ...DEFINITION_KINDS.C },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.C },
   },
   {
     name: PARSER_NAMES.CPP,
     extensions: [...LOGIC_EXTENSIONS.CPP],
     parserName: PARSER_NAMES.CPP,
+    scopeSeparator: '::',
     functionTypes: [...FUNCTION_TYPES.CPP],
     classTypes: [...CLASS_TYPES.CPP],
+    branchTypes: [...BRANCH_TYPES.CPP],
+    importTypes: [...IMPORT_TYPES.CPP],
+    definitionKinds: { ...DEFINITION_KINDS.CPP },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.CPP },
   },
   {
     name: PARSER_NAMES.RUBY,
     extensions: [...LOGIC_EXTENSIONS.RUBY],
     parserName: PARSER_NAMES.RUBY,
+    scopeSeparator: '::',
     functionTypes: [...FUNCTION_TYPES.RUBY],
     classTypes: [...CLASS_TYPES.RUBY],
+    branchTypes: [...BRANCH_TYPES.RUBY],
+    importTypes: [...IMPORT_TYPES.RUBY],
+    definitionKinds: { ...DEFINITION_KINDS.RUBY },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.RUBY },
   },
   {
     name: PARSER_NAMES.CSHARP,
@@ -90,13 +147,22 @@ export const LANGUAGE_CONFIGS: LanguageConfig[] = [
     parserName: PARSER_NAMES.CSHARP,
     functionTypes: [...FUNCTION_TYPES.CSHARP],
     classTypes: [...CLASS_TYPES.CSHARP],
+    branchTypes: [...BRANCH_TYPES.CSHARP],
+    importTypes: [...IMPORT_TYPES.CSHARP],
+    definitionKinds: { ...DEFINITION_KINDS.CSHARP },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.CSHARP },
   },
   {
     name: PARSER_NAMES.PHP,
     extensions: [...LOGIC_EXTENSIONS.PHP],
     parserName: PARSER_NAMES.PHP,
+    scopeSeparator: '::',
     functionTypes: [...FUNCTION_TYPES.PHP],
     classTypes: [...CLASS_TYPES.PHP],
+    branchTypes: [...BRANCH_TYPES.PHP],
+    importTypes: [...IMPORT_TYPES.PHP],
+    definitionKinds: { ...DEFINITION_KINDS.PHP },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.PHP },
   },
   {
     name: PARSER_NAMES.HTML,
@@ -104,6 +170,10 @@ export const LANGUAGE_CONFIGS: LanguageConfig[] = [
     parserName: PARSER_NAMES.HTML,
     functionTypes: [...FUNCTION_TYPES.HTML],
     classTypes: [...CLASS_TYPES.HTML],
+    branchTypes: [...BRANCH_TYPES.HTML],
+    importTypes: [...IMPORT_TYPES.HTML],
+    definitionKinds: { ...DEFINITION_KINDS.HTML },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.HTML },
   },
   {
     name: PARSER_NAMES.KOTLIN,
@@ -111,9 +181,108 @@ export const LANGUAGE_CONFIGS: LanguageConfig[] = [
     parserName: PARSER_NAMES.KOTLIN,
     functionTypes: [...FUNCTION_TYPES.KOTLIN],
     classTypes: [...CLASS_TYPES.KOTLIN],
+    branchTypes: [...BRANCH_TYPES.KOTLIN],
+    importTypes: [...IMPORT_TYPES.KOTLIN],
+    definitionKinds: { ...DEFINITION_KINDS.KOTLIN },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.KOTLIN },
+  },
+  {
+    name: PARSER_NAMES.SWIFT,
+    extensions: [...LOGIC_EXTENSIONS.SWIFT],
+    parserName: PARSER_NAMES.SWIFT,
+    functionTypes: [...FUNCTION_TYPES.SWIFT],
+    classTypes: [...CLASS_TYPES.SWIFT],
+    branchTypes: [...BRANCH_TYPES.SWIFT],
+    importTypes: [...IMPORT_TYPES.SWIFT],
+    definitionKinds: { ...DEFINITION_KINDS.SWIFT },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.SWIFT },
+  },
+  {
+    name: PARSER_NAMES.SCALA,
+    extensions: [...LOGIC_EXTENSIONS.SCALA],
+    parserName: PARSER_NAMES.SCALA,
+    functionTypes: [...FUNCTION_TYPES.SCALA],
+    classTypes: [...CLASS_TYPES.SCALA],
+    branchTypes: [...BRANCH_TYPES.SCALA],
+    importTypes: [...IMPORT_TYPES.SCALA],
+    definitionKinds: { ...DEFINITION_KINDS.SCALA },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.SCALA },
+  },
+  {
+    name: PARSER_NAMES.ELIXIR,
+    extensions: [...LOGIC_EXTENSIONS.ELIXIR],
+    parserName: PARSER_NAMES.ELIXIR,
+    functionTypes: [...FUNCTION_TYPES.ELIXIR],
+    classTypes: [...CLASS_TYPES.ELIXIR],
+    branchTypes: [...BRANCH_TYPES.ELIXIR],
+    importTypes: [...IMPORT_TYPES.ELIXIR],
+    definitionKinds: { ...DEFINITION_KINDS.ELIXIR },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.ELIXIR },
+  },
+  {
+    name: PARSER_NAMES.BASH,
+    extensions: [...LOGIC_EXTENSIONS.BASH],
+    parserName: PARSER_NAMES.BASH,
+    functionTypes: [...FUNCTION_TYPES.BASH],
+    classTypes: [...CLASS_TYPES.BASH],
+    branchTypes: [...BRANCH_TYPES.BASH],
+    importTypes: [...IMPORT_TYPES.BASH],
+    definitionKinds: { ...DEFINITION_KINDS.BASH },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.BASH },
+  },
+  {
+    name: PARSER_NAMES.JSON,
+    extensions: [...MARKUP_EXTENSIONS.JSON],
+    parserName: PARSER_NAMES.JSON,
+    functionTypes: [...FUNCTION_TYPES.JSON],
+    classTypes: [...CLASS_TYPES.JSON],
+    branchTypes: [...BRANCH_TYPES.JSON],
+    importTypes: [...IMPORT_TYPES.JSON],
+    definitionKinds: { ...DEFINITION_KINDS.JSON },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.JSON },
+  },
+  {
+    name: PARSER_NAMES.YAML,
+    extensions: [...MARKUP_EXTENSIONS.YAML],
+    parserName: PARSER_NAMES.YAML,
+    functionTypes: [...FUNCTION_TYPES.YAML],
+    classTypes: [...CLASS_TYPES.YAML],
+    branchTypes: [...BRANCH_TYPES.YAML],
+    importTypes: [...IMPORT_TYPES.YAML],
+    definitionKinds: { ...DEFINITION_KINDS.YAML },
+    modifierKeywords: { ...MODIFIER_KEYWORDS.YAML },
   },
 ]
 
+// Interpreters named on a `#!` line, for scripts without an extension
+const SHEBANG_INTERPRETERS: Record<string, string> = {
+  sh: PARSER_NAMES.BASH,
+  bash: PARSER_NAMES.BASH,
+  dash: PARSER_NAMES.BASH,
+  ksh: PARSER_NAMES.BASH,
+  zsh: PARSER_NAMES.BASH,
+  python: PARSER_NAMES.PYTHON,
+  node: PARSER_NAMES.JAVASCRIPT,
+  ruby: PARSER_NAMES.RUBY,
+}
+
+// C++-only constructs; a `.h` header without any of them is parsed as C
+const CPP_HEADER_HINTS = /^\s*(?:class\s+\w|namespace\s+\w|template\s*<|using\s+namespace\b)|\b(?:public|private|protected)\s*:|\bstd::|#include\s*<(?:iostream|string|vector|memory|map)>/m
+
+// Extensions shared by several grammars; without content the extension's own language is used
+const CONTENT_HEURISTICS: Record<string, (content: string) => string> = {
+  '.h': content => CPP_HEADER_HINTS.test(content) ? PARSER_NAMES.CPP : PARSER_NAMES.C,
+}
+
+interface LanguageOverride {
+  pattern: string
+  glob: RegExp
+  language: LanguageConfig
+}
+
+// Project root -> glob overrides from its config file, matched against root-relative paths
+const languageOverrides = new Map<string, LanguageOverride[]>()
+
 const GRAMMARS: Record<string, TreeSitterLanguage> = {
   [PARSER_NAMES.JAVASCRIPT]: JavaScript,
   [PARSER_NAMES.TYPESCRIPT]: TypeScript.typescript,
@@ -131,36 +300,585 @@ const GRAMMARS: Record<string, TreeSitterLanguage> = {
   [PARSER_NAMES.KOTLIN]: Kotlin,
 }
 
+// Built-in grammars required from their package when parsers are created rather than imported, so a
+// native binding that failed to build or load disables its language instead of every import of this module
+const PACKAGED_GRAMMARS = new Set<string>([
+  PARSER_NAMES.BASH,
+  PARSER_NAMES.SWIFT,
+  PARSER_NAMES.JSON,
+  PARSER_NAMES.YAML,
+  PARSER_NAMES.ELIXIR,
+  PARSER_NAMES.SCALA,
+])
+
 const parsers = new Map<string, Parser>()
+// Grammars of PACKAGED_GRAMMARS that loaded, by parser name
+const packagedGrammars = new Map<string, TreeSitterLanguage>()
+// Built-in languages whose grammar the tree-sitter runtime rejected or failed to load, with the reason
+const disabledLanguages = new Map<string, string>()
 
-export function initializeParsers(): void {
+/**
+ * Creates a parser per built-in language. A grammar built for an ABI this tree-sitter cannot read, or a
+ * grammar package that fails to load, only disables its own language, so the others keep working.
+ * `grammars` replaces bundled grammars by parser name and `loadPackage` replaces `require` for packaged
+ * ones, which lets tests supply a broken one. This runs on import in every parse worker too, so failures
+ * are only logged at debug level; the server warns about them once at startup.
+ */
+export function initializeParsers(
+  grammars: Record<string, TreeSitterLanguage> = {},
+  loadPackage: (packageName: string) => unknown = requireGrammar,
+): void {
   for (const config of LANGUAGE_CONFIGS) {
-    const grammar = GRAMMARS[config.parserName]
-    if (grammar) {
-      const parser = new Parser()
-      try {
-        parser.setLanguage(grammar)
-        parsers.set(config.name, parser)
-      }
-      catch (error) {
-        console.warn(`Failed to initialize parser for ${config.name}:`, error)
-      }
+    if (customLanguages.has(config.name)) continue
+
+    const grammar = grammars[config.parserName] ?? GRAMMARS[config.parserName] ?? loadPackagedGrammar(config, loadPackage)
+    if (!grammar) continue
+
+    const parser = new Parser()
+    try {
+      parser.setLanguage(grammar)
+      parsers.set(config.name, parser)
+      disabledLanguages.delete(config.name)
+    }
+    catch (error) {
+      const reason = error instanceof Error ? error.message : String(error)
+      parsers.delete(config.name)
+      disabledLanguages.set(config.name, reason)
+      getLogger().debug(`Disabled ${config.name}: its grammar is incompatible with this tree-sitter version (${reason})`)
     }
   }
 }
 
+function loadPackagedGrammar(config: LanguageConfig, loadPackage: (packageName: string) => unknown): TreeSitterLanguage | undefined {
+  const packageName = GRAMMAR_PACKAGES[config.parserName]
+  if (!packageName || !PACKAGED_GRAMMARS.has(config.parserName)) return undefined
+
+  try {
+    const loaded = loadPackage(packageName) as { default?: TreeSitterLanguage } & TreeSitterLanguage
+    const grammar = loaded.default ?? loaded
+    packagedGrammars.set(config.parserName, grammar)
+    return grammar
+  }
+  catch (error) {
+    const reason = `cannot load ${packageName}: ${error instanceof Error ? error.message : String(error)}`
+    packagedGrammars.delete(config.parserName)
+    parsers.delete(config.name)
+    disabledLanguages.set(config.name, reason)
+    getLogger().debug(`Disabled ${config.name}: ${reason}`)
+    return undefined
+  }
+}
+
 export function getParser(language: string): Parser | undefined {
   return parsers.get(language)
 }
 
+/**
+ * Lists the built-in languages left without a parser because their grammar failed to load
+ */
+export function getDisabledLanguages(): DisabledLanguage[] {
+  return Array.from(disabledLanguages, ([name, reason]) => ({ name, reason }))
+}
+
+export function getDisabledReason(language: string): string | undefined {
+  return disabledLanguages.get(language)
+}
+
+export function getGrammar(language: string): TreeSitterLanguage | undefined {
+  const config = getLanguageByName(language)
+  return config && !disabledLanguages.has(config.name)
+    ? GRAMMARS[config.parserName] ?? packagedGrammars.get(config.parserName)
+    : undefined
+}
+
 export function getLanguageByExtension(extension: string): LanguageConfig | undefined {
   return LANGUAGE_CONFIGS.find(config =>
     config.extensions.includes(extension.toLowerCase()),
   )
 }
 
+// Symbol kinds the built-in languages produce; custom grammars map their node types onto these
+const SYMBOL_KINDS = new Set(Object.values(DEFINITION_KINDS).flatMap(kinds => Object.values(kinds)))
+const CALLABLE_KINDS = new Set(['function', 'method', 'constructor'])
+const CONTAINER_KINDS = new Set(['class', 'struct', 'interface', 'trait', 'protocol', 'extension', 'enum', 'record', 'object', 'module'])
+
+const requireGrammar = createRequire(import.meta.url)
+const customLanguages = new Map<string, GrammarRegistration>()
+
+/**
+ * Loads a compiled grammar from a native addon (`.node`) or a package directory, the way
+ * `tree-sitter-*` packages are loaded
+ */
+export function loadGrammar(path: string): TreeSitterLanguage {
+  let loaded: unknown
+  try {
+    loaded = requireGrammar(resolve(path))
+  }
+  catch (error) {
+    throw createError('CONFIG_ERROR', `Cannot load grammar ${path}: ${error instanceof Error ? error.message : String(error)}`, { path })
+  }
+
+  const grammar = (loaded as { default?: unknown })?.default ?? loaded
+  if (typeof grammar !== 'object' || grammar === null || !('language' in grammar)) {
+    throw createError('CONFIG_ERROR', `${path} does not export a tree-sitter grammar`, { path })
+  }
+  return grammar
+}
+
+/**
+ * Adds a language whose grammar is supplied at runtime, so it can be indexed, searched and outlined like a
+ * built-in one. `definitionKinds` maps grammar node types to symbol kinds; callables become functions and
+ * container kinds become classes in the index. Registering a custom name again replaces it. Grammars built
+ * for an ABI this tree-sitter cannot read are rejected here rather than failing on the first parse.
+ */
+export function registerLanguage(registration: GrammarRegistration): LanguageConfig {
+  const name = registration.name.trim().toLowerCase()
+  if (!/^[a-z][a-z0-9_+-]*$/.test(name)) {
+    throw createError('CONFIG_ERROR', `Invalid language name: ${registration.name}`, { language: registration.name })
+  }
+  if (getLanguageByName(name) && !customLanguages.has(name)) {
+    throw createError('CONFIG_ERROR', `Language ${name} is built in and cannot be replaced`, { language: name })
+  }
+
+  const extensions = registration.extensions.map(ext => (ext.startsWith('.') ? ext : `.${ext}`).toLowerCase())
+  const claimed = extensions.find(ext => getLanguageByExtension(ext) && getLanguageByExtension(ext)!.name !== name)
+  if (claimed) {
+    throw createError('CONFIG_ERROR', `Extension ${claimed} already belongs to ${getLanguageByExtension(claimed)!.name}`, { language: name, extension: claimed })
+  }
+
+  const kinds = Object.entries(registration.definitionKinds)
+  const unknownKind = kinds.find(([, kind]) => !SYMBOL_KINDS.has(kind))
+  if (unknownKind) {
+    throw createError('CONFIG_ERROR', `Unknown symbol kind "${unknownKind[1]}" for ${unknownKind[0]}. Supported kinds: ${Array.from(SYMBOL_KINDS).sort().join(', ')}`, { language: name })
+  }
+
+  const parser = new Parser()
+  try {
+    parser.setLanguage(registration.grammar)
+  }
+  catch (error) {
+    throw createError('CONFIG_ERROR', `Grammar for ${name} is incompatible with this tree-sitter version: ${error instanceof Error ? error.message : String(error)}`, { language: name })
+  }
+
+  const config: LanguageConfig = {
+    name,
+    extensions,
+    parserName: name,
+    functionTypes: kinds.filter(([, kind]) => CALLABLE_KINDS.has(kind)).map(([type]) => type),
+    classTypes: kinds.filter(([, kind]) => CONTAINER_KINDS.has(kind)).map(([type]) => type),
+    branchTypes: [...registration.branchTypes ?? []],
+    importTypes: [],
+    definitionKinds: Object.fromEntries(kinds),
+  }
+  const index = LANGUAGE_CONFIGS.findIndex(existing => existing.name === name)
+  LANGUAGE_CONFIGS.splice(index === -1 ? LANGUAGE_CONFIGS.length : index, index === -1 ? 0 : 1, config)
+  customLanguages.set(name, registration)
+  GRAMMARS[name] = registration.grammar
+  parsers.set(name, parser)
+  return config
+}
+
+/**
+ * Captures the registered grammars and overrides so a parse worker can rebuild them with
+ * `restoreLanguageSetup`. Grammars registered without a `path` cannot be sent and are left out.
+ */
+export function getLanguageSetup(): LanguageSetup {
+  return {
+    grammars: Array.from(customLanguages.values())
+      .filter(registration => registration.path !== undefined)
+      .map(({ name, extensions, definitionKinds, branchTypes, path }) => ({ name, extensions, definitionKinds, branchTypes, path })),
+    overrides: Array.from(languageOverrides, ([root, entries]) => [
+      root,
+      Object.fromEntries(entries.map(entry => [entry.pattern, entry.language.name])),
+    ]),
+  }
+}
+
+export function restoreLanguageSetup(setup: LanguageSetup): void {
+  for (const registration of setup.grammars) {
+    registerLanguage({ ...registration, grammar: loadGrammar(registration.path!) })
+  }
+  for (const [root, overrides] of setup.overrides) {
+    setLanguageOverrides(root, overrides)
+  }
+}
+
+/**
+ * Maps globs relative to a project root to language names, taking precedence over extensions for files
+ * beneath it. An empty map removes the root's overrides.
+ */
+export function setLanguageOverrides(root: string, overrides: Record<string, string>): void {
+  const entries = Object.entries(overrides).map(([pattern, name]) => ({
+    pattern,
+    glob: compileGlob(pattern),
+    language: resolveLanguageNames([name])[0]!,
+  }))
+
+  if (entries.length > 0) {
+    languageOverrides.set(resolve(root), entries)
+  }
+  else {
+    languageOverrides.delete(resolve(root))
+  }
+}
+
+/**
+ * Resolves a file's language and how it was chosen: a config override, the extension, the `#!` line,
+ * then content heuristics for extensions shared by several grammars (`.h`). Content may be passed lazily
+ * so callers only read the file when the path alone is not enough.
+ */
+export function resolveFileLanguage(filePath: string, content?: string | (() => string)): LanguageResolution | undefined {
+  const override = findLanguageOverride(filePath)
+  if (override) return { language: override, source: 'override' }
+
+  const extension = extname(filePath).toLowerCase()
+  const heuristic = CONTENT_HEURISTICS[extension]
+  const byExtension = getLanguageByExtension(extension)
+  if (byExtension && !heuristic) return { language: byExtension, source: 'extension' }
+
+  const text = typeof content === 'function' ? content() : content
+  if (text === undefined) return byExtension && { language: byExtension, source: 'extension' }
+
+  const byShebang = getLanguageByShebang(text)
+  if (byShebang) return { language: byShebang, source: 'shebang' }
+
+  const guessed = heuristic ? getLanguageByName(heuristic(text)) : undefined
+  return guessed && { language: guessed, source: 'heuristic' }
+}
+
+export function getFileLanguage(filePath: string, content?: string): LanguageConfig | undefined {
+  return resolveFileLanguage(filePath, content)?.language
+}
+
+function findLanguageOverride(filePath: string): LanguageConfig | undefined {
+  for (const [root, overrides] of languageOverrides) {
+    const relativePath = relative(root, resolve(filePath))
+    if (!relativePath || relativePath.startsWith('..') || isAbsolute(relativePath)) continue
+
+    const portablePath = relativePath.split(sep).join('/')
+    const match = overrides.find(override => override.glob.test(portablePath))
+    if (match) return match.language
+  }
+  return undefined
+}
+
+/**
+ * `#!/bin/sh`, `#!/usr/bin/env bash` and `#!/usr/bin/env -S python3 -u` name the interpreter directly
+ * or after `env` and its options; version suffixes (`python3.12`) are ignored
+ */
+export function getLanguageByShebang(content: string): LanguageConfig | undefined {
+  const line = /^#![^\n]*/.exec(content)?.[0]
+  if (!line) return undefined
+
+  const words = line.slice(2).trim().split(/\s+/)
+  let interpreter = basename(words[0] ?? '')
+  if (interpreter === 'env') {
+    interpreter = basename(words.slice(1).find(word => !word.startsWith('-') && !word.includes('=')) ?? '')
+  }
+
+  const name = SHEBANG_INTERPRETERS[interpreter.replace(/[\d.]+$/, '')]
+  return name ? getLanguageByName(name) : undefined
+}
+
 export function getLanguageByName(name: string): LanguageConfig | undefined {
   return LANGUAGE_CONFIGS.find(config => config.name === name)
 }
 
+/**
+ * Resolves user-supplied language names (case-insensitive) to their configs. A language brings its
+ * dialects along, so `typescript` also selects `tsx`; each name's own config comes first.
+ */
+export function resolveLanguageNames(names: string[]): LanguageConfig[] {
+  return names.flatMap((name) => {
+    const config = getLanguageByName(name.trim().toLowerCase())
+    if (!config) {
+      throw createError('SEARCH_ERROR', `Unknown language: ${name}. Supported languages: ${getSupportedLanguageNames().join(', ')}`, { language: name })
+    }
+    return [config, ...LANGUAGE_CONFIGS.filter(dialect => dialect.dialectOf === config.name)]
+  })
+}
+
+/**
+ * Whether a file's language is among the selected names, directly or as a dialect of one
+ */
+export function isLanguageSelected(language: LanguageConfig, names: string[]): boolean {
+  return names.includes(language.name) || (language.dialectOf !== undefined && names.includes(language.dialectOf))
+}
+
+// `calc_test.go`, `test_calc.py`, `calc_spec.rb`; `.test.`/`.spec.` names and test directories are in TEST_PATTERNS
+const TEST_FILE_NAME = /(?:_test\.\w+|^test_\w*\.py|^conftest\.py|_spec\.rb)$/
+const RUST_TEST_ATTRIBUTE = /^#\[(?:[\w:]+::)?test\b|^#\[cfg\(test\)\]/
+
+/**
+ * Whether a file is test code as a whole, going by its name and directories
+ */
+export function isTestPath(relativePath: string): boolean {
+  const path = `/${relativePath.split(sep).join('/')}`
+  return isTestFile(path) || TEST_FILE_NAME.test(basename(path))
+}
+
+/**
+ * Whether files of a language can hold tests outside test paths, like Rust's `#[cfg(test)]` modules
+ */
+export function hasInlineTests(language: LanguageConfig): boolean {
+  return language.name === PARSER_NAMES.RUST
+}
+
+/**
+ * Whether a node sits in a test inside an otherwise non-test file: a Rust function marked `#[test]`
+ * (or `#[tokio::test]` and the like) or a module marked `#[cfg(test)]`
+ */
+export function isInlineTestCode(node: Parser.SyntaxNode, language: LanguageConfig): boolean {
+  if (!hasInlineTests(language)) return false
+
+  for (let current: Parser.SyntaxNode | null = node; current; current = current.parent) {
+    if (current.type !== 'function_item' && current.type !== 'mod_item') continue
+    for (let sibling = current.previousNamedSibling; sibling?.type === 'attribute_item'; sibling = sibling.previousNamedSibling) {
+      if (RUST_TEST_ATTRIBUTE.test(sibling.text)) return true
+    }
+  }
+  return false
+}
+
+/**
+ * Modifiers written on a declaration (`pub async fn`, `public static int`): the language's modifier keywords
+ * before its name, Python decorators such as `@staticmethod`, a JavaScript `export` around it, and the
+ * `async` of a function assigned to a variable
+ */
+export function getDeclarationModifiers(node: Parser.SyntaxNode, nameNode: Parser.SyntaxNode, language: LanguageConfig): DeclarationModifier[] {
+  const keywords = language.modifierKeywords ?? {}
+  const words: string[] = []
+
+  for (const child of node.children) {
+    if (child.endIndex > nameNode.startIndex) break
+    words.push(...child.text.match(/\w+/g) ?? [])
+  }
+  if (node.parent?.type === 'decorated_definition') {
+    for (const decorator of node.parent.namedChildren.filter(child => child.type === 'decorator')) {
+      words.push(...decorator.text.match(/\w+/g) ?? [])
+    }
+  }
+  // `export function f`, and `export const f = ...` one level further out around the declarator
+  if (node.parent?.type === 'export_statement' || node.parent?.parent?.type === 'export_statement') {
+    words.push('export')
+  }
+  if (node.type === 'variable_declarator' && node.childForFieldName('value')?.child(0)?.type === 'async') {
+    words.push('async')
+  }
+
+  return [...new Set(words.filter(word => Object.hasOwn(keywords, word)).map(word => keywords[word]!))]
+}
+
+export function getSupportedLanguageNames(): string[] {
+  return LANGUAGE_CONFIGS.map(config => config.name)
+}
+
+// npm packages of the built-in grammars, keyed by parser name
+const GRAMMAR_PACKAGES: Record<string, string> = {
+  [PARSER_NAMES.JAVASCRIPT]: 'tree-sitter-javascript',
+  [PARSER_NAMES.TYPESCRIPT]: 'tree-sitter-typescript',
+  [PARSER_NAMES.TSX]: 'tree-sitter-typescript',
+  [PARSER_NAMES.PYTHON]: 'tree-sitter-python',
+  [PARSER_NAMES.GO]: 'tree-sitter-go',
+  [PARSER_NAMES.RUST]: 'tree-sitter-rust',
+  [PARSER_NAMES.JAVA]: 'tree-sitter-java',
+  [PARSER_NAMES.C]: 'tree-sitter-c',
+  [PARSER_NAMES.CPP]: 'tree-sitter-cpp',
+  [PARSER_NAMES.RUBY]: 'tree-sitter-ruby',
+  [PARSER_NAMES.CSHARP]: 'tree-sitter-c-sharp',
+  [PARSER_NAMES.PHP]: 'tree-sitter-php',
+  [PARSER_NAMES.HTML]: 'tree-sitter-html',
+  [PARSER_NAMES.KOTLIN]: 'tree-sitter-kotlin',
+  [PARSER_NAMES.SWIFT]: 'tree-sitter-swift',
+  [PARSER_NAMES.SCALA]: 'tree-sitter-scala',
+  [PARSER_NAMES.ELIXIR]: 'tree-sitter-elixir',
+  [PARSER_NAMES.BASH]: 'tree-sitter-bash',
+  [PARSER_NAMES.JSON]: 'tree-sitter-json',
+  [PARSER_NAMES.YAML]: 'tree-sitter-yaml',
+}
+
+const grammarVersions = new Map<string, string | null>()
+
+/**
+ * Lists the languages whose parser is loaded, with the version of the grammar package behind each.
+ * Custom grammars come from no package, so their version is null.
+ */
+export function getLoadedLanguages(): LoadedLanguage[] {
+  return LANGUAGE_CONFIGS
+    .filter(config => parsers.has(config.name))
+    .map(config => ({
+      name: config.name,
+      extensions: config.extensions,
+      grammarVersion: customLanguages.has(config.name) ? null : getGrammarVersion(config.parserName),
+      custom: customLanguages.has(config.name),
+    }))
+}
+
+function getGrammarVersion(parserName: string): string | null {
+  const packageName = GRAMMAR_PACKAGES[parserName]
+  if (!packageName) return null
+
+  if (!grammarVersions.has(packageName)) {
+    let version: string | null
+    try {
+      version = (requireGrammar(`${packageName}/package.json`) as { version?: string }).version ?? null
+    }
+    catch {
+      // Packages whose exports hide package.json
+      version = null
+    }
+    grammarVersions.set(packageName, version)
+  }
+  return grammarVersions.get(packageName)!
+}
+
+type ImportReader = (node: Parser.SyntaxNode) => Omit<ImportSpecifier, 'line'>[]
+
+const IMPORT_READERS: Record<string, ImportReader> = {
+  [PARSER_NAMES.JAVASCRIPT]: readScriptImport,
+  [PARSER_NAMES.TYPESCRIPT]: readScriptImport,
+  [PARSER_NAMES.TSX]: readScriptImport,
+  [PARSER_NAMES.PYTHON]: readPythonImport,
+  [PARSER_NAMES.RUST]: readRustImport,
+}
+
+/**
+ * Collects the modules a file imports, using the language's import node types.
+ * Languages without a reader (or without import types) return no imports.
+ */
+export function extractImportSpecifiers(root: Parser.SyntaxNode, language: LanguageConfig): ImportSpecifier[] {
+  const reader = IMPORT_READERS[language.name]
+  if (!reader || language.importTypes.length === 0) return []
+
+  const imports: ImportSpecifier[] = []
+  const visit = (node: Parser.SyntaxNode) => {
+    if (language.importTypes.includes(node.type)) {
+      for (const specifier of reader(node)) {
+        imports.push({ ...specifier, line: node.startPosition.row + 1 })
+      }
+    }
+    for (const child of node.namedChildren) {
+      visit(child)
+    }
+  }
+  visit(root)
+  return imports
+}
+
+/**
+ * `import ... from './x'`, `export ... from './x'`, `require('./x')` and `import('./x')`, with the local
+ * names an `import` or a `const ... = require()` binds
+ */
+function readScriptImport(node: Parser.SyntaxNode): Omit<ImportSpecifier, 'line'>[] {
+  if (node.type === 'call_expression') {
+    const callee = node.childForFieldName('function')
+    if (!callee || (callee.text !== 'require' && callee.type !== 'import')) return []
+    const argument = node.childForFieldName('arguments')?.namedChild(0)
+    const binding = node.parent?.type === 'variable_declarator' ? node.parent.childForFieldName('name') : null
+    return argument?.type === 'string' ? [{ module: unquote(argument.text), names: binding ? readBindingNames(binding) : [] }] : []
+  }
+
+  const source = node.childForFieldName('source')
+  const clause = node.type === 'import_statement' ? node.namedChildren.find(child => child.type === 'import_clause') : undefined
+  return source ? [{ module: unquote(source.text), names: clause ? readBindingNames(clause) : [] }] : []
+}
+
+// Default, namespace and named imports (`a`, `* as b`, `{ c as d }`), and destructured requires
+function readBindingNames(node: Parser.SyntaxNode): string[] {
+  switch (node.type) {
+    case 'identifier':
+    case 'shorthand_property_identifier_pattern':
+      return [node.text]
+    case 'import_specifier': {
+      const local = node.childForFieldName('alias') ?? node.childForFieldName('name')
+      return local ? [local.text] : []
+    }
+    case 'pair_pattern': {
+      const value = node.childForFieldName('value')
+      return value ? readBindingNames(value) : []
+    }
+    default:
+      return node.namedChildren.flatMap(readBindingNames)
+  }
+}
+
+/**
+ * `import a.b` and `from .a import b`; imported names are kept because they may be submodules
+ */
+function readPythonImport(node: Parser.SyntaxNode): Omit<ImportSpecifier, 'line'>[] {
+  const readName = (child: Parser.SyntaxNode) =>
+    child.type === 'aliased_import' ? child.childForFieldName('name')?.text : child.text
+
+  if (node.type === 'import_statement') {
+    return node.namedChildren
+      .map(readName)
+      .filter((name): name is string => Boolean(name))
+      .map(module => ({ module, names: [] }))
+  }
+
+  const moduleNode = node.childForFieldName('module_name')
+  if (!moduleNode) return []
+
+  const names = node.namedChildren
+    .filter(child => child.startIndex !== moduleNode.startIndex && (child.type === 'dotted_name' || child.type === 'aliased_import'))
+    .map(readName)
+    .filter((name): name is string => Boolean(name))
+  return [{ module: moduleNode.text, names }]
+}
+
+/**
+ * `use a::b::{c, d}` expands to one path per leaf; `mod name;` declares a file module
+ */
+function readRustImport(node: Parser.SyntaxNode): Omit<ImportSpecifier, 'line'>[] {
+  if (node.type === 'mod_item') {
+    const name = node.childForFieldName('name')
+    return name && !node.childForFieldName('body') ? [{ module: `self::${name.text}`, names: [name.text] }] : []
+  }
+
+  const argument = node.childForFieldName('argument')
+  if (!argument) return []
+  const tree = argument.text.replace(/\s+/g, ' ').replace(/\s*(::|[{},])\s*/g, '$1')
+  return expandUseTree(tree)
+}
+
+// Each leaf binds its alias or last segment; a glob binds nothing by name
+function expandUseTree(tree: string, prefix = ''): Omit<ImportSpecifier, 'line'>[] {
+  const open = tree.indexOf('{')
+  if (open === -1) {
+    const alias = / as (\w+)$/.exec(tree)?.[1]
+    const glob = tree.endsWith('*')
+    const path = tree.replace(/ as \w+$/, '').replace(/::\*$/, '').replace(/::self$/, '')
+    const module = path === 'self' ? prefix : prefix ? `${prefix}::${path}` : path
+    if (!module) return []
+    return [{ module, names: glob ? [] : [alias ?? module.split('::').at(-1)!] }]
+  }
+
+  const base = tree.slice(0, open).replace(/::$/, '')
+  const nextPrefix = [prefix, base].filter(Boolean).join('::')
+  return splitTopLevel(tree.slice(open + 1, tree.lastIndexOf('}')))
+    .flatMap(item => expandUseTree(item, nextPrefix))
+}
+
+function splitTopLevel(list: string): string[] {
+  const items: string[] = []
+  let depth = 0
+  let current = ''
+  for (const char of list) {
+    if (char === '{') depth++
+    if (char === '}') depth--
+    if (char === ',' && depth === 0) {
+      items.push(current)
+      current = ''
+      continue
+    }
+    current += char
+  }
+  items.push(current)
+  return items.filter(item => item.length > 0)
+}
+
+function unquote(text: string): string {
+  return text.replace(/^['"`]|['"`]$/g, '')
+}
+
 initializeParsers()
\ No newline at end of file
@@ -0,0 +1,57 @@
+/**
+ * Tree node serialization - converts parsed trees to plain data for caching and worker transfer
+ */
+
+import { getFileLanguage } from './languages.js'
+import { attachTree } from './tree-cache.js'
+import type { TreeNode } from '../types/core.js'
+
+export type SerializedNode = Omit<TreeNode, 'rawNode' | 'tree' | 'parent' | 'children' | 'parameters'> & {
+  children?: SerializedNode[]
+  parameters?: SerializedNode[]
+}
+
+/**
+ * Strips native tree-sitter references and parent links so a node can be cloned or written to disk
+ */
+export function serializeNode(node: TreeNode): SerializedNode {
+  const serialized: SerializedNode = {
+    id: node.id,
+    type: node.type,
+    name: node.name,
+    path: node.path,
+    startLine: node.startLine,
+    endLine: node.endLine,
+    startColumn: node.startColumn,
+    endColumn: node.endColumn,
+    content: node.content,
+    skipped: node.skipped,
+    skipReason: node.skipReason,
+    encoding: node.encoding,
+    parseErrors: node.parseErrors,
+  }
+  if (node.children) serialized.children = node.children.map(serializeNode)
+  if (node.parameters) serialized.parameters = node.parameters.map(serializeNode)
+  return serialized
+}
+
+function restoreNode(serialized: SerializedNode): TreeNode {
+  const { children, parameters, ...rest } = serialized
+  const node: TreeNode = { ...rest }
+  if (children) node.children = children.map(restoreNode)
+  if (parameters) node.parameters = parameters.map(restoreNode)
+  return node
+}
+
+/**
+ * Restores a serialized file node; its syntax tree is re-parsed from content on first access
+ */
+export function restoreFileNode(serialized: SerializedNode): TreeNode {
+  const fileNode = restoreNode(serialized)
+
+  if (getFileLanguage(fileNode.path, fileNode.content) && !fileNode.skipped && fileNode.content !== undefined) {
+    attachTree(fileNode)
+  }
+
+  return fileNode
+}
\ No newline at end of file
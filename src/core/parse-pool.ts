@@ -0,0 +1,155 @@
+/**
+ * Parallel parsing - spreads file parsing across worker threads
+ */
+
+import { Worker } from 'worker_threads'
+import { availableParallelism, cpus } from 'os'
+import { parseFile } from './parser.js'
+import { getLanguageSetup } from './languages.js'
+import { restoreFileNode, type SerializedNode } from './node-serialization.js'
+import { getLogger } from '../utils/logger.js'
+import { createCancelledError, throwIfCancelled } from '../utils/cancellation.js'
+import type { TreeNode } from '../types/core.js'
+
+export interface ParseWorkerOutput {
+  filePath: string
+  file?: SerializedNode
+  error?: string
+}
+
+// Workers post each file as it finishes so progress can be reported, then `null` once the batch is done
+export type ParseWorkerMessage = ParseWorkerOutput | null
+
+// Running from TypeScript sources (tsx, vitest) needs the worker to load through tsx as well
+const RUNNING_FROM_SOURCE = import.meta.url.endsWith('.ts')
+const WORKER_URL = new URL(RUNNING_FROM_SOURCE ? './parse-worker.ts' : './parse-worker.js', import.meta.url)
+const WORKER_EXEC_ARGV = RUNNING_FROM_SOURCE ? ['--import', 'tsx'] : []
+
+let parseThreads: number | undefined
+
+/**
+ * Sets the default worker count (used by the --threads CLI option)
+ */
+export function setParseThreads(threads: number): void {
+  parseThreads = Math.max(1, Math.floor(threads))
+}
+
+export function getParseThreads(): number {
+  if (parseThreads !== undefined) return parseThreads
+  return typeof availableParallelism === 'function' ? availableParallelism() : cpus().length
+}
+
+/**
+ * Parses files on the current thread, skipping files that fail to parse; `signal` is checked before each file
+ * and `onFile` called after it
+ */
+export async function parseFilesSequentially(
+  filePaths: string[],
+  signal?: AbortSignal,
+  onFile?: (filePath: string) => void,
+): Promise<Map<string, TreeNode>> {
+  const logger = getLogger()
+  const results = new Map<string, TreeNode>()
+
+  for (const filePath of filePaths) {
+    throwIfCancelled(signal)
+    try {
+      results.set(filePath, await parseFile(filePath))
+    }
+    catch (error) {
+      logger.warn(`Failed to parse ${filePath}:`, error)
+    }
+    onFile?.(filePath)
+  }
+
+  return results
+}
+
+/**
+ * Parses files across worker threads, each with its own parser instances and a copy of the registered
+ * grammars and language overrides.
+ * Results keep the input order; syntax trees are rebuilt lazily on this thread when needed.
+ * Aborting `signal`, or any worker failing, terminates the workers; `onFile` is called as each worker
+ * finishes a file.
+ */
+export async function parseFilesInParallel(
+  filePaths: string[],
+  threads: number,
+  signal?: AbortSignal,
+  onFile?: (filePath: string) => void,
+): Promise<Map<string, TreeNode>> {
+  const logger = getLogger()
+  const workerCount = Math.max(1, Math.min(threads, filePaths.length))
+  const batches: string[][] = Array.from({ length: workerCount }, () => [])
+
+  filePaths.forEach((filePath, index) => {
+    batches[index % workerCount]!.push(filePath)
+  })
+
+  throwIfCancelled(signal)
+  // One abort stops every worker, so a failed worker does not leave its siblings parsing for nothing
+  const controller = new AbortController()
+  const stopWorkers = () => controller.abort()
+  signal?.addEventListener('abort', stopWorkers, { once: true })
+
+  // The first failure is what the caller sees, not the cancellations it causes in the other workers
+  let failure: unknown
+  let outputs: ParseWorkerOutput[]
+  try {
+    outputs = (await Promise.all(batches.map(batch => runWorker(batch, controller.signal, onFile).catch((error: unknown) => {
+      failure ??= error
+      controller.abort()
+      throw failure
+    })))).flat()
+  }
+  finally {
+    signal?.removeEventListener('abort', stopWorkers)
+  }
+  const byPath = new Map(outputs.map(output => [output.filePath, output]))
+  const results = new Map<string, TreeNode>()
+
+  for (const filePath of filePaths) {
+    const output = byPath.get(filePath)
+    if (output?.file) {
+      results.set(filePath, restoreFileNode(output.file))
+    }
+    else {
+      logger.warn(`Failed to parse ${filePath}:`, output?.error)
+    }
+  }
+
+  return results
+}
+
+function runWorker(files: string[], signal?: AbortSignal, onFile?: (filePath: string) => void): Promise<ParseWorkerOutput[]> {
+  return new Promise((resolve, reject) => {
+    if (files.length === 0) {
+      resolve([])
+      return
+    }
+
+    const worker = new Worker(WORKER_URL, { workerData: { files, languages: getLanguageSetup() }, execArgv: WORKER_EXEC_ARGV })
+    const cancel = () => {
+      reject(createCancelledError())
+      worker.terminate()
+    }
+    signal?.addEventListener('abort', cancel, { once: true })
+
+    const outputs: ParseWorkerOutput[] = []
+    worker.on('message', (message: ParseWorkerMessage) => {
+      if (message) {
+        outputs.push(message)
+        onFile?.(message.filePath)
+        return
+      }
+      signal?.removeEventListener('abort', cancel)
+      resolve(outputs)
+      worker.terminate()
+    })
+    worker.once('error', reject)
+    worker.once('exit', (code) => {
+      signal?.removeEventListener('abort', cancel)
+      if (code !== 0) reject(new Error(`Parse worker exited with code ${code}`))
+    })
+  })
+}
\ No newline at end of file
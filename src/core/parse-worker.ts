@@ -0,0 +1,27 @@
+/**
+ * Parse worker - parses a batch of files with this thread's own tree-sitter parsers
+ */
+
+import { parentPort, workerData } from 'worker_threads'
+import { parseFile } from './parser.js'
+import { serializeNode } from './node-serialization.js'
+import { restoreLanguageSetup } from './languages.js'
+import type { ParseWorkerMessage } from './parse-pool.js'
+import type { LanguageSetup } from '../types/core.js'
+
+function post(message: ParseWorkerMessage): void {
+  parentPort?.postMessage(message)
+}
+
+restoreLanguageSetup(workerData.languages as LanguageSetup)
+
+for (const filePath of workerData.files as string[]) {
+  try {
+    post({ filePath, file: serializeNode(await parseFile(filePath)) })
+  }
+  catch (error) {
+    post({ filePath, error: String(error) })
+  }
+}
+
+post(null)
\ No newline at end of file
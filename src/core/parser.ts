@@ -3,54 +3,77 @@
  */
 
 import Parser from 'tree-sitter'
-import { readFileSync, statSync } from 'fs'
-import { extname } from 'path'
+import { statSync } from 'fs'
 import { createError } from '../utils/errors.js'
 import { getLogger } from '../utils/logger.js'
-import { getParser, getLanguageByExtension } from './languages.js'
+import { readSourceFile } from '../utils/encoding.js'
+import { getDisabledReason, getParser, getFileLanguage, getLanguageByExtension, resolveFileLanguage } from './languages.js'
+import { attachTree, borrowTree, getResidentTree } from './tree-cache.js'
+import { getElixirDefinitionKind, getElixirDefinitionName, getReceiverTypeName, getTypeParameterSuffix, isPrototypeDeclarator } from './definitions.js'
 import { PARSER_LIMITS, PARSER_NAMES } from '../constants/parsers.js'
-import type { TreeNode, LanguageConfig } from '../types/core.js'
+import type { TreeNode, LanguageConfig, ParseErrorLocation, SourceEncoding } from '../types/core.js'
 
 /**
- * Parses a file and extracts its tree structure
+ * Parses a file and extracts its tree structure. Given the file's previous node, its syntax tree is
+ * reused so only the edited region is re-parsed.
  */
-export async function parseFile(filePath: string): Promise<TreeNode> {
+export async function parseFile(filePath: string, previous?: TreeNode): Promise<TreeNode> {
   const logger = getLogger()
 
   try {
-    const extension = extname(filePath)
-    const languageConfig = getLanguageByExtension(extension)
-
-    if (languageConfig?.name === PARSER_NAMES.KOTLIN) {
+    if (resolveFileLanguage(filePath)?.language.name === PARSER_NAMES.KOTLIN) {
       const fileSize = statSync(filePath).size
       if (fileSize >= PARSER_LIMITS.KOTLIN_MAX_FILE_SIZE) {
-        const rawContent = readFileSync(filePath, 'utf-8')
-        const content = truncateLongLines(rawContent, 1000)
+        const source = readSourceFile(filePath)
+        const content = truncateLongLines(source.content)
         logger.warn(`Kotlin file exceeds size limit (${fileSize} bytes > ${PARSER_LIMITS.KOTLIN_MAX_FILE_SIZE}): ${filePath}`)
         return {
           id: `file-${Date.now()}`,
           type: 'file',
           path: filePath,
           content,
+          ...withEncoding(source.encoding),
           skipped: true,
           skipReason: `File too large for Kotlin parser (${fileSize} bytes exceeds ${PARSER_LIMITS.KOTLIN_MAX_FILE_SIZE} byte limit)`,
         }
       }
     }
 
-    const rawContent = readFileSync(filePath, 'utf-8')
-    const content = truncateLongLines(rawContent, 1000)
+    const source = readSourceFile(filePath)
+    const content = truncateLongLines(source.content)
+    const resolution = resolveFileLanguage(filePath, content)
+    const fileLanguage = resolution?.language
+    if (resolution && resolution.source !== 'extension') {
+      logger.debug(`Parsing ${filePath} as ${resolution.language.name} (${resolution.source})`)
+    }
+
+    if (!fileLanguage) {
+      return {
+        id: `file-${Date.now()}`,
+        type: 'file',
+        path: filePath,
+        content,
+        ...withEncoding(source.encoding),
+      }
+    }
 
-    if (!languageConfig) {
+    const disabled = getDisabledReason(fileLanguage.name)
+    if (disabled) {
       return {
         id: `file-${Date.now()}`,
         type: 'file',
         path: filePath,
         content,
+        ...withEncoding(source.encoding),
+        skipped: true,
+        skipReason: `${fileLanguage.name} is disabled: ${disabled}`,
       }
     }
 
-    return parseContent(content, filePath, languageConfig)
+    const fileNode = previous
+      ? reparseContent(previous, content, fileLanguage)
+      : parseContent(content, filePath, fileLanguage)
+    return Object.assign(fileNode, withEncoding(source.encoding))
   }
   catch (error) {
     logger.warn(`Failed to parse ${filePath}:`, error)
@@ -59,11 +82,100 @@ export async function parseFile(filePath: string): Promise<TreeNode> {
 }
 
 /**
- * Parses content string and extracts tree elements
+ * Parses an in-memory buffer that belongs to no project, such as an unsaved editor buffer. It is named
+ * `stdin` plus the language's first extension, so later lookups by path resolve to the same language.
+ */
+export function parseBuffer(content: string, language: LanguageConfig): TreeNode {
+  const fileNode = parseContent(truncateLongLines(content), `stdin${language.extensions[0] ?? ''}`, language)
+  const disabled = getDisabledReason(language.name)
+  return disabled ? { ...fileNode, skipped: true, skipReason: `${language.name} is disabled: ${disabled}` } : fileNode
+}
+
+/**
+ * Lists a file node and every element beneath it, the nodes name searches run over
+ */
+export function extractAllNodes(fileNode: TreeNode): TreeNode[] {
+  const nodes: TreeNode[] = []
+
+  function traverse(node: TreeNode) {
+    nodes.push(node)
+    if (node.children) {
+      node.children.forEach(traverse)
+    }
+  }
+
+  traverse(fileNode)
+  return nodes
+}
+
+// Plain UTF-8 is the default, so only other encodings are recorded on the file node
+function withEncoding(encoding: SourceEncoding): Pick<TreeNode, 'encoding'> {
+  return encoding === 'utf-8' ? {} : { encoding }
+}
+
+/**
+ * Re-parses changed content using the previous node's tree as the base. The edit is found by diffing
+ * the old and new content; when the previous tree is not in memory the content is parsed from scratch.
+ */
+export function reparseContent(previous: TreeNode, content: string, language?: LanguageConfig): TreeNode {
+  if (!getResidentTree(previous) || previous.content === undefined) {
+    return parseContent(content, previous.path, language)
+  }
+
+  return borrowTree(previous, (tree) => {
+    tree!.edit(computeContentEdit(previous.content!, content))
+    return parseContent(content, previous.path, language, tree)
+  })
+}
+
+/**
+ * Describes the change between two versions of a text as one replaced range, from the first to the last
+ * differing character. Indices and columns count UTF-16 code units, like the trees parsed from strings.
  */
-export function parseContent(content: string, filePath: string, language?: LanguageConfig): TreeNode {
-  const extension = extname(filePath)
-  const languageConfig = language || getLanguageByExtension(extension)
+export function computeContentEdit(oldContent: string, newContent: string): Parser.Edit {
+  const shorter = Math.min(oldContent.length, newContent.length)
+  let start = 0
+  while (start < shorter && oldContent.charCodeAt(start) === newContent.charCodeAt(start)) {
+    start++
+  }
+
+  let oldEnd = oldContent.length
+  let newEnd = newContent.length
+  while (oldEnd > start && newEnd > start && oldContent.charCodeAt(oldEnd - 1) === newContent.charCodeAt(newEnd - 1)) {
+    oldEnd--
+    newEnd--
+  }
+
+  return {
+    startIndex: start,
+    oldEndIndex: oldEnd,
+    newEndIndex: newEnd,
+    startPosition: getPoint(oldContent, start),
+    oldEndPosition: getPoint(oldContent, oldEnd),
+    newEndPosition: getPoint(newContent, newEnd),
+  }
+}
+
+function getPoint(content: string, index: number): Parser.Point {
+  let row = 0
+  let lineStart = 0
+  for (let i = content.indexOf('\n'); i !== -1 && i < index; i = content.indexOf('\n', i + 1)) {
+    row++
+    lineStart = i + 1
+  }
+  return { row, column: index - lineStart }
+}
+
+/**
+ * Parses content string and extracts tree elements; `previousTree` must already be edited to match the content
+ */
+export function parseContent(
+  content: string,
+  filePath: string,
+  language?: LanguageConfig,
+  previousTree?: Parser.Tree,
+): TreeNode {
+  const languageConfig = language || getFileLanguage(filePath, content)
 
   if (!languageConfig) {
     return {
@@ -77,10 +189,11 @@ export function parseContent(content: string, filePath: string, language?: Langu
   try {
     const parser = getParser(languageConfig.name)
     if (!parser) {
-      throw new Error(`Parser not available for ${languageConfig.name}`)
+      const disabled = getDisabledReason(languageConfig.name)
+      throw new Error(`Parser not available for ${languageConfig.name}${disabled ? `: ${disabled}` : ''}`)
     }
 
-    const tree = parser.parse(content)
+    const tree = parser.parse(content, previousTree)
     const rootNode = tree.rootNode
 
     const fileNode: TreeNode = {
@@ -89,10 +202,14 @@ export function parseContent(content: string, filePath: string, language?: Langu
       path: filePath,
       content,
       children: [],
-      rawNode: rootNode, // Preserve raw tree-sitter node for error detection
     }
+    // The raw tree is kept for queries and error detection, subject to the tree cache limit
+    attachTree(fileNode, tree)
 
     extractElements(rootNode, content, filePath, languageConfig, fileNode)
+    if (rootNode.hasError) {
+      fileNode.parseErrors = collectParseErrors(rootNode)
+    }
 
     return fileNode
   }
@@ -101,6 +218,28 @@ export function parseContent(content: string, filePath: string, language?: Langu
   }
 }
 
+/**
+ * Locates the outermost ERROR nodes and the MISSING tokens outside them; subtrees without errors are not visited
+ */
+export function collectParseErrors(node: Parser.SyntaxNode, errors: ParseErrorLocation[] = []): ParseErrorLocation[] {
+  if (node.type === 'ERROR' || node.isMissing) {
+    errors.push({
+      kind: node.isMissing ? 'missing' : 'error',
+      startLine: node.startPosition.row + 1,
+      endLine: node.endPosition.row + 1,
+      startColumn: node.startPosition.column,
+      endColumn: node.endPosition.column,
+      ...node.isMissing ? { expected: node.type } : {},
+    })
+    return errors
+  }
+
+  for (const child of node.children) {
+    if (child.hasError || child.isMissing) collectParseErrors(child, errors)
+  }
+  return errors
+}
+
 function extractElements(
   node: Parser.SyntaxNode,
   content: string,
@@ -108,14 +247,20 @@ function extractElements(
   language: LanguageConfig,
   parent: TreeNode,
 ): void {
-  if (language.functionTypes.includes(node.type)) {
+  // Elixir defines functions and modules with calls rather than node types of their own
+  const elixirKind = language.name === PARSER_NAMES.ELIXIR ? getElixirDefinitionKind(node) : undefined
+
+  if (language.functionTypes.includes(node.type) || elixirKind === 'function' || elixirKind === 'macro') {
     const functionNode = extractFunction(node, content, filePath)
     if (functionNode) {
+      if (node.type === 'function_declarator' && isPrototypeDeclarator(node, language)) {
+        functionNode.declarationOnly = true
+      }
       parent.children?.push(functionNode)
     }
   }
 
-  if (language.classTypes.includes(node.type)) {
+  if (language.classTypes.includes(node.type) || elixirKind === 'module') {
     const classNode = extractClass(node, content, filePath)
     if (classNode) {
       parent.children?.push(classNode)
@@ -177,24 +322,20 @@ function extractClass(node: Parser.SyntaxNode, content: string, filePath: string
 }
 
 function getFunctionName(node: Parser.SyntaxNode, content: string): string | null {
-  const nameNode = node.childForFieldName('name')
-  if (nameNode) {
-    return content.substring(nameNode.startIndex, nameNode.endIndex)
-  }
-
-  for (const child of node.children) {
-    if (child.type === 'identifier' || child.type === 'simple_identifier') {
-      return content.substring(child.startIndex, child.endIndex)
-    }
-  }
-
-  return null
+  const nameNode = getElixirDefinitionName(node)
+    || node.childForFieldName('name')
+    || node.children.find(child => child.type === 'identifier' || child.type === 'simple_identifier')
+  if (!nameNode) return null
+
+  const name = content.substring(nameNode.startIndex, nameNode.endIndex)
+  const receiverType = getReceiverTypeName(node)
+  return (receiverType ? `${receiverType}.${name}` : name) + getTypeParameterSuffix(node)
 }
 
 function getClassName(node: Parser.SyntaxNode, content: string): string | null {
-  const nameNode = node.childForFieldName('name')
+  const nameNode = getElixirDefinitionName(node) || node.childForFieldName('name')
   if (nameNode) {
-    return content.substring(nameNode.startIndex, nameNode.endIndex)
+    return content.substring(nameNode.startIndex, nameNode.endIndex) + getTypeParameterSuffix(node)
   }
 
   if (node.type === 'type_declaration') {
@@ -274,7 +415,10 @@ function extractParameters(node: Parser.SyntaxNode, content: string): TreeNode[]
   return params
 }
 
-function truncateLongLines(content: string, maxLineLength: number): string {
+/**
+ * Cuts lines longer than `PARSER_LIMITS.MAX_LINE_LENGTH`, the way every indexed file's content is stored
+ */
+export function truncateLongLines(content: string, maxLineLength: number = PARSER_LIMITS.MAX_LINE_LENGTH): string {
   const lines = content.split('\n')
   const truncatedLines = lines.map((line) => {
     if (line.length > maxLineLength) {
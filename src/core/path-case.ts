@@ -0,0 +1,58 @@
+/**
+ * Path case - macOS and Windows filesystems ignore case by default, so paths differing only in case name one file
+ */
+
+import { readdirSync } from 'fs'
+import { join, parse, sep } from 'path'
+import type { ProjectConfig } from '../types/core.js'
+
+/** Whether paths ignore case when a project does not say */
+export const PLATFORM_IGNORES_CASE = process.platform === 'darwin' || process.platform === 'win32'
+
+/**
+ * Whether the project's paths ignore case: its `ignoreCaseInPaths` setting, or the platform default
+ */
+export function ignoresPathCase(config: Pick<ProjectConfig, 'ignoreCaseInPaths'>): boolean {
+  return config.ignoreCaseInPaths ?? PLATFORM_IGNORES_CASE
+}
+
+/**
+ * The key a path is compared by: folded when case is ignored, so `Src/App.ts` and `src/app.ts` share one
+ */
+export function pathKey(path: string, ignoreCase: boolean): string {
+  return ignoreCase ? path.toLowerCase() : path
+}
+
+/**
+ * Whether `path` is `directory` or lies beneath it
+ */
+export function isWithinPath(path: string, directory: string, ignoreCase: boolean): boolean {
+  return (pathKey(path, ignoreCase) + sep).startsWith(pathKey(directory, ignoreCase) + sep)
+}
+
+/**
+ * Spells a path with the case its entries have on disk, one segment at a time, so a path typed in another
+ * case names the file the walk indexed. Symlinks are not resolved; from the first segment that does not
+ * exist the path is kept as given.
+ */
+export function toDiskCase(path: string): string {
+  const { root } = parse(path)
+  const segments = path.slice(root.length).split(sep).filter(Boolean)
+
+  let current = root
+  for (const [index, segment] of segments.entries()) {
+    let entries: string[]
+    try {
+      entries = readdirSync(current)
+    }
+    catch {
+      return join(current, ...segments.slice(index))
+    }
+
+    const folded = segment.toLowerCase()
+    const entry = entries.includes(segment) ? segment : entries.find(name => name.toLowerCase() === folded)
+    if (!entry) return join(current, ...segments.slice(index))
+    current = join(current, entry)
+  }
+  return current
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+/**
+ * Cursor lookups - resolves a line and column in a parsed file to the syntax node under it
+ */
+
+import type Parser from 'tree-sitter'
+import { toSourceByteOffset } from '../utils/encoding.js'
+import { createError } from '../utils/errors.js'
+import type { TreeNode, NodeAtPosition } from '../types/core.js'
+
+// Whole-file nodes would otherwise put the entire source in the response
+const MAX_NODE_TEXT = 2000
+
+export interface NodeAtPositionOptions {
+  /** Return the smallest node of any kind, including punctuation and keywords (default: named nodes only) */
+  anonymous?: boolean
+}
+
+/**
+ * Finds the smallest node covering a 1-based line and 0-based column, like the positions search results report. A position past the end of its
+ * line or of the file is moved to the nearest valid one and reported in `clamped` instead of failing.
+ */
+export function getNodeAtPosition(
+  fileNode: TreeNode,
+  line: number,
+  column: number,
+  options: NodeAtPositionOptions = {},
+): NodeAtPosition {
+  const root: Parser.SyntaxNode | undefined = fileNode.rawNode
+  if (!root || fileNode.content === undefined) {
+    throw createError('FILE_ERROR', `File has no syntax tree: ${fileNode.path}`, { file: fileNode.path })
+  }
+
+  const content = fileNode.content
+  const lines = content.split('\n')
+  const row = Math.min(Math.max(0, line - 1), lines.length - 1)
+  const lineLength = lines[row]!.replace(/\r$/, '').length
+  const col = Math.min(Math.max(0, column), lineLength)
+
+  const point = { row, column: col }
+  const node = options.anonymous ? root.descendantForPosition(point) : root.namedDescendantForPosition(point)
+
+  const ancestors: string[] = []
+  for (let current = node.parent; current; current = current.parent) ancestors.push(current.type)
+
+  const text = node.text
+  const clamped = row !== line - 1 || col !== column
+
+  return {
+    line: row + 1,
+    column: col,
+    clamped,
+    ...clamped ? { requested: { line, column } } : {},
+    node: {
+      type: node.type,
+      named: node.isNamed,
+      text: text.length > MAX_NODE_TEXT ? text.slice(0, MAX_NODE_TEXT) : text,
+      ...text.length > MAX_NODE_TEXT ? { textTruncated: true } : {},
+      startLine: node.startPosition.row + 1,
+      endLine: node.endPosition.row + 1,
+      startColumn: node.startPosition.column,
+      endColumn: node.endPosition.column,
+      startByte: toSourceByteOffset(content, node.startIndex, fileNode.encoding),
+      endByte: toSourceByteOffset(content, node.endIndex, fileNode.encoding),
+    },
+    ancestors,
+  }
+}
\ No newline at end of file
@@ -0,0 +1,125 @@
+/**
+ * Reference lookup - lists identifier usages of a symbol using the parsed syntax trees
+ */
+
+import type Parser from 'tree-sitter'
+import { getFileLanguage } from './languages.js'
+import { IDENTIFIER_TYPES, getDefinitionNameNode } from './definitions.js'
+import { toSourceByteOffset } from '../utils/encoding.js'
+import type { TreeNode, ReferenceResult, LanguageConfig, SyntaxScope } from '../types/core.js'
+
+const CODE_REENTRY_TYPES = new Set([
+  'template_substitution',
+  'interpolation',
+  'string_interpolation',
+])
+
+export interface FindReferencesOptions {
+  includeDefinition?: boolean
+  pathPattern?: string
+}
+
+/**
+ * Finds identifier nodes matching a symbol, skipping strings, comments and (by default) declarations
+ */
+export function findReferences(
+  symbol: string,
+  files: TreeNode[],
+  options: FindReferencesOptions = {},
+): ReferenceResult[] {
+  const { includeDefinition = false, pathPattern } = options
+  const results: ReferenceResult[] = []
+
+  for (const fileNode of files) {
+    if (!fileNode.rawNode || !fileNode.content) continue
+    if (pathPattern && !fileNode.path.includes(pathPattern)) continue
+
+    const language = getFileLanguage(fileNode.path, fileNode.content)
+    if (!language) continue
+
+    collectReferences(symbol, fileNode, language, includeDefinition, results)
+  }
+
+  return results
+}
+
+function collectReferences(
+  symbol: string,
+  fileNode: TreeNode,
+  language: LanguageConfig,
+  includeDefinition: boolean,
+  results: ReferenceResult[],
+): void {
+  const content = fileNode.content!
+  const lines = content.split('\n')
+
+  visitReferences(fileNode.rawNode, language, name => name === symbol, (node, isDefinition) => {
+    if (isDefinition && !includeDefinition) return
+    results.push({
+      path: fileNode.path,
+      startLine: node.startPosition.row + 1,
+      endLine: node.endPosition.row + 1,
+      startColumn: node.startPosition.column,
+      endColumn: node.endPosition.column,
+      startByte: toSourceByteOffset(content, node.startIndex, fileNode.encoding),
+      endByte: toSourceByteOffset(content, node.endIndex, fileNode.encoding),
+      context: (lines[node.startPosition.row] || '').trim(),
+      isDefinition,
+    })
+  })
+}
+
+/**
+ * Calls back for each identifier whose name matches and that sits outside strings and comments,
+ * flagging the name nodes of declarations
+ */
+export function visitReferences(
+  root: Parser.SyntaxNode,
+  language: LanguageConfig,
+  matches: (name: string) => boolean,
+  onReference: (node: Parser.SyntaxNode, isDefinition: boolean) => void,
+): void {
+  const definitionNames = new Set<number>()
+
+  function visit(node: Parser.SyntaxNode) {
+    const nameNode = getDefinitionNameNode(node, language)
+    if (nameNode) {
+      definitionNames.add(nameNode.startIndex)
+    }
+
+    if (IDENTIFIER_TYPES.has(node.type) && matches(node.text) && !isInsideLiteralOrComment(node)) {
+      onReference(node, definitionNames.has(node.startIndex))
+    }
+
+    for (const child of node.namedChildren) {
+      visit(child)
+    }
+  }
+
+  visit(root)
+}
+
+function isInsideLiteralOrComment(node: Parser.SyntaxNode): boolean {
+  return node.parent ? getSyntaxScope(node.parent) !== 'code' : false
+}
+
+/**
+ * Classifies a node by its innermost enclosing comment or string literal; interpolations re-enter code.
+ * PHP's inline HTML is output verbatim, so it counts as a string
+ */
+export function getSyntaxScope(node: Parser.SyntaxNode): SyntaxScope {
+  let current: Parser.SyntaxNode | null = node
+  while (current) {
+    if (CODE_REENTRY_TYPES.has(current.type)) return 'code'
+    if (isInlineHtml(current)) return 'string'
+    if (current.type.includes('comment')) return 'comment'
+    if (current.type.includes('string')) return 'string'
+    current = current.parent
+  }
+  return 'code'
+}
+
+// HTML before the first `<?php` is a `text` node under the program; later HTML sits between `?>` and `<?php`
+function isInlineHtml(node: Parser.SyntaxNode): boolean {
+  return node.type === 'text_interpolation' || (node.type === 'text' && node.parent?.type === 'program')
+}
\ No newline at end of file
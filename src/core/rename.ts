@@ -0,0 +1,438 @@
+/**
+ * Rename preview - plans the edits a rename would make, resolving each identifier to its binding by lexical scope
+ */
+
+import type Parser from 'tree-sitter'
+import { resolve } from 'path'
+import { getFileLanguage } from './languages.js'
+import { IDENTIFIER_TYPES, getDefinitionNameNode } from './definitions.js'
+import { getSyntaxScope } from './references.js'
+import { getCallableKind } from './symbols.js'
+import { buildImportGraph } from './import-graph.js'
+import { PARSER_NAMES } from '../constants/index.js'
+import { toSourceByteOffset } from '../utils/encoding.js'
+import type { TreeNode, LanguageConfig, RenameEdit, RenamePlan } from '../types/core.js'
+
+export const RENAME_NOTE = 'Nothing is written. Names are resolved by lexical scope within a file; uses in files that import '
+  + 'the declaring file and method or field accesses (obj.name) are matched by name, and dynamic or string-based access is not seen.'
+
+const VALID_NAME_PATTERN = /^[\p{L}_$][\p{L}\p{N}_$]*$/u
+
+// Nodes whose names, parameters or locals are invisible outside them
+const SCOPE_TYPES = new Set([
+  'statement_block', 'block', 'compound_statement', 'for_statement', 'for_in_statement', 'catch_clause',
+  'function_declaration', 'generator_function_declaration', 'function_expression', 'function', 'generator_function',
+  'arrow_function', 'method_definition', 'function_definition', 'lambda', 'function_item', 'closure_expression',
+  'for_expression', 'match_arm', 'if_expression', 'while_expression', 'mod_item', 'method_declaration',
+  'constructor_declaration', 'func_literal',
+])
+
+// Python only scopes names to functions, lambdas and comprehensions
+const PYTHON_SCOPE_TYPES = new Set([
+  'function_definition', 'lambda', 'list_comprehension', 'set_comprehension', 'dictionary_comprehension', 'generator_expression',
+])
+
+// `var` ignores blocks and binds in the enclosing function
+const FUNCTION_SCOPE_TYPES = new Set([
+  'function_declaration', 'generator_function_declaration', 'function_expression', 'function', 'generator_function',
+  'arrow_function', 'method_definition',
+])
+
+const PARAMETER_LIST_TYPES = new Set(['formal_parameters', 'parameters', 'lambda_parameters', 'closure_parameters', 'parameter_list'])
+
+const MEMBER_IDENTIFIER_TYPES = new Set(['property_identifier', 'field_identifier'])
+
+const MEMBER_CONTAINER_TYPES = new Set(['class_body', 'declaration_list', 'interface_body', 'enum_body'])
+const NAMESPACE_TYPES = new Set(['mod_item', 'namespace_declaration', 'namespace_definition'])
+
+// Written as `name` but standing for `name: name`, so a rename must keep the key
+const SHORTHAND_TYPES = new Set(['shorthand_property_identifier', 'shorthand_property_identifier_pattern', 'shorthand_field_identifier'])
+
+// Parts of a pattern that are types, defaults, keys or paths rather than new names
+const NON_BINDING_FIELDS = ['type', 'value', 'default', 'right', 'return_type', 'condition']
+const NON_BINDING_TYPES = new Set(['type_annotation', 'scoped_identifier', 'attribute', 'subscript', 'member_expression'])
+
+const IMPORT_GRAPH_LANGUAGES = new Set<string>([PARSER_NAMES.JAVASCRIPT, PARSER_NAMES.TYPESCRIPT, PARSER_NAMES.TSX, PARSER_NAMES.PYTHON, PARSER_NAMES.RUST])
+const SCRIPT_LANGUAGES = new Set<string>([PARSER_NAMES.JAVASCRIPT, PARSER_NAMES.TYPESCRIPT, PARSER_NAMES.TSX])
+
+export interface RenameOptions {
+  /** Project root, used to resolve `file` and the import graph */
+  directory: string
+  file?: string
+  line?: number
+}
+
+interface Binding {
+  nameNode: Parser.SyntaxNode
+  kind: string
+  scope: Parser.SyntaxNode
+  member: boolean
+  /** Visible from the start of its scope (functions, parameters, JS `let`) rather than from `visibleFrom` (Rust `let`) */
+  hoisted: boolean
+  visibleFrom: number
+  path: string
+}
+
+interface FileBindings {
+  fileNode: TreeNode
+  language: LanguageConfig
+  bindings: Binding[]
+  occurrences: Parser.SyntaxNode[]
+}
+
+/**
+ * Plans a rename without touching any file. With `file` (and `line`), the binding named there is renamed;
+ * otherwise the symbol must have a single top-level or member declaration in the project.
+ * Same-named variables in other scopes, including shadowing ones in inner scopes, are left alone.
+ */
+export function planRename(symbol: string, newName: string, files: TreeNode[], options: RenameOptions): RenamePlan {
+  if (!VALID_NAME_PATTERN.test(newName)) {
+    throw new Error(`"${newName}" is not a valid identifier`)
+  }
+
+  const scanned = files
+    .map(fileNode => scanFile(symbol, fileNode))
+    .filter((scan): scan is FileBindings => scan !== null)
+
+  const target = pickTarget(symbol, scanned, options)
+  if (!target) {
+    return { edits: [], files: [], note: `No declaration of "${symbol}" found` }
+  }
+
+  const targetScan = scanned.find(scan => scan.fileNode.path === target.path)!
+  const importers = isTopLevel(target) ? findImporters(target.path, scanned, options.directory) : new Set<string>()
+  const edits: RenameEdit[] = []
+
+  for (const scan of scanned) {
+    if (!isSameLanguageFamily(scan.language, targetScan.language)) continue
+
+    const sameFile = scan === targetScan
+    if (!sameFile && !target.member && !importers.has(scan.fileNode.path)) continue
+
+    for (const occurrence of scan.occurrences) {
+      const binding = resolveOccurrence(occurrence, scan)
+      // Other files see a top-level target only through names they do not declare themselves
+      const renamed = binding === 'member'
+        ? target.member
+        : binding === null ? !sameFile && !target.member : binding === target
+      if (renamed) {
+        edits.push(createEdit(occurrence, scan.fileNode, newName))
+      }
+    }
+  }
+
+  edits.sort((a, b) => a.path.localeCompare(b.path) || a.startByte - b.startByte)
+
+  return {
+    target: {
+      name: symbol,
+      kind: target.kind,
+      path: target.path,
+      startLine: target.nameNode.startPosition.row + 1,
+      startColumn: target.nameNode.startPosition.column,
+    },
+    edits,
+    files: Array.from(new Set(edits.map(edit => edit.path))),
+  }
+}
+
+function scanFile(symbol: string, fileNode: TreeNode): FileBindings | null {
+  if (!fileNode.rawNode || !fileNode.content) return null
+
+  const language = getFileLanguage(fileNode.path, fileNode.content)
+  if (!language) return null
+
+  const bindings: Binding[] = []
+  const occurrences: Parser.SyntaxNode[] = []
+  const add = (nameNode: Parser.SyntaxNode, kind: string, scope: Parser.SyntaxNode, extra: Partial<Binding> = {}) => {
+    if (nameNode.text !== symbol) return
+    bindings.push({ nameNode, kind, scope, member: false, hoisted: true, visibleFrom: 0, path: fileNode.path, ...extra })
+  }
+
+  function visit(node: Parser.SyntaxNode) {
+    if ((IDENTIFIER_TYPES.has(node.type) || SHORTHAND_TYPES.has(node.type)) && node.text === symbol) {
+      if (!node.parent || getSyntaxScope(node.parent) === 'code') occurrences.push(node)
+    }
+
+    collectBindings(node, language!, add)
+
+    for (const child of node.namedChildren) {
+      visit(child)
+    }
+  }
+
+  visit(fileNode.rawNode)
+  return { fileNode, language, bindings, occurrences }
+}
+
+/**
+ * Adds the names a node introduces, each with the scope it is visible in
+ */
+function collectBindings(
+  node: Parser.SyntaxNode,
+  language: LanguageConfig,
+  add: (nameNode: Parser.SyntaxNode, kind: string, scope: Parser.SyntaxNode, extra?: Partial<Binding>) => void,
+): void {
+  const isPython = language.name === PARSER_NAMES.PYTHON
+
+  if (node.type === 'variable_declarator') {
+    const declaration = node.parent
+    const scope = declaration?.type === 'variable_declaration'
+      ? getFunctionScope(node)
+      : getScope(node.parent!, language)
+    const kind = getCallableKind(node, language) || (declaration?.child(0)?.type === 'const' ? 'const' : 'variable')
+    for (const name of getPatternNames(node.childForFieldName('name'))) add(name, kind, scope)
+    return
+  }
+
+  const nameNode = getDefinitionNameNode(node, language)
+  if (nameNode) {
+    const member = isMemberDeclaration(node, isPython)
+    add(nameNode, language.definitionKinds[node.type]!, getScope(node.parent || node, language), { member })
+  }
+
+  if (PARAMETER_LIST_TYPES.has(node.type)) {
+    // C declarators sit between the parameters and the function; function types have no body to scope to
+    const owner = node.parent?.type === 'function_declarator' ? node.parent.parent : node.parent
+    if (owner && getScopeTypes(language).has(owner.type)) {
+      for (const parameter of node.namedChildren) {
+        for (const name of getPatternNames(parameter)) add(name, 'parameter', owner)
+      }
+    }
+    return
+  }
+
+  switch (node.type) {
+    case 'arrow_function': {
+      const parameter = node.childForFieldName('parameter')
+      if (parameter) add(parameter, 'parameter', node)
+      break
+    }
+    case 'catch_clause':
+      for (const name of getPatternNames(node.childForFieldName('parameter'))) add(name, 'variable', node)
+      break
+    case 'for_in_statement':
+      if (!isPython && node.childForFieldName('kind')) {
+        for (const name of getPatternNames(node.childForFieldName('left'))) add(name, 'variable', node)
+      }
+      break
+    case 'for_expression':
+    case 'match_arm':
+      for (const name of getPatternNames(node.childForFieldName('pattern'))) add(name, 'variable', node)
+      break
+    case 'let_declaration':
+    case 'short_var_declaration': {
+      const pattern = node.childForFieldName('pattern') || node.childForFieldName('left')
+      for (const name of getPatternNames(pattern)) {
+        add(name, 'variable', getScope(node.parent!, language), { hoisted: false, visibleFrom: node.endIndex })
+      }
+      break
+    }
+    case 'range_clause':
+      if (node.text.includes(':=')) {
+        for (const name of getPatternNames(node.childForFieldName('left'))) add(name, 'variable', getScope(node, language))
+      }
+      break
+    case 'let_condition':
+      for (const name of getPatternNames(node.childForFieldName('pattern'))) add(name, 'variable', getScope(node, language))
+      break
+    case 'assignment':
+    case 'augmented_assignment':
+    case 'for_statement':
+    case 'for_in_clause':
+      if (isPython) {
+        for (const name of getPatternNames(node.childForFieldName('left'))) add(name, 'variable', getScope(node, language))
+      }
+      break
+    case 'as_pattern':
+    case 'named_expression':
+      if (isPython) {
+        const alias = node.childForFieldName('alias') || node.childForFieldName('name')
+        for (const name of getPatternNames(alias)) add(name, 'variable', getScope(node, language))
+      }
+      break
+  }
+}
+
+/**
+ * Collects the identifiers a pattern binds, skipping type annotations, default values, keys and paths
+ */
+function getPatternNames(pattern: Parser.SyntaxNode | null): Parser.SyntaxNode[] {
+  if (!pattern || NON_BINDING_TYPES.has(pattern.type)) return []
+  if (pattern.type === 'identifier' || SHORTHAND_TYPES.has(pattern.type)) return [pattern]
+
+  // In `{ key: value }` and `Point { x: px }` only the value side binds
+  if (pattern.type === 'pair_pattern') return getPatternNames(pattern.childForFieldName('value'))
+  if (pattern.type === 'field_pattern') return getPatternNames(pattern.childForFieldName('pattern') || pattern.childForFieldName('name'))
+
+  const skipped = NON_BINDING_FIELDS
+    .map(field => pattern.childForFieldName(field))
+    .filter((child): child is Parser.SyntaxNode => child !== null)
+
+  return pattern.namedChildren
+    .filter(child => !skipped.some(node => sameNode(node, child)))
+    .flatMap(child => getPatternNames(child))
+}
+
+/**
+ * Returns the binding an identifier refers to, `member` for `obj.name` style accesses, or null when it is not
+ * declared in the file (globals and imports)
+ */
+function resolveOccurrence(node: Parser.SyntaxNode, scan: FileBindings): Binding | 'member' | null {
+  const declared = scan.bindings.find(binding => sameNode(binding.nameNode, node))
+  if (declared?.member) return declared
+  if (declared?.hoisted) return getFirstHoisted(declared.scope, scan)!
+  if (declared) return declared
+  if (isMemberReference(node)) return 'member'
+  if (isKeywordArgumentName(node)) return null
+
+  for (let scope = getScope(node, scan.language); ; scope = getScope(scope.parent!, scan.language)) {
+    // A later Rust `let` shadows what came before it; hoisted names cover the whole scope
+    const shadowing = scan.bindings.filter(binding =>
+      !binding.hoisted && sameNode(binding.scope, scope) && binding.visibleFrom <= node.startIndex,
+    )
+    const binding = shadowing[shadowing.length - 1] || getFirstHoisted(scope, scan)
+    if (binding) return binding
+    if (!scope.parent) return null
+  }
+}
+
+/**
+ * Repeated assignments and `var` declarations in one scope are the same variable, named by the first
+ */
+function getFirstHoisted(scope: Parser.SyntaxNode, scan: FileBindings): Binding | undefined {
+  return scan.bindings.find(binding => binding.hoisted && !binding.member && sameNode(binding.scope, scope))
+}
+
+function pickTarget(symbol: string, scanned: FileBindings[], options: RenameOptions): Binding | null {
+  if (options.file) {
+    const path = resolve(options.directory, options.file)
+    const scan = scanned.find(candidate => candidate.fileNode.path === path)
+    if (!scan) throw new Error(`File not found in project: ${options.file}`)
+
+    const onLine = options.line === undefined
+      ? scan.occurrences
+      : scan.occurrences.filter(node => node.startPosition.row + 1 === options.line)
+    if (options.line !== undefined && onLine.length === 0) {
+      throw new Error(`"${symbol}" does not appear on line ${options.line} of ${options.file}`)
+    }
+
+    for (const occurrence of onLine) {
+      const binding = resolveOccurrence(occurrence, scan)
+      if (binding && binding !== 'member' && (options.line !== undefined || isTopLevel(binding) || binding.member)) return binding
+    }
+    if (options.line === undefined && scan.bindings.length > 0) return scan.bindings[0]!
+  }
+
+  // Without a position only names visible beyond their own body are candidates
+  const declarations = scanned.flatMap(scan => scan.bindings.filter(binding =>
+    binding.member || (isTopLevel(binding) && getFirstHoisted(binding.scope, scan) === binding),
+  ))
+  if (declarations.length > 1) {
+    const places = declarations.map(binding => `${binding.path}:${binding.nameNode.startPosition.row + 1}`).join(', ')
+    throw new Error(`"${symbol}" is declared ${declarations.length} times (${places}); pass file and line to pick one`)
+  }
+  return declarations[0] || null
+}
+
+/**
+ * Files that import the target's file; languages without an import graph share top-level names across files
+ */
+function findImporters(path: string, scanned: FileBindings[], directory: string): Set<string> {
+  const graph = buildImportGraph(scanned.map(scan => scan.fileNode), directory)
+  const importers = new Set(graph.edges.filter(edge => edge.to === path).map(edge => edge.from))
+
+  for (const scan of scanned) {
+    if (!IMPORT_GRAPH_LANGUAGES.has(scan.language.name)) importers.add(scan.fileNode.path)
+  }
+  return importers
+}
+
+function createEdit(node: Parser.SyntaxNode, fileNode: TreeNode, newName: string): RenameEdit {
+  const content = fileNode.content!
+  const isShorthand = SHORTHAND_TYPES.has(node.type) || node.parent?.type === 'shorthand_field_initializer'
+
+  return {
+    path: fileNode.path,
+    startByte: toSourceByteOffset(content, node.startIndex, fileNode.encoding),
+    endByte: toSourceByteOffset(content, node.endIndex, fileNode.encoding),
+    startLine: node.startPosition.row + 1,
+    startColumn: node.startPosition.column,
+    endLine: node.endPosition.row + 1,
+    endColumn: node.endPosition.column,
+    oldText: node.text,
+    newText: isShorthand ? `${node.text}: ${newName}` : newName,
+  }
+}
+
+function getScopeTypes(language: LanguageConfig): Set<string> {
+  return language.name === PARSER_NAMES.PYTHON ? PYTHON_SCOPE_TYPES : SCOPE_TYPES
+}
+
+function getScope(node: Parser.SyntaxNode, language: LanguageConfig): Parser.SyntaxNode {
+  const scopeTypes = getScopeTypes(language)
+  let current = node
+  while (current.parent && !scopeTypes.has(current.type)) {
+    current = current.parent
+  }
+  return current
+}
+
+function getFunctionScope(node: Parser.SyntaxNode): Parser.SyntaxNode {
+  let current = node
+  while (current.parent && !FUNCTION_SCOPE_TYPES.has(current.type)) {
+    current = current.parent
+  }
+  return current
+}
+
+function isTopLevel(binding: Binding): boolean {
+  return !binding.member && binding.scope.parent === null
+}
+
+/**
+ * Methods and fields live in their type rather than a lexical scope; Python methods are functions in a class body
+ */
+function isMemberDeclaration(node: Parser.SyntaxNode, isPython: boolean): boolean {
+  if (isPython) return node.parent?.type === 'block' && node.parent.parent?.type === 'class_definition'
+  return MEMBER_CONTAINER_TYPES.has(node.parent?.type || '') && !NAMESPACE_TYPES.has(node.parent?.parent?.type || '')
+}
+
+function isMemberReference(node: Parser.SyntaxNode): boolean {
+  if (MEMBER_IDENTIFIER_TYPES.has(node.type)) return true
+
+  const parent = node.parent
+  if (!parent) return false
+  switch (parent.type) {
+    case 'attribute':
+      return isField(node, parent, 'attribute')
+    case 'method_invocation':
+    case 'field_access':
+      return parent.childForFieldName('object') !== null && (isField(node, parent, 'name') || isField(node, parent, 'field'))
+    case 'scoped_identifier': {
+      // `Type::new` names an associated item; `crate::utils::helper` is a path to a free function
+      const path = parent.childForFieldName('path')
+      const owner = path?.type === 'scoped_identifier' ? path.childForFieldName('name') : path
+      return isField(node, parent, 'name') && /^[A-Z]/.test(owner?.text || '')
+    }
+    default:
+      return false
+  }
+}
+
+function isKeywordArgumentName(node: Parser.SyntaxNode): boolean {
+  return node.parent?.type === 'keyword_argument' && isField(node, node.parent, 'name')
+}
+
+function isField(node: Parser.SyntaxNode, parent: Parser.SyntaxNode, field: string): boolean {
+  const child = parent.childForFieldName(field)
+  return child !== null && sameNode(child, node)
+}
+
+function isSameLanguageFamily(a: LanguageConfig, b: LanguageConfig): boolean {
+  return a.name === b.name || (SCRIPT_LANGUAGES.has(a.name) && SCRIPT_LANGUAGES.has(b.name))
+}
+
+function sameNode(a: Parser.SyntaxNode, b: Parser.SyntaxNode): boolean {
+  return a.startIndex === b.startIndex && a.endIndex === b.endIndex && a.type === b.type
+}
\ No newline at end of file
@@ -0,0 +1,150 @@
+/**
+ * Search and replace - previews regex replacements across indexed files as edits, and writes them on request
+ */
+
+import { renameSync, statSync, writeFileSync } from 'fs'
+import { compileRegex, findRegexMatches, type RegexMatch } from './search.js'
+import { truncateLongLines } from './parser.js'
+import { PARSER_LIMITS } from '../constants/parsers.js'
+import { createError } from '../utils/errors.js'
+import { encodeSource, readSourceFile, toSourceByteOffset } from '../utils/encoding.js'
+import type { RenameEdit, ReplacePlan, TreeNode } from '../types/core.js'
+
+export const REPLACE_NOTE = 'Nothing is written unless apply is true. Matches are found the way search_code regex mode finds them: '
+  + 'line by line unless the m or s flag is set.'
+
+export interface ReplaceOptions {
+  regexFlags?: string
+  caseInsensitive?: boolean
+}
+
+/**
+ * Plans the edits replacing every match of `pattern` in `files`. The replacement may use `$1`, `$<name>`,
+ * `$&`, `` $` ``, `$'` and `$$` as in `String.prototype.replace`.
+ */
+export function planReplace(pattern: string, replacement: string, files: TreeNode[], options: ReplaceOptions = {}): ReplacePlan {
+  const { regexFlags = '', caseInsensitive = false } = options
+  const regex = compileRegex(pattern, caseInsensitive && !regexFlags.includes('i') ? regexFlags + 'i' : regexFlags)
+  const edits: RenameEdit[] = []
+
+  for (const fileNode of files) {
+    if (!fileNode.content || fileNode.skipped) continue
+    for (const found of findRegexMatches(regex, fileNode.content)) {
+      const newText = expandReplacement(replacement, found)
+      // Matches the replacement leaves as they are need no edit
+      if (newText === found.match[0]) continue
+      const edit = createReplaceEdit(fileNode, found, newText)
+      // Past the line limit the index holds the truncation marker, not the file's text
+      if (edit.startColumn > PARSER_LIMITS.MAX_LINE_LENGTH || edit.endColumn > PARSER_LIMITS.MAX_LINE_LENGTH) continue
+      edits.push(edit)
+    }
+  }
+
+  return { edits, files: Array.from(new Set(edits.map(edit => edit.path))) }
+}
+
+/**
+ * Writes a plan's edits, one file at a time through a temporary file and a rename so no file is left
+ * half written. Every file is checked first: edits must not overlap, and the file must still hold the
+ * content they were planned against. Returns the paths written.
+ */
+export function applyReplacePlan(plan: ReplacePlan, files: TreeNode[]): string[] {
+  const byPath = new Map(files.map(fileNode => [fileNode.path, fileNode]))
+  const updates = plan.files.map((path) => {
+    const fileNode = byPath.get(path)
+    const edits = plan.edits
+      .filter(edit => edit.path === path)
+      .sort((a, b) => a.startLine - b.startLine || a.startColumn - b.startColumn)
+
+    for (let i = 1; i < edits.length; i++) {
+      const previous = edits[i - 1]!
+      const edit = edits[i]!
+      if (previous.endLine > edit.startLine || (previous.endLine === edit.startLine && previous.endColumn > edit.startColumn)) {
+        throw createError('SEARCH_ERROR', `Replacements overlap in ${path} at line ${edit.startLine}`, { path, line: edit.startLine })
+      }
+    }
+
+    const source = readSourceFile(path)
+    // The index keeps long lines truncated, so the file is compared the way it was indexed
+    if (!fileNode || truncateLongLines(source.content) !== fileNode.content) {
+      throw createError('FILE_ERROR', `${path} changed since it was indexed; search again before applying`, { path })
+    }
+    return { path, encoding: source.encoding, content: applyEdits(source.content, edits) }
+  })
+
+  for (const update of updates) {
+    const temporary = `${update.path}.${process.pid}.tmp`
+    writeFileSync(temporary, encodeSource(update.content, update.encoding), { mode: statSync(update.path).mode })
+    renameSync(temporary, update.path)
+  }
+  return updates.map(update => update.path)
+}
+
+function createReplaceEdit(fileNode: TreeNode, { match, segment }: RegexMatch, newText: string): RenameEdit {
+  const content = fileNode.content!
+  const startIndex = segment.start + match.index
+  const endIndex = startIndex + match[0].length
+  const start = getPosition(content, startIndex)
+  const end = getPosition(content, endIndex)
+
+  return {
+    path: fileNode.path,
+    startByte: toSourceByteOffset(content, startIndex, fileNode.encoding),
+    endByte: toSourceByteOffset(content, endIndex, fileNode.encoding),
+    startLine: start.line,
+    startColumn: start.column,
+    endLine: end.line,
+    endColumn: end.column,
+    oldText: match[0],
+    newText,
+  }
+}
+
+/**
+ * Expands replacement patterns against one match. As in JavaScript, `$10` falls back to `$1` followed
+ * by `0` when there is no tenth group, and references to missing groups stay literal.
+ */
+function expandReplacement(template: string, { match, segment }: RegexMatch): string {
+  const groupCount = match.length - 1
+
+  return template.replace(/\$(\$|&|`|'|<([^>]*)>|(\d\d?))/g, (token, symbol: string, name: string | undefined, digits: string | undefined) => {
+    if (symbol === '$') return '$'
+    if (symbol === '&') return match[0]
+    if (symbol === '`') return segment.text.slice(0, match.index)
+    if (symbol === '\'') return segment.text.slice(match.index + match[0].length)
+    if (name !== undefined) return match.groups ? match.groups[name] ?? '' : token
+
+    const number = Number(digits)
+    if (number >= 1 && number <= groupCount) return match[number] ?? ''
+    if (digits!.length === 2 && Number(digits![0]) >= 1 && Number(digits![0]) <= groupCount) {
+      return (match[Number(digits![0])] ?? '') + digits![1]
+    }
+    return token
+  })
+}
+
+function applyEdits(content: string, edits: RenameEdit[]): string {
+  let result = ''
+  let cursor = 0
+  for (const edit of edits) {
+    const start = getIndex(content, edit.startLine, edit.startColumn)
+    result += content.slice(cursor, start) + edit.newText
+    cursor = getIndex(content, edit.endLine, edit.endColumn)
+  }
+  return result + content.slice(cursor)
+}
+
+// Lines are 1-based and columns count UTF-16 code units, like the rest of the index
+function getPosition(content: string, index: number): { line: number, column: number } {
+  const before = content.slice(0, index)
+  const lineStart = before.lastIndexOf('\n') + 1
+  return { line: before.split('\n').length, column: index - lineStart }
+}
+
+function getIndex(content: string, line: number, column: number): number {
+  let lineStart = 0
+  for (let row = 1; row < line; row++) {
+    lineStart = content.indexOf('\n', lineStart) + 1
+  }
+  return lineStart + column
+}
\ No newline at end of file
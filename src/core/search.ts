@@ -2,79 +2,1323 @@
  * Code search functionality - simplified from complex SearchEngine class
  */
 
-import type { TreeNode, SearchOptions, SearchResult, FindUsageResult } from '../types/core.js'
+import Parser from 'tree-sitter'
+import { statSync } from 'fs'
+import { dirname, extname, relative, resolve, sep } from 'path'
+import type { TreeNode, SearchFileAudit, SearchOptions, SearchPage, SearchResult, SkippedFile, SearchSort, SearchDedup, MatchRange, RelevanceScore, FindUsageResult, KindFilter, LanguageConfig, LineRange, SyntaxScope, TestScope } from '../types/core.js'
 import { createLightweightTreeNode } from '../types/core.js'
+import { getGrammar, getFileLanguage, hasInlineTests, isInlineTestCode, isTestPath } from './languages.js'
+import { findEnclosingDeclaration, findEnclosingScope, getEnclosingPath } from './definitions.js'
+import { extractAllNodes } from './parser.js'
+import { listDeclarations, matchesKind, type Declaration } from './symbols.js'
+import { compileGlob } from './gitignore.js'
+import { pathKey } from './path-case.js'
+import { getSyntaxScope } from './references.js'
+import { compileStructuralPattern, matchStructuralPattern, type StructuralPattern } from './structural.js'
+import { KEY_PATH_LANGUAGES, collectKeyPaths, compileKeyPath } from './key-paths.js'
+import { RELEVANCE_WEIGHTS, SEARCH_LIMITS, isGeneratedFile, isTestFile } from '../constants/index.js'
+import { createError } from '../utils/errors.js'
+import { runCancellable, runToCompletion, runWithDeadline, type CancellableSteps, type DeadlineRun } from '../utils/cancellation.js'
+import { getLogger } from '../utils/logger.js'
 import { escapeRegExp } from '../utils/string-analysis.js'
 import { getUsageContext, extractContent } from '../utils/content-extraction.js'
 
+// CRLF counts as one line break so lines, columns and `$` anchors match LF files
+const LINE_BREAK = /\r?\n/
+
+export interface CandidateFilterOptions {
+  languages?: LanguageConfig[]
+  include?: string[]
+  exclude?: string[]
+  /** Absolute paths to limit the search to, such as the files changed since a git ref */
+  files?: ReadonlySet<string>
+  /** Drops files that cannot hold a result in this test scope; inline tests are left to filterByTestScope */
+  testScope?: TestScope
+  /** Globs and the file set match paths in any case, as on case-insensitive filesystems */
+  ignoreCase?: boolean
+}
+
+/**
+ * Builds a file path predicate for the language, include/exclude and file set filters, applied before any
+ * matching. Globs are matched against paths relative to the project root; exclude wins when both match.
+ */
+export function createCandidateFilter(root: string, options: CandidateFilterOptions = {}): (filePath: string) => boolean {
+  const extensions = options.languages?.length
+    ? new Set(options.languages.flatMap(language => language.extensions))
+    : undefined
+  const { files, testScope = 'include', ignoreCase = false } = options
+  const include = (options.include || []).map(pattern => compileGlob(pattern, ignoreCase))
+  const exclude = (options.exclude || []).map(pattern => compileGlob(pattern, ignoreCase))
+  const fileKeys = files && ignoreCase ? new Set([...files].map(file => pathKey(file, true))) : files
+
+  return (filePath: string) => {
+    if (fileKeys && !fileKeys.has(pathKey(filePath, ignoreCase))) return false
+    if (extensions && !extensions.has(extname(filePath).toLowerCase())) return false
+
+    const relativePath = relative(root, filePath).split(sep).join('/')
+    if (testScope === 'exclude' && isTestPath(relativePath)) return false
+    if (testScope === 'only' && !isTestPath(relativePath)) {
+      // Outside test paths, only languages with inline tests can hold any
+      const language = getFileLanguage(filePath)
+      if (!language || !hasInlineTests(language)) return false
+    }
+
+    if (include.length === 0 && exclude.length === 0) return true
+    if (exclude.some(glob => glob.test(relativePath))) return false
+    return include.length === 0 || include.some(glob => glob.test(relativePath))
+  }
+}
+
+export interface AuditSources {
+  /** Files the index holds */
+  files: TreeNode[]
+  /** Files the walk left out for size or content */
+  skipped: SkippedFile[]
+  gitIgnored: number
+  /** Directories left unentered by the depth limit */
+  depthSkipped: number
+}
+
+/**
+ * Accounts for every file a search could have looked at, so an empty result can be told apart from a
+ * search that had nothing to look at
+ */
+export function auditSearchFiles(root: string, sources: AuditSources, options: CandidateFilterOptions = {}): SearchFileAudit {
+  const inLanguages = createCandidateFilter(root, { languages: options.languages })
+  const isCandidate = createCandidateFilter(root, options)
+  const excluded = {
+    gitignore: sources.gitIgnored,
+    tooLarge: sources.skipped.filter(file => file.reason === 'too_large').length,
+    binary: sources.skipped.filter(file => file.reason === 'binary').length,
+    languageFilter: 0,
+    pathFilter: 0,
+    unparsed: 0,
+    depthLimit: sources.depthSkipped,
+  }
+  let parsed = 0
+
+  for (const file of sources.files) {
+    if (!inLanguages(file.path)) excluded.languageFilter++
+    else if (!isCandidate(file.path)) excluded.pathFilter++
+    else if (file.skipped || !getFileLanguage(file.path, file.content)) excluded.unparsed++
+    else parsed++
+  }
+
+  const considered = parsed + Object.values(excluded).reduce((sum, count) => sum + count, 0)
+  return { considered, excluded, parsed }
+}
+
+/**
+ * Searches for code elements matching the query with progressive content inclusion
+ */
+export function searchCode(
+  query: string,
+  nodes: TreeNode[],
+  options: SearchOptions = {},
+): SearchResult[] {
+  return runToCompletion(searchCodeSteps(query, nodes, options))
+}
+
+function* searchCodeSteps(query: string, nodes: TreeNode[], options: SearchOptions): CancellableSteps<SearchResult[]> {
+  const {
+    maxResults = 20,
+    fuzzyThreshold = 30,
+    exactMatch = false,
+    wholeWord = false,
+    subwordAware = false,
+    caseInsensitive = false,
+    types = [],
+    pathPattern,
+    lineRange,
+    kind,
+    minQueryLength = SEARCH_LIMITS.MIN_QUERY_LENGTH,
+    forceContentInclusion = false,
+    maxContentLines = 150,
+    disableContentInclusion = false,
+  } = options
+
+  if (!exactMatch && !wholeWord && !subwordAware) checkQueryLength(query, minQueryLength)
+
+  // First pass: collect all matching results without content
+  const uniqueResults: MatchedResult[] = []
+  const collect = createCollector(uniqueResults, options.onMatch)
+  yield* visitMatches(query, nodes, { exactMatch, wholeWord, subwordAware, caseInsensitive, fuzzyThreshold, types, pathPattern, kind, lineRange }, (result) => {
+    collect(result)
+  })
+
+  // Sort and slice to get final result set
+  const sortedResults = uniqueResults
+    .sort(compareResults)
+    .slice(0, maxResults)
+
+  // Apply progressive content inclusion based on result count
+  return includeContentInResults(sortedResults, {
+    forceContentInclusion,
+    maxContentLines,
+    disableContentInclusion,
+    explicitMaxContentLines: 'maxContentLines' in options,
+  })
+}
+
+/**
+ * Streams matches to `onResult` in traversal order as they are found, without buffering or ranking.
+ * Results carry metadata only unless `forceContentInclusion` is set, and the walk stops after `maxResults`.
+ * Returns the number of results emitted.
+ */
+export function streamSearchCode(
+  query: string,
+  nodes: TreeNode[],
+  options: SearchOptions,
+  onResult: (result: SearchResult) => void,
+): number {
+  const {
+    maxResults = 20,
+    fuzzyThreshold = 30,
+    exactMatch = false,
+    wholeWord = false,
+    subwordAware = false,
+    caseInsensitive = false,
+    types = [],
+    pathPattern,
+    lineRange,
+    kind,
+    minQueryLength = SEARCH_LIMITS.MIN_QUERY_LENGTH,
+    forceContentInclusion = false,
+  } = options
+
+  if (!exactMatch && !wholeWord && !subwordAware) checkQueryLength(query, minQueryLength)
+
+  let count = 0
+  if (maxResults <= 0) return count
+
+  runToCompletion(visitMatches(query, nodes, { exactMatch, wholeWord, subwordAware, caseInsensitive, fuzzyThreshold, types, pathPattern, kind, lineRange }, (result) => {
+    const content = forceContentInclusion ? result.node.content : undefined
+    onResult({
+      ...result,
+      contentIncluded: content !== undefined,
+      content,
+      contentTruncated: content !== undefined ? false : undefined,
+      contentLines: content?.split('\n').length,
+    })
+    count++
+    return count < maxResults
+  }))
+
+  return count
+}
+
+/**
+ * Ranks named elements by subsequence similarity (e.g. `ChtPrvdr` -> `ChatProvider`).
+ * Ties break by shorter name, then path, so ordering is deterministic.
+ */
+export function searchFuzzy(
+  query: string,
+  nodes: TreeNode[],
+  options: SearchOptions = {},
+): SearchResult[] {
+  return runToCompletion(searchFuzzySteps(query, nodes, options))
+}
+
+function* searchFuzzySteps(query: string, nodes: TreeNode[], options: SearchOptions): CancellableSteps<SearchResult[]> {
+  const {
+    maxResults = 20,
+    fuzzyThreshold = 30,
+    types = [],
+    pathPattern,
+    lineRange,
+    minQueryLength = SEARCH_LIMITS.MIN_QUERY_LENGTH,
+    forceContentInclusion = false,
+    maxContentLines = 150,
+    disableContentInclusion = false,
+  } = options
+
+  checkQueryLength(query, minQueryLength)
+
+  const seenNodeIds = new Set<string>()
+  const results: MatchedResult[] = []
+  const collect = createCollector(results, options.onMatch)
+
+  for (const node of nodes) {
+    if (yield) break
+    if (!node.name || seenNodeIds.has(node.id)) continue
+    if (types.length > 0 && !types.includes(node.type)) continue
+    if (pathPattern && !node.path.includes(pathPattern)) continue
+    if (!inLineRange(node, lineRange)) continue
+
+    const score = scoreSubsequence(query, node.name)
+    if (score > 0 && score >= fuzzyThreshold) {
+      seenNodeIds.add(node.id)
+      collect({
+        node: createLightweightTreeNode(node),
+        score,
+        matches: ['name'],
+        highlight: getHighlight(node),
+      })
+    }
+  }
+
+  results.sort((a, b) =>
+    b.score - a.score
+    || (a.node.name?.length ?? 0) - (b.node.name?.length ?? 0)
+    || compareResults(a, b),
+  )
+
+  return includeContentInResults(results.slice(0, maxResults), {
+    forceContentInclusion,
+    maxContentLines,
+    disableContentInclusion,
+    explicitMaxContentLines: 'maxContentLines' in options,
+  })
+}
+
+/**
+ * Scores how well a query matches a name as an ordered subsequence (0-100).
+ * Characters landing on word boundaries (camelCase humps, `_`, `-`, `.`) and consecutive runs earn bonuses.
+ */
+export function scoreSubsequence(query: string, name: string): number {
+  if (query.length === 0 || name.length === 0) return 0
+  if (query.toLowerCase() === name.toLowerCase()) return 100
+
+  const queryLower = query.toLowerCase()
+  const nameLower = name.toLowerCase()
+  const pointsPerChar = 4
+
+  let points = 0
+  let nameIndex = 0
+  let previousMatch = -2
+
+  for (const char of queryLower) {
+    const found = nameLower.indexOf(char, nameIndex)
+    if (found === -1) return 0
+
+    points += 1
+    if (isWordBoundary(name, found)) points += 2
+    if (found === previousMatch + 1) points += 1
+
+    previousMatch = found
+    nameIndex = found + 1
+  }
+
+  const matchQuality = points / (query.length * pointsPerChar)
+  const coverage = query.length / name.length
+  return Math.min(99, Math.round(matchQuality * 70 + coverage * 30))
+}
+
+function isWordBoundary(name: string, index: number): boolean {
+  if (index === 0) return true
+
+  const previous = name[index - 1]!
+  const current = name[index]!
+  if (previous === '_' || previous === '-' || previous === '.') return true
+
+  return current !== current.toLowerCase() && previous === previous.toLowerCase()
+}
+
+/**
+ * Runs a tree-sitter query (.scm syntax) against every parsed file, returning one result per capture
+ */
+export function searchByQuery(
+  source: string,
+  files: TreeNode[],
+  options: SearchOptions = {},
+): SearchResult[] {
+  return runToCompletion(searchByQuerySteps(source, files, options))
+}
+
+function* searchByQuerySteps(source: string, files: TreeNode[], options: SearchOptions): CancellableSteps<SearchResult[]> {
+  const {
+    maxResults = 20,
+    types = [],
+    pathPattern,
+    lineRange,
+    scope,
+    forceContentInclusion = false,
+    maxContentLines = 150,
+    disableContentInclusion = false,
+  } = options
+
+  const compiled = new Map<string, Parser.Query | null>()
+  const compileErrors: TreeSitterQueryError[] = []
+  const results: MatchedResult[] = []
+  const collect = createCollector(results, options.onMatch)
+
+  for (const fileNode of files) {
+    if (yield) break
+    if (!fileNode.rawNode) continue
+    if (pathPattern && !fileNode.path.includes(pathPattern)) continue
+
+    const language = getFileLanguage(fileNode.path, fileNode.content)
+    if (!language) continue
+
+    if (!compiled.has(language.name)) {
+      compiled.set(language.name, compileQuery(source, language, compileErrors))
+    }
+    const query = compiled.get(language.name)
+    if (!query) continue
+
+    for (const capture of query.captures(fileNode.rawNode)) {
+      if (types.length > 0 && !types.includes(capture.node.type)) continue
+      if (scope && getSyntaxScope(capture.node) !== scope) continue
+      const node = createCaptureNode(capture, fileNode.path)
+      if (!inLineRange(node, lineRange)) continue
+      collect({
+        node,
+        score: 100,
+        matches: ['capture'],
+        highlight: getHighlight(node),
+        captureName: capture.name,
+      })
+    }
+  }
+
+  const compiledAny = Array.from(compiled.values()).some(query => query !== null)
+  if (!compiledAny && compileErrors.length > 0) {
+    const first = compileErrors[0]!
+    throw createError('SEARCH_ERROR', `Invalid tree-sitter query at offset ${first.offset}: ${first.message}`, {
+      offset: first.offset,
+      language: first.language,
+      reason: first.message,
+    })
+  }
+
+  return includeContentInResults(results.sort(compareResults).slice(0, maxResults), {
+    forceContentInclusion,
+    maxContentLines,
+    disableContentInclusion,
+    explicitMaxContentLines: 'maxContentLines' in options,
+  })
+}
+
+/**
+ * Matches a regular expression against file sources line by line, reporting the enclosing declaration.
+ * Patterns compiled with the `m` or `s` flag run against the whole file so they can span lines.
+ */
+export function searchByRegex(
+  pattern: string,
+  files: TreeNode[],
+  options: SearchOptions = {},
+): SearchResult[] {
+  return runToCompletion(searchByRegexSteps(pattern, files, options))
+}
+
+function* searchByRegexSteps(pattern: string, files: TreeNode[], options: SearchOptions): CancellableSteps<SearchResult[]> {
+  const {
+    maxResults = 20,
+    pathPattern,
+    lineRange,
+    regexFlags = '',
+    caseInsensitive = false,
+    scope,
+    forceContentInclusion = false,
+    maxContentLines = 150,
+    disableContentInclusion = false,
+  } = options
+
+  // JavaScript's `i` flag folds case by Unicode rules, not just ASCII
+  const regex = compileRegex(pattern, caseInsensitive && !regexFlags.includes('i') ? regexFlags + 'i' : regexFlags)
+  const results: MatchedResult[] = []
+  const collect = createCollector(results, options.onMatch)
+
+  for (const fileNode of files) {
+    if (yield) break
+    if (!fileNode.content) continue
+    if (pathPattern && !fileNode.path.includes(pathPattern)) continue
+
+    const lines = fileNode.content.split(LINE_BREAK)
+    for (const { match, segment } of findRegexMatches(regex, fileNode.content)) {
+      const result = createRegexResult(fileNode, lines, segment, match)
+      if (scope && getMatchScope(fileNode, result.node) !== scope) continue
+      if (!inLineRange(result.node, lineRange)) continue
+      collect(result)
+    }
+  }
+
+  return includeContentInResults(results.sort(compareResults).slice(0, maxResults), {
+    forceContentInclusion,
+    maxContentLines,
+    disableContentInclusion,
+    explicitMaxContentLines: 'maxContentLines' in options,
+  })
+}
+
+/**
+ * Matches a code snippet with `$NAME` metavariables (`calc.divide($A, $B:number)`) against each file's tree.
+ * The snippet is parsed in every candidate file's language; it is an error only if it parses in none.
+ */
+export function searchByStructure(
+  pattern: string,
+  files: TreeNode[],
+  options: SearchOptions = {},
+): SearchResult[] {
+  return runToCompletion(searchByStructureSteps(pattern, files, options))
+}
+
+function* searchByStructureSteps(pattern: string, files: TreeNode[], options: SearchOptions): CancellableSteps<SearchResult[]> {
+  const {
+    maxResults = 20,
+    pathPattern,
+    lineRange,
+    scope,
+    forceContentInclusion = false,
+    maxContentLines = 150,
+    disableContentInclusion = false,
+  } = options
+
+  const compiled = new Map<string, StructuralPattern | string>()
+  const results: MatchedResult[] = []
+  const collect = createCollector(results, options.onMatch)
+
+  for (const fileNode of files) {
+    if (yield) break
+    if (!fileNode.rawNode) continue
+    if (pathPattern && !fileNode.path.includes(pathPattern)) continue
+
+    const language = getFileLanguage(fileNode.path, fileNode.content)
+    if (!language) continue
+
+    if (!compiled.has(language.name)) {
+      compiled.set(language.name, compileStructuralPattern(pattern, language))
+    }
+    const structure = compiled.get(language.name)!
+    if (typeof structure === 'string') continue
+
+    for (const { node, bindings } of matchStructuralPattern(structure, fileNode.rawNode)) {
+      if (scope && getSyntaxScope(node) !== scope) continue
+      const match = createCaptureNode({ node, name: 'match' }, fileNode.path)
+      if (!inLineRange(match, lineRange)) continue
+      collect({
+        node: match,
+        score: 100,
+        matches: ['structural'],
+        highlight: getHighlight(match),
+        metavariables: Object.fromEntries(Object.entries(bindings).map(([name, bound]) => [name, {
+          text: bound.text,
+          startLine: bound.startPosition.row + 1,
+          endLine: bound.endPosition.row + 1,
+          startColumn: bound.startPosition.column,
+          endColumn: bound.endPosition.column,
+        }])),
+      })
+    }
+  }
+
+  const failures = Array.from(compiled.values()).filter((structure): structure is string => typeof structure === 'string')
+  if (failures.length > 0 && failures.length === compiled.size) {
+    throw createError('SEARCH_ERROR', `Invalid structural pattern: ${failures[0]}`, { pattern })
+  }
+
+  return includeContentInResults(results.sort(compareResults).slice(0, maxResults), {
+    forceContentInclusion,
+    maxContentLines,
+    disableContentInclusion,
+    explicitMaxContentLines: 'maxContentLines' in options,
+  })
+}
+
+/**
+ * Finds the keys of JSON and YAML files at a dotted path such as `database.host`, see compileKeyPath.
+ * Each result spans the key/value pair and carries the path, value, YAML anchor or alias, and the first
+ * occurrence of a duplicated key.
+ */
+export function searchByKeyPath(
+  query: string,
+  files: TreeNode[],
+  options: SearchOptions = {},
+): SearchResult[] {
+  return runToCompletion(searchByKeyPathSteps(query, files, options))
+}
+
+function* searchByKeyPathSteps(query: string, files: TreeNode[], options: SearchOptions): CancellableSteps<SearchResult[]> {
+  const {
+    maxResults = 20,
+    pathPattern,
+    lineRange,
+    caseInsensitive = false,
+    forceContentInclusion = false,
+    maxContentLines = 150,
+    disableContentInclusion = false,
+  } = options
+
+  const matches = compileKeyPath(query, caseInsensitive)
+  const results: MatchedResult[] = []
+  const collect = createCollector(results, options.onMatch)
+
+  for (const fileNode of files) {
+    if (yield) break
+    if (!fileNode.rawNode) continue
+    if (pathPattern && !fileNode.path.includes(pathPattern)) continue
+
+    const language = getFileLanguage(fileNode.path, fileNode.content)
+    if (!language || !KEY_PATH_LANGUAGES.has(language.name)) continue
+
+    for (const { node, segments, ...keyPath } of collectKeyPaths(fileNode.rawNode)) {
+      if (!matches(segments)) continue
+      const match = createCaptureNode({ node, name: 'keyPath' }, fileNode.path)
+      if (!inLineRange(match, lineRange)) continue
+      collect({
+        node: match,
+        score: 100,
+        matches: ['keyPath'],
+        highlight: getHighlight(match),
+        keyPath,
+      })
+    }
+  }
+
+  return includeContentInResults(results.sort(compareResults).slice(0, maxResults), {
+    forceContentInclusion,
+    maxContentLines,
+    disableContentInclusion,
+    explicitMaxContentLines: 'maxContentLines' in options,
+  })
+}
+
+export type SearchMatcher = 'name' | 'fuzzy' | 'regex' | 'tree-sitter' | 'structural' | 'keyPath'
+
+const SEARCH_STEPS: Record<SearchMatcher, (query: string, nodes: TreeNode[], options: SearchOptions) => CancellableSteps<SearchResult[]>> = {
+  'name': searchCodeSteps,
+  'fuzzy': searchFuzzySteps,
+  'regex': searchByRegexSteps,
+  'tree-sitter': searchByQuerySteps,
+  'structural': searchByStructureSteps,
+  'keyPath': searchByKeyPathSteps,
+}
+
+/**
+ * Runs one of the searches so an aborted `signal` stops it between files with a CANCELLED error.
+ * Regex, tree-sitter, structural and keyPath searches take file nodes; name and fuzzy searches take element nodes.
+ */
+export function searchCancellable(
+  matcher: SearchMatcher,
+  query: string,
+  nodes: TreeNode[],
+  options: SearchOptions = {},
+  signal?: AbortSignal,
+): Promise<SearchResult[]> {
+  return runCancellable(SEARCH_STEPS[matcher](query, nodes, options), signal)
+}
+
+/**
+ * Runs one of the searches like searchCancellable, returning the matches found so far once `deadline`
+ * (a `performance.now()` time) passes between files
+ */
+export function searchWithDeadline(
+  matcher: SearchMatcher,
+  query: string,
+  nodes: TreeNode[],
+  options: SearchOptions,
+  deadline: number,
+  signal?: AbortSignal,
+): Promise<DeadlineRun<SearchResult[]>> {
+  return runWithDeadline(SEARCH_STEPS[matcher](query, nodes, options), deadline, signal)
+}
+
+export const SEARCH_MATCHERS = Object.keys(SEARCH_STEPS) as readonly SearchMatcher[]
+// Values of the `mode` parameter, in schema order; tree-sitter queries are picked with `queryType` instead
+export const SEARCH_MODES: readonly SearchMatcher[] = ['name', 'regex', 'fuzzy', 'structural', 'keyPath']
+
+// Matchers that read whole files rather than element nodes
+export const FILE_MATCHERS: ReadonlySet<SearchMatcher> = new Set<SearchMatcher>(['regex', 'tree-sitter', 'structural', 'keyPath'])
+
+// Matchers that honor `scope`; the others match declarations or keys, which have no comment or string scope
+export const SCOPED_MATCHERS: ReadonlySet<SearchMatcher> = new Set<SearchMatcher>(['regex', 'tree-sitter', 'structural'])
+
+/**
+ * Searches a single buffer from `parseBuffer`, such as an unsaved editor buffer, without a project index
+ */
+export function searchBuffer(matcher: SearchMatcher, query: string, fileNode: TreeNode, options: SearchOptions = {}): SearchResult[] {
+  const nodes = FILE_MATCHERS.has(matcher) ? [fileNode] : extractAllNodes(fileNode)
+  return runToCompletion(SEARCH_STEPS[matcher](query, nodes, options))
+}
+
+export const SEARCH_SORTS: readonly SearchSort[] = ['relevance', 'path', 'recency']
+
+export interface SortOptions {
+  /** Project root; test and generated paths are recognized relative to it */
+  root: string
+  /** File the caller is working in, absolute or relative to the root; nearby results rank higher */
+  currentFile?: string
+  /** Indexed file nodes; `recency` reads the modification times the walk cached on them before stat-ing */
+  files?: TreeNode[]
+}
+
+/**
+ * Orders results before paging. `relevance` adds the RELEVANCE_WEIGHTS adjustments to each match score
+ * and records them in `relevance`; `path` orders by file and position; `recency` puts the most recently
+ * modified files first, breaking ties by position within the file.
+ */
+export function sortResults(results: SearchResult[], sort: SearchSort, options: SortOptions): SearchResult[] {
+  switch (sort) {
+    case 'path':
+      return [...results].sort(comparePositions)
+
+    case 'recency': {
+      const modified = new Map<string, number>()
+      for (const file of options.files ?? []) {
+        if (file.mtimeMs !== undefined) modified.set(file.path, file.mtimeMs)
+      }
+      const getModified = (path: string): number => {
+        let mtimeMs = modified.get(path)
+        if (mtimeMs === undefined) {
+          try {
+            mtimeMs = statSync(path).mtimeMs
+          }
+          catch {
+            mtimeMs = 0
+          }
+          modified.set(path, mtimeMs)
+        }
+        return mtimeMs
+      }
+      return [...results].sort((a, b) => getModified(b.node.path) - getModified(a.node.path) || comparePositions(a, b))
+    }
+
+    case 'relevance': {
+      const currentFile = options.currentFile === undefined ? undefined : resolve(options.root, options.currentFile)
+      return results
+        .map(result => ({ ...result, relevance: scoreRelevance(result, options.root, currentFile) }))
+        .sort((a, b) => b.relevance.total - a.relevance.total || compareResults(a, b))
+    }
+  }
+}
+
+function scoreRelevance(result: SearchResult, root: string, currentFile?: string): RelevanceScore {
+  const definition = isDefinitionResult(result) ? RELEVANCE_WEIGHTS.DEFINITION : 0
+  const proximity = currentFile === undefined ? 0 : scoreProximity(result.node.path, currentFile)
+
+  // Anchored at the root so a project that itself lives under a test directory is not penalized throughout
+  const relativePath = `/${relative(root, result.node.path).split(sep).join('/')}`
+  const path = isGeneratedFile(relativePath)
+    ? RELEVANCE_WEIGHTS.GENERATED_PATH
+    : isTestFile(relativePath) ? RELEVANCE_WEIGHTS.TEST_PATH : 0
+
+  return { total: result.score + definition + proximity + path, match: result.score, definition, proximity, path }
+}
+
 /**
- * Searches for code elements matching the query with progressive content inclusion
+ * Indexed symbols are declarations. A regex hit counts as one when it sits on the first line of the
+ * declaration enclosing it and covers that declaration's name; a capture when its node type declares.
  */
-export function searchCode(
-  query: string,
-  nodes: TreeNode[],
+function isDefinitionResult(result: SearchResult): boolean {
+  const { node, enclosingScope } = result
+  if (node.type === 'function' || node.type === 'class') return true
+
+  if (node.type === 'match') {
+    const text = node.content ?? ''
+    return enclosingScope !== undefined
+      && enclosingScope.startLine === node.startLine
+      && text.length > 0
+      && (text.includes(enclosingScope.name) || enclosingScope.name.includes(text))
+  }
+
+  const language = getFileLanguage(node.path)
+  return language !== undefined && Object.hasOwn(language.definitionKinds, node.type)
+}
+
+function scoreProximity(filePath: string, currentFile: string): number {
+  if (filePath === currentFile) return RELEVANCE_WEIGHTS.SAME_FILE
+
+  const from = dirname(currentFile).split(sep)
+  const to = dirname(filePath).split(sep)
+  let shared = 0
+  while (shared < from.length && shared < to.length && from[shared] === to[shared]) shared++
+
+  const hops = (from.length - shared) + (to.length - shared)
+  return Math.max(0, RELEVANCE_WEIGHTS.SAME_DIRECTORY - hops * RELEVANCE_WEIGHTS.PER_DIRECTORY_HOP)
+}
+
+export const SEARCH_DEDUP_MODES: readonly SearchDedup[] = ['range', 'line', 'none']
+
+export const TEST_SCOPES: readonly TestScope[] = ['include', 'exclude', 'only']
+
+/**
+ * Keeps the results inside or outside test code. Test files count as a whole; elsewhere a result is test
+ * code when its start is inside an inline test, such as a Rust `#[cfg(test)]` module.
+ */
+export function filterByTestScope(results: SearchResult[], files: TreeNode[], root: string, testScope: TestScope): SearchResult[] {
+  if (testScope === 'include') return results
+  const inScope = createTestScopeFilter(files, root, testScope)
+  return results.filter(result => inScope(result.node))
+}
+
+/**
+ * Tells whether a matched node is kept under `testScope`, deciding as filterByTestScope does
+ */
+export function createTestScopeFilter(files: TreeNode[], root: string, testScope: TestScope): (node: TreeNode) => boolean {
+  if (testScope === 'include') return () => true
+  const sources = new Map(files.map(file => [file.path, file]))
+
+  return ({ path, startLine, startColumn = 0 }) => {
+    let isTest = isTestPath(relative(root, path))
+
+    const fileNode = sources.get(path)
+    const language = fileNode && getFileLanguage(fileNode.path, fileNode.content)
+    if (!isTest && fileNode?.rawNode && language && startLine) {
+      isTest = isInlineTestCode(fileNode.rawNode.descendantForPosition({ row: startLine - 1, column: startColumn }), language)
+    }
+    return testScope === 'only' ? isTest : !isTest
+  }
+}
+
+/**
+ * Drops results covering the same file and span, keeping the first, so run it after sorting. `line` also
+ * folds every result on a line into the first one there, merging their `highlight` ranges.
+ */
+export function dedupResults(results: SearchResult[], mode: SearchDedup): SearchResult[] {
+  if (mode === 'none') return results
+
+  const kept = new Map<string, SearchResult>()
+  for (const result of results) {
+    const key = getDedupKey(result.node, mode)
+    const first = kept.get(key)
+    if (!first) {
+      kept.set(key, mode === 'line' ? { ...result, highlight: [...result.highlight ?? []] } : result)
+      continue
+    }
+    if (mode === 'line') {
+      const ranges = first.highlight!
+      for (const range of result.highlight ?? []) {
+        if (!ranges.some(existing => existing.start === range.start && existing.end === range.end)) ranges.push(range)
+      }
+      ranges.sort((a, b) => a.start - b.start || a.end - b.end)
+    }
+  }
+  return Array.from(kept.values())
+}
+
+export interface ResultCounter {
+  /** Matches added so far, before dedup */
+  added: number
+  add: (node: TreeNode) => void
+  /** Matches per file as `dedupResults` would leave them, in path order */
+  counts: () => Map<string, number>
+}
+
+/**
+ * Counts matches per file as they are found, keeping only their dedup keys rather than the results
+ */
+export function createResultCounter(mode: SearchDedup): ResultCounter {
+  const counts = new Map<string, number>()
+  const seen = new Set<string>()
+  const counter: ResultCounter = {
+    added: 0,
+    add: (node) => {
+      counter.added++
+      if (mode !== 'none') {
+        const key = getDedupKey(node, mode)
+        if (seen.has(key)) return
+        seen.add(key)
+      }
+      counts.set(node.path, (counts.get(node.path) ?? 0) + 1)
+    },
+    counts: () => new Map([...counts].sort(([a], [b]) => compareCodeUnits(a, b))),
+  }
+  return counter
+}
+
+// Results sharing this key are one result after dedup
+function getDedupKey(node: TreeNode, mode: Exclude<SearchDedup, 'none'>): string {
+  return mode === 'line'
+    ? `${node.path}:${node.startLine}`
+    : `${node.path}:${node.startLine}:${node.startColumn}:${node.endLine}:${node.endColumn}`
+}
+
+/**
+ * Slices one page out of a fully ranked result list, applying content inclusion to that page alone
+ */
+export function paginateResults(
+  results: SearchResult[],
+  offset: number,
+  limit: number,
   options: SearchOptions = {},
-): SearchResult[] {
+): SearchPage {
   const {
-    maxResults = 20,
-    fuzzyThreshold = 30,
-    exactMatch = false,
-    types = [],
-    pathPattern,
     forceContentInclusion = false,
     maxContentLines = 150,
     disableContentInclusion = false,
   } = options
 
-  // First pass: collect all matching results without content
-  const initialResults: Omit<SearchResult, 'contentIncluded' | 'content' | 'contentTruncated' | 'contentLines'>[] = []
+  const page = results.slice(offset, offset + limit)
+  return {
+    results: includeContentInResults(page, {
+      forceContentInclusion,
+      maxContentLines,
+      disableContentInclusion,
+      explicitMaxContentLines: 'maxContentLines' in options,
+    }),
+    totalResults: results.length,
+    hasMore: offset + page.length < results.length,
+  }
+}
 
-  function collectMatches(currentNodes: TreeNode[]) {
-    for (const node of currentNodes) {
-      if (types.length > 0 && !types.includes(node.type)) continue
-      if (pathPattern && !node.path.includes(pathPattern)) continue
-
-      const score = calculateScore(query, node, exactMatch, fuzzyThreshold)
-      if (score > 0) {
-        initialResults.push({
-          node: createLightweightTreeNode(node),
-          score,
-          matches: getMatches(query, node),
-        })
-      }
+/**
+ * Replaces each result's `context` with the source lines around it, clamped to the file bounds.
+ * Regex hits are widened around every matched line, other results around their first line.
+ * Windows are computed per result and never merged; `contextStartLine`/`contextEndLine` let callers dedupe.
+ */
+export function addContextLines(
+  results: SearchResult[],
+  files: TreeNode[],
+  contextBefore: number,
+  contextAfter: number,
+): SearchResult[] {
+  const sources = new Map(files.map(file => [file.path, file]))
+  const lineCache = new Map<string, string[]>()
 
-      if (node.children) {
-        collectMatches(node.children)
+  return results.map((result) => {
+    const { path, startLine } = result.node
+    const content = sources.get(path)?.content
+    if (!content || !startLine) return result
+
+    let lines = lineCache.get(path)
+    if (!lines) {
+      lines = content.split(LINE_BREAK)
+      lineCache.set(path, lines)
+    }
+
+    const matchEndLine = result.matches.includes('regex') ? result.node.endLine ?? startLine : startLine
+    const contextStartLine = Math.max(1, startLine - contextBefore)
+    const contextEndLine = Math.min(lines.length, matchEndLine + contextAfter)
+
+    return {
+      ...result,
+      context: lines.slice(contextStartLine - 1, contextEndLine).join('\n'),
+      contextStartLine,
+      contextEndLine,
+    }
+  })
+}
+
+/**
+ * Fills in `enclosingPath` from each result's start position in its file's syntax tree
+ */
+export function addEnclosingPaths(results: SearchResult[], files: TreeNode[]): SearchResult[] {
+  const sources = new Map(files.map(file => [file.path, file]))
+
+  return results.map((result) => {
+    const { path, startLine, startColumn = 0 } = result.node
+    const fileNode = sources.get(path)
+    const language = fileNode && getFileLanguage(fileNode.path, fileNode.content)
+    if (!fileNode?.rawNode || !language || !startLine) return result
+
+    const node = fileNode.rawNode.descendantForPosition({ row: startLine - 1, column: startColumn })
+    return { ...result, enclosingPath: getEnclosingPath(node, language) }
+  })
+}
+
+/**
+ * Fills in `nodeText` with the source of the innermost declaration around each result, such as the whole
+ * function a match sits in; outside any declaration it is the top-level statement holding the match. Text
+ * longer than `maxBytes` (UTF-8) is cut after the last statement that fits and marked `truncated`.
+ */
+export function addNodeTexts(results: SearchResult[], files: TreeNode[], maxBytes: number): SearchResult[] {
+  const sources = new Map(files.map(file => [file.path, file]))
+
+  return results.map((result) => {
+    const { path, startLine, startColumn = 0 } = result.node
+    const fileNode = sources.get(path)
+    const language = fileNode && getFileLanguage(fileNode.path, fileNode.content)
+    if (!fileNode?.rawNode || !fileNode.content || !language || !startLine) return result
+
+    const endLine = result.node.endLine ?? startLine
+    const endColumn = result.node.endColumn ?? startColumn
+    const matched = fileNode.rawNode.namedDescendantForPosition(
+      { row: startLine - 1, column: startColumn },
+      { row: endLine - 1, column: endColumn },
+    )
+    const node = findTextNode(matched, language)
+    const { text, truncated } = cutNodeText(node, fileNode.content, maxBytes)
+    return {
+      ...result,
+      nodeText: {
+        type: node.type,
+        startLine: node.startPosition.row + 1,
+        endLine: node.endPosition.row + 1,
+        text,
+        ...truncated ? { truncated } : {},
+      },
+    }
+  })
+}
+
+function findTextNode(node: Parser.SyntaxNode, language: LanguageConfig): Parser.SyntaxNode {
+  const declaration = findEnclosingDeclaration(node, language)
+  if (declaration) return declaration.node
+
+  let topLevel = node
+  while (topLevel.parent?.parent) topLevel = topLevel.parent
+  return topLevel
+}
+
+// Cuts end where a named node ends its line, which is a statement end in every supported grammar
+function cutNodeText(node: Parser.SyntaxNode, content: string, maxBytes: number): { text: string, truncated: boolean } {
+  const text = content.substring(node.startIndex, node.endIndex)
+  if (Buffer.byteLength(text) <= maxBytes) return { text, truncated: false }
+
+  // A character is at least one byte, so nodes starting or ending past maxBytes characters never fit
+  const ends: number[] = []
+  const visit = (current: Parser.SyntaxNode): void => {
+    for (const child of current.namedChildren) {
+      if (child.startIndex - node.startIndex >= maxBytes) return
+      if (child.endIndex - node.startIndex <= maxBytes && /^\r?\n/.test(content.substring(child.endIndex, child.endIndex + 2))) {
+        ends.push(child.endIndex)
       }
+      visit(child)
+    }
+  }
+  visit(node)
+
+  let cut = ends.sort((a, b) => b - a).find(end => Buffer.byteLength(content.substring(node.startIndex, end)) <= maxBytes) ?? 0
+  if (cut === 0) {
+    // No statement fits, so keep whole characters up to the byte cap
+    cut = node.startIndex
+    let bytes = 0
+    for (const char of text) {
+      bytes += Buffer.byteLength(char)
+      if (bytes > maxBytes) break
+      cut += char.length
     }
   }
+  return { text: content.substring(node.startIndex, cut), truncated: true }
+}
+
+type MatchedResult = Omit<SearchResult, 'contentIncluded' | 'content' | 'contentTruncated' | 'contentLines'>
+
+// Hands each match to `onMatch` when one is given, so a count keeps no result list
+function createCollector(results: MatchedResult[], onMatch?: (node: TreeNode) => void): (result: MatchedResult) => void {
+  return onMatch ? result => onMatch(result.node) : (result) => {
+    results.push(result)
+  }
+}
+
+/**
+ * Rejects a name or fuzzy query shorter than `minQueryLength` characters, which would match almost every
+ * element. An empty query is left alone: it lists every element on purpose.
+ */
+function checkQueryLength(query: string, minQueryLength: number): void {
+  const length = [...query].length
+  if (length === 0 || length >= minQueryLength) return
+
+  throw createError('SEARCH_ERROR', `Query "${query}" is shorter than ${minQueryLength} characters and would match almost every name. Use wholeWord or exactMatch to find a short name, regex mode for a pattern, or lower minQueryLength`, { query, minQueryLength })
+}
+
+/**
+ * Clamps a requested line span to the lines a file actually has, defaulting either end to the file's edge,
+ * so a span running past the end still searches what is there.
+ */
+export function clampLineRange(fileNode: TreeNode, startLine?: number, endLine?: number): LineRange {
+  const lineCount = fileNode.content?.split(LINE_BREAK).length ?? 1
+  const start = Math.min(Math.max(1, startLine ?? 1), lineCount)
+  return { startLine: start, endLine: Math.min(Math.max(start, endLine ?? lineCount), lineCount) }
+}
+
+// Whether a match overlaps the line range; file nodes carry no lines and always do
+function inLineRange(node: TreeNode, range: LineRange | undefined): boolean {
+  if (!range || node.startLine === undefined) return true
+  return node.startLine <= range.endLine && (node.endLine ?? node.startLine) >= range.startLine
+}
 
-  collectMatches(nodes)
+/**
+ * Walks nodes depth-first, passing each matching node once (the same node can be reached through
+ * several paths). Returning `false` from `onMatch` stops the walk.
+ */
+function* visitMatches(
+  query: string,
+  nodes: TreeNode[],
+  options: NameMatchOptions & { types: string[], pathPattern?: string, kind?: KindFilter, lineRange?: LineRange },
+  onMatch: (result: MatchedResult) => boolean | void,
+): CancellableSteps<void> {
+  const { types, pathPattern, kind, lineRange } = options
+  if (kind) {
+    yield* visitDeclarations(query, nodes, { ...options, kind }, onMatch)
+    return
+  }
 
-  // Remove duplicates by node ID (same node can appear multiple times due to different match paths)
+  const qualified = parseQualifiedQuery(query)
+  const files = new Map(qualified ? nodes.filter(node => node.type === 'file').map(file => [file.path, file]) : [])
+  const locate = (node: TreeNode): DeclarationSite | undefined => {
+    const fileNode = files.get(node.path)
+    const language = fileNode && getFileLanguage(fileNode.path, fileNode.content)
+    if (!fileNode?.rawNode || !language || !node.startLine) return undefined
+    return { node: fileNode.rawNode.descendantForPosition({ row: node.startLine - 1, column: node.startColumn ?? 0 }), language }
+  }
   const seenNodeIds = new Set<string>()
-  const uniqueResults = initialResults.filter((result) => {
-    if (seenNodeIds.has(result.node.id)) {
-      return false
+
+  function visit(currentNodes: TreeNode[]): boolean {
+    for (const node of currentNodes) {
+      const included = (types.length === 0 || types.includes(node.type)) && (!pathPattern || node.path.includes(pathPattern)) && inLineRange(node, lineRange)
+
+      if (included && !seenNodeIds.has(node.id)) {
+        const { score, term } = scoreNameQuery(query, qualified, node, () => locate(node), options)
+        if (score > 0) {
+          seenNodeIds.add(node.id)
+          const next = onMatch({
+            node: createLightweightTreeNode(node),
+            score,
+            matches: getMatches(term, node),
+            highlight: getHighlight(node),
+          })
+          if (next === false) return false
+        }
+      }
+
+      if (node.children && !visit(node.children)) return false
     }
-    seenNodeIds.add(result.node.id)
     return true
-  })
+  }
 
-  // Sort and slice to get final result set
-  const sortedResults = uniqueResults
-    .sort((a, b) => b.score - a.score)
-    .slice(0, maxResults)
+  // Top-level nodes are files or their elements, so yielding after each one checks in between files
+  for (const node of nodes) {
+    if (!visit([node])) return
+    if (yield) return
+  }
+}
 
-  // Apply progressive content inclusion based on result count
-  return includeContentInResults(sortedResults, {
-    forceContentInclusion,
-    maxContentLines,
-    disableContentInclusion,
-    explicitMaxContentLines: 'maxContentLines' in options,
+/**
+ * Name matching over the declarations a kind filter keeps, read from each file's tree rather than the
+ * indexed elements so every outline symbol (structs, consts, type aliases) can be found
+ */
+function* visitDeclarations(
+  query: string,
+  nodes: TreeNode[],
+  options: NameMatchOptions & { types: string[], pathPattern?: string, kind: KindFilter, lineRange?: LineRange },
+  onMatch: (result: MatchedResult) => boolean | void,
+): CancellableSteps<void> {
+  const { types, pathPattern, kind, lineRange } = options
+  const qualified = parseQualifiedQuery(query)
+
+  for (const fileNode of nodes) {
+    if (fileNode.type !== 'file' || !fileNode.rawNode) continue
+    if (pathPattern && !fileNode.path.includes(pathPattern)) continue
+
+    const language = getFileLanguage(fileNode.path, fileNode.content)
+    for (const declaration of listDeclarations(fileNode)) {
+      if (!matchesKind(declaration, kind)) continue
+      if (types.length > 0 && !types.includes(declaration.kind)) continue
+
+      const node = createDeclarationNode(declaration, fileNode.path)
+      if (!inLineRange(node, lineRange)) continue
+      const { score, term } = scoreNameQuery(query, qualified, node, () => language && { node: declaration.node, language }, options)
+      if (score === 0) continue
+
+      const next = onMatch({
+        node,
+        score,
+        matches: getMatches(term, node),
+        highlight: getHighlight(node),
+      })
+      if (next === false) return
+    }
+    if (yield) return
+  }
+}
+
+function createDeclarationNode(declaration: Declaration, filePath: string): TreeNode {
+  const { node } = declaration
+  return {
+    id: `declaration-${filePath}-${node.startIndex}`,
+    type: declaration.kind,
+    name: declaration.name,
+    path: filePath,
+    startLine: node.startPosition.row + 1,
+    endLine: node.endPosition.row + 1,
+    startColumn: node.startPosition.column,
+    endColumn: node.endPosition.column,
+    content: node.text,
+  }
+}
+
+/**
+ * Orders results by score, then file path and position, so repeated queries page without duplicates or gaps
+ */
+function compareResults(a: Pick<SearchResult, 'node' | 'score'>, b: Pick<SearchResult, 'node' | 'score'>): number {
+  return b.score - a.score || comparePositions(a, b)
+}
+
+/**
+ * The total order every sort ends with: file path by code unit, so it does not depend on the locale, then
+ * start and end. Columns count UTF-16 code units from the start of their line, and encoding a line never
+ * reorders its characters, so line and column order matches byte offsets in any encoding. Results tied on
+ * all of these share a file and span and keep the order that file's walk found them in, whatever order
+ * the files were searched in.
+ */
+function comparePositions(a: Pick<SearchResult, 'node'>, b: Pick<SearchResult, 'node'>): number {
+  return compareCodeUnits(a.node.path, b.node.path)
+    || (a.node.startLine ?? 0) - (b.node.startLine ?? 0)
+    || (a.node.startColumn ?? 0) - (b.node.startColumn ?? 0)
+    || (a.node.endLine ?? 0) - (b.node.endLine ?? 0)
+    || (a.node.endColumn ?? 0) - (b.node.endColumn ?? 0)
+}
+
+function compareCodeUnits(a: string, b: string): number {
+  return a < b ? -1 : a > b ? 1 : 0
+}
+
+/**
+ * Resolves the scope of the deepest node containing the whole match; a match that crosses
+ * a comment or string boundary is only contained by code, so it counts as code
+ */
+function getMatchScope(fileNode: TreeNode, match: TreeNode): SyntaxScope {
+  if (!fileNode.rawNode) return 'code'
+
+  const node = fileNode.rawNode.descendantForPosition(
+    { row: match.startLine! - 1, column: match.startColumn! },
+    { row: match.endLine! - 1, column: match.endColumn! },
+  )
+  return getSyntaxScope(node)
+}
+
+export interface RegexSegment {
+  text: string
+  /** 0-based line the segment starts on */
+  lineOffset: number
+  /** Offset of the segment's first character in the file content */
+  start: number
+}
+
+export interface RegexMatch {
+  match: RegExpExecArray
+  segment: RegexSegment
+}
+
+/**
+ * Runs a global regex over file content one line at a time, or over the whole text when the `m` or `s`
+ * flag lets it span lines. Empty matches are skipped.
+ */
+export function* findRegexMatches(regex: RegExp, content: string): Generator<RegexMatch> {
+  const segments = regex.multiline || regex.dotAll
+    ? [{ text: content, lineOffset: 0, start: 0 }]
+    : splitLineSegments(content)
+
+  for (const segment of segments) {
+    regex.lastIndex = 0
+    let match
+    while ((match = regex.exec(segment.text)) !== null) {
+      if (match[0].length === 0) {
+        regex.lastIndex++
+        continue
+      }
+      yield { match, segment }
+    }
+  }
+}
+
+function splitLineSegments(content: string): RegexSegment[] {
+  let start = 0
+  return content.split(LINE_BREAK).map((text, lineOffset) => {
+    const segment = { text, lineOffset, start }
+    start += text.length + (content[start + text.length] === '\r' ? 2 : 1)
+    return segment
   })
 }
 
+export function compileRegex(pattern: string, flags: string): RegExp {
+  try {
+    return new RegExp(pattern, flags.includes('g') ? flags : flags + 'g')
+  }
+  catch (error) {
+    const message = error instanceof Error ? error.message : String(error)
+    throw createError('SEARCH_ERROR', message, { pattern, flags })
+  }
+}
+
+function createRegexResult(
+  fileNode: TreeNode,
+  lines: string[],
+  segment: RegexSegment,
+  match: RegExpExecArray,
+): Omit<SearchResult, 'contentIncluded' | 'content' | 'contentTruncated' | 'contentLines'> {
+  const before = segment.text.substring(0, match.index).split(LINE_BREAK)
+  const startRow = segment.lineOffset + before.length - 1
+  const startColumn = before[before.length - 1]!.length
+  const matchLines = match[0].split(LINE_BREAK)
+  const endRow = startRow + matchLines.length - 1
+  const endColumn = matchLines.length > 1
+    ? matchLines[matchLines.length - 1]!.length
+    : startColumn + match[0].length
+
+  const language = getFileLanguage(fileNode.path, fileNode.content)
+  const enclosingScope = fileNode.rawNode && language
+    ? findEnclosingScope(fileNode.rawNode.descendantForPosition({ row: startRow, column: startColumn }), language)
+    : undefined
+
+  const node: TreeNode = {
+    id: `regex-${fileNode.path}-${startRow}-${startColumn}`,
+    type: 'match',
+    name: matchLines[0],
+    path: fileNode.path,
+    startLine: startRow + 1,
+    endLine: endRow + 1,
+    startColumn,
+    endColumn,
+    content: match[0],
+  }
+
+  return {
+    node,
+    score: 100,
+    matches: ['regex'],
+    highlight: getHighlight(node),
+    context: (lines[startRow] || '').trim(),
+    enclosingScope,
+  }
+}
+
+interface TreeSitterQueryError {
+  language: string
+  offset: number
+  message: string
+}
+
+function compileQuery(source: string, language: LanguageConfig, errors: TreeSitterQueryError[]): Parser.Query | null {
+  const grammar = getGrammar(language.name)
+  if (!grammar) return null
+
+  try {
+    return new Parser.Query(grammar, source)
+  }
+  catch (error) {
+    const message = error instanceof Error ? error.message : String(error)
+    const offsetMatch = message.match(/(?:offset|position)\s+(\d+)/i)
+    errors.push({
+      language: language.name,
+      offset: offsetMatch ? Number(offsetMatch[1]) : 0,
+      message,
+    })
+    getLogger().debug(`Query not applicable to ${language.name}: ${message}`)
+    return null
+  }
+}
+
+function createCaptureNode(capture: Pick<Parser.QueryCapture, 'node' | 'name'>, filePath: string): TreeNode {
+  const { node } = capture
+  return {
+    id: `capture-${filePath}-${node.startIndex}-${capture.name}`,
+    type: node.type,
+    name: node.text.split(LINE_BREAK)[0],
+    path: filePath,
+    startLine: node.startPosition.row + 1,
+    endLine: node.endPosition.row + 1,
+    startColumn: node.startPosition.column,
+    endColumn: node.endPosition.column,
+    content: node.text,
+  }
+}
+
+/**
+ * Columns of the matched text on the result's first line: the name of a symbol, or the matched text of
+ * a regex, query or structural match up to the end of its first line
+ */
+function getHighlight(node: TreeNode): MatchRange[] {
+  if (!node.name || node.content === undefined) return []
+
+  const index = node.content.split(LINE_BREAK)[0]!.indexOf(node.name)
+  if (index === -1) return []
+
+  const start = (node.startColumn ?? 0) + index
+  return [{ start, end: start + node.name.length }]
+}
+
 /**
  * Applies progressive content inclusion logic based on result count
  */
@@ -183,27 +1427,147 @@ function includeContentInResults(
   })
 }
 
-function calculateScore(query: string, node: TreeNode, exactMatch: boolean, fuzzyThreshold: number): number {
+interface NameMatchOptions {
+  exactMatch: boolean
+  wholeWord: boolean
+  subwordAware: boolean
+  caseInsensitive: boolean
+  fuzzyThreshold: number
+}
+
+function calculateScore(query: string, node: TreeNode, options: NameMatchOptions): number {
+  const { exactMatch, wholeWord, subwordAware, caseInsensitive, fuzzyThreshold } = options
   const name = node.name || ''
-  const queryLower = query.toLowerCase()
-  const nameLower = name.toLowerCase()
+  const fold = caseInsensitive ? foldCase : (text: string) => text
+  const queryFolded = fold(query)
+  const nameFolded = fold(name)
+
+  // Receiver-qualified methods (Go `Calculator.Add`) and C# generics (`Map<TResult>`) also match on the bare method name
+  const bareName = name.replace(/<.*>$/, '')
+  const memberName = bareName.substring(bareName.lastIndexOf('.') + 1)
 
   if (exactMatch) {
-    return name === query ? 100 : 0
+    return nameFolded === queryFolded || fold(memberName) === queryFolded ? 100 : 0
   }
 
-  if (name === query) return 100
+  if (name === query || memberName === query) return 100
+
+  if (nameFolded === queryFolded) return 95
 
-  if (nameLower === queryLower) return 95
+  if (subwordAware) return scoreSubwordMatch(query, name)
+  if (wholeWord) return splitWords(name).some(word => fold(word) === queryFolded) ? 80 : 0
 
-  if (nameLower.startsWith(queryLower)) return 85
+  if (nameFolded.startsWith(queryFolded)) return 85
 
-  if (nameLower.includes(queryLower)) return 70
+  if (nameFolded.includes(queryFolded)) return 70
 
-  const fuzzyScore = calculateFuzzyScore(queryLower, nameLower)
+  const fuzzyScore = calculateFuzzyScore(queryFolded, nameFolded)
   return fuzzyScore >= fuzzyThreshold ? fuzzyScore : 0
 }
 
+// `Calculator::add` or `Calculator.add`: a member name after one or more qualifiers, with one separator
+const QUALIFIED_QUERY = /^[\p{L}\p{N}_$]+(?:(::|\.)[\p{L}\p{N}_$]+)+$/u
+
+interface QualifiedQuery {
+  separator: string
+  qualifiers: string[]
+  member: string
+}
+
+// A declaration's syntax node and language, read to resolve the types and modules around it
+interface DeclarationSite {
+  node: Parser.SyntaxNode
+  language: LanguageConfig
+}
+
+function parseQualifiedQuery(query: string): QualifiedQuery | undefined {
+  const separator = QUALIFIED_QUERY.exec(query)?.[1]
+  if (!separator || query.includes(separator === '::' ? '.' : '::')) return undefined
+
+  const segments = query.split(separator)
+  return { separator, qualifiers: segments.slice(0, -1), member: segments.at(-1)! }
+}
+
+/**
+ * Scores a name query. A qualified query also matches its member name on declarations whose enclosing
+ * path ends with its qualifiers, written with the language's scope separator (`Calculator::add` in Rust,
+ * `Calculator.add` in TypeScript); the literal reading still counts, as some names carry their receiver.
+ * Returns the term that matched, for `matches`.
+ */
+function scoreNameQuery(
+  query: string,
+  qualified: QualifiedQuery | undefined,
+  node: TreeNode,
+  locate: () => DeclarationSite | undefined,
+  options: NameMatchOptions,
+): { score: number, term: string } {
+  const score = calculateScore(query, node, options)
+  if (!qualified) return { score, term: query }
+
+  const memberScore = calculateScore(qualified.member, node, options)
+  if (memberScore <= score) return { score, term: query }
+
+  const site = locate()
+  return site && isQualifiedBy(site, qualified, options.caseInsensitive)
+    ? { score: memberScore, term: qualified.member }
+    : { score, term: query }
+}
+
+function isQualifiedBy({ node, language }: DeclarationSite, query: QualifiedQuery, caseInsensitive: boolean): boolean {
+  const separator = language.scopeSeparator ?? '.'
+  if (query.separator !== separator) return false
+
+  const fold = caseInsensitive ? foldCase : (text: string) => text
+  // The last segment is the declaration's own name; generic parameters (`Stack<T>`) are not compared
+  const scopes = (getEnclosingPath(node, language) ?? '').split(separator).slice(0, -1)
+    .map(scope => fold(scope.replace(/<.*>$/, '')))
+  const offset = scopes.length - query.qualifiers.length
+  return offset >= 0 && query.qualifiers.every((qualifier, index) => scopes[offset + index] === fold(qualifier))
+}
+
+/**
+ * Unicode case folding for name comparisons; upper-casing first also folds `ß` to `ss`, which
+ * lower-casing alone leaves apart from `SS`
+ */
+function foldCase(text: string): string {
+  return text.toUpperCase().toLowerCase()
+}
+
+// Identifier characters; anything else in a name (`.`, `::`, `<>`, operator symbols) separates words
+const NON_WORD = /[^\p{L}\p{N}_$]+/u
+
+function splitWords(name: string): string[] {
+  return name.split(NON_WORD).filter(Boolean)
+}
+
+/**
+ * Splits each word at `_` and camelCase humps, keeping acronyms and digit runs whole:
+ * `parseHTTPResponse2` gives `parse`, `HTTP`, `Response`, `2`
+ */
+function splitSubwords(text: string): string[] {
+  return splitWords(text).flatMap(word =>
+    word.split(/[_$]+/).flatMap(part => part.match(/\p{Lu}+(?!\p{Ll})|\p{Lu}?\p{Ll}+|\p{N}+|[^\p{Lu}\p{Ll}\p{N}]+/gu) ?? []),
+  )
+}
+
+/**
+ * The query's segments must equal a run of the name's segments, so `User` and `user_name` match
+ * `getUserName` but `ser` does not. Runs starting the name rank above runs inside it. Segments
+ * always compare without case, since camelCase capitalizes every segment but the first.
+ */
+function scoreSubwordMatch(query: string, name: string): number {
+  const querySegments = splitSubwords(query).map(foldCase)
+  const nameSegments = splitSubwords(name).map(foldCase)
+  if (querySegments.length === 0) return 0
+
+  for (let start = 0; start + querySegments.length <= nameSegments.length; start++) {
+    if (querySegments.every((segment, i) => nameSegments[start + i] === segment)) {
+      return start === 0 ? 85 : 75
+    }
+  }
+  return 0
+}
+
 function calculateFuzzyScore(query: string, target: string): number {
   if (query.length === 0) return 100
   if (target.length === 0) return 0
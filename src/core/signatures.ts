@@ -0,0 +1,223 @@
+/**
+ * Function signatures - names, parameters, return types and visibility of declarations, without their bodies
+ */
+
+import type Parser from 'tree-sitter'
+import { createHash } from 'crypto'
+import { getFileLanguage } from './languages.js'
+import { IDENTIFIER_TYPES, getDefinitionNameNode, getQualifiedName } from './definitions.js'
+import { getCallableKind } from './symbols.js'
+import { PARSER_NAMES } from '../constants/index.js'
+import type { TreeNode, FunctionSignature, LanguageConfig, SignatureParameter } from '../types/core.js'
+
+// Return types live under different fields per grammar; Java, C and C++ put them in `type`
+const RETURN_TYPE_FIELDS = ['return_type', 'result', 'returns', 'type']
+const SKIPPED_PARAMETER_TYPES = new Set(['comment', 'attribute_item', 'keyword_separator', 'positional_separator'])
+const VISIBILITY_WORD = /\b(public|private|protected|internal)\b/
+// `private(set)` only narrows the setter
+const SWIFT_VISIBILITY_WORD = /\b(open|public|internal|fileprivate|private)\b(?!\s*\(set\))/
+
+export interface ExtractSignaturesOptions {
+  /** Add `bodyHash`, a hash of each body with whitespace collapsed, for telling moved code apart from new code */
+  bodyHash?: boolean
+}
+
+/**
+ * Lists the signatures of a file's top-level functions and the methods of its types, in source order.
+ * Functions nested in other functions are not part of an API surface and are left out.
+ */
+export function extractSignatures(fileNode: TreeNode, options: ExtractSignaturesOptions = {}): FunctionSignature[] {
+  if (!fileNode.rawNode) return []
+
+  const language = getFileLanguage(fileNode.path, fileNode.content)
+  if (!language) return []
+
+  const signatures: FunctionSignature[] = []
+
+  function visit(node: Parser.SyntaxNode) {
+    const kind = getCallableKind(node, language!)
+    const nameNode = kind ? getDefinitionNameNode(node, language!) : null
+    if (kind && nameNode) {
+      signatures.push(buildSignature(node, nameNode, kind, fileNode.path, language!, options))
+      return
+    }
+
+    for (const child of node.namedChildren) {
+      visit(child)
+    }
+  }
+
+  visit(fileNode.rawNode)
+  return signatures
+}
+
+function buildSignature(
+  node: Parser.SyntaxNode,
+  nameNode: Parser.SyntaxNode,
+  kind: string,
+  path: string,
+  language: LanguageConfig,
+  options: ExtractSignaturesOptions,
+): FunctionSignature {
+  // `const add = (a, b) => ...` keeps its parameters on the function value
+  const fn = node.type === 'variable_declarator' ? node.childForFieldName('value') || node : node
+  const body = options.bodyHash ? getBody(fn) : null
+
+  return {
+    name: getQualifiedName(node, nameNode),
+    kind,
+    path,
+    line: node.startPosition.row + 1,
+    signature: getHeaderText(node, fn),
+    parameters: getParameters(fn),
+    returnType: getReturnType(fn),
+    visibility: getVisibility(node, nameNode, language),
+    ...body ? { bodyHash: createHash('sha1').update(collapse(body.text)).digest('hex') } : {},
+  }
+}
+
+function getBody(fn: Parser.SyntaxNode): Parser.SyntaxNode | null {
+  return fn.childForFieldName('body') || fn.namedChildren.find(child => child.type === 'function_body') || null
+}
+
+function getHeaderText(node: Parser.SyntaxNode, fn: Parser.SyntaxNode): string {
+  const body = getBody(fn)
+  const text = body ? node.text.slice(0, body.startIndex - node.startIndex) : node.text.split('\n')[0]!
+  return collapse(text)
+}
+
+function getParameters(fn: Parser.SyntaxNode): SignatureParameter[] {
+  // A lone arrow function parameter (`x => x * 2`) has no parameter list around it
+  const single = fn.childForFieldName('parameter')
+  if (single) return [{ name: single.text, type: null }]
+
+  // Swift's parameters sit directly on the declaration
+  const list = findParameterList(fn)
+  const parameters = list ? list.namedChildren : fn.namedChildren.filter(child => child.type === 'parameter')
+
+  return parameters
+    .filter(child => !SKIPPED_PARAMETER_TYPES.has(child.type))
+    .flatMap(readParameter)
+}
+
+/**
+ * C and C++ keep the parameter list on the function declarator, Kotlin on an unnamed child
+ */
+function findParameterList(fn: Parser.SyntaxNode): Parser.SyntaxNode | null {
+  const parameters = fn.childForFieldName('parameters')
+  if (parameters) return parameters
+
+  let declarator = fn.childForFieldName('declarator')
+  while (declarator && declarator.type !== 'function_declarator') {
+    declarator = declarator.childForFieldName('declarator')
+  }
+  return declarator?.childForFieldName('parameters')
+    || fn.namedChildren.find(child => child.type === 'function_value_parameters')
+    || null
+}
+
+function readParameter(parameter: Parser.SyntaxNode): SignatureParameter[] {
+  const typeNode = parameter.childForFieldName('type')
+    || parameter.namedChildren.find(child => child.type === 'user_type' || child.type === 'nullable_type' || child.type === 'function_type')
+  const type = typeNode ? cleanType(typeNode.text) : null
+
+  // Go declares several names with one type (`a, b float64`)
+  const names = parameter.childrenForFieldName('name')
+  if (names.length > 1) return names.map(name => ({ name: name.text, type }))
+
+  // Bare names and destructuring or splat patterns (`{ a, b }`, `*args`) are the whole parameter
+  if (IDENTIFIER_TYPES.has(parameter.type) || parameter.type === 'self_parameter' || parameter.type.endsWith('_pattern')) {
+    return [{ name: collapse((parameter.childForFieldName('left') || parameter).text), type: null }]
+  }
+
+  const nameNode = names[0]
+    || parameter.childForFieldName('pattern')
+    || parameter.childForFieldName('declarator')
+    || parameter.namedChildren.find(child => child !== typeNode)
+    || null
+  const name = nameNode ? collapse(unwrapDeclarator(nameNode).text) : null
+
+  // C's `f(void)` takes no parameters
+  if (!name && type === 'void') return []
+  return [{ name, type }]
+}
+
+// `char *name` and `int values[]` wrap the name in declarators
+function unwrapDeclarator(node: Parser.SyntaxNode): Parser.SyntaxNode {
+  let current = node
+  while (current.type.endsWith('_declarator')) {
+    const inner = current.childForFieldName('declarator') || current.namedChildren.find(child => IDENTIFIER_TYPES.has(child.type))
+    if (!inner) break
+    current = inner
+  }
+  return current
+}
+
+function getReturnType(fn: Parser.SyntaxNode): string | null {
+  for (const field of RETURN_TYPE_FIELDS) {
+    const typeNode = fn.childForFieldName(field)
+    if (typeNode) return cleanType(typeNode.text)
+  }
+
+  // Kotlin's return type is the unnamed type after the parameter list
+  const children = fn.namedChildren
+  const parameters = children.findIndex(child => child.type === 'function_value_parameters')
+  const typeNode = parameters === -1 ? undefined : children.slice(parameters + 1).find(child => child.type.endsWith('_type'))
+  return typeNode ? cleanType(typeNode.text) : null
+}
+
+/**
+ * Explicit modifiers win; otherwise each language's convention or default applies
+ */
+function getVisibility(node: Parser.SyntaxNode, nameNode: Parser.SyntaxNode, language: LanguageConfig): string | null {
+  const modifiers = node.namedChildren.filter(child => child.type.includes('modifier'))
+  if (language.name === PARSER_NAMES.RUST) {
+    return modifiers.find(child => child.type === 'visibility_modifier')?.text || (isTraitMember(node) ? 'pub' : 'private')
+  }
+  if (language.name === PARSER_NAMES.SWIFT) {
+    return SWIFT_VISIBILITY_WORD.exec(modifiers.map(child => child.text).join(' '))?.[1] ?? 'internal'
+  }
+
+  const explicit = VISIBILITY_WORD.exec(modifiers.map(child => child.text).join(' '))
+  if (explicit) return explicit[1]!
+
+  const name = nameNode.text
+  switch (language.name) {
+    case PARSER_NAMES.JAVASCRIPT:
+    case PARSER_NAMES.TYPESCRIPT:
+    case PARSER_NAMES.TSX: {
+      if (node.type === 'method_definition') return nameNode.type === 'private_property_identifier' ? 'private' : 'public'
+      const declaration = node.type === 'variable_declarator' ? node.parent : node
+      return declaration?.parent?.type === 'export_statement' ? 'public' : 'private'
+    }
+    case PARSER_NAMES.PYTHON:
+      return name.startsWith('_') && !(name.startsWith('__') && name.endsWith('__')) ? 'private' : 'public'
+    case PARSER_NAMES.GO:
+      return /^[A-Z]/.test(name) ? 'public' : 'private'
+    case PARSER_NAMES.C:
+      return node.namedChildren.some(child => child.type === 'storage_class_specifier' && child.text === 'static') ? 'private' : 'public'
+    case PARSER_NAMES.JAVA:
+      return 'package'
+    case PARSER_NAMES.CSHARP:
+      return 'private'
+    case PARSER_NAMES.KOTLIN:
+    case PARSER_NAMES.PHP:
+      return 'public'
+    default:
+      return null
+  }
+}
+
+function isTraitMember(node: Parser.SyntaxNode): boolean {
+  const owner = node.parent?.type === 'declaration_list' ? node.parent.parent : null
+  return owner?.type === 'trait_item' || (owner?.type === 'impl_item' && owner.childForFieldName('trait') !== null)
+}
+
+// TypeScript annotations include their `:` and Python's return type its `->`
+function cleanType(text: string): string {
+  return collapse(text.replace(/^\s*(?::|->)/, ''))
+}
+
+function collapse(text: string): string {
+  return text.replace(/\s+/g, ' ').trim()
+}
\ No newline at end of file
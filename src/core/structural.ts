@@ -0,0 +1,108 @@
+/**
+ * Structural patterns - code snippets with `$NAME` metavariables, parsed like source and matched node for node
+ */
+
+import type Parser from 'tree-sitter'
+import { getParser } from './languages.js'
+import type { LanguageConfig } from '../types/core.js'
+
+// `$A` or `$A:kind`; names are upper case so `$` in JavaScript or PHP code is left alone
+const METAVARIABLE_PATTERN = /\$([A-Z_][A-Z0-9_]*)(?::([a-z_]+))?/g
+
+interface Metavariable {
+  name: string
+  /** Node type the bound subtree must have, from `$B:number` */
+  kind?: string
+}
+
+export interface StructuralPattern {
+  root: Parser.SyntaxNode
+  /** Placeholder identifier in the parsed pattern -> metavariable it stands for */
+  metavariables: Map<string, Metavariable>
+}
+
+export interface StructuralMatch {
+  node: Parser.SyntaxNode
+  bindings: Record<string, Parser.SyntaxNode>
+}
+
+/**
+ * Parses a pattern in one language, swapping metavariables for identifiers that parse anywhere an
+ * expression or name can. Returns an error message when the pattern is not valid code in that language.
+ */
+export function compileStructuralPattern(pattern: string, language: LanguageConfig): StructuralPattern | string {
+  const parser = getParser(language.name)
+  if (!parser) return `No parser for ${language.name}`
+
+  const metavariables = new Map<string, Metavariable>()
+  const placeholders = new Map<string, string>()
+  const source = pattern.trim().replace(METAVARIABLE_PATTERN, (_token, name: string, kind: string | undefined) => {
+    let placeholder = placeholders.get(name)
+    if (!placeholder) {
+      placeholder = `__tsmcp_mv_${placeholders.size}`
+      placeholders.set(name, placeholder)
+      metavariables.set(placeholder, { name })
+    }
+    if (kind) metavariables.get(placeholder)!.kind = kind
+    return placeholder
+  })
+
+  const tree = parser.parse(source)
+  if (tree.rootNode.hasError) return `Pattern is not valid ${language.name}`
+
+  const root = unwrapPattern(tree.rootNode, source.replace(/;\s*$/, ''))
+  if (metavariables.has(root.text)) return 'Pattern must contain code besides a metavariable'
+  return { root, metavariables }
+}
+
+/**
+ * Finds every subtree matching the pattern, outer matches first. A metavariable used twice must bind
+ * to the same text both times.
+ */
+export function* matchStructuralPattern(pattern: StructuralPattern, root: Parser.SyntaxNode): Generator<StructuralMatch> {
+  const stack = [root]
+  while (stack.length > 0) {
+    const node = stack.pop()!
+    if (node.type === pattern.root.type) {
+      const bindings: Record<string, Parser.SyntaxNode> = {}
+      if (matchNode(pattern.root, node, pattern.metavariables, bindings)) yield { node, bindings }
+    }
+    for (let i = node.childCount - 1; i >= 0; i--) stack.push(node.child(i)!)
+  }
+}
+
+// The program and statement wrappers around a snippet are not part of what it means
+function unwrapPattern(node: Parser.SyntaxNode, text: string): Parser.SyntaxNode {
+  let current = node
+  while (current.namedChildCount === 1) {
+    const child = current.namedChild(0)!
+    if (child.text.replace(/;\s*$/, '') !== text) break
+    current = child
+  }
+  return current
+}
+
+function matchNode(
+  pattern: Parser.SyntaxNode,
+  node: Parser.SyntaxNode,
+  metavariables: Map<string, Metavariable>,
+  bindings: Record<string, Parser.SyntaxNode>,
+): boolean {
+  const metavariable = metavariables.get(pattern.text)
+  if (metavariable) {
+    if (metavariable.kind && node.type !== metavariable.kind) return false
+    const bound = bindings[metavariable.name]
+    if (bound) return bound.text === node.text
+    bindings[metavariable.name] = node
+    return true
+  }
+
+  if (pattern.type !== node.type) return false
+  if (pattern.childCount === 0) return pattern.text === node.text
+
+  // Comments are extras in every grammar, so they may sit anywhere without breaking a match
+  const patternChildren = pattern.children.filter(child => !child.isExtra)
+  const nodeChildren = node.children.filter(child => !child.isExtra)
+  return patternChildren.length === nodeChildren.length
+    && patternChildren.every((child, i) => matchNode(child, nodeChildren[i]!, metavariables, bindings))
+}
\ No newline at end of file
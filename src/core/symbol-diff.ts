@@ -0,0 +1,122 @@
+/**
+ * Symbol diff - compares the function signatures of two git commits, file by file
+ */
+
+import { resolve } from 'path'
+import { getFileLanguage, isLanguageSelected } from './languages.js'
+import { parseContent } from './parser.js'
+import { extractSignatures } from './signatures.js'
+import { releaseTrees } from './tree-cache.js'
+import { getPathsChangedBetween, readFileAtRef } from './git.js'
+import { decodeSource } from '../utils/encoding.js'
+import type { FunctionSignature, SymbolDiff } from '../types/core.js'
+
+export interface SymbolDiffOptions {
+  /** Only files whose absolute path contains this substring are compared */
+  pathPattern?: string
+  /** Language names to compare; all languages when unset */
+  languages?: string[]
+}
+
+/**
+ * Diffs the signatures of the files changed between `from` and `to`. Declarations are matched by file
+ * and name; a removed and an added declaration with the same kind and body are reported as a rename.
+ */
+export function diffSignatures(directory: string, from: string, to: string, options: SymbolDiffOptions = {}): SymbolDiff {
+  const diff: SymbolDiff = { added: [], removed: [], changed: [], renamed: [] }
+
+  const paths = getPathsChangedBetween(directory, from, to).sort()
+  for (const path of paths) {
+    const filePath = resolve(directory, path)
+    if (options.pathPattern !== undefined && !filePath.includes(options.pathPattern)) continue
+
+    const before = readSignatures(directory, from, path, options.languages)
+    const after = readSignatures(directory, to, path, options.languages)
+    matchSignatures(before, after, diff)
+  }
+
+  detectRenames(diff)
+  return {
+    added: diff.added.map(withoutHash),
+    removed: diff.removed.map(withoutHash),
+    changed: diff.changed.map(pair => ({ before: withoutHash(pair.before), after: withoutHash(pair.after) })),
+    renamed: diff.renamed.map(pair => ({ before: withoutHash(pair.before), after: withoutHash(pair.after) })),
+  }
+}
+
+function readSignatures(directory: string, ref: string, path: string, languages?: string[]): FunctionSignature[] {
+  const buffer = readFileAtRef(directory, ref, path)
+  if (!buffer) return []
+
+  const filePath = resolve(directory, path)
+  const { content } = decodeSource(buffer)
+  const language = getFileLanguage(filePath, content)
+  if (!language || (languages?.length && !isLanguageSelected(language, languages))) return []
+
+  const fileNode = parseContent(content, filePath, language)
+  try {
+    return extractSignatures(fileNode, { bodyHash: true })
+  }
+  finally {
+    releaseTrees([fileNode])
+  }
+}
+
+/**
+ * Pairs one file's declarations by name. Identical ones cancel out first, so an overload that is
+ * untouched does not get paired with an edited sibling.
+ */
+function matchSignatures(before: FunctionSignature[], after: FunctionSignature[], diff: SymbolDiff): void {
+  const remaining = [...after]
+  const unmatched: FunctionSignature[] = []
+
+  for (const signature of before) {
+    const index = remaining.findIndex(candidate => isSameDeclaration(signature, candidate))
+    if (index === -1) unmatched.push(signature)
+    else remaining.splice(index, 1)
+  }
+
+  for (const signature of unmatched) {
+    const index = remaining.findIndex(candidate => candidate.name === signature.name)
+    if (index === -1) {
+      diff.removed.push(signature)
+      continue
+    }
+    diff.changed.push({ before: signature, after: remaining[index]! })
+    remaining.splice(index, 1)
+  }
+
+  diff.added.push(...remaining)
+}
+
+// Moved lines alone are not a change
+function isSameDeclaration(a: FunctionSignature, b: FunctionSignature): boolean {
+  return a.name === b.name && a.kind === b.kind && a.signature === b.signature && a.visibility === b.visibility
+}
+
+/**
+ * Renames may cross files, so they are looked for once every file has been compared
+ */
+function detectRenames(diff: SymbolDiff): void {
+  const removed: FunctionSignature[] = []
+
+  for (const signature of diff.removed) {
+    const index = signature.bodyHash === undefined
+      ? -1
+      : diff.added.findIndex(candidate => candidate.bodyHash === signature.bodyHash && candidate.kind === signature.kind)
+    if (index === -1) {
+      removed.push(signature)
+      continue
+    }
+    diff.renamed.push({ before: signature, after: diff.added[index]! })
+    diff.added.splice(index, 1)
+  }
+
+  diff.removed = removed
+}
+
+function withoutHash(signature: FunctionSignature): FunctionSignature {
+  const copy = { ...signature }
+  delete copy.bodyHash
+  return copy
+}
\ No newline at end of file
@@ -0,0 +1,390 @@
+/**
+ * Symbol outlines - builds a nested, LSP-style document symbol tree from tree-sitter definitions
+ */
+
+import type Parser from 'tree-sitter'
+import { LANGUAGE_CONFIGS, getDeclarationModifiers, getFileLanguage } from './languages.js'
+import { PARSER_NAMES } from '../constants/index.js'
+import { getDeclaredKind, getDefinitionNameNode, getElixirDefinitionKind, getPrototypeNameNode, getQualifiedName } from './definitions.js'
+import { createError } from '../utils/errors.js'
+import type { TreeNode, DocumentSymbol, DeclarationModifier, KindFilter, LanguageConfig } from '../types/core.js'
+
+// Containers that group definitions but have no name field of their own
+const CONTAINER_TYPES: Record<string, string> = {
+  impl_item: 'impl',
+  companion_object: 'object',
+}
+
+// Swift property accessors and observers, named by their keyword
+const ACCESSOR_NAMES: Record<string, string> = {
+  computed_getter: 'get',
+  computed_setter: 'set',
+  computed_modify: 'modify',
+  willset_clause: 'willSet',
+  didset_clause: 'didSet',
+}
+
+// Kotlin declares locals with the same `property_declaration` as class properties
+const LOCAL_KINDS = new Set(['variable', 'const', 'static', 'property'])
+const CALLABLE_KINDS: ReadonlySet<string> = new Set(['function', 'method', 'constructor', 'component', 'accessor'])
+// Kind filter standing for every callable kind
+const ANY_CALLABLE_KIND = 'fn'
+const DECLARATION_MODIFIERS: DeclarationModifier[] = ['async', 'pub', 'static']
+// Containers, macro calls and Swift declaration kinds, which have no entry in definitionKinds
+const EXTRA_OUTLINE_KINDS: Record<string, string[]> = {
+  [PARSER_NAMES.RUST]: ['impl', 'macro_call'],
+  [PARSER_NAMES.SWIFT]: ['struct', 'enum', 'extension', 'accessor'],
+  [PARSER_NAMES.ELIXIR]: ['module', 'macro'],
+}
+const TYPE_KINDS = new Set(['class', 'struct', 'interface', 'trait', 'protocol', 'extension', 'impl', 'object', 'record', 'enum'])
+const FUNCTION_VALUE_TYPES = new Set(['arrow_function', 'function', 'function_expression', 'generator_function'])
+// Plain TypeScript has no JSX, so `.ts` files are left out
+const JSX_LANGUAGES = new Set<string>([PARSER_NAMES.JAVASCRIPT, PARSER_NAMES.TSX])
+const JSX_TYPES = ['jsx_element', 'jsx_self_closing_element', 'jsx_fragment']
+const COMPONENT_NAME = /^[A-Z]/
+// Comments may sit between a Rust item and its attributes
+const RUST_COMMENT_TYPES = new Set(['line_comment', 'block_comment'])
+
+export interface Declaration {
+  /** Qualified like outline names */
+  name: string
+  kind: string
+  modifiers: DeclarationModifier[]
+  node: Parser.SyntaxNode
+}
+
+export interface ListSymbolsOptions {
+  depth?: number
+}
+
+/**
+ * Returns the symbol outline of a parsed file; depth 1 keeps only top-level symbols
+ */
+export function listSymbols(fileNode: TreeNode, options: ListSymbolsOptions = {}): DocumentSymbol[] {
+  if (!fileNode.rawNode) return []
+
+  const language = getFileLanguage(fileNode.path, fileNode.content)
+  if (!language) return []
+
+  const maxDepth = options.depth !== undefined && options.depth > 0 ? options.depth : Infinity
+  return collectSymbols(fileNode.rawNode, language, 1, maxDepth, undefined)
+}
+
+/**
+ * Lists every symbol of a parsed file's outline in document order, with its syntax node and modifiers
+ */
+export function listDeclarations(fileNode: TreeNode): Declaration[] {
+  if (!fileNode.rawNode) return []
+
+  const language = getFileLanguage(fileNode.path, fileNode.content)
+  if (!language) return []
+
+  const declarations: Declaration[] = []
+  collectSymbols(fileNode.rawNode, language, 1, Infinity, undefined, (symbol, node) => {
+    const nameNode = getDefinitionNameNode(node, language) ?? getPrototypeNameNode(node, language)
+    declarations.push({
+      name: symbol.name,
+      kind: symbol.kind,
+      modifiers: nameNode ? getDeclarationModifiers(node, nameNode, language) : [],
+      node,
+    })
+  })
+  return declarations
+}
+
+/**
+ * Outline kinds a language's symbols can have, sorted
+ */
+export function getOutlineKinds(language: LanguageConfig): string[] {
+  const kinds = new Set(Object.values(language.definitionKinds))
+  // Functions nested in types are outlined as methods
+  if (kinds.has('function')) kinds.add('method')
+  if (JSX_LANGUAGES.has(language.name)) kinds.add('component')
+  if (language.definitionKinds.variable_declarator) kinds.add('const')
+  for (const kind of EXTRA_OUTLINE_KINDS[language.name] ?? []) {
+    kinds.add(kind)
+  }
+  return [...kinds].sort()
+}
+
+/**
+ * Parses a kind filter such as `struct`, `async_fn` or `pub_static_method`: a kind from getOutlineKinds, or
+ * `fn` for any callable, after any number of `async_`, `pub_` and `static_` prefixes. Unknown kinds list the
+ * valid ones for the given languages, or for every language when none are given.
+ */
+export function compileKindFilter(kind: string, languages?: LanguageConfig[]): KindFilter {
+  const valid = new Set((languages ?? LANGUAGE_CONFIGS).flatMap(getOutlineKinds))
+  const segments = kind.split('_')
+  const modifiers: DeclarationModifier[] = []
+  // A kind spelled like a modifier, such as Rust's `static`, is the kind
+  while (segments.length > 1 && !valid.has(segments.join('_')) && DECLARATION_MODIFIERS.includes(segments[0] as DeclarationModifier)) {
+    modifiers.push(segments.shift() as DeclarationModifier)
+  }
+
+  const base = segments.join('_')
+  if (base === ANY_CALLABLE_KIND) return { kinds: CALLABLE_KINDS, modifiers }
+  if (!valid.has(base)) {
+    const scope = languages ? ` for ${languages.map(language => language.name).join(', ')}` : ''
+    throw createError('SEARCH_ERROR', `Unknown kind: ${kind}. Valid kinds${scope}: ${[...valid].sort().join(', ')}, or ${ANY_CALLABLE_KIND} for any function or method. Prefix ${DECLARATION_MODIFIERS.map(modifier => `${modifier}_`).join(', ')} to require a modifier, as in async_fn`, { kind })
+  }
+  return { kinds: new Set([base]), modifiers }
+}
+
+/**
+ * Whether a declaration passes a kind filter
+ */
+export function matchesKind(declaration: Pick<Declaration, 'kind' | 'modifiers'>, filter: KindFilter): boolean {
+  return filter.kinds.has(declaration.kind) && filter.modifiers.every(modifier => declaration.modifiers.includes(modifier))
+}
+
+/**
+ * Returns the kind of a named function, method, or function-valued variable declaration, or null for other nodes
+ */
+export function getCallableKind(node: Parser.SyntaxNode, language: LanguageConfig): string | null {
+  const mapped = language.definitionKinds[node.type]
+  // Elixir calls declare something only when they are a `def` or the like
+  const kind = mapped && node.type === 'call' ? getElixirDefinitionKind(node) : mapped
+  if (!kind) return null
+  if (CALLABLE_KINDS.has(kind)) return kind
+
+  if (node.type === 'variable_declarator') {
+    const value = node.childForFieldName('value')
+    if (value && FUNCTION_VALUE_TYPES.has(value.type)) return 'function'
+  }
+  return null
+}
+
+/**
+ * Counts symbols in an outline, including nested children
+ */
+export function countSymbols(symbols: DocumentSymbol[]): number {
+  return symbols.reduce((sum, symbol) => sum + 1 + countSymbols(symbol.children), 0)
+}
+
+function collectSymbols(
+  node: Parser.SyntaxNode,
+  language: LanguageConfig,
+  level: number,
+  maxDepth: number,
+  parentKind: string | undefined,
+  onSymbol?: (symbol: DocumentSymbol, node: Parser.SyntaxNode) => void,
+): DocumentSymbol[] {
+  const symbols: DocumentSymbol[] = []
+
+  for (const child of node.namedChildren) {
+    if (child.type === 'token_tree' && language.name === PARSER_NAMES.RUST) {
+      symbols.push(...collectTokenTreeMacros(child, level, maxDepth))
+      continue
+    }
+
+    const symbol = createSymbol(child, language, parentKind)
+
+    if (!symbol) {
+      symbols.push(...collectSymbols(child, language, level, maxDepth, parentKind, onSymbol))
+      continue
+    }
+
+    // Locals inside function bodies are noise in an outline
+    if (LOCAL_KINDS.has(symbol.kind) && parentKind && CALLABLE_KINDS.has(parentKind)) {
+      continue
+    }
+
+    onSymbol?.(symbol, child)
+    if (level < maxDepth) {
+      symbol.children = collectSymbols(child, language, level + 1, maxDepth, symbol.kind, onSymbol)
+    }
+    symbols.push(symbol)
+  }
+
+  return language.name === PARSER_NAMES.ELIXIR ? groupClauses(symbols) : symbols
+}
+
+/**
+ * Folds the clauses of a multi-clause Elixir function into its first clause, which then spans them all and
+ * lists the lines of each
+ */
+function groupClauses(symbols: DocumentSymbol[]): DocumentSymbol[] {
+  const grouped: DocumentSymbol[] = []
+  const firstClauses = new Map<string, DocumentSymbol>()
+
+  for (const symbol of symbols) {
+    const key = `${symbol.kind} ${symbol.name}`
+    const first = symbol.kind === 'module' ? undefined : firstClauses.get(key)
+    if (!first) {
+      firstClauses.set(key, symbol)
+      grouped.push(symbol)
+      continue
+    }
+
+    first.clauses ??= [{ startLine: first.startLine, endLine: first.endLine }]
+    first.clauses.push({ startLine: symbol.startLine, endLine: symbol.endLine })
+    first.endLine = Math.max(first.endLine, symbol.endLine)
+    first.children.push(...symbol.children)
+  }
+
+  return grouped
+}
+
+function createSymbol(
+  node: Parser.SyntaxNode,
+  language: LanguageConfig,
+  parentKind: string | undefined,
+): DocumentSymbol | null {
+  const containerKind = CONTAINER_TYPES[node.type]
+  if (containerKind) {
+    return buildSymbol(getContainerName(node), containerKind, node)
+  }
+
+  const accessorName = ACCESSOR_NAMES[node.type]
+  if (accessorName && parentKind === 'property') {
+    return buildSymbol(accessorName, 'accessor', node)
+  }
+
+  if (node.type === 'macro_invocation' && language.name === PARSER_NAMES.RUST) {
+    const macro = node.childForFieldName('macro')
+    return macro ? buildSymbol(`${macro.text}!`, 'macro_call', node) : null
+  }
+
+  const prototypeName = getPrototypeNameNode(node, language)
+  if (prototypeName) {
+    const kind = parentKind && TYPE_KINDS.has(parentKind) ? 'method' : 'function'
+    return { ...buildSymbol(prototypeName.text, kind, node), declarationOnly: true }
+  }
+
+  const nameNode = getDefinitionNameNode(node, language)
+  if (!nameNode) return null
+
+  const symbol = buildSymbol(getQualifiedName(node, nameNode), resolveOutlineKind(node, language, parentKind), node)
+  const decorators = getDecorators(node)
+  if (decorators.length > 0) {
+    symbol.decorators = decorators
+  }
+  const attributes = language.name === PARSER_NAMES.RUST ? getAttributes(node) : []
+  if (attributes.length > 0) {
+    symbol.attributes = attributes
+  }
+  return symbol
+}
+
+/**
+ * Collects the outer attributes of a Rust item without the `#[` and `]` (`#[derive(Debug)]` gives `derive(Debug)`).
+ * They are parsed as siblings placed before the item rather than as its children.
+ */
+function getAttributes(node: Parser.SyntaxNode): string[] {
+  const attributes: string[] = []
+
+  for (let sibling = node.previousNamedSibling; sibling; sibling = sibling.previousNamedSibling) {
+    if (RUST_COMMENT_TYPES.has(sibling.type)) continue
+    if (sibling.type !== 'attribute_item') break
+    const attribute = sibling.namedChildren.find(child => child.type === 'attribute')
+    if (attribute) attributes.unshift(attribute.text)
+  }
+
+  return attributes
+}
+
+/**
+ * Rust macro arguments are unparsed token trees, so calls nested in them (`assert!(matches!(...))`)
+ * show up as an identifier, a `!` and a token tree rather than as `macro_invocation` nodes
+ */
+function collectTokenTreeMacros(tree: Parser.SyntaxNode, level: number, maxDepth: number): DocumentSymbol[] {
+  const symbols: DocumentSymbol[] = []
+  const tokens = tree.children
+
+  for (let i = 0; i < tokens.length; i++) {
+    const token = tokens[i]!
+    const bang = tokens[i + 1]
+    const args = tokens[i + 2]
+
+    if (token.type === 'identifier' && bang?.type === '!' && args?.type === 'token_tree') {
+      const symbol = buildSymbol(`${token.text}!`, 'macro_call', token)
+      symbol.endLine = args.endPosition.row + 1
+      if (level < maxDepth) {
+        symbol.children = collectTokenTreeMacros(args, level + 1, maxDepth)
+      }
+      symbols.push(symbol)
+      i += 2
+    }
+    else if (token.type === 'token_tree') {
+      symbols.push(...collectTokenTreeMacros(token, level, maxDepth))
+    }
+  }
+
+  return symbols
+}
+
+/**
+ * Collects decorator expressions without the leading `@` (`@pytest.fixture` gives `pytest.fixture`).
+ * Python wraps decorated definitions in a `decorated_definition`; TypeScript keeps them as children.
+ */
+function getDecorators(node: Parser.SyntaxNode): string[] {
+  const holder = node.parent?.type === 'decorated_definition' ? node.parent : node
+
+  return holder.namedChildren
+    .filter(child => child.type === 'decorator')
+    .map(decorator => decorator.text.replace(/^@/, '').trim())
+}
+
+function buildSymbol(name: string, kind: string, node: Parser.SyntaxNode): DocumentSymbol {
+  return {
+    name,
+    kind,
+    startLine: node.startPosition.row + 1,
+    endLine: node.endPosition.row + 1,
+    children: [],
+  }
+}
+
+function resolveOutlineKind(node: Parser.SyntaxNode, language: LanguageConfig, parentKind: string | undefined): string {
+  const kind = getDeclaredKind(node, language.definitionKinds[node.type]!)
+  if (kind === 'function' && parentKind && TYPE_KINDS.has(parentKind)) {
+    return 'method'
+  }
+
+  if (node.type === 'variable_declarator') {
+    const value = node.childForFieldName('value')
+    if (value && FUNCTION_VALUE_TYPES.has(value.type)) return isComponent(node, value, language) ? 'component' : 'function'
+    if (node.parent?.child(0)?.type === 'const') return 'const'
+  }
+
+  if (kind === 'function' && isComponent(node, node, language)) return 'component'
+  return kind
+}
+
+/**
+ * React function components: a capitalized function that returns JSX, found by the syntax alone
+ */
+function isComponent(nameHolder: Parser.SyntaxNode, fn: Parser.SyntaxNode, language: LanguageConfig): boolean {
+  if (!JSX_LANGUAGES.has(language.name)) return false
+  if (!COMPONENT_NAME.test(nameHolder.childForFieldName('name')?.text ?? '')) return false
+
+  const body = fn.childForFieldName('body')
+  if (!body) return false
+  if (body.type !== 'statement_block') return containsJsx(body)
+  return findReturns(body).some(returned => containsJsx(returned))
+}
+
+// Returns inside nested functions belong to those functions, not the component
+function findReturns(node: Parser.SyntaxNode): Parser.SyntaxNode[] {
+  return node.namedChildren.flatMap((child) => {
+    if (FUNCTION_VALUE_TYPES.has(child.type) || child.type === 'function_declaration') return []
+    return child.type === 'return_statement' ? [child] : findReturns(child)
+  })
+}
+
+function containsJsx(node: Parser.SyntaxNode): boolean {
+  return JSX_TYPES.includes(node.type) || node.descendantsOfType(JSX_TYPES).length > 0
+}
+
+function getContainerName(node: Parser.SyntaxNode): string {
+  // Kotlin calls an unnamed companion object `Companion`
+  if (node.type === 'companion_object') {
+    return node.namedChildren.find(child => child.type === 'type_identifier')?.text || 'Companion'
+  }
+
+  const typeNode = node.childForFieldName('type')
+  const traitNode = node.childForFieldName('trait')
+  const typeName = typeNode?.text || 'unknown'
+
+  return traitNode ? `impl ${traitNode.text} for ${typeName}` : `impl ${typeName}`
+}
\ No newline at end of file
@@ -0,0 +1,71 @@
+/**
+ * TODO extraction - collects tagged comments (TODO, FIXME, ...) with their assignee and message
+ */
+
+import type Parser from 'tree-sitter'
+import { escapeRegExp } from '../utils/string-analysis.js'
+import type { TreeNode, TodoItem } from '../types/core.js'
+
+export const DEFAULT_TODO_TAGS = ['TODO', 'FIXME', 'HACK', 'XXX']
+
+export interface FindTodosOptions {
+  tags?: string[]
+  pathPattern?: string
+}
+
+/**
+ * Scans comment nodes for tags, reading `TAG(author)` or `TAG @author` annotations and the rest of the line
+ */
+export function findTodos(files: TreeNode[], options: FindTodosOptions = {}): TodoItem[] {
+  const { tags = DEFAULT_TODO_TAGS, pathPattern } = options
+  if (tags.length === 0) return []
+
+  const pattern = new RegExp(
+    `(?<!\\w)(${tags.map(escapeRegExp).join('|')})(?!\\w)(?:\\(([^)]*)\\))?(?:\\s*@([\\w.-]+))?[\\s:-]*(.*)`,
+  )
+  const todos: TodoItem[] = []
+
+  for (const fileNode of files) {
+    if (!fileNode.rawNode) continue
+    if (pathPattern && !fileNode.path.includes(pathPattern)) continue
+
+    for (const comment of collectComments(fileNode.rawNode)) {
+      comment.text.split('\n').forEach((line, index) => {
+        const match = pattern.exec(line)
+        if (!match) return
+
+        const author = (match[2] || match[3] || '').trim()
+        todos.push({
+          tag: match[1]!,
+          ...(author && { author }),
+          message: cleanMessage(match[4] || ''),
+          path: fileNode.path,
+          startLine: comment.startPosition.row + index + 1,
+          startColumn: (index === 0 ? comment.startPosition.column : 0) + match.index,
+          context: line.trim(),
+        })
+      })
+    }
+  }
+
+  return todos
+}
+
+function collectComments(root: Parser.SyntaxNode): Parser.SyntaxNode[] {
+  const comments: Parser.SyntaxNode[] = []
+  const visit = (node: Parser.SyntaxNode) => {
+    if (node.type.includes('comment')) {
+      comments.push(node)
+      return
+    }
+    for (const child of node.namedChildren) {
+      visit(child)
+    }
+  }
+  visit(root)
+  return comments
+}
+
+function cleanMessage(text: string): string {
+  return text.replace(/\s*(?:\*\/|-->|#>)\s*$/, '').trim()
+}
\ No newline at end of file
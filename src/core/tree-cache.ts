@@ -0,0 +1,152 @@
+/**
+ * Syntax tree cache - keeps the most recently used parse trees resident and re-parses evicted ones on demand
+ */
+
+import type Parser from 'tree-sitter'
+import { getFileLanguage, getParser } from './languages.js'
+import { MEMORY_LIMITS } from '../constants/persistence.js'
+import type { TreeNode, TreeCacheStats } from '../types/core.js'
+
+interface TreeSlot {
+  tree?: Parser.Tree
+  pins: number
+}
+
+// Least recently used first; a Set keeps insertion order, so touching a slot re-inserts it.
+// Only filled while a limit is set, so without one trees are freed along with their file nodes.
+const resident = new Set<TreeSlot>()
+const slots = new WeakMap<TreeNode, TreeSlot>()
+const counters = { hits: 0, misses: 0, evictions: 0 }
+
+let maxTrees: number = MEMORY_LIMITS.MAX_RESIDENT_TREES
+
+/**
+ * Limits how many parse trees stay in memory; 0 keeps every tree. Set it before indexing, since trees
+ * loaded while unlimited are not tracked.
+ */
+export function setMaxTrees(limit: number): void {
+  maxTrees = Math.max(0, limit)
+  evictTrees()
+}
+
+export function getMaxTrees(): number {
+  return maxTrees
+}
+
+/**
+ * Routes a file node's `tree` and `rawNode` through the cache. Symbol metadata on the node is untouched;
+ * only the tree is dropped on eviction and re-parsed from `content` the next time it is read.
+ */
+export function attachTree(fileNode: TreeNode, tree?: Parser.Tree): void {
+  const slot: TreeSlot = { pins: 0 }
+  slots.set(fileNode, slot)
+
+  Object.defineProperty(fileNode, 'tree', {
+    configurable: true,
+    enumerable: false,
+    get: () => loadTree(slot, fileNode),
+    set: (value?: Parser.Tree) => storeTree(slot, value),
+  })
+  Object.defineProperty(fileNode, 'rawNode', {
+    configurable: true,
+    enumerable: false,
+    get: () => loadTree(slot, fileNode)?.rootNode,
+  })
+
+  if (tree) storeTree(slot, tree)
+}
+
+/**
+ * Returns the file's tree only if it is in memory, without parsing or counting a lookup
+ */
+export function getResidentTree(fileNode: TreeNode): Parser.Tree | undefined {
+  return slots.get(fileNode)?.tree
+}
+
+/**
+ * Keeps the file's tree resident while `use` runs, even if other trees are loaded meanwhile.
+ * Evicting only drops the cache's reference, so nodes already handed out stay valid either way.
+ */
+export function borrowTree<T>(fileNode: TreeNode, use: (tree: Parser.Tree | undefined) => T): T {
+  const slot = slots.get(fileNode)
+  if (!slot) return use(fileNode.tree)
+
+  slot.pins++
+  try {
+    return use(loadTree(slot, fileNode))
+  }
+  finally {
+    slot.pins--
+    evictTrees()
+  }
+}
+
+/**
+ * Drops the cached trees of files leaving the index, e.g. when their project is closed
+ */
+export function releaseTrees(fileNodes: Iterable<TreeNode>): void {
+  for (const fileNode of fileNodes) {
+    const slot = slots.get(fileNode)
+    if (!slot) continue
+
+    slot.tree = undefined
+    resident.delete(slot)
+  }
+}
+
+export function getTreeCacheStats(): TreeCacheStats {
+  const lookups = counters.hits + counters.misses
+  return {
+    maxTrees,
+    residentTrees: resident.size,
+    pinnedTrees: Array.from(resident).filter(slot => slot.pins > 0).length,
+    ...counters,
+    hitRate: lookups === 0 ? 0 : Math.round((counters.hits / lookups) * 1000) / 1000,
+  }
+}
+
+export function resetTreeCacheStats(): void {
+  counters.hits = 0
+  counters.misses = 0
+  counters.evictions = 0
+}
+
+function loadTree(slot: TreeSlot, fileNode: TreeNode): Parser.Tree | undefined {
+  if (slot.tree) {
+    counters.hits++
+    if (resident.delete(slot)) resident.add(slot)
+    return slot.tree
+  }
+
+  const language = getFileLanguage(fileNode.path, fileNode.content)
+  if (!language || fileNode.skipped || fileNode.content === undefined) return undefined
+
+  const tree = getParser(language.name)?.parse(fileNode.content)
+  if (!tree) return undefined
+
+  counters.misses++
+  storeTree(slot, tree)
+  return tree
+}
+
+function storeTree(slot: TreeSlot, tree?: Parser.Tree): void {
+  slot.tree = tree
+  resident.delete(slot)
+  if (tree && maxTrees > 0) {
+    resident.add(slot)
+    evictTrees()
+  }
+}
+
+function evictTrees(): void {
+  if (maxTrees === 0) return
+
+  for (const slot of resident) {
+    if (resident.size <= maxTrees) break
+    if (slot.pins > 0) continue
+
+    slot.tree = undefined
+    resident.delete(slot)
+    counters.evictions++
+  }
+}
\ No newline at end of file
@@ -0,0 +1,244 @@
+/**
+ * Type hierarchy - links classes, interfaces and traits to their supertypes and subtypes across the project
+ */
+
+import type Parser from 'tree-sitter'
+import { extname } from 'path'
+import { getFileLanguage, getLanguageByExtension } from './languages.js'
+import { findDefinition } from './definitions.js'
+import { buildImportGraph } from './import-graph.js'
+import { getBaseName, getImplementedTraits } from './implementations.js'
+import { PARSER_NAMES } from '../constants/index.js'
+import type { TreeNode, DefinitionResult, TypeHierarchy, TypeHierarchyNode, TypeRelation } from '../types/core.js'
+
+export const HIERARCHY_DIRECTIONS = ['up', 'down', 'both'] as const
+export type HierarchyDirection = typeof HIERARCHY_DIRECTIONS[number]
+
+const INHERITANCE_LANGUAGES = new Set<string>([
+  PARSER_NAMES.JAVASCRIPT,
+  PARSER_NAMES.TYPESCRIPT,
+  PARSER_NAMES.TSX,
+  PARSER_NAMES.PYTHON,
+  PARSER_NAMES.RUST,
+  PARSER_NAMES.JAVA,
+  PARSER_NAMES.CSHARP,
+])
+const NO_INHERITANCE_LANGUAGES = new Set<string>([PARSER_NAMES.GO, PARSER_NAMES.C])
+
+const TYPE_KINDS = new Set(['class', 'interface', 'trait', 'struct', 'enum', 'type', 'record', 'object', 'union'])
+
+const CLASS_TYPES = new Set([
+  'class_declaration',
+  'abstract_class_declaration',
+  'class',
+  'enum_declaration',
+  'record_declaration',
+  'struct_declaration',
+])
+
+// Nodes that name a supertype; keyword arguments, lifetimes and type arguments in the same lists are not types
+const TYPE_REFERENCE_TYPES = new Set([
+  'identifier',
+  'type_identifier',
+  'generic_type',
+  'generic_name',
+  'scoped_type_identifier',
+  'nested_type_identifier',
+  'qualified_name',
+  'member_expression',
+  'attribute',
+])
+
+export interface TypeHierarchyOptions {
+  direction?: HierarchyDirection
+  /** Project root, used to resolve imports when a type name is declared in several files */
+  root?: string
+  /** Levels followed from the queried type (default: unlimited) */
+  maxDepth?: number
+}
+
+type Relation = TypeRelation['relation']
+
+/**
+ * Builds the hierarchy around a type: `up` follows extended and implemented types, `down` the types
+ * extending or implementing it. Types are matched by unqualified name and located through their declarations.
+ */
+export function buildTypeHierarchy(symbol: string, files: TreeNode[], options: TypeHierarchyOptions = {}): TypeHierarchy {
+  const { direction = 'both', root, maxDepth = Infinity } = options
+  const name = symbol.split(/::|\./).pop()!
+  const relations = collectTypeRelations(files)
+  const locate = createTypeLocator(files, root)
+
+  const rootDefinition = locate(name)
+  const hierarchyRoot: TypeHierarchyNode = { name, ...toLocation(rootDefinition) }
+
+  function expand(typeName: string, key: 'supertypes' | 'subtypes', depth: number, ancestors: Set<string>): TypeHierarchyNode[] {
+    const seen = new Set<string>()
+    const nodes: TypeHierarchyNode[] = []
+
+    for (const relation of relations) {
+      const [from, to]: [string, string] = key === 'supertypes' ? [relation.subtype, relation.supertype] : [relation.supertype, relation.subtype]
+      if (from !== typeName || seen.has(to)) continue
+      seen.add(to)
+
+      const node: TypeHierarchyNode = { name: to, relation: relation.relation, ...toLocation(locate(to, relation.path)) }
+      if (!ancestors.has(to) && depth < maxDepth) {
+        node[key] = expand(to, key, depth + 1, new Set([...ancestors, to]))
+      }
+      nodes.push(node)
+    }
+
+    return nodes
+  }
+
+  if (direction !== 'down') hierarchyRoot.supertypes = expand(name, 'supertypes', 1, new Set([name]))
+  if (direction !== 'up') hierarchyRoot.subtypes = expand(name, 'subtypes', 1, new Set([name]))
+
+  const isEmpty = !hierarchyRoot.supertypes?.length && !hierarchyRoot.subtypes?.length
+  const note = isEmpty ? describeEmptyHierarchy(name, rootDefinition) : undefined
+  return { root: hierarchyRoot, direction, ...(note && { note }) }
+}
+
+/**
+ * Every extends and implements edge declared in the project's supported languages
+ */
+export function collectTypeRelations(files: TreeNode[]): TypeRelation[] {
+  const relations: TypeRelation[] = []
+
+  for (const fileNode of files) {
+    if (!fileNode.rawNode) continue
+
+    const language = getFileLanguage(fileNode.path, fileNode.content)
+    if (!language || !INHERITANCE_LANGUAGES.has(language.name)) continue
+
+    const visit = (node: Parser.SyntaxNode) => {
+      const add = (subtype: Parser.SyntaxNode | null, supertype: Parser.SyntaxNode, relation: Relation) => {
+        if (!subtype || isBlanketTarget(node, subtype)) return
+        relations.push({
+          subtype: getBaseName(subtype),
+          supertype: getBaseName(supertype),
+          relation,
+          path: fileNode.path,
+          line: node.startPosition.row + 1,
+        })
+      }
+
+      for (const [implementor, traitNode] of getImplementedTraits(node)) {
+        add(implementor, traitNode, 'implements')
+      }
+      for (const [supertype, relation] of getDeclaredSupertypes(node)) {
+        add(node.childForFieldName('name'), supertype, relation)
+      }
+
+      for (const child of node.namedChildren) {
+        visit(child)
+      }
+    }
+    visit(fileNode.rawNode)
+  }
+
+  return relations
+}
+
+/**
+ * Supertypes listed in a declaration header: superclasses, base lists, supertraits and extended interfaces.
+ * TypeScript `implements` clauses and Rust impls are read by getImplementedTraits.
+ */
+function getDeclaredSupertypes(node: Parser.SyntaxNode): [Parser.SyntaxNode, Relation][] {
+  const extend = (types: Parser.SyntaxNode[]) => types.map((type): [Parser.SyntaxNode, Relation] => [type, 'extends'])
+
+  if (CLASS_TYPES.has(node.type)) {
+    const heritage = findChild(node, 'class_heritage')
+    if (heritage) {
+      // TypeScript wraps the superclass in an extends clause; JavaScript puts it directly in the heritage
+      return extend(getTypeReferences(findChild(heritage, 'extends_clause') ?? heritage))
+    }
+
+    return [
+      ...extend(getTypeReferences(node.childForFieldName('superclass'))),
+      ...getTypeReferences(findChild(node.childForFieldName('interfaces'), 'type_list'))
+        .map((type): [Parser.SyntaxNode, Relation] => [type, 'implements']),
+      ...extend(getTypeReferences(findChild(node, 'base_list'))),
+    ]
+  }
+
+  switch (node.type) {
+    case 'interface_declaration':
+      return extend([
+        ...getTypeReferences(findChild(node, 'extends_type_clause')),
+        ...getTypeReferences(findChild(findChild(node, 'extends_interfaces'), 'type_list')),
+        ...getTypeReferences(findChild(node, 'base_list')),
+      ])
+    case 'trait_item':
+      return extend(getTypeReferences(node.childForFieldName('bounds')))
+    case 'class_definition':
+      return extend(getTypeReferences(node.childForFieldName('superclasses')))
+    default:
+      return []
+  }
+}
+
+function findChild(node: Parser.SyntaxNode | null | undefined, type: string): Parser.SyntaxNode | null {
+  return node?.namedChildren.find(child => child.type === type) ?? null
+}
+
+function getTypeReferences(node: Parser.SyntaxNode | null): Parser.SyntaxNode[] {
+  return node ? node.namedChildren.filter(child => TYPE_REFERENCE_TYPES.has(child.type)) : []
+}
+
+/**
+ * `impl<T: Display> ToString for T` implements the trait for a type parameter, not a named type
+ */
+function isBlanketTarget(node: Parser.SyntaxNode, subtype: Parser.SyntaxNode): boolean {
+  const typeParameters = node.childForFieldName('type_parameters')
+  if (!typeParameters || subtype.type !== 'type_identifier') return false
+  return typeParameters.namedChildren.some(parameter =>
+    (parameter.type === 'type_identifier' ? parameter : parameter.childForFieldName('left'))?.text === subtype.text,
+  )
+}
+
+/**
+ * Resolves a type name to its declaration, preferring the referencing file, then the files it imports
+ */
+function createTypeLocator(files: TreeNode[], root?: string): (name: string, fromPath?: string) => DefinitionResult | undefined {
+  const definitions = new Map<string, DefinitionResult[]>()
+  let imports: Map<string, Set<string>> | undefined
+
+  const getImports = (path: string): Set<string> => {
+    if (!imports) {
+      imports = new Map()
+      for (const edge of root ? buildImportGraph(files, root).edges : []) {
+        const targets = imports.get(edge.from) ?? new Set()
+        targets.add(edge.to)
+        imports.set(edge.from, targets)
+      }
+    }
+    return imports.get(path) ?? new Set()
+  }
+
+  return (name, fromPath) => {
+    let candidates = definitions.get(name)
+    if (!candidates) {
+      candidates = findDefinition(name, files).filter(definition => TYPE_KINDS.has(definition.kind))
+      definitions.set(name, candidates)
+    }
+    if (candidates.length <= 1 || !fromPath) return candidates[0]
+
+    return candidates.find(definition => definition.path === fromPath)
+      ?? candidates.find(definition => getImports(fromPath).has(definition.path))
+      ?? candidates[0]
+  }
+}
+
+function toLocation(definition?: DefinitionResult): Pick<TypeHierarchyNode, 'kind' | 'path' | 'startLine'> {
+  return definition ? { kind: definition.kind, path: definition.path, startLine: definition.startLine } : {}
+}
+
+function describeEmptyHierarchy(name: string, definition?: DefinitionResult): string {
+  if (!definition) return `No type named "${name}" was found in the project`
+
+  const language = getLanguageByExtension(extname(definition.path))?.name
+  if (!language || INHERITANCE_LANGUAGES.has(language)) return `${name} has no supertypes or subtypes in the project`
+  if (NO_INHERITANCE_LANGUAGES.has(language)) return `${name} is declared in ${language}, which has no class or trait inheritance`
+  return `Type hierarchies are not supported for ${language} yet`
+}
\ No newline at end of file
@@ -5,6 +5,7 @@
 import { watch, FSWatcher } from 'chokidar'
 import { debounce } from '../utils/helpers.js'
 import { getLogger } from '../utils/logger.js'
+import { WATCH_CONFIG } from '../constants/persistence.js'
 import type { FileChange } from '../types/core.js'
 
 export type FileChangeHandler = (changes: FileChange[]) => void
@@ -20,16 +21,18 @@ export class FileWatcher {
   private changes: FileChange[] = []
   private logger = getLogger()
   private flushChanges: () => void
+  private ignored: string[]
 
   constructor(
     private directory: string,
     private handler: FileChangeHandler,
     options: WatchOptions = {},
   ) {
-    const { debounceMs = 300 } = options
+    const { debounceMs = WATCH_CONFIG.DEBOUNCE_MS } = options
+    this.ignored = options.ignored || [...WATCH_CONFIG.IGNORED_PATTERNS]
     this.flushChanges = debounce(() => {
       if (this.changes.length > 0) {
-        this.handler([...this.changes])
+        this.handler(coalesceChanges(this.changes))
         this.changes = []
       }
     }, debounceMs)
@@ -39,15 +42,9 @@ export class FileWatcher {
     if (this.watcher) return
 
     const options = {
-      ignored: [
-        '**/node_modules/**',
-        '**/.git/**',
-        '**/dist/**',
-        '**/build/**',
-        '**/.cache/**',
-      ],
-      persistent: true,
-      ignoreInitial: true,
+      ignored: this.ignored,
+      persistent: WATCH_CONFIG.PERSISTENT,
+      ignoreInitial: WATCH_CONFIG.IGNORE_INITIAL,
     }
 
     this.watcher = watch(this.directory, options)
@@ -56,6 +53,7 @@ export class FileWatcher {
       .on('add', path => this.addChange('created', path))
       .on('change', path => this.addChange('modified', path))
       .on('unlink', path => this.addChange('deleted', path))
+      .on('unlinkDir', path => this.addChange('deleted', path))
       .on('error', error => this.logger.error('File watcher error:', error))
 
     this.logger.info(`File watcher started for ${this.directory}`)
@@ -79,6 +77,18 @@ export class FileWatcher {
   }
 }
 
+/**
+ * Collapses a burst of events to the latest change per path, so a bulk save re-parses each file once
+ */
+export function coalesceChanges(changes: FileChange[]): FileChange[] {
+  const latest = new Map<string, FileChange>()
+  for (const change of changes) {
+    latest.delete(change.path)
+    latest.set(change.path, change)
+  }
+  return Array.from(latest.values())
+}
+
 export function createFileWatcher(
   directory: string,
   handler: FileChangeHandler,
@@ -4,7 +4,19 @@
 
 export * from './core/parser.js'
 export * from './core/search.js'
+export * from './core/definitions.js'
+export * from './core/references.js'
+export * from './core/implementations.js'
+export * from './core/type-hierarchy.js'
+export * from './core/dead-code.js'
+export * from './core/rename.js'
+export * from './core/symbols.js'
+export * from './core/todos.js'
+export * from './core/call-graph.js'
+export * from './core/import-graph.js'
+export * from './core/complexity.js'
 export * from './core/file-walker.js'
+export * from './core/tree-cache.js'
 export * from './core/watcher.js'
 
 export * from './import/resolver.js'
@@ -15,19 +27,28 @@ export * from './analysis/index.js'
 export * from './analysis/quality.js'
 export * from './analysis/deadcode.js'
 export * from './analysis/structure.js'
+export * from './analysis/duplicates.js'
 
 export * from './project/manager.js'
 export * from './project/monorepo.js'
 export * from './project/memory.js'
+export * from './project/config.js'
 
 export * from './mcp/server.js'
 export * from './mcp/handlers.js'
 export * from './mcp/schemas.js'
 
+export * from './output/sarif.js'
+export * from './output/ripgrep.js'
+export * from './output/jsonl.js'
+export * from './output/progress.js'
+export * from './output/paths.js'
+
 export * from './cli/index.js'
 export * from './cli/commands.js'
 
 export * from './utils/errors.js'
+export * from './utils/cancellation.js'
 export * from './utils/logger.js'
 export * from './utils/helpers.js'
 
@@ -35,5 +56,5 @@ export * from './types/core.js'
 export * from './types/analysis.js'
 
 export { createCLI } from './cli/index.js'
-export { startMCPServer } from './mcp/server.js'
+export { startMCPServer, type MCPServerOptions } from './mcp/server.js'
 export { analyzeProject } from './analysis/index.js'
\ No newline at end of file
@@ -2,15 +2,49 @@
  * MCP tool request handlers - simplified from complex handler system
  */
 
+import { isAbsolute, resolve } from 'path'
 import { analyzeProject } from '../analysis/index.js'
 import { analyzeErrors, partitionErrors } from '../analysis/errors.js'
-import { searchCode, findUsage } from '../core/search.js'
-import { createPersistentManager, getOrCreateProject } from '../project/persistent-manager.js'
+import { findDuplicates } from '../analysis/duplicates.js'
+import { findDeadCode, DEAD_CODE_NOTE } from '../core/dead-code.js'
+import { createCandidateFilter, addContextLines, addEnclosingPaths, addNodeTexts, searchBuffer, clampLineRange, SEARCH_MATCHERS, SEARCH_MODES, type SearchMatcher } from '../core/search.js'
+import { createQueryEngine, projectQuerySource } from '../core/engine.js'
+import { ignoresPathCase } from '../core/path-case.js'
+import { findDefinition } from '../core/definitions.js'
+import { parseBuffer } from '../core/parser.js'
+import { findReferences } from '../core/references.js'
+import { planRename, RENAME_NOTE } from '../core/rename.js'
+import { planReplace, applyReplacePlan, REPLACE_NOTE } from '../core/replace.js'
+import { findImplementations } from '../core/implementations.js'
+import { listSymbols, countSymbols } from '../core/symbols.js'
+import { findTodos, DEFAULT_TODO_TAGS } from '../core/todos.js'
+import { buildCallGraph, findCallers } from '../core/call-graph.js'
+import { getNodeAtPosition } from '../core/position.js'
+import { extractSignatures } from '../core/signatures.js'
+import { diffSignatures } from '../core/symbol-diff.js'
+import { buildImportGraph, resolveImport } from '../core/import-graph.js'
+import { buildTypeHierarchy, HIERARCHY_DIRECTIONS, type HierarchyDirection } from '../core/type-hierarchy.js'
+import { analyzeComplexity } from '../core/complexity.js'
+import { getTreeCacheStats, releaseTrees } from '../core/tree-cache.js'
+import { getDisabledLanguages, getFileLanguage, getLoadedLanguages, resolveLanguageNames } from '../core/languages.js'
+import { createBlameLookup } from '../core/git.js'
+import { getAllFiles, getDepthSkipped, getOutsideRootLinks, getSkippedFiles, getClassifiedFiles, getIndexStats, resolveProjectPath, updateProject, type IndexOptions } from '../project/manager.js'
+import { createPersistentManager, findProjectForPath, getOrCreateProject, listProjects, getProjectRoots, removeProjectFromManager, sanitizeProjectId, type IndexChangeListener } from '../project/persistent-manager.js'
+import { getProject } from '../project/memory.js'
 import { findDependencyModuleDirs } from '../project/monorepo.js'
 import { getLogger } from '../utils/logger.js'
-import { handleError } from '../utils/errors.js'
+import { getVersion } from '../utils/version.js'
+import { SEARCH_LIMITS } from '../constants/parsers.js'
+import { portablePathReplacer, toPortablePath } from '../output/paths.js'
+import { createPositionResolver } from '../output/offsets.js'
+import { streamToolResult } from './streaming.js'
+import { groupByFile, GROUP_ORDERS, type GroupOrder } from '../output/groups.js'
+import { reportFileAudit } from '../output/diagnostics.js'
+import { searchResultsToMarkdown, SEARCH_OUTPUT_FORMATS } from '../output/markdown.js'
+import { OUTPUT_FORMATS, searchResultsToSarif, findingsToSarif, errorsToSarif } from '../output/sarif.js'
+import { handleError, createError } from '../utils/errors.js'
 import type { AnalysisOptions } from '../types/analysis.js'
-import type { JsonObject, Project } from '../types/core.js'
+import type { JsonObject, JsonValue, LanguageConfig, LineRange, PartialToolResult, Project, ProjectConfig, SearchOptions, SearchDedup, SyntaxScope, TodoItem } from '../types/core.js'
 
 const mcpPersistentManager = createPersistentManager(10)
 
@@ -50,85 +84,1463 @@ interface MCPToolResult {
   [key: string]: unknown
 }
 
-async function getOrCreateMCPProject(projectId?: string, directory?: string, ignoreDirs?: string[]): Promise<Project> {
+let watchMode = process.env.NODE_ENV !== 'test'
+
+/**
+ * Enables or disables incremental reindexing of projects opened by tool calls
+ */
+export function setWatchMode(enabled: boolean): void {
+  watchMode = enabled
+}
+
+/**
+ * Calls `listener` with each batch of files a watched project reindexes; returns a function that stops it
+ */
+export function onIndexChange(listener: IndexChangeListener): () => void {
+  mcpPersistentManager.changeListeners.add(listener)
+  return () => mcpPersistentManager.changeListeners.delete(listener)
+}
+
+async function getOrCreateMCPProject(
+  projectId?: string,
+  directory?: string,
+  ignoreDirs?: string[],
+  indexOptions: IndexOptions = {},
+  walkOptions: WalkArgs = {},
+): Promise<Project> {
   const actualDirectory = directory || (projectId && projectId.startsWith('/') ? projectId : process.cwd())
   const actualProjectId = projectId && !projectId.startsWith('/') ? projectId : undefined
 
+  // An open project named without a directory is used as it was opened
+  if (actualProjectId && !directory) {
+    const opened = getProject(mcpPersistentManager.memory, sanitizeProjectId(actualProjectId))
+    if (opened) return opened
+  }
+
   return getOrCreateProject(mcpPersistentManager, {
     directory: actualDirectory,
     ignoreDirs: ignoreDirs || [],
-    autoWatch: process.env.NODE_ENV !== 'test',
-  }, actualProjectId)
+    ...walkOptions,
+    autoWatch: watchMode,
+    useCache: process.env.NODE_ENV !== 'test',
+  }, actualProjectId, indexOptions)
+}
+
+// With no project named, a file given as an absolute path selects the open project containing it
+function projectIdFor(projectId: JsonValue | undefined, directory: JsonValue | undefined, file: JsonValue | undefined): string | undefined {
+  if (typeof projectId === 'string') return projectId
+  if (typeof directory === 'string' || typeof file !== 'string' || !isAbsolute(file)) return undefined
+  return findProjectForPath(mcpPersistentManager, file)?.id
+}
+
+const SYNTAX_SCOPES: SyntaxScope[] = ['code', 'comment', 'string']
+
+function parseLanguageArg(language: JsonValue | undefined): LanguageConfig[] | undefined {
+  if (language === undefined || language === null) return undefined
+
+  const names = Array.isArray(language) ? language : [language]
+  if (!names.every(name => typeof name === 'string')) {
+    throw createError('SEARCH_ERROR', 'Language must be a string or an array of strings')
+  }
+  return resolveLanguageNames(names as string[])
+}
+
+// Every format list starts with json, the default
+function parseFormatArg<Format extends string>(format: JsonValue | undefined, formats: readonly Format[]): Format {
+  if (format === undefined || format === null) return 'json' as Format
+  if (!formats.includes(format as Format)) {
+    throw new Error(`Invalid format: ${format}. Expected one of: ${formats.join(', ')}`)
+  }
+  return format as Format
+}
+
+function parseDirectionArg(direction: JsonValue | undefined): HierarchyDirection {
+  if (direction === undefined || direction === null) return 'both'
+  if (!HIERARCHY_DIRECTIONS.includes(direction as HierarchyDirection)) {
+    throw new Error(`Invalid direction: ${direction}. Expected one of: ${HIERARCHY_DIRECTIONS.join(', ')}`)
+  }
+  return direction as HierarchyDirection
+}
+
+function parseGlobArg(patterns: JsonValue | undefined): string[] {
+  if (typeof patterns === 'string') return [patterns]
+  return Array.isArray(patterns) ? patterns.filter((pattern): pattern is string => typeof pattern === 'string') : []
+}
+
+type WalkArgs = Pick<ProjectConfig, 'useDefaultExcludes' | 'maxDepth' | 'followSymlinks' | 'allowOutsideRoot' | 'ignoreCaseInPaths'>
+
+function parseWalkArgs(args: JsonObject): WalkArgs {
+  const { useDefaultExcludes, maxDepth } = args
+  const walkArgs: WalkArgs = {}
+  if (useDefaultExcludes !== undefined && useDefaultExcludes !== null) {
+    if (typeof useDefaultExcludes !== 'boolean') {
+      throw new Error('useDefaultExcludes must be a boolean')
+    }
+    walkArgs.useDefaultExcludes = useDefaultExcludes
+  }
+  if (maxDepth !== undefined && maxDepth !== null) {
+    if (typeof maxDepth !== 'number' || !Number.isInteger(maxDepth) || maxDepth < 0) {
+      throw new Error('maxDepth must be a non-negative integer')
+    }
+    walkArgs.maxDepth = maxDepth
+  }
+  for (const flag of ['followSymlinks', 'allowOutsideRoot', 'ignoreCaseInPaths'] as const) {
+    const value = args[flag]
+    if (value === undefined || value === null) continue
+    if (typeof value !== 'boolean') {
+      throw new Error(`${flag} must be a boolean`)
+    }
+    walkArgs[flag] = value
+  }
+  return walkArgs
+}
+
+export interface ToolRequestContext extends IndexOptions {
+  /** Receives the chunks of a call made with `stream: true`; without it the response is not split */
+  onPartialResult?: (partial: PartialToolResult) => void
+}
+
+/**
+ * Routes a tool call. `context.onProgress` receives indexing progress when the call opens a new project;
+ * aborting `context.signal` (a client cancel notification) stops an in-flight search between files.
+ * With `stream: true` the result's arrays go to `context.onPartialResult` and the response carries the rest.
+ */
+export async function handleToolRequest(request: MCPToolRequest, context: ToolRequestContext = {}): Promise<MCPToolResult> {
+  const { name, arguments: args = {} } = request.params
+  const { onPartialResult, ...indexOptions } = context
+
+  const result = await routeToolRequest(name, args, indexOptions)
+  return args.stream === true && onPartialResult ? streamToolResult(name, result, onPartialResult) : result
+}
+
+async function routeToolRequest(name: string, args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const logger = getLogger()
+
+  logger.debug(`Handling tool request: ${name}`)
+
+  switch (name) {
+    case 'search_code':
+      return runSupersedingSearch(args, context)
+
+    case 'search_buffer':
+      return handleSearchBuffer(args)
+
+    case 'find_usage':
+      return handleFindUsage(args, context)
+
+    case 'find_definition':
+      return handleFindDefinition(args, context)
+
+    case 'find_references':
+      return handleFindReferences(args, context)
+
+    case 'find_callers':
+      return handleFindCallers(args, context)
+
+    case 'rename_symbol':
+      return handleRenameSymbol(args, context)
+
+    case 'search_and_replace':
+      return handleSearchAndReplace(args, context)
+
+    case 'find_implementations':
+      return handleFindImplementations(args, context)
+
+    case 'list_symbols':
+      return handleListSymbols(args, context)
+
+    case 'extract_signatures':
+      return handleExtractSignatures(args, context)
+    case 'symbol_diff':
+      return handleSymbolDiff(args, context)
+
+    case 'get_node_at_position':
+      return handleGetNodeAtPosition(args, context)
+
+    case 'call_graph':
+      return handleCallGraph(args, context)
+
+    case 'import_graph':
+      return handleImportGraph(args, context)
+
+    case 'resolve_import':
+      return handleResolveImport(args, context)
+
+    case 'type_hierarchy':
+      return handleTypeHierarchy(args, context)
+
+    case 'complexity':
+      return handleComplexity(args, context)
+
+    case 'find_duplicates':
+      return handleFindDuplicates(args, context)
+
+    case 'find_dead_code':
+      return handleFindDeadCode(args, context)
+
+    case 'find_todos':
+      return handleFindTodos(args, context)
+
+    case 'analyze_code':
+      return handleAnalyzeCode(args, context)
+
+    case 'check_errors':
+      return handleCheckErrors(args, context)
+
+    case 'open_project':
+      return handleOpenProject(args, context)
+
+    case 'list_projects':
+      return handleListProjects()
+
+    case 'close_project':
+      return handleCloseProject(args)
+
+    case 'stats':
+      return handleStats(args, context)
+
+    case 'health':
+      return handleHealth()
+
+    default:
+      throw new Error(`Unknown tool: ${name}`)
+  }
+}
+
+// In-flight search_code calls by project; a newer search of the same project cancels the older one
+const activeSearches = new Map<string, AbortController>()
+
+/**
+ * Cancels every in-flight search, e.g. when the client disconnects
+ */
+export function cancelActiveSearches(): void {
+  for (const controller of activeSearches.values()) {
+    controller.abort()
+  }
+  activeSearches.clear()
+}
+
+async function runSupersedingSearch(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const { signal } = context
+  const key = String(args.projectId ?? args.directory ?? process.cwd())
+  activeSearches.get(key)?.abort()
+
+  const controller = new AbortController()
+  const abort = () => controller.abort()
+  if (signal?.aborted) abort()
+  signal?.addEventListener('abort', abort, { once: true })
+  activeSearches.set(key, controller)
+
+  try {
+    return await handleSearchCode(args, { ...context, signal: controller.signal })
+  }
+  finally {
+    signal?.removeEventListener('abort', abort)
+    if (activeSearches.get(key) === controller) {
+      activeSearches.delete(key)
+    }
+  }
+}
+
+async function handleSearchCode(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    query,
+    queryType = 'name',
+    mode = 'name',
+    regexFlags = '',
+    maxResults = 10,
+    fuzzyThreshold = 30,
+    minQueryLength = SEARCH_LIMITS.MIN_QUERY_LENGTH,
+    kind,
+    exactMatch = false,
+    wholeWord = false,
+    subwordAware = false,
+    caseInsensitive = false,
+    types = [],
+    pathPattern,
+    file,
+    startLine,
+    endLine,
+    ignoreDirs = [],
+    language,
+    include,
+    exclude,
+    offset = 0,
+    limit,
+    contextBefore = 0,
+    contextAfter = 0,
+    includeNodeText = false,
+    maxNodeBytes = SEARCH_LIMITS.DEFAULT_NODE_BYTES,
+    scope,
+    testScope = 'include',
+    format,
+    // New content inclusion options
+    forceContentInclusion = false,
+    maxContentLines = 150,
+    disableContentInclusion = false,
+    changedSince,
+    includeUntracked = true,
+    blame = false,
+    sort = 'relevance',
+    currentFile,
+    dedup = true,
+    groupBy,
+    groupOrder = 'count',
+    countOnly = false,
+    timeoutMs,
+  } = args
+
+  if (typeof query !== 'string') {
+    throw new Error('Query must be a string')
+  }
+  if (timeoutMs !== undefined && (typeof timeoutMs !== 'number' || !(timeoutMs > 0))) {
+    throw new Error('timeoutMs must be a positive number')
+  }
+  if (typeof countOnly !== 'boolean') {
+    throw new Error('countOnly must be a boolean')
+  }
+  if (currentFile !== undefined && typeof currentFile !== 'string') {
+    throw new Error('currentFile must be a string')
+  }
+  if (typeof maxNodeBytes !== 'number' || !(maxNodeBytes >= 1)) {
+    throw new Error('maxNodeBytes must be a positive number')
+  }
+  if (typeof minQueryLength !== 'number' || !Number.isInteger(minQueryLength) || minQueryLength < 0) {
+    throw new Error('minQueryLength must be a non-negative integer')
+  }
+  if (kind !== undefined && (typeof kind !== 'string' || kind === '')) {
+    throw new Error('kind must be a non-empty string')
+  }
+  if (changedSince !== undefined && (typeof changedSince !== 'string' || changedSince.trim() === '')) {
+    throw new Error('changedSince must be a non-empty git ref')
+  }
+  if (file !== undefined && (typeof file !== 'string' || file === '')) {
+    throw new Error('file must be a non-empty string')
+  }
+  for (const [name, line] of [['startLine', startLine], ['endLine', endLine]] as const) {
+    if (line !== undefined && (typeof line !== 'number' || !Number.isInteger(line))) {
+      throw new Error(`${name} must be an integer`)
+    }
+  }
+  if ((startLine !== undefined || endLine !== undefined) && file === undefined) {
+    throw new Error('startLine and endLine require file')
+  }
+  if (typeof startLine === 'number' && typeof endLine === 'number' && startLine > endLine) {
+    throw new Error('startLine must not be after endLine')
+  }
+
+  try {
+    const languages = parseLanguageArg(language)
+    const outputFormat = parseFormatArg(format, SEARCH_OUTPUT_FORMATS)
+    if (scope !== undefined && !SYNTAX_SCOPES.includes(scope as SyntaxScope)) {
+      throw createError('SEARCH_ERROR', `Invalid scope: ${scope}. Expected one of: ${SYNTAX_SCOPES.join(', ')}`, { scope })
+    }
+    if (groupBy !== undefined && groupBy !== 'file') {
+      throw createError('SEARCH_ERROR', `Invalid groupBy: ${groupBy}. Expected: file`, { groupBy })
+    }
+    if (!GROUP_ORDERS.includes(groupOrder as GroupOrder)) {
+      throw createError('SEARCH_ERROR', `Invalid groupOrder: ${groupOrder}. Expected one of: ${GROUP_ORDERS.join(', ')}`, { groupOrder })
+    }
+    if (countOnly && (outputFormat !== 'json' || groupBy !== undefined)) {
+      throw createError('SEARCH_ERROR', `countOnly already counts per file and cannot be combined with ${groupBy !== undefined ? 'groupBy' : `format ${outputFormat}`}`, { countOnly, groupBy, format })
+    }
+
+    const project = await getOrCreateMCPProject(
+      typeof projectId === 'string' ? projectId : undefined,
+      typeof directory === 'string' ? directory : undefined,
+      Array.isArray(ignoreDirs) ? ignoreDirs as string[] : [],
+      context,
+      parseWalkArgs(args),
+    )
+
+    // A file narrows the search to that file and its lines to the span asked for, clamped to the file
+    let range: { file: string } & LineRange | undefined
+    if (typeof file === 'string') {
+      const targetFile = resolveProjectPath(project, file)
+      const fileNode = getAllFiles(project).find(f => f.path === targetFile)
+      if (!fileNode) {
+        throw createError('FILE_ERROR', `File is not indexed in this project: ${file}`, { file: targetFile })
+      }
+      range = {
+        file: targetFile,
+        ...clampLineRange(fileNode, startLine as number | undefined, endLine as number | undefined),
+      }
+    }
+
+    const searchOptions: SearchOptions = {
+      fuzzyThreshold: Number(fuzzyThreshold),
+      minQueryLength,
+      exactMatch: Boolean(exactMatch),
+      wholeWord: Boolean(wholeWord),
+      subwordAware: Boolean(subwordAware),
+      caseInsensitive: Boolean(caseInsensitive),
+      regexFlags: String(regexFlags),
+      types: Array.isArray(types) ? types as string[] : [],
+      pathPattern: typeof pathPattern === 'string' ? pathPattern : undefined,
+      scope: scope as SyntaxScope | undefined,
+      // New content inclusion options
+      forceContentInclusion: Boolean(forceContentInclusion),
+      maxContentLines: Number(maxContentLines),
+      disableContentInclusion: Boolean(disableContentInclusion),
+    }
+
+    const search = await createQueryEngine(projectQuerySource(project)).search(query, {
+      ...searchOptions,
+      matcher: queryType === 'tree-sitter' ? 'tree-sitter' : String(mode),
+      kind: typeof kind === 'string' ? kind : undefined,
+      languages,
+      include: parseGlobArg(include),
+      exclude: parseGlobArg(exclude),
+      changedSince: changedSince as string | undefined,
+      includeUntracked: Boolean(includeUntracked),
+      file: range?.file,
+      lineRange: range && { startLine: range.startLine, endLine: range.endLine },
+      testScope: String(testScope),
+      sort: String(sort),
+      currentFile,
+      dedup: dedup as SearchDedup | boolean,
+      offset: Number(offset),
+      limit: Number(limit ?? maxResults),
+      countOnly,
+      timeoutMs,
+      signal: context.signal,
+    })
+    const { audit, timedOut } = search
+
+    if (search.message !== undefined) {
+      if (outputFormat === 'markdown') {
+        return {
+          content: [{
+            type: 'text',
+            text: searchResultsToMarkdown(query, [], { root: project.config.directory, files: [], message: search.message }),
+          }],
+        }
+      }
+      if (outputFormat === 'sarif') {
+        return {
+          content: [{
+            type: 'text',
+            text: JSON.stringify(searchResultsToSarif(query, [], {
+              root: project.config.directory,
+              properties: { projectId: project.id, query, ...range ? { range } : {}, totalResults: 0, hasMore: false, message: search.message, diagnostics: { files: reportFileAudit(audit, 0) } },
+            })),
+          }],
+        }
+      }
+
+      return {
+        content: [{
+          type: 'text',
+          text: JSON.stringify({
+            projectId: project.id,
+            query,
+            ...range ? { range } : {},
+            ...countOnly ? { counts: {}, totalFiles: 0 } : groupBy === 'file' ? { groups: [], totalFiles: 0 } : { results: [] },
+            totalResults: 0,
+            hasMore: false,
+            message: search.message,
+            diagnostics: { files: reportFileAudit(audit, 0) },
+          }, portablePathReplacer),
+        }],
+      }
+    }
+
+    const diagnostics = { dedup: search.dedup, totalBeforeDedup: search.totalBeforeDedup, files: reportFileAudit(audit, search.totalResults) }
+    if (search.counts) {
+      const counts: Record<string, number> = {}
+      for (const [path, count] of search.counts) {
+        counts[toPortablePath(path)] = count
+      }
+      return {
+        content: [{
+          type: 'text',
+          text: JSON.stringify({
+            projectId: project.id,
+            query,
+            ...range ? { range } : {},
+            counts,
+            totalFiles: search.totalFiles,
+            totalResults: search.totalResults,
+            hasMore: false,
+            ...timedOut ? { timedOut } : {},
+            diagnostics,
+          }, portablePathReplacer),
+        }],
+      }
+    }
+
+    const linesBefore = Math.max(0, Math.floor(Number(contextBefore)) || 0)
+    const linesAfter = Math.max(0, Math.floor(Number(contextAfter)) || 0)
+    const withPaths = addEnclosingPaths(search.results, getAllFiles(project))
+    const withContext = linesBefore > 0 || linesAfter > 0
+      ? addContextLines(withPaths, getAllFiles(project), linesBefore, linesAfter)
+      : withPaths
+    const withNodeText = includeNodeText
+      ? addNodeTexts(withContext, getAllFiles(project), Math.floor(maxNodeBytes))
+      : withContext
+
+    // Blame only the files on this page, once each
+    const blameLine = blame ? createBlameLookup(project.config.directory) : undefined
+    const results = blameLine
+      ? withNodeText.map(result => ({ ...result, blame: blameLine(result.node.path, result.node.startLine ?? 1) }))
+      : withNodeText
+
+    if (outputFormat === 'markdown') {
+      return {
+        content: [{
+          type: 'text',
+          text: searchResultsToMarkdown(query, results, {
+            root: project.config.directory,
+            files: getAllFiles(project),
+            totalResults: search.totalResults,
+            totalFiles: search.totalFiles,
+            offset: search.offset,
+          }),
+        }],
+      }
+    }
+
+    if (outputFormat === 'sarif') {
+      return {
+        content: [{
+          type: 'text',
+          text: JSON.stringify(searchResultsToSarif(query, results, {
+            root: project.config.directory,
+            files: getAllFiles(project),
+            properties: { projectId: project.id, query, ...range ? { range } : {}, totalResults: search.totalResults, offset: search.offset, limit: search.limit, hasMore: search.hasMore, ...timedOut ? { timedOut } : {}, diagnostics },
+          })),
+        }],
+      }
+    }
+
+    const resolvePosition = createPositionResolver(getAllFiles(project))
+    const shaped = results.map(r => ({
+      name: r.node.name,
+      type: r.node.type,
+      path: r.node.path,
+      ...resolvePosition(r.node),
+      score: r.score,
+      relevance: r.relevance,
+      matches: r.matches,
+      highlight: r.highlight,
+      captureName: r.captureName,
+      context: r.context,
+      contextStartLine: r.contextStartLine,
+      contextEndLine: r.contextEndLine,
+      nodeText: r.nodeText,
+      enclosingScope: r.enclosingScope,
+      enclosingPath: r.enclosingPath,
+      declarationOnly: r.node.declarationOnly,
+      metavariables: r.metavariables,
+      keyPath: r.keyPath,
+      blame: r.blame,
+      contentIncluded: r.contentIncluded,
+      content: r.content,
+      contentTruncated: r.contentTruncated,
+      contentLines: r.contentLines,
+    }))
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          query,
+          ...range ? { range } : {},
+          // Grouping only reshapes the page; ranking and paging work on results either way
+          ...groupBy === 'file'
+            ? { groups: groupByFile(shaped, groupOrder as GroupOrder), totalFiles: search.totalFiles }
+            : { results: shaped },
+          totalResults: search.totalResults,
+          offset: search.offset,
+          limit: search.limit,
+          hasMore: search.hasMore,
+          ...timedOut ? { timedOut } : {},
+          diagnostics,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Search code failed')
+  }
+}
+
+async function handleSearchBuffer(args: JsonObject): Promise<MCPToolResult> {
+  const {
+    content,
+    language,
+    query,
+    queryType = 'name',
+    mode = 'name',
+    regexFlags = '',
+    maxResults = 10,
+    exactMatch = false,
+    caseInsensitive = false,
+    types = [],
+  } = args
+
+  if (typeof content !== 'string') {
+    throw new Error('Content must be a string')
+  }
+  if (typeof language !== 'string') {
+    throw new Error('Language must be a string')
+  }
+  if (typeof query !== 'string') {
+    throw new Error('Query must be a string')
+  }
+
+  try {
+    const matcher = (queryType === 'tree-sitter' ? 'tree-sitter' : String(mode)) as SearchMatcher
+    if (!SEARCH_MATCHERS.includes(matcher)) {
+      throw createError('SEARCH_ERROR', `Invalid mode: ${mode}. Expected one of: ${SEARCH_MODES.join(', ')}`, { mode })
+    }
+
+    const config = resolveLanguageNames([language])[0]!
+    const fileNode = parseBuffer(content, config)
+    if (fileNode.skipped) {
+      throw createError('PARSE_ERROR', fileNode.skipReason || `Cannot parse ${config.name}`, { language: config.name })
+    }
+
+    try {
+      const results = addEnclosingPaths(searchBuffer(matcher, query, fileNode, {
+        maxResults: Number(maxResults),
+        exactMatch: Boolean(exactMatch),
+        caseInsensitive: Boolean(caseInsensitive),
+        regexFlags: String(regexFlags),
+        types: Array.isArray(types) ? types as string[] : [],
+      }), [fileNode])
+
+      const resolvePosition = createPositionResolver([fileNode])
+      return {
+        content: [{
+          type: 'text',
+          text: JSON.stringify({
+            language: config.name,
+            query,
+            results: results.map(r => ({
+              name: r.node.name,
+              type: r.node.type,
+              ...resolvePosition(r.node),
+              score: r.score,
+              matches: r.matches,
+              captureName: r.captureName,
+              enclosingPath: r.enclosingPath,
+              declarationOnly: r.node.declarationOnly,
+              metavariables: r.metavariables,
+              keyPath: r.keyPath,
+              contentIncluded: r.contentIncluded,
+              content: r.content,
+              contentTruncated: r.contentTruncated,
+              contentLines: r.contentLines,
+            })),
+            totalResults: results.length,
+            ...fileNode.parseErrors ? { parseErrors: fileNode.parseErrors } : {},
+          }),
+        }],
+      }
+    }
+    finally {
+      // The buffer belongs to no project, so nothing will release its tree later
+      releaseTrees([fileNode])
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Search buffer failed')
+  }
+}
+
+async function handleFindUsage(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    identifier,
+    caseSensitive = false,
+    exactMatch = true,
+    maxResults = 50,
+    pathPattern,
+  } = args
+
+  if (typeof identifier !== 'string') {
+    throw new Error('Identifier must be a string')
+  }
+
+  try {
+    const project = await getOrCreateMCPProject(
+      typeof projectId === 'string' ? projectId : undefined,
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+    const { usages, totalUsages } = createQueryEngine(projectQuerySource(project)).findUsage(identifier, {
+      caseSensitive: Boolean(caseSensitive),
+      exactMatch: Boolean(exactMatch),
+      pathPattern: typeof pathPattern === 'string' ? pathPattern : undefined,
+      maxResults: Number(maxResults),
+    })
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          identifier,
+          usages: usages.map(result => ({
+            path: result.node.path,
+            startLine: result.startLine,
+            endLine: result.endLine,
+            startColumn: result.startColumn,
+            endColumn: result.endColumn,
+            type: result.node.type,
+            name: result.node.name,
+            context: result.context,
+          })),
+          totalUsages,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Find usage failed')
+  }
+}
+
+async function handleFindDefinition(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    symbol,
+    file,
+    line,
+  } = args
+
+  if (typeof symbol !== 'string') {
+    throw new Error('Symbol must be a string')
+  }
+
+  try {
+    const project = await getOrCreateMCPProject(
+      projectIdFor(projectId, directory, file),
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    const definitions = findDefinition(symbol, getAllFiles(project), {
+      directory: project.config.directory,
+      file: typeof file === 'string' ? resolveProjectPath(project, file) : undefined,
+      line: typeof line === 'number' ? line : undefined,
+    })
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          symbol,
+          definitions,
+          totalDefinitions: definitions.length,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Find definition failed')
+  }
+}
+
+async function handleFindReferences(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    symbol,
+    includeDefinition = false,
+    maxResults = 50,
+    pathPattern,
+  } = args
+
+  if (typeof symbol !== 'string') {
+    throw new Error('Symbol must be a string')
+  }
+
+  try {
+    const project = await getOrCreateMCPProject(
+      typeof projectId === 'string' ? projectId : undefined,
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    const references = findReferences(symbol, getAllFiles(project), {
+      includeDefinition: Boolean(includeDefinition),
+      pathPattern: typeof pathPattern === 'string' ? pathPattern : undefined,
+    })
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          symbol,
+          references: references.slice(0, Number(maxResults)),
+          totalReferences: references.length,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Find references failed')
+  }
+}
+
+async function handleFindCallers(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    symbol,
+    maxResults = 50,
+    pathPattern,
+  } = args
+
+  if (typeof symbol !== 'string') {
+    throw new Error('Symbol must be a string')
+  }
+
+  try {
+    const project = await getOrCreateMCPProject(
+      typeof projectId === 'string' ? projectId : undefined,
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    const callers = findCallers(symbol, getAllFiles(project), {
+      pathPattern: typeof pathPattern === 'string' ? pathPattern : undefined,
+    })
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          symbol,
+          callers: callers.slice(0, Number(maxResults)),
+          totalCallers: callers.length,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Find callers failed')
+  }
+}
+
+async function handleRenameSymbol(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    symbol,
+    newName,
+    file,
+    line,
+  } = args
+
+  if (typeof symbol !== 'string') {
+    throw new Error('Symbol must be a string')
+  }
+  if (typeof newName !== 'string') {
+    throw new Error('New name must be a string')
+  }
+
+  try {
+    const project = await getOrCreateMCPProject(
+      projectIdFor(projectId, directory, file),
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    const plan = planRename(symbol, newName, getAllFiles(project), {
+      directory: project.config.directory,
+      file: typeof file === 'string' ? resolveProjectPath(project, file) : undefined,
+      line: typeof line === 'number' ? line : undefined,
+    })
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          symbol,
+          newName,
+          ...plan,
+          totalEdits: plan.edits.length,
+          note: plan.note || RENAME_NOTE,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Rename preview failed')
+  }
+}
+
+async function handleSearchAndReplace(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    pattern,
+    replacement,
+    regexFlags = '',
+    caseInsensitive = false,
+    language,
+    include,
+    exclude,
+    apply = false,
+  } = args
+
+  if (typeof pattern !== 'string' || pattern === '') {
+    throw new Error('Pattern must be a non-empty string')
+  }
+  if (typeof replacement !== 'string') {
+    throw new Error('Replacement must be a string')
+  }
+
+  try {
+    const project = await getOrCreateMCPProject(
+      typeof projectId === 'string' ? projectId : undefined,
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    const isCandidate = createCandidateFilter(project.config.directory, {
+      languages: parseLanguageArg(language),
+      include: parseGlobArg(include),
+      exclude: parseGlobArg(exclude),
+      ignoreCase: ignoresPathCase(project.config),
+    })
+    const files = getAllFiles(project).filter(file => isCandidate(file.path))
+    const plan = planReplace(pattern, replacement, files, {
+      regexFlags: String(regexFlags),
+      caseInsensitive: Boolean(caseInsensitive),
+    })
+
+    const written = apply ? applyReplacePlan(plan, files) : []
+    if (written.length > 0) {
+      await updateProject(project, written.map(path => ({ type: 'modified', path, timestamp: Date.now() })))
+    }
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          pattern,
+          replacement,
+          ...plan,
+          totalEdits: plan.edits.length,
+          applied: written.length > 0,
+          note: apply ? `Wrote ${plan.edits.length} edits to ${written.length} files` : REPLACE_NOTE,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Search and replace failed')
+  }
+}
+
+async function handleFindImplementations(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    symbol,
+    maxResults = 50,
+    pathPattern,
+  } = args
+
+  if (typeof symbol !== 'string') {
+    throw new Error('Symbol must be a string')
+  }
+
+  try {
+    const project = await getOrCreateMCPProject(
+      typeof projectId === 'string' ? projectId : undefined,
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    const implementations = findImplementations(symbol, getAllFiles(project), {
+      pathPattern: typeof pathPattern === 'string' ? pathPattern : undefined,
+    })
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          symbol,
+          implementations: implementations.slice(0, Number(maxResults)),
+          totalImplementations: implementations.length,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Find implementations failed')
+  }
+}
+
+async function handleListSymbols(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    file,
+    depth,
+    pathPattern,
+  } = args
+
+  try {
+    const project = await getOrCreateMCPProject(
+      projectIdFor(projectId, directory, file),
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    let files = getAllFiles(project)
+    if (typeof file === 'string') {
+      const targetFile = resolveProjectPath(project, file)
+      files = files.filter(f => f.path === targetFile)
+      if (files.length === 0) {
+        throw createError('FILE_ERROR', `File is not indexed in this project: ${file}`, { file: targetFile })
+      }
+    }
+    else if (typeof pathPattern === 'string') {
+      files = files.filter(f => f.path.includes(pathPattern))
+    }
+
+    // Files with syntax errors keep the symbols outside the broken regions and report where those are
+    const outlines = files
+      .map(f => ({
+        path: f.path,
+        symbols: listSymbols(f, { depth: typeof depth === 'number' ? depth : undefined }),
+        ...f.parseErrors ? { parseErrors: f.parseErrors } : {},
+      }))
+      .filter(outline => outline.symbols.length > 0 || outline.parseErrors)
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          files: outlines,
+          totalSymbols: outlines.reduce((sum, outline) => sum + countSymbols(outline.symbols), 0),
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'List symbols failed')
+  }
+}
+
+async function handleExtractSignatures(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    file,
+    pathPattern,
+  } = args
+
+  try {
+    const project = await getOrCreateMCPProject(
+      projectIdFor(projectId, directory, file),
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    let files = getAllFiles(project)
+    if (typeof file === 'string') {
+      const targetFile = resolveProjectPath(project, file)
+      files = files.filter(f => f.path === targetFile)
+      if (files.length === 0) {
+        throw createError('FILE_ERROR', `File is not indexed in this project: ${file}`, { file: targetFile })
+      }
+    }
+    else if (typeof pathPattern === 'string') {
+      files = files.filter(f => f.path.includes(pathPattern))
+    }
+
+    // Sorted by path so the output of two commits can be diffed
+    const signatures = files
+      .sort((a, b) => (a.path < b.path ? -1 : a.path > b.path ? 1 : 0))
+      .flatMap(f => extractSignatures(f))
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          signatures,
+          totalSignatures: signatures.length,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Extract signatures failed')
+  }
+}
+
+async function handleSymbolDiff(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    from,
+    to = 'HEAD',
+    pathPattern,
+  } = args
+
+  if (typeof from !== 'string' || !from.trim()) {
+    throw new Error('from must be a git ref')
+  }
+  if (typeof to !== 'string' || !to.trim()) {
+    throw new Error('to must be a git ref')
+  }
+
+  try {
+    const project = await getOrCreateMCPProject(
+      typeof projectId === 'string' ? projectId : undefined,
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    const diff = diffSignatures(project.config.directory, from, to, {
+      pathPattern: typeof pathPattern === 'string' ? pathPattern : undefined,
+      languages: project.config.languages,
+    })
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          from,
+          to,
+          ...diff,
+          summary: {
+            added: diff.added.length,
+            removed: diff.removed.length,
+            changed: diff.changed.length,
+            renamed: diff.renamed.length,
+          },
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Symbol diff failed')
+  }
+}
+
+async function handleGetNodeAtPosition(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    file,
+    line,
+    column,
+    anonymous = false,
+  } = args
+
+  if (typeof file !== 'string') {
+    throw new Error('File must be a string')
+  }
+  if (!Number.isInteger(line) || !Number.isInteger(column)) {
+    throw new Error('Line and column must be integers')
+  }
+
+  try {
+    const project = await getOrCreateMCPProject(
+      projectIdFor(projectId, directory, file),
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    const targetFile = resolveProjectPath(project, file)
+    const fileNode = getAllFiles(project).find(f => f.path === targetFile)
+    if (!fileNode) {
+      throw createError('FILE_ERROR', `File is not indexed in this project: ${file}`, { file: targetFile })
+    }
+
+    const result = getNodeAtPosition(fileNode, line as number, column as number, { anonymous: Boolean(anonymous) })
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          path: fileNode.path,
+          ...result,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Get node at position failed')
+  }
+}
+
+async function handleCallGraph(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    symbol,
+    depth = 1,
+    pathPattern,
+  } = args
+
+  if (typeof symbol !== 'string') {
+    throw new Error('Symbol must be a string')
+  }
+
+  try {
+    const project = await getOrCreateMCPProject(
+      typeof projectId === 'string' ? projectId : undefined,
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    let files = getAllFiles(project)
+    if (typeof pathPattern === 'string') {
+      files = files.filter(f => f.path.includes(pathPattern))
+    }
+
+    const graph = buildCallGraph(symbol, files, { depth: Number(depth) || 1 })
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          ...graph,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Call graph failed')
+  }
 }
 
-function getSearchNodes(project: Project) {
-  const allNodes = Array.from(project.files.values())
-  const elementNodes = Array.from(project.nodes.values()).flat()
-  return [...allNodes, ...elementNodes]
+async function handleImportGraph(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    file,
+    pathPattern,
+  } = args
+
+  try {
+    const project = await getOrCreateMCPProject(
+      projectIdFor(projectId, directory, file),
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    const files = getAllFiles(project)
+    let targetFile: string | undefined
+    if (typeof file === 'string') {
+      targetFile = resolveProjectPath(project, file)
+      if (!files.some(f => f.path === targetFile)) {
+        throw createError('FILE_ERROR', `File is not indexed in this project: ${file}`, { file: targetFile })
+      }
+    }
+
+    const graph = buildImportGraph(files, project.config.directory, {
+      file: targetFile,
+      pathPattern: typeof pathPattern === 'string' ? pathPattern : undefined,
+    })
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          ...(targetFile && { file: targetFile }),
+          ...graph,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Import graph failed')
+  }
 }
 
-export async function handleToolRequest(request: MCPToolRequest): Promise<MCPToolResult> {
-  const { name, arguments: args = {} } = request.params
-  const logger = getLogger()
+async function handleResolveImport(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    file,
+    symbol,
+  } = args
 
-  logger.debug(`Handling tool request: ${name}`)
+  if (typeof file !== 'string') {
+    throw new Error('File must be a string')
+  }
+  if (typeof symbol !== 'string' || symbol === '') {
+    throw new Error('Symbol must be a non-empty string')
+  }
 
-  switch (name) {
-    case 'search_code':
-      return handleSearchCode(args)
+  try {
+    const project = await getOrCreateMCPProject(
+      projectIdFor(projectId, directory, file),
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
 
-    case 'find_usage':
-      return handleFindUsage(args)
+    const files = getAllFiles(project)
+    const targetFile = resolveProjectPath(project, file)
+    const fileNode = files.find(f => f.path === targetFile)
+    if (!fileNode) {
+      throw createError('FILE_ERROR', `File is not indexed in this project: ${file}`, { file: targetFile })
+    }
 
-    case 'analyze_code':
-      return handleAnalyzeCode(args)
+    const imports = resolveImport(fileNode, symbol, files, project.config.directory)
+    if (imports.length === 0) {
+      throw createError('SEARCH_ERROR', `${symbol} is not imported by ${file}`, { file: targetFile, symbol })
+    }
 
-    case 'check_errors':
-      return handleCheckErrors(args)
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          path: fileNode.path,
+          symbol,
+          imports,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Resolve import failed')
+  }
+}
 
-    default:
-      throw new Error(`Unknown tool: ${name}`)
+async function handleTypeHierarchy(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    symbol,
+    direction,
+    depth,
+  } = args
+
+  if (typeof symbol !== 'string') {
+    throw new Error('Symbol must be a string')
+  }
+
+  try {
+    const hierarchyDirection = parseDirectionArg(direction)
+    const project = await getOrCreateMCPProject(
+      typeof projectId === 'string' ? projectId : undefined,
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    const hierarchy = buildTypeHierarchy(symbol, getAllFiles(project), {
+      direction: hierarchyDirection,
+      root: project.config.directory,
+      maxDepth: typeof depth === 'number' && depth > 0 ? depth : undefined,
+    })
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          ...hierarchy,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Type hierarchy failed')
   }
 }
 
-async function handleSearchCode(args: JsonObject): Promise<MCPToolResult> {
+async function handleComplexity(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
   const {
     projectId,
     directory,
-    query,
-    maxResults = 10,
-    fuzzyThreshold = 30,
-    exactMatch = false,
-    types = [],
+    threshold = 0,
     pathPattern,
-    // New content inclusion options
-    forceContentInclusion = false,
-    maxContentLines = 150,
-    disableContentInclusion = false,
+    maxResults = 50,
   } = args
 
-  if (typeof query !== 'string') {
-    throw new Error('Query must be a string')
+  try {
+    const project = await getOrCreateMCPProject(
+      typeof projectId === 'string' ? projectId : undefined,
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    const functions = analyzeComplexity(getAllFiles(project), {
+      threshold: Number(threshold) || 0,
+      pathPattern: typeof pathPattern === 'string' ? pathPattern : undefined,
+    })
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          functions: functions.slice(0, Number(maxResults)),
+          totalFunctions: functions.length,
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Complexity analysis failed')
   }
+}
+
+async function handleFindDuplicates(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    minNodes,
+    pathPattern,
+    maxResults = 50,
+  } = args
 
   try {
     const project = await getOrCreateMCPProject(
       typeof projectId === 'string' ? projectId : undefined,
       typeof directory === 'string' ? directory : undefined,
       [],
+      context,
     )
-    const searchNodes = getSearchNodes(project)
 
-    const results = searchCode(query as string, searchNodes, {
-      maxResults: Number(maxResults),
-      fuzzyThreshold: Number(fuzzyThreshold),
-      exactMatch: Boolean(exactMatch),
-      types: Array.isArray(types) ? types as string[] : [],
+    const clusters = findDuplicates(getAllFiles(project), {
+      minNodes: typeof minNodes === 'number' && minNodes > 0 ? minNodes : undefined,
       pathPattern: typeof pathPattern === 'string' ? pathPattern : undefined,
-      // New content inclusion options
-      forceContentInclusion: Boolean(forceContentInclusion),
-      maxContentLines: Number(maxContentLines),
-      disableContentInclusion: Boolean(disableContentInclusion),
     })
 
     return {
@@ -136,88 +1548,101 @@ async function handleSearchCode(args: JsonObject): Promise<MCPToolResult> {
         type: 'text',
         text: JSON.stringify({
           projectId: project.id,
-          query,
-          results: results.map(r => ({
-            name: r.node.name,
-            type: r.node.type,
-            path: r.node.path,
-            startLine: r.node.startLine,
-            endLine: r.node.endLine,
-            startColumn: r.node.startColumn,
-            endColumn: r.node.endColumn,
-            score: r.score,
-            matches: r.matches,
-            contentIncluded: r.contentIncluded,
-            content: r.content,
-            contentTruncated: r.contentTruncated,
-            contentLines: r.contentLines,
-          })),
-          totalResults: results.length,
-        }),
+          clusters: clusters.slice(0, Number(maxResults)),
+          totalClusters: clusters.length,
+        }, portablePathReplacer),
       }],
     }
   }
   catch (error) {
-    throw handleError(error, 'Search code failed')
+    throw handleError(error, 'Duplicate detection failed')
   }
 }
 
-async function handleFindUsage(args: JsonObject): Promise<MCPToolResult> {
+async function handleFindDeadCode(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
   const {
     projectId,
     directory,
-    identifier,
-    caseSensitive = false,
-    exactMatch = true,
-    maxResults = 50,
     pathPattern,
+    maxResults = 50,
   } = args
 
-  if (typeof identifier !== 'string') {
-    throw new Error('Identifier must be a string')
+  try {
+    const project = await getOrCreateMCPProject(
+      typeof projectId === 'string' ? projectId : undefined,
+      typeof directory === 'string' ? directory : undefined,
+      [],
+      context,
+    )
+
+    const deadCode = findDeadCode(getAllFiles(project), {
+      pathPattern: typeof pathPattern === 'string' ? pathPattern : undefined,
+    })
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          deadCode: deadCode.slice(0, Number(maxResults)),
+          totalDeadCode: deadCode.length,
+          note: DEAD_CODE_NOTE,
+        }, portablePathReplacer),
+      }],
+    }
   }
+  catch (error) {
+    throw handleError(error, 'Dead code search failed')
+  }
+}
+
+async function handleFindTodos(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const {
+    projectId,
+    directory,
+    tags,
+    pathPattern,
+  } = args
 
   try {
     const project = await getOrCreateMCPProject(
       typeof projectId === 'string' ? projectId : undefined,
       typeof directory === 'string' ? directory : undefined,
       [],
+      context,
     )
-    const searchNodes = getSearchNodes(project)
 
-    const results = findUsage(identifier, searchNodes, {
-      caseSensitive: Boolean(caseSensitive),
-      exactMatch: Boolean(exactMatch),
+    const tagList = Array.isArray(tags)
+      ? tags.filter((tag): tag is string => typeof tag === 'string' && tag.length > 0)
+      : DEFAULT_TODO_TAGS
+
+    const todos = findTodos(getAllFiles(project), {
+      tags: tagList,
       pathPattern: typeof pathPattern === 'string' ? pathPattern : undefined,
     })
 
+    const byTag: Record<string, TodoItem[]> = Object.fromEntries(tagList.map(tag => [tag, []]))
+    for (const todo of todos) {
+      byTag[todo.tag]!.push(todo)
+    }
+
     return {
       content: [{
         type: 'text',
         text: JSON.stringify({
           projectId: project.id,
-          identifier,
-          usages: results.slice(0, Number(maxResults)).map(result => ({
-            path: result.node.path,
-            startLine: result.startLine,
-            endLine: result.endLine,
-            startColumn: result.startColumn,
-            endColumn: result.endColumn,
-            type: result.node.type,
-            name: result.node.name,
-            context: result.context,
-          })),
-          totalUsages: results.length,
-        }),
+          tags: byTag,
+          totalTodos: todos.length,
+        }, portablePathReplacer),
       }],
     }
   }
   catch (error) {
-    throw handleError(error, 'Find usage failed')
+    throw handleError(error, 'Find todos failed')
   }
 }
 
-async function handleAnalyzeCode(args: JsonObject): Promise<MCPToolResult> {
+async function handleAnalyzeCode(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
   const {
     projectId,
     directory,
@@ -225,15 +1650,19 @@ async function handleAnalyzeCode(args: JsonObject): Promise<MCPToolResult> {
     pathPattern,
     ignoreDirs = [],
     maxResults = 15,
+    format,
   } = args
 
   const analysisTypesArray = Array.isArray(analysisTypes) ? analysisTypes as string[] : ['quality']
 
   try {
+    const outputFormat = parseFormatArg(format, OUTPUT_FORMATS)
     const project = await getOrCreateMCPProject(
       typeof projectId === 'string' ? projectId : undefined,
       typeof directory === 'string' ? directory : undefined,
       Array.isArray(ignoreDirs) ? ignoreDirs as string[] : [],
+      context,
+      parseWalkArgs(args),
     )
 
     const depDirs = findDependencyModuleDirs(project.config.directory, project.nodes)
@@ -264,6 +1693,19 @@ async function handleAnalyzeCode(args: JsonObject): Promise<MCPToolResult> {
 
     const limitedFindings = filteredFindings.slice(0, Number(maxResults))
 
+    if (outputFormat === 'sarif') {
+      return {
+        content: [{
+          type: 'text',
+          text: JSON.stringify(findingsToSarif(limitedFindings, {
+            root: project.config.directory,
+            files: getAllFiles(project),
+            properties: { projectId: project.id, totalFindings: result.findings.length, filteredFindings: limitedFindings.length },
+          })),
+        }],
+      }
+    }
+
     return {
       content: [{
         type: 'text',
@@ -280,7 +1722,7 @@ async function handleAnalyzeCode(args: JsonObject): Promise<MCPToolResult> {
             totalFindings: result.findings.length,
             filteredFindings: limitedFindings.length,
           },
-        }),
+        }, portablePathReplacer),
       }],
     }
   }
@@ -289,20 +1731,24 @@ async function handleAnalyzeCode(args: JsonObject): Promise<MCPToolResult> {
   }
 }
 
-async function handleCheckErrors(args: JsonObject): Promise<MCPToolResult> {
+async function handleCheckErrors(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
   const {
     projectId,
     directory,
     pathPattern,
     ignoreDirs = [],
     maxResults = 50,
+    format,
   } = args
 
   try {
+    const outputFormat = parseFormatArg(format, OUTPUT_FORMATS)
     const project = await getOrCreateMCPProject(
       typeof projectId === 'string' ? projectId : undefined,
       typeof directory === 'string' ? directory : undefined,
       Array.isArray(ignoreDirs) ? ignoreDirs as string[] : [],
+      context,
+      parseWalkArgs(args),
     )
 
     const result = analyzeErrors(project)
@@ -325,8 +1771,23 @@ async function handleCheckErrors(args: JsonObject): Promise<MCPToolResult> {
 
     const limitedErrors = filteredErrors.slice(0, Number(maxResults))
 
+    if (outputFormat === 'sarif') {
+      return {
+        content: [{
+          type: 'text',
+          text: JSON.stringify(errorsToSarif(limitedErrors, {
+            root: project.config.directory,
+            files: getAllFiles(project),
+            properties: { projectId: project.id, totalSourceErrors: partitioned.sourceErrors.length, filteredErrors: limitedErrors.length },
+          })),
+        }],
+      }
+    }
+
+    const files = new Map(getAllFiles(project).map(file => [file.path, file]))
     const condensedErrors = limitedErrors.map(e => ({
       file: e.file,
+      language: getFileLanguage(e.file, files.get(e.file)?.content)?.name,
       line: e.line,
       endLine: e.endLine,
       type: e.type,
@@ -347,11 +1808,155 @@ async function handleCheckErrors(args: JsonObject): Promise<MCPToolResult> {
           totalSourceErrors: partitioned.sourceErrors.length,
           totalDependencyErrors: partitioned.totalDependencyErrors,
           filteredErrors: limitedErrors.length,
-        }),
+          skippedFiles: getSkippedFiles(project),
+          depthSkipped: getDepthSkipped(project),
+          outsideRootLinks: getOutsideRootLinks(project),
+          classifiedFiles: getClassifiedFiles(project),
+        }, portablePathReplacer),
       }],
     }
   }
   catch (error) {
     throw handleError(error, 'Error analysis failed')
   }
+}
+
+async function handleOpenProject(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const { projectId, directory, ignoreDirs } = args
+
+  if (typeof directory !== 'string' || directory.trim() === '') {
+    throw new Error('Directory must be a non-empty string')
+  }
+
+  try {
+    const project = await getOrCreateMCPProject(
+      typeof projectId === 'string' ? projectId : undefined,
+      resolve(directory),
+      Array.isArray(ignoreDirs) ? ignoreDirs.filter((dir): dir is string => typeof dir === 'string') : [],
+      context,
+      parseWalkArgs(args),
+    )
+    const index = getIndexStats(project)
+    const outsideRootLinks = getOutsideRootLinks(project)
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projectId: project.id,
+          directory: project.config.directory,
+          rootType: project.rootType ?? 'unknown',
+          roots: getProjectRoots(project),
+          totalFiles: index.totalFiles,
+          totalSymbols: index.totalSymbols,
+          isWatched: mcpPersistentManager.watchers.has(project.id),
+          ...outsideRootLinks.length > 0 ? { outsideRootLinks } : {},
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Open project failed')
+  }
+}
+
+async function handleListProjects(): Promise<MCPToolResult> {
+  const projects = listProjects(mcpPersistentManager)
+
+  return {
+    content: [{
+      type: 'text',
+      text: JSON.stringify({
+        projects,
+        totalProjects: projects.length,
+        maxProjects: mcpPersistentManager.memory.maxProjects,
+      }, portablePathReplacer),
+    }],
+  }
+}
+
+async function handleCloseProject(args: JsonObject): Promise<MCPToolResult> {
+  const { projectId } = args
+
+  if (typeof projectId !== 'string' || projectId.trim() === '') {
+    throw new Error('Project ID must be a non-empty string')
+  }
+
+  const id = sanitizeProjectId(projectId)
+  const directory = mcpPersistentManager.projectToDirectory.get(id)
+  if (!removeProjectFromManager(mcpPersistentManager, id)) {
+    throw createError('PROJECT_ERROR', `Project not found: ${projectId}`, { projectId })
+  }
+
+  return {
+    content: [{
+      type: 'text',
+      text: JSON.stringify({ projectId: id, directory, closed: true }, portablePathReplacer),
+    }],
+  }
+}
+
+async function handleStats(args: JsonObject, context: IndexOptions): Promise<MCPToolResult> {
+  const { projectId, directory } = args
+
+  try {
+    // Naming a project reports only that one, indexing it first if needed
+    let selected: Project | undefined
+    if (typeof projectId === 'string' || typeof directory === 'string') {
+      selected = await getOrCreateMCPProject(
+        typeof projectId === 'string' ? projectId : undefined,
+        typeof directory === 'string' ? directory : undefined,
+        [],
+        context,
+      )
+    }
+
+    const projects = listProjects(mcpPersistentManager)
+      .filter(entry => !selected || entry.projectId === selected.id)
+      .map(entry => ({
+        ...entry,
+        index: getIndexStats(mcpPersistentManager.memory.projects.get(entry.projectId)!),
+      }))
+
+    const { heapUsed, rss } = process.memoryUsage()
+
+    return {
+      content: [{
+        type: 'text',
+        text: JSON.stringify({
+          projects,
+          treeCache: getTreeCacheStats(),
+          memory: {
+            estimatedIndexBytes: projects.reduce((sum, entry) => sum + entry.index.estimatedMemoryBytes, 0),
+            heapUsedBytes: heapUsed,
+            rssBytes: rss,
+          },
+        }, portablePathReplacer),
+      }],
+    }
+  }
+  catch (error) {
+    throw handleError(error, 'Stats failed')
+  }
+}
+
+/**
+ * Answers from in-memory state only, so it stays fast while an index is being built
+ */
+function handleHealth(): MCPToolResult {
+  const indexing = Array.from(mcpPersistentManager.indexing)
+
+  return {
+    content: [{
+      type: 'text',
+      text: JSON.stringify({
+        version: getVersion(),
+        status: indexing.length > 0 ? 'building' : 'ready',
+        indexing,
+        openProjects: mcpPersistentManager.memory.projects.size,
+        languages: getLoadedLanguages(),
+        disabledLanguages: getDisabledLanguages(),
+      }),
+    }],
+  }
 }
\ No newline at end of file
@@ -2,6 +2,13 @@
  * MCP tool and resource schemas - consistent parameter support across all tools
  */
 
+// Shared by the tools whose results can outgrow one message
+const STREAM_PROPERTY = {
+  type: 'boolean',
+  description: 'Optional: Send the result\'s arrays as notifications/tools/partial_result notifications, about 64KB each, and return only the remaining fields plus a streamed summary. Leave off for clients that cannot read custom notifications',
+  default: false,
+} as const
+
 export const MCP_TOOLS = [
   {
     name: 'search_code',
@@ -11,7 +18,23 @@ export const MCP_TOOLS = [
       properties: {
         query: {
           type: 'string',
-          description: 'Search query (name of element)',
+          description: 'Search query (name of element, or a tree-sitter query when queryType is "tree-sitter")',
+        },
+        queryType: {
+          type: 'string',
+          enum: ['name', 'tree-sitter'],
+          description: 'How to interpret the query: element name matching, or tree-sitter query (.scm) syntax returning one result per capture',
+          default: 'name',
+        },
+        mode: {
+          type: 'string',
+          enum: ['name', 'regex', 'fuzzy', 'structural', 'keyPath'],
+          description: 'Matching mode: element name matching, a regular expression applied to each source line, fuzzy subsequence ranking (e.g. "ChtPrvdr" finds "ChatProvider"), a structural code pattern with $NAME metavariables (e.g. "calc.divide($A, $B:number)"), or a dotted key path into JSON and YAML files (e.g. "database.host", with * for any one key and ** for any depth)',
+          default: 'name',
+        },
+        regexFlags: {
+          type: 'string',
+          description: 'Optional: Regular expression flags for regex mode (e.g. "i"); "m" or "s" match across the whole file',
         },
         projectId: {
           type: 'string',
@@ -25,21 +48,196 @@ export const MCP_TOOLS = [
           type: 'string',
           description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
         },
+        file: {
+          type: 'string',
+          description: 'Optional: Only search this file, absolute or relative to the project root. The response\'s range reports the file and the lines searched',
+        },
+        startLine: {
+          type: 'number',
+          description: 'Optional: With file, first line to search (1-based); matches wholly before it are skipped. Clamped to the file',
+        },
+        endLine: {
+          type: 'number',
+          description: 'Optional: With file, last line to search (inclusive); matches wholly after it are skipped. Clamped to the file',
+        },
+        ignoreDirs: {
+          type: 'array',
+          items: { type: 'string' },
+          description: 'Optional: Additional directories to ignore (beyond default ignore list and .gitignore rules)',
+        },
+        useDefaultExcludes: {
+          type: 'boolean',
+          description: 'Optional: Set to false to also index the default excluded directories (node_modules, target, dist, build, venv and the rest); only ignoreDirs, exclude globs and .gitignore rules then apply, and hidden directories stay skipped (default: true)',
+        },
+        maxDepth: {
+          type: 'number',
+          description: 'Optional: Directory levels to descend below the project root, which is depth 0; deeper directories are not indexed (default: 15)',
+        },
+        followSymlinks: {
+          type: 'boolean',
+          description: 'Optional: Follow symlinked files and directories, indexing each real path once; links pointing outside the project root are skipped and listed (default: false)',
+        },
+        allowOutsideRoot: {
+          type: 'boolean',
+          description: 'Optional: With followSymlinks, also follow links pointing outside the project root (default: false)',
+        },
+        ignoreCaseInPaths: {
+          type: 'boolean',
+          description: 'Optional: Match paths, include/exclude globs and .gitignore rules in any case, and treat paths differing only in case as one file (default: true on macOS and Windows)',
+        },
+        language: {
+          oneOf: [
+            { type: 'string' },
+            { type: 'array', items: { type: 'string' } },
+          ],
+          description: 'Optional: Only search files of these languages (e.g., "typescript" or ["python", "go"])',
+        },
+        include: {
+          type: 'array',
+          items: { type: 'string' },
+          description: 'Optional: Glob patterns relative to the project root; only matching files are searched (e.g., ["src/**/*.ts"])',
+        },
+        exclude: {
+          type: 'array',
+          items: { type: 'string' },
+          description: 'Optional: Glob patterns relative to the project root to skip (e.g., ["**/generated/**"]); wins over include',
+        },
+        changedSince: {
+          type: 'string',
+          description: 'Optional: Git ref (e.g., "main"); only search files changed on this branch since it diverged from the ref, plus uncommitted edits. Errors when the project is not a git repository',
+        },
+        includeUntracked: {
+          type: 'boolean',
+          description: 'Optional: With changedSince, also search files git does not track yet (ignored files never count)',
+          default: true,
+        },
+        blame: {
+          type: 'boolean',
+          description: 'Optional: Add the author and short commit hash of the last change to each result\'s first line; untracked or modified files get null fields and dirty: true',
+          default: false,
+        },
+        sort: {
+          type: 'string',
+          enum: ['relevance', 'path', 'recency'],
+          description: 'Optional: Result order. relevance boosts definitions and files near currentFile and demotes test and generated files; path orders by file and line; recency puts recently modified files first',
+          default: 'relevance',
+        },
+        dedup: {
+          oneOf: [
+            { type: 'boolean' },
+            { type: 'string', enum: ['range', 'line', 'none'] },
+          ],
+          description: 'Optional: Drop results covering the same file span (true or "range"), also fold results on the same line into one with several highlight ranges ("line"), or keep every match (false or "none")',
+          default: true,
+        },
+        groupBy: {
+          type: 'string',
+          enum: ['file'],
+          description: 'Optional: Return the page as `groups` of { file, count, matches } instead of a flat `results` list, with `totalFiles` next to `totalResults`',
+        },
+        groupOrder: {
+          type: 'string',
+          enum: ['count', 'path'],
+          description: 'Optional: With groupBy, put the files with the most matches first (count) or sort them by path',
+          default: 'count',
+        },
+        countOnly: {
+          type: 'boolean',
+          description: 'Optional: Return only the number of matches per file (counts) and totalResults, without the matches themselves; not combinable with groupBy or format sarif (default: false)',
+          default: false,
+        },
+        timeoutMs: {
+          type: 'number',
+          description: 'Optional: Stop searching after this many milliseconds, checked between files, and return the matches found so far with timedOut: true (default: no limit)',
+        },
+        currentFile: {
+          type: 'string',
+          description: 'Optional: File the caller is working in, absolute or relative to the project root; with relevance sorting, results in and near it rank higher',
+        },
         maxResults: {
           type: 'number',
           description: 'Maximum number of results',
           default: 10,
         },
+        offset: {
+          type: 'number',
+          description: 'Optional: Number of ranked results to skip, for paging',
+          default: 0,
+        },
+        limit: {
+          type: 'number',
+          description: 'Optional: Page size (defaults to maxResults); values above 500 are clamped to 500',
+        },
+        contextBefore: {
+          type: 'number',
+          description: 'Optional: Source lines to include before each match in its context field',
+          default: 0,
+        },
+        contextAfter: {
+          type: 'number',
+          description: 'Optional: Source lines to include after each match in its context field',
+          default: 0,
+        },
+        includeNodeText: {
+          type: 'boolean',
+          description: 'Optional: Add nodeText to each result: the source of the declaration around the match (such as the whole function) or, outside any declaration, its top-level statement',
+          default: false,
+        },
+        maxNodeBytes: {
+          type: 'number',
+          description: 'Optional: UTF-8 size cap for nodeText; longer text is cut after the last statement that fits and marked truncated',
+          default: 4096,
+        },
+        scope: {
+          type: 'string',
+          enum: ['code', 'comment', 'string'],
+          description: 'Optional: Keep only regex, structural or tree-sitter matches inside code, comments, or string literals; other modes reject it',
+        },
+        testScope: {
+          type: 'string',
+          enum: ['include', 'exclude', 'only'],
+          description: 'Optional: Search test code too (include, default), leave it out (exclude), or search only tests (only). Tests are test files and directories plus inline tests such as Rust #[cfg(test)] modules',
+        },
+        format: {
+          type: 'string',
+          enum: ['json', 'sarif', 'markdown'],
+          description: 'Optional: Response format; sarif returns a SARIF 2.1.0 log for code scanning tools, markdown a section per file with each match line in a code fence for pasting into chat or a PR',
+          default: 'json',
+        },
         fuzzyThreshold: {
           type: 'number',
           description: 'Minimum fuzzy match score to include results',
           default: 30,
         },
+        kind: {
+          type: 'string',
+          description: 'Optional: Name mode only; search declarations of this kind from each file\'s outline, such as "struct", "enum" or "const", or "fn" for any function or method. Prefix async_, pub_ or static_ to require modifiers ("async_fn", "pub_struct"). With an empty query every declaration of the kind is returned; an unknown kind lists the valid ones for the selected language',
+        },
+        minQueryLength: {
+          type: 'number',
+          description: 'Optional: Shortest query name and fuzzy modes accept, since shorter ones match almost everything; exactMatch, wholeWord and subwordAware searches, the other modes and an empty query are exempt, and 0 turns the check off',
+          default: 2,
+        },
         exactMatch: {
           type: 'boolean',
           description: 'Require exact name match',
           default: false,
         },
+        wholeWord: {
+          type: 'boolean',
+          description: 'Optional: Name mode only; match whole identifier words, so "add" finds add but not address',
+          default: false,
+        },
+        subwordAware: {
+          type: 'boolean',
+          description: 'Optional: Name mode only; treat camelCase and snake_case segments as words, so "user" finds getUser and user_name but not username',
+          default: false,
+        },
+        caseInsensitive: {
+          type: 'boolean',
+          description: 'Optional: Ignore case (Unicode-aware) in name and regex modes, which are case-sensitive by default; fuzzy and tree-sitter queries are unaffected',
+          default: false,
+        },
         types: {
           type: 'array',
           items: { type: 'string' },
@@ -49,6 +247,64 @@ export const MCP_TOOLS = [
       required: ['query'],
     },
   },
+  {
+    name: 'search_buffer',
+    description: 'Search a single in-memory buffer, such as an unsaved editor file, without indexing a project',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        content: {
+          type: 'string',
+          description: 'Source text to parse and search',
+        },
+        language: {
+          type: 'string',
+          description: 'Language to parse the content as (e.g., "rust", "typescript")',
+        },
+        query: {
+          type: 'string',
+          description: 'Search query (name of element, or a tree-sitter query when queryType is "tree-sitter")',
+        },
+        queryType: {
+          type: 'string',
+          enum: ['name', 'tree-sitter'],
+          description: 'How to interpret the query, as in search_code',
+          default: 'name',
+        },
+        mode: {
+          type: 'string',
+          enum: ['name', 'regex', 'fuzzy', 'structural', 'keyPath'],
+          description: 'Matching mode, as in search_code',
+          default: 'name',
+        },
+        regexFlags: {
+          type: 'string',
+          description: 'Optional: Regular expression flags for regex mode (e.g. "i")',
+        },
+        maxResults: {
+          type: 'number',
+          description: 'Maximum number of results',
+          default: 10,
+        },
+        exactMatch: {
+          type: 'boolean',
+          description: 'Require exact name match',
+          default: false,
+        },
+        caseInsensitive: {
+          type: 'boolean',
+          description: 'Optional: Ignore case in name and regex modes',
+          default: false,
+        },
+        types: {
+          type: 'array',
+          items: { type: 'string' },
+          description: 'Filter by element types (function, class, variable, etc.)',
+        },
+      },
+      required: ['content', 'language', 'query'],
+    },
+  },
   {
     name: 'find_usage',
     description: 'Find all usages of a function, variable, class, or identifier',
@@ -91,79 +347,1041 @@ export const MCP_TOOLS = [
     },
   },
   {
-    name: 'analyze_code',
-    description: 'Analyze code quality, structure, dead code, and configuration issues',
+    name: 'find_definition',
+    description: 'Resolve a symbol to its declaration site (function, class, struct, const, etc.)',
     inputSchema: {
       type: 'object',
       properties: {
+        symbol: {
+          type: 'string',
+          description: 'Name of the symbol to resolve',
+        },
         projectId: {
           type: 'string',
-          description: 'Optional: Project ID for targeting specific cached project (if not provided, directory is required)',
+          description: 'Optional: Project ID for targeting specific cached project',
         },
         directory: {
           type: 'string',
-          description: 'Optional: Directory to analyze (default: current working directory)',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+        file: {
+          type: 'string',
+          description: 'Optional: File the symbol is referenced from (absolute or relative to the project directory)',
+        },
+        line: {
+          type: 'number',
+          description: 'Optional: 1-based line of the reference, used with file to pick the nearest enclosing scope',
+        },
+      },
+      required: ['symbol'],
+    },
+  },
+  {
+    name: 'find_references',
+    description: 'List identifier references to a symbol, skipping strings, comments, and the declaration itself',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        symbol: {
+          type: 'string',
+          description: 'Name of the symbol to find references to',
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
         },
         pathPattern: {
           type: 'string',
           description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
         },
-        ignoreDirs: {
-          type: 'array',
-          items: { type: 'string' },
-          description: 'Optional: Additional directories to ignore (beyond default ignore list)',
-        },
-        analysisTypes: {
-          type: 'array',
-          items: {
-            type: 'string',
-            enum: ['quality', 'structure', 'deadcode'],
-          },
-          description: 'Analysis types to run: quality, deadcode, structure',
-          default: ['quality'],
+        includeDefinition: {
+          type: 'boolean',
+          description: 'Include the declaration site in the results',
+          default: false,
         },
         maxResults: {
           type: 'number',
-          description: 'Maximum number of findings to return',
-          default: 15,
+          description: 'Maximum number of results',
+          default: 50,
         },
       },
-      required: ['analysisTypes'],
+      required: ['symbol'],
     },
   },
   {
-    name: 'check_errors',
-    description: 'Find actionable syntax errors with detailed context and fix suggestions',
+    name: 'find_callers',
+    description: 'List the call sites of a function or method, each with the function making the call. Unlike find_references, imports, arguments and other mentions that are not calls are left out; receiver calls such as obj.save() match on the method name',
     inputSchema: {
       type: 'object',
       properties: {
+        symbol: {
+          type: 'string',
+          description: 'Name of the function or method; a qualified name such as "Store.save" matches calls by its last segment',
+        },
         projectId: {
           type: 'string',
           description: 'Optional: Project ID for targeting specific cached project',
         },
         directory: {
           type: 'string',
-          description: 'Optional: Directory to check for errors (default: current working directory)',
+          description: 'Optional: Directory to search (default: current working directory)',
         },
         pathPattern: {
           type: 'string',
           description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
         },
-        ignoreDirs: {
-          type: 'array',
-          items: { type: 'string' },
-          description: 'Optional: Additional directories to ignore (beyond default ignore list)',
-        },
         maxResults: {
           type: 'number',
-          description: 'Maximum number of errors to return',
+          description: 'Maximum number of results',
           default: 50,
         },
       },
-      required: [],
+      required: ['symbol'],
     },
   },
-]
+  {
+    name: 'rename_symbol',
+    description: 'Preview a rename: list every edit (file, byte range, old and new text) that renaming a symbol would make, following lexical scope so shadowed and unrelated same-named variables are left alone. Files are not modified',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        symbol: {
+          type: 'string',
+          description: 'Current name of the symbol',
+        },
+        newName: {
+          type: 'string',
+          description: 'Name to rename the symbol to',
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+        file: {
+          type: 'string',
+          description: 'Optional: File declaring or using the symbol (absolute or relative to the project directory); required when several declarations share the name',
+        },
+        line: {
+          type: 'number',
+          description: 'Optional: 1-based line in file where the symbol appears, used to pick the binding to rename',
+        },
+      },
+      required: ['symbol', 'newName'],
+    },
+  },
+  {
+    name: 'search_and_replace',
+    description: 'Preview a regex replacement across the project: list every edit (file, byte range, old and new text) in the same shape as rename_symbol. Files are only written when apply is true',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        pattern: {
+          type: 'string',
+          description: 'JavaScript regular expression, matched line by line unless regexFlags has m or s',
+        },
+        replacement: {
+          type: 'string',
+          description: 'Replacement text; $1, $<name>, $& and $$ refer to the match as in String.prototype.replace',
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+        regexFlags: {
+          type: 'string',
+          description: 'Optional: Flags for the pattern (i, m, s, u); g is always added',
+          default: '',
+        },
+        caseInsensitive: {
+          type: 'boolean',
+          description: 'Optional: Ignore case when matching, like adding the i flag',
+          default: false,
+        },
+        language: {
+          oneOf: [
+            { type: 'string' },
+            { type: 'array', items: { type: 'string' } },
+          ],
+          description: 'Optional: Only replace in files of these languages',
+        },
+        include: {
+          type: 'array',
+          items: { type: 'string' },
+          description: 'Optional: Glob patterns relative to the project root; only matching files are edited',
+        },
+        exclude: {
+          type: 'array',
+          items: { type: 'string' },
+          description: 'Optional: Glob patterns relative to the project root to leave alone; wins over include',
+        },
+        apply: {
+          type: 'boolean',
+          description: 'Optional: Write the edits. Each file is replaced atomically after checking that its edits do not overlap and it has not changed since indexing',
+          default: false,
+        },
+      },
+      required: ['pattern', 'replacement'],
+    },
+  },
+  {
+    name: 'find_implementations',
+    description: 'List the types implementing a trait or interface (Rust impl blocks, TypeScript implements clauses), flagging generic and blanket impls',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        symbol: {
+          type: 'string',
+          description: 'Name of the trait or interface, optionally qualified (e.g., "Display", "fmt::Display")',
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+        pathPattern: {
+          type: 'string',
+          description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
+        },
+        maxResults: {
+          type: 'number',
+          description: 'Maximum number of results',
+          default: 50,
+        },
+      },
+      required: ['symbol'],
+    },
+  },
+  {
+    name: 'list_symbols',
+    description: 'Return the nested symbol outline (functions, classes, structs, impls, methods) of a file or the whole project',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        file: {
+          type: 'string',
+          description: 'Optional: File to outline, absolute or relative to the project directory (default: every file)',
+        },
+        depth: {
+          type: 'number',
+          description: 'Optional: Maximum nesting depth; 1 returns only top-level symbols',
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+        pathPattern: {
+          type: 'string',
+          description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
+        },
+        stream: STREAM_PROPERTY,
+      },
+    },
+  },
+  {
+    name: 'extract_signatures',
+    description: 'List function and method signatures (name, parameters with types, return type, visibility) of a file or the whole project, without their bodies',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        file: {
+          type: 'string',
+          description: 'Optional: File to read signatures from, absolute or relative to the project directory (default: every file)',
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+        pathPattern: {
+          type: 'string',
+          description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
+        },
+        stream: STREAM_PROPERTY,
+      },
+    },
+  },
+  {
+    name: 'symbol_diff',
+    description: 'Compare function and method signatures between two git commits: the declarations added, removed, changed and renamed (same body under a new name), for reviewing API changes',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        from: {
+          type: 'string',
+          description: 'Git ref to compare from (branch, tag or commit)',
+        },
+        to: {
+          type: 'string',
+          description: 'Optional: Git ref to compare to (default: HEAD)',
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+        pathPattern: {
+          type: 'string',
+          description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
+        },
+      },
+      required: ['from'],
+    },
+  },
+  {
+    name: 'get_node_at_position',
+    description: 'Return the syntax node at a line and column of a file, for editor cursor queries: its type, text, line, column and byte range, and the types of its ancestors up to the root. Positions past the end of a line or file are clamped and flagged',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        file: {
+          type: 'string',
+          description: 'File to look in, absolute or relative to the project directory',
+        },
+        line: {
+          type: 'number',
+          description: '1-based line number',
+        },
+        column: {
+          type: 'number',
+          description: '0-based column, as search results report it',
+        },
+        anonymous: {
+          type: 'boolean',
+          description: 'Optional: Return the smallest node of any kind, including punctuation and keywords, instead of the smallest named node',
+          default: false,
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+      },
+      required: ['file', 'line', 'column'],
+    },
+  },
+  {
+    name: 'call_graph',
+    description: 'Show the functions a function calls and the functions that call it, resolved against project definitions',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        symbol: {
+          type: 'string',
+          description: 'Function or method name (bare or qualified, e.g. "divide" or "Calculator.Add")',
+        },
+        depth: {
+          type: 'number',
+          description: 'Optional: Levels of callers and callees to follow',
+          default: 1,
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+        pathPattern: {
+          type: 'string',
+          description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
+        },
+        stream: STREAM_PROPERTY,
+      },
+      required: ['symbol'],
+    },
+  },
+  {
+    name: 'import_graph',
+    description: 'Build a directed graph of file-to-file imports (JS/TS import and require, Python import and from, Rust use and mod) and flag circular dependencies',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        file: {
+          type: 'string',
+          description: 'Optional: Only include this file and the files it imports transitively (absolute or relative to the project directory)',
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+        pathPattern: {
+          type: 'string',
+          description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
+        },
+        stream: STREAM_PROPERTY,
+      },
+    },
+  },
+  {
+    name: 'resolve_import',
+    description: 'Find the project file an imported name comes from, following the import in the given file by the same rules as import_graph; packages and other unresolvable imports are reported with resolved: false',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        file: {
+          type: 'string',
+          description: 'File containing the import (absolute or relative to the project directory)',
+        },
+        symbol: {
+          type: 'string',
+          description: 'Name the import binds (e.g., "Cart" for import { Cart } from \'./cart.js\', "Stock" for use crate::inventory::Stock)',
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+      },
+      required: ['file', 'symbol'],
+    },
+  },
+  {
+    name: 'type_hierarchy',
+    description: 'Show the supertypes and subtypes of a class, interface, struct or trait as a tree (extends, implements, Rust impls and supertraits)',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        symbol: {
+          type: 'string',
+          description: 'Type name to root the hierarchy at (e.g., "Shape", "MemoryStore")',
+        },
+        direction: {
+          type: 'string',
+          enum: ['up', 'down', 'both'],
+          description: 'Optional: up for supertypes, down for subtypes, or both',
+          default: 'both',
+        },
+        depth: {
+          type: 'number',
+          description: 'Optional: Maximum levels to follow from the type (default: unlimited)',
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+      },
+      required: ['symbol'],
+    },
+  },
+  {
+    name: 'complexity',
+    description: 'Report the cyclomatic complexity of each function (branches, loops, && and ||, ternaries), most complex first',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        threshold: {
+          type: 'number',
+          description: 'Optional: Only report functions whose complexity is above this value',
+          default: 0,
+        },
+        maxResults: {
+          type: 'number',
+          description: 'Maximum number of functions to return',
+          default: 50,
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+        pathPattern: {
+          type: 'string',
+          description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
+        },
+      },
+    },
+  },
+  {
+    name: 'find_duplicates',
+    description: 'Find clusters of structurally identical functions and blocks, ignoring identifier names and literal values',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        minNodes: {
+          type: 'number',
+          description: 'Optional: Minimum syntax node count for a function or block to be compared',
+          default: 30,
+        },
+        maxResults: {
+          type: 'number',
+          description: 'Maximum number of clusters to return',
+          default: 50,
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+        pathPattern: {
+          type: 'string',
+          description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
+        },
+        stream: STREAM_PROPERTY,
+      },
+    },
+  },
+  {
+    name: 'find_dead_code',
+    description: 'Find private functions and types with no references in the project (Rust non-pub, unexported TS, _python names, etc.), skipping entry points and tests',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+        pathPattern: {
+          type: 'string',
+          description: 'Optional: Only report symbols in files containing this text in their path; references are still counted project-wide',
+        },
+        maxResults: {
+          type: 'number',
+          description: 'Maximum number of symbols to return',
+          default: 50,
+        },
+        stream: STREAM_PROPERTY,
+      },
+    },
+  },
+  {
+    name: 'find_todos',
+    description: 'List TODO, FIXME, HACK, and XXX comments grouped by tag, with any (author) or @name assignee',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        tags: {
+          type: 'array',
+          items: { type: 'string' },
+          description: 'Optional: Comment tags to collect (default: ["TODO", "FIXME", "HACK", "XXX"])',
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to search (default: current working directory)',
+        },
+        pathPattern: {
+          type: 'string',
+          description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
+        },
+      },
+    },
+  },
+  {
+    name: 'analyze_code',
+    description: 'Analyze code quality, structure, dead code, and configuration issues',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project (if not provided, directory is required)',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to analyze (default: current working directory)',
+        },
+        pathPattern: {
+          type: 'string',
+          description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
+        },
+        ignoreDirs: {
+          type: 'array',
+          items: { type: 'string' },
+          description: 'Optional: Additional directories to ignore (beyond default ignore list)',
+        },
+        useDefaultExcludes: {
+          type: 'boolean',
+          description: 'Optional: Set to false to also index the default excluded directories (node_modules, target, dist, build, venv and the rest); only ignoreDirs, exclude globs and .gitignore rules then apply, and hidden directories stay skipped (default: true)',
+        },
+        maxDepth: {
+          type: 'number',
+          description: 'Optional: Directory levels to descend below the project root, which is depth 0; deeper directories are not indexed (default: 15)',
+        },
+        followSymlinks: {
+          type: 'boolean',
+          description: 'Optional: Follow symlinked files and directories, indexing each real path once; links pointing outside the project root are skipped and listed (default: false)',
+        },
+        allowOutsideRoot: {
+          type: 'boolean',
+          description: 'Optional: With followSymlinks, also follow links pointing outside the project root (default: false)',
+        },
+        ignoreCaseInPaths: {
+          type: 'boolean',
+          description: 'Optional: Match paths, include/exclude globs and .gitignore rules in any case, and treat paths differing only in case as one file (default: true on macOS and Windows)',
+        },
+        analysisTypes: {
+          type: 'array',
+          items: {
+            type: 'string',
+            enum: ['quality', 'structure', 'deadcode'],
+          },
+          description: 'Analysis types to run: quality, deadcode, structure',
+          default: ['quality'],
+        },
+        maxResults: {
+          type: 'number',
+          description: 'Maximum number of findings to return',
+          default: 15,
+        },
+        format: {
+          type: 'string',
+          enum: ['json', 'sarif'],
+          description: 'Optional: Response format; sarif returns a SARIF 2.1.0 log for code scanning tools',
+          default: 'json',
+        },
+        stream: STREAM_PROPERTY,
+      },
+      required: ['analysisTypes'],
+    },
+  },
+  {
+    name: 'check_errors',
+    description: 'Find actionable syntax errors with detailed context and fix suggestions',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        projectId: {
+          type: 'string',
+          description: 'Optional: Project ID for targeting specific cached project',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Directory to check for errors (default: current working directory)',
+        },
+        pathPattern: {
+          type: 'string',
+          description: 'Optional: Filter results to files containing this text in their path (e.g., "server", "client", "components")',
+        },
+        ignoreDirs: {
+          type: 'array',
+          items: { type: 'string' },
+          description: 'Optional: Additional directories to ignore (beyond default ignore list)',
+        },
+        useDefaultExcludes: {
+          type: 'boolean',
+          description: 'Optional: Set to false to also index the default excluded directories (node_modules, target, dist, build, venv and the rest); only ignoreDirs, exclude globs and .gitignore rules then apply, and hidden directories stay skipped (default: true)',
+        },
+        maxDepth: {
+          type: 'number',
+          description: 'Optional: Directory levels to descend below the project root, which is depth 0; deeper directories are not indexed (default: 15)',
+        },
+        followSymlinks: {
+          type: 'boolean',
+          description: 'Optional: Follow symlinked files and directories, indexing each real path once; links pointing outside the project root are skipped and listed (default: false)',
+        },
+        allowOutsideRoot: {
+          type: 'boolean',
+          description: 'Optional: With followSymlinks, also follow links pointing outside the project root (default: false)',
+        },
+        ignoreCaseInPaths: {
+          type: 'boolean',
+          description: 'Optional: Match paths, include/exclude globs and .gitignore rules in any case, and treat paths differing only in case as one file (default: true on macOS and Windows)',
+        },
+        maxResults: {
+          type: 'number',
+          description: 'Maximum number of errors to return',
+          default: 50,
+        },
+        format: {
+          type: 'string',
+          enum: ['json', 'sarif'],
+          description: 'Optional: Response format; sarif returns a SARIF 2.1.0 log for code scanning tools',
+          default: 'json',
+        },
+        stream: STREAM_PROPERTY,
+      },
+      required: [],
+    },
+  },
+  {
+    name: 'open_project',
+    description: 'Index a project root and keep it open under a projectId; pass that projectId to search_code and the other tools to target this project',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        directory: {
+          type: 'string',
+          description: 'Root directory of the project to open',
+        },
+        projectId: {
+          type: 'string',
+          description: 'Optional: ID to register the project under (default: derived from the directory name)',
+        },
+        ignoreDirs: {
+          type: 'array',
+          items: { type: 'string' },
+          description: 'Optional: Additional directories to ignore (beyond default ignore list)',
+        },
+        useDefaultExcludes: {
+          type: 'boolean',
+          description: 'Optional: Set to false to also index the default excluded directories (node_modules, target, dist, build, venv and the rest); only ignoreDirs, exclude globs and .gitignore rules then apply, and hidden directories stay skipped (default: true)',
+        },
+        maxDepth: {
+          type: 'number',
+          description: 'Optional: Directory levels to descend below the project root, which is depth 0; deeper directories are not indexed (default: 15)',
+        },
+        followSymlinks: {
+          type: 'boolean',
+          description: 'Optional: Follow symlinked files and directories, indexing each real path once; links pointing outside the project root are skipped and listed (default: false)',
+        },
+        allowOutsideRoot: {
+          type: 'boolean',
+          description: 'Optional: With followSymlinks, also follow links pointing outside the project root (default: false)',
+        },
+        ignoreCaseInPaths: {
+          type: 'boolean',
+          description: 'Optional: Match paths, include/exclude globs and .gitignore rules in any case, and treat paths differing only in case as one file (default: true on macOS and Windows)',
+        },
+      },
+      required: ['directory'],
+    },
+  },
+  {
+    name: 'list_projects',
+    description: 'List the open projects with their projectId, root directory, last access time and watch status',
+    inputSchema: {
+      type: 'object',
+      properties: {},
+    },
+  },
+  {
+    name: 'close_project',
+    description: 'Close an open project, stopping its file watcher and freeing its index',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        projectId: {
+          type: 'string',
+          description: 'ID of the project to close',
+        },
+      },
+      required: ['projectId'],
+    },
+  },
+  {
+    name: 'stats',
+    description: 'Report index size for the projects held in memory (files, symbols and bytes per language, retained parse trees), parse tree cache hit rate, and approximate memory use',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        projectId: {
+          type: 'string',
+          description: 'Optional: Report only this project, indexing it first if needed',
+        },
+        directory: {
+          type: 'string',
+          description: 'Optional: Report only the project rooted at this directory, indexing it first if needed',
+        },
+      },
+      required: [],
+    },
+  },
+  {
+    name: 'health',
+    description: 'Check that the server is responsive without parsing anything: server version, loaded languages with their grammar versions, whether an index is being built, and how many projects are open',
+    inputSchema: {
+      type: 'object',
+      properties: {},
+      required: [],
+    },
+  },
+]
+
+// Result schemas describe the JSON object each tool returns as its text content. A streamed result drops
+// its arrays and adds `streamed`, so arrays are never required
+const PROJECT_ID = { type: 'string', description: 'Project the tool ran against' } as const
+const STREAMED = {
+  type: 'object',
+  description: 'Present when stream was set: the chunk count and the number of items sent per array field',
+  properties: { chunks: { type: 'integer' }, fields: { type: 'object', additionalProperties: { type: 'integer' } } },
+} as const
+const SARIF_NOTE = '; with format sarif, a SARIF 2.1.0 log instead'
+
+function objectResult(description: string, properties: Record<string, object>, required: string[] = ['projectId']) {
+  return {
+    type: 'object',
+    description,
+    properties: { ...properties, streamed: STREAMED },
+    required,
+  }
+}
+
+function listOf(description: string) {
+  return { type: 'array', items: { type: 'object' }, description }
+}
+
+function countOf(description: string) {
+  return { type: 'integer', description }
+}
+
+export const MCP_TOOL_RESULTS: Record<string, object> = {
+  search_code: objectResult(`Ranked search matches, one page at a time${SARIF_NOTE}, or with format markdown, a markdown document`, {
+    projectId: PROJECT_ID,
+    query: { type: 'string' },
+    results: listOf('Matches on this page; absent when groupBy is file'),
+    groups: listOf('Matches on this page grouped by file, when groupBy is file'),
+    counts: { type: 'object', additionalProperties: { type: 'number' }, description: 'Matches per file, in path order, when countOnly is set' },
+    totalFiles: countOf('Files with a match, when groupBy is file or countOnly is set'),
+    totalResults: countOf('Matches across all pages'),
+    offset: countOf('Index of the first match on this page'),
+    limit: countOf('Page size used'),
+    hasMore: { type: 'boolean' },
+    timedOut: { type: 'boolean', description: 'Set when timeoutMs elapsed before the search finished' },
+    message: { type: 'string', description: 'Explains an empty result' },
+    diagnostics: { type: 'object', description: 'Deduplication and file audit details' },
+  }, ['projectId', 'query', 'totalResults', 'hasMore']),
+  search_buffer: objectResult('Matches in the given source text', {
+    language: { type: 'string' },
+    query: { type: 'string' },
+    results: listOf('Matches in the buffer'),
+    totalResults: countOf('Number of matches'),
+    parseErrors: listOf('Syntax errors tree-sitter recovered from'),
+  }, ['language', 'query', 'totalResults']),
+  find_usage: objectResult('Places an identifier is used', {
+    projectId: PROJECT_ID,
+    identifier: { type: 'string' },
+    usages: listOf('Usages, up to maxResults'),
+    totalUsages: countOf('Usages found before the maxResults cut'),
+  }),
+  find_definition: objectResult('Declarations of a symbol', {
+    projectId: PROJECT_ID,
+    symbol: { type: 'string' },
+    definitions: listOf('Matching declarations'),
+    totalDefinitions: countOf('Number of declarations'),
+  }),
+  find_references: objectResult('References to a symbol', {
+    projectId: PROJECT_ID,
+    symbol: { type: 'string' },
+    references: listOf('References, up to maxResults'),
+    totalReferences: countOf('References found before the maxResults cut'),
+  }),
+  find_callers: objectResult('Call sites of a function', {
+    projectId: PROJECT_ID,
+    symbol: { type: 'string' },
+    callers: listOf('Callers, up to maxResults'),
+    totalCallers: countOf('Callers found before the maxResults cut'),
+  }),
+  rename_symbol: objectResult('Edits that would rename a symbol; nothing is written', {
+    projectId: PROJECT_ID,
+    symbol: { type: 'string' },
+    newName: { type: 'string' },
+    target: { type: 'object', description: 'Declaration being renamed' },
+    edits: listOf('Byte and line ranges to replace'),
+    files: { type: 'array', items: { type: 'string' }, description: 'Files the edits touch' },
+    totalEdits: countOf('Number of edits'),
+    note: { type: 'string' },
+  }),
+  search_and_replace: objectResult('Edits for a structural replacement, written when apply was set', {
+    projectId: PROJECT_ID,
+    pattern: { type: 'string' },
+    replacement: { type: 'string' },
+    edits: listOf('Byte and line ranges to replace'),
+    files: { type: 'array', items: { type: 'string' }, description: 'Files the edits touch' },
+    totalEdits: countOf('Number of edits'),
+    applied: { type: 'boolean' },
+    note: { type: 'string' },
+  }),
+  find_implementations: objectResult('Types implementing or extending a type', {
+    projectId: PROJECT_ID,
+    symbol: { type: 'string' },
+    implementations: listOf('Implementations, up to maxResults'),
+    totalImplementations: countOf('Implementations found before the maxResults cut'),
+  }),
+  list_symbols: objectResult('Symbol outlines per file', {
+    projectId: PROJECT_ID,
+    files: listOf('One outline per file, with nested symbols'),
+    totalSymbols: countOf('Symbols across all outlines'),
+  }),
+  extract_signatures: objectResult('Function and method signatures', {
+    projectId: PROJECT_ID,
+    signatures: listOf('Signatures with parameters and return types'),
+    totalSignatures: countOf('Number of signatures'),
+  }),
+  symbol_diff: objectResult('Declarations that changed between two git revisions', {
+    projectId: PROJECT_ID,
+    from: { type: 'string' },
+    to: { type: 'string' },
+    added: listOf('Declarations only in to'),
+    removed: listOf('Declarations only in from'),
+    changed: listOf('Same name and file, different signature or visibility'),
+    renamed: listOf('Removed and added declarations with the same body'),
+    summary: { type: 'object', description: 'Count of each kind of change' },
+  }),
+  get_node_at_position: objectResult('Smallest syntax node at a position', {
+    projectId: PROJECT_ID,
+    path: { type: 'string' },
+    line: countOf('Line looked up, after clamping'),
+    column: countOf('Column looked up, after clamping'),
+    clamped: { type: 'boolean' },
+    requested: { type: 'object', description: 'The position asked for, when it was clamped' },
+    node: { type: 'object' },
+    ancestors: { type: 'array', items: { type: 'string' }, description: 'Parent node types from the innermost out' },
+  }),
+  call_graph: objectResult('Calls into and out of a function', {
+    projectId: PROJECT_ID,
+    symbol: { type: 'string' },
+    functions: listOf('Functions in the graph'),
+    callees: listOf('Call edges out of the functions'),
+    callers: listOf('Call edges into the functions'),
+    external: listOf('Calls to functions outside the project'),
+  }),
+  import_graph: objectResult('Import edges between project files', {
+    projectId: PROJECT_ID,
+    file: { type: 'string', description: 'File the graph was limited to' },
+    files: { type: 'array', items: { type: 'string' } },
+    edges: listOf('Imports between project files'),
+    external: listOf('Imports of outside modules'),
+    cycles: { type: 'array', items: { type: 'array', items: { type: 'string' } }, description: 'Import cycles' },
+  }),
+  resolve_import: objectResult('Where an imported name comes from', {
+    projectId: PROJECT_ID,
+    path: { type: 'string', description: 'File containing the import' },
+    symbol: { type: 'string' },
+    imports: listOf('Imports binding the name, each with its module, line, resolved, and the file it resolves to'),
+  }),
+  type_hierarchy: objectResult('Supertypes and subtypes of a type', {
+    projectId: PROJECT_ID,
+    root: { type: 'object', description: 'The type asked for, with its parents and children' },
+    direction: { type: 'string', enum: ['up', 'down', 'both'] },
+    note: { type: 'string', description: 'Explains an empty hierarchy' },
+  }),
+  complexity: objectResult('Cyclomatic complexity per function, most complex first', {
+    projectId: PROJECT_ID,
+    functions: listOf('Functions, up to maxResults'),
+    totalFunctions: countOf('Functions above the threshold, before the maxResults cut'),
+  }),
+  find_duplicates: objectResult('Clusters of near-identical code', {
+    projectId: PROJECT_ID,
+    clusters: listOf('Clusters, up to maxResults'),
+    totalClusters: countOf('Clusters found before the maxResults cut'),
+  }),
+  find_dead_code: objectResult('Symbols with no references', {
+    projectId: PROJECT_ID,
+    deadCode: listOf('Unreferenced symbols, up to maxResults'),
+    totalDeadCode: countOf('Unreferenced symbols found'),
+    note: { type: 'string' },
+  }),
+  find_todos: objectResult('TODO-style comments grouped by tag', {
+    projectId: PROJECT_ID,
+    tags: { type: 'object', additionalProperties: { type: 'array', items: { type: 'object' } } },
+    totalTodos: countOf('Comments found'),
+  }),
+  analyze_code: objectResult(`Analysis findings${SARIF_NOTE}`, {
+    analysis: { type: 'object', description: 'Findings, metrics and the options used, including projectId' },
+  }, ['analysis']),
+  check_errors: objectResult(`Syntax errors in project files${SARIF_NOTE}`, {
+    projectId: PROJECT_ID,
+    errors: listOf('Errors in project sources, up to maxResults'),
+    dependencyModules: listOf('Error counts per dependency module'),
+    summary: { type: 'object' },
+    totalSourceErrors: countOf('Errors in project sources'),
+    totalDependencyErrors: countOf('Errors in dependency modules'),
+    filteredErrors: countOf('Errors returned'),
+    skippedFiles: listOf('Files skipped as binary or too large'),
+    depthSkipped: { type: 'array', items: { type: 'string' }, description: 'Directories beyond maxDepth' },
+    outsideRootLinks: { type: 'array', items: { type: 'string' }, description: 'Symlinks not followed for pointing outside the project root' },
+    classifiedFiles: listOf('Files classified by content rather than extension'),
+  }),
+  open_project: objectResult('The indexed project', {
+    projectId: PROJECT_ID,
+    directory: { type: 'string' },
+    rootType: { type: 'string' },
+    roots: listOf('Nested project roots'),
+    totalFiles: countOf('Indexed files'),
+    totalSymbols: countOf('Indexed symbols'),
+    isWatched: { type: 'boolean' },
+    outsideRootLinks: { type: 'array', items: { type: 'string' }, description: 'Symlinks not followed for pointing outside the project root' },
+  }, ['projectId', 'directory']),
+  list_projects: objectResult('Open projects', {
+    projects: listOf('Open projects with their root directory, last access time and watch status'),
+    totalProjects: countOf('Open projects'),
+    maxProjects: countOf('Projects kept open before the least recently used is closed'),
+  }, ['totalProjects']),
+  close_project: objectResult('The closed project', {
+    projectId: PROJECT_ID,
+    directory: { type: 'string' },
+    closed: { type: 'boolean' },
+  }, ['projectId', 'closed']),
+  stats: objectResult('Index and memory statistics', {
+    projects: listOf('Index size per project and language'),
+    treeCache: { type: 'object', description: 'Retained parse trees and hit rate' },
+    memory: { type: 'object', description: 'Approximate index size, heap and resident memory in bytes' },
+  }, ['treeCache', 'memory']),
+  health: objectResult('Server status', {
+    version: { type: 'string' },
+    status: { type: 'string', enum: ['building', 'ready'] },
+    indexing: { type: 'array', items: { type: 'string' }, description: 'IDs of projects whose index is being built' },
+    openProjects: countOf('Open projects'),
+    languages: listOf('Loaded languages with their grammar versions'),
+    disabledLanguages: listOf('Languages whose grammar failed to load'),
+  }, ['version', 'status']),
+}
+
+/**
+ * JSON Schemas for each tool: the input schema the server advertises and validates against, and the
+ * shape of the result's JSON text content
+ */
+export function getToolJsonSchemas() {
+  return MCP_TOOLS.map(tool => ({
+    name: tool.name,
+    description: tool.description,
+    inputSchema: tool.inputSchema,
+    outputSchema: MCP_TOOL_RESULTS[tool.name],
+  }))
+}
 
 export const MCP_RESOURCES = [
   {
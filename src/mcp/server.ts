@@ -9,22 +9,54 @@ import {
   CallToolRequestSchema,
   ListResourcesRequestSchema,
   ReadResourceRequestSchema,
+  type ServerNotification,
 } from '@modelcontextprotocol/sdk/types.js'
 
 import { analyzeProject } from '../analysis/index.js'
-import { handleToolRequest } from './handlers.js'
+import { handleToolRequest, setWatchMode, cancelActiveSearches, onIndexChange } from './handlers.js'
+import { PARTIAL_RESULT_METHOD } from './streaming.js'
 import { MCP_TOOLS, MCP_RESOURCES } from './schemas.js'
+import { setMaxFileSize } from '../core/file-walker.js'
+import { setMaxTrees } from '../core/tree-cache.js'
+import { getDisabledLanguages } from '../core/languages.js'
 import { getLogger } from '../utils/logger.js'
 import { handleError } from '../utils/errors.js'
+import { isCancelledError } from '../utils/cancellation.js'
 import { getVersion } from '../utils/version.js'
-import type { JsonObject } from '../types/core.js'
+import { toPortablePath } from '../output/paths.js'
+import type { IndexProgress } from '../project/manager.js'
+import type { FileChange, JsonObject, PartialToolResult } from '../types/core.js'
+
+export const INDEX_CHANGED_METHOD = 'notifications/index/fileChanged'
+
+// Clients opt in to INDEX_CHANGED_METHOD notifications by declaring this experimental capability
+export const INDEX_EVENTS_CAPABILITY = 'indexEvents'
+
+export interface MCPServerOptions {
+  /** Keep project indexes fresh by reparsing changed files (default: on outside tests) */
+  watch?: boolean
+  /** Files larger than this many bytes are skipped when indexing (default: 5MB) */
+  maxFileSize?: number
+  /** Parse trees kept in memory before the least recently used are evicted; 0 for no limit (default: 5000) */
+  maxTrees?: number
+}
 
 /**
  * Starts the MCP server with stdio transport
  */
-export async function startMCPServer(): Promise<void> {
+export async function startMCPServer(options: MCPServerOptions = {}): Promise<void> {
   const logger = getLogger()
 
+  if (options.watch !== undefined) {
+    setWatchMode(options.watch)
+  }
+  if (options.maxFileSize !== undefined) {
+    setMaxFileSize(options.maxFileSize)
+  }
+  if (options.maxTrees !== undefined) {
+    setMaxTrees(options.maxTrees)
+  }
+
   try {
     const server = new Server(
       {
@@ -35,6 +67,7 @@ export async function startMCPServer(): Promise<void> {
         capabilities: {
           resources: {},
           tools: {},
+          experimental: { [INDEX_EVENTS_CAPABILITY]: {} },
         },
       },
     )
@@ -43,7 +76,7 @@ export async function startMCPServer(): Promise<void> {
       tools: MCP_TOOLS,
     }))
 
-    server.setRequestHandler(CallToolRequestSchema, async (request) => {
+    server.setRequestHandler(CallToolRequestSchema, async (request, extra) => {
       try {
         const toolRequest = {
           ...request,
@@ -52,9 +85,19 @@ export async function startMCPServer(): Promise<void> {
             arguments: request.params.arguments as JsonObject,
           },
         }
-        return await handleToolRequest(toolRequest)
+        // The SDK aborts this signal when the client sends notifications/cancelled for the request
+        const progressToken = request.params._meta?.progressToken
+        return await handleToolRequest(toolRequest, {
+          signal: extra.signal,
+          onProgress: progressToken !== undefined ? createProgressNotifier(progressToken, extra.sendNotification) : undefined,
+          onPartialResult: partial => sendPartialResult(partial, extra.requestId, progressToken, extra.sendNotification),
+        })
       }
       catch (error) {
+        if (isCancelledError(error)) {
+          logger.debug(`Tool request cancelled: ${request.params.name}`)
+          throw error
+        }
         logger.error('Tool request failed:', error)
         throw handleError(error, `Tool request failed: ${request.params.name}`)
       }
@@ -94,13 +137,91 @@ export async function startMCPServer(): Promise<void> {
       }
     })
 
+    let stopIndexEvents: (() => void) | undefined
+    server.oninitialized = () => {
+      if (server.getClientCapabilities()?.experimental?.[INDEX_EVENTS_CAPABILITY]) {
+        stopIndexEvents = onIndexChange((projectId, changes) => sendIndexChanges(projectId, changes, notification => server.notification(notification)))
+      }
+    }
+
+    server.onclose = () => {
+      cancelActiveSearches()
+      stopIndexEvents?.()
+    }
+
     const transport = new StdioServerTransport()
     await server.connect(transport)
 
     logger.info('MCP server started successfully')
+    for (const { name, reason } of getDisabledLanguages()) {
+      logger.warn(`Language ${name} is disabled: ${reason}`)
+    }
   }
   catch (error) {
     logger.error('Failed to start MCP server:', error)
     throw handleError(error, 'Failed to start MCP server')
   }
+}
+
+/**
+ * Sends one chunk of a streamed tool response. The request id, and the progress token when the client
+ * sent one, tie the chunk to its call; the SDK has no schema for this notification, hence the cast.
+ */
+function sendPartialResult(
+  partial: PartialToolResult,
+  requestId: string | number,
+  progressToken: string | number | undefined,
+  sendNotification: (notification: ServerNotification) => Promise<void>,
+): void {
+  sendNotification({
+    method: PARTIAL_RESULT_METHOD,
+    params: { requestId, ...progressToken !== undefined ? { progressToken } : {}, ...partial },
+  } as unknown as ServerNotification).catch(error => getLogger().debug('Failed to send partial result:', error))
+}
+
+/**
+ * Sends one notification per file in a reindexed batch. The watcher has already debounced the events and
+ * coalesced them to the latest change per path, so a burst of saves to one file sends it once.
+ */
+function sendIndexChanges(
+  projectId: string,
+  changes: FileChange[],
+  sendNotification: (notification: ServerNotification) => Promise<void>,
+): void {
+  for (const { path, type } of changes) {
+    sendNotification({
+      method: INDEX_CHANGED_METHOD,
+      params: { projectId, path: toPortablePath(path), kind: type },
+    } as unknown as ServerNotification).catch(error => getLogger().debug('Failed to send index change:', error))
+  }
+}
+
+// Per-file notifications would flood the client on large projects
+const PROGRESS_INTERVAL_MS = 100
+
+/**
+ * Forwards index progress as MCP progress notifications for a request that asked for them with a progress token
+ */
+function createProgressNotifier(
+  progressToken: string | number,
+  sendNotification: (notification: ServerNotification) => Promise<void>,
+): (progress: IndexProgress) => void {
+  let lastSent = 0
+
+  return (progress) => {
+    const now = performance.now()
+    const finished = progress.filesDone === progress.totalFiles
+    if (!finished && now - lastSent < PROGRESS_INTERVAL_MS) return
+    lastSent = now
+
+    sendNotification({
+      method: 'notifications/progress',
+      params: {
+        progressToken,
+        progress: progress.filesDone,
+        total: progress.totalFiles,
+        message: `Indexed ${progress.currentPath}`,
+      },
+    }).catch(error => getLogger().debug('Failed to send progress notification:', error))
+  }
 }
\ No newline at end of file
@@ -0,0 +1,71 @@
+/**
+ * Streamed tool responses - sends a large JSON result's arrays as partial-result notifications so the
+ * final response stays small
+ */
+
+import { STREAM_LIMITS } from '../constants/index.js'
+import type { JsonObject, JsonValue, PartialToolResult } from '../types/core.js'
+
+export const PARTIAL_RESULT_METHOD = 'notifications/tools/partial_result'
+
+interface TextToolResult {
+  content: Array<{
+    type: 'text'
+    text: string
+  }>
+  [key: string]: unknown
+}
+
+/**
+ * Splits every top-level array of a JSON result into chunks of about `chunkBytes`, passing each to `send`
+ * in order. The returned result keeps the other fields and, under `streamed`, the chunk count and the
+ * number of items sent per field. Results that are not a JSON object (markdown reports) are returned as is.
+ */
+export function streamToolResult<T extends TextToolResult>(
+  tool: string,
+  result: T,
+  send: (partial: PartialToolResult) => void,
+  chunkBytes: number = STREAM_LIMITS.CHUNK_BYTES,
+): T {
+  const text = result.content[0]?.text
+  let payload: JsonValue
+  try {
+    payload = JSON.parse(text ?? '')
+  }
+  catch {
+    return result
+  }
+  if (typeof payload !== 'object' || payload === null || Array.isArray(payload)) return result
+
+  const rest: JsonObject = {}
+  const fields: Record<string, number> = {}
+  let chunk = 0
+
+  for (const [field, value] of Object.entries(payload)) {
+    if (!Array.isArray(value)) {
+      rest[field] = value
+      continue
+    }
+
+    fields[field] = value.length
+    let items: JsonValue[] = []
+    let size = 0
+    for (const item of value) {
+      const itemSize = JSON.stringify(item).length
+      // A single item larger than a chunk still goes out, alone
+      if (items.length > 0 && size + itemSize > chunkBytes) {
+        send({ tool, field, chunk: chunk++, items })
+        items = []
+        size = 0
+      }
+      items.push(item)
+      size += itemSize
+    }
+    if (items.length > 0) send({ tool, field, chunk: chunk++, items })
+  }
+
+  return {
+    ...result,
+    content: [{ type: 'text', text: JSON.stringify({ ...rest, streamed: { chunks: chunk, fields } }) }],
+  }
+}
\ No newline at end of file
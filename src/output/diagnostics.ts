@@ -0,0 +1,32 @@
+/**
+ * Search diagnostics - the file audit as reported next to search results
+ */
+
+import type { SearchFileAudit } from '../types/core.js'
+
+export interface FileAuditReport extends SearchFileAudit {
+  /** Set when nothing matched because no file was searched, naming what left the files out */
+  note?: string
+}
+
+const EXCLUSION_LABELS: Record<keyof SearchFileAudit['excluded'], string> = {
+  gitignore: 'gitignore',
+  tooLarge: 'too large',
+  binary: 'binary',
+  languageFilter: 'language filter',
+  pathFilter: 'path filters',
+  unparsed: 'not parsable',
+  depthLimit: 'beyond maxDepth',
+}
+
+export function reportFileAudit(audit: SearchFileAudit, totalResults: number): FileAuditReport {
+  if (totalResults > 0 || audit.parsed > 0) return audit
+
+  const reasons = (Object.keys(EXCLUSION_LABELS) as (keyof SearchFileAudit['excluded'])[])
+    .filter(key => audit.excluded[key] > 0)
+    .map(key => `${audit.excluded[key]} ${EXCLUSION_LABELS[key]}`)
+  const note = audit.considered === 0
+    ? 'No files were found under the project directory'
+    : `No file was searched: all ${audit.considered} were excluded (${reasons.join(', ')})`
+  return { ...audit, note }
+}
\ No newline at end of file
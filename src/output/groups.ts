@@ -0,0 +1,34 @@
+/**
+ * Result grouping - folds a flat list of results into one entry per file
+ */
+
+export type GroupOrder = 'count' | 'path'
+
+export const GROUP_ORDERS: readonly GroupOrder[] = ['count', 'path']
+
+export interface FileGroup<T> {
+  file: string
+  count: number
+  /** The file's results in their original order, without the `path` every one of them shares */
+  matches: Omit<T, 'path'>[]
+}
+
+/**
+ * Groups results by their `path`. `count` puts the files with the most matches first, breaking ties by
+ * path; `path` sorts by path alone.
+ */
+export function groupByFile<T extends { path: string }>(results: T[], order: GroupOrder = 'count'): FileGroup<T>[] {
+  const groups = new Map<string, FileGroup<T>>()
+  for (const { path, ...match } of results) {
+    let group = groups.get(path)
+    if (!group) {
+      group = { file: path, count: 0, matches: [] }
+      groups.set(path, group)
+    }
+    group.matches.push(match)
+    group.count++
+  }
+
+  return Array.from(groups.values()).sort((a, b) =>
+    (order === 'count' ? b.count - a.count : 0) || a.file.localeCompare(b.file))
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+/**
+ * JSON Lines output - writes one search result per line as it is found, then a closing summary line
+ */
+
+import { portablePathReplacer } from './paths.js'
+import type { SearchResult } from '../types/core.js'
+
+export interface JsonlSummary {
+  type: 'summary'
+  query: string
+  totalResults: number
+}
+
+export interface JsonlWriter {
+  write(result: SearchResult): void
+  /** Writes the summary line; a stream without one ended early */
+  end(query: string): JsonlSummary
+}
+
+/**
+ * Each result is written to the stream immediately so consumers can process it before the search finishes
+ */
+export function createJsonlWriter(stream: { write(chunk: string): unknown } = process.stdout): JsonlWriter {
+  let totalResults = 0
+
+  return {
+    write(result) {
+      stream.write(`${JSON.stringify(result, portablePathReplacer)}\n`)
+      totalResults++
+    },
+    end(query) {
+      const summary: JsonlSummary = { type: 'summary', query, totalResults }
+      stream.write(`${JSON.stringify(summary)}\n`)
+      return summary
+    },
+  }
+}
\ No newline at end of file
@@ -0,0 +1,116 @@
+/**
+ * Markdown output - search results as a section per file with a code fence per match, for pasting into chat or a PR
+ */
+
+import { relative } from 'path'
+import { toPortablePath } from './paths.js'
+import { OUTPUT_FORMATS } from './sarif.js'
+import { getFileLanguage } from '../core/languages.js'
+import { PARSER_NAMES } from '../constants/index.js'
+import type { SearchResult, TreeNode } from '../types/core.js'
+
+export const SEARCH_OUTPUT_FORMATS = [...OUTPUT_FORMATS, 'markdown'] as const
+export type SearchOutputFormat = typeof SEARCH_OUTPUT_FORMATS[number]
+
+// Fence info strings differing from the parser name
+const FENCE_LANGUAGES: Record<string, string> = {
+  [PARSER_NAMES.CSHARP]: 'csharp',
+}
+
+// Marks the match line, as in usage contexts
+const MATCH_MARKER = '→ '
+
+export interface MarkdownOptions {
+  /** Paths are shown relative to this directory */
+  root: string
+  /** Indexed files, read for each match's source lines and language */
+  files: TreeNode[]
+  /** Matches across every page (default: the results given) */
+  totalResults?: number
+  /** Files with a match across every page (default: the files of the results given) */
+  totalFiles?: number
+  /** Index of the first result given among all matches */
+  offset?: number
+  /** Shown under the header, such as why nothing matched */
+  message?: string
+}
+
+/**
+ * Formats a page of results as markdown: a summary header, then a section per file in result order with
+ * each match's line, and its context lines when the results carry them, in a fence tagged with the file's
+ * language. The match line is marked with `→`.
+ */
+export function searchResultsToMarkdown(query: string, results: SearchResult[], options: MarkdownOptions): string {
+  const { root, files, offset = 0, message } = options
+  const byPath = new Map<string, SearchResult[]>()
+  for (const result of results) {
+    const group = byPath.get(result.node.path)
+    if (group) group.push(result)
+    else byPath.set(result.node.path, [result])
+  }
+  const totalResults = options.totalResults ?? results.length
+  const totalFiles = options.totalFiles ?? byPath.size
+
+  const blocks = [totalResults === 0
+    ? `# No matches for ${inlineCode(query)}`
+    : `# ${plural(totalResults, 'match', 'matches')} for ${inlineCode(query)} in ${plural(totalFiles, 'file', 'files')}`]
+  if (results.length > 0 && results.length < totalResults) {
+    blocks.push(`Showing ${offset + 1}-${offset + results.length} of ${totalResults}.`)
+  }
+  if (message) blocks.push(message)
+
+  const sources = new Map(files.map(file => [file.path, file]))
+  for (const [path, group] of byPath) {
+    const fileNode = sources.get(path)
+    const lines = (fileNode?.content ?? '').split('\n').map(line => line.replace(/\r$/, ''))
+    const language = fileNode && getFileLanguage(fileNode.path, fileNode.content)
+    const fenceLanguage = language ? FENCE_LANGUAGES[language.name] ?? language.name : ''
+
+    blocks.push(`## ${inlineCode(toPortablePath(relative(root, path)))}`)
+    for (const result of group) {
+      blocks.push(describeMatch(result), fence(matchLines(result, lines), fenceLanguage))
+    }
+  }
+
+  return blocks.join('\n\n')
+}
+
+function describeMatch({ node, enclosingPath }: SearchResult): string {
+  const title = node.name ? `**${inlineCode(node.name)}** ${node.type}` : `**${node.type}**`
+  const scope = enclosingPath && enclosingPath !== node.name ? ` in ${inlineCode(enclosingPath)}` : ''
+  return `${title} at line ${node.startLine ?? 1}${scope}`
+}
+
+// The match line, widened to the result's context window; each line carries its number
+function matchLines(result: SearchResult, lines: string[]): string[] {
+  const line = result.node.startLine ?? 1
+  const first = Math.min(line, result.contextStartLine ?? line)
+  const last = Math.max(line, result.contextEndLine ?? line)
+  const width = String(last).length
+
+  const numbered: string[] = []
+  for (let number = first; number <= last; number++) {
+    const marker = number === line ? MATCH_MARKER : ' '.repeat(MATCH_MARKER.length)
+    numbered.push(`${marker}${String(number).padStart(width)} | ${lines[number - 1] ?? ''}`)
+  }
+  return numbered
+}
+
+// A fence longer than any backtick run in the lines, so source holding ``` cannot close it early
+function fence(lines: string[], language: string): string {
+  const longest = Math.max(0, ...lines.map(line => Math.max(0, ...(line.match(/`+/g) ?? []).map(run => run.length))))
+  const ticks = '`'.repeat(Math.max(3, longest + 1))
+  return [`${ticks}${language}`, ...lines, ticks].join('\n')
+}
+
+function inlineCode(text: string): string {
+  const longest = Math.max(0, ...(text.match(/`+/g) ?? []).map(run => run.length))
+  const ticks = '`'.repeat(longest + 1)
+  // A space keeps a leading or trailing backtick apart from the delimiters
+  const padded = text.startsWith('`') || text.endsWith('`') ? ` ${text} ` : text
+  return `${ticks}${padded}${ticks}`
+}
+
+function plural(count: number, one: string, many: string): string {
+  return `${count} ${count === 1 ? one : many}`
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+/**
+ * Result positions - converts the UTF-16 columns results carry into character columns and byte offsets
+ * into the file on disk
+ */
+
+import { getEncodedLength, toSourceByteOffset } from '../utils/encoding.js'
+import type { ResultPosition, SourceEncoding, TreeNode } from '../types/core.js'
+
+interface LineIndex {
+  lines: string[]
+  byteOffsets: number[]
+  encoding?: SourceEncoding
+}
+
+/**
+ * Returns a lookup that resolves a result node against its file's source, indexing each file once on
+ * first use. Without the source, character columns fall back to the UTF-16 ones and `byteRange` is null.
+ */
+export function createPositionResolver(files: TreeNode[]): (node: TreeNode) => ResultPosition {
+  const sources = new Map(files.filter(file => file.content !== undefined).map(file => [file.path, file]))
+  const indexes = new Map<string, LineIndex | null>()
+
+  const getIndex = (path: string): LineIndex | null => {
+    let index = indexes.get(path)
+    if (index === undefined) {
+      const source = sources.get(path)
+      index = source === undefined ? null : indexLines(source.content!, source.encoding)
+      indexes.set(path, index)
+    }
+    return index
+  }
+
+  return (node) => {
+    const startLine = node.startLine ?? 1
+    const endLine = node.endLine ?? startLine
+    const utf16Column = node.startColumn ?? 0
+    const utf16EndColumn = node.endColumn ?? utf16Column
+    const index = getIndex(node.path)
+    if (!index) {
+      return { startLine, startColumn: utf16Column, endLine, endColumn: utf16EndColumn, utf16Column, utf16EndColumn, byteRange: null }
+    }
+
+    const start = resolvePoint(index, startLine, utf16Column)
+    const end = resolvePoint(index, endLine, utf16EndColumn)
+    return {
+      startLine,
+      startColumn: start.column,
+      endLine,
+      endColumn: end.column,
+      utf16Column,
+      utf16EndColumn,
+      byteRange: { start: start.byte, end: end.byte },
+    }
+  }
+}
+
+// Offsets start past the BOM and count the file's own encoding, as toSourceByteOffset does
+function indexLines(content: string, encoding?: SourceEncoding): LineIndex {
+  const lines = content.split('\n')
+  const byteOffsets: number[] = []
+  let offset = toSourceByteOffset(content, 0, encoding)
+  for (const line of lines) {
+    byteOffsets.push(offset)
+    offset += getEncodedLength(`${line}\n`, encoding)
+  }
+  return { lines, byteOffsets, encoding }
+}
+
+// A surrogate pair is one character but two UTF-16 code units and four UTF-8 bytes
+function resolvePoint(index: LineIndex, line: number, utf16Column: number): { column: number, byte: number } {
+  const row = Math.min(Math.max(0, line - 1), index.lines.length - 1)
+  const prefix = index.lines[row]!.slice(0, utf16Column)
+  return {
+    column: Array.from(prefix).length,
+    byte: index.byteOffsets[row]! + getEncodedLength(prefix, index.encoding),
+  }
+}
\ No newline at end of file
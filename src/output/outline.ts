@@ -0,0 +1,35 @@
+/**
+ * Symbol outline output - prints a file's document symbols as an indented tree for the terminal
+ */
+
+import { Chalk } from 'chalk'
+import type { DocumentSymbol } from '../types/core.js'
+
+export interface OutlineOptions {
+  color?: boolean
+  /** Spaces per nesting level (default: 2) */
+  indent?: number
+}
+
+/**
+ * Formats one line per symbol, children indented under their parent: `kind name (line N)`.
+ * Symbols spanning several lines show the range, e.g. `(lines 3-12)`.
+ */
+export function formatOutlineLines(symbols: DocumentSymbol[], options: OutlineOptions = {}): string[] {
+  const { color = false, indent = 2 } = options
+  const paint = new Chalk({ level: color ? 1 : 0 })
+  const lines: string[] = []
+
+  const visit = (current: DocumentSymbol[], level: number) => {
+    for (const symbol of current) {
+      const span = symbol.endLine > symbol.startLine
+        ? `lines ${symbol.startLine}-${symbol.endLine}`
+        : `line ${symbol.startLine}`
+      lines.push(`${' '.repeat(level * indent)}${paint.magenta(symbol.kind)} ${paint.bold(symbol.name)} ${paint.dim(`(${span})`)}`)
+      visit(symbol.children, level + 1)
+    }
+  }
+
+  visit(symbols, 0)
+  return lines
+}
\ No newline at end of file
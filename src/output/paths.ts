@@ -0,0 +1,28 @@
+/**
+ * Portable paths - reported paths use forward slashes on every platform
+ */
+
+import { sep } from 'path'
+
+// Result fields that hold a path, a list of paths, or a `path:line` location
+const PATH_KEYS = new Set(['path', 'file', 'from', 'to', 'files', 'cycles', 'location'])
+
+export function toPortablePath(path: string, separator: string = sep): string {
+  return separator === '/' ? path : path.split(separator).join('/')
+}
+
+/**
+ * `JSON.stringify` replacer that rewrites the path fields of results; other strings are left untouched
+ */
+export function portablePathReplacer(key: string, value: unknown): unknown {
+  return PATH_KEYS.has(key) ? toPortablePaths(value) : value
+}
+
+/**
+ * Rewrites a path or a (nested) list of paths; objects are returned as-is for the replacer to visit
+ */
+export function toPortablePaths(value: unknown, separator: string = sep): unknown {
+  if (typeof value === 'string') return toPortablePath(value, separator)
+  if (Array.isArray(value)) return value.map(item => toPortablePaths(item, separator))
+  return value
+}
\ No newline at end of file
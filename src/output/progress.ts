@@ -0,0 +1,51 @@
+/**
+ * Index progress bar - redraws a single stderr line while a project is parsed
+ */
+
+import { relative } from 'path'
+import type { IndexProgress } from '../project/manager.js'
+
+const BAR_WIDTH = 24
+const REDRAW_INTERVAL_MS = 50
+
+export interface ProgressBarOptions {
+  /** Paths are shown relative to this directory (default: the current working directory) */
+  cwd?: string
+  /** Terminal width; the line is cut to fit */
+  columns?: number
+}
+
+/**
+ * Formats one progress line, e.g. `Indexing [██████░░░░] 120/480 src/core/search.ts`
+ */
+export function formatProgressBar(progress: IndexProgress, options: ProgressBarOptions = {}): string {
+  const { cwd = process.cwd(), columns = 80 } = options
+  const { filesDone, totalFiles, currentPath } = progress
+
+  const ratio = totalFiles > 0 ? Math.min(1, filesDone / totalFiles) : 1
+  const filled = Math.round(ratio * BAR_WIDTH)
+  const bar = '█'.repeat(filled) + '░'.repeat(BAR_WIDTH - filled)
+  const line = `Indexing [${bar}] ${filesDone}/${totalFiles} ${relative(cwd, currentPath)}`
+
+  return line.length < columns ? line : line.slice(0, Math.max(0, columns - 1))
+}
+
+/**
+ * Returns a progress callback that draws on `stream`, or undefined when it is not a terminal.
+ * The line is cleared once every file is indexed.
+ */
+export function createProgressBar(
+  stream: NodeJS.WriteStream = process.stderr,
+): ((progress: IndexProgress) => void) | undefined {
+  if (!stream.isTTY) return undefined
+
+  let lastDrawn = 0
+  return (progress) => {
+    const finished = progress.filesDone >= progress.totalFiles
+    const now = performance.now()
+    if (!finished && now - lastDrawn < REDRAW_INTERVAL_MS) return
+    lastDrawn = now
+
+    stream.write(finished ? '\r\u001B[K' : `\r${formatProgressBar(progress, { columns: stream.columns })}\u001B[K`)
+  }
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+/**
+ * Ripgrep-style line output - prints `path:line:column:text` for editor integrations and scripts
+ */
+
+import { Chalk } from 'chalk'
+import { relative } from 'path'
+import { toPortablePath } from './paths.js'
+import type { SearchResult, TreeNode } from '../types/core.js'
+
+export const COLOR_MODES = ['auto', 'always', 'never'] as const
+export type ColorMode = typeof COLOR_MODES[number]
+
+export interface RipgrepOptions {
+  /** Paths are printed relative to this directory (default: the current working directory) */
+  cwd?: string
+  color?: boolean
+}
+
+/**
+ * `auto` colors only when writing to a terminal and `NO_COLOR` is unset or empty
+ */
+export function shouldUseColor(
+  mode: ColorMode,
+  stream: { isTTY?: boolean } = process.stdout,
+  env: NodeJS.ProcessEnv = process.env,
+): boolean {
+  if (mode === 'always') return true
+  if (mode === 'never') return false
+  return Boolean(stream.isTTY) && !env.NO_COLOR
+}
+
+/**
+ * Formats one line per result with 1-based line and column numbers, like `rg --vimgrep`.
+ * The column points at the result's name on its first line when it appears there.
+ */
+export function formatRipgrepLines(results: SearchResult[], files: TreeNode[], options: RipgrepOptions = {}): string[] {
+  const { cwd = process.cwd(), color = false } = options
+  const paint = new Chalk({ level: color ? 1 : 0 })
+  const fileLines = new Map<string, string[]>()
+
+  const getLines = (path: string): string[] => {
+    let lines = fileLines.get(path)
+    if (!lines) {
+      const content = files.find(file => file.path === path)?.content ?? ''
+      lines = content.split('\n').map(line => line.replace(/\r$/, ''))
+      fileLines.set(path, lines)
+    }
+    return lines
+  }
+
+  return results.map(({ node }) => {
+    const line = node.startLine ?? 1
+    const text = getLines(node.path)[line - 1] ?? ''
+    const [start, end] = getMatchSpan(node, text)
+
+    const highlighted = text.slice(0, start) + paint.bold.red(text.slice(start, end)) + text.slice(end)
+    return `${paint.magenta(toPortablePath(relative(cwd, node.path)))}:${paint.green(String(line))}:${start + 1}:${highlighted}`
+  })
+}
+
+function getMatchSpan(node: TreeNode, text: string): [number, number] {
+  const startColumn = Math.min(node.startColumn ?? 0, text.length)
+
+  if (node.name) {
+    const nameIndex = text.indexOf(node.name, startColumn)
+    if (nameIndex !== -1) return [nameIndex, nameIndex + node.name.length]
+  }
+
+  const endColumn = node.endLine === node.startLine && node.endColumn !== undefined
+    ? Math.min(node.endColumn, text.length)
+    : text.length
+  return [startColumn, Math.max(startColumn, endColumn)]
+}
\ No newline at end of file
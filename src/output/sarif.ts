@@ -0,0 +1,269 @@
+/**
+ * SARIF 2.1.0 serialization - converts search results, analysis findings, and syntax errors for code scanning
+ */
+
+import { isAbsolute, relative } from 'path'
+import { pathToFileURL } from 'url'
+import { toPortablePath } from './paths.js'
+import { getVersion } from '../utils/version.js'
+import { getEncodedLength, toSourceByteOffset } from '../utils/encoding.js'
+import type { ActionableError } from '../analysis/errors.js'
+import type { Finding } from '../types/analysis.js'
+import type { JsonObject, SearchResult, SourceEncoding, TreeNode } from '../types/core.js'
+
+export const OUTPUT_FORMATS = ['json', 'sarif'] as const
+export type OutputFormat = typeof OUTPUT_FORMATS[number]
+
+const SARIF_SCHEMA = 'https://json.schemastore.org/sarif-2.1.0.json'
+const SARIF_VERSION = '2.1.0'
+const TOOL_NAME = 'tree-sitter-mcp'
+const TOOL_URI = 'https://github.com/nendotools/tree-sitter-mcp'
+const SOURCE_ROOT = '%SRCROOT%'
+
+export type SarifLevel = 'error' | 'warning' | 'note'
+
+const FINDING_LEVELS: Record<Finding['severity'], SarifLevel> = {
+  critical: 'error',
+  warning: 'warning',
+  info: 'note',
+}
+
+const SYNTAX_RULE_DESCRIPTIONS: Record<ActionableError['type'], string> = {
+  missing: 'Missing syntax',
+  parse_error: 'Unparseable code',
+  extra: 'Unexpected syntax',
+}
+
+/**
+ * A result ready for serialization; lines and columns are 1-based
+ */
+export interface SarifEntry {
+  ruleId: string
+  ruleDescription?: string
+  level: SarifLevel
+  message: string
+  file: string
+  startLine: number
+  startColumn?: number
+  endLine?: number
+  endColumn?: number
+}
+
+export interface SarifOptions {
+  /** Artifact URIs are written relative to this directory */
+  root?: string
+  /** Parsed files whose content is used to add byte offsets to regions */
+  files?: TreeNode[]
+  properties?: JsonObject
+}
+
+export interface SarifRegion {
+  startLine: number
+  startColumn?: number
+  endLine?: number
+  endColumn?: number
+  byteOffset?: number
+  byteLength?: number
+}
+
+export interface SarifResult {
+  ruleId: string
+  ruleIndex: number
+  level: SarifLevel
+  message: { text: string }
+  locations: {
+    physicalLocation: {
+      artifactLocation: { uri: string, uriBaseId?: string }
+      region: SarifRegion
+    }
+  }[]
+}
+
+export interface SarifRule {
+  id: string
+  shortDescription: { text: string }
+  defaultConfiguration: { level: SarifLevel }
+}
+
+export interface SarifLog {
+  $schema: string
+  version: string
+  runs: {
+    tool: { driver: { name: string, version: string, informationUri: string, rules: SarifRule[] } }
+    originalUriBaseIds?: Record<string, { uri: string }>
+    results: SarifResult[]
+    properties?: JsonObject
+  }[]
+}
+
+/**
+ * Builds a single-run SARIF log; rules are collected from the entries in first-seen order
+ */
+export function createSarifLog(entries: SarifEntry[], options: SarifOptions = {}): SarifLog {
+  const { root, files = [], properties } = options
+  const sources = new Map(files.filter(file => file.content !== undefined).map(file => [file.path, file]))
+  const lineIndexes = new Map<string, LineIndex>()
+  const rules: SarifRule[] = []
+  const ruleIndexes = new Map<string, number>()
+
+  const results = entries.map((entry): SarifResult => {
+    let ruleIndex = ruleIndexes.get(entry.ruleId)
+    if (ruleIndex === undefined) {
+      ruleIndex = rules.length
+      ruleIndexes.set(entry.ruleId, ruleIndex)
+      rules.push({
+        id: entry.ruleId,
+        shortDescription: { text: entry.ruleDescription || entry.ruleId },
+        defaultConfiguration: { level: entry.level },
+      })
+    }
+
+    const region: SarifRegion = {
+      startLine: entry.startLine,
+      ...(entry.startColumn !== undefined && { startColumn: entry.startColumn }),
+      ...(entry.endLine !== undefined && { endLine: entry.endLine }),
+      ...(entry.endColumn !== undefined && { endColumn: entry.endColumn }),
+    }
+
+    const source = sources.get(entry.file)
+    if (source) {
+      const index = lineIndexes.get(entry.file) || indexLines(source.content!, source.encoding)
+      lineIndexes.set(entry.file, index)
+      const start = toByteOffset(index, entry.startLine, entry.startColumn ?? 1)
+      if (start !== null) {
+        region.byteOffset = start
+        const end = entry.endLine !== undefined && entry.endColumn !== undefined
+          ? toByteOffset(index, entry.endLine, entry.endColumn)
+          : null
+        if (end !== null && end >= start) {
+          region.byteLength = end - start
+        }
+      }
+    }
+
+    return {
+      ruleId: entry.ruleId,
+      ruleIndex,
+      level: entry.level,
+      message: { text: entry.message },
+      locations: [{
+        physicalLocation: {
+          artifactLocation: toArtifactLocation(entry.file, root),
+          region,
+        },
+      }],
+    }
+  })
+
+  return {
+    $schema: SARIF_SCHEMA,
+    version: SARIF_VERSION,
+    runs: [{
+      tool: { driver: { name: TOOL_NAME, version: getVersion(), informationUri: TOOL_URI, rules } },
+      ...(root && { originalUriBaseIds: { [SOURCE_ROOT]: { uri: `${pathToFileURL(root).href}/` } } }),
+      results,
+      ...(properties && { properties }),
+    }],
+  }
+}
+
+/**
+ * Search matches become `note` results; internal columns are 0-based and shifted to SARIF's 1-based columns
+ */
+export function searchResultsToSarif(query: string, results: SearchResult[], options: SarifOptions = {}): SarifLog {
+  return createSarifLog(results.map(({ node }): SarifEntry => ({
+    ruleId: 'search/match',
+    ruleDescription: 'Code matching a search query',
+    level: 'note',
+    message: `${node.name ? `${node.type} ${node.name}` : node.type} matches "${query}"`,
+    file: node.path,
+    startLine: node.startLine ?? 1,
+    ...(node.startColumn !== undefined && { startColumn: node.startColumn + 1 }),
+    ...(node.endLine !== undefined && { endLine: node.endLine }),
+    ...(node.endColumn !== undefined && { endColumn: node.endColumn + 1 }),
+  })), options)
+}
+
+/**
+ * Analysis findings use `type/category` rule IDs; `path:line` locations keep their line
+ */
+export function findingsToSarif(findings: Finding[], options: SarifOptions = {}): SarifLog {
+  return createSarifLog(findings.map((finding): SarifEntry => {
+    const match = /^(.*):(\d+)$/.exec(finding.location)
+    return {
+      ruleId: `${finding.type}/${finding.category}`,
+      level: FINDING_LEVELS[finding.severity] ?? 'note',
+      message: finding.description,
+      file: match ? match[1]! : finding.location,
+      startLine: match ? Math.max(1, Number(match[2])) : 1,
+    }
+  }), options)
+}
+
+/**
+ * Syntax errors are always `error` results; their columns are already 1-based
+ */
+export function errorsToSarif(errors: ActionableError[], options: SarifOptions = {}): SarifLog {
+  return createSarifLog(errors.map((error): SarifEntry => ({
+    ruleId: `syntax/${error.type}`,
+    ruleDescription: SYNTAX_RULE_DESCRIPTIONS[error.type],
+    level: 'error',
+    message: `${describeSyntaxError(error)}. ${error.suggestion}`,
+    file: error.file,
+    startLine: error.line,
+    startColumn: error.column,
+    endLine: error.endLine,
+    endColumn: error.endColumn,
+  })), options)
+}
+
+function describeSyntaxError(error: ActionableError): string {
+  switch (error.type) {
+    case 'missing':
+      return `Missing ${error.nodeType}`
+    case 'extra':
+      return `Unexpected ${error.nodeType}`
+    default:
+      return `Parse error at "${error.text}"`
+  }
+}
+
+function toArtifactLocation(file: string, root?: string): { uri: string, uriBaseId?: string } {
+  if (root && isAbsolute(file)) {
+    const path = relative(root, file)
+    if (!path.startsWith('..') && !isAbsolute(path)) {
+      return { uri: toPortablePath(path), uriBaseId: SOURCE_ROOT }
+    }
+  }
+  return { uri: isAbsolute(file) ? pathToFileURL(file).href : toPortablePath(file) }
+}
+
+interface LineIndex {
+  lines: string[]
+  offsets: number[]
+  encoding?: SourceEncoding
+}
+
+/**
+ * Offsets count bytes in the file as stored, so UTF-16 and BOM-prefixed files map back to the original bytes
+ */
+function indexLines(content: string, encoding?: SourceEncoding): LineIndex {
+  const lines = content.split('\n')
+  const offsets: number[] = []
+  const newlineLength = getEncodedLength('\n', encoding)
+  let offset = toSourceByteOffset(content, 0, encoding)
+  for (const line of lines) {
+    offsets.push(offset)
+    offset += getEncodedLength(line, encoding) + newlineLength
+  }
+  return { lines, offsets, encoding }
+}
+
+/**
+ * Converts a 1-based line and column to a 0-based byte offset into the file
+ */
+function toByteOffset(index: LineIndex, line: number, column: number): number | null {
+  const lineStart = index.offsets[line - 1]
+  if (lineStart === undefined) return null
+  return lineStart + getEncodedLength(index.lines[line - 1]!.slice(0, Math.max(0, column - 1)), index.encoding)
+}
\ No newline at end of file
@@ -0,0 +1,197 @@
+/**
+ * Project config file - reads `.tree-sitter-mcp.toml` from a project root so settings need not be repeated per call
+ */
+
+import { readFileSync } from 'fs'
+import { join, resolve } from 'path'
+import { loadGrammar, registerLanguage, resolveLanguageNames } from '../core/languages.js'
+import { setMaxFileSize } from '../core/file-walker.js'
+import { setParseThreads } from '../core/parse-pool.js'
+import { setCacheDirectory } from './index-cache.js'
+import { parseToml, type TomlTable, type TomlValue } from '../utils/toml.js'
+import { createError } from '../utils/errors.js'
+import type { ProjectConfig } from '../types/core.js'
+
+export const CONFIG_FILE_NAME = '.tree-sitter-mcp.toml'
+
+export interface Config {
+  /** Globs of files to leave out of the index, relative to the project root */
+  exclude?: string[]
+  /** When set, only files matching one of these globs are indexed */
+  include?: string[]
+  languages?: string[]
+  maxFileSize?: number
+  threads?: number
+  /** Index cache directory; a relative path resolves against the project root */
+  cacheDir?: string
+  /** Globs mapped to the language their files are parsed as, ahead of extensions and shebangs */
+  languageOverrides?: Record<string, string>
+  /** Extra languages backed by compiled grammars, keyed by language name */
+  grammars?: Record<string, GrammarConfig>
+  /** Whether paths and globs match in any case (default: true on macOS and Windows) */
+  ignoreCaseInPaths?: boolean
+}
+
+export interface GrammarConfig {
+  /** Native addon or package directory; a relative path resolves against the project root */
+  path: string
+  extensions: string[]
+  /** Grammar node types mapped to symbol kinds */
+  kinds: Record<string, string>
+}
+
+type ConfigKeyType = 'string' | 'boolean' | 'count' | 'string[]' | 'string table' | 'grammar table'
+
+const CONFIG_KEYS: Record<keyof Config, ConfigKeyType> = {
+  exclude: 'string[]',
+  include: 'string[]',
+  languages: 'string[]',
+  maxFileSize: 'count',
+  threads: 'count',
+  cacheDir: 'string',
+  languageOverrides: 'string table',
+  grammars: 'grammar table',
+  ignoreCaseInPaths: 'boolean',
+}
+
+/**
+ * Validates config file text; unknown keys and wrongly typed values are errors so typos do not go unnoticed
+ */
+export function parseConfig(content: string, source = CONFIG_FILE_NAME): Config {
+  let values: Record<string, TomlValue>
+  try {
+    values = parseToml(content)
+  }
+  catch (error) {
+    throw createError('CONFIG_ERROR', `Invalid ${source}: ${error instanceof Error ? error.message : String(error)}`, { source })
+  }
+
+  const config: Record<string, unknown> = {}
+  for (const [key, value] of Object.entries(values)) {
+    const type = Object.hasOwn(CONFIG_KEYS, key) ? CONFIG_KEYS[key as keyof Config] : undefined
+    if (!type) {
+      throw createError('CONFIG_ERROR', `Unknown key "${key}" in ${source}. Supported keys: ${Object.keys(CONFIG_KEYS).join(', ')}`, { source, key })
+    }
+    if (!hasType(value, type)) {
+      throw createError('CONFIG_ERROR', `"${key}" in ${source} must be ${describeType(type)}`, { source, key })
+    }
+    config[key] = value
+  }
+
+  const result = config as Config
+  // Languages the file itself defines are only registered once it is applied
+  const defined = new Set(Object.keys(result.grammars ?? {}).map(name => name.trim().toLowerCase()))
+  if (result.languages) {
+    result.languages = resolveConfigLanguages(result.languages, defined, source, 'languages')
+  }
+  if (result.languageOverrides) {
+    const patterns = Object.keys(result.languageOverrides)
+    const names = resolveConfigLanguages(Object.values(result.languageOverrides), defined, source, 'languageOverrides')
+    result.languageOverrides = Object.fromEntries(patterns.map((pattern, i) => [pattern, names[i]!]))
+  }
+  return result
+}
+
+function resolveConfigLanguages(names: string[], defined: Set<string>, source: string, key: keyof Config): string[] {
+  try {
+    return names.map(name => defined.has(name.trim().toLowerCase())
+      ? name.trim().toLowerCase()
+      : resolveLanguageNames([name])[0]!.name)
+  }
+  catch (error) {
+    throw createError('CONFIG_ERROR', `${source}: ${error instanceof Error ? error.message : String(error)}`, { source, key })
+  }
+}
+
+/**
+ * Reads the config file in a directory, returning an empty config when there is none
+ */
+export function loadConfig(directory: string): Config {
+  const path = join(directory, CONFIG_FILE_NAME)
+  let content: string
+  try {
+    content = readFileSync(path, 'utf-8')
+  }
+  catch {
+    return {}
+  }
+  return parseConfig(content, path)
+}
+
+/**
+ * Applies the process-wide settings (`maxFileSize`, `threads`, `cacheDir`, `grammars`). Call it before
+ * handling command-line flags so the flags override the file.
+ */
+export function applyGlobalConfig(config: Config, directory: string): void {
+  if (config.maxFileSize !== undefined) setMaxFileSize(config.maxFileSize)
+  if (config.threads !== undefined) setParseThreads(config.threads)
+  if (config.cacheDir !== undefined) setCacheDirectory(resolve(directory, config.cacheDir))
+
+  for (const [name, grammar] of Object.entries(config.grammars ?? {})) {
+    const path = resolve(directory, grammar.path)
+    registerLanguage({ name, extensions: grammar.extensions, grammar: loadGrammar(path), definitionKinds: grammar.kinds, path })
+  }
+}
+
+/**
+ * Fills the `include`, `exclude`, `languages`, `languageOverrides` and `ignoreCaseInPaths` the caller
+ * left unset from the config file in the project root
+ */
+export function withConfigFile(config: ProjectConfig): ProjectConfig {
+  const file = loadConfig(config.directory)
+  return {
+    ...config,
+    languages: config.languages?.length ? config.languages : file.languages,
+    include: config.include?.length ? config.include : file.include,
+    exclude: config.exclude?.length ? config.exclude : file.exclude,
+    languageOverrides: config.languageOverrides ?? file.languageOverrides,
+    ignoreCaseInPaths: config.ignoreCaseInPaths ?? file.ignoreCaseInPaths,
+  }
+}
+
+function hasType(value: TomlValue, type: ConfigKeyType): boolean {
+  switch (type) {
+    case 'string':
+      return typeof value === 'string'
+    case 'boolean':
+      return typeof value === 'boolean'
+    case 'count':
+      return typeof value === 'number' && Number.isInteger(value) && value >= 0
+    case 'string[]':
+      return Array.isArray(value) && value.every(item => typeof item === 'string')
+    case 'string table':
+      return isTable(value) && Object.values(value).every(item => typeof item === 'string')
+    case 'grammar table':
+      return isTable(value) && Object.values(value).every(isGrammar)
+  }
+}
+
+function isGrammar(value: TomlValue): boolean {
+  if (!isTable(value)) return false
+  const { path, extensions, kinds, ...rest } = value
+  return Object.keys(rest).length === 0
+    && path !== undefined && hasType(path, 'string')
+    && extensions !== undefined && hasType(extensions, 'string[]')
+    && kinds !== undefined && hasType(kinds, 'string table')
+}
+
+function isTable(value: TomlValue): value is TomlTable {
+  return typeof value === 'object' && !Array.isArray(value)
+}
+
+function describeType(type: ConfigKeyType): string {
+  switch (type) {
+    case 'string':
+      return 'a string'
+    case 'boolean':
+      return 'true or false'
+    case 'count':
+      return 'a non-negative integer'
+    case 'string[]':
+      return 'an array of strings'
+    case 'string table':
+      return 'a table of strings'
+    case 'grammar table':
+      return 'a table of grammars, each with a `path` string, an `extensions` array and a `kinds` table of strings'
+  }
+}
\ No newline at end of file
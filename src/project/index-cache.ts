@@ -0,0 +1,196 @@
+/**
+ * On-disk index cache - reuses parsed file trees across server starts when file contents are unchanged
+ */
+
+import { createHash } from 'crypto'
+import { existsSync, mkdirSync, readFileSync, statSync, writeFileSync } from 'fs'
+import { homedir } from 'os'
+import { join } from 'path'
+import { serializeNode, restoreFileNode, type SerializedNode } from '../core/node-serialization.js'
+import { INDEX_CACHE_CONFIG } from '../constants/persistence.js'
+import { getLogger } from '../utils/logger.js'
+import { getVersion } from '../utils/version.js'
+import type { TreeNode } from '../types/core.js'
+
+export interface CachedFileEntry {
+  mtimeMs: number
+  hash: string
+  file: SerializedNode
+}
+
+/** The modification time and content hash of a file as it was before parsing */
+export type FileStamp = Pick<CachedFileEntry, 'mtimeMs' | 'hash'>
+
+export interface IndexCache {
+  version: string
+  directory: string
+  files: Record<string, CachedFileEntry>
+  seen: Set<string>
+  dirty: boolean
+}
+
+let cacheEnabled = true
+
+/**
+ * Globally enables or disables the index cache (used by the --no-cache CLI flag)
+ */
+export function setIndexCacheEnabled(enabled: boolean): void {
+  cacheEnabled = enabled
+}
+
+export function isIndexCacheEnabled(): boolean {
+  return cacheEnabled
+}
+
+/**
+ * Version tag stored with every cache file; a schema or package (grammar) upgrade invalidates old entries
+ */
+export function getCacheVersion(): string {
+  return `${INDEX_CACHE_CONFIG.VERSION}:${getVersion()}`
+}
+
+let configuredCacheDirectory: string | undefined
+
+/**
+ * Sets the cache location (used by the `cacheDir` config key); the environment variable still wins
+ */
+export function setCacheDirectory(directory?: string): void {
+  configuredCacheDirectory = directory
+}
+
+export function getCacheDirectory(): string {
+  const override = process.env[INDEX_CACHE_CONFIG.DIR_ENV] || configuredCacheDirectory
+  if (override) return override
+
+  const base = process.env.XDG_CACHE_HOME || join(homedir(), '.cache')
+  return join(base, INDEX_CACHE_CONFIG.DIR_NAME)
+}
+
+export function getCacheFilePath(directory: string): string {
+  const key = createHash(INDEX_CACHE_CONFIG.HASH_ALGORITHM).update(directory).digest('hex').substring(0, 16)
+  return join(getCacheDirectory(), `${key}.json`)
+}
+
+/**
+ * Loads the cache for a project directory, returning an empty cache when missing, corrupt, or stale.
+ * Changed language overrides make it stale, since files may now be parsed with another grammar.
+ */
+export function loadIndexCache(directory: string, languageOverrides: Record<string, string> = {}): IndexCache {
+  const logger = getLogger()
+  const overrides = Object.entries(languageOverrides).sort(([a], [b]) => a.localeCompare(b))
+  const cache: IndexCache = {
+    version: overrides.length > 0 ? `${getCacheVersion()}:${JSON.stringify(overrides)}` : getCacheVersion(),
+    directory,
+    files: {},
+    seen: new Set(),
+    dirty: false,
+  }
+
+  const cachePath = getCacheFilePath(directory)
+  if (!existsSync(cachePath)) return cache
+
+  try {
+    const stored = JSON.parse(readFileSync(cachePath, 'utf-8'))
+    if (stored.version !== cache.version || stored.directory !== directory) {
+      logger.info(`Discarding stale index cache for ${directory}`)
+      cache.dirty = true
+      return cache
+    }
+    cache.files = stored.files || {}
+  }
+  catch (error) {
+    logger.warn(`Failed to read index cache ${cachePath}:`, error)
+    cache.dirty = true
+  }
+
+  return cache
+}
+
+/**
+ * Writes the cache, dropping entries for files that were not visited in this indexing pass. Returns false
+ * when the write failed, leaving whatever cache file was there before
+ */
+export function saveIndexCache(cache: IndexCache): boolean {
+  const logger = getLogger()
+
+  for (const filePath of Object.keys(cache.files)) {
+    if (!cache.seen.has(filePath)) {
+      delete cache.files[filePath]
+      cache.dirty = true
+    }
+  }
+
+  if (!cache.dirty) return true
+
+  try {
+    mkdirSync(getCacheDirectory(), { recursive: true })
+    writeFileSync(getCacheFilePath(cache.directory), JSON.stringify({
+      version: cache.version,
+      directory: cache.directory,
+      files: cache.files,
+    }))
+    cache.dirty = false
+    return true
+  }
+  catch (error) {
+    logger.warn(`Failed to write index cache for ${cache.directory}:`, error)
+    return false
+  }
+}
+
+/**
+ * Returns the cached tree for a file when its content hash is unchanged, or undefined when it must be re-parsed.
+ * `knownMtimeMs`, when the walk already stat'ed the file, saves another stat.
+ */
+export function getCachedFile(filePath: string, cache: IndexCache, knownMtimeMs?: number): TreeNode | undefined {
+  cache.seen.add(filePath)
+
+  const entry = cache.files[filePath]
+  if (!entry) return undefined
+
+  try {
+    const mtimeMs = knownMtimeMs ?? statSync(filePath).mtimeMs
+    if (entry.mtimeMs === mtimeMs) {
+      return restoreFileNode(entry.file)
+    }
+
+    if (entry.hash === hashFile(filePath)) {
+      entry.mtimeMs = mtimeMs
+      cache.dirty = true
+      return restoreFileNode(entry.file)
+    }
+  }
+  catch {
+    /* unreadable files are re-parsed (and reported) by the caller */
+  }
+
+  return undefined
+}
+
+/**
+ * Stamps a file before it is parsed, returning undefined when it cannot be read. `knownMtimeMs`, when the
+ * walk already stat'ed the file, saves another stat; taken before the hash, it cannot describe a later edit.
+ */
+export function stampFile(filePath: string, knownMtimeMs?: number): FileStamp | undefined {
+  try {
+    const mtimeMs = knownMtimeMs ?? statSync(filePath).mtimeMs
+    return { mtimeMs, hash: hashFile(filePath) }
+  }
+  catch {
+    return undefined
+  }
+}
+
+/**
+ * Records a freshly parsed file so the next run can reuse it. The stamp is taken before parsing, so an
+ * edit made while the file was parsed leaves a stamp that no longer matches and the file is parsed again.
+ */
+export function storeCachedFile(filePath: string, fileNode: TreeNode, cache: IndexCache, stamp: FileStamp): void {
+  cache.files[filePath] = { ...stamp, file: serializeNode(fileNode) }
+  cache.seen.add(filePath)
+  cache.dirty = true
+}
+
+function hashFile(filePath: string): string {
+  return createHash(INDEX_CACHE_CONFIG.HASH_ALGORITHM).update(readFileSync(filePath)).digest('hex')
+}
\ No newline at end of file
@@ -2,15 +2,25 @@
  * Simplified project management - streamlined from complex TreeManager class
  */
 
-import { resolve } from 'path'
-import { parseFile } from '../core/parser.js'
-import { findProjectFiles } from '../core/file-walker.js'
+import { realpathSync } from 'fs'
+import { relative, resolve, sep } from 'path'
+import { extractAllNodes, parseFile } from '../core/parser.js'
+import { getFileLanguage, resolveFileLanguage } from '../core/languages.js'
+import { getResidentTree } from '../core/tree-cache.js'
+import { DEFAULT_PROJECT_DEPTH, findProjectFiles, isProjectFile } from '../core/file-walker.js'
+import { createCandidateFilter } from '../core/search.js'
 import { createFileWatcher } from '../core/watcher.js'
+import { ignoresPathCase, isWithinPath, pathKey, toDiskCase } from '../core/path-case.js'
 import { generateId } from '../utils/helpers.js'
 import { getLogger } from '../utils/logger.js'
-import { handleError } from '../utils/errors.js'
-import type { Project, ProjectConfig, TreeNode, FileChange } from '../types/core.js'
-import { detectMonorepo } from './monorepo.js'
+import { createError, handleError } from '../utils/errors.js'
+import { isCancelledError, throwIfCancelled } from '../utils/cancellation.js'
+import type { Project, ProjectConfig, TreeNode, FileChange, SkippedFile, ClassifiedFile, IndexStats } from '../types/core.js'
+import { detectMonorepo, detectProjectType, getRootIgnoreDirs } from './monorepo.js'
+import { loadIndexCache, saveIndexCache, getCachedFile, getCacheFilePath, stampFile, storeCachedFile, type FileStamp, type IndexCache } from './index-cache.js'
+import { parseFilesInParallel, parseFilesSequentially, getParseThreads } from '../core/parse-pool.js'
+import { PARSER_LIMITS } from '../constants/parsers.js'
+import { MEMORY_LIMITS } from '../constants/persistence.js'
 
 export function createProject(config: ProjectConfig, isSubProject = false): Project {
   const project: Project = {
@@ -20,8 +30,9 @@ export function createProject(config: ProjectConfig, isSubProject = false): Proj
       directory: resolve(config.directory),
       languages: config.languages || [],
       ignoreDirs: config.ignoreDirs || [],
-      maxDepth: config.maxDepth || 10,
+      maxDepth: config.maxDepth ?? DEFAULT_PROJECT_DEPTH,
     },
+    rootType: detectProjectType(resolve(config.directory)),
     files: new Map(),
     nodes: new Map(),
   }
@@ -31,7 +42,7 @@ export function createProject(config: ProjectConfig, isSubProject = false): Proj
     if (monorepoInfo.isMonorepo) {
       project.isMonorepo = true
       project.subProjects = monorepoInfo.subProjects.map(subPath =>
-        createProject({ ...config, directory: subPath }, true),
+        createProject({ ...config, directory: subPath, globRoot: project.config.globRoot ?? project.config.directory }, true),
       )
     }
   }
@@ -39,71 +50,308 @@ export function createProject(config: ProjectConfig, isSubProject = false): Proj
   return project
 }
 
-export async function parseProject(project: Project): Promise<Project> {
+export interface IndexProgress {
+  filesDone: number
+  totalFiles: number
+  /** The file that was just indexed */
+  currentPath: string
+}
+
+export interface IndexOptions {
+  /** Aborting stops indexing between files with a CANCELLED error */
+  signal?: AbortSignal
+  /** Called after each file is indexed, including files restored from the cache */
+  onProgress?: (progress: IndexProgress) => void
+  /** When true, failing to write the on-disk cache is an error rather than a warning */
+  requireCacheWrite?: boolean
+}
+
+interface ParseStep {
+  project: Project
+  files: string[]
+  /** Modification times the walk saw, kept on the file nodes */
+  mtimes: Map<string, number>
+}
+
+/**
+ * Parses a project, or each of its sub-projects for monorepos.
+ * Nested roots are parsed deepest-first so a file under overlapping roots is indexed once, by its closest root.
+ * Every root is walked before parsing starts, so progress reports know the total file count up front.
+ */
+export async function parseProject(
+  project: Project,
+  claimedFiles = new Set<string>(),
+  cache?: IndexCache,
+  options: IndexOptions = {},
+): Promise<Project> {
   const logger = getLogger()
+  const { signal, onProgress } = options
 
   try {
     logger.info(`Parsing project: ${project.config.directory}`)
 
-    // Clear existing files and nodes before reparsing
-    project.files.clear()
-    project.nodes.clear()
+    const steps = await planParse(project, claimedFiles)
+    const totalFiles = steps.reduce((total, step) => total + step.files.length, 0)
+    // A parallel parse that falls back to one thread reports some files twice
+    const done = new Set<string>()
+    const onFile = (currentPath: string) => {
+      if (!onProgress || done.has(currentPath)) return
+      done.add(currentPath)
+      onProgress({ filesDone: done.size, totalFiles, currentPath })
+    }
 
-    if (project.subProjects && project.subProjects.length > 0) {
-      logger.info(`Parsing ${project.subProjects.length} sub-projects`)
-      for (const subProject of project.subProjects) {
-        try {
-          await parseProject(subProject)
-        }
-        catch (error) {
-          logger.error(`Failed to parse sub-project ${subProject.config.directory}:`, error)
+    for (const step of steps) {
+      try {
+        throwIfCancelled(signal)
+        const parsed = await parseFiles(step.files, step.project.config.threads ?? getParseThreads(), cache, signal, onFile, step.mtimes)
+        step.project.parseFailures = step.files.filter(filePath => !parsed.has(filePath))
+        for (const [filePath, fileNode] of parsed) {
+          fileNode.mtimeMs = step.mtimes.get(filePath)
+          step.project.files.set(filePath, fileNode)
+          step.project.nodes.set(filePath, extractAllNodes(fileNode))
         }
       }
+      catch (error) {
+        if (step.project === project || isCancelledError(error)) throw error
+        logger.error(`Failed to parse sub-project ${step.project.config.directory}:`, error)
+      }
+    }
+
+    logger.info(`Project parsed successfully: ${countFiles(project)} files`)
+    return project
+  }
+  catch (error) {
+    throw handleError(error, `Failed to parse project ${project.config.directory}`)
+  }
+}
+
+/**
+ * Clears the project (and sub-projects) and walks each root, claiming its files
+ */
+async function planParse(project: Project, claimedFiles: Set<string>, roots: Project[] = []): Promise<ParseStep[]> {
+  const logger = getLogger()
+
+  // Clear existing files and nodes before reparsing
+  project.files.clear()
+  project.nodes.clear()
+
+  if (!project.subProjects || project.subProjects.length === 0) {
+    const mtimes = new Map<string, number>()
+    return [{ project, files: await claimProjectFiles(project, claimedFiles, roots, mtimes), mtimes }]
+  }
+
+  logger.info(`Parsing ${project.subProjects.length} sub-projects`)
+  const deepestFirst = [...project.subProjects].sort((a, b) =>
+    b.config.directory.split(sep).length - a.config.directory.split(sep).length,
+  )
+
+  const steps: ParseStep[] = []
+  for (const subProject of deepestFirst) {
+    try {
+      steps.push(...await planParse(subProject, claimedFiles, [project, ...project.subProjects]))
+    }
+    catch (error) {
+      logger.error(`Failed to parse sub-project ${subProject.config.directory}:`, error)
+    }
+  }
+  return steps
+}
+
+async function claimProjectFiles(
+  project: Project,
+  claimedFiles: Set<string>,
+  roots: Project[],
+  mtimes: Map<string, number>,
+): Promise<string[]> {
+  const skipped: SkippedFile[] = []
+  const gitIgnored: string[] = []
+  const depthSkipped: string[] = []
+  const outsideRootLinks: string[] = []
+  const ignoreCase = ignoresPathCase(project.config)
+  const isIncluded = createProjectFileFilter(project.config)
+  const files = (await findProjectFiles(
+    project.config.directory,
+    project.config.languages,
+    getProjectIgnoreDirs(project),
+    {
+      maxDepth: project.config.maxDepth,
+      maxFileSize: project.config.maxFileSize,
+      useDefaultExcludes: project.config.useDefaultExcludes,
+      followSymlinks: project.config.followSymlinks,
+      allowOutsideRoot: project.config.allowOutsideRoot,
+      roots: roots.map(root => root.config.directory),
+      ignoreCase,
+      onSkip: file => skipped.push(file),
+      onGitIgnored: path => gitIgnored.push(path),
+      onDepthLimit: path => depthSkipped.push(path),
+      onOutsideRoot: path => outsideRootLinks.push(path),
+      onFileFound: (path, mtimeMs) => mtimes.set(path, mtimeMs),
+    },
+  )).filter(filePath => isIncluded(filePath) && !isIgnoredByNestedRoot(project, filePath, roots))
+
+  getLogger().info(`Found ${files.length} files to parse in ${project.config.directory}`)
+  if (skipped.length > 0) {
+    getLogger().info(`Skipped ${skipped.length} oversized or binary files in ${project.config.directory}`)
+  }
+
+  // Skipped files are claimed too, so an enclosing root does not report them again
+  project.skippedFiles = skipped.filter(file => !claimedFiles.has(file.path))
+  project.skippedFiles.forEach(file => claimedFiles.add(file.path))
+  const unclaimedIgnored = gitIgnored.filter(path => !claimedFiles.has(path))
+  project.gitIgnoredCount = unclaimedIgnored.length
+  unclaimedIgnored.forEach(path => claimedFiles.add(path))
+  // A nested root walks its own tree from depth 0, so directories inside one are not skipped
+  project.depthSkipped = depthSkipped.filter(path => !roots.some(root => root !== project
+    && root.config.directory.startsWith(project.config.directory + sep)
+    && (path + sep).startsWith(root.config.directory + sep)))
+  project.outsideRootLinks = outsideRootLinks
+
+  // A followed symlink claims its target too, so the file is not indexed again under another root; where
+  // case is ignored, paths differing only in case claim one file
+  const claimKeys = (filePath: string) => (project.config.followSymlinks ? [filePath, realpathSync(filePath)] : [filePath])
+    .map(key => pathKey(key, ignoreCase))
+  const pending = files.filter(filePath => !claimKeys(filePath).some(key => claimedFiles.has(key)))
+  pending.forEach(filePath => claimKeys(filePath).forEach(key => claimedFiles.add(key)))
+  return pending
+}
+
+/**
+ * The project's own ignore dirs plus, unless default excludes are turned off, those of its root type
+ */
+function getProjectIgnoreDirs(project: Project): string[] {
+  const rootIgnoreDirs = project.config.useDefaultExcludes === false ? [] : getRootIgnoreDirs(project.rootType)
+  return [...(project.config.ignoreDirs || []), ...rootIgnoreDirs]
+}
+
+/**
+ * A file under a nested root stays out when that root's type ignores one of its directories,
+ * even though the enclosing root, of another type, walked it
+ */
+function isIgnoredByNestedRoot(project: Project, filePath: string, roots: Project[]): boolean {
+  return roots.some((root) => {
+    const directory = root.config.directory
+    if (root === project || root.config.useDefaultExcludes === false || !directory.startsWith(project.config.directory + sep) || !filePath.startsWith(directory + sep)) {
+      return false
+    }
+    const ignoreDirs = getRootIgnoreDirs(root.rootType)
+    return relative(directory, filePath).split(sep).slice(0, -1).some(segment => ignoreDirs.includes(segment))
+  })
+}
+
+/**
+ * The config's `include` and `exclude` globs, matched relative to the root project's directory
+ */
+function createProjectFileFilter(config: ProjectConfig): (filePath: string) => boolean {
+  return createCandidateFilter(config.globRoot ?? config.directory, { include: config.include, exclude: config.exclude, ignoreCase: ignoresPathCase(config) })
+}
+
+function countFiles(project: Project): number {
+  return project.files.size + (project.subProjects || []).reduce((total, subProject) => total + countFiles(subProject), 0)
+}
+
+/**
+ * Parses files, reusing cached trees and spreading the rest across workers when there are enough of them
+ */
+async function parseFiles(
+  filePaths: string[],
+  threads: number,
+  cache?: IndexCache,
+  signal?: AbortSignal,
+  onFile?: (filePath: string) => void,
+  mtimes?: Map<string, number>,
+): Promise<Map<string, TreeNode>> {
+  const logger = getLogger()
+  const cached = new Map<string, TreeNode>()
+  const toParse: string[] = []
+
+  for (const filePath of filePaths) {
+    const fileNode = cache ? getCachedFile(filePath, cache, mtimes?.get(filePath)) : undefined
+    if (fileNode) {
+      cached.set(filePath, fileNode)
+      onFile?.(filePath)
     }
     else {
-      const files = await findProjectFiles(
-        project.config.directory,
-        project.config.languages,
-        project.config.ignoreDirs,
-      )
+      toParse.push(filePath)
+    }
+  }
 
-      logger.info(`Found ${files.length} files to parse`)
+  // Stamped before parsing, so the cache describes the bytes parsed rather than an edit made meanwhile
+  const stamps = new Map<string, FileStamp | undefined>()
+  if (cache) toParse.forEach(filePath => stamps.set(filePath, stampFile(filePath, mtimes?.get(filePath))))
 
-      for (const filePath of files) {
-        try {
-          const fileNode = await parseFile(filePath)
-          project.files.set(filePath, fileNode)
+  let parsed: Map<string, TreeNode>
+  if (threads > 1 && toParse.length >= PARSER_LIMITS.PARALLEL_MIN_FILES) {
+    try {
+      parsed = await parseFilesInParallel(toParse, threads, signal, onFile)
+    }
+    catch (error) {
+      if (isCancelledError(error)) throw error
+      logger.warn('Parallel parsing failed, falling back to a single thread:', error)
+      parsed = await parseFilesSequentially(toParse, signal, onFile)
+    }
+  }
+  else {
+    parsed = await parseFilesSequentially(toParse, signal, onFile)
+  }
 
-          const allNodes = extractAllNodes(fileNode)
-          project.nodes.set(filePath, allNodes)
-        }
-        catch (error) {
-          logger.warn(`Failed to parse ${filePath}:`, error)
-        }
-      }
+  if (cache) {
+    for (const [filePath, fileNode] of parsed) {
+      const stamp = stamps.get(filePath)
+      // A file that could not be read before parsing is simply not cached
+      if (stamp) storeCachedFile(filePath, fileNode, cache, stamp)
     }
+  }
 
-    logger.info(`Project parsed successfully: ${project.files.size} files`)
-    return project
+  const results = new Map<string, TreeNode>()
+  for (const filePath of filePaths) {
+    const fileNode = cached.get(filePath) || parsed.get(filePath)
+    if (fileNode) results.set(filePath, fileNode)
   }
-  catch (error) {
-    throw handleError(error, `Failed to parse project ${project.config.directory}`)
+  return results
+}
+
+/**
+ * Indexes a project through the on-disk cache, re-parsing only files whose content changed since the last run
+ */
+export async function loadOrBuildIndex(project: Project, options: IndexOptions = {}): Promise<Project> {
+  const cache = loadIndexCache(project.config.directory, project.config.languageOverrides)
+  await parseProject(project, new Set(), cache, options)
+  if (!saveIndexCache(cache) && options.requireCacheWrite) {
+    const cacheFile = getCacheFilePath(project.config.directory)
+    throw createError('FILE_ERROR', `Failed to write the index cache to ${cacheFile}`, { cacheFile })
   }
+  return project
 }
 
+/**
+ * Applies watcher changes incrementally. Each change goes to the (sub-)project that owns the path;
+ * deletions evict the path, and anything beneath it, from every project in the tree.
+ */
 export async function updateProject(project: Project, changes: FileChange[]): Promise<void> {
   const logger = getLogger()
 
-  for (const change of changes) {
+  const ignoreCase = ignoresPathCase(project.config)
+
+  for (const reported of changes) {
+    // A path reported in another case is keyed as the walk indexed it
+    const change = ignoreCase ? { ...reported, path: toDiskCase(reported.path) } : reported
     switch (change.type) {
       case 'created':
-      case 'modified':
-        try {
-          const fileNode = await parseFile(change.path)
-          project.files.set(change.path, fileNode)
+      case 'modified': {
+        const owner = findOwningProject(project, change.path)
+        const { directory, languages, maxDepth, maxFileSize, useDefaultExcludes, followSymlinks, allowOutsideRoot } = owner.config
+        const walkOptions = { maxDepth, maxFileSize, useDefaultExcludes, followSymlinks, allowOutsideRoot, roots: [project.config.directory], ignoreCase }
+
+        if (!isProjectFile(directory, change.path, languages, getProjectIgnoreDirs(owner), walkOptions) || !createProjectFileFilter(owner.config)(change.path)) {
+          evictPath(project, change.path, ignoreCase)
+          break
+        }
 
-          const allNodes = extractAllNodes(fileNode)
-          project.nodes.set(change.path, allNodes)
+        try {
+          const fileNode = await parseFile(change.path, owner.files.get(change.path))
+          evictPath(project, change.path, ignoreCase)
+          owner.files.set(change.path, fileNode)
+          owner.nodes.set(change.path, extractAllNodes(fileNode))
 
           logger.debug(`Updated file: ${change.path}`)
         }
@@ -111,21 +359,45 @@ export async function updateProject(project: Project, changes: FileChange[]): Pr
           logger.warn(`Failed to update ${change.path}:`, error)
         }
         break
+      }
 
       case 'deleted':
-        project.files.delete(change.path)
-        project.nodes.delete(change.path)
+        evictPath(project, change.path, ignoreCase)
         logger.debug(`Removed file: ${change.path}`)
         break
     }
   }
 }
 
+function findOwningProject(project: Project, filePath: string): Project {
+  if (!project.subProjects || project.subProjects.length === 0) return project
+
+  const holder = project.subProjects.find(sub => sub.files.has(filePath))
+  if (holder) return findOwningProject(holder, filePath)
+
+  const containing = project.subProjects
+    .filter(sub => filePath !== sub.config.directory && isWithinPath(filePath, sub.config.directory, ignoresPathCase(sub.config)))
+    .sort((a, b) => b.config.directory.length - a.config.directory.length)[0]
+
+  return containing ? findOwningProject(containing, filePath) : project
+}
+
+function evictPath(project: Project, targetPath: string, ignoreCase = false): void {
+  for (const filePath of Array.from(project.files.keys())) {
+    if (isWithinPath(filePath, targetPath, ignoreCase)) {
+      project.files.delete(filePath)
+      project.nodes.delete(filePath)
+    }
+  }
+
+  project.subProjects?.forEach(sub => evictPath(sub, targetPath, ignoreCase))
+}
+
 export function watchProject(project: Project, onUpdate?: (changes: FileChange[]) => void): () => void {
   const watcher = createFileWatcher(
     project.config.directory,
-    (changes) => {
-      updateProject(project, changes)
+    async (changes) => {
+      await updateProject(project, changes)
       onUpdate?.(changes)
     },
   )
@@ -155,6 +427,85 @@ export function getAllNodes(project: Project): TreeNode[] {
   return allNodes
 }
 
+/**
+ * Files left out of the index across the project and its sub-projects
+ */
+export function getSkippedFiles(project: Project): SkippedFile[] {
+  return [...(project.skippedFiles || []), ...(project.subProjects || []).flatMap(getSkippedFiles)]
+}
+
+/**
+ * Directories of the project and its sub-projects the walk did not enter for lying deeper than `maxDepth`
+ */
+export function getDepthSkipped(project: Project): string[] {
+  return [...(project.depthSkipped || []), ...(project.subProjects || []).flatMap(getDepthSkipped)]
+}
+
+/**
+ * Files of the project and its sub-projects the parser threw on, so they are missing from the index
+ */
+export function getParseFailures(project: Project): string[] {
+  return [...(project.parseFailures || []), ...(project.subProjects || []).flatMap(getParseFailures)]
+}
+
+/**
+ * Symlinks of the project and its sub-projects the walk did not follow for pointing outside the project root
+ */
+export function getOutsideRootLinks(project: Project): string[] {
+  return [...(project.outsideRootLinks || []), ...(project.subProjects || []).flatMap(getOutsideRootLinks)]
+}
+
+/**
+ * Resolves a path argument given as absolute or relative to the project root. A path outside the root is
+ * an error, so it is not mistaken for a file that simply has no results. Where case is ignored, the path
+ * is spelled as on disk, so `SRC/App.ts` names the indexed `src/app.ts`
+ */
+export function resolveProjectPath(project: Project, filePath: string): string {
+  const root = project.config.directory
+  const ignoreCase = ignoresPathCase(project.config)
+  const resolved = ignoreCase ? toDiskCase(resolve(root, filePath)) : resolve(root, filePath)
+  if (!isWithinPath(resolved, root, ignoreCase)) {
+    throw createError('FILE_ERROR', `Path is outside the project root ${root}: ${filePath}. Pass the project's directory or projectId to search it`, { file: resolved, root })
+  }
+  return resolved
+}
+
+export function getGitIgnoredCount(project: Project): number {
+  return (project.gitIgnoredCount ?? 0) + (project.subProjects || []).reduce((total, subProject) => total + getGitIgnoredCount(subProject), 0)
+}
+
+/**
+ * Lists files classified by a config override, `#!` line or content heuristic, so surprising grammar
+ * choices can be traced
+ */
+export function getClassifiedFiles(project: Project): ClassifiedFile[] {
+  return getAllFiles(project).flatMap((fileNode) => {
+    const resolution = resolveFileLanguage(fileNode.path, fileNode.content)
+    return resolution && resolution.source !== 'extension'
+      ? [{ path: fileNode.path, language: resolution.language.name, source: resolution.source }]
+      : []
+  })
+}
+
+/**
+ * Collects parsed file nodes for a project and its sub-projects, deduplicated by path
+ */
+export function getAllFiles(project: Project): TreeNode[] {
+  const filesByPath = new Map<string, TreeNode>()
+
+  function collect(current: Project) {
+    for (const [filePath, fileNode] of current.files) {
+      if (!filesByPath.has(filePath)) {
+        filesByPath.set(filePath, fileNode)
+      }
+    }
+    current.subProjects?.forEach(collect)
+  }
+
+  collect(project)
+  return Array.from(filesByPath.values())
+}
+
 export function getProjectStats(project: Project): {
   totalFiles: number
   totalNodes: number
@@ -183,16 +534,52 @@ export function getProjectStats(project: Project): {
   }
 }
 
-function extractAllNodes(fileNode: TreeNode): TreeNode[] {
-  const nodes: TreeNode[] = []
+/**
+ * File, symbol and byte counts by language across the project and its sub-projects; skipped files are not counted
+ */
+export function getIndexStats(project: Project): IndexStats {
+  const nodesByFile = new Map<string, TreeNode[]>()
+  const collect = (current: Project) => {
+    for (const [filePath, nodes] of current.nodes) {
+      if (!nodesByFile.has(filePath)) nodesByFile.set(filePath, nodes)
+    }
+    current.subProjects?.forEach(collect)
+  }
+  collect(project)
+
+  const stats: IndexStats = {
+    totalFiles: 0,
+    totalSymbols: 0,
+    totalBytes: 0,
+    parseTrees: 0,
+    estimatedMemoryBytes: 0,
+    languages: {},
+  }
+
+  for (const fileNode of getAllFiles(project)) {
+    if (fileNode.skipped) continue
 
-  function traverse(node: TreeNode) {
-    nodes.push(node)
-    if (node.children) {
-      node.children.forEach(traverse)
+    const language = getFileLanguage(fileNode.path, fileNode.content)?.name ?? 'other'
+    let entry = stats.languages[language]
+    if (!entry) {
+      entry = { files: 0, symbols: 0, bytes: 0 }
+      stats.languages[language] = entry
     }
+    const symbols = (nodesByFile.get(fileNode.path) ?? []).filter(node => node !== fileNode).length
+    const bytes = Buffer.byteLength(fileNode.content ?? '', 'utf8')
+
+    entry.files++
+    entry.symbols += symbols
+    entry.bytes += bytes
+    stats.totalFiles++
+    stats.totalSymbols += symbols
+    stats.totalBytes += bytes
+    if (getResidentTree(fileNode)) stats.parseTrees++
   }
 
-  traverse(fileNode)
-  return nodes
+  stats.estimatedMemoryBytes = stats.totalBytes
+    + stats.totalFiles * MEMORY_LIMITS.ESTIMATED_BYTES_PER_FILE
+    + stats.totalSymbols * MEMORY_LIMITS.ESTIMATED_BYTES_PER_NODE
+
+  return stats
 }
\ No newline at end of file
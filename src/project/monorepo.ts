@@ -6,7 +6,7 @@ import { join, resolve } from 'path'
 import { readFileSync as fsReadFileSync, readdirSync } from 'fs'
 import { isDirectory, isFile } from '../utils/helpers.js'
 import { getLogger } from '../utils/logger.js'
-import { GLOBAL_IGNORE_DIRS } from '../constants/index.js'
+import { GLOBAL_IGNORE_DIRS, ROOT_TYPE_IGNORE_DIRS } from '../constants/index.js'
 import type { MonorepoInfo } from '../types/analysis.js'
 
 const PROJECT_INDICATORS = [
@@ -311,7 +311,10 @@ export function getMonorepoStructure(directory: string): {
   }
 }
 
-function detectProjectType(projectPath: string): string {
+/**
+ * Names the toolchain of a root from its manifest: npm, rust, go, python, java, or unknown without one
+ */
+export function detectProjectType(projectPath: string): string {
   if (isFile(join(projectPath, 'package.json'))) return 'npm'
   if (isFile(join(projectPath, 'Cargo.toml'))) return 'rust'
   if (isFile(join(projectPath, 'go.mod'))) return 'go'
@@ -320,6 +323,13 @@ function detectProjectType(projectPath: string): string {
   return 'unknown'
 }
 
+/**
+ * Directories skipped by default under a root of this type
+ */
+export function getRootIgnoreDirs(rootType?: string): string[] {
+  return [...(rootType ? ROOT_TYPE_IGNORE_DIRS[rootType] ?? [] : [])]
+}
+
 function readDirSync(dir: string): string[] {
   try {
     return readdirSync(dir)
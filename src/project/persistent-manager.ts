@@ -6,24 +6,38 @@ import { resolve, basename } from 'path'
 import { createHash } from 'crypto'
 import { access, constants } from 'fs/promises'
 import { createMemoryManager, addProject, getProject, removeProject, type MemoryManager } from './memory.js'
-import { createProject, parseProject, watchProject, updateProject } from './manager.js'
+import { createProject, parseProject, loadOrBuildIndex, watchProject, getAllFiles, type IndexOptions } from './manager.js'
+import { releaseTrees } from '../core/tree-cache.js'
+import { setLanguageOverrides } from '../core/languages.js'
+import { ignoresPathCase, isWithinPath, toDiskCase } from '../core/path-case.js'
+import { DEFAULT_PROJECT_DEPTH } from '../core/file-walker.js'
+import { isIndexCacheEnabled } from './index-cache.js'
+import { withConfigFile } from './config.js'
 import { getLogger } from '../utils/logger.js'
 import { PROJECT_ID_PATTERNS } from '../constants/persistence.js'
-import type { Project, ProjectConfig, FileChange } from '../types/core.js'
+import type { Project, ProjectConfig, ProjectRoot, FileChange } from '../types/core.js'
 
 export interface PersistentProjectManager {
   memory: MemoryManager
   directoryToProject: Map<string, string>
   projectToDirectory: Map<string, string>
   watchers: Map<string, () => void>
+  /** Projects whose index is being built, by ID */
+  indexing: Set<string>
+  /** Called after a watched project reindexes a batch of changed files */
+  changeListeners: Set<IndexChangeListener>
 }
 
+export type IndexChangeListener = (projectId: string, changes: FileChange[]) => void
+
 export function createPersistentManager(maxProjects = 10): PersistentProjectManager {
   return {
     memory: createMemoryManager(maxProjects),
     directoryToProject: new Map(),
     projectToDirectory: new Map(),
     watchers: new Map(),
+    indexing: new Set(),
+    changeListeners: new Set(),
   }
 }
 
@@ -43,7 +57,27 @@ function hasConfigChanged(oldConfig: ProjectConfig, newConfig: ProjectConfig): b
   }
 
   // Check other significant config changes
-  if (oldConfig.maxDepth !== newConfig.maxDepth) {
+  if ((oldConfig.maxDepth ?? DEFAULT_PROJECT_DEPTH) !== (newConfig.maxDepth ?? DEFAULT_PROJECT_DEPTH)) {
+    return true
+  }
+
+  if ((oldConfig.useDefaultExcludes ?? true) !== (newConfig.useDefaultExcludes ?? true)) {
+    return true
+  }
+
+  if (Boolean(oldConfig.followSymlinks) !== Boolean(newConfig.followSymlinks) || Boolean(oldConfig.allowOutsideRoot) !== Boolean(newConfig.allowOutsideRoot)) {
+    return true
+  }
+
+  if (oldConfig.ignoreCaseInPaths !== newConfig.ignoreCaseInPaths) {
+    return true
+  }
+
+  if (!isSameList(oldConfig.include, newConfig.include) || !isSameList(oldConfig.exclude, newConfig.exclude)) {
+    return true
+  }
+
+  if (JSON.stringify(oldConfig.languageOverrides ?? {}) !== JSON.stringify(newConfig.languageOverrides ?? {})) {
     return true
   }
 
@@ -64,21 +98,33 @@ function hasConfigChanged(oldConfig: ProjectConfig, newConfig: ProjectConfig): b
   return false
 }
 
+function isSameList(a: string[] = [], b: string[] = []): boolean {
+  return a.length === b.length && a.every((item, index) => item === b[index])
+}
+
 export async function getOrCreateProject(
   manager: PersistentProjectManager,
   config: ProjectConfig,
   projectId?: string,
+  indexOptions: IndexOptions = {},
 ): Promise<Project> {
   const logger = getLogger()
-  const directory = resolve(config.directory)
+  const resolved = resolve(config.directory)
 
   try {
-    await access(directory, constants.R_OK)
+    await access(resolved, constants.R_OK)
   }
   catch {
-    throw new Error(`Directory does not exist or is not accessible: ${directory}`)
+    throw new Error(`Directory does not exist or is not accessible: ${resolved}`)
   }
 
+  // Settings passed by the caller win over the project's .tree-sitter-mcp.toml
+  config = withConfigFile({ ...config, directory: resolved })
+  // Where case is ignored, a directory typed in another case opens the same project
+  const directory = ignoresPathCase(config) ? toDiskCase(resolved) : resolved
+  config = { ...config, directory }
+  setLanguageOverrides(directory, config.languageOverrides ?? {})
+
   const rawProjectId = projectId || generateProjectId(manager, directory)
   const finalProjectId = sanitizeProjectId(rawProjectId)
 
@@ -98,7 +144,8 @@ export async function getOrCreateProject(
   })
   project.id = finalProjectId
 
-  await parseProject(project)
+  // A cancelled index is never registered, so the next request starts over
+  await indexProject(manager, project, indexOptions)
 
   // Handle eviction before adding new project
   if (manager.memory.projects.size >= manager.memory.maxProjects) {
@@ -166,12 +213,16 @@ export function sanitizeProjectId(projectId: string): string {
 export function listProjects(manager: PersistentProjectManager): Array<{
   projectId: string
   directory: string
+  rootType: string
+  roots: ProjectRoot[]
   lastAccessed: number
   isWatched: boolean
 }> {
   const projects: Array<{
     projectId: string
     directory: string
+    rootType: string
+    roots: ProjectRoot[]
     lastAccessed: number
     isWatched: boolean
   }> = []
@@ -180,6 +231,8 @@ export function listProjects(manager: PersistentProjectManager): Array<{
     projects.push({
       projectId,
       directory: project.config.directory,
+      rootType: project.rootType ?? 'unknown',
+      roots: getProjectRoots(project),
       lastAccessed: manager.memory.lastAccessed.get(projectId) || 0,
       isWatched: manager.watchers.has(projectId),
     })
@@ -188,12 +241,40 @@ export function listProjects(manager: PersistentProjectManager): Array<{
   return projects.sort((a, b) => b.lastAccessed - a.lastAccessed)
 }
 
+/**
+ * The open project with the deepest root containing a path, if any
+ */
+export function findProjectForPath(manager: PersistentProjectManager, filePath: string): Project | undefined {
+  const path = resolve(filePath)
+  let found: Project | undefined
+  for (const project of manager.memory.projects.values()) {
+    const root = project.config.directory
+    if (isWithinPath(path, root, ignoresPathCase(project.config)) && (!found || root.length > found.config.directory.length)) {
+      found = project
+    }
+  }
+  return found
+}
+
+/**
+ * The manifest roots indexed as sub-projects, each before the roots nested in it; empty for a single-root project
+ */
+export function getProjectRoots(project: Project): ProjectRoot[] {
+  return (project.subProjects || []).map(subProject => ({
+    directory: subProject.config.directory,
+    rootType: subProject.rootType ?? 'unknown',
+  }))
+}
+
+/**
+ * Stops watching the project and drops its index and cached trees; returns false for unknown IDs
+ */
 export function removeProjectFromManager(
   manager: PersistentProjectManager,
   projectId: string,
-): void {
+): boolean {
   const project = manager.memory.projects.get(projectId)
-  if (!project) return
+  if (!project) return false
 
   stopWatching(manager, projectId)
 
@@ -201,7 +282,9 @@ export function removeProjectFromManager(
   manager.directoryToProject.delete(directory)
   manager.projectToDirectory.delete(projectId)
 
+  releaseTrees(getAllFiles(project))
   removeProject(manager.memory, projectId)
+  return true
 }
 
 export function clearAllProjects(manager: PersistentProjectManager): void {
@@ -236,13 +319,25 @@ async function updateProjectDirectory(
 
   project.config = { ...config, directory: newDirectory }
 
-  await parseProject(project)
+  await indexProject(manager, project)
 
   if (config.autoWatch !== false) {
     startWatching(manager, project)
   }
 }
 
+async function indexProject(manager: PersistentProjectManager, project: Project, options: IndexOptions = {}): Promise<Project> {
+  manager.indexing.add(project.id)
+  try {
+    return await (project.config.useCache && isIndexCacheEnabled()
+      ? loadOrBuildIndex(project, options)
+      : parseProject(project, new Set(), undefined, options))
+  }
+  finally {
+    manager.indexing.delete(project.id)
+  }
+}
+
 function startWatching(
   manager: PersistentProjectManager,
   project: Project,
@@ -250,9 +345,11 @@ function startWatching(
   if (manager.watchers.has(project.id)) return
 
   const logger = getLogger()
-  const stopWatcher = watchProject(project, async (changes: FileChange[]) => {
-    logger.debug(`Project ${project.id} file changes: ${changes.length}`)
-    await updateProject(project, changes)
+  const stopWatcher = watchProject(project, (changes: FileChange[]) => {
+    logger.debug(`Project ${project.id} reindexed ${changes.length} changed paths`)
+    for (const listener of manager.changeListeners) {
+      listener(project.id, changes)
+    }
   })
 
   manager.watchers.set(project.id, stopWatcher)
@@ -0,0 +1,74 @@
+/**
+ * CLI --json-schema tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { spawnSync } from 'child_process'
+import { join } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import { MCP_TOOLS, MCP_TOOL_RESULTS } from '../../mcp/schemas.js'
+import type { JsonObject } from '../../types/core.js'
+
+const CLI_PATH = join(process.cwd(), 'src/cli.ts')
+const POSITIVE_FIXTURE = join(process.cwd(), 'src/test/fixtures/minimal-positive')
+
+function runJsonSchema(args: string[]): { stdout: string, status: number } {
+  const result = spawnSync('npx', ['tsx', CLI_PATH, '--json-schema', ...args], {
+    encoding: 'utf-8',
+    stdio: 'pipe',
+    env: { ...process.env, NO_COLOR: '1' },
+  })
+
+  return {
+    stdout: result.stdout || '',
+    status: result.status || 0,
+  }
+}
+
+describe('CLI --json-schema', () => {
+  it('should print input and output schemas for every tool', () => {
+    const { stdout, status } = runJsonSchema([])
+
+    expect(status).toBe(0)
+    const schema = JSON.parse(stdout)
+    expect(schema.tools.map((tool: any) => tool.name)).toEqual(MCP_TOOLS.map(tool => tool.name))
+    for (const tool of schema.tools) {
+      expect(tool.inputSchema.type).toBe('object')
+      expect(tool.outputSchema.type).toBe('object')
+    }
+  })
+
+  it('should print a single tool when named', () => {
+    const { stdout } = runJsonSchema(['search_code'])
+
+    const schema = JSON.parse(stdout)
+    expect(schema.tools).toHaveLength(1)
+    expect(schema.tools[0].inputSchema.required).toContain('query')
+    expect(schema.tools[0].outputSchema.properties.totalResults.type).toBe('integer')
+  })
+
+  it('should fail for an unknown tool', () => {
+    const { stdout, status } = runJsonSchema(['not_a_tool'])
+
+    expect(status).toBe(1)
+    expect(stdout).toContain('Unknown tool: not_a_tool')
+  })
+
+  it('should describe every field a tool returns', async () => {
+    const calls: Array<[string, JsonObject]> = [
+      ['search_code', { query: 'TestUser', directory: POSITIVE_FIXTURE }],
+      ['list_symbols', { directory: POSITIVE_FIXTURE }],
+      ['check_errors', { directory: POSITIVE_FIXTURE }],
+      ['health', {}],
+    ]
+
+    for (const [name, args] of calls) {
+      const result = await handleToolRequest({ params: { name, arguments: args } })
+      const payload = JSON.parse(result.content[0].text)
+      const schema = MCP_TOOL_RESULTS[name] as { properties: Record<string, unknown>, required: string[] }
+
+      expect(Object.keys(schema.properties)).toEqual(expect.arrayContaining(Object.keys(payload)))
+      expect(Object.keys(payload)).toEqual(expect.arrayContaining(schema.required))
+    }
+  })
+})
\ No newline at end of file
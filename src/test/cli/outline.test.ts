@@ -0,0 +1,86 @@
+/**
+ * CLI outline command tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { spawnSync } from 'child_process'
+import { join } from 'path'
+
+const CLI_PATH = join(process.cwd(), 'src/cli.ts')
+const FIXTURES_DIR = join(process.cwd(), 'src/test/fixtures')
+const PROVIDER_FILE = join(FIXTURES_DIR, 'relevance-ranking/src/chat/provider.ts')
+const PARTIAL_FILE = join(FIXTURES_DIR, 'partial-syntax/src/orders.ts')
+
+function runOutlineCommand(args: string[], input?: string): { stdout: string, stderr: string, status: number } {
+  const result = spawnSync('npx', ['tsx', CLI_PATH, 'outline', ...args], {
+    encoding: 'utf-8',
+    stdio: 'pipe',
+    input,
+    env: { ...process.env, NO_COLOR: '1' },
+  })
+
+  return {
+    stdout: result.stdout || '',
+    stderr: result.stderr || '',
+    status: result.status || 0,
+  }
+}
+
+describe('CLI outline command', () => {
+  it('should print nested symbols with kind, name and line', () => {
+    const result = runOutlineCommand([PROVIDER_FILE])
+
+    expect(result.status).toBe(0)
+    expect(result.stdout).toContain('  class ChatProvider (lines 1-7)')
+    expect(result.stdout).toContain('    method constructor (line 2)')
+    expect(result.stdout).toContain('    method describe (lines 4-6)')
+  })
+
+  it('should limit nesting with --depth', () => {
+    const result = runOutlineCommand([PROVIDER_FILE, '--depth', '1'])
+
+    expect(result.status).toBe(0)
+    expect(result.stdout).toContain('class ChatProvider')
+    expect(result.stdout).not.toContain('method describe')
+  })
+
+  it('should return the list_symbols outline as json', () => {
+    const result = runOutlineCommand([PROVIDER_FILE, '--output', 'json'])
+
+    expect(result.status).toBe(0)
+    const output = JSON.parse(result.stdout)
+    expect(output.totalSymbols).toBe(3)
+    expect(output.symbols[0]).toMatchObject({ name: 'ChatProvider', kind: 'class', startLine: 1 })
+    expect(output.symbols[0].children.map((child: any) => child.name)).toEqual(['constructor', 'describe'])
+  })
+
+  it('should note syntax errors after the recovered symbols', () => {
+    const result = runOutlineCommand([PARTIAL_FILE])
+
+    expect(result.status).toBe(0)
+    expect(result.stdout).toMatch(/syntax error\(s\); symbols inside them are missing/)
+  })
+
+  it('should reject an invalid depth', () => {
+    const result = runOutlineCommand([PROVIDER_FILE, '--depth', '0'])
+
+    expect(result.status).toBe(1)
+    expect(result.stdout).toContain('Invalid depth value')
+  })
+
+  it('should fail on a file it cannot parse', () => {
+    const result = runOutlineCommand([join(FIXTURES_DIR, 'relevance-ranking/package.json')])
+
+    expect(result.status).toBe(1)
+    expect(result.stdout).toContain('Unsupported file type')
+  })
+
+  it('should outline source piped on stdin', () => {
+    const result = runOutlineCommand(['--stdin', '--language', 'python', '--output', 'json'], 'class Greeter:\n    def greet(self):\n        pass\n')
+
+    expect(result.status).toBe(0)
+    const output = JSON.parse(result.stdout)
+    expect(output.path).toBe('stdin.py')
+    expect(output.symbols[0]).toMatchObject({ name: 'Greeter', kind: 'class' })
+  })
+})
\ No newline at end of file
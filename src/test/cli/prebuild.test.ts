@@ -0,0 +1,82 @@
+/**
+ * CLI prebuild command tests - writing the index cache and failing on parse errors with --strict
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest'
+import { spawnSync } from 'child_process'
+import { existsSync, mkdtempSync, rmSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+
+const CLI_PATH = join(process.cwd(), 'src/cli.ts')
+const FIXTURES_DIR = join(process.cwd(), 'src/test/fixtures')
+const ERROR_SCENARIOS_DIR = join(FIXTURES_DIR, 'error-scenarios')
+const CLEAN_CODE_DIR = join(FIXTURES_DIR, 'clean-code')
+
+describe('CLI Prebuild Command', () => {
+  let cacheDir: string
+
+  beforeEach(() => {
+    cacheDir = mkdtempSync(join(tmpdir(), 'tsmcp-prebuild-'))
+  })
+
+  afterEach(() => {
+    rmSync(cacheDir, { recursive: true, force: true })
+  })
+
+  function runPrebuild(args: string[], globalArgs: string[] = [], cacheDirectory = cacheDir): { stdout: string, status: number } {
+    const result = spawnSync('npx', ['tsx', CLI_PATH, ...globalArgs, 'prebuild', ...args], {
+      encoding: 'utf-8',
+      stdio: 'pipe',
+      env: { ...process.env, NO_COLOR: '1', TREE_SITTER_MCP_CACHE_DIR: cacheDirectory },
+    })
+    return { stdout: result.stdout || '', status: result.status || 0 }
+  }
+
+  // The report is the last thing written, after any indexing log lines
+  function parseReport(stdout: string): any {
+    return JSON.parse(stdout.slice(stdout.search(/^\{/m)))
+  }
+
+  it('should write the index cache and report file and symbol counts', () => {
+    const { stdout, status } = runPrebuild(['--directory', CLEAN_CODE_DIR])
+    const report = parseReport(stdout)
+
+    expect(status).toBe(0)
+    expect(report.totalFiles).toBe(1)
+    expect(report.totalSymbols).toBeGreaterThan(0)
+    expect(report.failedFiles).toEqual([])
+    expect(report.filesWithErrors).toEqual([])
+    expect(report.cacheFile.startsWith(cacheDir)).toBe(true)
+    expect(existsSync(report.cacheFile)).toBe(true)
+  })
+
+  it('should list files with syntax errors and exit non-zero only with --strict', () => {
+    const lenient = runPrebuild(['--directory', ERROR_SCENARIOS_DIR])
+    const report = parseReport(lenient.stdout)
+
+    expect(lenient.status).toBe(0)
+    expect(report.filesWithErrors.length).toBeGreaterThan(0)
+    expect(report.filesWithErrors[0].errors).toBeGreaterThan(0)
+
+    expect(runPrebuild(['--directory', ERROR_SCENARIOS_DIR, '--strict']).status).toBe(1)
+    expect(runPrebuild(['--directory', CLEAN_CODE_DIR, '--strict']).status).toBe(0)
+  })
+
+  it('should fail when the cache cannot be written', () => {
+    // A file where the cache directory should be makes the write fail
+    const blocked = join(cacheDir, 'blocked')
+    writeFileSync(blocked, '')
+    const { stdout, status } = runPrebuild(['--directory', CLEAN_CODE_DIR], [], blocked)
+
+    expect(status).toBe(1)
+    expect(parseReport(stdout).message).toContain('Failed to write the index cache')
+  })
+
+  it('should refuse to run with --no-cache', () => {
+    const { stdout, status } = runPrebuild(['--directory', CLEAN_CODE_DIR], ['--no-cache'])
+
+    expect(status).toBe(1)
+    expect(parseReport(stdout).message).toContain('--no-cache')
+  })
+})
\ No newline at end of file
@@ -4,7 +4,7 @@
  */
 
 import { describe, it, expect } from 'vitest'
-import { resolve } from 'path'
+import { relative, resolve } from 'path'
 import { spawnSync } from 'child_process'
 
 // Helper function to extract JSON from output that may contain log messages
@@ -66,10 +66,11 @@ describe('CLI search Command', () => {
   const cliPath = resolve(import.meta.dirname, '../../cli.ts')
 
   // Helper function to run CLI command
-  function runCLI(args: string[], expectError = false): { stdout: string, stderr: string, exitCode: number } {
+  function runCLI(args: string[], expectError = false, input?: string): { stdout: string, stderr: string, exitCode: number } {
     const result = spawnSync('npx', ['tsx', cliPath, ...args], {
       encoding: 'utf8',
       stdio: 'pipe',
+      input,
     })
 
     if (result.error && !expectError) {
@@ -224,6 +225,45 @@ describe('CLI search Command', () => {
       expect(result.exitCode).toBe(0)
       expect(result.stdout).toContain('No results found')
     })
+
+    it('should output rg lines relative to the working directory', () => {
+      const result = runCLI(['search', 'TestUser', '-d', positiveFixture, '--output', 'rg', '--color', 'never'])
+      const indexPath = relative(process.cwd(), resolve(positiveFixture, 'src/index.ts'))
+
+      expect(result.exitCode).toBe(0)
+      const lines = result.stdout.trim().split('\n')
+      expect(lines.length).toBeGreaterThan(0)
+      expect(lines).toContain(`${indexPath}:2:18:export interface TestUser {`)
+      expect(result.stdout).not.toContain('\u001B[')
+      expect(result.stdout).not.toContain('[INFO]')
+    })
+
+    it('should exit with 1 when rg output has no matches', () => {
+      const result = runCLI(['search', 'NonexistentFunction', '-d', emptyFixture, '--output', 'rg'], true)
+
+      expect(result.exitCode).toBe(1)
+      expect(result.stdout).not.toMatch(/:\d+:\d+:/)
+    })
+
+    it('should stream jsonl results and close with a summary line', () => {
+      const result = runCLI(['search', 'TestUser', '-d', positiveFixture, '--output', 'jsonl'])
+
+      expect(result.exitCode).toBe(0)
+      const lines = result.stdout.trim().split('\n').map(line => JSON.parse(line))
+      const summary = lines[lines.length - 1]
+
+      expect(summary).toEqual({ type: 'summary', query: 'TestUser', totalResults: lines.length - 1 })
+      expect(summary.totalResults).toBeGreaterThan(0)
+      expect(lines.slice(0, -1).map(line => line.node.name)).toContain('TestUser')
+      lines.slice(0, -1).forEach(line => expect(line).toHaveProperty('score'))
+    })
+
+    it('should reject an invalid color value', () => {
+      const result = runCLI(['search', 'TestUser', '-d', positiveFixture, '--output', 'rg', '--color', 'sometimes'], true)
+
+      expect(result.exitCode).not.toBe(0)
+      expect(result.stderr + result.stdout).toContain('Invalid color value')
+    })
   })
 
   describe('Error Handling', () => {
@@ -311,6 +351,16 @@ describe('CLI search Command', () => {
       expect(output.results).toBeInstanceOf(Array)
     })
 
+    it('should reject a one-character query unless --min-query-length allows it', () => {
+      const rejected = runCLI(['search', 'T', '-d', positiveFixture, '--output', 'json'], true)
+      expect(rejected.exitCode).toBe(1)
+      expect(extractJSONFromOutput(rejected.stdout).message).toContain('shorter than 2 characters')
+
+      const allowed = runCLI(['search', 'T', '-d', positiveFixture, '--min-query-length', '1', '--output', 'json'])
+      expect(allowed.exitCode).toBe(0)
+      expect(extractJSONFromOutput(allowed.stdout).results.length).toBeGreaterThan(0)
+    })
+
     it('should handle queries with special characters', () => {
       const result = runCLI(['search', 'Test@#$%^&*()', '-d', positiveFixture, '--output', 'json'])
 
@@ -351,6 +401,26 @@ describe('CLI search Command', () => {
     })
   })
 
+  describe('Stdin', () => {
+    const source = 'struct Parser;\n\nimpl Parser {\n    fn parse(&self) {}\n}\n\nfn parse_all() {}\n'
+
+    it('should search a piped buffer without a project', () => {
+      const result = runCLI(['search', 'parse', '--stdin', '--language', 'rust', '--output', 'json'], false, source)
+
+      expect(result.exitCode).toBe(0)
+      const output = extractJSONFromOutput(result.stdout)
+      expect(output.results.map((r: any) => r.name)).toEqual(expect.arrayContaining(['parse', 'parse_all']))
+      expect(output.results.find((r: any) => r.name === 'parse').enclosingPath).toBe('Parser::parse')
+    })
+
+    it('should require a language', () => {
+      const result = runCLI(['search', 'parse', '--stdin', '--output', 'json'], true, source)
+
+      expect(result.exitCode).toBe(1)
+      expect(extractJSONFromOutput(result.stdout).message).toContain('--stdin requires --language')
+    })
+  })
+
   describe('Performance', () => {
     it('should complete search within reasonable time', () => {
       const startTime = Date.now()
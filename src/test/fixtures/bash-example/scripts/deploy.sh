@@ -0,0 +1,27 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+DEPLOY_ENV="${1:-staging}"
+RETRIES=3
+
+function log {
+  local message="$1"
+  echo "[deploy] $message"
+}
+
+build_image() {
+  log "building for $DEPLOY_ENV"
+  docker build -t "app:$DEPLOY_ENV" .
+}
+
+push_image() {
+  for attempt in $(seq 1 "$RETRIES"); do
+    if docker push "app:$DEPLOY_ENV"; then
+      return 0
+    fi
+    log "push attempt $attempt failed"
+  done
+  return 1
+}
+
+build_image && push_image
\ No newline at end of file
@@ -0,0 +1,17 @@
+#pragma once
+
+namespace geometry {
+
+class Circle {
+public:
+    explicit Circle(double radius);
+    double area() const;
+    double radius() const { return radius_; }
+
+private:
+    double radius_;
+};
+
+double distance(double x1, double y1, double x2, double y2);
+
+}
\ No newline at end of file
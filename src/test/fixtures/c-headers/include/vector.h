@@ -0,0 +1,18 @@
+#ifndef VECTOR_H
+#define VECTOR_H
+
+#include <stddef.h>
+
+struct vector {
+    double *items;
+    size_t length;
+    size_t capacity;
+};
+
+struct vector *vector_new(size_t capacity);
+void vector_push(struct vector *vec, double value);
+double vector_sum(const struct vector *vec);
+
+extern int (*on_resize)(size_t length);
+
+#endif
\ No newline at end of file
@@ -0,0 +1,37 @@
+#include <stdlib.h>
+#include "../include/vector.h"
+
+static void grow(struct vector *vec);
+
+int (*on_resize)(size_t length) = NULL;
+
+struct vector *vector_new(size_t capacity) {
+    struct vector *vec = malloc(sizeof(struct vector));
+    vec->items = malloc(capacity * sizeof(double));
+    vec->length = 0;
+    vec->capacity = capacity;
+    return vec;
+}
+
+void vector_push(struct vector *vec, double value) {
+    if (vec->length == vec->capacity) {
+        grow(vec);
+    }
+    vec->items[vec->length++] = value;
+}
+
+double vector_sum(const struct vector *vec) {
+    double total = 0;
+    for (size_t i = 0; i < vec->length; i++) {
+        total += vec->items[i];
+    }
+    return total;
+}
+
+static void grow(struct vector *vec) {
+    vec->capacity *= 2;
+    vec->items = realloc(vec->items, vec->capacity * sizeof(double));
+    if (on_resize) {
+        on_resize(vec->capacity);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+import { applyDiscount, addTax } from './pricing.js'
+
+export class Checkout {
+  private items: number[] = []
+
+  total(): number {
+    const subtotal = this.items.reduce((sum, item) => sum + item, 0)
+    return addTax(applyDiscount(subtotal, 0.1))
+  }
+
+  receipt(): string {
+    return `Total: ${this.total().toFixed(2)}`
+  }
+}
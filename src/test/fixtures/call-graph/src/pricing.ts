@@ -0,0 +1,12 @@
+export function applyDiscount(total: number, rate: number): number {
+  return roundCents(total * (1 - rate))
+}
+
+export function roundCents(value: number): number {
+  return Math.round(value * 100) / 100
+}
+
+export const addTax = (amount: number): number => roundCents(amount * 1.2)
+
+// Passes roundCents as a value; only the map call is a call site
+export const roundAll = (values: number[]): number[] => values.map(roundCents)
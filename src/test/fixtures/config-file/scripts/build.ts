@@ -0,0 +1,3 @@
+export function buildScript(): void {
+  console.log('build')
+}
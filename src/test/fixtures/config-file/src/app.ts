@@ -0,0 +1,5 @@
+import { formatTotal } from './format.js'
+
+export function runApp(values: number[]): string {
+  return formatTotal(values.reduce((sum, value) => sum + value, 0))
+}
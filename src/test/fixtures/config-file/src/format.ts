@@ -0,0 +1,3 @@
+export function formatTotal(total: number): string {
+  return `Total: ${total}`
+}
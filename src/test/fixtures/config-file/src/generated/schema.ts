@@ -0,0 +1,3 @@
+export function generatedSchema(): string {
+  return 'schema'
+}
@@ -0,0 +1,2 @@
+def python_helper():
+    return 'helper'
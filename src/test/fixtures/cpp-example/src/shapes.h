@@ -0,0 +1,20 @@
+#pragma once
+
+namespace geometry {
+
+class Shape {
+public:
+    virtual ~Shape() = default;
+    virtual double area() const = 0;
+};
+
+class Square : public Shape {
+public:
+    explicit Square(double side) : side_(side) {}
+    double area() const override { return side_ * side_; }
+
+private:
+    double side_;
+};
+
+}
\ No newline at end of file
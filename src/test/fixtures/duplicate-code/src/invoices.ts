@@ -0,0 +1,29 @@
+export interface InvoiceItem {
+  amount: number
+  count: number
+}
+
+// Copied from orderTotal with the names changed
+export function invoiceTotal(items: InvoiceItem[], rebate: number): number {
+  let sum = 0
+  for (const item of items) {
+    if (item.count > 0) {
+      sum += item.amount * item.count
+    }
+  }
+  return Math.round(sum * (1 - rebate) * 1000) / 1000
+}
+
+export function invoiceLabel(id: string): string {
+  return `invoice-${id}`
+}
+
+export function validateInvoice(items: InvoiceItem[]): string[] {
+  const errors: string[] = []
+  items.forEach((item, index) => {
+    if (item.amount <= 0) {
+      errors.push(`Item ${index} has no amount`)
+    }
+  })
+  return errors
+}
\ No newline at end of file
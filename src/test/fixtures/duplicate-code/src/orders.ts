@@ -0,0 +1,28 @@
+export interface OrderLine {
+  price: number
+  quantity: number
+}
+
+export function orderTotal(lines: OrderLine[], discount: number): number {
+  let total = 0
+  for (const line of lines) {
+    if (line.quantity > 0) {
+      total += line.price * line.quantity
+    }
+  }
+  return Math.round(total * (1 - discount) * 100) / 100
+}
+
+export function orderLabel(id: string): string {
+  return `order-${id}`
+}
+
+export function validateOrder(lines: OrderLine[]): string[] {
+  const errors: string[] = []
+  lines.forEach((line, index) => {
+    if (line.price < 0) {
+      errors.push(`Line ${index} has a negative price`)
+    }
+  })
+  return errors
+}
\ No newline at end of file
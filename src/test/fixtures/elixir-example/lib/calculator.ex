@@ -39,4 +39,10 @@ defmodule Example.MathUtils do
   def fibonacci(0), do: 0
   def fibonacci(1), do: 1
   def fibonacci(n) when n > 1, do: fibonacci(n - 1) + fibonacci(n - 2)
+
+  defmacro unless_zero(value, do: block) do
+    quote do
+      if unquote(value) != 0, do: unquote(block)
+    end
+  end
 end
\ No newline at end of file
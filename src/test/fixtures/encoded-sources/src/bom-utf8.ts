@@ -0,0 +1,7 @@
+﻿export function bomGreeting(name: string): string {
+  return `Hello, ${name}`
+}
+
+export class BomLedger {
+  entries: string[] = []
+}
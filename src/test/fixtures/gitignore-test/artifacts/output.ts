@@ -0,0 +1 @@
+export const IgnoredArtifact = true
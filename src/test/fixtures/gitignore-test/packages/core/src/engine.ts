@@ -0,0 +1,3 @@
+export class CoreEngine {
+  start(): void {}
+}
@@ -0,0 +1 @@
+export function ignoredStaging(): void {}
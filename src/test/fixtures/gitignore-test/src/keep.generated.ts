@@ -0,0 +1,3 @@
+export interface KeptGenerated {
+  id: string
+}
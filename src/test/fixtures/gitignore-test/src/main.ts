@@ -0,0 +1,3 @@
+export class GitignoreMain {
+  run(): void {}
+}
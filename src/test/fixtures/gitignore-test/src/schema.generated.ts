@@ -0,0 +1,3 @@
+export interface IgnoredGenerated {
+  id: string
+}
@@ -0,0 +1,3 @@
+export function keptScratchTop(): number {
+  return 1
+}
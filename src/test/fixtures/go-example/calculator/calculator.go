@@ -0,0 +1,66 @@
+package calculator
+
+import "errors"
+
+// ErrDivisionByZero is returned when dividing by zero
+var ErrDivisionByZero = errors.New("division by zero")
+
+// MaxHistory caps how many operations are remembered
+const MaxHistory = 100
+
+// Calculator describes the basic arithmetic operations
+type Calculator interface {
+	Add(a, b float64) float64
+	Subtract(a, b float64) float64
+	Multiply(a, b float64) float64
+	Divide(a, b float64) (float64, error)
+}
+
+// BasicCalculator implements Calculator and records its history
+type BasicCalculator struct {
+	history []float64
+}
+
+// NewBasicCalculator creates an empty calculator
+func NewBasicCalculator() *BasicCalculator {
+	return &BasicCalculator{history: make([]float64, 0, MaxHistory)}
+}
+
+func (c *BasicCalculator) Add(a, b float64) float64 {
+	return c.record(a + b)
+}
+
+func (c *BasicCalculator) Subtract(a, b float64) float64 {
+	return c.record(a - b)
+}
+
+func (c *BasicCalculator) Multiply(a, b float64) float64 {
+	return c.record(a * b)
+}
+
+func (c *BasicCalculator) Divide(a, b float64) (float64, error) {
+	if b == 0 {
+		return 0, ErrDivisionByZero
+	}
+	return c.record(a / b), nil
+}
+
+func (c BasicCalculator) HistoryCount() int {
+	return len(c.history)
+}
+
+func (c *BasicCalculator) record(result float64) float64 {
+	if len(c.history) < MaxHistory {
+		c.history = append(c.history, result)
+	}
+	return result
+}
+
+// Stack is a generic LIFO used for operand storage
+type Stack[T any] struct {
+	items []T
+}
+
+func (s *Stack[T]) Push(item T) {
+	s.items = append(s.items, item)
+}
@@ -0,0 +1,28 @@
+package models
+
+// User is a registered account
+type User struct {
+	ID    int64
+	Name  string
+	Email string
+}
+
+// UserRepository stores users in memory
+type UserRepository struct {
+	users map[int64]User
+}
+
+// NewUserRepository creates an empty repository
+func NewUserRepository() *UserRepository {
+	return &UserRepository{users: make(map[int64]User)}
+}
+
+func (r *UserRepository) Save(user User) User {
+	r.users[user.ID] = user
+	return user
+}
+
+func (r *UserRepository) FindByID(id int64) (User, bool) {
+	user, ok := r.users[id]
+	return user, ok
+}
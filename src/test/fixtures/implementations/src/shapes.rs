@@ -0,0 +1,53 @@
+use std::fmt;
+
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+pub struct Circle {
+    pub radius: f64,
+}
+
+pub struct Square {
+    pub side: f64,
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        3.14159 * self.radius * self.radius
+    }
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.side * self.side
+    }
+}
+
+impl<T: Shape> Shape for Box<T> {
+    fn area(&self) -> f64 {
+        (**self).area()
+    }
+}
+
+impl fmt::Display for Circle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "circle({})", self.radius)
+    }
+}
+
+impl Circle {
+    pub fn new(radius: f64) -> Self {
+        Circle { radius }
+    }
+}
+
+pub trait Polygon: Shape {
+    fn sides(&self) -> u32;
+}
+
+impl Polygon for Square {
+    fn sides(&self) -> u32 {
+        4
+    }
+}
@@ -0,0 +1,46 @@
+export interface Store<T> {
+  get(id: string): T | undefined
+  put(id: string, value: T): void
+}
+
+export interface Disposable {
+  dispose(): void
+}
+
+export class MemoryStore<T> implements Store<T>, Disposable {
+  private items = new Map<string, T>()
+
+  get(id: string): T | undefined {
+    return this.items.get(id)
+  }
+
+  put(id: string, value: T): void {
+    this.items.set(id, value)
+  }
+
+  dispose(): void {
+    this.items.clear()
+  }
+}
+
+export class CounterStore implements Store<number> {
+  private counts: Record<string, number> = {}
+
+  get(id: string): number | undefined {
+    return this.counts[id]
+  }
+
+  put(id: string, value: number): void {
+    this.counts[id] = value
+  }
+}
+
+class Resource {
+  opened = true
+}
+
+export class FileHandle extends Resource implements Disposable {
+  dispose(): void {
+    this.opened = false
+  }
+}
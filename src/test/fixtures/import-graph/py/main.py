@@ -0,0 +1,7 @@
+import json
+
+import shop.orders
+
+
+def main():
+    print(json.dumps(shop.orders.summary()))
\ No newline at end of file
@@ -0,0 +1 @@
+"""Shop package"""
\ No newline at end of file
@@ -0,0 +1,5 @@
+from shop import tax
+
+
+def total(prices):
+    return tax.apply(sum(prices))
\ No newline at end of file
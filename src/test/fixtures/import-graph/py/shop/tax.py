@@ -0,0 +1,5 @@
+RATE = 0.2
+
+
+def apply(amount):
+    return amount * (1 + RATE)
\ No newline at end of file
@@ -0,0 +1,25 @@
+use std::collections::HashMap;
+
+use super::report::summary;
+
+pub struct Stock {
+    items: HashMap<String, u32>,
+}
+
+impl Stock {
+    pub fn new(items: HashMap<String, u32>) -> Self {
+        Stock { items }
+    }
+
+    pub fn add(&mut self, name: &str, count: u32) {
+        *self.items.entry(name.to_string()).or_insert(0) += count;
+    }
+
+    pub fn describe(&self) -> String {
+        summary(self)
+    }
+
+    pub fn total(&self) -> u32 {
+        self.items.values().sum()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+mod inventory;
+mod report;
+
+use std::collections::HashMap;
+
+use crate::inventory::Stock;
+
+fn main() {
+    let mut stock = Stock::new(HashMap::new());
+    stock.add("widget", 3);
+    println!("{}", report::summary(&stock));
+}
\ No newline at end of file
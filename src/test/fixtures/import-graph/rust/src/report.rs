@@ -0,0 +1,5 @@
+use crate::inventory::Stock;
+
+pub fn summary(stock: &Stock) -> String {
+    format!("{} items in stock", stock.total())
+}
\ No newline at end of file
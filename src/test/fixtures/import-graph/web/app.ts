@@ -0,0 +1,12 @@
+import express from 'express'
+import { Cart } from './cart.js'
+import { formatPrice } from './format.js'
+
+export function createApp() {
+  const app = express()
+  const cart = new Cart()
+  app.get('/total', (_req, res) => res.send(formatPrice(cart.total())))
+  return app
+}
+
+export type App = ReturnType<typeof createApp>
\ No newline at end of file
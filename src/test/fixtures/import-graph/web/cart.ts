@@ -0,0 +1,18 @@
+import type { App } from './app.js'
+import { formatPrice } from './format.js'
+
+export class Cart {
+  private items: number[] = []
+
+  total(): number {
+    return this.items.reduce((sum, item) => sum + item, 0)
+  }
+
+  label(): string {
+    return formatPrice(this.total())
+  }
+
+  mount(app: App): void {
+    app.locals.cart = this
+  }
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+export function formatPrice(value: number): string {
+  return `$${value.toFixed(2)}`
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+package com.example.utils
+
+const val MAX_LENGTH = 80
+
+val String.lastChar: Char
+    get() = this[length - 1]
+
+fun String.shout(): String = uppercase() + "!"
+
+fun String?.orEmptyText(): String = this ?: ""
+
+fun List<Int>.sumOfSquares(): Int {
+    val squares = map { it * it }
+    return squares.sum()
+}
+
+fun truncate(text: String): String = text.take(MAX_LENGTH)
\ No newline at end of file
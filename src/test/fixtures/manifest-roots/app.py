@@ -0,0 +1,2 @@
+def run_app():
+    return 'running'
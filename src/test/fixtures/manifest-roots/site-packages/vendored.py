@@ -0,0 +1,2 @@
+def vendored_helper():
+    return 'vendored'
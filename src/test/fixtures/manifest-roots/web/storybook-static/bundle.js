@@ -0,0 +1,3 @@
+export function bundledStory() {
+  return 'story'
+}
@@ -0,0 +1,76 @@
+using System;
+using System.Collections.Generic;
+
+namespace MultiLang.Calculator
+{
+    public interface ICalculator
+    {
+        double Add(double a, double b);
+        double Subtract(double a, double b);
+    }
+
+    public class Calculator : ICalculator
+    {
+        private readonly List<double> history = new List<double>();
+
+        public int HistoryCount { get; private set; }
+
+        public Calculator()
+        {
+            HistoryCount = 0;
+        }
+
+        public double Add(double a, double b)
+        {
+            return Record(a + b);
+        }
+
+        public double Subtract(double a, double b)
+        {
+            return Record(a - b);
+        }
+
+        public double Divide(double a, double b)
+        {
+            if (b == 0)
+            {
+                throw new DivideByZeroException("Division by zero");
+            }
+            return Record(a / b);
+        }
+
+        public List<TResult> Map<TResult>(Func<double, TResult> selector)
+        {
+            var results = new List<TResult>();
+            foreach (var value in history)
+            {
+                results.Add(selector(value));
+            }
+            return results;
+        }
+
+        private double Record(double value)
+        {
+            history.Add(value);
+            HistoryCount++;
+            return value;
+        }
+    }
+
+    public class ResultCache<TKey, TValue>
+    {
+        private readonly Dictionary<TKey, TValue> entries = new Dictionary<TKey, TValue>();
+
+        public int Count => entries.Count;
+
+        public TValue GetOrAdd(TKey key, Func<TKey, TValue> factory)
+        {
+            if (!entries.TryGetValue(key, out var value))
+            {
+                value = factory(key);
+                entries[key] = value;
+            }
+            return value;
+        }
+    }
+}
@@ -0,0 +1,5 @@
+export class NestedService {
+  describe(): string {
+    return 'nested'
+  }
+}
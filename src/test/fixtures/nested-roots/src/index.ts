@@ -0,0 +1,3 @@
+export function rootHelper(value: string): string {
+  return value.trim()
+}
@@ -0,0 +1,20 @@
+// applyDiscount is missing a closing parenthesis; everything around it is valid
+
+export function calculateTotal(items: number[]): number {
+  return items.reduce((sum, item) => sum + item, 0)
+}
+
+export function applyDiscount(total: number, rate: number): number {
+  const discounted = total * (1 - rate
+  return discounted
+}
+
+export class OrderFormatter {
+  format(total: number): string {
+    return `$${total.toFixed(2)}`
+  }
+}
+
+export function shippingCost(weight: number): number {
+  return weight * 1.5
+}
\ No newline at end of file
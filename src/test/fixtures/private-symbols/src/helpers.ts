@@ -0,0 +1,31 @@
+interface InternalShape {
+  width: number
+}
+
+function capitalize(value: string): string {
+  return value.charAt(0).toUpperCase() + value.slice(1)
+}
+
+function neverCalled(): void {
+  console.log('unused')
+}
+
+const unusedArrow = () => 'unused'
+
+export function formatName(name: string): string {
+  return capitalize(name.trim())
+}
+
+export class Greeter {
+  private names = new Map<string, string>()
+
+  greet(id: string): string {
+    return `Hello, ${this.lookup(id)}`
+  }
+
+  private lookup(id: string): string {
+    return this.names.get(id) ?? formatName(id)
+  }
+
+  private forgotten(): void {}
+}
\ No newline at end of file
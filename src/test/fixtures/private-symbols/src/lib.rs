@@ -0,0 +1,45 @@
+pub struct Config {
+    pub value: i32,
+}
+
+struct Orphan {
+    id: u32,
+}
+
+pub fn public_api(input: i32) -> i32 {
+    used_helper(input)
+}
+
+pub fn make_config() -> Config {
+    Config { value: 1 }
+}
+
+fn used_helper(input: i32) -> i32 {
+    input + 1
+}
+
+fn unused_helper() -> i32 {
+    42
+}
+
+fn countdown(n: u32) -> u32 {
+    if n == 0 { 0 } else { countdown(n - 1) }
+}
+
+fn main() {
+    println!("{}", public_api(1));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_value() -> i32 {
+        1
+    }
+
+    #[test]
+    fn adds_one() {
+        assert_eq!(used_helper(fixture_value()), 2);
+    }
+}
\ No newline at end of file
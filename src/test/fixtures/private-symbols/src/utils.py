@@ -0,0 +1,29 @@
+def _sum(values):
+    return sum(values)
+
+
+def _stale(values):
+    return len(values)
+
+
+def public_total(values):
+    return _sum(values)
+
+
+class _Unused:
+    def __init__(self):
+        self.items = []
+
+
+class Report:
+    def __init__(self, rows):
+        self.rows = rows
+
+    def render(self):
+        return "\n".join(self._format(row) for row in self.rows)
+
+    def _format(self, row):
+        return str(row)
+
+    def _render_row(self, row):
+        return f"<tr>{row}</tr>"
\ No newline at end of file
@@ -0,0 +1,53 @@
+"""
+Calculator with decorated methods, nested helpers, and route-style handlers
+"""
+
+import functools
+from dataclasses import dataclass
+from typing import Callable, List
+
+MAX_HISTORY = 100
+
+
+def logged(func: Callable) -> Callable:
+    @functools.wraps(func)
+    def wrapper(*args, **kwargs):
+        return func(*args, **kwargs)
+
+    return wrapper
+
+
+@dataclass
+class CalculationResult:
+    operation: str
+    value: float
+
+
+class Calculator:
+    def __init__(self):
+        self.history: List[CalculationResult] = []
+
+    @logged
+    def add(self, a: float, b: float) -> float:
+        return self._record('add', a + b)
+
+    def divide(self, a: float, b: float) -> float:
+        if b == 0:
+            raise ZeroDivisionError('division by zero')
+        return self._record('divide', a / b)
+
+    @property
+    def history_count(self) -> int:
+        return len(self.history)
+
+    @staticmethod
+    def square(value: float) -> float:
+        return value * value
+
+    def _record(self, operation: str, value: float) -> float:
+        def trim():
+            del self.history[:-MAX_HISTORY]
+
+        self.history.append(CalculationResult(operation, value))
+        trim()
+        return value
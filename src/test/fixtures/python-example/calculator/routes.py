@@ -0,0 +1,27 @@
+"""
+Route-style handlers built on a tiny registry
+"""
+
+ROUTES = {}
+
+
+class App:
+    def route(self, path: str):
+        def register(handler):
+            ROUTES[path] = handler
+            return handler
+
+        return register
+
+
+app = App()
+
+
+@app.route('/add')
+def add_handler(a: float, b: float) -> float:
+    return a + b
+
+
+@app.route('/health')
+def health_handler() -> str:
+    return 'ok'
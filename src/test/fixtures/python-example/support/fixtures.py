@@ -0,0 +1,19 @@
+"""
+Pytest-style fixtures for the calculator
+"""
+
+import pytest
+
+from calculator.core import Calculator
+
+
+@pytest.fixture
+def calculator() -> Calculator:
+    return Calculator()
+
+
+@pytest.fixture(scope='module')
+def seeded_calculator() -> Calculator:
+    calc = Calculator()
+    calc.add(1, 2)
+    return calc
@@ -0,0 +1,3 @@
+export function Counter({ count }) {
+  return <button type="button">{count}</button>
+}
\ No newline at end of file
@@ -0,0 +1,31 @@
+interface GreetingProps {
+  name: string
+  items: string[]
+}
+
+export function Greeting({ name, items }: GreetingProps) {
+  const title = formatTitle(name)
+  return (
+    <section className="greeting">
+      <h1>{title}</h1>
+      <ul>
+        {items.map(item => <li key={item}>{item}</li>)}
+      </ul>
+    </section>
+  )
+}
+
+export const Badge = ({ label }: { label: string }) => <span className="badge">{label}</span>
+
+export const Layout = ({ children }: { children: React.ReactNode }) => {
+  return <>{children}</>
+}
+
+export function formatTitle(name: string): string {
+  return `Hello, ${name}`
+}
+
+export function Capitalize(text: string): string {
+  const renderer = () => <b>{text}</b>
+  return text.toUpperCase()
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+import type { ChatProvider } from '../chat/provider'
+import { openSession } from '../chat/session'
+
+export function main(): void {
+  const provider: ChatProvider = openSession('default')
+  console.log(provider.describe())
+}
\ No newline at end of file
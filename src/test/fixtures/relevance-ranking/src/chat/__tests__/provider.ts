@@ -0,0 +1,5 @@
+import { ChatProvider } from '../provider'
+
+export function testDescribe(): boolean {
+  return new ChatProvider('test-model').describe() === 'chat via test-model'
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+export class ChatProvider {
+  constructor(private readonly model: string) {}
+
+  describe(): string {
+    return `chat via ${this.model}`
+  }
+}
\ No newline at end of file
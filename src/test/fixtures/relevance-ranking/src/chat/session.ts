@@ -0,0 +1,5 @@
+import { ChatProvider } from './provider'
+
+export function openSession(model: string): ChatProvider {
+  return new ChatProvider(model)
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+// Generated client stub; do not edit
+export class ChatProvider {
+  describe(): string {
+    return 'stub'
+  }
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+export function total(items: number[]): { count: number } {
+  let count = 0
+  for (const item of items) {
+    count += item
+  }
+  return { count }
+}
+
+export function report(count: number): string {
+  const label = (count: number) => `${count} items`
+  return label(count)
+}
+
+export class Tally {
+  increment(step: number): number {
+    return step + 1
+  }
+
+  twice(step: number): number {
+    return this.increment(this.increment(step))
+  }
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+import { total, Tally } from './counter.js'
+
+const summary = total([1, 2, 3])
+const tally = new Tally()
+console.log(summary.count, tally.increment(1))
\ No newline at end of file
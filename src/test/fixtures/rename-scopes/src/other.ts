@@ -0,0 +1,5 @@
+function total(values: number[]): number {
+  return values.length
+}
+
+console.log(total([]))
\ No newline at end of file
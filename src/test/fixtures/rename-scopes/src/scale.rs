@@ -0,0 +1,9 @@
+pub fn scale(value: i32) -> i32 {
+    let factor = 2;
+    let result = {
+        let factor = 10;
+        value * factor
+    };
+    let factor = factor + result;
+    factor * 3
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+def summarize(rows):
+    count = len(rows)
+
+    def describe(count):
+        return f"{count} rows"
+
+    count += 1
+    return describe(count)
\ No newline at end of file
@@ -1,4 +1,10 @@
 class Calculator
+  class << self
+    def default
+      new
+    end
+  end
+
   def add(a, b)
     a + b
   end
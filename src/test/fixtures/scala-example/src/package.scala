@@ -0,0 +1,19 @@
+package com.example
+
+package object utils {
+  val DefaultPrecision: Int = 2
+  var roundingEnabled = true
+
+  def round(value: Double): Double = {
+    val factor = math.pow(10, DefaultPrecision)
+    math.round(value * factor) / factor
+  }
+
+  object Formats {
+    val Currency = "%.2f"
+
+    object Iso {
+      def timestamp(millis: Long): String = java.time.Instant.ofEpochMilli(millis).toString
+    }
+  }
+}
\ No newline at end of file
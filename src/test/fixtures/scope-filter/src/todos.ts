@@ -0,0 +1,11 @@
+// TODO: persist todos instead of keeping them in memory
+export const TODO_PREFIX = 'TODO'
+
+export function collectTodos(lines: string[]): string[] {
+  /* TODO: support multi-line markers */
+  return lines.filter(line => line.includes(TODO_PREFIX))
+}
+
+export function describeTodo(text: string): string {
+  return `TODO item: ${text}`
+}
@@ -0,0 +1,97 @@
+import Foundation
+
+protocol Calculator {
+    var precision: Int { get set }
+    func add(_ a: Double, _ b: Double) -> Double
+    func subtract(_ a: Double, _ b: Double) -> Double
+}
+
+public class BasicCalculator: Calculator {
+    public var precision: Int = 2
+    private(set) var history: [Double] = [] {
+        didSet {
+            print("history has \(history.count) entries")
+        }
+    }
+
+    public init(precision: Int) {
+        self.precision = precision
+    }
+
+    public func add(_ a: Double, _ b: Double) -> Double {
+        return record(a + b)
+    }
+
+    public func subtract(_ a: Double, _ b: Double) -> Double {
+        return record(a - b)
+    }
+
+    func divide(_ a: Double, by b: Double) throws -> Double {
+        guard b != 0 else {
+            throw CalculatorError.divisionByZero
+        }
+        return record(a / b)
+    }
+
+    fileprivate func record(_ value: Double) -> Double {
+        let rounded = (value * 100).rounded() / 100
+        history.append(rounded)
+        return rounded
+    }
+
+    enum CalculatorError: Error {
+        case divisionByZero
+    }
+}
+
+struct Measurement {
+    var value: Double
+    var unit: Unit
+
+    var description: String {
+        get {
+            return "\(value) \(unit.symbol)"
+        }
+        set {
+            value = Double(newValue.split(separator: " ")[0]) ?? 0
+        }
+    }
+
+    struct Unit {
+        let symbol: String
+
+        var isMetric: Bool {
+            return symbol == "m" || symbol == "kg"
+        }
+    }
+}
+
+enum Operation {
+    case add
+    case subtract
+
+    var symbol: String {
+        switch self {
+        case .add:
+            return "+"
+        case .subtract:
+            return "-"
+        }
+    }
+
+    func apply(_ a: Double, _ b: Double) -> Double {
+        let calculator = BasicCalculator(precision: 2)
+        return self == .add ? calculator.add(a, b) : calculator.subtract(a, b)
+    }
+}
+
+extension BasicCalculator {
+    func multiply(_ a: Double, _ b: Double) -> Double {
+        return record(a * b)
+    }
+}
+
+func main() {
+    let calc = BasicCalculator(precision: 2)
+    print("5 + 3 = \(calc.add(5, 3))")
+}
\ No newline at end of file
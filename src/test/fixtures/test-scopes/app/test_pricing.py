@@ -0,0 +1,5 @@
+from pricing import compute_total
+
+
+def test_compute_total():
+    assert compute_total([1, 2]) == 3
\ No newline at end of file
@@ -0,0 +1,17 @@
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+pub fn double(a: i32) -> i32 {
+    add(a, a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_numbers() {
+        assert_eq!(add(2, 3), 5);
+    }
+}
\ No newline at end of file
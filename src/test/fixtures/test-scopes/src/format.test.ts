@@ -0,0 +1,8 @@
+import { describe, it, expect } from 'vitest'
+import { formatTotal } from './format'
+
+describe('formatTotal', () => {
+  it('formats cents', () => {
+    expect(formatTotal(1.5)).toBe('$1.50')
+  })
+})
\ No newline at end of file
@@ -0,0 +1,3 @@
+export function formatTotal(total: number): string {
+  return `$${total.toFixed(2)}`
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+use test_scopes::add;
+
+#[test]
+fn adds_from_outside() {
+    assert_eq!(add(1, 1), 2);
+}
\ No newline at end of file
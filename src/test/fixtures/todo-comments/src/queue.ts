@@ -0,0 +1,18 @@
+// TODO(alice): replace the array with a ring buffer
+export class JobQueue {
+  private jobs: string[] = []
+
+  push(job: string): void {
+    // FIXME @bob: reject duplicate jobs
+    this.jobs.push(job)
+  }
+
+  /*
+   * HACK: drain synchronously until workers exist
+   * NOTE: not a tracked tag
+   */
+  drain(): string[] {
+    const label = 'TODO: this string is not a comment'
+    return this.jobs.splice(0).concat(label ? [] : [])
+  }
+}
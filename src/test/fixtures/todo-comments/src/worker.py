@@ -0,0 +1,9 @@
+# XXX retries are not bounded
+def run(job):
+    # TODO: log job timings
+    return job
+
+
+# PERF(carol): batch the writes
+def flush(jobs):
+    return list(jobs)
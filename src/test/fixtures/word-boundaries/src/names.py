@@ -0,0 +1,2 @@
+def get_user_name(user_id):
+    return str(user_id)
\ No newline at end of file
@@ -0,0 +1,31 @@
+export function add(a: number, b: number): number {
+  return a + b
+}
+
+export function address(user: string): string {
+  return `${user}@example.com`
+}
+
+export function padding(width: number): string {
+  return ' '.repeat(width)
+}
+
+export function addUser(name: string): string {
+  return name
+}
+
+export function getUser(id: number): string {
+  return String(id)
+}
+
+export function username(): string {
+  return 'admin'
+}
+
+export class HTTPServer {
+  listen(): void {}
+}
+
+export function größe(): number {
+  return 0
+}
\ No newline at end of file
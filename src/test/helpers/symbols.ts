@@ -0,0 +1,13 @@
+/**
+ * Symbol outline lookups shared by the language support tests
+ */
+
+import type { DocumentSymbol } from '../../types/core.js'
+
+export function findSymbol(symbols: DocumentSymbol[], name: string): DocumentSymbol | undefined {
+  return symbols.find(s => s.name === name)
+}
+
+export function childNames(symbols: DocumentSymbol[], name: string): string[] {
+  return findSymbol(symbols, name)?.children.map(s => s.name) || []
+}
\ No newline at end of file
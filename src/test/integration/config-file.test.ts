@@ -0,0 +1,75 @@
+/**
+ * Project config file tests - .tree-sitter-mcp.toml parsing, validation and precedence
+ */
+
+import { describe, it, expect, afterEach } from 'vitest'
+import { resolve } from 'path'
+import { parseConfig, loadConfig, applyGlobalConfig } from '../../project/config.js'
+import { createPersistentManager, getOrCreateProject } from '../../project/persistent-manager.js'
+import { getMaxFileSize, setMaxFileSize } from '../../core/file-walker.js'
+import { getCacheDirectory, setCacheDirectory } from '../../project/index-cache.js'
+import { MEMORY_LIMITS } from '../../constants/persistence.js'
+
+describe('Project Config File', () => {
+  const fixtureDir = resolve(import.meta.dirname, '../fixtures/config-file')
+  const cacheDirEnv = process.env.TREE_SITTER_MCP_CACHE_DIR
+
+  afterEach(() => {
+    setMaxFileSize(MEMORY_LIMITS.MAX_FILE_SIZE_BYTES)
+    setCacheDirectory(undefined)
+    if (cacheDirEnv !== undefined) process.env.TREE_SITTER_MCP_CACHE_DIR = cacheDirEnv
+  })
+
+  it('should read the fixture config', () => {
+    expect(loadConfig(fixtureDir)).toEqual({
+      include: ['src/**'],
+      exclude: ['src/generated/**'],
+      languages: ['typescript'],
+    })
+    expect(loadConfig(resolve(fixtureDir, 'src'))).toEqual({})
+  })
+
+  it('should reject unknown keys, wrong types and unknown languages', () => {
+    expect(() => parseConfig('exlude = ["dist/**"]')).toThrow('Unknown key "exlude"')
+    expect(() => parseConfig('threads = "4"')).toThrow('"threads" in .tree-sitter-mcp.toml must be a non-negative integer')
+    expect(() => parseConfig('include = "src/**"')).toThrow('must be an array of strings')
+    expect(() => parseConfig('languages = ["cobol"]')).toThrow('Unknown language: cobol')
+    expect(() => parseConfig('[cache]\ndir = "x"')).toThrow('Unknown key "cache"')
+    expect(() => parseConfig('threads = ')).toThrow('Invalid .tree-sitter-mcp.toml: Line 1')
+  })
+
+  it('should read language overrides as a table of globs', () => {
+    expect(parseConfig('[languageOverrides]\n"include/**/*.h" = "CPP"\n"bin/*" = "bash"')).toEqual({
+      languageOverrides: { 'include/**/*.h': 'cpp', 'bin/*': 'bash' },
+    })
+    expect(() => parseConfig('languageOverrides = ["cpp"]')).toThrow('"languageOverrides" in .tree-sitter-mcp.toml must be a table of strings')
+    expect(() => parseConfig('[languageOverrides]\n"*.h" = "cobol"')).toThrow('Unknown language: cobol')
+  })
+
+  it('should apply process-wide settings relative to the config directory', () => {
+    delete process.env.TREE_SITTER_MCP_CACHE_DIR
+    applyGlobalConfig(parseConfig('maxFileSize = 1024\ncacheDir = ".cache/index"'), '/repo')
+
+    expect(getMaxFileSize()).toBe(1024)
+    expect(getCacheDirectory()).toBe(resolve('/repo', '.cache/index'))
+  })
+
+  it('should index only the files the config selects', async () => {
+    const project = await getOrCreateProject(createPersistentManager(), { directory: fixtureDir })
+    const files = Array.from(project.files.keys()).map(path => path.slice(fixtureDir.length + 1)).sort()
+
+    expect(files).toEqual(['src/app.ts', 'src/format.ts'])
+    expect(project.config.languages).toEqual(['typescript'])
+  })
+
+  it('should let settings passed by the caller override the file', async () => {
+    const project = await getOrCreateProject(createPersistentManager(), {
+      directory: fixtureDir,
+      languages: ['python'],
+      include: ['**'],
+    })
+    const files = Array.from(project.files.keys()).map(path => path.slice(fixtureDir.length + 1))
+
+    expect(files).toEqual(['src/helpers.py'])
+  })
+})
\ No newline at end of file
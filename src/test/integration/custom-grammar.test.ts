@@ -0,0 +1,57 @@
+/**
+ * Custom grammar tests - languages registered at runtime from a compiled grammar and a node-kind mapping
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { parseConfig, loadConfig, applyGlobalConfig } from '../../project/config.js'
+import { createPersistentManager, getOrCreateProject } from '../../project/persistent-manager.js'
+import { getAllFiles } from '../../project/manager.js'
+import { getLanguageByExtension, getLanguageSetup, loadGrammar, registerLanguage } from '../../core/languages.js'
+import { searchCode } from '../../core/search.js'
+import { listSymbols } from '../../core/symbols.js'
+
+describe('Custom Grammars', () => {
+  const fixtureDir = resolve(import.meta.dirname, '../fixtures/custom-grammar')
+  const pythonGrammarDir = resolve(import.meta.dirname, '../../../node_modules/tree-sitter-python')
+
+  it('should read grammar tables from the config file', () => {
+    expect(loadConfig(fixtureDir)).toEqual({
+      grammars: {
+        starlark: {
+          path: '../../../../node_modules/tree-sitter-python',
+          extensions: ['.star', '.bzl'],
+          kinds: { function_definition: 'function' },
+        },
+      },
+    })
+    expect(parseConfig('languages = ["starlark"]\n[grammars.starlark]\npath = "x"\nextensions = []\n[grammars.starlark.kinds]').languages).toEqual(['starlark'])
+    expect(() => parseConfig('[grammars.starlark]\npath = "x"')).toThrow('"grammars" in .tree-sitter-mcp.toml must be a table of grammars')
+  })
+
+  it('should index, search and outline files of a registered language', async () => {
+    applyGlobalConfig(loadConfig(fixtureDir), fixtureDir)
+    expect(getLanguageByExtension('.bzl')?.name).toBe('starlark')
+
+    const project = await getOrCreateProject(createPersistentManager(), { directory: fixtureDir })
+    const files = getAllFiles(project)
+    const build = files.find(file => file.path.endsWith('BUILD.star'))!
+
+    expect(searchCode('cc_binary', files).map(result => result.node.name)).toContain('cc_binary_with_tests')
+    expect(listSymbols(build).map(symbol => [symbol.name, symbol.kind])).toEqual([
+      ['cc_binary_with_tests', 'function'],
+      ['py_library_set', 'function'],
+    ])
+    expect(getLanguageSetup().grammars).toEqual([expect.objectContaining({ name: 'starlark', path: pythonGrammarDir })])
+  })
+
+  it('should reject clashing registrations and unusable grammars', () => {
+    const grammar = loadGrammar(pythonGrammarDir)
+
+    expect(() => registerLanguage({ name: 'Python', extensions: ['.pyx'], grammar, definitionKinds: {} })).toThrow('built in and cannot be replaced')
+    expect(() => registerLanguage({ name: 'cython', extensions: ['py'], grammar, definitionKinds: {} })).toThrow('Extension .py already belongs to python')
+    expect(() => registerLanguage({ name: 'cython', extensions: ['.pyx'], grammar, definitionKinds: { function_definition: 'routine' } })).toThrow('Unknown symbol kind "routine"')
+    expect(() => registerLanguage({ name: 'broken', extensions: ['.brk'], grammar: { language: {} }, definitionKinds: {} })).toThrow('Grammar for broken is incompatible')
+    expect(() => loadGrammar(resolve(fixtureDir, 'missing.node'))).toThrow('Cannot load grammar')
+  })
+})
\ No newline at end of file
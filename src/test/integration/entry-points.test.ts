@@ -0,0 +1,70 @@
+/**
+ * CLI and MCP entry points - both run the shared query engine, so the same query gives the same answer
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { spawnSync } from 'child_process'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('CLI and MCP entry points', () => {
+  const fixture = resolve(import.meta.dirname, '../fixtures/minimal-positive')
+  const cliPath = resolve(import.meta.dirname, '../../cli.ts')
+
+  function runCLI(args: string[]): any {
+    const result = spawnSync('npx', ['tsx', cliPath, ...args, '--output', 'json'], {
+      encoding: 'utf8',
+      stdio: 'pipe',
+    })
+    // Log lines come before the JSON document
+    const lines = result.stdout.split('\n')
+    return JSON.parse(lines.slice(lines.findIndex(line => line.startsWith('{'))).join('\n'))
+  }
+
+  async function callTool(name: string, args: JsonObject): Promise<any> {
+    const result = await handleToolRequest({ params: { name, arguments: args } })
+    return JSON.parse(result.content[0]!.text)
+  }
+
+  const summarize = (results: any[]) => results.map(r => ({ path: r.path, name: r.name, type: r.type, startLine: r.startLine, score: r.score }))
+
+  it('should return the same search results and totals', async () => {
+    const cli = runCLI(['search', 'User', '-d', fixture, '--max-results', '3', '--sort', 'path'])
+    const mcp = await callTool('search_code', { query: 'User', directory: fixture, maxResults: 3, sort: 'path' })
+
+    expect(cli.results.length).toBeGreaterThan(0)
+    expect(summarize(cli.results)).toEqual(summarize(mcp.results))
+    expect(cli.totalResults).toBe(mcp.totalResults)
+  })
+
+  it('should return the same usages', async () => {
+    const cli = runCLI(['find-usage', 'TestUser', '-d', fixture, '--exact'])
+    const mcp = await callTool('find_usage', { identifier: 'TestUser', directory: fixture, exactMatch: true })
+    const locate = (usages: any[]) => usages.map(u => ({ path: u.path, startLine: u.startLine, startColumn: u.startColumn, context: u.context }))
+
+    expect(cli.totalUsages).toBeGreaterThan(0)
+    expect(locate(cli.usages)).toEqual(locate(mcp.usages))
+    expect(cli.totalUsages).toBe(mcp.totalUsages)
+  })
+
+  it('should reject an invalid sort with the same message', async () => {
+    const cli = runCLI(['search', 'User', '-d', fixture, '--sort', 'popularity'])
+    const mcp = await callTool('search_code', { query: 'User', directory: fixture, sort: 'popularity' })
+      .catch((error: Error) => ({ message: error.message }))
+
+    expect(cli.error).toBe(true)
+    expect(cli.message).toMatch(/^Invalid sort: popularity/)
+    expect(mcp.message).toBe(cli.message)
+  })
+
+  it('should reject an unknown mode with the same message for projects and buffers', async () => {
+    const project = await callTool('search_code', { query: 'User', directory: fixture, mode: 'exact' })
+      .catch((error: Error) => ({ message: error.message }))
+    const buffer = await callTool('search_buffer', { query: 'User', content: 'class User {}', language: 'typescript', mode: 'exact' })
+      .catch((error: Error) => ({ message: error.message }))
+
+    expect(project.message).toBe('Invalid mode: exact. Expected one of: name, regex, fuzzy, structural, keyPath')
+    expect(buffer.message).toBe(project.message)
+  })
+})
\ No newline at end of file
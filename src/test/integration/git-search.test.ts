@@ -0,0 +1,101 @@
+/**
+ * Git-aware search tests - changedSince scoping and per-line blame on search_code results
+ */
+
+import { describe, it, expect, beforeAll, afterAll } from 'vitest'
+import { execFileSync } from 'child_process'
+import { mkdtempSync, mkdirSync, writeFileSync, rmSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+import { createBlameLookup, getChangedFiles } from '../../core/git.js'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('Git-Aware Search', () => {
+  let repoDir: string
+  let plainDir: string
+
+  const git = (...args: string[]) => execFileSync('git', ['-c', 'user.name=Test Author', '-c', 'user.email=test@example.com', ...args], { cwd: repoDir, stdio: 'pipe' })
+
+  async function search(args: JsonObject) {
+    const result = await handleToolRequest({ params: { name: 'search_code', arguments: { query: 'format', ...args } } })
+    return JSON.parse(result.content[0].text)
+  }
+
+  beforeAll(() => {
+    repoDir = mkdtempSync(join(tmpdir(), 'tsmcp-git-'))
+    plainDir = mkdtempSync(join(tmpdir(), 'tsmcp-nogit-'))
+    mkdirSync(join(repoDir, 'src'))
+    writeFileSync(join(repoDir, 'src/stable.ts'), 'export function formatStable() {}\n')
+    writeFileSync(join(repoDir, 'src/edited.ts'), 'export function formatEdited() {}\n')
+    writeFileSync(join(repoDir, '.gitignore'), 'ignored.ts\n')
+    git('init', '-q', '-b', 'main')
+    git('add', '-A')
+    git('commit', '-q', '-m', 'base')
+
+    git('checkout', '-q', '-b', 'feature')
+    writeFileSync(join(repoDir, 'src/edited.ts'), 'export function formatEdited(value: string) { return value }\n')
+    writeFileSync(join(repoDir, 'src/added.ts'), 'export function formatAdded() {}\n')
+    git('add', '-A')
+    git('commit', '-q', '-m', 'feature work')
+    writeFileSync(join(repoDir, 'src/untracked.ts'), 'export function formatUntracked() {}\n')
+    writeFileSync(join(repoDir, 'src/ignored.ts'), 'export function formatIgnored() {}\n')
+    writeFileSync(join(plainDir, 'plain.ts'), 'export function formatPlain() {}\n')
+  })
+
+  afterAll(() => {
+    rmSync(repoDir, { recursive: true, force: true })
+    rmSync(plainDir, { recursive: true, force: true })
+  })
+
+  it('should list committed, uncommitted and untracked changes since the merge base', () => {
+    const changed = Array.from(getChangedFiles(repoDir, 'main')).map(path => path.slice(repoDir.length + 1)).sort()
+    expect(changed).toEqual(['src/added.ts', 'src/edited.ts', 'src/untracked.ts'])
+
+    const tracked = getChangedFiles(repoDir, 'main', { includeUntracked: false })
+    expect(tracked.has(join(repoDir, 'src/untracked.ts'))).toBe(false)
+  })
+
+  it('should only search changed files', async () => {
+    const names = (await search({ directory: repoDir, changedSince: 'main', maxResults: 20 })).results.map((r: any) => r.name)
+
+    expect(names).toEqual(expect.arrayContaining(['formatEdited', 'formatAdded', 'formatUntracked']))
+    expect(names).not.toContain('formatStable')
+    expect(names).not.toContain('formatIgnored')
+
+    const tracked = (await search({ directory: repoDir, changedSince: 'main', includeUntracked: false, maxResults: 20 })).results.map((r: any) => r.name)
+    expect(tracked).not.toContain('formatUntracked')
+  })
+
+  it('should report an empty change set instead of searching everything', async () => {
+    const content = await search({ directory: repoDir, changedSince: 'HEAD', includeUntracked: false })
+
+    expect(content.results).toEqual([])
+    expect(content.message).toContain('changedSince: HEAD')
+  })
+
+  it('should reject projects outside git and unknown refs', async () => {
+    await expect(search({ directory: plainDir, changedSince: 'main' })).rejects.toThrow('is not inside a git repository')
+    await expect(search({ directory: repoDir, changedSince: 'no-such-branch' })).rejects.toThrow('Unknown git ref')
+    await expect(search({ directory: repoDir, changedSince: '' })).rejects.toThrow('changedSince must be a non-empty git ref')
+  })
+
+  it('should blame result lines and mark untracked files as dirty', async () => {
+    const head = execFileSync('git', ['rev-parse', '--short=7', 'HEAD'], { cwd: repoDir, encoding: 'utf-8' }).trim()
+    const results = (await search({ directory: repoDir, blame: true, maxResults: 20 })).results
+    const byName = new Map(results.map((r: any) => [r.name, r.blame]))
+
+    expect(byName.get('formatAdded')).toEqual({ author: 'Test Author', commit: head })
+    expect(byName.get('formatUntracked')).toEqual({ author: null, commit: null, dirty: true })
+    expect((await search({ directory: repoDir })).results[0].blame).toBeUndefined()
+  })
+
+  it('should blame each file once', () => {
+    const blameLine = createBlameLookup(repoDir)
+    const first = blameLine(join(repoDir, 'src/stable.ts'), 1)
+
+    expect(first.author).toBe('Test Author')
+    expect(blameLine(join(repoDir, 'src/stable.ts'), 1)).toBe(first)
+    expect(() => createBlameLookup(plainDir)).toThrow('is not inside a git repository')
+  })
+})
\ No newline at end of file
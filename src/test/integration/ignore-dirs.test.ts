@@ -435,4 +435,79 @@ describe('Selective Path Ignores', () => {
       })
     })
   })
+
+  describe('Nested .gitignore rules', () => {
+    const gitignoreFixture = resolve(fixturesDir, 'gitignore-test')
+
+    async function findNames(query: string, extra: JsonObject = {}): Promise<string[]> {
+      const result = await handleToolRequest({
+        params: {
+          name: 'search_code',
+          arguments: { query, directory: gitignoreFixture, exactMatch: true, ...extra },
+        },
+      })
+      const content = JSON.parse(result.content[0].text)
+      return content.results.map((r: any) => r.name)
+    }
+
+    it('should exclude files matched by root and nested .gitignore files', async () => {
+      expect(await findNames('IgnoredGenerated')).toHaveLength(0)
+      expect(await findNames('IgnoredArtifact')).toHaveLength(0)
+      expect(await findNames('ignoredScratch')).toHaveLength(0)
+      expect(await findNames('ignoredStaging')).toHaveLength(0)
+    }, TEST_TIMEOUT)
+
+    it('should keep negated and out-of-scope matches', async () => {
+      expect(await findNames('GitignoreMain')).toContain('GitignoreMain')
+      expect(await findNames('KeptGenerated')).toContain('KeptGenerated')
+      expect(await findNames('keptScratchTop')).toContain('keptScratchTop')
+      expect(await findNames('anchoredStagingHelper')).toContain('anchoredStagingHelper')
+    }, TEST_TIMEOUT)
+
+    it('should compose with explicit ignore directories', async () => {
+      expect(await findNames('CoreEngine')).toContain('CoreEngine')
+      expect(await findNames('CoreEngine', { ignoreDirs: ['packages'] })).toHaveLength(0)
+      expect(await findNames('IgnoredGenerated', { ignoreDirs: ['packages'] })).toHaveLength(0)
+    }, TEST_TIMEOUT)
+  })
+
+  describe('Default excludes', () => {
+    async function findNames(query: string, extra: JsonObject = {}): Promise<string[]> {
+      const result = await handleToolRequest({
+        params: {
+          name: 'search_code',
+          arguments: { query, directory: ignoreTestFixture, exactMatch: true, ...extra },
+        },
+      })
+      const content = JSON.parse(result.content[0].text)
+      return content.results.map((r: any) => r.name)
+    }
+
+    it('should skip vendored directories by default', async () => {
+      expect(await findNames('vendorFunction')).toHaveLength(0)
+      expect(await findNames('vendorFunction', { useDefaultExcludes: true })).toHaveLength(0)
+    }, TEST_TIMEOUT)
+
+    it('should index vendored directories with useDefaultExcludes false', async () => {
+      expect(await findNames('vendorFunction', { useDefaultExcludes: false })).toContain('vendorFunction')
+      expect(await findNames('vendorFunction', { useDefaultExcludes: false, ignoreDirs: ['vendor'] })).toHaveLength(0)
+    }, TEST_TIMEOUT)
+
+    it('should reject a non-boolean useDefaultExcludes', async () => {
+      await expect(findNames('vendorFunction', { useDefaultExcludes: 'no' })).rejects.toThrow('useDefaultExcludes must be a boolean')
+    }, TEST_TIMEOUT)
+
+    it('should index vendored directories with --no-default-excludes', () => {
+      const search = (flags: string[]) => extractJSONFromOutput(runCLI([
+        'search', 'vendorFunction',
+        '--directory', ignoreTestFixture,
+        '--exact',
+        '--output', 'json',
+        ...flags,
+      ]).stdout)
+
+      expect(search([]).results).toHaveLength(0)
+      expect(search(['--no-default-excludes']).results.map((r: any) => r.name)).toContain('vendorFunction')
+    }, TEST_TIMEOUT)
+  })
 })
\ No newline at end of file
@@ -0,0 +1,132 @@
+/**
+ * On-disk index cache tests - reuse of unchanged files, invalidation, and lazy syntax trees
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest'
+import { mkdtempSync, mkdirSync, writeFileSync, readFileSync, rmSync, existsSync, utimesSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+import { createProject, loadOrBuildIndex, type IndexProgress } from '../../project/manager.js'
+import { getCacheFilePath, getCachedFile, loadIndexCache, stampFile, storeCachedFile } from '../../project/index-cache.js'
+import { parseFile } from '../../core/parser.js'
+import { findDefinition } from '../../core/definitions.js'
+import { INDEX_CACHE_CONFIG } from '../../constants/persistence.js'
+
+describe('Index Cache', () => {
+  let workDir: string
+  let projectDir: string
+  let previousCacheDir: string | undefined
+
+  beforeEach(() => {
+    workDir = mkdtempSync(join(tmpdir(), 'tsmcp-cache-'))
+    projectDir = join(workDir, 'project')
+    mkdirSync(join(projectDir, 'src'), { recursive: true })
+    writeFileSync(join(projectDir, 'src/greeter.ts'), 'export class Greeter {\n  greet(): string {\n    return "hi"\n  }\n}\n')
+    writeFileSync(join(projectDir, 'src/math.ts'), 'export function addNumbers(a: number, b: number): number {\n  return a + b\n}\n')
+
+    previousCacheDir = process.env[INDEX_CACHE_CONFIG.DIR_ENV]
+    process.env[INDEX_CACHE_CONFIG.DIR_ENV] = join(workDir, 'cache')
+  })
+
+  afterEach(() => {
+    if (previousCacheDir === undefined) {
+      delete process.env[INDEX_CACHE_CONFIG.DIR_ENV]
+    }
+    else {
+      process.env[INDEX_CACHE_CONFIG.DIR_ENV] = previousCacheDir
+    }
+    rmSync(workDir, { recursive: true, force: true })
+  })
+
+  async function index() {
+    return loadOrBuildIndex(createProject({ directory: projectDir }))
+  }
+
+  function elementNames(project: Awaited<ReturnType<typeof index>>): string[] {
+    return Array.from(project.nodes.values()).flat().map(n => n.name).filter((n): n is string => Boolean(n))
+  }
+
+  it('should write a cache file with an entry per parsed file', async () => {
+    await index()
+
+    const cachePath = getCacheFilePath(projectDir)
+    expect(existsSync(cachePath)).toBe(true)
+
+    const cache = loadIndexCache(projectDir)
+    expect(Object.keys(cache.files).sort()).toEqual([
+      join(projectDir, 'src/greeter.ts'),
+      join(projectDir, 'src/math.ts'),
+    ])
+  })
+
+  it('should restore the same elements from the cache', async () => {
+    const fresh = await index()
+    const cached = await index()
+
+    expect(elementNames(cached).sort()).toEqual(elementNames(fresh).sort())
+  })
+
+  it('should rebuild syntax trees lazily for cached files', async () => {
+    await index()
+    const cached = await index()
+
+    const definitions = findDefinition('Greeter', Array.from(cached.files.values()))
+    expect(definitions).toHaveLength(1)
+    expect(definitions[0]!.kind).toBe('class')
+  })
+
+  it('should re-parse files whose content changed', async () => {
+    await index()
+    writeFileSync(join(projectDir, 'src/math.ts'), 'export function multiplyNumbers(a: number, b: number): number {\n  return a * b\n}\n')
+
+    const project = await index()
+    const names = elementNames(project)
+    expect(names).toContain('multiplyNumbers')
+    expect(names).not.toContain('addNumbers')
+  })
+
+  it('should not serve a file edited while it was parsed', async () => {
+    const mathPath = join(projectDir, 'src/math.ts')
+    const cache = loadIndexCache(projectDir)
+    const stamp = stampFile(mathPath)!
+
+    // The edit lands after the stamp but before the parse reads the file
+    writeFileSync(mathPath, 'export function multiplyNumbers(a: number, b: number): number {\n  return a * b\n}\n')
+    utimesSync(mathPath, new Date(), new Date(stamp.mtimeMs + 5000))
+    storeCachedFile(mathPath, await parseFile(mathPath), cache, stamp)
+
+    expect(getCachedFile(mathPath, cache)).toBeUndefined()
+  })
+
+  it('should drop entries for deleted files', async () => {
+    await index()
+    rmSync(join(projectDir, 'src/math.ts'))
+    await index()
+
+    const cache = loadIndexCache(projectDir)
+    expect(Object.keys(cache.files)).toEqual([join(projectDir, 'src/greeter.ts')])
+  })
+
+  it('should discard caches written with a different version tag', async () => {
+    await index()
+
+    const cachePath = getCacheFilePath(projectDir)
+    const stored = JSON.parse(readFileSync(cachePath, 'utf-8'))
+    writeFileSync(cachePath, JSON.stringify({ ...stored, version: 'stale' }))
+
+    expect(Object.keys(loadIndexCache(projectDir).files)).toHaveLength(0)
+  })
+
+  it('should report cached files as progress too', async () => {
+    await index()
+
+    const progress: IndexProgress[] = []
+    await loadOrBuildIndex(createProject({ directory: projectDir }), { onProgress: update => progress.push(update) })
+
+    expect(progress.map(update => `${update.filesDone}/${update.totalFiles}`)).toEqual(['1/2', '2/2'])
+    expect(progress.map(update => update.currentPath).sort()).toEqual([
+      join(projectDir, 'src/greeter.ts'),
+      join(projectDir, 'src/math.ts'),
+    ])
+  })
+})
\ No newline at end of file
@@ -10,6 +10,7 @@ import { getOrCreateProject } from '../../project/persistent-manager.js'
 import { createPersistentManager } from '../../project/persistent-manager.js'
 import { searchCode, findUsage } from '../../core/search.js'
 import { analyzeProject } from '../../analysis/index.js'
+import type { IndexProgress } from '../../project/manager.js'
 import type { TreeNode, Project } from '../../types/core.js'
 
 const TEST_FIXTURE_PATH = join(process.cwd(), 'src/test/fixtures/mixed-monorepo')
@@ -357,6 +358,26 @@ module.exports = { compiled: true }`)
     expect(subProjectDirs).toContain('client')
   })
 
+  it('should report index progress across sub-projects with the total known up front', async () => {
+    const progress: IndexProgress[] = []
+    const project = await getOrCreateProject(
+      persistentManager,
+      {
+        directory: TEST_FIXTURE_PATH,
+        languages: [],
+      },
+      'mixed-monorepo-progress-test',
+      { onProgress: update => progress.push(update) },
+    )
+
+    const indexedFiles = project.subProjects!.reduce((total, subProject) => total + subProject.files.size, 0)
+    expect(indexedFiles).toBeGreaterThan(0)
+    expect(progress).toHaveLength(indexedFiles)
+    expect(progress.every(update => update.totalFiles === indexedFiles)).toBe(true)
+    expect(progress.map(update => update.filesDone)).toEqual(progress.map((_, index) => index + 1))
+    expect(new Set(progress.map(update => update.currentPath)).size).toBe(indexedFiles)
+  })
+
   it('should parse only legitimate source files, ignoring node_modules and build artifacts', async () => {
     const project = await getOrCreateProject(
       persistentManager,
@@ -0,0 +1,54 @@
+/**
+ * Parallel parsing benchmark - indexes the multi-lang fixture across workers and checks results match one thread
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { findProjectFiles } from '../../core/file-walker.js'
+import { parseFilesInParallel, parseFilesSequentially } from '../../core/parse-pool.js'
+import { findDefinition } from '../../core/definitions.js'
+import type { TreeNode } from '../../types/core.js'
+
+describe('Parallel Parsing', () => {
+  const multiLangFixture = resolve(import.meta.dirname, '../fixtures/multi-lang')
+
+  function summarize(files: Map<string, TreeNode>): string[] {
+    const entries: string[] = []
+    for (const [filePath, fileNode] of files) {
+      for (const child of fileNode.children || []) {
+        entries.push(`${filePath}:${child.type}:${child.name}:${child.startLine}-${child.endLine}`)
+      }
+    }
+    return entries.sort()
+  }
+
+  it('should produce the same elements as sequential parsing', async () => {
+    const files = await findProjectFiles(multiLangFixture)
+    expect(files.length).toBeGreaterThan(1)
+
+    const sequential = await parseFilesSequentially(files)
+    const parallel = await parseFilesInParallel(files, 4)
+
+    expect(Array.from(parallel.keys())).toEqual(Array.from(sequential.keys()))
+    expect(summarize(parallel)).toEqual(summarize(sequential))
+  }, 60000)
+
+  it('should keep syntax-tree features working on worker-parsed files', async () => {
+    const files = await findProjectFiles(multiLangFixture)
+    const parallel = await parseFilesInParallel(files, 2)
+
+    const definitions = findDefinition('Calculator', Array.from(parallel.values()))
+    expect(definitions.some(d => d.path.endsWith('lib.rs'))).toBe(true)
+  }, 60000)
+
+  it('should terminate workers and reject when cancelled', async () => {
+    const files = await findProjectFiles(multiLangFixture)
+    const controller = new AbortController()
+
+    const parsing = parseFilesInParallel(files, 2, controller.signal)
+    controller.abort()
+
+    await expect(parsing).rejects.toMatchObject({ code: 'CANCELLED' })
+    await expect(parseFilesSequentially(files, controller.signal)).rejects.toMatchObject({ code: 'CANCELLED' })
+  }, 60000)
+})
\ No newline at end of file
@@ -0,0 +1,72 @@
+/**
+ * Path case tests - with ignoreCaseInPaths, paths and globs differing from the disk only in case name the same files
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest'
+import { mkdtempSync, mkdirSync, writeFileSync, rmSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+import { createProject, getAllFiles, parseProject, resolveProjectPath, updateProject } from '../../project/manager.js'
+import { toDiskCase } from '../../core/path-case.js'
+import { handleToolRequest } from '../../mcp/handlers.js'
+
+describe('Path Case', () => {
+  let projectDir: string
+
+  beforeEach(() => {
+    projectDir = mkdtempSync(join(tmpdir(), 'tsmcp-case-'))
+    mkdirSync(join(projectDir, 'src'))
+    mkdirSync(join(projectDir, 'Generated'))
+    writeFileSync(join(projectDir, 'src/Index.ts'), 'export function startServer() {}\n')
+    writeFileSync(join(projectDir, 'Generated/schema.ts'), 'export function startSchema() {}\n')
+  })
+
+  afterEach(() => {
+    rmSync(projectDir, { recursive: true, force: true })
+  })
+
+  it('should spell a path with its on-disk case', () => {
+    expect(toDiskCase(join(projectDir, 'SRC/index.TS'))).toBe(join(projectDir, 'src/Index.ts'))
+    expect(toDiskCase(join(projectDir, 'SRC/missing.ts'))).toBe(join(projectDir, 'src/missing.ts'))
+  })
+
+  it('should resolve a file given in another case to the indexed path', async () => {
+    const result = await handleToolRequest({
+      params: {
+        name: 'search_code',
+        arguments: { query: 'startServer', directory: projectDir, ignoreCaseInPaths: true, file: 'SRC/index.ts' },
+      },
+    })
+    const content = JSON.parse(result.content[0].text)
+
+    expect(content.results.map((r: any) => r.path)).toEqual([join(projectDir, 'src/Index.ts')])
+  })
+
+  it('should keep paths case-sensitive when ignoreCaseInPaths is false', () => {
+    const project = createProject({ directory: projectDir, ignoreCaseInPaths: false })
+
+    expect(resolveProjectPath(project, 'SRC/index.ts')).toBe(join(projectDir, 'SRC/index.ts'))
+  })
+
+  it('should apply exclude globs and ignore dirs written in another case', async () => {
+    const project = createProject({ directory: projectDir, ignoreCaseInPaths: true, exclude: ['generated/**'] })
+    await parseProject(project)
+    expect(getAllFiles(project).map(file => file.path)).toEqual([join(projectDir, 'src/Index.ts')])
+
+    const ignoring = createProject({ directory: projectDir, ignoreCaseInPaths: true, ignoreDirs: ['GENERATED'] })
+    await parseProject(ignoring)
+    expect(getAllFiles(ignoring).map(file => file.path)).toEqual([join(projectDir, 'src/Index.ts')])
+  })
+
+  it('should not index a file twice when a change names it in another case', async () => {
+    const project = createProject({ directory: projectDir, ignoreCaseInPaths: true })
+    await parseProject(project)
+
+    await updateProject(project, [{ type: 'modified', path: join(projectDir, 'SRC/INDEX.ts'), timestamp: Date.now() }])
+    expect(getAllFiles(project).filter(file => file.path.toLowerCase().endsWith('index.ts')).map(file => file.path))
+      .toEqual([join(projectDir, 'src/Index.ts')])
+
+    await updateProject(project, [{ type: 'deleted', path: join(projectDir, 'Src/index.ts'), timestamp: Date.now() }])
+    expect(getAllFiles(project).some(file => file.path.endsWith('Index.ts'))).toBe(false)
+  })
+})
\ No newline at end of file
@@ -0,0 +1,49 @@
+/**
+ * Manifest root detection tests - root types, nested roots and per-type default ignores
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { createPersistentManager, getOrCreateProject, getProjectRoots } from '../../project/persistent-manager.js'
+import { getAllFiles } from '../../project/manager.js'
+import { detectProjectType, getRootIgnoreDirs } from '../../project/monorepo.js'
+
+describe('Project Root Detection', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const manifestRoots = resolve(fixturesDir, 'manifest-roots')
+
+  it('should name the root type from its manifest', () => {
+    expect(detectProjectType(manifestRoots)).toBe('python')
+    expect(detectProjectType(resolve(manifestRoots, 'web'))).toBe('npm')
+    expect(detectProjectType(resolve(fixturesDir, 'multi-lang/rust'))).toBe('rust')
+    expect(detectProjectType(resolve(fixturesDir, 'multi-lang/go'))).toBe('go')
+    expect(detectProjectType(resolve(fixturesDir, 'c-example'))).toBe('unknown')
+    expect(getRootIgnoreDirs('unknown')).toEqual([])
+  })
+
+  it('should index nested manifests as typed sub-projects', async () => {
+    const project = await getOrCreateProject(createPersistentManager(), { directory: manifestRoots })
+
+    expect(project.rootType).toBe('python')
+    expect(getProjectRoots(project)).toEqual([
+      { directory: manifestRoots, rootType: 'python' },
+      { directory: resolve(manifestRoots, 'web'), rootType: 'npm' },
+    ])
+  })
+
+  it('should skip the directories each root type ignores, including from enclosing roots', async () => {
+    const project = await getOrCreateProject(createPersistentManager(), { directory: manifestRoots })
+    const files = getAllFiles(project).map(file => file.path.slice(manifestRoots.length + 1)).sort()
+
+    expect(files).toEqual(['app.py', 'web/index.ts'])
+  })
+
+  it('should fall back to a single root without a manifest', async () => {
+    const directory = resolve(fixturesDir, 'c-example')
+    const project = await getOrCreateProject(createPersistentManager(), { directory })
+
+    expect(project.rootType).toBe('unknown')
+    expect(project.subProjects).toBeUndefined()
+    expect(project.files.size).toBeGreaterThan(0)
+  })
+})
\ No newline at end of file
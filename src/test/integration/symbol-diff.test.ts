@@ -0,0 +1,109 @@
+/**
+ * Symbol diff tests - signatures added, removed, changed and renamed between git commits
+ */
+
+import { describe, it, expect, beforeAll, afterAll } from 'vitest'
+import { execFileSync } from 'child_process'
+import { mkdtempSync, mkdirSync, writeFileSync, rmSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('Symbol Diff', () => {
+  let repoDir: string
+
+  const git = (...args: string[]) => execFileSync('git', ['-c', 'user.name=Test Author', '-c', 'user.email=test@example.com', ...args], { cwd: repoDir, stdio: 'pipe' })
+
+  async function diff(args: JsonObject) {
+    const result = await handleToolRequest({ params: { name: 'symbol_diff', arguments: { directory: repoDir, ...args } } })
+    return JSON.parse(result.content[0].text)
+  }
+
+  beforeAll(() => {
+    repoDir = mkdtempSync(join(tmpdir(), 'tsmcp-symdiff-'))
+    mkdirSync(join(repoDir, 'src'))
+    writeFileSync(join(repoDir, 'src/math.ts'), [
+      'export function add(a: number, b: number): number {',
+      '  return a + b',
+      '}',
+      '',
+      'export function scale(value: number, factor: number): number {',
+      '  return value * factor',
+      '}',
+      '',
+      'export function obsolete(): void {',
+      '  console.log("gone")',
+      '}',
+      '',
+    ].join('\n'))
+    writeFileSync(join(repoDir, 'src/unchanged.ts'), 'export function stable() {\n  return 1\n}\n')
+    git('init', '-q', '-b', 'main')
+    git('add', '-A')
+    git('commit', '-q', '-m', 'base')
+
+    writeFileSync(join(repoDir, 'src/math.ts'), [
+      '// add moved down a line',
+      'export function add(a: number, b: number): number {',
+      '  return a + b',
+      '}',
+      '',
+      'export function multiply(value: number, factor: number): number {',
+      '  return value * factor',
+      '}',
+      '',
+      'export function subtract(a: number, b: number, c = 0): number {',
+      '  return a - b - c',
+      '}',
+      '',
+    ].join('\n'))
+    writeFileSync(join(repoDir, 'src/strings.ts'), 'export function shout(text: string): string {\n  return text.toUpperCase()\n}\n')
+    git('add', '-A')
+    git('commit', '-q', '-m', 'api changes')
+
+    writeFileSync(join(repoDir, 'src/strings.ts'), 'function shout(text: string): string {\n  return text.toUpperCase()\n}\n')
+    git('commit', '-q', '-am', 'hide shout')
+  })
+
+  afterAll(() => {
+    rmSync(repoDir, { recursive: true, force: true })
+  })
+
+  it('should list added and removed signatures, ignoring moved ones', async () => {
+    const result = await diff({ from: 'main~2', to: 'main~1' })
+
+    expect(result.added.map((s: any) => s.name).sort()).toEqual(['shout', 'subtract'])
+    expect(result.removed.map((s: any) => s.name)).toEqual(['obsolete'])
+    expect(result.summary).toEqual({ added: 2, removed: 1, changed: 0, renamed: 1 })
+  })
+
+  it('should detect renames from identical bodies', async () => {
+    const result = await diff({ from: 'main~2', to: 'main~1' })
+
+    expect(result.renamed).toHaveLength(1)
+    expect(result.renamed[0].before.name).toBe('scale')
+    expect(result.renamed[0].after.name).toBe('multiply')
+    expect(result.renamed[0].after.bodyHash).toBeUndefined()
+  })
+
+  it('should report visibility changes as changed signatures', async () => {
+    const result = await diff({ from: 'main~1' })
+
+    expect(result.to).toBe('HEAD')
+    expect(result.changed).toHaveLength(1)
+    expect(result.changed[0].before.visibility).toBe('public')
+    expect(result.changed[0].after.visibility).toBe('private')
+  })
+
+  it('should filter by path pattern', async () => {
+    const result = await diff({ from: 'main~2', pathPattern: 'strings' })
+
+    expect(result.added.map((s: any) => s.name)).toEqual(['shout'])
+    expect(result.renamed).toEqual([])
+  })
+
+  it('should reject missing and unknown refs', async () => {
+    await expect(diff({})).rejects.toThrow('from must be a git ref')
+    await expect(diff({ from: 'no-such-branch' })).rejects.toThrow('Unknown git ref')
+  })
+})
\ No newline at end of file
@@ -0,0 +1,176 @@
+/**
+ * Directory walk guard tests - symlink loops and followed symlinks, oversized files, binary files, and the depth limit
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest'
+import { mkdtempSync, mkdirSync, writeFileSync, rmSync, symlinkSync, utimesSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+import { walkDirectory } from '../../core/file-walker.js'
+import { createProject, getDepthSkipped, getSkippedFiles, parseProject } from '../../project/manager.js'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { SkippedFile } from '../../types/core.js'
+
+describe('Directory Walk Limits', () => {
+  let projectDir: string
+
+  beforeEach(() => {
+    projectDir = mkdtempSync(join(tmpdir(), 'tsmcp-walk-'))
+    mkdirSync(join(projectDir, 'src'))
+    writeFileSync(join(projectDir, 'src/small.ts'), 'export const small = 1\n')
+    writeFileSync(join(projectDir, 'src/generated.ts'), `export const table = [${'0, '.repeat(500)}]\n`)
+    writeFileSync(join(projectDir, 'src/blob.ts'), Buffer.from([0x7F, 0x45, 0x4C, 0x46, 0x00, 0x01, 0x00, 0x00]))
+    writeFileSync(join(projectDir, 'src/wide.ts'), Buffer.concat([Buffer.from([0xFF, 0xFE]), Buffer.from('export const wide = 2\n', 'utf16le')]))
+  })
+
+  afterEach(() => {
+    rmSync(projectDir, { recursive: true, force: true })
+  })
+
+  it('should not follow a symlink back into a walked directory', async () => {
+    symlinkSync(projectDir, join(projectDir, 'src/loop'), 'dir')
+
+    const files = await walkDirectory(projectDir, { maxDepth: 50 })
+
+    expect(files.some(file => file.includes('loop'))).toBe(false)
+    expect(files.filter(file => file.endsWith('small.ts'))).toHaveLength(1)
+  })
+
+  describe('Following symlinks', () => {
+    let outsideDir: string
+
+    beforeEach(() => {
+      outsideDir = mkdtempSync(join(tmpdir(), 'tsmcp-outside-'))
+      writeFileSync(join(outsideDir, 'external.ts'), 'export const external = 1\n')
+      mkdirSync(join(projectDir, 'shared'))
+      writeFileSync(join(projectDir, 'shared/util.ts'), 'export const util = 1\n')
+      mkdirSync(join(projectDir, 'app'))
+      symlinkSync(join(projectDir, 'shared'), join(projectDir, 'app/shared'), 'dir')
+      symlinkSync(outsideDir, join(projectDir, 'app/vendor'), 'dir')
+    })
+
+    afterEach(() => {
+      rmSync(outsideDir, { recursive: true, force: true })
+    })
+
+    it('should skip symlinks by default', async () => {
+      const files = await walkDirectory(projectDir)
+
+      expect(files.filter(file => file.endsWith('util.ts'))).toEqual([join(projectDir, 'shared/util.ts')])
+      expect(files.some(file => file.endsWith('external.ts'))).toBe(false)
+    })
+
+    it('should index a file reached through a symlink once and not loop', async () => {
+      symlinkSync(projectDir, join(projectDir, 'src/loop'), 'dir')
+
+      const files = await walkDirectory(projectDir, { maxDepth: 50, followSymlinks: true })
+
+      expect(files.filter(file => file.endsWith('util.ts'))).toHaveLength(1)
+      expect(files.filter(file => file.endsWith('small.ts'))).toHaveLength(1)
+      expect(files.some(file => file.includes('loop'))).toBe(false)
+    })
+
+    it('should skip links pointing outside the root unless allowOutsideRoot is set', async () => {
+      const outside: string[] = []
+      const files = await walkDirectory(projectDir, { followSymlinks: true, onOutsideRoot: path => outside.push(path) })
+
+      expect(files.some(file => file.endsWith('external.ts'))).toBe(false)
+      expect(outside).toEqual([join(projectDir, 'app/vendor')])
+
+      const allowed = await walkDirectory(projectDir, { followSymlinks: true, allowOutsideRoot: true })
+      expect(allowed).toContain(join(projectDir, 'app/vendor/external.ts'))
+    })
+
+    it('should report links outside the root from open_project', async () => {
+      const result = await handleToolRequest({
+        params: { name: 'open_project', arguments: { directory: projectDir, followSymlinks: true } },
+      })
+      const response = JSON.parse(result.content[0].text)
+
+      expect(response.outsideRootLinks).toEqual([join(projectDir, 'app/vendor')])
+      await handleToolRequest({ params: { name: 'close_project', arguments: { projectId: response.projectId } } })
+    })
+
+    it('should reject a non-boolean followSymlinks', async () => {
+      await expect(handleToolRequest({
+        params: { name: 'open_project', arguments: { directory: projectDir, followSymlinks: 'yes' } },
+      })).rejects.toThrow('followSymlinks must be a boolean')
+    })
+  })
+
+  it('should skip files over the size limit and binary files', async () => {
+    const skipped: SkippedFile[] = []
+    const files = await walkDirectory(projectDir, { maxFileSize: 1000, onSkip: file => skipped.push(file) })
+
+    expect(files.map(file => file.slice(projectDir.length)).sort()).toEqual(['/src/small.ts', '/src/wide.ts'])
+    expect(skipped.map(({ path, reason }) => [path.slice(projectDir.length), reason]).sort()).toEqual([
+      ['/src/blob.ts', 'binary'],
+      ['/src/generated.ts', 'too_large'],
+    ])
+  })
+
+  it('should record skipped files on the project', async () => {
+    const project = createProject({ directory: projectDir, maxFileSize: 1000 })
+    await parseProject(project)
+
+    expect(getSkippedFiles(project).map(file => file.reason).sort()).toEqual(['binary', 'too_large'])
+    expect(project.files.has(join(projectDir, 'src/generated.ts'))).toBe(false)
+    expect(project.files.has(join(projectDir, 'src/small.ts'))).toBe(true)
+  })
+
+  it('should stop descending past maxDepth, counting the root as depth 0', async () => {
+    mkdirSync(join(projectDir, 'src/deep/deeper'), { recursive: true })
+    writeFileSync(join(projectDir, 'root.ts'), 'export const root = 0\n')
+    writeFileSync(join(projectDir, 'src/deep/level2.ts'), 'export const level2 = 2\n')
+    writeFileSync(join(projectDir, 'src/deep/deeper/level3.ts'), 'export const level3 = 3\n')
+
+    const limited: string[] = []
+    const files = await walkDirectory(projectDir, { maxDepth: 1, onDepthLimit: path => limited.push(path) })
+
+    expect(files.map(file => file.slice(projectDir.length))).toContain('/root.ts')
+    expect(files.map(file => file.slice(projectDir.length))).toContain('/src/small.ts')
+    expect(files.some(file => file.includes('level'))).toBe(false)
+    expect(limited).toEqual([join(projectDir, 'src/deep')])
+
+    const rootOnly = await walkDirectory(projectDir, { maxDepth: 0 })
+    expect(rootOnly.map(file => file.slice(projectDir.length))).toEqual(['/root.ts'])
+  })
+
+  it('should record depth-skipped directories on the project', async () => {
+    mkdirSync(join(projectDir, 'src/deep/deeper'), { recursive: true })
+    writeFileSync(join(projectDir, 'src/deep/deeper/level3.ts'), 'export const level3 = 3\n')
+
+    const project = createProject({ directory: projectDir, maxDepth: 2 })
+    await parseProject(project)
+
+    expect(getDepthSkipped(project)).toEqual([join(projectDir, 'src/deep/deeper')])
+    expect(project.files.has(join(projectDir, 'src/deep/deeper/level3.ts'))).toBe(false)
+    expect(project.files.has(join(projectDir, 'src/small.ts'))).toBe(true)
+  })
+
+  it('should sort by the modification time the walk cached, across pages', async () => {
+    writeFileSync(join(projectDir, 'src/older.ts'), 'export const sharedName = 1\n')
+    writeFileSync(join(projectDir, 'src/newer.ts'), 'export const sharedName = 2\n')
+    utimesSync(join(projectDir, 'src/older.ts'), 1000, 1000)
+    utimesSync(join(projectDir, 'src/newer.ts'), 2000, 2000)
+
+    const searchPage = async (offset: number) => {
+      const result = await handleToolRequest({
+        params: {
+          name: 'search_code',
+          arguments: { query: 'sharedName', exactMatch: true, sort: 'recency', limit: 1, offset, directory: projectDir },
+        },
+      })
+      return JSON.parse(result.content[0].text).results.map((r: any) => r.path)
+    }
+
+    expect(await searchPage(0)).toEqual([expect.stringMatching(/newer\.ts$/)])
+    // Touching the files after indexing must not reshuffle later pages
+    utimesSync(join(projectDir, 'src/older.ts'), 3000, 3000)
+    expect(await searchPage(1)).toEqual([expect.stringMatching(/older\.ts$/)])
+
+    const project = createProject({ directory: projectDir })
+    await parseProject(project)
+    expect(project.files.get(join(projectDir, 'src/newer.ts'))?.mtimeMs).toBe(2000 * 1000)
+  })
+})
\ No newline at end of file
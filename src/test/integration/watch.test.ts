@@ -0,0 +1,149 @@
+/**
+ * Incremental reindexing tests - watcher changes update, route to sub-projects, and evict deleted files
+ */
+
+import { describe, it, expect, beforeEach, afterEach, vi } from 'vitest'
+import Parser from 'tree-sitter'
+import { mkdtempSync, mkdirSync, writeFileSync, rmSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+import { createProject, parseProject, updateProject, getAllNodes } from '../../project/manager.js'
+import { coalesceChanges } from '../../core/watcher.js'
+import { createPersistentManager, getOrCreateProject, clearAllProjects } from '../../project/persistent-manager.js'
+import { searchCode } from '../../core/search.js'
+import type { FileChange, Project } from '../../types/core.js'
+
+describe('Incremental Reindexing', () => {
+  let rootDir: string
+
+  beforeEach(() => {
+    rootDir = mkdtempSync(join(tmpdir(), 'tsmcp-watch-'))
+    mkdirSync(join(rootDir, 'src'), { recursive: true })
+    mkdirSync(join(rootDir, 'service/src'), { recursive: true })
+    writeFileSync(join(rootDir, 'package.json'), '{ "name": "root" }')
+    writeFileSync(join(rootDir, 'service/package.json'), '{ "name": "service" }')
+    writeFileSync(join(rootDir, 'src/app.ts'), 'export function startApp(): void {}\n')
+    writeFileSync(join(rootDir, 'service/src/api.ts'), 'export function handleRequest(): void {}\n')
+  })
+
+  afterEach(() => {
+    rmSync(rootDir, { recursive: true, force: true })
+  })
+
+  function change(type: FileChange['type'], path: string): FileChange {
+    return { type, path, timestamp: Date.now() }
+  }
+
+  function matchPaths(project: Project, query: string): string[] {
+    return searchCode(query, getAllNodes(project), { exactMatch: true }).map(r => r.node.path)
+  }
+
+  async function indexedProject(): Promise<Project> {
+    return parseProject(createProject({ directory: rootDir }))
+  }
+
+  it('should index created files and re-parse modified ones', async () => {
+    const project = await indexedProject()
+    const newFile = join(rootDir, 'src/extra.ts')
+
+    writeFileSync(newFile, 'export function extraHelper(): void {}\n')
+    await updateProject(project, [change('created', newFile)])
+    expect(matchPaths(project, 'extraHelper')).toEqual([newFile])
+
+    writeFileSync(newFile, 'export function renamedHelper(): void {}\n')
+    await updateProject(project, [change('modified', newFile)])
+    expect(matchPaths(project, 'extraHelper')).toHaveLength(0)
+    expect(matchPaths(project, 'renamedHelper')).toEqual([newFile])
+  })
+
+  it('should re-parse modified files from their previous syntax tree', async () => {
+    const project = await indexedProject()
+    const appFile = join(rootDir, 'src/app.ts')
+
+    await updateProject(project, [change('modified', appFile)])
+    const previousTree = project.files.get(appFile)!.tree
+    expect(previousTree).toBeDefined()
+
+    writeFileSync(appFile, 'export function startApp(): void {}\nexport function stopApp(): void {}\n')
+    const parse = vi.spyOn(Parser.prototype, 'parse')
+    await updateProject(project, [change('modified', appFile)])
+    expect(parse).toHaveBeenCalledWith(expect.any(String), previousTree)
+    parse.mockRestore()
+
+    expect(matchPaths(project, 'stopApp')).toEqual([appFile])
+    expect(matchPaths(project, 'startApp')).toEqual([appFile])
+  })
+
+  it('should update the owning sub-project without duplicating entries', async () => {
+    const project = await indexedProject()
+    const apiFile = join(rootDir, 'service/src/api.ts')
+
+    writeFileSync(apiFile, 'export function handleRequest(): number {\n  return 1\n}\n')
+    await updateProject(project, [change('modified', apiFile)])
+
+    expect(matchPaths(project, 'handleRequest')).toEqual([apiFile])
+    expect(project.files.has(apiFile)).toBe(false)
+  })
+
+  it('should evict deleted files and directories', async () => {
+    const project = await indexedProject()
+    const appFile = join(rootDir, 'src/app.ts')
+
+    rmSync(appFile)
+    await updateProject(project, [change('deleted', appFile)])
+    expect(matchPaths(project, 'startApp')).toHaveLength(0)
+
+    rmSync(join(rootDir, 'service/src'), { recursive: true })
+    await updateProject(project, [change('deleted', join(rootDir, 'service/src'))])
+    expect(matchPaths(project, 'handleRequest')).toHaveLength(0)
+  })
+
+  it('should skip files the indexer would ignore', async () => {
+    const project = await indexedProject()
+    const ignoredFile = join(rootDir, 'src/node_modules/dep.ts')
+
+    mkdirSync(join(rootDir, 'src/node_modules'), { recursive: true })
+    writeFileSync(ignoredFile, 'export function dependencyCode(): void {}\n')
+    await updateProject(project, [change('created', ignoredFile)])
+
+    expect(matchPaths(project, 'dependencyCode')).toHaveLength(0)
+  })
+
+  it('should coalesce bursts to the latest change per path', () => {
+    const coalesced = coalesceChanges([
+      change('created', '/a.ts'),
+      change('modified', '/b.ts'),
+      change('modified', '/a.ts'),
+      change('deleted', '/b.ts'),
+    ])
+
+    expect(coalesced.map(c => `${c.type}:${c.path}`)).toEqual(['modified:/a.ts', 'deleted:/b.ts'])
+  })
+
+  it('should tell change listeners about each reindexed file once per batch', async () => {
+    const manager = createPersistentManager()
+    const batches: Array<{ projectId: string, changes: FileChange[] }> = []
+    manager.changeListeners.add((projectId, changes) => batches.push({ projectId, changes }))
+
+    const project = await getOrCreateProject(manager, { directory: rootDir, autoWatch: true })
+    const appFile = join(rootDir, 'src/app.ts')
+    try {
+      // Keep saving until the watcher is ready and reports the file
+      await vi.waitFor(() => {
+        for (let save = 0; save < 3; save++) {
+          writeFileSync(appFile, `export function startApp(): number { return ${save} }\n`)
+        }
+        expect(batches.some(batch => batch.changes.some(c => c.path === appFile))).toBe(true)
+      }, { timeout: 10000, interval: 500 })
+    }
+    finally {
+      clearAllProjects(manager)
+    }
+
+    expect(batches.every(batch => batch.projectId === project.id)).toBe(true)
+    for (const { changes } of batches) {
+      expect(new Set(changes.map(c => c.path)).size).toBe(changes.length)
+    }
+    expect(matchPaths(project, 'startApp')).toEqual([appFile])
+  })
+})
\ No newline at end of file
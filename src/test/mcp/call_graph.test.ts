@@ -0,0 +1,66 @@
+/**
+ * MCP call_graph tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP call_graph Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const callGraphFixture = resolve(fixturesDir, 'call-graph')
+
+  async function callCallGraph(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'call_graph',
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should list callees within the same file', async () => {
+    const graph = await callCallGraph({ symbol: 'applyDiscount', directory: callGraphFixture })
+
+    expect(graph.functions).toHaveLength(1)
+    expect(graph.callees.map((e: any) => e.callee.name)).toEqual(['roundCents'])
+    expect(graph.external).toEqual([])
+  })
+
+  it('should resolve cross-file callees and report unresolved calls as external', async () => {
+    const graph = await callCallGraph({ symbol: 'total', directory: callGraphFixture })
+
+    const callees = graph.callees.map((e: any) => e.callee)
+    expect(callees.map((c: any) => c.name).sort()).toEqual(['addTax', 'applyDiscount'])
+    expect(callees.every((c: any) => c.path.endsWith('pricing.ts'))).toBe(true)
+    expect(graph.external.map((e: any) => e.name)).toEqual(['reduce'])
+    expect(graph.external[0].line).toBe(7)
+  })
+
+  it('should list callers, including arrow function constants', async () => {
+    const graph = await callCallGraph({ symbol: 'roundCents', directory: callGraphFixture })
+
+    expect(graph.callers.map((e: any) => e.caller.name).sort()).toEqual(['addTax', 'applyDiscount'])
+    expect(graph.callers.every((e: any) => e.depth === 1)).toBe(true)
+  })
+
+  it('should follow additional levels with depth', async () => {
+    const callers = await callCallGraph({ symbol: 'roundCents', directory: callGraphFixture, depth: 2 })
+    const secondLevel = callers.callers.filter((e: any) => e.depth === 2).map((e: any) => e.caller.name)
+    expect(secondLevel).toEqual(['total', 'total'])
+
+    const callees = await callCallGraph({ symbol: 'receipt', directory: callGraphFixture, depth: 2 })
+    expect(callees.callees.filter((e: any) => e.depth === 1).map((e: any) => e.callee.name)).toEqual(['total'])
+    expect(callees.external.map((e: any) => `${e.name}@${e.depth}`).sort()).toEqual(['reduce@2', 'toFixed@1'])
+  })
+
+  it('should return an empty graph for unknown functions', async () => {
+    const graph = await callCallGraph({ symbol: 'XyzNonexistentFunction', directory: callGraphFixture })
+
+    expect(graph.functions).toEqual([])
+    expect(graph.callees).toEqual([])
+    expect(graph.callers).toEqual([])
+  })
+})
\ No newline at end of file
@@ -394,4 +394,45 @@ describe('MCP check_errors tool', () => {
       expect(data1.errors.length).toBe(data2.errors.length)
     })
   })
+
+  describe('language classification', () => {
+    it('should list files classified by shebang or content heuristics', async () => {
+      const result = await handleToolRequest({
+        params: {
+          name: 'check_errors',
+          arguments: { directory: join(FIXTURES_DIR, 'cpp-example') },
+        },
+      })
+      const data = JSON.parse(result.content[0].text)
+
+      expect(data.classifiedFiles).toEqual([
+        expect.objectContaining({ path: expect.stringContaining('shapes.h'), language: 'cpp', source: 'heuristic' }),
+      ])
+      expect(data.errors.every((error: any) => typeof error.language === 'string')).toBe(true)
+    })
+  })
+
+  describe('SARIF output', () => {
+    it('should report syntax errors as SARIF error results', async () => {
+      const result = await handleToolRequest({
+        params: {
+          name: 'check_errors',
+          arguments: { directory: ERROR_SCENARIOS_DIR, format: 'sarif' },
+        },
+      })
+      const log = JSON.parse(result.content[0].text)
+      const json = JSON.parse((await handleToolRequest({
+        params: {
+          name: 'check_errors',
+          arguments: { directory: ERROR_SCENARIOS_DIR },
+        },
+      })).content[0].text)
+
+      const run = log.runs[0]
+      expect(run.results).toHaveLength(json.errors.length)
+      expect(run.tool.driver.rules.every((rule: any) => rule.id.startsWith('syntax/'))).toBe(true)
+      expect(run.results[0].level).toBe('error')
+      expect(run.results[0].locations[0].physicalLocation.region.startLine).toBe(json.errors[0].line)
+    })
+  })
 })
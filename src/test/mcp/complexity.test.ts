@@ -0,0 +1,68 @@
+/**
+ * MCP complexity tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP complexity Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const multiLangFixture = resolve(fixturesDir, 'multi-lang')
+  const todoFixture = resolve(fixturesDir, 'todo-comments')
+
+  async function callComplexity(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'complexity',
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should pin the complexity of the Rust fixture functions', async () => {
+    const content = await callComplexity({
+      directory: multiLangFixture,
+      pathPattern: 'rust/src/',
+      threshold: 1,
+    })
+
+    expect(content.functions.map((f: any) => [f.function, f.complexity])).toEqual([
+      ['fmt', 4],
+      ['main', 3],
+      ['divide', 2],
+      ['sqrt', 2],
+    ])
+    expect(content.functions[0].file).toContain('lib.rs')
+    expect(content.functions[0].startLine).toBe(26)
+  })
+
+  it('should report every function at the default threshold', async () => {
+    const content = await callComplexity({
+      directory: multiLangFixture,
+      pathPattern: 'rust/src/',
+      maxResults: 100,
+    })
+
+    expect(content.totalFunctions).toBe(17)
+    expect(content.functions.filter((f: any) => f.complexity === 1)).toHaveLength(13)
+  })
+
+  it('should sort results by descending complexity', async () => {
+    const content = await callComplexity({ directory: multiLangFixture, maxResults: 100 })
+
+    const complexities = content.functions.map((f: any) => f.complexity)
+    expect(complexities).toEqual([...complexities].sort((a, b) => b - a))
+  })
+
+  it('should count ternaries as branches', async () => {
+    const content = await callComplexity({ directory: todoFixture, pathPattern: 'queue.ts' })
+
+    expect(content.functions.map((f: any) => [f.function, f.complexity])).toEqual([
+      ['drain', 2],
+      ['push', 1],
+    ])
+  })
+})
\ No newline at end of file
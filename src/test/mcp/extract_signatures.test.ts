@@ -0,0 +1,86 @@
+/**
+ * MCP extract_signatures tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP extract_signatures Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const multiLangFixture = resolve(fixturesDir, 'multi-lang')
+
+  async function callExtractSignatures(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'extract_signatures',
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  function findSignature(content: any, name: string) {
+    return content.signatures.find((s: any) => s.name === name)
+  }
+
+  it('should return TypeScript parameter types, return types and visibility', async () => {
+    const content = await callExtractSignatures({ directory: multiLangFixture, file: 'ts/calculator.ts' })
+
+    expect(findSignature(content, 'add')).toMatchObject({
+      kind: 'method',
+      signature: 'add(a: number, b: number): number',
+      parameters: [{ name: 'a', type: 'number' }, { name: 'b', type: 'number' }],
+      returnType: 'number',
+      visibility: 'public',
+    })
+    expect(findSignature(content, 'recordOperation').visibility).toBe('private')
+    expect(content.signatures.some((s: any) => s.signature.includes('{'))).toBe(false)
+  })
+
+  it('should return null for missing Python types', async () => {
+    const content = await callExtractSignatures({ directory: multiLangFixture, file: 'python/calculator.py' })
+
+    expect(findSignature(content, 'add')).toMatchObject({
+      parameters: [{ name: 'self', type: null }, { name: 'a', type: 'float' }, { name: 'b', type: 'float' }],
+      returnType: 'float',
+      visibility: 'public',
+    })
+    expect(findSignature(content, '_record_operation').visibility).toBe('private')
+    expect(content.signatures.filter((s: any) => s.name === '__init__').every((s: any) => s.returnType === null)).toBe(true)
+  })
+
+  it('should read Go and Rust signatures', async () => {
+    const go = await callExtractSignatures({ directory: multiLangFixture, file: 'go/calculator.go' })
+    expect(findSignature(go, 'Calculator.Divide')).toMatchObject({
+      parameters: [{ name: 'a', type: 'float64' }, { name: 'b', type: 'float64' }],
+      returnType: '(float64, error)',
+      visibility: 'public',
+    })
+
+    const rust = await callExtractSignatures({ directory: multiLangFixture, file: 'rust/src/lib.rs' })
+    expect(findSignature(rust, 'add')).toMatchObject({
+      parameters: [{ name: '&mut self', type: null }, { name: 'a', type: 'f64' }, { name: 'b', type: 'f64' }],
+      returnType: 'f64',
+      visibility: 'pub',
+    })
+  })
+
+  it('should return the same output on every call, sorted by path', async () => {
+    const first = await callExtractSignatures({ directory: multiLangFixture })
+    const second = await callExtractSignatures({ directory: multiLangFixture })
+
+    expect(second).toEqual(first)
+    const paths = first.signatures.map((s: any) => s.path)
+    expect(paths).toEqual([...paths].sort())
+    expect(first.totalSignatures).toBe(first.signatures.length)
+  })
+
+  it('should reject files outside the index', async () => {
+    await expect(callExtractSignatures({
+      directory: multiLangFixture,
+      file: 'src/missing.ts',
+    })).rejects.toThrow('File is not indexed')
+  })
+})
\ No newline at end of file
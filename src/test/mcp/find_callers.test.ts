@@ -0,0 +1,68 @@
+/**
+ * MCP find_callers tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP find_callers Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const callGraphFixture = resolve(fixturesDir, 'call-graph')
+
+  async function callTool(name: string, args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name,
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should list call sites with the calling function', async () => {
+    const content = await callTool('find_callers', { symbol: 'roundCents', directory: callGraphFixture })
+
+    expect(content.totalCallers).toBe(2)
+    expect(content.callers.map((c: any) => c.caller.name).sort()).toEqual(['addTax', 'applyDiscount'])
+    expect(content.callers.find((c: any) => c.caller.name === 'applyDiscount')).toMatchObject({
+      startLine: 2,
+      context: 'return roundCents(total * (1 - rate))',
+      caller: { kind: 'function', startLine: 1, endLine: 3 },
+    })
+  })
+
+  it('should leave out references that are not calls', async () => {
+    const references = await callTool('find_references', { symbol: 'roundCents', directory: callGraphFixture })
+    const callers = await callTool('find_callers', { symbol: 'roundCents', directory: callGraphFixture })
+
+    // The value passed to map is a reference but not a call
+    expect(references.references.some((r: any) => r.context.includes('values.map(roundCents)'))).toBe(true)
+    expect(callers.callers.some((c: any) => c.context.includes('values.map(roundCents)'))).toBe(false)
+
+    const imported = await callTool('find_callers', { symbol: 'applyDiscount', directory: callGraphFixture })
+    expect(imported.callers).toHaveLength(1)
+    expect(imported.callers[0].path).toMatch(/checkout\.ts$/)
+    expect(imported.callers[0].caller.name).toBe('total')
+  })
+
+  it('should match receiver calls on the method name', async () => {
+    const content = await callTool('find_callers', { symbol: 'total', directory: callGraphFixture })
+
+    // The total parameter and its uses in applyDiscount are not calls
+    expect(content.callers).toHaveLength(1)
+    expect(content.callers[0].context).toContain('this.total().toFixed(2)')
+    expect(content.callers[0].caller).toMatchObject({ name: 'receipt', kind: 'method' })
+
+    const qualified = await callTool('find_callers', { symbol: 'Checkout.total', directory: callGraphFixture })
+    expect(qualified.callers).toEqual(content.callers)
+  })
+
+  it('should return no callers for unknown functions', async () => {
+    const content = await callTool('find_callers', { symbol: 'XyzNonexistentFunction', directory: callGraphFixture })
+
+    expect(content.callers).toEqual([])
+    expect(content.totalCallers).toBe(0)
+  })
+})
\ No newline at end of file
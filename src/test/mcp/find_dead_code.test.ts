@@ -0,0 +1,68 @@
+/**
+ * MCP find_dead_code tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP find_dead_code Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const privateSymbolsFixture = resolve(fixturesDir, 'private-symbols')
+
+  async function callFindDeadCode(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'find_dead_code',
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  function namesIn(content: any, file: string): string[] {
+    return content.deadCode
+      .filter((symbol: any) => symbol.path.endsWith(file))
+      .map((symbol: any) => symbol.name)
+  }
+
+  it('should report unreferenced private Rust items, including self-recursive ones', async () => {
+    const content = await callFindDeadCode({ directory: privateSymbolsFixture })
+
+    expect(namesIn(content, 'lib.rs')).toEqual(['Orphan', 'unused_helper', 'countdown'])
+    const orphan = content.deadCode.find((symbol: any) => symbol.name === 'Orphan')
+    expect(orphan).toMatchObject({ kind: 'struct', startLine: 5, context: 'struct Orphan {', confidence: 'high' })
+  })
+
+  it('should skip public items, main and test code', async () => {
+    const content = await callFindDeadCode({ directory: privateSymbolsFixture })
+    const names = content.deadCode.map((symbol: any) => symbol.name)
+
+    for (const name of ['public_api', 'make_config', 'used_helper', 'main', 'fixture_value', 'adds_one', 'formatName', 'Greeter', '__init__']) {
+      expect(names).not.toContain(name)
+    }
+  })
+
+  it('should report unexported TypeScript declarations and unused private methods', async () => {
+    const content = await callFindDeadCode({ directory: privateSymbolsFixture, pathPattern: 'helpers.ts' })
+
+    expect(namesIn(content, 'helpers.ts')).toEqual(['InternalShape', 'neverCalled', 'unusedArrow', 'forgotten'])
+    expect(content.deadCode.every((symbol: any) => symbol.confidence === 'medium')).toBe(true)
+    expect(content.totalDeadCode).toBe(4)
+  })
+
+  it('should report underscore-prefixed Python functions, classes and methods', async () => {
+    const content = await callFindDeadCode({ directory: privateSymbolsFixture, pathPattern: 'utils.py' })
+
+    expect(namesIn(content, 'utils.py')).toEqual(['_stale', '_Unused', '_render_row'])
+  })
+
+  it('should limit results and explain how references are matched', async () => {
+    const content = await callFindDeadCode({ directory: privateSymbolsFixture, maxResults: 2 })
+
+    expect(content.deadCode).toHaveLength(2)
+    expect(content.totalDeadCode).toBe(10)
+    expect(content.note).toMatch(/matched by name/)
+  })
+})
\ No newline at end of file
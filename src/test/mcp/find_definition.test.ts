@@ -0,0 +1,81 @@
+/**
+ * MCP find_definition tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP find_definition Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const positiveFixture = resolve(fixturesDir, 'minimal-positive')
+  const multiLangFixture = resolve(fixturesDir, 'multi-lang')
+
+  async function callFindDefinition(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'find_definition',
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should resolve a TypeScript class declaration', async () => {
+    const content = await callFindDefinition({
+      symbol: 'TestUserService',
+      directory: positiveFixture,
+    })
+
+    expect(content.totalDefinitions).toBe(1)
+    const [definition] = content.definitions
+    expect(definition.kind).toBe('class')
+    expect(definition.nodeType).toBe('class_declaration')
+    expect(definition.path).toContain('index.ts')
+    expect(definition.endByte).toBeGreaterThan(definition.startByte)
+  })
+
+  it('should resolve Rust struct and function items', async () => {
+    const struct = await callFindDefinition({
+      symbol: 'Calculator',
+      directory: multiLangFixture,
+    })
+    const kinds = struct.definitions.map((d: any) => d.kind)
+    expect(kinds).toContain('struct')
+
+    const fn = await callFindDefinition({
+      symbol: 'divide',
+      directory: multiLangFixture,
+    })
+    expect(fn.definitions.some((d: any) => d.nodeType === 'function_item')).toBe(true)
+  })
+
+  it('should narrow to a single definition when file and line are given', async () => {
+    const content = await callFindDefinition({
+      symbol: 'divide',
+      directory: multiLangFixture,
+      file: 'rust/src/lib.rs',
+      line: 75,
+    })
+
+    expect(content.totalDefinitions).toBe(1)
+    expect(content.definitions[0].path).toContain('lib.rs')
+  })
+
+  it('should return no definitions for unknown symbols', async () => {
+    const content = await callFindDefinition({
+      symbol: 'XyzNonexistentSymbol',
+      directory: positiveFixture,
+    })
+
+    expect(content.definitions).toEqual([])
+    expect(content.totalDefinitions).toBe(0)
+  })
+
+  it('should throw error for missing symbol', async () => {
+    await expect(handleToolRequest({
+      params: { name: 'find_definition', arguments: { directory: positiveFixture } },
+    })).rejects.toThrow('Symbol must be a string')
+  })
+})
\ No newline at end of file
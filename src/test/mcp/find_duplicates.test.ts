@@ -0,0 +1,76 @@
+/**
+ * MCP find_duplicates tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP find_duplicates Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const duplicateFixture = resolve(fixturesDir, 'duplicate-code')
+
+  async function callFindDuplicates(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'find_duplicates',
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  const namesOf = (cluster: any) => cluster.locations.map((location: any) => location.name).sort()
+
+  it('should cluster functions that differ only in names and literals', async () => {
+    const content = await callFindDuplicates({ directory: duplicateFixture })
+
+    expect(content.totalClusters).toBe(1)
+    const [cluster] = content.clusters
+    expect(namesOf(cluster)).toEqual(['invoiceTotal', 'orderTotal'])
+    expect(cluster.nodeCount).toBeGreaterThanOrEqual(30)
+
+    const invoice = cluster.locations.find((location: any) => location.name === 'invoiceTotal')
+    expect(invoice.file).toContain('invoices.ts')
+    expect(invoice.startLine).toBe(7)
+    expect(invoice.endLine).toBe(15)
+  })
+
+  it('should not report blocks nested inside a reported cluster', async () => {
+    const content = await callFindDuplicates({ directory: duplicateFixture, minNodes: 5 })
+
+    const totals = content.clusters.filter((cluster: any) => namesOf(cluster).includes('orderTotal'))
+    expect(totals).toHaveLength(1)
+    expect(totals[0].locations.map((location: any) => location.startLine).sort()).toEqual([6, 7])
+  })
+
+  it('should include smaller functions when minNodes is lowered', async () => {
+    const content = await callFindDuplicates({ directory: duplicateFixture, minNodes: 5 })
+
+    const labels = content.clusters.find((cluster: any) => namesOf(cluster).includes('orderLabel'))
+    expect(labels).toBeDefined()
+    expect(namesOf(labels)).toEqual(['invoiceLabel', 'orderLabel'])
+  })
+
+  it('should keep functions with different operators apart', async () => {
+    const content = await callFindDuplicates({ directory: duplicateFixture, minNodes: 5 })
+
+    // validateOrder and validateInvoice compare with < and <=, so only their identical inner blocks match
+    const validators = content.clusters.filter((cluster: any) => namesOf(cluster).includes('validateOrder'))
+    expect(validators.flatMap((cluster: any) => cluster.locations.map((location: any) => location.startLine))).not.toContain(20)
+  })
+
+  it('should return no clusters above the largest function size', async () => {
+    const content = await callFindDuplicates({ directory: duplicateFixture, minNodes: 1000 })
+
+    expect(content.clusters).toEqual([])
+    expect(content.totalClusters).toBe(0)
+  })
+
+  it('should apply pathPattern before clustering', async () => {
+    const content = await callFindDuplicates({ directory: duplicateFixture, pathPattern: 'orders.ts' })
+
+    expect(content.totalClusters).toBe(0)
+  })
+})
\ No newline at end of file
@@ -0,0 +1,77 @@
+/**
+ * MCP find_implementations tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP find_implementations Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const implementationsFixture = resolve(fixturesDir, 'implementations')
+
+  async function callFindImplementations(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'find_implementations',
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should list Rust impl blocks for a trait, flagging the blanket impl as generic', async () => {
+    const content = await callFindImplementations({
+      symbol: 'Shape',
+      directory: implementationsFixture,
+    })
+
+    expect(content.totalImplementations).toBe(3)
+    expect(content.implementations.map((i: any) => [i.implementor, i.generic])).toEqual([
+      ['Circle', false],
+      ['Square', false],
+      ['Box<T>', true],
+    ])
+    expect(content.implementations[0].path).toMatch(/shapes\.rs$/)
+    expect(content.implementations[0].startLine).toBe(15)
+    expect(content.implementations[2].context).toBe('impl<T: Shape> Shape for Box<T> {')
+  })
+
+  it('should match qualified trait paths', async () => {
+    const content = await callFindImplementations({
+      symbol: 'fmt::Display',
+      directory: implementationsFixture,
+    })
+
+    expect(content.implementations).toHaveLength(1)
+    expect(content.implementations[0]).toMatchObject({ implementor: 'Circle', trait: 'fmt::Display', generic: false })
+  })
+
+  it('should list TypeScript classes implementing an interface', async () => {
+    const content = await callFindImplementations({
+      symbol: 'Store',
+      directory: implementationsFixture,
+    })
+
+    expect(content.implementations.map((i: any) => [i.implementor, i.trait, i.generic])).toEqual([
+      ['MemoryStore', 'Store<T>', true],
+      ['CounterStore', 'Store<number>', false],
+    ])
+  })
+
+  it('should find interfaces listed after an extends clause or another interface', async () => {
+    const content = await callFindImplementations({
+      symbol: 'Disposable',
+      directory: implementationsFixture,
+    })
+
+    expect(content.implementations.map((i: any) => i.implementor)).toEqual(['MemoryStore', 'FileHandle'])
+  })
+
+  it('should throw error for missing symbol', async () => {
+    await expect(callFindImplementations({
+      directory: implementationsFixture,
+    })).rejects.toThrow('Symbol must be a string')
+  })
+})
\ No newline at end of file
@@ -0,0 +1,65 @@
+/**
+ * MCP find_references tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP find_references Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const positiveFixture = resolve(fixturesDir, 'minimal-positive')
+
+  async function callFindReferences(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'find_references',
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should list references without the declaration by default', async () => {
+    const content = await callFindReferences({
+      symbol: 'TestUser',
+      directory: positiveFixture,
+    })
+
+    expect(content.totalReferences).toBeGreaterThan(0)
+    expect(content.references.every((r: any) => r.isDefinition === false)).toBe(true)
+    expect(content.references[0].context).toContain('TestUser')
+    expect(content.references[0].endByte).toBeGreaterThan(content.references[0].startByte)
+  })
+
+  it('should include the declaration when includeDefinition is true', async () => {
+    const without = await callFindReferences({
+      symbol: 'TestUser',
+      directory: positiveFixture,
+    })
+    const withDefinition = await callFindReferences({
+      symbol: 'TestUser',
+      directory: positiveFixture,
+      includeDefinition: true,
+    })
+
+    expect(withDefinition.totalReferences).toBe(without.totalReferences + 1)
+    expect(withDefinition.references.some((r: any) => r.isDefinition)).toBe(true)
+  })
+
+  it('should skip matches inside comments and strings', async () => {
+    const content = await callFindReferences({
+      symbol: 'test',
+      directory: positiveFixture,
+    })
+
+    expect(content.totalReferences).toBe(0)
+  })
+
+  it('should throw error for missing symbol', async () => {
+    await expect(callFindReferences({
+      directory: positiveFixture,
+    })).rejects.toThrow('Symbol must be a string')
+  })
+})
\ No newline at end of file
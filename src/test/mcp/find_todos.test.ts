@@ -0,0 +1,73 @@
+/**
+ * MCP find_todos tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP find_todos Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const todoFixture = resolve(fixturesDir, 'todo-comments')
+
+  async function callFindTodos(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'find_todos',
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should group default tags found in comments', async () => {
+    const content = await callFindTodos({ directory: todoFixture })
+
+    expect(Object.keys(content.tags)).toEqual(['TODO', 'FIXME', 'HACK', 'XXX'])
+    expect(content.tags.TODO).toHaveLength(2)
+    expect(content.tags.FIXME).toHaveLength(1)
+    expect(content.tags.HACK).toHaveLength(1)
+    expect(content.tags.XXX).toHaveLength(1)
+    expect(content.totalTodos).toBe(5)
+  })
+
+  it('should capture parenthesized and @ assignees with the message', async () => {
+    const content = await callFindTodos({ directory: todoFixture, pathPattern: 'queue.ts' })
+
+    const [todo] = content.tags.TODO
+    expect(todo.author).toBe('alice')
+    expect(todo.message).toBe('replace the array with a ring buffer')
+    expect(todo.startLine).toBe(1)
+
+    const [fixme] = content.tags.FIXME
+    expect(fixme.author).toBe('bob')
+    expect(fixme.message).toBe('reject duplicate jobs')
+    expect(fixme.startLine).toBe(6)
+  })
+
+  it('should report the line of tags inside block comments', async () => {
+    const content = await callFindTodos({ directory: todoFixture, pathPattern: 'queue.ts' })
+
+    const [hack] = content.tags.HACK
+    expect(hack.startLine).toBe(11)
+    expect(hack.message).toBe('drain synchronously until workers exist')
+    expect(hack.author).toBeUndefined()
+  })
+
+  it('should ignore tags outside comments', async () => {
+    const content = await callFindTodos({ directory: todoFixture })
+
+    expect(content.tags.TODO.every((t: any) => !t.context.includes('string is not a comment'))).toBe(true)
+  })
+
+  it('should accept custom tags', async () => {
+    const content = await callFindTodos({ directory: todoFixture, tags: ['PERF', 'NOTE'] })
+
+    expect(Object.keys(content.tags)).toEqual(['PERF', 'NOTE'])
+    expect(content.tags.PERF[0].author).toBe('carol')
+    expect(content.tags.PERF[0].message).toBe('batch the writes')
+    expect(content.tags.NOTE).toHaveLength(1)
+    expect(content.totalTodos).toBe(2)
+  })
+})
\ No newline at end of file
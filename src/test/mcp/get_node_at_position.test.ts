@@ -0,0 +1,72 @@
+/**
+ * MCP get_node_at_position tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { readFileSync } from 'fs'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP get_node_at_position Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const callGraphFixture = resolve(fixturesDir, 'call-graph')
+  const pricingLines = readFileSync(resolve(callGraphFixture, 'src/pricing.ts'), 'utf-8').split('\n')
+
+  async function callGetNode(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'get_node_at_position',
+        arguments: { directory: callGraphFixture, file: 'src/pricing.ts', ...args },
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should return the named node under the cursor with its ancestors', async () => {
+    const content = await callGetNode({ line: 2, column: 10 })
+
+    expect(content.clamped).toBe(false)
+    expect(content.node).toMatchObject({
+      type: 'identifier',
+      named: true,
+      text: 'roundCents',
+      startLine: 2,
+      startColumn: 9,
+      endColumn: 19,
+    })
+    expect(content.node.endByte - content.node.startByte).toBe('roundCents'.length)
+    expect(content.ancestors[0]).toBe('call_expression')
+    expect(content.ancestors).toContain('function_declaration')
+    expect(content.ancestors.at(-1)).toBe('program')
+  })
+
+  it('should return punctuation tokens when anonymous nodes are asked for', async () => {
+    const content = await callGetNode({ line: 2, column: 19, anonymous: true })
+
+    expect(content.node).toMatchObject({ type: '(', named: false, text: '(' })
+    expect(content.ancestors[0]).toBe('arguments')
+  })
+
+  it('should clamp a column past the end of the line', async () => {
+    const content = await callGetNode({ line: 2, column: 500 })
+
+    expect(content.clamped).toBe(true)
+    expect(content.requested).toEqual({ line: 2, column: 500 })
+    expect(content.line).toBe(2)
+    expect(content.column).toBe(pricingLines[1]!.length)
+  })
+
+  it('should clamp a line past the end of the file', async () => {
+    const content = await callGetNode({ line: 999, column: 0 })
+
+    expect(content.clamped).toBe(true)
+    expect(content.line).toBe(pricingLines.length)
+    expect(content.node.type).toBeDefined()
+  })
+
+  it('should reject files outside the index and non-integer positions', async () => {
+    await expect(callGetNode({ file: 'src/missing.ts', line: 1, column: 0 })).rejects.toThrow(/not indexed/)
+    await expect(callGetNode({ line: 1.5, column: 0 })).rejects.toThrow(/integers/)
+  })
+})
\ No newline at end of file
@@ -0,0 +1,45 @@
+/**
+ * MCP health tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { readFileSync } from 'fs'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP health Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+
+  async function callTool(name: string, args: JsonObject = {}) {
+    const result = await handleToolRequest({
+      params: {
+        name,
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should report the server version and loaded grammars', async () => {
+    const { version } = JSON.parse(readFileSync(resolve(import.meta.dirname, '../../../package.json'), 'utf-8'))
+    const content = await callTool('health')
+
+    expect(content.version).toBe(version)
+    const typescript = content.languages.find((language: any) => language.name === 'typescript')
+    expect(typescript).toMatchObject({ extensions: expect.arrayContaining(['.ts']), custom: false })
+    expect(typescript.grammarVersion).toMatch(/^\d+\.\d+\.\d+/)
+    expect(content.languages.map((language: any) => language.name)).toContain('swift')
+  })
+
+  it('should be ready once indexing finishes and count open projects', async () => {
+    const directory = resolve(fixturesDir, 'simple-ts')
+    await callTool('open_project', { directory, projectId: 'health-check' })
+
+    const content = await callTool('health')
+
+    expect(content.status).toBe('ready')
+    expect(content.indexing).toEqual([])
+    expect(content.openProjects).toBeGreaterThanOrEqual(1)
+  })
+})
\ No newline at end of file
@@ -0,0 +1,117 @@
+/**
+ * MCP import_graph tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve, relative } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP import_graph Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const importFixture = resolve(fixturesDir, 'import-graph')
+
+  async function callImportGraph(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'import_graph',
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  const rel = (path: string) => relative(importFixture, path)
+  const edgesOf = (content: any) => content.edges.map((edge: any) => `${rel(edge.from)} -> ${rel(edge.to)}`).sort()
+  const cyclesOf = (content: any) => content.cycles.map((cycle: string[]) => Array.from(new Set(cycle.map(rel))).sort())
+
+  it('should resolve TypeScript imports, including .js specifiers for .ts files', async () => {
+    const content = await callImportGraph({ directory: importFixture, pathPattern: 'web/' })
+
+    expect(edgesOf(content)).toEqual([
+      'web/app.ts -> web/cart.ts',
+      'web/app.ts -> web/format.ts',
+      'web/cart.ts -> web/app.ts',
+      'web/cart.ts -> web/format.ts',
+    ])
+    expect(content.external).toEqual([
+      expect.objectContaining({ module: 'express', line: 1 }),
+    ])
+  })
+
+  it('should resolve Python absolute, relative and submodule imports', async () => {
+    const content = await callImportGraph({ directory: importFixture, pathPattern: 'py/' })
+
+    expect(edgesOf(content)).toEqual([
+      'py/main.py -> py/shop/orders.py',
+      'py/shop/orders.py -> py/shop/pricing.py',
+      'py/shop/pricing.py -> py/shop/tax.py',
+    ])
+    expect(content.external.map((entry: any) => entry.module)).toEqual(['json'])
+    expect(content.cycles).toEqual([])
+  })
+
+  it('should resolve Rust mod declarations and crate, self and super paths', async () => {
+    const content = await callImportGraph({ directory: importFixture, pathPattern: 'rust/' })
+
+    expect(edgesOf(content)).toEqual([
+      'rust/src/inventory.rs -> rust/src/report.rs',
+      'rust/src/main.rs -> rust/src/inventory.rs',
+      'rust/src/main.rs -> rust/src/inventory.rs',
+      'rust/src/main.rs -> rust/src/report.rs',
+      'rust/src/report.rs -> rust/src/inventory.rs',
+    ])
+    const modEdge = content.edges.find((edge: any) => edge.module === 'self::report')
+    expect(modEdge.line).toBe(2)
+    expect(content.external.map((entry: any) => entry.module)).toEqual([
+      'std::collections::HashMap',
+      'std::collections::HashMap',
+    ])
+  })
+
+  it('should flag circular dependencies', async () => {
+    const content = await callImportGraph({ directory: importFixture })
+
+    expect(cyclesOf(content)).toEqual(expect.arrayContaining([
+      ['web/app.ts', 'web/cart.ts'],
+      ['rust/src/inventory.rs', 'rust/src/report.rs'],
+    ]))
+    expect(content.cycles).toHaveLength(2)
+    for (const cycle of content.cycles) {
+      expect(cycle[0]).toBe(cycle[cycle.length - 1])
+    }
+  })
+
+  it('should scope the graph to a file and its transitive imports', async () => {
+    const content = await callImportGraph({ directory: importFixture, file: 'py/main.py' })
+
+    expect(content.file).toBe(resolve(importFixture, 'py/main.py'))
+    expect(content.files.map(rel).sort()).toEqual([
+      'py/main.py',
+      'py/shop/orders.py',
+      'py/shop/pricing.py',
+      'py/shop/tax.py',
+    ])
+    expect(content.edges).toHaveLength(3)
+  })
+
+  it('should return a lone file when it imports nothing from the project', async () => {
+    const content = await callImportGraph({ directory: importFixture, file: 'web/format.ts' })
+
+    expect(content.files.map(rel)).toEqual(['web/format.ts'])
+    expect(content.edges).toEqual([])
+    expect(content.cycles).toEqual([])
+  })
+
+  it('should keep cycles reachable from the scoped file', async () => {
+    const content = await callImportGraph({ directory: importFixture, file: 'rust/src/main.rs' })
+
+    expect(content.files).toHaveLength(3)
+    expect(cyclesOf(content)).toEqual([['rust/src/inventory.rs', 'rust/src/report.rs']])
+  })
+
+  it('should reject a file that is not indexed', async () => {
+    await expect(callImportGraph({ directory: importFixture, file: 'web/missing.ts' }))
+      .rejects.toThrow('File is not indexed')
+  })
+})
\ No newline at end of file
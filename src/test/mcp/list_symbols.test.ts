@@ -0,0 +1,182 @@
+/**
+ * MCP list_symbols tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP list_symbols Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const positiveFixture = resolve(fixturesDir, 'minimal-positive')
+  const multiLangFixture = resolve(fixturesDir, 'multi-lang')
+  const reactFixture = resolve(fixturesDir, 'react-tsx')
+
+  async function callListSymbols(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'list_symbols',
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should nest TypeScript methods under their class', async () => {
+    const content = await callListSymbols({
+      directory: positiveFixture,
+      file: 'src/index.ts',
+    })
+
+    expect(content.files).toHaveLength(1)
+    const service = content.files[0].symbols.find((s: any) => s.name === 'TestUserService')
+    expect(service.kind).toBe('class')
+    expect(service.children.map((c: any) => c.name)).toEqual(['addUser', 'findUserById', 'getAllUsers'])
+    expect(service.children.every((c: any) => c.kind === 'method')).toBe(true)
+    expect(service.startLine).toBeLessThan(service.endLine)
+  })
+
+  it('should outline Rust structs, enums, impls, and methods', async () => {
+    const content = await callListSymbols({
+      directory: multiLangFixture,
+      file: 'rust/src/lib.rs',
+    })
+
+    const symbols = content.files[0].symbols
+    const byName = new Map(symbols.map((s: any) => [s.name, s]))
+
+    expect((byName.get('Calculator') as any).kind).toBe('struct')
+    expect((byName.get('CalculatorError') as any).kind).toBe('enum')
+
+    const impl = byName.get('impl Calculator') as any
+    expect(impl.kind).toBe('impl')
+    expect(impl.children.map((c: any) => c.name)).toContain('divide')
+    expect(impl.children.every((c: any) => c.kind === 'method')).toBe(true)
+
+    expect(byName.has('impl fmt::Display for CalculatorError')).toBe(true)
+  })
+
+  it('should list Rust attributes on the items they annotate', async () => {
+    const content = await callListSymbols({
+      directory: multiLangFixture,
+      file: 'rust/src/lib.rs',
+    })
+
+    const symbols = content.files[0].symbols
+    const byName = new Map(symbols.map((s: any) => [s.name, s]))
+
+    expect((byName.get('CalculationResult') as any).attributes).toEqual(['derive(Debug, Clone, Serialize, Deserialize)'])
+    expect((byName.get('CalculatorError') as any).attributes).toEqual(['derive(Debug)'])
+    expect((byName.get('impl Calculator') as any).attributes).toBeUndefined()
+
+    const tests = byName.get('tests') as any
+    expect(tests.attributes).toEqual(['cfg(test)'])
+    expect(tests.children.find((c: any) => c.name === 'test_add').attributes).toEqual(['test'])
+  })
+
+  it('should list Rust macro invocations, including ones nested in macro arguments', async () => {
+    const lib = await callListSymbols({
+      directory: multiLangFixture,
+      file: 'rust/src/lib.rs',
+    })
+    const main = await callListSymbols({
+      directory: multiLangFixture,
+      file: 'rust/src/main.rs',
+    })
+
+    const mainFn = main.files[0].symbols.find((s: any) => s.name === 'main')
+    const calls = mainFn.children.filter((c: any) => c.kind === 'macro_call')
+    expect(calls.map((c: any) => c.name)).toContain('println!')
+
+    const tests = lib.files[0].symbols.find((s: any) => s.name === 'tests')
+    const divideTest = tests.children.find((c: any) => c.name === 'test_divide_by_zero')
+    const [assertCall] = divideTest.children
+    expect(assertCall).toMatchObject({ name: 'assert!', kind: 'macro_call' })
+    expect(assertCall.children).toMatchObject([{ name: 'matches!', kind: 'macro_call', startLine: assertCall.startLine }])
+  })
+
+  it('should report React components in TSX files', async () => {
+    const content = await callListSymbols({
+      directory: reactFixture,
+      file: 'src/Greeting.tsx',
+    })
+
+    const kinds = Object.fromEntries(content.files[0].symbols.map((s: any) => [s.name, s.kind]))
+    expect(kinds).toMatchObject({
+      GreetingProps: 'interface',
+      Greeting: 'component',
+      Badge: 'component',
+      Layout: 'component',
+      formatTitle: 'function',
+      Capitalize: 'function',
+    })
+  })
+
+  it('should report React components in JSX files', async () => {
+    const content = await callListSymbols({
+      directory: reactFixture,
+      file: 'src/Counter.jsx',
+    })
+
+    expect(content.files[0].symbols.map((s: any) => [s.name, s.kind])).toEqual([['Counter', 'component']])
+  })
+
+  it('should limit nesting with depth', async () => {
+    const content = await callListSymbols({
+      directory: multiLangFixture,
+      file: 'rust/src/lib.rs',
+      depth: 1,
+    })
+
+    const symbols = content.files[0].symbols
+    expect(symbols.length).toBeGreaterThan(0)
+    expect(symbols.every((s: any) => s.children.length === 0)).toBe(true)
+  })
+
+  it('should outline every file when no file is given', async () => {
+    const content = await callListSymbols({ directory: multiLangFixture })
+
+    const paths = content.files.map((f: any) => f.path)
+    expect(paths.some((p: string) => p.endsWith('.go'))).toBe(true)
+    expect(paths.some((p: string) => p.endsWith('.py'))).toBe(true)
+    expect(content.totalSymbols).toBeGreaterThan(content.files.length)
+  })
+
+  it('should reject files outside the index', async () => {
+    await expect(callListSymbols({
+      directory: positiveFixture,
+      file: 'src/missing.ts',
+    })).rejects.toThrow('File is not indexed')
+  })
+
+  it('should accept an absolute file path', async () => {
+    const content = await callListSymbols({
+      directory: positiveFixture,
+      file: resolve(positiveFixture, 'src/index.ts'),
+    })
+
+    expect(content.files).toHaveLength(1)
+    expect(content.files[0].symbols.map((s: any) => s.name)).toContain('TestUserService')
+  })
+
+  it('should use the open project containing an absolute file when no project is named', async () => {
+    const opened = await callListSymbols({ directory: positiveFixture, file: 'src/index.ts' })
+    const content = await callListSymbols({ file: resolve(positiveFixture, 'src/index.ts') })
+
+    expect(content.projectId).toBe(opened.projectId)
+    expect(content.files).toHaveLength(1)
+  })
+
+  it('should reject a path outside the project root', async () => {
+    await expect(callListSymbols({
+      directory: positiveFixture,
+      file: resolve(multiLangFixture, 'rust/src/lib.rs'),
+    })).rejects.toThrow(`Path is outside the project root ${positiveFixture}`)
+
+    await expect(callListSymbols({
+      directory: positiveFixture,
+      file: '../multi-lang/rust/src/lib.rs',
+    })).rejects.toThrow('Path is outside the project root')
+  })
+})
\ No newline at end of file
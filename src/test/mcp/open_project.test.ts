@@ -0,0 +1,74 @@
+/**
+ * MCP open_project, list_projects and close_project tool tests
+ */
+
+import { describe, it, expect, beforeEach } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest, clearMCPMemory } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP project tools', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const pythonDir = resolve(fixturesDir, 'python-example')
+  const goDir = resolve(fixturesDir, 'go-example')
+
+  async function callTool(name: string, args: JsonObject = {}) {
+    const result = await handleToolRequest({
+      params: {
+        name,
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  beforeEach(() => {
+    clearMCPMemory()
+  })
+
+  it('should open several projects and list their roots', async () => {
+    const python = await callTool('open_project', { directory: pythonDir, projectId: 'py' })
+    const go = await callTool('open_project', { directory: goDir })
+
+    expect(python).toMatchObject({ projectId: 'py', directory: pythonDir })
+    expect(python.totalFiles).toBeGreaterThan(0)
+    expect(go.projectId).toBe('go-example')
+
+    const listed = await callTool('list_projects')
+    expect(listed.totalProjects).toBe(2)
+    expect(listed.projects).toEqual(expect.arrayContaining([
+      expect.objectContaining({ projectId: 'py', directory: pythonDir }),
+      expect.objectContaining({ projectId: 'go-example', directory: goDir }),
+    ]))
+  })
+
+  it('should route tools to an open project by projectId alone', async () => {
+    await callTool('open_project', { directory: pythonDir, projectId: 'py' })
+    await callTool('open_project', { directory: goDir, projectId: 'go' })
+
+    const python = await callTool('list_symbols', { projectId: 'py' })
+    const go = await callTool('list_symbols', { projectId: 'go' })
+
+    expect(python.projectId).toBe('py')
+    expect(python.files.length).toBeGreaterThan(0)
+    expect(python.files.every((file: any) => file.path.startsWith(pythonDir))).toBe(true)
+    expect(go.projectId).toBe('go')
+    expect(go.files.every((file: any) => file.path.startsWith(goDir))).toBe(true)
+  })
+
+  it('should close a project and forget it', async () => {
+    await callTool('open_project', { directory: pythonDir, projectId: 'py' })
+
+    const closed = await callTool('close_project', { projectId: 'py' })
+    expect(closed).toEqual({ projectId: 'py', directory: pythonDir, closed: true })
+
+    const listed = await callTool('list_projects')
+    expect(listed.projects).toEqual([])
+
+    await expect(callTool('close_project', { projectId: 'py' })).rejects.toThrow('Project not found: py')
+  })
+
+  it('should require a directory to open', async () => {
+    await expect(callTool('open_project', {})).rejects.toThrow('Directory must be a non-empty string')
+  })
+})
\ No newline at end of file
@@ -0,0 +1,103 @@
+/**
+ * MCP rename_symbol tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { readFileSync } from 'fs'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP rename_symbol Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const renameFixture = resolve(fixturesDir, 'rename-scopes')
+
+  async function callRenameSymbol(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'rename_symbol',
+        arguments: { directory: renameFixture, ...args },
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  function lines(content: any): [string, number][] {
+    return content.edits.map((edit: any) => [edit.path.split('/').pop(), edit.startLine])
+  }
+
+  it('should rename a local variable without touching shadowing parameters', async () => {
+    const content = await callRenameSymbol({ symbol: 'count', newName: 'sum', file: 'src/counter.ts', line: 2 })
+
+    expect(content.target).toMatchObject({ name: 'count', kind: 'variable', startLine: 2 })
+    expect(lines(content)).toEqual([['counter.ts', 2], ['counter.ts', 4], ['counter.ts', 6]])
+    expect(content.edits[0]).toMatchObject({ startColumn: 6, endColumn: 11, oldText: 'count', newText: 'sum' })
+    expect(content.edits[0].endByte - content.edits[0].startByte).toBe(5)
+  })
+
+  it('should keep the key of shorthand properties', async () => {
+    const content = await callRenameSymbol({ symbol: 'count', newName: 'sum', file: 'src/counter.ts', line: 2 })
+
+    expect(content.edits[2]).toMatchObject({ startLine: 6, oldText: 'count', newText: 'count: sum' })
+  })
+
+  it('should rename a top-level function in files that import it only', async () => {
+    const content = await callRenameSymbol({ symbol: 'total', newName: 'sumAll', file: 'src/counter.ts' })
+
+    expect(lines(content)).toEqual([['counter.ts', 1], ['main.ts', 1], ['main.ts', 3]])
+    expect(content.files).toHaveLength(2)
+    expect(content.totalEdits).toBe(3)
+  })
+
+  it('should ask for a position when several declarations share the name', async () => {
+    await expect(callRenameSymbol({ symbol: 'total', newName: 'sumAll' })).rejects.toThrow(/declared 2 times/)
+  })
+
+  it('should rename methods and their member accesses', async () => {
+    const content = await callRenameSymbol({ symbol: 'increment', newName: 'bump', file: 'src/counter.ts', line: 15 })
+
+    expect(content.target.kind).toBe('method')
+    expect(lines(content)).toEqual([['counter.ts', 15], ['counter.ts', 20], ['counter.ts', 20], ['main.ts', 5]])
+  })
+
+  it('should follow Rust let shadowing', async () => {
+    const content = await callRenameSymbol({ symbol: 'factor', newName: 'base', file: 'src/scale.rs', line: 2 })
+
+    expect(lines(content)).toEqual([['scale.rs', 2], ['scale.rs', 7]])
+    expect(content.edits[1].startColumn).toBe(17)
+  })
+
+  it('should treat repeated Python assignments as one variable', async () => {
+    const content = await callRenameSymbol({ symbol: 'count', newName: 'total', file: 'src/stats.py', line: 2 })
+
+    expect(lines(content)).toEqual([['stats.py', 2], ['stats.py', 7], ['stats.py', 8]])
+  })
+
+  it('should give byte offsets into the original bytes of UTF-16 and BOM-prefixed files', async () => {
+    const encodedFixture = resolve(fixturesDir, 'encoded-sources')
+
+    const utf16 = await callRenameSymbol({ directory: encodedFixture, symbol: 'Utf16Invoice', newName: 'Invoice' })
+    expect(utf16.edits.length).toBeGreaterThan(1)
+    for (const edit of utf16.edits) {
+      expect(readFileSync(edit.path).subarray(edit.startByte, edit.endByte).toString('utf16le')).toBe('Utf16Invoice')
+    }
+
+    const bom = await callRenameSymbol({ directory: encodedFixture, symbol: 'bomGreeting', newName: 'greet' })
+    expect(bom.edits).toHaveLength(1)
+    expect(bom.edits[0].startByte).toBe(3 + 'export function '.length)
+    expect(readFileSync(bom.edits[0].path).subarray(bom.edits[0].startByte, bom.edits[0].endByte).toString('utf-8')).toBe('bomGreeting')
+  })
+
+  it('should report when nothing declares the symbol', async () => {
+    const content = await callRenameSymbol({ symbol: 'missing', newName: 'found' })
+
+    expect(content.edits).toEqual([])
+    expect(content.note).toBe('No declaration of "missing" found')
+  })
+
+  it('should reject invalid names and lines without the symbol', async () => {
+    await expect(callRenameSymbol({ symbol: 'count', newName: '1st' })).rejects.toThrow(/not a valid identifier/)
+    await expect(callRenameSymbol({ symbol: 'count', newName: 'sum', file: 'src/counter.ts', line: 3 }))
+      .rejects.toThrow(/does not appear on line 3/)
+  })
+})
\ No newline at end of file
@@ -0,0 +1,60 @@
+/**
+ * MCP resolve_import tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve, relative } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP resolve_import Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const importFixture = resolve(fixturesDir, 'import-graph')
+
+  async function callResolveImport(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'resolve_import',
+        arguments: { directory: importFixture, ...args },
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  const rel = (path: string) => relative(importFixture, path)
+
+  it('should resolve a named TypeScript import through its .js specifier', async () => {
+    const content = await callResolveImport({ file: 'web/app.ts', symbol: 'Cart' })
+
+    expect(content.imports).toHaveLength(1)
+    expect(content.imports[0]).toMatchObject({ module: './cart.js', line: 2, resolved: true })
+    expect(rel(content.imports[0].file)).toBe('web/cart.ts')
+  })
+
+  it('should mark a package import as unresolved with its raw path', async () => {
+    const content = await callResolveImport({ file: 'web/app.ts', symbol: 'express' })
+
+    expect(content.imports).toEqual([{ module: 'express', line: 1, resolved: false }])
+  })
+
+  it('should resolve a relative Python import of a name to its module', async () => {
+    const content = await callResolveImport({ file: 'py/shop/orders.py', symbol: 'total' })
+
+    expect(rel(content.imports[0].file)).toBe('py/shop/pricing.py')
+  })
+
+  it('should resolve Rust use paths and leave the standard library unresolved', async () => {
+    const stock = await callResolveImport({ file: 'rust/src/main.rs', symbol: 'Stock' })
+    const hashMap = await callResolveImport({ file: 'rust/src/main.rs', symbol: 'HashMap' })
+    const summary = await callResolveImport({ file: 'rust/src/inventory.rs', symbol: 'summary' })
+
+    expect(rel(stock.imports[0].file)).toBe('rust/src/inventory.rs')
+    expect(hashMap.imports).toEqual([{ module: 'std::collections::HashMap', line: 4, resolved: false }])
+    expect(rel(summary.imports[0].file)).toBe('rust/src/report.rs')
+  })
+
+  it('should fail for a name the file does not import', async () => {
+    await expect(callResolveImport({ file: 'web/app.ts', symbol: 'formatDate' }))
+      .rejects.toThrow('formatDate is not imported by web/app.ts')
+  })
+})
\ No newline at end of file
@@ -0,0 +1,98 @@
+/**
+ * MCP search_and_replace tool tests - capture group previews, atomic apply, and the pre-write checks
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest'
+import { mkdtempSync, mkdirSync, writeFileSync, readFileSync, rmSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import { applyReplacePlan, planReplace } from '../../core/replace.js'
+import type { JsonObject, RenameEdit, TreeNode } from '../../types/core.js'
+
+describe('MCP search_and_replace Tool', () => {
+  let projectDir: string
+
+  const logFile = () => join(projectDir, 'src/log.ts')
+  const windowsFile = () => join(projectDir, 'src/windows.ts')
+
+  beforeEach(() => {
+    projectDir = mkdtempSync(join(tmpdir(), 'tsmcp-replace-'))
+    mkdirSync(join(projectDir, 'src'))
+    writeFileSync(logFile(), 'export function run(a: number) {\n  console.log(a)\n  console.log("done", a)\n}\n')
+    writeFileSync(windowsFile(), 'export const first = 1\r\nconsole.log(first)\r\n')
+  })
+
+  afterEach(() => {
+    rmSync(projectDir, { recursive: true, force: true })
+  })
+
+  async function callReplace(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'search_and_replace',
+        arguments: { directory: projectDir, pattern: 'console\\.log\\((.*)\\)', replacement: 'logger.debug($1)', ...args },
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should preview edits with capture groups expanded without writing', async () => {
+    const content = await callReplace({})
+
+    expect(content.totalEdits).toBe(3)
+    expect(content.applied).toBe(false)
+    expect(content.edits[0]).toMatchObject({
+      startLine: 2,
+      startColumn: 2,
+      endLine: 2,
+      endColumn: 16,
+      oldText: 'console.log(a)',
+      newText: 'logger.debug(a)',
+    })
+    expect(content.edits.find((edit: any) => edit.path.endsWith('windows.ts'))).toMatchObject({ startLine: 2, startColumn: 0, newText: 'logger.debug(first)' })
+    expect(readFileSync(logFile(), 'utf-8')).toContain('console.log(a)')
+  })
+
+  it('should write every file and refresh the index when applied', async () => {
+    const content = await callReplace({ apply: true })
+
+    expect(content.applied).toBe(true)
+    expect(readFileSync(logFile(), 'utf-8')).toBe('export function run(a: number) {\n  logger.debug(a)\n  logger.debug("done", a)\n}\n')
+    expect(readFileSync(windowsFile(), 'utf-8')).toBe('export const first = 1\r\nlogger.debug(first)\r\n')
+    expect((await callReplace({})).totalEdits).toBe(0)
+  })
+
+  it('should expand named groups and leave missing references literal', () => {
+    const fileNode: TreeNode = { id: 'f', type: 'file', path: '/virtual/a.ts', content: 'let total = sum\n' }
+    const plan = planReplace('let (?<name>\\w+) = (\\w+)', 'const $<name> = $2 // $3 $$', [fileNode])
+
+    expect(plan.edits.map(edit => edit.newText)).toEqual(['const total = sum // $3 $'])
+  })
+
+  it('should refuse overlapping edits and files changed since indexing', () => {
+    const fileNode: TreeNode = { id: 'f', type: 'file', path: logFile(), content: readFileSync(logFile(), 'utf-8') }
+    const edit = (startColumn: number, endColumn: number): RenameEdit => ({
+      path: logFile(), startByte: 0, endByte: 0, startLine: 2, startColumn, endLine: 2, endColumn, oldText: '', newText: 'x',
+    })
+
+    expect(() => applyReplacePlan({ edits: [edit(2, 10), edit(5, 12)], files: [logFile()] }, [fileNode])).toThrow('Replacements overlap')
+    expect(() => applyReplacePlan({ edits: [edit(2, 5)], files: [logFile()] }, [{ ...fileNode, content: 'stale' }])).toThrow('changed since it was indexed')
+    expect(readFileSync(logFile(), 'utf-8')).toContain('console.log(a)')
+  })
+
+  it('should apply edits to files with lines past the index line limit', async () => {
+    const longLine = `export const data = '${'x'.repeat(1200)}' // console.log(hidden)`
+    writeFileSync(logFile(), `console.log(1)\n${longLine}\n`)
+
+    const content = await callReplace({ apply: true })
+
+    expect(content.applied).toBe(true)
+    expect(content.edits.filter((edit: any) => edit.path.endsWith('log.ts'))).toHaveLength(1)
+    expect(readFileSync(logFile(), 'utf-8')).toBe(`logger.debug(1)\n${longLine}\n`)
+  })
+
+  it('should reject an empty pattern', async () => {
+    await expect(callReplace({ pattern: '' })).rejects.toThrow('Pattern must be a non-empty string')
+  })
+})
\ No newline at end of file
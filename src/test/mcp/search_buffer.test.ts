@@ -0,0 +1,61 @@
+/**
+ * MCP search_buffer tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP search_buffer Tool', () => {
+  const source = [
+    'export class Cart {',
+    '  total(): number {',
+    '    return 0',
+    '  }',
+    '}',
+    '',
+    'export function totalOf(cart: Cart) {',
+    '  return cart.total()',
+    '}',
+  ].join('\n')
+
+  async function callTool(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'search_buffer',
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should search unsaved content without a project', async () => {
+    const content = await callTool({ content: source, language: 'typescript', query: 'total' })
+
+    expect(content.language).toBe('typescript')
+    const names = content.results.map((r: any) => r.name)
+    expect(names).toEqual(expect.arrayContaining(['total', 'totalOf']))
+    expect(content.results.find((r: any) => r.name === 'total')).toMatchObject({ startLine: 2, enclosingPath: 'Cart.total' })
+  })
+
+  it('should support the other matching modes', async () => {
+    const regex = await callTool({ content: source, language: 'typescript', query: 'return \\w+\\.total', mode: 'regex' })
+    expect(regex.results).toHaveLength(1)
+    expect(regex.results[0].startLine).toBe(8)
+
+    const query = await callTool({ content: source, language: 'typescript', query: '(class_declaration name: (type_identifier) @name)', queryType: 'tree-sitter' })
+    expect(query.results.map((r: any) => r.captureName)).toEqual(['name'])
+  })
+
+  it('should report what it recovered from broken content', async () => {
+    const content = await callTool({ content: 'function ok() {}\nfunction broken( {', language: 'javascript', query: 'ok', exactMatch: true })
+
+    expect(content.results[0].name).toBe('ok')
+    expect(content.parseErrors.length).toBeGreaterThan(0)
+  })
+
+  it('should reject unknown languages and missing content', async () => {
+    await expect(callTool({ content: source, language: 'cobol', query: 'total' })).rejects.toThrow()
+    await expect(callTool({ language: 'typescript', query: 'total' })).rejects.toThrow('Content must be a string')
+  })
+})
\ No newline at end of file
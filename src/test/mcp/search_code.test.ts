@@ -4,6 +4,7 @@
  */
 
 import { describe, it, expect } from 'vitest'
+import { readFileSync } from 'fs'
 import { resolve } from 'path'
 import { handleToolRequest } from '../../mcp/handlers.js'
 import type { JsonObject } from '../../types/core.js'
@@ -386,6 +387,25 @@ describe('MCP search_code Tool', () => {
       expect(content.results).toBeInstanceOf(Array)
     })
 
+    it('should reject a one-character name query', async () => {
+      await expect(callSearchCode({ query: 'T', directory: positiveFixture }))
+        .rejects.toThrow(/shorter than 2 characters[\s\S]*wholeWord or exactMatch/)
+      await expect(callSearchCode({ query: 'T', mode: 'fuzzy', directory: positiveFixture }))
+        .rejects.toThrow(/shorter than 2 characters/)
+    })
+
+    it('should accept short queries that are exact, whole-word, regex or allowed by minQueryLength', async () => {
+      for (const args of [{ exactMatch: true }, { wholeWord: true }, { mode: 'regex' }, { minQueryLength: 1 }]) {
+        const result = await callSearchCode({ query: 'T', directory: positiveFixture, ...args })
+        expect(JSON.parse(result.content[0].text).results).toBeInstanceOf(Array)
+      }
+    })
+
+    it('should reject a negative minQueryLength', async () => {
+      await expect(callSearchCode({ query: 'TestUser', directory: positiveFixture, minQueryLength: -1 }))
+        .rejects.toThrow('minQueryLength must be a non-negative integer')
+    })
+
     it('should handle very large maxResults', async () => {
       const result = await callSearchCode({
         query: 'Test',
@@ -421,4 +441,1196 @@ describe('MCP search_code Tool', () => {
       expect(content.results).toBeInstanceOf(Array)
     })
   })
+
+  describe('Tree-sitter Query Mode', () => {
+    it('should return one result per capture with the capture name', async () => {
+      const result = await callSearchCode({
+        query: '(class_declaration name: (type_identifier) @class.name)',
+        queryType: 'tree-sitter',
+        directory: positiveFixture,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results.length).toBeGreaterThan(0)
+      expect(content.results[0].captureName).toBe('class.name')
+      expect(content.results.some((r: any) => r.name === 'TestUserService')).toBe(true)
+    })
+
+    it('should report the offset of an invalid query', async () => {
+      await expect(callSearchCode({
+        query: '(class_declaration name: (',
+        queryType: 'tree-sitter',
+        directory: positiveFixture,
+      })).rejects.toThrow(/Invalid tree-sitter query at offset \d+/)
+    })
+  })
+
+  describe('Regex Mode', () => {
+    it('should match per line and report the enclosing declaration', async () => {
+      const result = await callSearchCode({
+        query: 'this\\.users\\.push',
+        mode: 'regex',
+        directory: positiveFixture,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results).toHaveLength(1)
+      expect(content.results[0].matches).toContain('regex')
+      expect(content.results[0].enclosingScope.name).toBe('addUser')
+    })
+
+    it('should honor line anchors', async () => {
+      const result = await callSearchCode({
+        query: '^export function \\w+',
+        mode: 'regex',
+        directory: positiveFixture,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results.length).toBeGreaterThanOrEqual(3)
+      content.results.forEach((r: any) => expect(r.startColumn).toBe(0))
+    })
+
+    it('should surface regex compile errors', async () => {
+      await expect(callSearchCode({
+        query: 'fn(\\w+',
+        mode: 'regex',
+        directory: positiveFixture,
+      })).rejects.toThrow(/Invalid regular expression/)
+    })
+  })
+
+  describe('Structural Mode', () => {
+    it('should bind metavariables to the matched subtrees', async () => {
+      const result = await callSearchCode({
+        query: 'this.users.push($USER)',
+        mode: 'structural',
+        directory: positiveFixture,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results).toHaveLength(1)
+      expect(content.results[0].matches).toContain('structural')
+      expect(content.results[0].metavariables.USER).toMatchObject({ text: 'user', startLine: 12 })
+    })
+
+    it('should only bind constrained metavariables to nodes of that kind', async () => {
+      const result = await callSearchCode({
+        query: '$VALUE.length > $MIN:number',
+        mode: 'structural',
+        directory: positiveFixture,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results).toHaveLength(1)
+      expect(content.results[0].metavariables.VALUE.text).toBe('input')
+      expect(content.results[0].metavariables.MIN.text).toBe('0')
+
+      const strings = JSON.parse((await callSearchCode({
+        query: '$VALUE.length > $MIN:string',
+        mode: 'structural',
+        directory: positiveFixture,
+      })).content[0].text)
+      expect(strings.results).toHaveLength(0)
+    })
+
+    it('should require repeated metavariables to match the same text', async () => {
+      const same = JSON.parse((await callSearchCode({
+        query: '$A.id === $A',
+        mode: 'structural',
+        directory: positiveFixture,
+      })).content[0].text)
+      expect(same.results).toHaveLength(0)
+
+      const different = JSON.parse((await callSearchCode({
+        query: '$A.id === $B',
+        mode: 'structural',
+        directory: positiveFixture,
+      })).content[0].text)
+      expect(different.results).toHaveLength(1)
+    })
+
+    it('should reject a pattern that is not valid code', async () => {
+      await expect(callSearchCode({
+        query: 'this.users.push($USER',
+        mode: 'structural',
+        directory: positiveFixture,
+      })).rejects.toThrow(/Invalid structural pattern/)
+    })
+  })
+
+  describe('Result Sorting', () => {
+    const rankingFixture = resolve(fixturesDir, 'relevance-ranking')
+
+    it('should rank the definition above references and demote test files', async () => {
+      const result = await callSearchCode({
+        query: 'ChatProvider',
+        mode: 'regex',
+        directory: rankingFixture,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results[0].path).toMatch(/src\/chat\/provider\.ts$/)
+      expect(content.results[0].relevance).toMatchObject({ total: 120, match: 100, definition: 20, proximity: 0, path: 0 })
+      expect(content.results.at(-1).path).toMatch(/__tests__\/provider\.ts$/)
+      expect(content.results.at(-1).relevance.path).toBe(-15)
+    })
+
+    it('should boost results near currentFile', async () => {
+      const result = await callSearchCode({
+        query: 'ChatProvider',
+        mode: 'regex',
+        directory: rankingFixture,
+        currentFile: 'src/app/main.ts',
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      const paths = content.results.map((r: any) => r.path.replace(/^.*\/src\//, 'src/'))
+      expect(paths[0]).toBe('src/chat/provider.ts')
+      expect(paths[1]).toBe('src/app/main.ts')
+      expect(paths.indexOf('src/chat/session.ts')).toBeLessThan(paths.indexOf('src/generated/chat.generated.ts'))
+      expect(content.results[1].relevance.proximity).toBe(15)
+    })
+
+    it('should demote generated definitions below hand-written ones', async () => {
+      const result = await callSearchCode({
+        query: 'ChatProvider',
+        exactMatch: true,
+        types: ['class'],
+        directory: rankingFixture,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results).toHaveLength(2)
+      expect(content.results[0].path).toMatch(/src\/chat\/provider\.ts$/)
+      expect(content.results[1].relevance.path).toBe(-25)
+    })
+
+    it('should order by path when asked', async () => {
+      const result = await callSearchCode({
+        query: 'ChatProvider',
+        mode: 'regex',
+        sort: 'path',
+        directory: rankingFixture,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      const positions = content.results.map((r: any) => `${r.path}:${String(r.startLine).padStart(4, '0')}`)
+      expect(positions).toEqual([...positions].sort())
+      expect(content.results[0].relevance).toBeUndefined()
+    })
+
+    it('should return byte-identical output for a repeated query', async () => {
+      const args = { query: 'ChatProvider', mode: 'regex', dedup: 'none', directory: rankingFixture }
+      const first = await callSearchCode(args)
+      const second = await callSearchCode(args)
+
+      expect(second.content[0].text).toBe(first.content[0].text)
+    })
+
+    it('should break ties on path and start by end position', async () => {
+      const result = await callSearchCode({
+        query: 'ChatProvider',
+        mode: 'regex',
+        sort: 'path',
+        dedup: 'none',
+        directory: rankingFixture,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      const keys = content.results.map((r: any) => [r.path, r.startLine, r.startColumn, r.endLine, r.endColumn])
+      const sorted = [...keys].sort((a: any[], b: any[]) => {
+        for (let i = 0; i < a.length; i++) {
+          if (a[i] !== b[i]) return a[i] < b[i] ? -1 : 1
+        }
+        return 0
+      })
+      expect(keys).toEqual(sorted)
+    })
+
+    it('should reject an unknown sort', async () => {
+      await expect(callSearchCode({
+        query: 'ChatProvider',
+        sort: 'popularity',
+        directory: rankingFixture,
+      })).rejects.toThrow(/Invalid sort: popularity/)
+    })
+  })
+
+  describe('Deduplication', () => {
+    const overlappingQuery = '(class_declaration name: (type_identifier) @class.name) (type_identifier) @type'
+
+    it('should drop results covering the same span by default', async () => {
+      const deduped = JSON.parse((await callSearchCode({ query: overlappingQuery, queryType: 'tree-sitter', directory: positiveFixture, maxResults: 100 })).content[0].text)
+      const kept = JSON.parse((await callSearchCode({ query: overlappingQuery, queryType: 'tree-sitter', directory: positiveFixture, maxResults: 100, dedup: false })).content[0].text)
+
+      expect(deduped.results.filter((r: any) => r.name === 'TestUserService')).toHaveLength(1)
+      expect(kept.results.filter((r: any) => r.name === 'TestUserService')).toHaveLength(2)
+      expect(deduped.totalResults).toBe(deduped.results.length)
+      expect(deduped.diagnostics).toMatchObject({ dedup: 'range', totalBeforeDedup: kept.totalResults })
+    })
+
+    it('should fold matches on the same line into one result with several highlight ranges', async () => {
+      const result = await callSearchCode({
+        query: 'user',
+        mode: 'regex',
+        directory: positiveFixture,
+        dedup: 'line',
+        sort: 'path',
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results.map((r: any) => r.startLine)).toEqual([9, 11, 12, 16, 20])
+      const findLine = content.results.find((r: any) => r.startLine === 16)
+      expect(findLine.highlight).toEqual([{ start: 16, end: 20 }, { start: 27, end: 31 }, { start: 35, end: 39 }])
+      expect(content.diagnostics.totalBeforeDedup).toBe(8)
+    })
+
+    it('should reject an unknown dedup mode', async () => {
+      await expect(callSearchCode({
+        query: 'user',
+        directory: positiveFixture,
+        dedup: 'file',
+      })).rejects.toThrow(/Invalid dedup: file/)
+    })
+  })
+
+  describe('Match Highlights', () => {
+    it('should highlight a symbol name within its declaration line', async () => {
+      const result = await callSearchCode({ query: 'TestUserService', directory: positiveFixture, exactMatch: true })
+
+      const content = JSON.parse(result.content[0].text)
+      const service = content.results.find((r: any) => r.type === 'class')
+      expect(service.highlight).toEqual([{ start: 13, end: 28 }])
+    })
+
+    it('should keep highlight columns relative to the match line when context is requested', async () => {
+      const result = await callSearchCode({
+        query: 'this\\.users\\.find',
+        mode: 'regex',
+        directory: positiveFixture,
+        contextBefore: 2,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results[0].contextStartLine).toBe(14)
+      expect(content.results[0].highlight).toEqual([{ start: 11, end: 26 }])
+    })
+
+    it('should highlight each tree-sitter capture', async () => {
+      const result = await callSearchCode({
+        query: '(call_expression function: (member_expression property: (property_identifier) @method))',
+        queryType: 'tree-sitter',
+        directory: positiveFixture,
+        sort: 'path',
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      const push = content.results.find((r: any) => r.name === 'push')
+      expect(push.highlight).toEqual([{ start: 15, end: 19 }])
+    })
+  })
+
+  describe('Enclosing Paths', () => {
+    const multiLangFixture = resolve(fixturesDir, 'multi-lang')
+
+    it('should join the enclosing declarations of a match', async () => {
+      const result = await callSearchCode({ query: 'a / b', mode: 'regex', directory: multiLangFixture, pathPattern: 'ts/' })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results[0].enclosingPath).toBe('Calculator.divide')
+    })
+
+    it('should end a declaration result with its own name', async () => {
+      const result = await callSearchCode({ query: 'divide', exactMatch: true, directory: multiLangFixture, pathPattern: 'ts/' })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results.find((r: any) => r.type === 'function').enclosingPath).toBe('Calculator.divide')
+    })
+
+    it('should use the language scope separator', async () => {
+      const result = await callSearchCode({ query: 'let result = a \\+ b', mode: 'regex', directory: multiLangFixture, pathPattern: 'rust' })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results[0].enclosingPath).toBe('Calculator::add')
+    })
+
+    it('should show anonymous functions as a placeholder', async () => {
+      const result = await callSearchCode({ query: 'line\\.includes', mode: 'regex', directory: resolve(fixturesDir, 'scope-filter') })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results[0].enclosingPath).toBe('collectTodos.{closure}')
+    })
+  })
+
+  describe('Qualified Names', () => {
+    const multiLangFixture = resolve(fixturesDir, 'multi-lang')
+
+    it('should match a method by its type with the language scope separator', async () => {
+      const rust = JSON.parse((await callSearchCode({ query: 'Calculator::add', exactMatch: true, directory: multiLangFixture, pathPattern: 'rust' })).content[0].text)
+      const ts = JSON.parse((await callSearchCode({ query: 'Calculator.add', exactMatch: true, directory: multiLangFixture, pathPattern: 'ts/' })).content[0].text)
+
+      expect(rust.results.map((r: any) => [r.name, r.startLine])).toEqual([['add', 52]])
+      expect(ts.results.map((r: any) => [r.name, r.enclosingPath])).toEqual([['add', 'Calculator.add']])
+      expect(ts.results[0].matches).toContain('name')
+    })
+
+    it('should not match members of other types or another separator', async () => {
+      const otherType = await callSearchCode({ query: 'CalculatorError::fmt', exactMatch: true, directory: multiLangFixture, pathPattern: 'rust' })
+      const wrongType = await callSearchCode({ query: 'Calculator::fmt', exactMatch: true, directory: multiLangFixture, pathPattern: 'rust' })
+      const dotted = await callSearchCode({ query: 'Calculator.add', exactMatch: true, directory: multiLangFixture, pathPattern: 'rust' })
+
+      expect(JSON.parse(otherType.content[0].text).results).toHaveLength(1)
+      expect(JSON.parse(wrongType.content[0].text).results).toHaveLength(0)
+      expect(JSON.parse(dotted.content[0].text).results).toHaveLength(0)
+    })
+
+    it('should score the member name as a name query', async () => {
+      const result = await callSearchCode({ query: 'Calculator::sub', directory: multiLangFixture, pathPattern: 'rust' })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results.find((r: any) => r.name === 'subtract')).toMatchObject({ score: 85, matches: expect.arrayContaining(['name']) })
+    })
+  })
+
+  describe('Nested Project Roots', () => {
+    const nestedRootsFixture = resolve(fixturesDir, 'nested-roots')
+
+    it('should find symbols under the root and the nested project', async () => {
+      const rootResult = await callSearchCode({
+        query: 'rootHelper',
+        directory: nestedRootsFixture,
+      })
+      const nestedResult = await callSearchCode({
+        query: 'NestedService',
+        directory: nestedRootsFixture,
+      })
+
+      const rootContent = JSON.parse(rootResult.content[0].text)
+      const nestedContent = JSON.parse(nestedResult.content[0].text)
+
+      expect(rootContent.results.some((r: any) => r.name === 'rootHelper')).toBe(true)
+      expect(nestedContent.results.some((r: any) => r.name === 'NestedService')).toBe(true)
+    })
+
+    it('should not return duplicates for files under overlapping roots', async () => {
+      const result = await callSearchCode({
+        query: 'NestedService',
+        directory: nestedRootsFixture,
+        exactMatch: true,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      const keys = content.results.map((r: any) => `${r.path}:${r.startLine}:${r.type}`)
+      expect(keys.length).toBeGreaterThan(0)
+      expect(new Set(keys).size).toBe(keys.length)
+    })
+  })
+
+  describe('Language Filter', () => {
+    const multiLangFixture = resolve(fixturesDir, 'multi-lang')
+
+    it('should only return results from the requested language', async () => {
+      const result = await callSearchCode({
+        query: 'Calculator',
+        directory: multiLangFixture,
+        language: 'typescript',
+        maxResults: 50,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results.length).toBeGreaterThan(0)
+      expect(content.results.every((r: any) => r.path.endsWith('.ts'))).toBe(true)
+    })
+
+    it('should accept a list of languages', async () => {
+      const result = await callSearchCode({
+        query: 'divide',
+        directory: multiLangFixture,
+        language: ['python', 'rust'],
+        maxResults: 50,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      const extensions = new Set(content.results.map((r: any) => r.path.substring(r.path.lastIndexOf('.'))))
+      expect(content.results.length).toBeGreaterThan(0)
+      expect([...extensions].every(ext => ext === '.py' || ext === '.rs')).toBe(true)
+    })
+
+    it('should include TSX files when filtering by typescript', async () => {
+      const reactFixture = resolve(fixturesDir, 'react-tsx')
+      const typescript = JSON.parse((await callSearchCode({ query: 'Greeting', directory: reactFixture, language: 'typescript' })).content[0].text)
+      const tsx = JSON.parse((await callSearchCode({ query: 'Greeting', directory: reactFixture, language: 'tsx' })).content[0].text)
+
+      expect(typescript.results.some((r: any) => r.path.endsWith('Greeting.tsx'))).toBe(true)
+      expect(tsx.results.map((r: any) => r.path)).toEqual(typescript.results.map((r: any) => r.path))
+    })
+
+    it('should list supported languages for an unknown name', async () => {
+      await expect(callSearchCode({
+        query: 'Calculator',
+        directory: multiLangFixture,
+        language: 'cobol',
+      })).rejects.toThrow(/Unknown language: cobol\. Supported languages: .*typescript/)
+    })
+  })
+
+  describe('Include and Exclude Globs', () => {
+    const multiLangFixture = resolve(fixturesDir, 'multi-lang')
+
+    it('should only search files matching include globs', async () => {
+      const result = await callSearchCode({
+        query: 'divide',
+        directory: multiLangFixture,
+        include: ['rust/**/*.rs'],
+        maxResults: 50,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results.length).toBeGreaterThan(0)
+      expect(content.results.every((r: any) => r.path.endsWith('.rs'))).toBe(true)
+    })
+
+    it('should let exclude win over include', async () => {
+      const result = await callSearchCode({
+        query: 'divide',
+        directory: multiLangFixture,
+        include: ['*.rs', '*.py'],
+        exclude: ['rust'],
+        maxResults: 50,
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.results.length).toBeGreaterThan(0)
+      expect(content.results.every((r: any) => r.path.endsWith('.py'))).toBe(true)
+    })
+
+    it('should explain when no files survive the filters', async () => {
+      const result = await callSearchCode({
+        query: 'divide',
+        directory: multiLangFixture,
+        include: ['rust/**'],
+        exclude: ['rust'],
+      })
+
+      const content = JSON.parse(result.content[0].text)
+      expect(content.totalResults).toBe(0)
+      expect(content.message).toContain('No indexed files match the search filters')
+      expect(content.message).toContain('exclude: rust')
+    })
+  })
+
+  describe('Pagination', () => {
+    const multiLangFixture = resolve(fixturesDir, 'multi-lang')
+
+    async function searchPage(offset: number, limit: number) {
+      const result = await callSearchCode({
+        query: 'return',
+        mode: 'regex',
+        directory: multiLangFixture,
+        offset,
+        limit,
+      })
+      return JSON.parse(result.content[0].text)
+    }
+
+    it('should page through every hit exactly once in a stable order', async () => {
+      const first = await searchPage(0, 5)
+      expect(first.totalResults).toBeGreaterThan(10)
+      expect(first.results).toHaveLength(5)
+      expect(first.hasMore).toBe(true)
+
+      const seen: any[] = []
+      for (let offset = 0; offset < first.totalResults; offset += 5) {
+        const page = await searchPage(offset, 5)
+        expect(page.totalResults).toBe(first.totalResults)
+        seen.push(...page.results)
+      }
+
+      expect(seen).toHaveLength(first.totalResults)
+      expect(new Set(seen.map(r => `${r.path}:${r.startLine}:${r.startColumn}`)).size).toBe(seen.length)
+      for (let i = 1; i < seen.length; i++) {
+        const [prev, next] = [seen[i - 1], seen[i]]
+        const order = prev.path.localeCompare(next.path) || prev.startLine - next.startLine || prev.startColumn - next.startColumn
+        expect(order).toBeLessThan(0)
+      }
+    })
+
+    it('should report no more results on the last page', async () => {
+      const { totalResults } = await searchPage(0, 1)
+      const last = await searchPage(totalResults - 1, 5)
+
+      expect(last.results).toHaveLength(1)
+      expect(last.hasMore).toBe(false)
+    })
+
+    it('should clamp limits above the maximum page size', async () => {
+      const page = await searchPage(0, 100000)
+      expect(page.limit).toBe(500)
+    })
+  })
+
+  describe('Node Text', () => {
+    const multiLangFixture = resolve(fixturesDir, 'multi-lang')
+
+    it('should include the whole declaration around a match', async () => {
+      const result = await callSearchCode({ query: 'a / b', mode: 'regex', directory: multiLangFixture, pathPattern: 'ts/', includeNodeText: true })
+
+      const [hit] = JSON.parse(result.content[0].text).results
+      expect(hit.nodeText.type).toBe('method_definition')
+      expect([hit.nodeText.startLine, hit.nodeText.endLine]).toEqual([33, 40])
+      expect(hit.nodeText.text).toMatch(/^divide\(a: number, b: number\): number \{[\s\S]*return result;\n {2}\}$/)
+      expect(hit.nodeText.truncated).toBeUndefined()
+    })
+
+    it('should cut long text at a statement boundary', async () => {
+      const result = await callSearchCode({ query: 'a / b', mode: 'regex', directory: multiLangFixture, pathPattern: 'ts/', includeNodeText: true, maxNodeBytes: 100 })
+
+      const [hit] = JSON.parse(result.content[0].text).results
+      expect(hit.nodeText.truncated).toBe(true)
+      expect(Buffer.byteLength(hit.nodeText.text)).toBeLessThanOrEqual(100)
+      expect(hit.nodeText.text).toMatch(/throw new Error\('Division by zero'\);$/)
+    })
+
+    it('should leave node text out unless requested', async () => {
+      const result = await callSearchCode({ query: 'a / b', mode: 'regex', directory: multiLangFixture, pathPattern: 'ts/' })
+
+      const [hit] = JSON.parse(result.content[0].text).results
+      expect(hit.nodeText).toBeUndefined()
+    })
+
+    it('should reject a non-positive maxNodeBytes', async () => {
+      await expect(callSearchCode({ query: 'a / b', mode: 'regex', directory: multiLangFixture, includeNodeText: true, maxNodeBytes: 0 }))
+        .rejects.toThrow('maxNodeBytes must be a positive number')
+    })
+  })
+
+  describe('Key Paths', () => {
+    const configFixture = resolve(fixturesDir, 'config-files')
+
+    async function searchKeys(query: string, args: JsonObject = {}) {
+      const result = await callSearchCode({ query, mode: 'keyPath', directory: configFixture, ...args })
+      return JSON.parse(result.content[0].text).results
+    }
+
+    it('should find a nested key in YAML and JSON with its value', async () => {
+      const results = await searchKeys('database.host')
+
+      expect(results).toHaveLength(2)
+      expect(results[0].path).toMatch(/config\/app\.yaml$/)
+      expect(results[0].startLine).toBe(7)
+      expect(results[0].keyPath).toMatchObject({ path: 'database.host', value: { type: 'plain_scalar', text: 'db.internal', startLine: 7 } })
+      expect(results[1].path).toMatch(/settings\.json$/)
+      expect(results[1].keyPath.value).toMatchObject({ type: 'string', text: '"json.internal"', startLine: 3 })
+    })
+
+    it('should match sequence items and wildcards', async () => {
+      const items = await searchKeys('servers.*.host')
+      expect(items.map((r: any) => r.keyPath.path)).toEqual(['servers.0.host', 'servers.1.host'])
+
+      const anywhere = await searchKeys('**.host')
+      expect(anywhere).toHaveLength(5)
+    })
+
+    it('should report anchors and the anchor an alias refers to', async () => {
+      const [defaults] = await searchKeys('defaults')
+      expect(defaults.keyPath.anchor).toEqual({ name: 'db_defaults', line: 1, column: 10 })
+
+      const [replica] = await searchKeys('replica.database')
+      expect(replica.keyPath.alias).toEqual({ name: 'db_defaults', line: 11, column: 12, anchor: { line: 1, column: 10 } })
+
+      const [merge] = await searchKeys('database.<<')
+      expect(merge.keyPath.alias).toMatchObject({ name: 'db_defaults', line: 6 })
+    })
+
+    it('should point duplicate keys at their first occurrence', async () => {
+      const ttl = await searchKeys('cache.ttl')
+      expect(ttl.map((r: any) => r.keyPath.duplicateOf)).toEqual([undefined, { line: 20, column: 2 }])
+
+      const level = await searchKeys('logging.level')
+      expect(level[1].keyPath.duplicateOf).toEqual({ line: 8, column: 4 })
+    })
+
+    it('should reject an empty path segment', async () => {
+      await expect(searchKeys('database..host')).rejects.toThrow(/Invalid key path/)
+    })
+  })
+
+  describe('Declaration Kinds', () => {
+    const multiLangFixture = resolve(fixturesDir, 'multi-lang')
+
+    async function searchKind(kind: string, args: JsonObject = {}) {
+      const result = await callSearchCode({ query: '', kind, directory: multiLangFixture, maxResults: 100, ...args })
+      return JSON.parse(result.content[0].text).results
+    }
+
+    it('should list every declaration of a kind', async () => {
+      const structs = await searchKind('struct', { language: 'rust' })
+
+      expect(structs.map((r: any) => r.name)).toEqual(expect.arrayContaining(['CalculationResult', 'Calculator']))
+      expect(structs.every((r: any) => r.type === 'struct')).toBe(true)
+    })
+
+    it('should require the modifiers a kind is prefixed with', async () => {
+      const all = await searchKind('fn', { language: 'rust' })
+      const pub = await searchKind('pub_fn', { language: 'rust' })
+
+      expect(all.some((r: any) => r.name === 'main')).toBe(true)
+      expect(pub.some((r: any) => r.name === 'main')).toBe(false)
+      expect(pub.some((r: any) => /\badd$/.test(r.name))).toBe(true)
+
+      const exported = await searchKind('pub_fn', { language: 'typescript' })
+      expect(exported.map((r: any) => r.name)).toEqual(['createCalculator'])
+    })
+
+    it('should still match the query against declaration names', async () => {
+      const results = await searchKind('class', { query: 'Calc', language: 'typescript' })
+      expect(results.map((r: any) => r.name)).toEqual(['Calculator'])
+    })
+
+    it('should list the valid kinds for the language when the kind is unknown', async () => {
+      await expect(searchKind('structure', { language: 'rust' }))
+        .rejects.toThrow(/Unknown kind: structure\. Valid kinds for rust: .*\bstruct\b.*async_/)
+    })
+
+    it('should reject kind outside name mode', async () => {
+      await expect(searchKind('struct', { mode: 'regex', query: 'x' }))
+        .rejects.toThrow('kind applies only to name mode')
+    })
+  })
+
+  describe('Line Ranges', () => {
+    const multiLangFixture = resolve(fixturesDir, 'multi-lang')
+
+    it('should only search the lines of the span', async () => {
+      const result = await callSearchCode({
+        query: 'recordOperation',
+        mode: 'regex',
+        directory: multiLangFixture,
+        file: 'ts/calculator.ts',
+        startLine: 33,
+        endLine: 40,
+      })
+      const content = JSON.parse(result.content[0].text)
+
+      expect(content.results.map((r: any) => r.startLine)).toEqual([38])
+      expect(content.range).toEqual({ file: expect.stringMatching(/ts\/calculator\.ts$/), startLine: 33, endLine: 40 })
+    })
+
+    it('should keep elements that overlap the span', async () => {
+      const result = await callSearchCode({ query: 'divide', exactMatch: true, directory: multiLangFixture, file: 'ts/calculator.ts', startLine: 36, endLine: 37 })
+      const content = JSON.parse(result.content[0].text)
+
+      expect(content.results.some((r: any) => r.name === 'divide' && r.startLine === 33)).toBe(true)
+    })
+
+    it('should clamp a span past the end of the file and report it', async () => {
+      const result = await callSearchCode({
+        query: 'recordOperation',
+        mode: 'regex',
+        directory: multiLangFixture,
+        file: 'ts/calculator.ts',
+        startLine: -5,
+        endLine: 10000,
+      })
+      const content = JSON.parse(result.content[0].text)
+
+      expect(content.range).toMatchObject({ startLine: 1, endLine: 68 })
+      expect(content.results.length).toBeGreaterThan(5)
+    })
+
+    it('should reject a line span without a file or in reverse order', async () => {
+      await expect(callSearchCode({ query: 'x', directory: multiLangFixture, startLine: 1 }))
+        .rejects.toThrow('startLine and endLine require file')
+      await expect(callSearchCode({ query: 'x', directory: multiLangFixture, file: 'ts/calculator.ts', startLine: 9, endLine: 3 }))
+        .rejects.toThrow('startLine must not be after endLine')
+    })
+  })
+
+  describe('Context Lines', () => {
+    const multiLangFixture = resolve(fixturesDir, 'multi-lang')
+
+    it('should include surrounding lines with their line numbers', async () => {
+      const result = await callSearchCode({
+        query: 'def divide',
+        mode: 'regex',
+        directory: multiLangFixture,
+        contextBefore: 1,
+        contextAfter: 2,
+      })
+
+      const [hit] = JSON.parse(result.content[0].text).results
+      expect(hit.contextStartLine).toBe(hit.startLine - 1)
+      expect(hit.contextEndLine).toBe(hit.startLine + 2)
+      expect(hit.context.split('\n')).toHaveLength(4)
+      expect(hit.context.split('\n')[1]).toContain('def divide')
+    })
+
+    it('should clamp context windows to the file boundaries', async () => {
+      const result = await callSearchCode({
+        query: 'Calculator',
+        directory: multiLangFixture,
+        language: 'typescript',
+        exactMatch: true,
+        contextBefore: 10000,
+        contextAfter: 10000,
+      })
+
+      const [hit] = JSON.parse(result.content[0].text).results
+      expect(hit.contextStartLine).toBe(1)
+      expect(hit.context.split('\n')).toHaveLength(hit.contextEndLine)
+    })
+
+    it('should keep the bare matched line when no context is requested', async () => {
+      const result = await callSearchCode({
+        query: 'def divide',
+        mode: 'regex',
+        directory: multiLangFixture,
+      })
+
+      const [hit] = JSON.parse(result.content[0].text).results
+      expect(hit.context).toBe(hit.context.trim())
+      expect(hit.contextStartLine).toBeUndefined()
+    })
+  })
+
+  describe('Scope Filter', () => {
+    const scopeFixture = resolve(fixturesDir, 'scope-filter')
+
+    async function searchScope(args: JsonObject) {
+      const result = await callSearchCode({ mode: 'regex', directory: scopeFixture, maxResults: 50, ...args })
+      return JSON.parse(result.content[0].text).results
+    }
+
+    it('should only return matches inside comments', async () => {
+      const results = await searchScope({ query: 'TODO:', scope: 'comment' })
+
+      expect(results.map((r: any) => r.startLine)).toEqual([1, 5])
+    })
+
+    it('should only return matches inside string literals', async () => {
+      const results = await searchScope({ query: 'TODO', scope: 'string' })
+
+      expect(results.map((r: any) => r.startLine)).toEqual([2, 10])
+    })
+
+    it('should only return matches in code', async () => {
+      const results = await searchScope({ query: 'TODO', scope: 'code' })
+
+      expect(results.length).toBeGreaterThan(0)
+      expect(results.every((r: any) => r.context.includes('TODO_PREFIX'))).toBe(true)
+    })
+
+    it('should treat matches spanning a comment boundary as code', async () => {
+      const query = 'memory\\nexport'
+      const asComment = await searchScope({ query, regexFlags: 's', scope: 'comment' })
+      const asCode = await searchScope({ query, regexFlags: 's', scope: 'code' })
+
+      expect(asComment).toHaveLength(0)
+      expect(asCode).toHaveLength(1)
+    })
+
+    it('should reject unknown scopes', async () => {
+      await expect(searchScope({ query: 'TODO', scope: 'docs' })).rejects.toThrow(/Invalid scope: docs/)
+    })
+
+    it('should reject scope in modes that cannot honor it', async () => {
+      for (const mode of ['name', 'fuzzy', 'keyPath']) {
+        await expect(searchScope({ query: 'TODO', mode, scope: 'comment' })).rejects.toThrow(`scope applies only to regex, structural and tree-sitter searches, not ${mode}`)
+      }
+    })
+  })
+
+  describe('Test Scope', () => {
+    const testScopesFixture = resolve(fixturesDir, 'test-scopes')
+
+    async function searchPaths(args: JsonObject) {
+      const result = await callSearchCode({ directory: testScopesFixture, sort: 'path', maxResults: 50, ...args })
+      return JSON.parse(result.content[0].text).results.map((r: any) => `${r.path.slice(testScopesFixture.length + 1)}:${r.startLine}`)
+    }
+
+    it('should search test code by default', async () => {
+      expect(await searchPaths({ query: 'add\\(', mode: 'regex' })).toEqual([
+        'src/calculator.rs:1',
+        'src/calculator.rs:6',
+        'src/calculator.rs:15',
+        'tests/integration.rs:5',
+      ])
+    })
+
+    it('should leave out test files and inline Rust tests', async () => {
+      expect(await searchPaths({ query: 'add\\(', mode: 'regex', testScope: 'exclude' })).toEqual([
+        'src/calculator.rs:1',
+        'src/calculator.rs:6',
+      ])
+      expect(await searchPaths({ query: 'compute_total', testScope: 'exclude' })).toEqual(['app/pricing.py:1'])
+    })
+
+    it('should search only test code', async () => {
+      expect(await searchPaths({ query: 'add\\(', mode: 'regex', testScope: 'only' })).toEqual([
+        'src/calculator.rs:15',
+        'tests/integration.rs:5',
+      ])
+      expect(await searchPaths({ query: 'formatTotal', mode: 'regex', testScope: 'only' })).toEqual([
+        'src/format.test.ts:2',
+        'src/format.test.ts:4',
+        'src/format.test.ts:6',
+      ])
+    })
+
+    it('should treat Python test_ files as tests', async () => {
+      expect(await searchPaths({ query: 'compute_total', testScope: 'only' })).toEqual(['app/test_pricing.py:4'])
+    })
+
+    it('should reject unknown test scopes', async () => {
+      await expect(callSearchCode({ query: 'add', directory: testScopesFixture, testScope: 'tests' })).rejects.toThrow('Invalid testScope')
+    })
+  })
+
+  describe('Word Boundaries', () => {
+    const wordFixture = resolve(fixturesDir, 'word-boundaries')
+
+    async function searchNames(args: JsonObject) {
+      const result = await callSearchCode({ directory: wordFixture, maxResults: 50, ...args })
+      return JSON.parse(result.content[0].text).results.map((r: any) => r.name)
+    }
+
+    it('should match substrings without boundary options', async () => {
+      const names = await searchNames({ query: 'add' })
+
+      expect(names).toEqual(expect.arrayContaining(['add', 'address', 'addUser', 'padding']))
+    })
+
+    it('should only match whole words with wholeWord', async () => {
+      const names = await searchNames({ query: 'add', wholeWord: true })
+
+      expect(names).toContain('add')
+      expect(names).not.toContain('address')
+      expect(names).not.toContain('addUser')
+      expect(names).not.toContain('padding')
+    })
+
+    it('should match camelCase and snake_case segments with subwordAware', async () => {
+      const names = await searchNames({ query: 'user', subwordAware: true })
+
+      expect(names).toEqual(expect.arrayContaining(['addUser', 'getUser', 'get_user_name']))
+      expect(names).not.toContain('username')
+    })
+
+    it('should match multi-segment queries against runs of segments', async () => {
+      expect(await searchNames({ query: 'userName', subwordAware: true })).toEqual(['get_user_name'])
+      expect(await searchNames({ query: 'server', subwordAware: true })).toEqual(['HTTPServer'])
+      expect(await searchNames({ query: 'ser', subwordAware: true })).toEqual([])
+    })
+
+    it('should rank segments at the start of a name first', async () => {
+      const names = await searchNames({ query: 'add', subwordAware: true })
+
+      expect(names.slice(0, 2)).toEqual(['add', 'addUser'])
+      expect(names).not.toContain('address')
+    })
+
+    it('should reject boundary options outside name mode', async () => {
+      await expect(searchNames({ query: 'add', mode: 'regex', wholeWord: true })).rejects.toThrow(/only to name mode/)
+    })
+  })
+
+  describe('Case Sensitivity', () => {
+    const wordFixture = resolve(fixturesDir, 'word-boundaries')
+
+    async function searchNames(args: JsonObject) {
+      const result = await callSearchCode({ directory: wordFixture, maxResults: 50, fuzzyThreshold: 101, ...args })
+      return JSON.parse(result.content[0].text).results.map((r: any) => r.name)
+    }
+
+    it('should match names case-sensitively by default', async () => {
+      expect(await searchNames({ query: 'httpserver' })).toEqual([])
+      expect(await searchNames({ query: 'HTTPServer' })).toEqual(['HTTPServer'])
+    })
+
+    it('should fold case in name mode with caseInsensitive', async () => {
+      expect(await searchNames({ query: 'httpserver', caseInsensitive: true })).toEqual(['HTTPServer'])
+      expect(await searchNames({ query: 'GRÖSSE', caseInsensitive: true })).toEqual(['größe'])
+    })
+
+    it('should fold case in regex mode with caseInsensitive', async () => {
+      expect(await searchNames({ query: 'function GRÖ', mode: 'regex' })).toEqual([])
+      expect(await searchNames({ query: 'function GRÖ', mode: 'regex', caseInsensitive: true })).toEqual(['function grö'])
+    })
+  })
+
+  describe('SARIF Output', () => {
+    it('should return a SARIF log with 1-based regions', async () => {
+      const result = await callSearchCode({ query: 'TestUser', directory: positiveFixture, format: 'sarif' })
+      const log = JSON.parse(result.content[0].text)
+
+      expect(log.version).toBe('2.1.0')
+      const run = log.runs[0]
+      expect(run.properties.query).toBe('TestUser')
+      expect(run.results.length).toBeGreaterThan(0)
+      expect(run.results.length).toBe(Math.min(run.properties.totalResults, run.properties.limit))
+
+      for (const sarifResult of run.results) {
+        expect(sarifResult.ruleId).toBe('search/match')
+        const { artifactLocation, region } = sarifResult.locations[0].physicalLocation
+        expect(artifactLocation.uriBaseId).toBe('%SRCROOT%')
+        expect(artifactLocation.uri.startsWith('/')).toBe(false)
+        expect(region.startLine).toBeGreaterThanOrEqual(1)
+        expect(region.startColumn).toBeGreaterThanOrEqual(1)
+        expect(region.byteOffset).toBeGreaterThanOrEqual(0)
+      }
+    })
+
+    it('should match the JSON result positions', async () => {
+      const args = { query: 'TestUser', directory: positiveFixture, maxResults: 1 }
+      const json = JSON.parse((await callSearchCode(args)).content[0].text)
+      const log = JSON.parse((await callSearchCode({ ...args, format: 'sarif' })).content[0].text)
+
+      const region = log.runs[0].results[0].locations[0].physicalLocation.region
+      expect(region.startLine).toBe(json.results[0].startLine)
+      expect(region.startColumn).toBe(json.results[0].startColumn + 1)
+    })
+
+    it('should reject unknown formats', async () => {
+      await expect(callSearchCode({ query: 'TestUser', directory: positiveFixture, format: 'xml' }))
+        .rejects.toThrow('Invalid format: xml')
+    })
+  })
+
+  describe('Markdown Output', () => {
+    it('should return a markdown document with a fenced section per file', async () => {
+      const args = { query: 'TestUser', directory: positiveFixture, maxResults: 2 }
+      const json = JSON.parse((await callSearchCode(args)).content[0].text)
+      const markdown = (await callSearchCode({ ...args, format: 'markdown' })).content[0].text
+
+      expect(markdown.startsWith(`# ${json.totalResults} matches for \`TestUser\``)).toBe(true)
+      expect(markdown).toContain('## `src/index.ts`')
+      expect(markdown).toContain('```typescript\n→ ')
+      expect(markdown).toContain(`**\`${json.results[0].name}\`** ${json.results[0].type} at line ${json.results[0].startLine}`)
+    })
+
+    it('should reject markdown with countOnly', async () => {
+      await expect(callSearchCode({ query: 'TestUser', directory: positiveFixture, countOnly: true, format: 'markdown' }))
+        .rejects.toThrow('cannot be combined with format markdown')
+    })
+  })
+
+  describe('Cancellation', () => {
+    it('should reject when the request is already cancelled', async () => {
+      const controller = new AbortController()
+      controller.abort()
+
+      const request = handleToolRequest({
+        params: { name: 'search_code', arguments: { query: 'TestUser', directory: positiveFixture } },
+      }, { signal: controller.signal })
+
+      await expect(request).rejects.toMatchObject({ code: 'CANCELLED' })
+    })
+
+    it('should cancel an in-flight search when a newer one starts on the same project', async () => {
+      const first = callSearchCode({ query: 'TestUser', directory: positiveFixture })
+      const second = callSearchCode({ query: 'createTestUser', directory: positiveFixture })
+
+      await expect(first).rejects.toMatchObject({ code: 'CANCELLED' })
+      const content = JSON.parse((await second).content[0].text)
+      expect(content.query).toBe('createTestUser')
+      expect(content.results.length).toBeGreaterThan(0)
+    })
+
+    it('should not cancel searches of other projects', async () => {
+      const [positive, empty] = await Promise.all([
+        callSearchCode({ query: 'TestUser', directory: positiveFixture }),
+        callSearchCode({ query: 'TestUser', directory: emptyFixture }),
+      ])
+
+      expect(JSON.parse(positive.content[0].text).results.length).toBeGreaterThan(0)
+      expect(JSON.parse(empty.content[0].text).results).toEqual([])
+    })
+  })
+
+
+  describe('Source Encodings', () => {
+    const encodedFixture = resolve(fixturesDir, 'encoded-sources')
+
+    async function findOne(query: string) {
+      const content = JSON.parse((await callSearchCode({ query, directory: encodedFixture, exactMatch: true })).content[0].text)
+      expect(content.results).toHaveLength(1)
+      return content.results[0]
+    }
+
+    it('should strip a UTF-8 BOM before parsing', async () => {
+      expect((await findOne('bomGreeting')).startLine).toBe(1)
+      expect((await findOne('BomLedger')).startLine).toBe(5)
+
+      // A leftover BOM would sit before `export` on the first line
+      const anchored = await callSearchCode({ query: '^export function bomGreeting', mode: 'regex', directory: encodedFixture })
+      expect(JSON.parse(anchored.content[0].text).results).toHaveLength(1)
+    })
+
+    it('should find symbols in UTF-16 LE and BE files', async () => {
+      const invoice = await findOne('formatUtf16Invoice')
+      expect(invoice.path).toBe(resolve(encodedFixture, 'src/utf16le.ts'))
+      expect(invoice.startLine).toBe(7)
+      expect((await findOne('Utf16Invoice')).startLine).toBe(2)
+
+      const total = await findOne('utf16_big_endian_total')
+      expect(total.path).toBe(resolve(encodedFixture, 'src/utf16be.py'))
+      expect(total.startLine).toBe(1)
+      expect((await findOne('Utf16Report')).startLine).toBe(5)
+    })
+
+    it('should report SARIF byte offsets into the original UTF-16 bytes', async () => {
+      const invoice = await findOne('formatUtf16Invoice')
+      const log = JSON.parse((await callSearchCode({
+        query: 'formatUtf16Invoice',
+        directory: encodedFixture,
+        exactMatch: true,
+        format: 'sarif',
+      })).content[0].text)
+
+      const lines = readFileSync(invoice.path).toString('utf16le', 2).split('\n')
+      const charOffset = lines.slice(0, invoice.startLine - 1).join('\n').length + 1 + invoice.startColumn
+      const region = log.runs[0].results[0].locations[0].physicalLocation.region
+      expect(region.byteOffset).toBe(2 + charOffset * 2)
+    })
+  })
+
+  describe('Grouping', () => {
+    const rankingFixture = resolve(fixturesDir, 'relevance-ranking')
+
+    async function search(args: JsonObject) {
+      const result = await callSearchCode({ query: 'ChatProvider', mode: 'regex', directory: rankingFixture, maxResults: 100, ...args })
+      return JSON.parse(result.content[0].text)
+    }
+
+    it('should group the page by file with per-file counts', async () => {
+      const flat = await search({})
+      const grouped = await search({ groupBy: 'file' })
+
+      expect(grouped.results).toBeUndefined()
+      expect(grouped.totalResults).toBe(flat.totalResults)
+      expect(grouped.totalFiles).toBe(new Set(flat.results.map((r: any) => r.path)).size)
+      expect(grouped.totalFiles).toBeGreaterThan(1)
+
+      const counts = grouped.groups.map((group: any) => group.count)
+      expect(counts).toEqual([...counts].sort((a: number, b: number) => b - a))
+      expect(counts.reduce((sum: number, count: number) => sum + count, 0)).toBe(flat.results.length)
+      for (const group of grouped.groups) {
+        expect(group.matches).toHaveLength(group.count)
+        expect(group.matches[0].path).toBeUndefined()
+        expect(flat.results.filter((r: any) => r.path === group.file)).toHaveLength(group.count)
+      }
+    })
+
+    it('should sort groups by path on request', async () => {
+      const grouped = await search({ groupBy: 'file', groupOrder: 'path' })
+
+      const files = grouped.groups.map((group: any) => group.file)
+      expect(files).toEqual([...files].sort((a: string, b: string) => a.localeCompare(b)))
+    })
+
+    it('should reject unknown grouping options', async () => {
+      await expect(search({ groupBy: 'language' })).rejects.toThrow('Invalid groupBy')
+      await expect(search({ groupBy: 'file', groupOrder: 'size' })).rejects.toThrow('Invalid groupOrder')
+    })
+  })
+
+  describe('Count Only', () => {
+    const rankingFixture = resolve(fixturesDir, 'relevance-ranking')
+
+    async function search(args: JsonObject) {
+      const result = await callSearchCode({ query: 'ChatProvider', mode: 'regex', directory: rankingFixture, maxResults: 100, ...args })
+      return JSON.parse(result.content[0].text)
+    }
+
+    it('should return per-file counts instead of matches', async () => {
+      const flat = await search({})
+      const counted = await search({ countOnly: true })
+
+      expect(counted.results).toBeUndefined()
+      expect(counted.totalResults).toBe(flat.totalResults)
+      expect(counted.totalFiles).toBe(Object.keys(counted.counts).length)
+      for (const [file, count] of Object.entries(counted.counts)) {
+        expect(flat.results.filter((r: any) => r.path === file)).toHaveLength(count as number)
+      }
+      expect(Object.keys(counted.counts)).toEqual(Object.keys(counted.counts).sort())
+    })
+
+    it('should apply testScope and dedup while counting', async () => {
+      const flat = await search({ testScope: 'exclude', dedup: 'line' })
+      const counted = await search({ countOnly: true, testScope: 'exclude', dedup: 'line' })
+
+      expect(counted.totalResults).toBe(flat.totalResults)
+      expect(Object.keys(counted.counts).some(file => file.includes('__tests__'))).toBe(false)
+      expect(Object.keys(counted.counts)).toEqual(Array.from(new Set(flat.results.map((r: any) => r.path))).sort())
+    })
+
+    it('should count every match past the page size', async () => {
+      const counted = await search({ countOnly: true, maxResults: 1 })
+
+      expect(counted.totalResults).toBeGreaterThan(1)
+      expect(counted.hasMore).toBe(false)
+    })
+
+    it('should reject countOnly with groupBy or SARIF output', async () => {
+      await expect(search({ countOnly: true, groupBy: 'file' })).rejects.toThrow('cannot be combined with groupBy')
+      await expect(search({ countOnly: true, format: 'sarif' })).rejects.toThrow('cannot be combined with format sarif')
+      await expect(search({ countOnly: 'yes' })).rejects.toThrow('countOnly must be a boolean')
+    })
+  })
+
+  describe('File Audit', () => {
+    const gitignoreFixture = resolve(fixturesDir, 'gitignore-test')
+
+    async function search(args: JsonObject) {
+      const result = await callSearchCode({ directory: gitignoreFixture, ...args })
+      return JSON.parse(result.content[0].text)
+    }
+
+    it('should account for every file the search could have looked at', async () => {
+      const content = await search({ query: 'main' })
+      const { files } = content.diagnostics
+
+      expect(files.excluded.gitignore).toBeGreaterThanOrEqual(2)
+      expect(files.parsed).toBeGreaterThan(0)
+      const excluded = Object.values(files.excluded) as number[]
+      expect(files.considered).toBe(files.parsed + excluded.reduce((sum, count) => sum + count, 0))
+      expect(files.note).toBeUndefined()
+    })
+
+    it('should explain an empty result when every file was excluded', async () => {
+      const indexed = (await search({ query: 'main' })).diagnostics.files.parsed
+      const content = await search({ query: 'main', language: 'python' })
+      const { files } = content.diagnostics
+
+      expect(content.totalResults).toBe(0)
+      expect(files.parsed).toBe(0)
+      expect(files.excluded.languageFilter).toBe(indexed)
+      expect(files.note).toMatch(/^No file was searched: all \d+ were excluded \(\d+ gitignore, \d+ language filter\)$/)
+    })
+  })
+
+  describe('Timeout', () => {
+    const rankingFixture = resolve(fixturesDir, 'relevance-ranking')
+
+    async function search(args: JsonObject) {
+      const result = await callSearchCode({ query: 'ChatProvider', mode: 'regex', directory: rankingFixture, maxResults: 100, ...args })
+      return JSON.parse(result.content[0].text)
+    }
+
+    it('should return every match when the search beats the deadline', async () => {
+      const unlimited = await search({})
+      const bounded = await search({ timeoutMs: 60_000 })
+
+      expect(unlimited.timedOut).toBeUndefined()
+      expect(bounded.timedOut).toBeUndefined()
+      expect(bounded.totalResults).toBe(unlimited.totalResults)
+      expect(bounded.results).toEqual(unlimited.results)
+    })
+
+    it('should reject a timeout that is not a positive number', async () => {
+      await expect(search({ timeoutMs: 0 })).rejects.toThrow('timeoutMs must be a positive number')
+      await expect(search({ timeoutMs: '100' })).rejects.toThrow('timeoutMs must be a positive number')
+    })
+  })
 })
\ No newline at end of file
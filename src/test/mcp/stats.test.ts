@@ -0,0 +1,59 @@
+/**
+ * MCP stats tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP stats Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+
+  async function callTool(name: string, args: JsonObject = {}) {
+    const result = await handleToolRequest({
+      params: {
+        name,
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should report tree cache counters and loaded projects', async () => {
+    const directory = resolve(fixturesDir, 'simple-ts')
+    await callTool('find_references', { symbol: 'User', directory })
+
+    const content = await callTool('stats')
+
+    expect(content.treeCache).toMatchObject({
+      maxTrees: expect.any(Number),
+      residentTrees: expect.any(Number),
+      hits: expect.any(Number),
+      misses: expect.any(Number),
+      evictions: expect.any(Number),
+    })
+    expect(content.treeCache.hits + content.treeCache.misses).toBeGreaterThan(0)
+    expect(content.projects.map((project: any) => project.directory)).toContain(directory)
+  })
+
+  it('should break the named project down by language', async () => {
+    const directory = resolve(fixturesDir, 'rename-scopes')
+    const content = await callTool('stats', { directory })
+
+    expect(content.projects).toHaveLength(1)
+    const { index } = content.projects[0]
+    expect(index.totalFiles).toBeGreaterThan(0)
+    expect(index.totalSymbols).toBeGreaterThan(0)
+    expect(Object.keys(index.languages).length).toBeGreaterThan(1)
+
+    const languages = Object.values(index.languages) as { files: number, symbols: number, bytes: number }[]
+    expect(languages.reduce((sum, entry) => sum + entry.files, 0)).toBe(index.totalFiles)
+    expect(languages.reduce((sum, entry) => sum + entry.bytes, 0)).toBe(index.totalBytes)
+    expect(index.estimatedMemoryBytes).toBeGreaterThan(index.totalBytes)
+
+    expect(content.memory.estimatedIndexBytes).toBe(index.estimatedMemoryBytes)
+    expect(content.memory.heapUsedBytes).toBeGreaterThan(0)
+    expect(content.treeCache.hitRate).toBeGreaterThanOrEqual(0)
+  })
+})
\ No newline at end of file
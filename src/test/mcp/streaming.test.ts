@@ -0,0 +1,85 @@
+/**
+ * Streamed MCP tool responses - partial-result chunks and the final summary
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import { streamToolResult } from '../../mcp/streaming.js'
+import type { JsonObject, PartialToolResult } from '../../types/core.js'
+
+describe('MCP streamed responses', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const callGraphFixture = resolve(fixturesDir, 'call-graph')
+
+  async function callTool(name: string, args: JsonObject, partials?: PartialToolResult[]) {
+    const result = await handleToolRequest({
+      params: {
+        name,
+        arguments: args,
+      },
+    }, { onPartialResult: partials ? partial => partials.push(partial) : undefined })
+    return JSON.parse(result.content[0].text)
+  }
+
+  it('should reassemble to the buffered response', async () => {
+    const buffered = await callTool('call_graph', { symbol: 'roundCents', directory: callGraphFixture })
+
+    const partials: PartialToolResult[] = []
+    const final = await callTool('call_graph', { symbol: 'roundCents', directory: callGraphFixture, stream: true }, partials)
+
+    expect(final.callers).toBeUndefined()
+    expect(final.symbol).toBe('roundCents')
+    expect(final.streamed.chunks).toBe(partials.length)
+    expect(final.streamed.fields.callers).toBe(buffered.callers.length)
+
+    const { streamed, ...rest } = final
+    const arrays: Record<string, unknown[]> = Object.fromEntries(Object.keys(streamed.fields).map(field => [field, []]))
+    for (const partial of partials) {
+      expect(partial.tool).toBe('call_graph')
+      arrays[partial.field]!.push(...partial.items)
+    }
+    const reassembled = { ...rest, ...arrays }
+    expect(reassembled).toEqual(buffered)
+  })
+
+  it('should number chunks in order across fields', async () => {
+    const partials: PartialToolResult[] = []
+    await callTool('call_graph', { symbol: 'roundCents', directory: callGraphFixture, depth: 2, stream: true }, partials)
+
+    expect(partials.map(partial => partial.chunk)).toEqual(partials.map((_, i) => i))
+  })
+
+  it('should return one buffered message without stream', async () => {
+    const partials: PartialToolResult[] = []
+    const content = await callTool('call_graph', { symbol: 'roundCents', directory: callGraphFixture }, partials)
+
+    expect(partials).toEqual([])
+    expect(content.streamed).toBeUndefined()
+    expect(content.callers.length).toBeGreaterThan(0)
+  })
+
+  it('should split arrays into chunks of about the requested size', () => {
+    const items = Array.from({ length: 10 }, (_, i) => ({ id: i, text: 'x'.repeat(40) }))
+    const partials: PartialToolResult[] = []
+    const result = streamToolResult('find_duplicates', {
+      content: [{ type: 'text', text: JSON.stringify({ projectId: 'p', duplicates: items, empty: [] }) }],
+    }, partial => partials.push(partial), 200)
+
+    expect(partials.length).toBeGreaterThan(1)
+    expect(partials.every(partial => JSON.stringify(partial.items).length <= 220)).toBe(true)
+    expect(partials.flatMap(partial => partial.items)).toEqual(items)
+    expect(JSON.parse(result.content[0].text)).toEqual({
+      projectId: 'p',
+      streamed: { chunks: partials.length, fields: { duplicates: 10, empty: 0 } },
+    })
+  })
+
+  it('should pass results that are not JSON objects through', () => {
+    const partials: PartialToolResult[] = []
+    const original = { content: [{ type: 'text' as const, text: '# Report' }] }
+
+    expect(streamToolResult('analyze_code', original, partial => partials.push(partial))).toBe(original)
+    expect(partials).toEqual([])
+  })
+})
\ No newline at end of file
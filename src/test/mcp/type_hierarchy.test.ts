@@ -0,0 +1,110 @@
+/**
+ * MCP type_hierarchy tool tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { handleToolRequest } from '../../mcp/handlers.js'
+import type { JsonObject } from '../../types/core.js'
+
+describe('MCP type_hierarchy Tool', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../fixtures')
+  const implementationsFixture = resolve(fixturesDir, 'implementations')
+
+  async function callTypeHierarchy(args: JsonObject) {
+    const result = await handleToolRequest({
+      params: {
+        name: 'type_hierarchy',
+        arguments: args,
+      },
+    })
+    return JSON.parse(result.content[0].text)
+  }
+
+  function names(nodes: any[] = []): string[] {
+    return nodes.map(node => node.name)
+  }
+
+  it('should follow implemented traits and supertraits upwards', async () => {
+    const content = await callTypeHierarchy({
+      symbol: 'Square',
+      direction: 'up',
+      directory: implementationsFixture,
+    })
+
+    expect(content.root).toMatchObject({ name: 'Square', kind: 'struct' })
+    expect(content.root.subtypes).toBeUndefined()
+    expect(content.root.supertypes.map((node: any) => [node.name, node.relation])).toEqual([
+      ['Shape', 'implements'],
+      ['Polygon', 'implements'],
+    ])
+
+    const polygon = content.root.supertypes[1]
+    expect(polygon.path).toMatch(/shapes\.rs$/)
+    expect(polygon.supertypes.map((node: any) => [node.name, node.relation])).toEqual([['Shape', 'extends']])
+  })
+
+  it('should list implementing types and subtraits downwards', async () => {
+    const content = await callTypeHierarchy({
+      symbol: 'Shape',
+      direction: 'down',
+      directory: implementationsFixture,
+    })
+
+    expect(names(content.root.subtypes)).toEqual(['Circle', 'Square', 'Box', 'Polygon'])
+    expect(names(content.root.subtypes[3].subtypes)).toEqual(['Square'])
+  })
+
+  it('should combine extends and implements for TypeScript classes', async () => {
+    const content = await callTypeHierarchy({
+      symbol: 'FileHandle',
+      directory: implementationsFixture,
+    })
+
+    expect(content.direction).toBe('both')
+    expect(content.root.supertypes.map((node: any) => [node.name, node.kind, node.relation])).toEqual([
+      ['Disposable', 'interface', 'implements'],
+      ['Resource', 'class', 'extends'],
+    ])
+    expect(content.root.subtypes).toEqual([])
+  })
+
+  it('should stop at the requested depth', async () => {
+    const content = await callTypeHierarchy({
+      symbol: 'Shape',
+      direction: 'down',
+      depth: 1,
+      directory: implementationsFixture,
+    })
+
+    expect(content.root.subtypes[3].subtypes).toBeUndefined()
+  })
+
+  it('should return an empty hierarchy with a note for languages without inheritance', async () => {
+    const content = await callTypeHierarchy({
+      symbol: 'User',
+      directory: resolve(fixturesDir, 'go-example'),
+    })
+
+    expect(content.root.supertypes).toEqual([])
+    expect(content.root.subtypes).toEqual([])
+    expect(content.note).toContain('no class or trait inheritance')
+  })
+
+  it('should note unknown types', async () => {
+    const content = await callTypeHierarchy({
+      symbol: 'MissingType',
+      directory: implementationsFixture,
+    })
+
+    expect(content.note).toContain('No type named "MissingType"')
+  })
+
+  it('should reject an invalid direction', async () => {
+    await expect(callTypeHierarchy({
+      symbol: 'Shape',
+      direction: 'sideways',
+      directory: implementationsFixture,
+    })).rejects.toThrow('Invalid direction')
+  })
+})
\ No newline at end of file
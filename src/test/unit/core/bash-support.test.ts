@@ -0,0 +1,59 @@
+/**
+ * Bash language support tests - both function forms, variables, and shebang detection
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { parseFile } from '../../../core/parser.js'
+import { listSymbols } from '../../../core/symbols.js'
+import { walkDirectory } from '../../../core/file-walker.js'
+import { getLanguageByShebang } from '../../../core/languages.js'
+import type { TreeNode } from '../../../types/core.js'
+
+describe('Bash Support', () => {
+  const bashFixture = resolve(import.meta.dirname, '../../fixtures/bash-example')
+  const deployScript = resolve(bashFixture, 'scripts/deploy.sh')
+  const releaseScript = resolve(bashFixture, 'bin/release')
+
+  function functionNames(node: TreeNode): string[] {
+    return (node.children || []).filter(n => n.type === 'function').map(n => n.name!)
+  }
+
+  it('should index both function definition forms', async () => {
+    const fileNode = await parseFile(deployScript)
+
+    expect(functionNames(fileNode)).toEqual(['log', 'build_image', 'push_image'])
+  })
+
+  it('should outline top-level variables but not function locals', async () => {
+    const symbols = listSymbols(await parseFile(deployScript))
+    const byName = new Map(symbols.map(s => [s.name, s]))
+
+    expect(byName.get('DEPLOY_ENV')?.kind).toBe('variable')
+    expect(byName.get('RETRIES')?.kind).toBe('variable')
+    expect(byName.get('log')?.kind).toBe('function')
+    expect(byName.get('log')?.children).toEqual([])
+    expect(byName.has('message')).toBe(false)
+  })
+
+  it('should parse extensionless scripts by their shebang', async () => {
+    const fileNode = await parseFile(releaseScript)
+
+    expect(functionNames(fileNode)).toEqual(['tag_release'])
+    expect(listSymbols(fileNode).map(s => s.name)).toEqual(['VERSION', 'tag_release'])
+  })
+
+  it('should include shebang scripts when walking for shell files', async () => {
+    const files = await walkDirectory(bashFixture, { languages: ['bash'] })
+
+    expect(files.sort()).toEqual([releaseScript, deployScript])
+  })
+
+  it('should recognize interpreters named directly or through env', () => {
+    expect(getLanguageByShebang('#!/bin/sh\necho hi')?.name).toBe('bash')
+    expect(getLanguageByShebang('#!/usr/bin/env bash')?.name).toBe('bash')
+    expect(getLanguageByShebang('#! /usr/bin/env -S python3.12 -u')?.name).toBe('python')
+    expect(getLanguageByShebang('#!/usr/bin/perl')).toBeUndefined()
+    expect(getLanguageByShebang('echo "#!/bin/sh"')).toBeUndefined()
+  })
+})
\ No newline at end of file
@@ -0,0 +1,64 @@
+/**
+ * C and C++ support tests - header prototypes told apart from the definitions in sources
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { extractAllNodes, parseFile } from '../../../core/parser.js'
+import { listSymbols } from '../../../core/symbols.js'
+
+describe('C and C++ Support', () => {
+  const fixtureDir = resolve(import.meta.dirname, '../../fixtures/c-headers')
+  const header = resolve(fixtureDir, 'include/vector.h')
+  const source = resolve(fixtureDir, 'src/vector.c')
+  const cppHeader = resolve(fixtureDir, 'include/circle.hpp')
+
+  it('should outline header prototypes as declaration-only functions', async () => {
+    const symbols = listSymbols(await parseFile(header))
+
+    expect(symbols.map(s => [s.name, s.kind, s.declarationOnly])).toEqual([
+      ['vector', 'struct', undefined],
+      ['vector_new', 'function', true],
+      ['vector_push', 'function', true],
+      ['vector_sum', 'function', true],
+    ])
+  })
+
+  it('should flag prototypes but not definitions in a source file', async () => {
+    const symbols = listSymbols(await parseFile(source))
+    const grow = symbols.filter(s => s.name === 'grow')
+
+    expect(grow.map(s => s.declarationOnly)).toEqual([true, undefined])
+    expect(symbols.find(s => s.name === 'vector_sum')?.declarationOnly).toBeUndefined()
+  })
+
+  it('should carry the flag on indexed functions', async () => {
+    const headerSum = extractAllNodes(await parseFile(header)).find(n => n.type === 'function' && n.name === 'vector_sum')
+    const sourceSum = extractAllNodes(await parseFile(source)).find(n => n.type === 'function' && n.name === 'vector_sum')
+
+    expect(headerSum?.declarationOnly).toBe(true)
+    expect(sourceSum?.declarationOnly).toBeUndefined()
+  })
+
+  it('should not mistake function pointer variables for prototypes', async () => {
+    const symbols = listSymbols(await parseFile(header))
+    const nodes = extractAllNodes(await parseFile(source))
+
+    expect(symbols.find(s => s.name.includes('on_resize'))).toBeUndefined()
+    expect(nodes.some(n => n.name === 'on_resize' && n.declarationOnly)).toBe(false)
+  })
+
+  it('should nest C++ method declarations under their class and namespace', async () => {
+    const [namespace] = listSymbols(await parseFile(cppHeader))
+
+    expect(namespace).toMatchObject({ name: 'geometry', kind: 'namespace' })
+    const [circle, distance] = namespace!.children
+    expect(circle).toMatchObject({ name: 'Circle', kind: 'class' })
+    expect(circle!.children.map(s => [s.name, s.kind, s.declarationOnly])).toEqual([
+      ['Circle', 'method', true],
+      ['area', 'method', true],
+      ['radius', 'method', undefined],
+    ])
+    expect(distance).toMatchObject({ name: 'distance', kind: 'function', declarationOnly: true })
+  })
+})
\ No newline at end of file
@@ -0,0 +1,68 @@
+/**
+ * C# language support tests - namespaces, properties, and generic symbol names
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { extractAllNodes, parseFile } from '../../../core/parser.js'
+import { listSymbols } from '../../../core/symbols.js'
+import { findDefinition } from '../../../core/definitions.js'
+import { searchCode } from '../../../core/search.js'
+
+describe('C# Support', () => {
+  const calculatorFile = resolve(import.meta.dirname, '../../fixtures/multi-lang/csharp/Calculator.cs')
+
+  it('should extract classes, interfaces, and methods', async () => {
+    const fileNode = await parseFile(calculatorFile)
+    const nodes = extractAllNodes(fileNode)
+
+    const classes = nodes.filter(n => n.type === 'class').map(n => n.name)
+    expect(classes).toContain('ICalculator')
+    expect(classes).toContain('Calculator')
+    expect(classes).toContain('ResultCache<TKey, TValue>')
+
+    const functions = nodes.filter(n => n.type === 'function').map(n => n.name)
+    expect(functions).toContain('Divide')
+    expect(functions).toContain('Map<TResult>')
+  })
+
+  it('should find generic methods by their bare name in search', async () => {
+    const fileNode = await parseFile(calculatorFile)
+    const results = searchCode('Map', extractAllNodes(fileNode), { exactMatch: true })
+
+    expect(results.map(r => r.node.name)).toEqual(['Map<TResult>'])
+  })
+
+  it('should outline namespaces, properties, and generic names', async () => {
+    const fileNode = await parseFile(calculatorFile)
+    const [namespace] = listSymbols(fileNode)
+
+    expect(namespace!.name).toBe('MultiLang.Calculator')
+    expect(namespace!.kind).toBe('namespace')
+
+    const calculator = namespace!.children.find(s => s.name === 'Calculator')!
+    const members = new Map(calculator.children.map(s => [s.name, s.kind]))
+    expect(members.get('HistoryCount')).toBe('property')
+    expect(members.get('Calculator')).toBe('constructor')
+    expect(members.get('Map<TResult>')).toBe('method')
+
+    const cache = namespace!.children.find(s => s.kind === 'class' && s.name.startsWith('ResultCache'))!
+    expect(cache.name).toBe('ResultCache<TKey, TValue>')
+    expect(cache.children.map(s => s.name)).toEqual(['Count', 'GetOrAdd'])
+  })
+
+  it('should resolve definitions by bare or generic name', async () => {
+    const files = [await parseFile(calculatorFile)]
+
+    const bare = findDefinition('Map', files)
+    expect(bare).toHaveLength(1)
+    expect(bare[0]!.qualifiedName).toBe('Map<TResult>')
+
+    const generic = findDefinition('ResultCache<TKey, TValue>', files)
+    expect(generic).toHaveLength(1)
+    expect(generic[0]!.kind).toBe('class')
+
+    const property = findDefinition('HistoryCount', files)
+    expect(property[0]!.kind).toBe('property')
+  })
+})
\ No newline at end of file
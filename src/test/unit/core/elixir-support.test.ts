@@ -0,0 +1,74 @@
+/**
+ * Elixir language support tests - modules, functions and macros defined by calls, and multi-clause grouping
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { extractAllNodes, parseFile } from '../../../core/parser.js'
+import { listSymbols, listDeclarations, compileKindFilter, matchesKind } from '../../../core/symbols.js'
+import { findDefinition } from '../../../core/definitions.js'
+import { getLanguageByExtension } from '../../../core/languages.js'
+import { findSymbol } from '../../helpers/symbols.js'
+
+describe('Elixir Support', () => {
+  const fixtureDir = resolve(import.meta.dirname, '../../fixtures/elixir-example/lib')
+  const calculatorFile = resolve(fixtureDir, 'calculator.ex')
+  const userFile = resolve(fixtureDir, 'user.ex')
+
+  it('should extract functions, macros and modules', async () => {
+    const nodes = extractAllNodes(await parseFile(calculatorFile))
+    const functions = nodes.filter(n => n.type === 'function').map(n => n.name)
+    const classes = nodes.filter(n => n.type === 'class').map(n => n.name)
+
+    expect(functions).toEqual(expect.arrayContaining(['add', 'divide', 'factorial', 'is_prime?', 'unless_zero']))
+    expect(functions).not.toContain('def')
+    expect(classes).toEqual(['Example.Calculator', 'Example.MathUtils'])
+  })
+
+  it('should nest functions and macros under their module', async () => {
+    const symbols = listSymbols(await parseFile(calculatorFile))
+
+    expect(symbols.map(s => [s.name, s.kind])).toEqual([
+      ['Example.Calculator', 'module'],
+      ['Example.MathUtils', 'module'],
+    ])
+    expect(symbols[1]!.children.map(s => [s.name, s.kind])).toEqual([
+      ['factorial', 'function'],
+      ['is_prime?', 'function'],
+      ['fibonacci', 'function'],
+      ['unless_zero', 'macro'],
+    ])
+  })
+
+  it('should group pattern-matched clauses into one symbol', async () => {
+    const symbols = listSymbols(await parseFile(calculatorFile))
+    const calculator = symbols[0]!.children
+    const isPrime = findSymbol(symbols[1]!.children, 'is_prime?')!
+
+    expect(calculator.map(s => s.name)).toEqual(['add', 'subtract', 'multiply', 'divide'])
+    expect(findSymbol(calculator, 'divide')?.clauses).toEqual([
+      { startLine: 16, endLine: 16 },
+      { startLine: 17, endLine: 17 },
+    ])
+    expect(isPrime).toMatchObject({ startLine: 30, endLine: 36 })
+    expect(isPrime.clauses?.map(c => c.startLine)).toEqual([30, 31, 32, 33])
+    expect(findSymbol(calculator, 'add')?.clauses).toBeUndefined()
+  })
+
+  it('should tell public functions from private ones', async () => {
+    const declarations = listDeclarations(await parseFile(userFile))
+    const elixir = getLanguageByExtension('.ex')!
+    const publicNames = declarations.filter(d => matchesKind(d, compileKindFilter('pub_fn', [elixir]))).map(d => d.name)
+
+    expect(publicNames).toContain('create_user')
+    expect(publicNames).not.toContain('generate_id')
+    expect(declarations.some(d => d.name === 'generate_id')).toBe(true)
+  })
+
+  it('should resolve definitions with their declared kind', async () => {
+    const files = [await parseFile(calculatorFile)]
+
+    expect(findDefinition('factorial', files).map(d => d.kind)).toEqual(['function', 'function'])
+    expect(findDefinition('unless_zero', files)[0]?.kind).toBe('macro')
+  })
+})
\ No newline at end of file
@@ -0,0 +1,56 @@
+/**
+ * Fuzzy subsequence scoring and ranking tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { scoreSubsequence, searchFuzzy } from '../../../core/search.js'
+import type { TreeNode } from '../../../types/core.js'
+
+function element(id: string, name: string, path: string): TreeNode {
+  return { id, type: 'class', name, path, startLine: 1, endLine: 1 }
+}
+
+describe('Fuzzy Search', () => {
+  it('should match abbreviated subsequences on word boundaries', () => {
+    expect(scoreSubsequence('ChtPrvdr', 'ChatProvider')).toBeGreaterThan(40)
+    expect(scoreSubsequence('ChtPrvdr', 'ChatProvider')).toBeGreaterThan(scoreSubsequence('ChtPrvdr', 'ChatterProviderFactory'))
+  })
+
+  it('should return 0 when characters are out of order', () => {
+    expect(scoreSubsequence('rdvrP', 'ChatProvider')).toBe(0)
+  })
+
+  it('should score case-insensitive exact names highest', () => {
+    expect(scoreSubsequence('chatprovider', 'ChatProvider')).toBe(100)
+  })
+
+  it('should drop results below the threshold and cap by maxResults', () => {
+    const nodes = [
+      element('1', 'ChatProvider', '/b.ts'),
+      element('2', 'CheckoutPriceValidator', '/a.ts'),
+      element('3', 'Unrelated', '/c.ts'),
+    ]
+
+    const all = searchFuzzy('ChtPrvdr', nodes, { fuzzyThreshold: 1 })
+    expect(all.map(r => r.node.name)).not.toContain('Unrelated')
+
+    const capped = searchFuzzy('ChtPrvdr', nodes, { fuzzyThreshold: 1, maxResults: 1 })
+    expect(capped).toHaveLength(1)
+    expect(capped[0]!.node.name).toBe('ChatProvider')
+  })
+
+  it('should break ties by shorter name then path', () => {
+    const nodes = [
+      element('1', 'Parser', '/z.ts'),
+      element('2', 'Parser', '/a.ts'),
+      element('3', 'ParserX', '/a.ts'),
+    ]
+
+    const results = searchFuzzy('prsr', nodes, { fuzzyThreshold: 1 })
+    expect(results.map(r => `${r.node.name}@${r.node.path}`)).toEqual([
+      'Parser@/a.ts',
+      'Parser@/z.ts',
+      'ParserX@/a.ts',
+    ])
+  })
+})
\ No newline at end of file
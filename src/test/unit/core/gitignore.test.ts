@@ -0,0 +1,68 @@
+/**
+ * Gitignore pattern parsing and matching tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { parseGitignore, isGitIgnored, compileGlob } from '../../../core/gitignore.js'
+
+describe('Gitignore Matching', () => {
+  const base = '/repo'
+
+  it('should match unanchored patterns at any depth', () => {
+    const rules = parseGitignore('*.log\n', base)
+    expect(isGitIgnored('/repo/debug.log', false, rules)).toBe(true)
+    expect(isGitIgnored('/repo/a/b/debug.log', false, rules)).toBe(true)
+    expect(isGitIgnored('/repo/debug.ts', false, rules)).toBe(false)
+  })
+
+  it('should anchor patterns containing a slash to the .gitignore directory', () => {
+    const rules = parseGitignore('/out\nsrc/gen\n', base)
+    expect(isGitIgnored('/repo/out', true, rules)).toBe(true)
+    expect(isGitIgnored('/repo/pkg/out', true, rules)).toBe(false)
+    expect(isGitIgnored('/repo/src/gen', true, rules)).toBe(true)
+    expect(isGitIgnored('/repo/pkg/src/gen', true, rules)).toBe(false)
+  })
+
+  it('should apply directory-only patterns to directories', () => {
+    const rules = parseGitignore('cache/\n', base)
+    expect(isGitIgnored('/repo/cache', true, rules)).toBe(true)
+    expect(isGitIgnored('/repo/cache', false, rules)).toBe(false)
+  })
+
+  it('should let later negations re-include files', () => {
+    const rules = parseGitignore('*.rs\n!keep.rs\n', base)
+    expect(isGitIgnored('/repo/drop.rs', false, rules)).toBe(true)
+    expect(isGitIgnored('/repo/keep.rs', false, rules)).toBe(false)
+  })
+
+  it('should support double-star segments', () => {
+    const rules = parseGitignore('**/fixtures/**\n', base)
+    expect(isGitIgnored('/repo/fixtures/a.ts', false, rules)).toBe(true)
+    expect(isGitIgnored('/repo/x/fixtures/y/a.ts', false, rules)).toBe(true)
+  })
+
+  it('should ignore comments and paths outside the base directory', () => {
+    const rules = parseGitignore('# comment\nsecret.ts\n', '/repo/pkg')
+    expect(rules).toHaveLength(1)
+    expect(isGitIgnored('/repo/secret.ts', false, rules)).toBe(false)
+    expect(isGitIgnored('/repo/pkg/secret.ts', false, rules)).toBe(true)
+  })
+
+  it('should compile path globs for include and exclude filters', () => {
+    expect(compileGlob('src/**/*.rs').test('src/a/b/lib.rs')).toBe(true)
+    expect(compileGlob('src/**/*.rs').test('pkg/src/lib.rs')).toBe(false)
+    expect(compileGlob('**/tests/**').test('crates/core/tests/it.rs')).toBe(true)
+    expect(compileGlob('*.py').test('python/calculator.py')).toBe(true)
+    expect(compileGlob('rust').test('rust/src/main.rs')).toBe(true)
+  })
+
+  it('should match rules and globs in any case when case is ignored', () => {
+    const rules = parseGitignore('Build/\n*.LOG\n', base, true)
+    expect(isGitIgnored('/repo/build', true, rules)).toBe(true)
+    expect(isGitIgnored('/repo/debug.log', false, rules)).toBe(true)
+    expect(isGitIgnored('/repo/debug.log', false, parseGitignore('*.LOG\n', base))).toBe(false)
+
+    expect(compileGlob('SRC/**/*.ts', true).test('src/app/main.ts')).toBe(true)
+    expect(compileGlob('SRC/**/*.ts').test('src/app/main.ts')).toBe(false)
+  })
+})
\ No newline at end of file
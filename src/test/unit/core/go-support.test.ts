@@ -0,0 +1,56 @@
+/**
+ * Go language support tests - symbol kinds and receiver-qualified method names
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { extractAllNodes, parseFile } from '../../../core/parser.js'
+import { listSymbols } from '../../../core/symbols.js'
+import { findDefinition } from '../../../core/definitions.js'
+import { searchCode } from '../../../core/search.js'
+
+describe('Go Support', () => {
+  const goFixture = resolve(import.meta.dirname, '../../fixtures/go-example')
+  const calculatorFile = resolve(goFixture, 'calculator/calculator.go')
+
+  it('should qualify method names with their receiver type', async () => {
+    const fileNode = await parseFile(calculatorFile)
+    const names = extractAllNodes(fileNode).filter(n => n.type === 'function').map(n => n.name)
+
+    expect(names).toContain('NewBasicCalculator')
+    expect(names).toContain('BasicCalculator.Add')
+    expect(names).toContain('BasicCalculator.HistoryCount')
+    expect(names).toContain('Stack.Push')
+  })
+
+  it('should find qualified methods by their bare name in search', async () => {
+    const fileNode = await parseFile(calculatorFile)
+    const results = searchCode('Divide', extractAllNodes(fileNode), { exactMatch: true })
+
+    expect(results.map(r => r.node.name)).toEqual(['BasicCalculator.Divide'])
+  })
+
+  it('should outline types, constants, and methods', async () => {
+    const fileNode = await parseFile(calculatorFile)
+    const symbols = listSymbols(fileNode)
+    const byName = new Map(symbols.map(s => [s.name, s.kind]))
+
+    expect(byName.get('Calculator')).toBe('type')
+    expect(byName.get('BasicCalculator')).toBe('type')
+    expect(byName.get('MaxHistory')).toBe('const')
+    expect(byName.get('BasicCalculator.Subtract')).toBe('method')
+    expect(byName.get('NewBasicCalculator')).toBe('function')
+  })
+
+  it('should resolve definitions by bare or qualified name', async () => {
+    const files = [await parseFile(calculatorFile), await parseFile(resolve(goFixture, 'models/user.go'))]
+
+    const bare = findDefinition('Save', files)
+    expect(bare).toHaveLength(1)
+    expect(bare[0]!.qualifiedName).toBe('UserRepository.Save')
+
+    const qualified = findDefinition('BasicCalculator.Multiply', files)
+    expect(qualified).toHaveLength(1)
+    expect(qualified[0]!.kind).toBe('method')
+  })
+})
\ No newline at end of file
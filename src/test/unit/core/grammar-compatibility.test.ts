@@ -0,0 +1,76 @@
+/**
+ * Grammar compatibility tests - a grammar the runtime rejects disables only its own language
+ */
+
+import { describe, it, expect, afterEach, vi } from 'vitest'
+import { mkdtempSync, rmSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+import { createRequire } from 'module'
+import { initializeParsers, getParser, getGrammar, getDisabledLanguages, getLoadedLanguages } from '../../../core/languages.js'
+import { parseContent, parseFile } from '../../../core/parser.js'
+
+const requirePackage = createRequire(import.meta.url)
+
+describe('Grammar Compatibility', () => {
+  afterEach(() => {
+    vi.restoreAllMocks()
+    initializeParsers()
+  })
+
+  it('should disable a language whose grammar is rejected and keep the others, without warning', () => {
+    const warn = vi.spyOn(console, 'warn')
+
+    initializeParsers({ kotlin: { language: {} } })
+
+    expect(getParser('kotlin')).toBeUndefined()
+    expect(getGrammar('kotlin')).toBeUndefined()
+    expect(getDisabledLanguages()).toEqual([{ name: 'kotlin', reason: expect.any(String) }])
+    expect(getLoadedLanguages().map(language => language.name)).not.toContain('kotlin')
+    expect(warn).not.toHaveBeenCalled()
+
+    const fileNode = parseContent('function add(a: number, b: number) { return a + b }', 'add.ts')
+    expect(fileNode.children?.map(child => child.name)).toContain('add')
+  })
+
+  it('should disable a language whose grammar package fails to load', () => {
+    const warn = vi.spyOn(console, 'warn')
+
+    initializeParsers({}, (packageName) => {
+      if (packageName === 'tree-sitter-bash') throw new Error('No native build was found')
+      return requirePackage(packageName)
+    })
+
+    expect(getParser('bash')).toBeUndefined()
+    expect(getGrammar('bash')).toBeUndefined()
+    expect(getDisabledLanguages()).toEqual([{ name: 'bash', reason: expect.stringContaining('No native build was found') }])
+    expect(getParser('typescript')).toBeDefined()
+    expect(warn).not.toHaveBeenCalled()
+  })
+
+  it('should skip files of a disabled language with the reason', async () => {
+    initializeParsers({ kotlin: { language: {} } })
+
+    const directory = mkdtempSync(join(tmpdir(), 'grammar-compatibility-'))
+    try {
+      const filePath = join(directory, 'Main.kt')
+      writeFileSync(filePath, 'fun main() {}\n')
+      const fileNode = await parseFile(filePath)
+
+      expect(fileNode.skipped).toBe(true)
+      expect(fileNode.skipReason).toMatch(/^kotlin is disabled: /)
+    }
+    finally {
+      rmSync(directory, { recursive: true, force: true })
+    }
+  })
+
+  it('should re-enable the language once its grammar loads again', () => {
+    initializeParsers({ kotlin: { language: {} } })
+
+    initializeParsers()
+
+    expect(getParser('kotlin')).toBeDefined()
+    expect(getDisabledLanguages()).toEqual([])
+  })
+})
\ No newline at end of file
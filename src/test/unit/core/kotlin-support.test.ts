@@ -0,0 +1,63 @@
+/**
+ * Kotlin language support tests - symbol kinds, companion objects, and extension receivers
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { extractAllNodes, parseFile } from '../../../core/parser.js'
+import { listSymbols } from '../../../core/symbols.js'
+import { findDefinition } from '../../../core/definitions.js'
+import { findSymbol } from '../../helpers/symbols.js'
+
+describe('Kotlin Support', () => {
+  const kotlinFixture = resolve(import.meta.dirname, '../../fixtures/kotlin-example/src')
+  const extensionsFile = resolve(kotlinFixture, 'Extensions.kt')
+  const userFile = resolve(kotlinFixture, 'User.kt')
+
+  it('should qualify extension functions with their receiver type', async () => {
+    const fileNode = await parseFile(extensionsFile)
+    const names = extractAllNodes(fileNode).filter(n => n.type === 'function').map(n => n.name)
+
+    expect(names).toContain('String.shout')
+    expect(names).toContain('String.orEmptyText')
+    expect(names).toContain('List.sumOfSquares')
+    expect(names).toContain('truncate')
+  })
+
+  it('should outline top-level properties without function locals', async () => {
+    const symbols = listSymbols(await parseFile(extensionsFile))
+
+    expect(findSymbol(symbols, 'MAX_LENGTH')?.kind).toBe('property')
+    expect(findSymbol(symbols, 'String.lastChar')?.kind).toBe('property')
+    expect(findSymbol(symbols, 'List.sumOfSquares')?.children).toEqual([])
+  })
+
+  it('should nest companion objects under their class', async () => {
+    const symbols = listSymbols(await parseFile(userFile))
+    const user = findSymbol(symbols, 'User')
+    const companion = findSymbol(user?.children || [], 'Companion')
+
+    expect(user?.kind).toBe('class')
+    expect(findSymbol(user?.children || [], 'getDisplayName')?.kind).toBe('method')
+    expect(companion?.kind).toBe('object')
+    expect(companion?.children.map(s => s.name)).toEqual(['createUser', 'generateId'])
+    expect(findSymbol(symbols, 'createUser')).toBeUndefined()
+  })
+
+  it('should outline class properties', async () => {
+    const symbols = listSymbols(await parseFile(userFile))
+    const repository = findSymbol(symbols, 'UserRepository')
+
+    expect(findSymbol(repository?.children || [], 'users')?.kind).toBe('property')
+  })
+
+  it('should resolve extension definitions by bare or qualified name', async () => {
+    const files = [await parseFile(extensionsFile)]
+
+    const bare = findDefinition('shout', files)
+    expect(bare).toHaveLength(1)
+    expect(bare[0]!.qualifiedName).toBe('String.shout')
+
+    expect(findDefinition('String.lastChar', files)[0]?.kind).toBe('property')
+  })
+})
\ No newline at end of file
@@ -0,0 +1,55 @@
+/**
+ * Language detection tests - overrides, extensions, shebangs and content heuristics in priority order
+ */
+
+import { describe, it, expect, afterEach } from 'vitest'
+import { resolve } from 'path'
+import { readFileSync } from 'fs'
+import { resolveFileLanguage, setLanguageOverrides } from '../../../core/languages.js'
+
+describe('Language Detection', () => {
+  const fixturesDir = resolve(import.meta.dirname, '../../fixtures')
+  const cppHeader = resolve(fixturesDir, 'cpp-example/src/shapes.h')
+  const cHeader = resolve(fixturesDir, 'c-example/src/user.h')
+
+  function detect(path: string, content?: string) {
+    const resolution = resolveFileLanguage(path, content)
+    return resolution && { language: resolution.language.name, source: resolution.source }
+  }
+
+  afterEach(() => {
+    setLanguageOverrides(fixturesDir, {})
+  })
+
+  it('should use unambiguous extensions without reading content', () => {
+    expect(detect('/repo/src/app.ts')).toEqual({ language: 'typescript', source: 'extension' })
+    expect(detect('/repo/deploy.sh', '#!/usr/bin/env python3')).toEqual({ language: 'bash', source: 'extension' })
+  })
+
+  it('should fall back to the shebang for extensionless scripts', () => {
+    expect(detect('/repo/bin/release', '#!/bin/sh\necho hi')).toEqual({ language: 'bash', source: 'shebang' })
+    expect(detect('/repo/bin/serve', '#!/usr/bin/env node\n')).toEqual({ language: 'javascript', source: 'shebang' })
+    expect(detect('/repo/notes.txt', 'just text')).toBeUndefined()
+  })
+
+  it('should tell C and C++ headers apart by their content', () => {
+    expect(detect(cppHeader, readFileSync(cppHeader, 'utf-8'))).toEqual({ language: 'cpp', source: 'heuristic' })
+    expect(detect(cHeader, readFileSync(cHeader, 'utf-8'))).toEqual({ language: 'c', source: 'heuristic' })
+    expect(detect(cHeader)).toEqual({ language: 'c', source: 'extension' })
+  })
+
+  it('should let overrides win over every other source', () => {
+    setLanguageOverrides(fixturesDir, { 'c-example/**/*.h': 'cpp', 'bash-example/bin/*': 'python' })
+
+    expect(detect(cHeader)).toEqual({ language: 'cpp', source: 'override' })
+    expect(detect(resolve(fixturesDir, 'bash-example/bin/release'), '#!/bin/sh')).toEqual({ language: 'python', source: 'override' })
+    expect(detect(resolve(fixturesDir, 'c-example/src/user.c'))).toEqual({ language: 'c', source: 'extension' })
+
+    setLanguageOverrides(fixturesDir, {})
+    expect(detect(cHeader)).toEqual({ language: 'c', source: 'extension' })
+  })
+
+  it('should reject overrides naming unknown languages', () => {
+    expect(() => setLanguageOverrides(fixturesDir, { '*.x': 'cobol' })).toThrow('Unknown language: cobol')
+  })
+})
\ No newline at end of file
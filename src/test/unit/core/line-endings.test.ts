@@ -0,0 +1,46 @@
+/**
+ * CRLF line ending tests for regex search positions and context lines
+ */
+
+import { describe, it, expect } from 'vitest'
+import { addContextLines, searchByRegex } from '../../../core/search.js'
+import type { TreeNode } from '../../../types/core.js'
+
+const crlfFile: TreeNode = {
+  id: 'file-crlf',
+  type: 'file',
+  path: '/src/crlf.ts',
+  content: 'const first = 1\r\nconst total = compute(first)\r\nexport default total\r\n',
+}
+
+describe('CRLF line endings', () => {
+  it('should report the column of a mid-line match on a CRLF line', () => {
+    const [result] = searchByRegex('compute', [crlfFile])
+
+    expect(result!.node.startLine).toBe(2)
+    expect(result!.node.startColumn).toBe(14)
+    expect(result!.node.endColumn).toBe(21)
+  })
+
+  it('should anchor end-of-line patterns before the carriage return', () => {
+    const results = searchByRegex('total$', [crlfFile])
+
+    expect(results.map(result => [result.node.startLine, result.node.startColumn])).toEqual([[3, 15]])
+    expect(results[0]!.node.name).toBe('total')
+  })
+
+  it('should count CRLF as one break in multi-line matches', () => {
+    const [result] = searchByRegex('first\\)\\s+export', [crlfFile], { regexFlags: 'm' })
+
+    expect(result!.node.startLine).toBe(2)
+    expect(result!.node.endLine).toBe(3)
+    expect(result!.node.endColumn).toBe(6)
+    expect(result!.node.name).toBe('first)')
+  })
+
+  it('should strip carriage returns from context lines', () => {
+    const [result] = addContextLines(searchByRegex('compute', [crlfFile]), [crlfFile], 1, 1)
+
+    expect(result!.context).toBe('const first = 1\nconst total = compute(first)\nexport default total')
+  })
+})
\ No newline at end of file
@@ -2,10 +2,13 @@
  * Parser tests for Tree-Sitter functionality
  */
 
-import { describe, it, expect, beforeAll } from 'vitest'
+import { describe, it, expect, beforeAll, vi } from 'vitest'
 import Parser from 'tree-sitter'
 import JavaScript from 'tree-sitter-javascript'
-import { parseContent } from '../../../core/parser.js'
+import { resolve } from 'path'
+import { parseContent, parseFile, reparseContent, computeContentEdit } from '../../../core/parser.js'
+import { searchCode } from '../../../core/search.js'
+import { listSymbols } from '../../../core/symbols.js'
 
 describe('Tree-Sitter Parser', () => {
   let parser: Parser
@@ -317,3 +320,72 @@ describe('Language Fixture Parsing', () => {
     })
   })
 })
+
+describe('Incremental Re-parsing', () => {
+  const before = 'function first() {\n  return 1\n}\n\nfunction second() {}\n'
+  const after = before.replace('return 1', 'return 40 + 2')
+
+  it('should describe a change as one replaced range', () => {
+    expect(computeContentEdit('abc\ndef', 'abc\nxyzdef')).toEqual({
+      startIndex: 4,
+      oldEndIndex: 4,
+      newEndIndex: 7,
+      startPosition: { row: 1, column: 0 },
+      oldEndPosition: { row: 1, column: 0 },
+      newEndPosition: { row: 1, column: 3 },
+    })
+    expect(computeContentEdit('same', 'same')).toMatchObject({ startIndex: 4, oldEndIndex: 4, newEndIndex: 4 })
+  })
+
+  it('should re-parse from the previous tree and match a full parse', () => {
+    const previous = parseContent(before, 'example.js')
+    const parse = vi.spyOn(Parser.prototype, 'parse')
+
+    const incremental = reparseContent(previous, after)
+    expect(parse).toHaveBeenCalledWith(after, previous.tree)
+    parse.mockRestore()
+
+    const full = parseContent(after, 'example.js')
+    expect(incremental.rawNode.toString()).toBe(full.rawNode.toString())
+    expect(incremental.children?.map(node => [node.name, node.startLine, node.startColumn])).toEqual([
+      ['first', 1, 0],
+      ['second', 5, 0],
+    ])
+    expect(incremental.rawNode.descendantForIndex(after.indexOf('second')).text).toBe('second')
+  })
+
+  it('should parse from scratch when no previous tree is loaded', () => {
+    const previous = { ...parseContent(before, 'example.js'), tree: undefined }
+    const parse = vi.spyOn(Parser.prototype, 'parse')
+
+    const result = reparseContent(previous, after)
+    expect(parse).toHaveBeenCalledWith(after, undefined)
+    parse.mockRestore()
+    expect(result.rawNode.hasError).toBe(false)
+  })
+})
+
+
+describe('Partial Syntax Trees', () => {
+  const brokenFile = resolve(import.meta.dirname, '../../fixtures/partial-syntax/src/orders.ts')
+
+  it('should keep the symbols around a broken function', async () => {
+    const fileNode = await parseFile(brokenFile)
+    const names = listSymbols(fileNode).map(symbol => symbol.name)
+
+    expect(names).toEqual(expect.arrayContaining(['calculateTotal', 'OrderFormatter', 'shippingCost']))
+    expect(searchCode('shippingCost', [fileNode]).map(result => result.node.name)).toContain('shippingCost')
+  })
+
+  it('should record where the parser recovered from errors', async () => {
+    const { parseErrors } = await parseFile(brokenFile)
+
+    expect(parseErrors?.length).toBeGreaterThan(0)
+    for (const error of parseErrors!) {
+      expect(['error', 'missing']).toContain(error.kind)
+      expect(error.startLine).toBeGreaterThanOrEqual(7)
+      expect(error.endLine).toBeLessThanOrEqual(10)
+    }
+    expect(parseContent('function ok() {}', 'ok.js').parseErrors).toBeUndefined()
+  })
+})
\ No newline at end of file
@@ -0,0 +1,65 @@
+/**
+ * PHP language support tests - nested outlines, and symbols inside PHP blocks kept apart from inline HTML
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { extractAllNodes, parseFile } from '../../../core/parser.js'
+import { listSymbols } from '../../../core/symbols.js'
+import { getFileLanguage } from '../../../core/languages.js'
+import { searchByRegex } from '../../../core/search.js'
+import { childNames } from '../../helpers/symbols.js'
+
+describe('PHP Support', () => {
+  const phpFixture = resolve(import.meta.dirname, '../../fixtures/php-example')
+  const calculatorFile = resolve(phpFixture, 'src/Calculator.php')
+  const userFile = resolve(phpFixture, 'src/User.php')
+  const layoutFile = resolve(phpFixture, 'templates/layout.phtml')
+
+  it('should detect .php and .phtml files', () => {
+    expect(getFileLanguage(calculatorFile)?.name).toBe('php')
+    expect(getFileLanguage(layoutFile)?.name).toBe('php')
+  })
+
+  it('should index functions, methods, classes and interfaces', async () => {
+    const fileNode = await parseFile(calculatorFile)
+    const nodes = extractAllNodes(fileNode)
+
+    expect(nodes.filter(n => n.type === 'class').map(n => n.name)).toEqual(['CalculatorInterface', 'Calculator', 'MathUtils'])
+    expect(nodes.filter(n => n.type === 'function').map(n => n.name)).toContain('factorial')
+  })
+
+  it('should nest methods under their class or interface', async () => {
+    const symbols = listSymbols(await parseFile(calculatorFile))
+    const byName = new Map(symbols.map(s => [s.name, s.kind]))
+
+    expect(byName.get('CalculatorInterface')).toBe('interface')
+    expect(byName.get('Calculator')).toBe('class')
+    expect(childNames(symbols, 'Calculator')).toEqual(['add', 'subtract', 'multiply', 'divide'])
+    expect(childNames(symbols, 'CalculatorInterface')).toEqual(['add', 'subtract', 'multiply', 'divide'])
+    expect(symbols.find(s => s.name === 'Calculator')!.children[0]!.kind).toBe('method')
+
+    const userSymbols = listSymbols(await parseFile(userFile))
+    expect(childNames(userSymbols, 'User')).toContain('__construct')
+    expect(childNames(userSymbols, 'UserRepository')).toContain('findByEmail')
+  })
+
+  it('should find symbols inside PHP blocks but not in inline HTML', async () => {
+    const fileNode = await parseFile(layoutFile)
+    const symbols = listSymbols(fileNode)
+
+    expect(symbols.map(s => [s.name, s.kind])).toEqual([['Greets', 'trait'], ['renderHeader', 'function']])
+    expect(childNames(symbols, 'Greets')).toEqual(['greet'])
+    expect(extractAllNodes(fileNode).map(n => n.name)).not.toContain('HeaderTitle')
+  })
+
+  it('should count inline HTML as text rather than code', async () => {
+    const fileNode = await parseFile(layoutFile)
+
+    const inCode = searchByRegex('function \\w+', [fileNode], { scope: 'code', maxResults: 10 })
+    expect(inCode.map(r => r.node.startLine).sort((a, b) => a - b)).toEqual([9, 15])
+
+    const inText = searchByRegex('function \\w+', [fileNode], { scope: 'string', maxResults: 10 })
+    expect(inText.map(r => r.node.startLine).sort((a, b) => a - b)).toEqual([3, 20])
+  })
+})
\ No newline at end of file
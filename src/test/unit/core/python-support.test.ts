@@ -0,0 +1,52 @@
+/**
+ * Python language support tests - decorators and nesting in symbol outlines
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { parseFile } from '../../../core/parser.js'
+import { listSymbols } from '../../../core/symbols.js'
+import { findSymbol } from '../../helpers/symbols.js'
+import type { DocumentSymbol } from '../../../types/core.js'
+
+describe('Python Support', () => {
+  const pythonFixture = resolve(import.meta.dirname, '../../fixtures/python-example')
+
+  async function outline(relativePath: string): Promise<DocumentSymbol[]> {
+    return listSymbols(await parseFile(resolve(pythonFixture, relativePath)))
+  }
+
+  it('should nest methods under classes and report decorators', async () => {
+    const symbols = await outline('calculator/core.py')
+    const calculator = findSymbol(symbols, 'Calculator')!
+
+    expect(calculator.kind).toBe('class')
+    expect(calculator.children.map(c => c.name)).toEqual(['__init__', 'add', 'divide', 'history_count', 'square', '_record'])
+    expect(calculator.children.every(c => c.kind === 'method')).toBe(true)
+
+    expect(findSymbol(calculator.children, 'add')!.decorators).toEqual(['logged'])
+    expect(findSymbol(calculator.children, 'history_count')!.decorators).toEqual(['property'])
+    expect(findSymbol(calculator.children, 'divide')!.decorators).toBeUndefined()
+    expect(findSymbol(symbols, 'CalculationResult')!.decorators).toEqual(['dataclass'])
+  })
+
+  it('should nest inner functions under their enclosing function', async () => {
+    const symbols = await outline('calculator/core.py')
+
+    const logged = findSymbol(symbols, 'logged')!
+    expect(logged.children.map(c => c.name)).toEqual(['wrapper'])
+    expect(logged.children[0]!.decorators).toEqual(['functools.wraps(func)'])
+
+    const record = findSymbol(findSymbol(symbols, 'Calculator')!.children, '_record')!
+    expect(record.children.map(c => c.name)).toEqual(['trim'])
+  })
+
+  it('should expose route and fixture decorators with their arguments', async () => {
+    const routes = await outline('calculator/routes.py')
+    expect(findSymbol(routes, 'add_handler')!.decorators).toEqual(["app.route('/add')"])
+
+    const fixtures = await outline('support/fixtures.py')
+    expect(findSymbol(fixtures, 'calculator')!.decorators).toEqual(['pytest.fixture'])
+    expect(findSymbol(fixtures, 'seeded_calculator')!.decorators).toEqual(["pytest.fixture(scope='module')"])
+  })
+})
\ No newline at end of file
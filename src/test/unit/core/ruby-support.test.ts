@@ -0,0 +1,56 @@
+/**
+ * Ruby language support tests - nested outlines and class methods kept apart from instance methods
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { extractAllNodes, parseFile } from '../../../core/parser.js'
+import { listSymbols } from '../../../core/symbols.js'
+import { findDefinition } from '../../../core/definitions.js'
+import { searchCode } from '../../../core/search.js'
+import { childNames } from '../../helpers/symbols.js'
+
+describe('Ruby Support', () => {
+  const rubyFixture = resolve(import.meta.dirname, '../../fixtures/ruby-example/lib')
+  const calculatorFile = resolve(rubyFixture, 'calculator.rb')
+  const userFile = resolve(rubyFixture, 'user.rb')
+
+  it('should index instance and class methods', async () => {
+    const fileNode = await parseFile(calculatorFile)
+    const names = extractAllNodes(fileNode).filter(n => n.type === 'function').map(n => n.name)
+
+    expect(names).toContain('add')
+    expect(names).toContain('self.factorial')
+    expect(names).toContain('self.default')
+  })
+
+  it('should nest methods under their class or module', async () => {
+    const symbols = listSymbols(await parseFile(calculatorFile))
+    const byName = new Map(symbols.map(s => [s.name, s.kind]))
+
+    expect(byName.get('Calculator')).toBe('class')
+    expect(byName.get('MathUtils')).toBe('module')
+    expect(childNames(symbols, 'Calculator')).toEqual(['self.default', 'add', 'subtract', 'multiply', 'divide'])
+    expect(childNames(symbols, 'MathUtils')).toContain('self.prime?')
+  })
+
+  it('should keep class methods apart from instance methods', async () => {
+    const symbols = listSymbols(await parseFile(userFile))
+    const user = childNames(symbols, 'User')
+
+    expect(user).toContain('display_name')
+    expect(user).toContain('self.create_user')
+    expect(user).not.toContain('create_user')
+  })
+
+  it('should find class methods by their bare name', async () => {
+    const fileNode = await parseFile(calculatorFile)
+    const results = searchCode('factorial', extractAllNodes(fileNode), { exactMatch: true })
+    expect(results.map(r => r.node.name)).toEqual(['self.factorial'])
+
+    const definitions = findDefinition('gcd', [fileNode])
+    expect(definitions).toHaveLength(1)
+    expect(definitions[0]!.qualifiedName).toBe('self.gcd')
+    expect(definitions[0]!.kind).toBe('method')
+  })
+})
\ No newline at end of file
@@ -0,0 +1,83 @@
+/**
+ * Scala language support tests - classes, objects, traits, package objects, and val/var members
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { extractAllNodes, parseFile } from '../../../core/parser.js'
+import { listSymbols } from '../../../core/symbols.js'
+import { findDefinition } from '../../../core/definitions.js'
+import { getLanguageByExtension } from '../../../core/languages.js'
+import { findSymbol } from '../../helpers/symbols.js'
+
+describe('Scala Support', () => {
+  const fixtureDir = resolve(import.meta.dirname, '../../fixtures/scala-example/src')
+  const calculatorFile = resolve(fixtureDir, 'Calculator.scala')
+  const userFile = resolve(fixtureDir, 'User.scala')
+  const packageFile = resolve(fixtureDir, 'package.scala')
+
+  it('should detect .scala and .sc files', () => {
+    expect(getLanguageByExtension('.scala')?.name).toBe('scala')
+    expect(getLanguageByExtension('.sc')?.name).toBe('scala')
+  })
+
+  it('should extract functions and types', async () => {
+    const nodes = extractAllNodes(await parseFile(calculatorFile))
+
+    expect(nodes.filter(n => n.type === 'class').map(n => n.name)).toEqual(['Calculator', 'BasicCalculator', 'MathUtils', 'Main'])
+    expect(nodes.filter(n => n.type === 'function').map(n => n.name)).toEqual(expect.arrayContaining(['add', 'divide', 'factorial', 'factorialHelper', 'fibonacci']))
+  })
+
+  it('should nest members under their enclosing type', async () => {
+    const symbols = listSymbols(await parseFile(calculatorFile))
+
+    expect(symbols.map(s => [s.name, s.kind])).toEqual([
+      ['Calculator', 'trait'],
+      ['BasicCalculator', 'class'],
+      ['MathUtils', 'object'],
+      ['Main', 'object'],
+    ])
+    const calculator = findSymbol(symbols, 'Calculator')
+    expect(calculator?.children.map(s => [s.name, s.kind])).toEqual([
+      ['add', 'method'],
+      ['subtract', 'method'],
+      ['multiply', 'method'],
+      ['divide', 'method'],
+    ])
+    const factorial = findSymbol(findSymbol(symbols, 'MathUtils')?.children || [], 'factorial')
+    expect(factorial?.kind).toBe('method')
+    expect(factorial?.children.map(s => [s.name, s.kind])).toEqual([['factorialHelper', 'function']])
+  })
+
+  it('should outline val and var members without function locals', async () => {
+    const symbols = listSymbols(await parseFile(userFile))
+    const repository = findSymbol(symbols, 'UserRepository')
+
+    expect(findSymbol(repository?.children || [], 'users')?.kind).toBe('property')
+    expect(findSymbol(repository?.children || [], 'deleteById')?.children).toEqual([])
+    expect(symbols.filter(s => s.name === 'User').map(s => s.kind)).toEqual(['class', 'object'])
+  })
+
+  it('should outline package objects and nested objects', async () => {
+    const symbols = listSymbols(await parseFile(packageFile))
+    const utils = findSymbol(symbols, 'utils')
+
+    expect(utils?.kind).toBe('object')
+    expect(utils?.children.map(s => [s.name, s.kind])).toEqual([
+      ['DefaultPrecision', 'property'],
+      ['roundingEnabled', 'property'],
+      ['round', 'method'],
+      ['Formats', 'object'],
+    ])
+    const formats = findSymbol(utils?.children || [], 'Formats')
+    expect(formats?.children.map(s => s.name)).toEqual(['Currency', 'Iso'])
+    expect(findSymbol(formats?.children || [], 'Iso')?.children.map(s => [s.name, s.kind])).toEqual([['timestamp', 'method']])
+  })
+
+  it('should find definitions in nested objects', async () => {
+    const results = findDefinition('timestamp', [await parseFile(packageFile)])
+
+    expect(results).toHaveLength(1)
+    expect(results[0]!.kind).toBe('function')
+  })
+})
\ No newline at end of file
@@ -0,0 +1,94 @@
+/**
+ * Swift language support tests - declaration kinds, nested types, and computed property accessors
+ */
+
+import { describe, it, expect } from 'vitest'
+import { resolve } from 'path'
+import { extractAllNodes, parseFile } from '../../../core/parser.js'
+import { listSymbols } from '../../../core/symbols.js'
+import { findDefinition } from '../../../core/definitions.js'
+import { extractSignatures } from '../../../core/signatures.js'
+import { findSymbol } from '../../helpers/symbols.js'
+
+describe('Swift Support', () => {
+  const calculatorFile = resolve(import.meta.dirname, '../../fixtures/swift-example/src/Calculator.swift')
+
+  it('should extract functions and types', async () => {
+    const nodes = extractAllNodes(await parseFile(calculatorFile))
+    const functions = nodes.filter(n => n.type === 'function').map(n => n.name)
+    const classes = nodes.filter(n => n.type === 'class').map(n => n.name)
+
+    expect(functions).toEqual(expect.arrayContaining(['add', 'subtract', 'divide', 'record', 'multiply', 'main']))
+    expect(classes).toEqual(expect.arrayContaining(['Calculator', 'BasicCalculator', 'Measurement', 'Unit', 'Operation']))
+  })
+
+  it('should tell classes, structs, enums, protocols and extensions apart', async () => {
+    const symbols = listSymbols(await parseFile(calculatorFile))
+
+    expect(symbols.map(s => [s.name, s.kind])).toEqual([
+      ['Calculator', 'protocol'],
+      ['BasicCalculator', 'class'],
+      ['Measurement', 'struct'],
+      ['Operation', 'enum'],
+      ['BasicCalculator', 'extension'],
+      ['main', 'function'],
+    ])
+  })
+
+  it('should nest members and nested types under their type', async () => {
+    const symbols = listSymbols(await parseFile(calculatorFile))
+    const calculator = findSymbol(symbols, 'BasicCalculator')!
+    const measurement = findSymbol(symbols, 'Measurement')!
+
+    expect(calculator.children.map(s => [s.name, s.kind])).toEqual([
+      ['precision', 'property'],
+      ['history', 'property'],
+      ['init', 'constructor'],
+      ['add', 'method'],
+      ['subtract', 'method'],
+      ['divide', 'method'],
+      ['record', 'method'],
+      ['CalculatorError', 'enum'],
+    ])
+    expect(findSymbol(measurement.children, 'Unit')?.kind).toBe('struct')
+    expect(findSymbol(symbols, 'Unit')).toBeUndefined()
+    expect(findSymbol(symbols[0]!.children, 'add')?.kind).toBe('method')
+    expect(symbols[4]!.children.map(s => s.name)).toEqual(['multiply'])
+  })
+
+  it('should list property accessors and observers as members of the property', async () => {
+    const symbols = listSymbols(await parseFile(calculatorFile))
+    const calculator = findSymbol(symbols, 'BasicCalculator')!
+    const description = findSymbol(findSymbol(symbols, 'Measurement')!.children, 'description')
+
+    expect(description?.kind).toBe('property')
+    expect(description?.children.map(s => [s.name, s.kind])).toEqual([['get', 'accessor'], ['set', 'accessor']])
+    expect(findSymbol(calculator.children, 'history')?.children.map(s => s.name)).toEqual(['didSet'])
+    expect(findSymbol(findSymbol(symbols, 'Operation')!.children, 'symbol')?.kind).toBe('property')
+  })
+
+  it('should leave locals out of the outline', async () => {
+    const symbols = listSymbols(await parseFile(calculatorFile))
+
+    expect(findSymbol(symbols, 'main')?.children).toEqual([])
+    expect(findSymbol(findSymbol(symbols, 'BasicCalculator')!.children, 'record')?.children).toEqual([])
+  })
+
+  it('should resolve definitions with their declared kind', async () => {
+    const files = [await parseFile(calculatorFile)]
+
+    expect(findDefinition('Measurement', files)[0]?.kind).toBe('struct')
+    expect(findDefinition('CalculatorError', files)[0]?.kind).toBe('enum')
+    expect(findDefinition('description', files)[0]?.kind).toBe('property')
+  })
+
+  it('should read signatures with Swift visibility defaults', async () => {
+    const signatures = extractSignatures(await parseFile(calculatorFile))
+    const find = (name: string) => signatures.find(s => s.name === name)
+
+    expect(signatures.filter(s => s.name === 'add').map(s => s.visibility)).toEqual(['internal', 'public'])
+    expect(find('divide')?.parameters).toEqual([{ name: 'a', type: 'Double' }, { name: 'b', type: 'Double' }])
+    expect(find('divide')?.returnType).toBe('Double')
+    expect(find('record')?.visibility).toBe('fileprivate')
+  })
+})
\ No newline at end of file
@@ -0,0 +1,79 @@
+/**
+ * Tree cache tests - bounded residency, on-demand re-parsing and pinned trees
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest'
+import { parseContent } from '../../../core/parser.js'
+import {
+  setMaxTrees,
+  getMaxTrees,
+  borrowTree,
+  getResidentTree,
+  getTreeCacheStats,
+  resetTreeCacheStats,
+} from '../../../core/tree-cache.js'
+import type { TreeNode } from '../../../types/core.js'
+
+describe('Tree Cache', () => {
+  const defaultMaxTrees = getMaxTrees()
+
+  beforeEach(() => {
+    setMaxTrees(2)
+    resetTreeCacheStats()
+  })
+
+  afterEach(() => {
+    setMaxTrees(defaultMaxTrees)
+  })
+
+  function parseFunction(name: string) {
+    return parseContent(`function ${name}() {\n  return 1\n}\n`, `${name}.js`)
+  }
+
+  it('should evict the least recently used tree beyond the limit', () => {
+    const first = parseFunction('first')
+    const second = parseFunction('second')
+    const third = parseFunction('third')
+
+    expect(getResidentTree(first)).toBeUndefined()
+    expect(getResidentTree(second)).toBeDefined()
+    expect(getResidentTree(third)).toBeDefined()
+    expect(getTreeCacheStats()).toMatchObject({ maxTrees: 2, residentTrees: 2, evictions: 1 })
+  })
+
+  it('should keep symbol metadata and re-parse evicted trees on demand', () => {
+    const first = parseFunction('first')
+    parseFunction('second')
+    parseFunction('third')
+
+    expect(first.children?.map(node => node.name)).toEqual(['first'])
+    expect(first.rawNode.descendantForIndex(9).text).toBe('first')
+    expect(getResidentTree(first)).toBeDefined()
+
+    expect(first.rawNode.type).toBe('program')
+    expect(getTreeCacheStats()).toMatchObject({ hits: 1, misses: 1, evictions: 2, hitRate: 0.5 })
+  })
+
+  it('should not evict a borrowed tree', () => {
+    const first = parseFunction('first')
+    let second: TreeNode | undefined
+
+    borrowTree(first, (tree) => {
+      second = parseFunction('second')
+      parseFunction('third')
+      expect(getResidentTree(first)).toBe(tree)
+      expect(getTreeCacheStats().pinnedTrees).toBe(1)
+    })
+
+    expect(getResidentTree(second!)).toBeUndefined()
+    expect(getTreeCacheStats()).toMatchObject({ residentTrees: 2, pinnedTrees: 0 })
+  })
+
+  it('should keep every tree without a limit', () => {
+    setMaxTrees(0)
+    const files = ['first', 'second', 'third'].map(parseFunction)
+
+    expect(files.every(file => getResidentTree(file) !== undefined)).toBe(true)
+    expect(getTreeCacheStats().evictions).toBe(0)
+  })
+})
\ No newline at end of file
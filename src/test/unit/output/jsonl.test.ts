@@ -0,0 +1,77 @@
+/**
+ * JSON Lines streaming output tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { createJsonlWriter } from '../../../output/jsonl.js'
+import { searchCode, streamSearchCode } from '../../../core/search.js'
+import type { SearchResult, TreeNode } from '../../../types/core.js'
+
+function element(id: string, name: string, children?: TreeNode[]): TreeNode {
+  return { id, type: 'function', name, path: '/src/users.ts', startLine: 1, endLine: 3, content: `function ${name}() {}`, children }
+}
+
+const nested = element('2', 'UserRepository')
+const nodes = [
+  element('1', 'loadUser', [nested]),
+  nested,
+  element('3', 'formatDate'),
+  element('4', 'User'),
+]
+
+describe('JSONL output', () => {
+  it('should stream matches in traversal order as they are found', () => {
+    const streamed: string[] = []
+    const count = streamSearchCode('User', nodes, {}, result => streamed.push(result.node.name!))
+
+    expect(streamed).toEqual(['loadUser', 'UserRepository', 'User'])
+    expect(count).toBe(3)
+    // Ranked search returns the same matches, best first
+    expect(searchCode('User', nodes).map(result => result.node.name)).toEqual(['User', 'UserRepository', 'loadUser'])
+  })
+
+  it('should stop the walk after maxResults', () => {
+    const streamed: SearchResult[] = []
+    const count = streamSearchCode('User', nodes, { maxResults: 2 }, result => streamed.push(result))
+
+    expect(count).toBe(2)
+    expect(streamed.map(result => result.node.id)).toEqual(['1', '2'])
+  })
+
+  it('should include content only when forced', () => {
+    const [plain] = collect({})
+    const [forced] = collect({ forceContentInclusion: true })
+
+    expect(plain!.contentIncluded).toBe(false)
+    expect(plain!.content).toBeUndefined()
+    expect(forced!.contentIncluded).toBe(true)
+    expect(forced!.content).toBe('function loadUser() {}')
+    expect(forced!.contentLines).toBe(1)
+  })
+
+  it('should write one JSON object per line and close with a summary', () => {
+    const chunks: string[] = []
+    const writer = createJsonlWriter({ write: chunk => chunks.push(chunk) })
+
+    let written = 0
+    streamSearchCode('User', nodes, {}, (result) => {
+      // Earlier results are already written when the next one is found
+      expect(chunks).toHaveLength(written++)
+      writer.write(result)
+    })
+    const summary = writer.end('User')
+
+    expect(chunks.every(chunk => chunk.endsWith('\n') && !chunk.slice(0, -1).includes('\n'))).toBe(true)
+    const lines = chunks.map(chunk => JSON.parse(chunk))
+    expect(lines).toHaveLength(4)
+    expect(lines.slice(0, 3).map(line => line.node.name)).toEqual(['loadUser', 'UserRepository', 'User'])
+    expect(lines[3]).toEqual({ type: 'summary', query: 'User', totalResults: 3 })
+    expect(summary.totalResults).toBe(3)
+  })
+})
+
+function collect(options: { forceContentInclusion?: boolean }): SearchResult[] {
+  const results: SearchResult[] = []
+  streamSearchCode('loadUser', nodes, options, result => results.push(result))
+  return results
+}
\ No newline at end of file
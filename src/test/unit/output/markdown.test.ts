@@ -0,0 +1,91 @@
+/**
+ * Markdown output tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { searchResultsToMarkdown } from '../../../output/markdown.js'
+import type { SearchResult, TreeNode } from '../../../types/core.js'
+
+const ROOT = '/work/project'
+
+const users: TreeNode = {
+  id: 'users',
+  type: 'file',
+  path: `${ROOT}/src/users.ts`,
+  content: 'import { db } from "./db"\r\n\r\nexport function findUser(id: number) {\r\n  return db.get(id)\r\n}\r\n',
+}
+
+const lib: TreeNode = {
+  id: 'lib',
+  type: 'file',
+  path: `${ROOT}/src/lib.rs`,
+  content: 'impl Calculator {\n    pub fn add(&self) -> i32 {\n        // ```\n        1\n    }\n}\n',
+}
+
+function result(file: TreeNode, node: Partial<TreeNode>, extra: Partial<SearchResult> = {}): SearchResult {
+  return {
+    node: { id: 'node', type: 'function', path: file.path, ...node },
+    score: 100,
+    matches: [],
+    contentIncluded: false,
+    ...extra,
+  }
+}
+
+describe('markdown output', () => {
+  it('should write a header, a section per file and a fenced match line', () => {
+    const markdown = searchResultsToMarkdown('findUser', [
+      result(users, { name: 'findUser', startLine: 3 }),
+    ], { root: ROOT, files: [users] })
+
+    expect(markdown).toBe([
+      '# 1 match for `findUser` in 1 file',
+      '## `src/users.ts`',
+      '**`findUser`** function at line 3',
+      '```typescript\n→ 3 | export function findUser(id: number) {\n```',
+    ].join('\n\n'))
+  })
+
+  it('should widen the fence to the context lines and mark the match line', () => {
+    const markdown = searchResultsToMarkdown('db', [
+      result(users, { type: 'match', startLine: 4 }, { contextStartLine: 3, contextEndLine: 5 }),
+    ], { root: ROOT, files: [users] })
+
+    expect(markdown).toContain('```typescript\n  3 | export function findUser(id: number) {\n→ 4 |   return db.get(id)\n  5 | }\n```')
+  })
+
+  it('should group results by file in result order and name their enclosing path', () => {
+    const markdown = searchResultsToMarkdown('add', [
+      result(lib, { name: 'add', startLine: 2 }, { enclosingPath: 'Calculator::add' }),
+      result(users, { name: 'findUser', startLine: 3 }),
+      result(lib, { type: 'match', startLine: 4 }),
+    ], { root: ROOT, files: [users, lib] })
+
+    const headings = markdown.split('\n').filter(line => line.startsWith('#'))
+    expect(headings).toEqual(['# 3 matches for `add` in 2 files', '## `src/lib.rs`', '## `src/users.ts`'])
+    expect(markdown).toContain('**`add`** function at line 2 in `Calculator::add`')
+    expect(markdown).toContain('```rust\n→ 4 |         1\n```')
+  })
+
+  it('should lengthen the fence past backtick runs in the source', () => {
+    const markdown = searchResultsToMarkdown('fence', [
+      result(lib, { type: 'match', startLine: 3 }),
+    ], { root: ROOT, files: [lib] })
+
+    expect(markdown).toContain('````rust\n→ 3 |         // ```\n````')
+  })
+
+  it('should report the page against the totals', () => {
+    const markdown = searchResultsToMarkdown('find', [
+      result(users, { name: 'findUser', startLine: 3 }),
+    ], { root: ROOT, files: [users], totalResults: 12, totalFiles: 4, offset: 5 })
+
+    expect(markdown.split('\n\n').slice(0, 2)).toEqual(['# 12 matches for `find` in 4 files', 'Showing 6-6 of 12.'])
+  })
+
+  it('should explain an empty result', () => {
+    const markdown = searchResultsToMarkdown('x`y', [], { root: ROOT, files: [], message: 'No indexed files match the search filters (language: rust)' })
+
+    expect(markdown).toBe('# No matches for ``x`y``\n\nNo indexed files match the search filters (language: rust)')
+  })
+})
\ No newline at end of file
@@ -0,0 +1,57 @@
+/**
+ * Result position conversion tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { createPositionResolver } from '../../../output/offsets.js'
+import type { TreeNode } from '../../../types/core.js'
+
+const file: TreeNode = {
+  id: 'file',
+  type: 'file',
+  path: '/project/src/labels.ts',
+  content: 'const label = \'😀 café\'; target()\nnaïve(target)\n',
+}
+
+function match(startLine: number, startColumn: number, endColumn: number, path = file.path): TreeNode {
+  return { id: 'match', type: 'identifier', name: 'target', path, startLine, endLine: startLine, startColumn, endColumn }
+}
+
+describe('result positions', () => {
+  const resolvePosition = createPositionResolver([file])
+
+  it('should count an emoji as one character but two UTF-16 units and four bytes', () => {
+    expect(resolvePosition(match(1, 25, 31))).toEqual({
+      startLine: 1,
+      startColumn: 24,
+      endLine: 1,
+      endColumn: 30,
+      utf16Column: 25,
+      utf16EndColumn: 31,
+      // The emoji takes 4 bytes and the é in café 2
+      byteRange: { start: 28, end: 34 },
+    })
+  })
+
+  it('should offset bytes by the lines before the match', () => {
+    const position = resolvePosition(match(2, 6, 12))
+
+    expect(position.startColumn).toBe(6)
+    expect(position.utf16Column).toBe(6)
+    expect(position.byteRange).toEqual({ start: 37 + 7, end: 37 + 13 })
+  })
+
+  it('should count bytes in the file\'s encoding, past its BOM', () => {
+    const bom = createPositionResolver([{ ...file, encoding: 'utf-8-bom' }])
+    expect(bom(match(2, 6, 12)).byteRange).toEqual({ start: 3 + 37 + 7, end: 3 + 37 + 13 })
+
+    // Each UTF-16 code unit, the emoji's two included, takes 2 bytes
+    const utf16 = createPositionResolver([{ ...file, encoding: 'utf-16le' }])
+    expect(utf16(match(1, 25, 31)).byteRange).toEqual({ start: 2 + 25 * 2, end: 2 + 31 * 2 })
+    expect(utf16(match(2, 6, 12)).byteRange).toEqual({ start: 2 + (34 + 6) * 2, end: 2 + (34 + 12) * 2 })
+  })
+
+  it('should fall back to UTF-16 columns when the source is not loaded', () => {
+    expect(createPositionResolver([])(match(1, 25, 31))).toMatchObject({ startColumn: 25, endColumn: 31, byteRange: null })
+  })
+})
\ No newline at end of file
@@ -0,0 +1,41 @@
+/**
+ * Symbol outline output tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { stripVTControlCharacters } from 'util'
+import { formatOutlineLines } from '../../../output/outline.js'
+import type { DocumentSymbol } from '../../../types/core.js'
+
+function symbol(name: string, kind: string, startLine: number, endLine: number, children: DocumentSymbol[] = []): DocumentSymbol {
+  return { name, kind, startLine, endLine, children }
+}
+
+const outline = [
+  symbol('ChatProvider', 'class', 1, 7, [
+    symbol('constructor', 'method', 2, 2),
+    symbol('describe', 'method', 4, 6),
+  ]),
+  symbol('openSession', 'function', 9, 11),
+]
+
+describe('outline output', () => {
+  it('should indent children under their parent with kind, name and lines', () => {
+    expect(formatOutlineLines(outline)).toEqual([
+      'class ChatProvider (lines 1-7)',
+      '  method constructor (line 2)',
+      '  method describe (lines 4-6)',
+      'function openSession (lines 9-11)',
+    ])
+  })
+
+  it('should honor a custom indent', () => {
+    expect(formatOutlineLines(outline, { indent: 4 })[1]).toBe('    method constructor (line 2)')
+  })
+
+  it('should only add color codes when asked', () => {
+    const colored = formatOutlineLines(outline, { color: true })
+    expect(colored[0]).not.toBe('class ChatProvider (lines 1-7)')
+    expect(colored.map(line => stripVTControlCharacters(line))).toEqual(formatOutlineLines(outline))
+  })
+})
\ No newline at end of file
@@ -0,0 +1,23 @@
+/**
+ * Portable path tests for reported result paths
+ */
+
+import { describe, it, expect } from 'vitest'
+import { portablePathReplacer, toPortablePath, toPortablePaths } from '../../../output/paths.js'
+
+describe('Portable paths', () => {
+  it('should convert Windows separators to forward slashes', () => {
+    expect(toPortablePath('C:\\repo\\src\\index.ts', '\\')).toBe('C:/repo/src/index.ts')
+    expect(toPortablePaths([['src\\a.ts', 'src\\b.ts']], '\\')).toEqual([['src/a.ts', 'src/b.ts']])
+  })
+
+  it('should leave POSIX paths unchanged', () => {
+    expect(toPortablePath('/repo/src/odd\\name.ts', '/')).toBe('/repo/src/odd\\name.ts')
+  })
+
+  it('should leave non-path fields untouched when serializing', () => {
+    const json = JSON.stringify({ path: '/repo/src/index.ts', context: 'const re = /\\d/' }, portablePathReplacer)
+
+    expect(JSON.parse(json)).toEqual({ path: '/repo/src/index.ts', context: 'const re = /\\d/' })
+  })
+})
\ No newline at end of file
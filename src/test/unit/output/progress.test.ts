@@ -0,0 +1,57 @@
+/**
+ * Index progress bar tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { createProgressBar, formatProgressBar } from '../../../output/progress.js'
+
+function fakeTerminal(isTTY: boolean) {
+  const writes: string[] = []
+  const stream = {
+    isTTY,
+    columns: 60,
+    write: (chunk: string) => {
+      writes.push(chunk)
+      return true
+    },
+  } as unknown as NodeJS.WriteStream
+  return { stream, writes }
+}
+
+describe('Progress bar', () => {
+  it('should show the bar, counts and the path relative to the working directory', () => {
+    const line = formatProgressBar({ filesDone: 6, totalFiles: 24, currentPath: '/work/src/search.ts' }, { cwd: '/work' })
+
+    expect(line).toBe(`Indexing [${'█'.repeat(6)}${'░'.repeat(18)}] 6/24 src/search.ts`)
+  })
+
+  it('should cut the line to the terminal width', () => {
+    const line = formatProgressBar({ filesDone: 1, totalFiles: 2, currentPath: `/work/${'nested/'.repeat(20)}file.ts` }, { cwd: '/work', columns: 50 })
+
+    expect(line).toHaveLength(49)
+    expect(line).toMatch(/^Indexing \[/)
+  })
+
+  it('should treat an empty project as complete', () => {
+    expect(formatProgressBar({ filesDone: 0, totalFiles: 0, currentPath: '/work' }, { cwd: '/work' })).toContain('█'.repeat(24))
+  })
+
+  it('should only draw on a terminal', () => {
+    expect(createProgressBar(fakeTerminal(false).stream)).toBeUndefined()
+    expect(createProgressBar(fakeTerminal(true).stream)).toBeTypeOf('function')
+  })
+
+  it('should redraw one line and clear it when indexing finishes', () => {
+    const { stream, writes } = fakeTerminal(true)
+    const onProgress = createProgressBar(stream)!
+
+    onProgress({ filesDone: 1, totalFiles: 3, currentPath: `${process.cwd()}/a.ts` })
+    // Still within the redraw interval, so this one is skipped
+    onProgress({ filesDone: 2, totalFiles: 3, currentPath: `${process.cwd()}/b.ts` })
+    onProgress({ filesDone: 3, totalFiles: 3, currentPath: `${process.cwd()}/c.ts` })
+
+    expect(writes).toHaveLength(2)
+    expect(writes[0]).toMatch(/^\r.*1\/3 a\.ts\u001B\[K$/)
+    expect(writes[1]).toBe('\r\u001B[K')
+  })
+})
\ No newline at end of file
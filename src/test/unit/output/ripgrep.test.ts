@@ -0,0 +1,74 @@
+/**
+ * Ripgrep-style output tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { stripVTControlCharacters } from 'util'
+import { formatRipgrepLines, shouldUseColor } from '../../../output/ripgrep.js'
+import type { SearchResult, TreeNode } from '../../../types/core.js'
+
+const CWD = '/work/project'
+
+const file: TreeNode = {
+  id: 'file',
+  type: 'file',
+  path: `${CWD}/src/users.ts`,
+  content: 'import { db } from "./db"\r\n\r\nexport function findUser(id: number) {\r\n  return db.get(id)\r\n}\r\n',
+}
+
+function result(node: Partial<TreeNode>): SearchResult {
+  return {
+    node: { id: 'node', type: 'function', path: file.path, ...node },
+    score: 100,
+    matches: [],
+    contentIncluded: false,
+  }
+}
+
+describe('ripgrep output', () => {
+  it('should print relative path, 1-based line and column, and the source line', () => {
+    const lines = formatRipgrepLines([
+      result({ name: 'findUser', startLine: 3, startColumn: 7, endLine: 5, endColumn: 1 }),
+    ], [file], { cwd: CWD })
+
+    expect(lines).toEqual(['src/users.ts:3:17:export function findUser(id: number) {'])
+  })
+
+  it('should use the match range when the name is not on the line', () => {
+    const lines = formatRipgrepLines([
+      result({ type: 'match', name: 'db.get(', startLine: 4, startColumn: 9, endLine: 4, endColumn: 16 }),
+      result({ type: 'call', startLine: 4, startColumn: 9, endLine: 4, endColumn: 19 }),
+    ], [file], { cwd: CWD })
+
+    expect(lines).toEqual([
+      'src/users.ts:4:10:  return db.get(id)',
+      'src/users.ts:4:10:  return db.get(id)',
+    ])
+  })
+
+  it('should print paths outside the working directory relative to it', () => {
+    const lines = formatRipgrepLines([result({ name: 'findUser', startLine: 3, startColumn: 0 })], [file], { cwd: `${CWD}/docs` })
+
+    expect(lines[0]).toMatch(/^\.\.\/src\/users\.ts:3:17:/)
+  })
+
+  it('should color the path, line number and match only when asked', () => {
+    const [plain] = formatRipgrepLines([result({ name: 'findUser', startLine: 3, startColumn: 0 })], [file], { cwd: CWD })
+    const [colored] = formatRipgrepLines([result({ name: 'findUser', startLine: 3, startColumn: 0 })], [file], { cwd: CWD, color: true })
+
+    expect(plain).not.toContain('\u001B[')
+    expect(colored).toContain('\u001B[35msrc/users.ts\u001B[39m')
+    expect(colored).toContain('\u001B[32m3\u001B[39m')
+    expect(colored).toContain('\u001B[31mfindUser\u001B[39m')
+    expect(stripVTControlCharacters(colored)).toBe(plain)
+  })
+
+  it('should decide auto color from the terminal and NO_COLOR', () => {
+    expect(shouldUseColor('auto', { isTTY: true }, {})).toBe(true)
+    expect(shouldUseColor('auto', { isTTY: false }, {})).toBe(false)
+    expect(shouldUseColor('auto', { isTTY: true }, { NO_COLOR: '1' })).toBe(false)
+    expect(shouldUseColor('auto', { isTTY: true }, { NO_COLOR: '' })).toBe(true)
+    expect(shouldUseColor('always', { isTTY: false }, { NO_COLOR: '1' })).toBe(true)
+    expect(shouldUseColor('never', { isTTY: true }, {})).toBe(false)
+  })
+})
\ No newline at end of file
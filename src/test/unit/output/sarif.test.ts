@@ -0,0 +1,121 @@
+/**
+ * SARIF serialization tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { createSarifLog, searchResultsToSarif, findingsToSarif, errorsToSarif } from '../../../output/sarif.js'
+import type { ActionableError } from '../../../analysis/errors.js'
+import type { Finding } from '../../../types/analysis.js'
+import type { SearchResult, TreeNode } from '../../../types/core.js'
+
+const ROOT = '/work/project'
+
+describe('SARIF output', () => {
+  it('should write a 2.1.0 log with the tool driver and source root', () => {
+    const log = createSarifLog([], { root: ROOT, properties: { projectId: 'demo' } })
+
+    expect(log.version).toBe('2.1.0')
+    expect(log.$schema).toContain('sarif-2.1.0')
+    expect(log.runs).toHaveLength(1)
+    expect(log.runs[0]!.tool.driver.name).toBe('tree-sitter-mcp')
+    expect(log.runs[0]!.originalUriBaseIds).toEqual({ '%SRCROOT%': { uri: 'file:///work/project/' } })
+    expect(log.runs[0]!.properties).toEqual({ projectId: 'demo' })
+  })
+
+  it('should collect each rule once and point results at its index', () => {
+    const log = createSarifLog([
+      { ruleId: 'a', level: 'warning', message: 'first', file: `${ROOT}/x.ts`, startLine: 1 },
+      { ruleId: 'b', level: 'note', message: 'second', file: `${ROOT}/x.ts`, startLine: 2 },
+      { ruleId: 'a', level: 'warning', message: 'third', file: `${ROOT}/y.ts`, startLine: 3 },
+    ], { root: ROOT })
+
+    const run = log.runs[0]!
+    expect(run.tool.driver.rules.map(rule => rule.id)).toEqual(['a', 'b'])
+    expect(run.results.map(result => result.ruleIndex)).toEqual([0, 1, 0])
+    expect(run.results[2]!.locations[0]!.physicalLocation.artifactLocation).toEqual({ uri: 'y.ts', uriBaseId: '%SRCROOT%' })
+  })
+
+  it('should add byte offsets from file content, counting multi-byte characters', () => {
+    const file: TreeNode = { id: 'f', type: 'file', path: `${ROOT}/src/a.ts`, content: 'ab\ncdé fg\n' }
+    const log = createSarifLog([{
+      ruleId: 'r',
+      level: 'note',
+      message: 'm',
+      file: file.path,
+      startLine: 2,
+      startColumn: 3,
+      endLine: 2,
+      endColumn: 6,
+    }], { root: ROOT, files: [file] })
+
+    expect(log.runs[0]!.results[0]!.locations[0]!.physicalLocation.region).toEqual({
+      startLine: 2,
+      startColumn: 3,
+      endLine: 2,
+      endColumn: 6,
+      byteOffset: 5,
+      byteLength: 4,
+    })
+  })
+
+  it('should shift 0-based search columns to 1-based', () => {
+    const result: SearchResult = {
+      node: { id: 'n', type: 'function', name: 'add', path: `${ROOT}/math.ts`, startLine: 3, endLine: 5, startColumn: 0, endColumn: 1 },
+      score: 100,
+      matches: ['add'],
+      contentIncluded: false,
+    }
+    const log = searchResultsToSarif('add', [result], { root: ROOT })
+
+    const sarifResult = log.runs[0]!.results[0]!
+    expect(sarifResult.level).toBe('note')
+    expect(sarifResult.message.text).toBe('function add matches "add"')
+    expect(sarifResult.locations[0]!.physicalLocation.region).toEqual({ startLine: 3, startColumn: 1, endLine: 5, endColumn: 2 })
+  })
+
+  it('should map finding severities to levels and read the line from the location', () => {
+    const findings: Finding[] = [
+      { type: 'quality', category: 'long_method', severity: 'critical', location: `${ROOT}/big.ts:12`, description: 'Too long' },
+      { type: 'deadcode', category: 'unused_file', severity: 'info', location: `${ROOT}/old.ts`, description: 'Unused' },
+    ]
+    const log = findingsToSarif(findings, { root: ROOT })
+
+    const [critical, info] = log.runs[0]!.results
+    expect(critical!.ruleId).toBe('quality/long_method')
+    expect(critical!.level).toBe('error')
+    expect(critical!.locations[0]!.physicalLocation).toEqual({
+      artifactLocation: { uri: 'big.ts', uriBaseId: '%SRCROOT%' },
+      region: { startLine: 12 },
+    })
+    expect(info!.level).toBe('note')
+    expect(info!.locations[0]!.physicalLocation.region).toEqual({ startLine: 1 })
+  })
+
+  it('should keep the 1-based columns of syntax errors', () => {
+    const error: ActionableError = {
+      type: 'missing',
+      nodeType: ')',
+      file: `${ROOT}/broken.ts`,
+      line: 4,
+      column: 10,
+      endLine: 4,
+      endColumn: 10,
+      text: '',
+      context: 'call(a',
+      suggestion: 'Add the closing parenthesis',
+    }
+    const log = errorsToSarif([error], { root: ROOT })
+
+    const sarifResult = log.runs[0]!.results[0]!
+    expect(sarifResult.ruleId).toBe('syntax/missing')
+    expect(sarifResult.level).toBe('error')
+    expect(sarifResult.message.text).toBe('Missing ). Add the closing parenthesis')
+    expect(sarifResult.locations[0]!.physicalLocation.region).toEqual({ startLine: 4, startColumn: 10, endLine: 4, endColumn: 10 })
+  })
+
+  it('should fall back to file URIs outside the root', () => {
+    const log = createSarifLog([{ ruleId: 'r', level: 'note', message: 'm', file: '/elsewhere/z.py', startLine: 1 }], { root: ROOT })
+
+    expect(log.runs[0]!.results[0]!.locations[0]!.physicalLocation.artifactLocation).toEqual({ uri: 'file:///elsewhere/z.py' })
+  })
+})
\ No newline at end of file
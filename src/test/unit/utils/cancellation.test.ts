@@ -0,0 +1,110 @@
+/**
+ * Cooperative cancellation tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { runCancellable, runToCompletion, runWithDeadline, throwIfCancelled, isCancelledError, type CancellableSteps } from '../../../utils/cancellation.js'
+import { searchCancellable, searchCode, searchWithDeadline } from '../../../core/search.js'
+import type { TreeNode } from '../../../types/core.js'
+
+function busyWait(ms: number): void {
+  const until = performance.now() + ms
+  while (performance.now() < until) { /* busy */ }
+}
+
+function* countTo(limit: number, onStep: () => void = () => {}): CancellableSteps<number> {
+  let count = 0
+  while (count < limit) {
+    onStep()
+    count++
+    if (yield) break
+  }
+  return count
+}
+
+describe('Cancellation', () => {
+  it('should run steps to completion without a signal', async () => {
+    expect(runToCompletion(countTo(5))).toBe(5)
+    await expect(runCancellable(countTo(5))).resolves.toBe(5)
+  })
+
+  it('should reject before starting when the signal is already aborted', async () => {
+    const controller = new AbortController()
+    controller.abort()
+    let steps = 0
+
+    await expect(runCancellable(countTo(5, () => steps++), controller.signal)).rejects.toMatchObject({ code: 'CANCELLED' })
+    expect(steps).toBe(0)
+  })
+
+  it('should stop between steps once the signal is aborted mid-run', async () => {
+    const controller = new AbortController()
+    setTimeout(() => controller.abort(), 20)
+
+    // Each step busy-waits so the run spans many time slices
+    let steps = 0
+    const run = runCancellable(countTo(1_000_000, () => {
+      steps++
+      busyWait(1)
+    }), controller.signal)
+
+    const error = await run.catch(caught => caught)
+    expect(isCancelledError(error)).toBe(true)
+    expect(steps).toBeGreaterThan(0)
+    expect(steps).toBeLessThan(1_000_000)
+  })
+
+  it('should only throw from throwIfCancelled once aborted', () => {
+    const controller = new AbortController()
+    expect(() => throwIfCancelled(controller.signal)).not.toThrow()
+    expect(() => throwIfCancelled()).not.toThrow()
+
+    controller.abort()
+    expect(() => throwIfCancelled(controller.signal)).toThrow('Request was cancelled')
+    expect(isCancelledError(new Error('Request was cancelled'))).toBe(false)
+  })
+
+  it('should give the same results as the synchronous search', async () => {
+    const nodes: TreeNode[] = ['UserService', 'UserRepository', 'formatUser', 'Order'].map((name, index) => ({
+      id: String(index),
+      type: 'class',
+      name,
+      path: `/src/file${index}.ts`,
+      startLine: 1,
+      endLine: 1,
+    }))
+
+    const cancellable = await searchCancellable('name', 'User', nodes, {}, new AbortController().signal)
+    expect(cancellable).toEqual(searchCode('User', nodes))
+    expect(cancellable.length).toBe(3)
+  })
+
+  it('should return the partial result once the deadline passes', async () => {
+    const run = await runWithDeadline(countTo(1_000_000, () => busyWait(1)), performance.now() + 20)
+
+    expect(run.timedOut).toBe(true)
+    expect(run.value).toBeGreaterThan(0)
+    expect(run.value).toBeLessThan(1_000_000)
+  })
+
+  it('should finish without timing out when the deadline is not reached', async () => {
+    await expect(runWithDeadline(countTo(5), performance.now() + 60_000)).resolves.toEqual({ value: 5, timedOut: false })
+    await expect(runWithDeadline(countTo(5), Infinity)).resolves.toEqual({ value: 5, timedOut: false })
+  })
+
+  it('should rank the matches a timed out search found before the deadline', async () => {
+    const nodes: TreeNode[] = ['UserService', 'UserRepository', 'formatUser'].map((name, index) => ({
+      id: String(index),
+      type: 'class',
+      name,
+      path: `/src/file${index}.ts`,
+      startLine: 1,
+      endLine: 1,
+    }))
+
+    // A deadline already past stops the name search after the first file
+    const run = await searchWithDeadline('name', 'User', nodes, {}, performance.now() - 1)
+    expect(run.timedOut).toBe(true)
+    expect(run.value.map(result => result.node.name)).toEqual(['UserService'])
+  })
+})
\ No newline at end of file
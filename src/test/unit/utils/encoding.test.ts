@@ -0,0 +1,48 @@
+/**
+ * Source encoding detection tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { decodeSource, detectEncoding, getEncodedLength, toSourceByteOffset } from '../../../utils/encoding.js'
+
+const SOURCE = 'const café = 1\nexport { café }\n'
+
+function utf16be(text: string): Buffer {
+  return Buffer.from(text, 'utf16le').swap16()
+}
+
+describe('Source encodings', () => {
+  it('should leave plain UTF-8 untouched', () => {
+    expect(decodeSource(Buffer.from(SOURCE))).toEqual({ content: SOURCE, encoding: 'utf-8' })
+  })
+
+  it('should strip a UTF-8 BOM', () => {
+    const decoded = decodeSource(Buffer.concat([Buffer.from([0xEF, 0xBB, 0xBF]), Buffer.from(SOURCE)]))
+
+    expect(decoded).toEqual({ content: SOURCE, encoding: 'utf-8-bom' })
+    expect(decoded.content.charCodeAt(0)).not.toBe(0xFEFF)
+  })
+
+  it('should decode UTF-16 with a BOM in either byte order', () => {
+    expect(decodeSource(Buffer.concat([Buffer.from([0xFF, 0xFE]), Buffer.from(SOURCE, 'utf16le')])))
+      .toEqual({ content: SOURCE, encoding: 'utf-16le' })
+    expect(decodeSource(Buffer.concat([Buffer.from([0xFE, 0xFF]), utf16be(SOURCE)])))
+      .toEqual({ content: SOURCE, encoding: 'utf-16be' })
+  })
+
+  it('should read text without a BOM as UTF-8', () => {
+    expect(detectEncoding(Buffer.from('a'))).toBe('utf-8')
+    expect(detectEncoding(Buffer.from([0xFF]))).toBe('utf-8')
+    expect(detectEncoding(Buffer.from([0xEF, 0xBB]))).toBe('utf-8')
+  })
+
+  it('should map decoded offsets back to bytes in the original file', () => {
+    const index = SOURCE.indexOf('export')
+
+    expect(toSourceByteOffset(SOURCE, index)).toBe(Buffer.byteLength(SOURCE.slice(0, index)))
+    expect(toSourceByteOffset(SOURCE, index, 'utf-8-bom')).toBe(3 + Buffer.byteLength(SOURCE.slice(0, index)))
+    expect(toSourceByteOffset(SOURCE, index, 'utf-16le')).toBe(2 + index * 2)
+    expect(getEncodedLength('é', 'utf-8')).toBe(2)
+    expect(getEncodedLength('é', 'utf-16be')).toBe(2)
+  })
+})
\ No newline at end of file
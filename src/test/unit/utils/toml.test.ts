@@ -0,0 +1,54 @@
+/**
+ * TOML reader tests
+ */
+
+import { describe, it, expect } from 'vitest'
+import { parseToml } from '../../../utils/toml.js'
+
+describe('TOML Reader', () => {
+  it('should read strings, numbers, booleans and arrays', () => {
+    const values = parseToml([
+      '# settings',
+      'name = "tree \\"sitter\\""',
+      'path = \'C:\\cache\'',
+      'size = 5_242_880',
+      'ratio = 0.5',
+      'mask = 0xff',
+      'enabled = true',
+      'globs = [',
+      '  "src/**", # sources',
+      '  "lib/**",',
+      ']',
+    ].join('\n'))
+
+    expect(values).toEqual({
+      name: 'tree "sitter"',
+      path: 'C:\\cache',
+      size: 5242880,
+      ratio: 0.5,
+      mask: 255,
+      enabled: true,
+      globs: ['src/**', 'lib/**'],
+    })
+  })
+
+  it('should nest tables and dotted keys', () => {
+    expect(parseToml('a.b = 1\n\n[server]\nport = 80\n[server.tls]\non = false\n')).toEqual({
+      a: { b: 1 },
+      server: { port: 80, tls: { on: false } },
+    })
+  })
+
+  it('should report the line of syntax errors', () => {
+    expect(() => parseToml('a = 1\nb = [1, 2')).toThrow('Line 2:')
+    expect(() => parseToml('a = 1\na = 2')).toThrow('Line 2: Duplicate key "a"')
+    expect(() => parseToml('a = "open')).toThrow('Unterminated string')
+    expect(() => parseToml('a = 1 2')).toThrow('Line 1: Unexpected "2"')
+  })
+
+  it('should reject unsupported syntax instead of misreading it', () => {
+    expect(() => parseToml('when = 1979-05-27')).toThrow('Dates and times are not supported')
+    expect(() => parseToml('point = { x = 1 }')).toThrow('Inline tables are not supported')
+    expect(() => parseToml('[[items]]')).toThrow('Arrays of tables are not supported')
+  })
+})
\ No newline at end of file
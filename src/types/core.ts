@@ -8,6 +8,19 @@ export type JsonArray = JsonValue[]
 
 export type TreeSitterLanguage = unknown
 
+export interface ParseErrorLocation {
+  /** `error` for text the parser could not fit into the grammar, `missing` for a token it inserted */
+  kind: 'error' | 'missing'
+  startLine: number
+  endLine: number
+  startColumn: number
+  endColumn: number
+  /** Node type of the inserted token, for `missing` */
+  expected?: string
+}
+
+export type SourceEncoding = 'utf-8' | 'utf-8-bom' | 'utf-16le' | 'utf-16be'
+
 export interface TreeNode {
   id: string
   type: string
@@ -23,7 +36,16 @@ export interface TreeNode {
   parent?: TreeNode
   skipped?: boolean
   skipReason?: string
+  /** Set on file nodes read from UTF-16 or BOM-prefixed UTF-8; content is always the decoded text */
+  encoding?: SourceEncoding
+  /** Set on file nodes whose tree has ERROR or MISSING nodes; symbols outside them are still indexed */
+  parseErrors?: ParseErrorLocation[]
+  /** Set on C and C++ functions declared without a body, such as header prototypes */
+  declarationOnly?: boolean
+  /** On file nodes, the modification time the directory walk saw; the recency sort reads it instead of a stat */
+  mtimeMs?: number
   rawNode?: any // Raw tree-sitter node for error detection
+  tree?: any // Tree behind rawNode, edited and reused as the base when the file is re-parsed
 }
 
 /**
@@ -79,6 +101,7 @@ export function createLightweightTreeNode(node: TreeNode): TreeNode {
     content: node.content,
     skipped: node.skipped,
     skipReason: node.skipReason,
+    declarationOnly: node.declarationOnly,
     // Deliberately exclude reference properties to break memory chains
     parameters: undefined,
     children: undefined,
@@ -87,12 +110,71 @@ export function createLightweightTreeNode(node: TreeNode): TreeNode {
   }
 }
 
+export interface TreeCacheStats {
+  /** 0 when every tree is kept */
+  maxTrees: number
+  /** Trees tracked for eviction; always 0 without a limit */
+  residentTrees: number
+  pinnedTrees: number
+  hits: number
+  misses: number
+  evictions: number
+  hitRate: number
+}
+
+export interface LanguageIndexStats {
+  files: number
+  symbols: number
+  bytes: number
+}
+
+export interface IndexStats {
+  totalFiles: number
+  totalSymbols: number
+  /** UTF-8 size of the indexed file contents */
+  totalBytes: number
+  /** Files whose parse tree is currently in memory */
+  parseTrees: number
+  /** Rough size of the index itself: file contents plus per-file and per-symbol overhead */
+  estimatedMemoryBytes: number
+  languages: Record<string, LanguageIndexStats>
+}
+
 export interface ProjectConfig {
   directory: string
   languages?: string[]
   ignoreDirs?: string[]
+  /** When false, the default exclude set is not applied and only `ignoreDirs`, `exclude` and `.gitignore` rules leave files out */
+  useDefaultExcludes?: boolean
+  /** Directory levels the walk descends below the root, which is depth 0 (default: 15) */
   maxDepth?: number
+  /** When true, the walk follows symlinks, indexing each file once by its canonical path (default: false) */
+  followSymlinks?: boolean
+  /** When true, followed symlinks may point outside the project root */
+  allowOutsideRoot?: boolean
+  /**
+   * When true, paths differing only in case name one file, and gitignore rules and include/exclude globs
+   * ignore case (default: true on macOS and Windows, whose filesystems ignore case)
+   */
+  ignoreCaseInPaths?: boolean
   autoWatch?: boolean
+  useCache?: boolean
+  threads?: number
+  /** Files larger than this many bytes are skipped during the walk */
+  maxFileSize?: number
+  /** When set, only files matching one of these globs are indexed */
+  include?: string[]
+  /** Globs of files left out of the index */
+  exclude?: string[]
+  /** Directory `include` and `exclude` globs are relative to (default: `directory`); sub-projects inherit their root's */
+  globRoot?: string
+  /** Globs relative to the project root mapped to the language their files are parsed as, ahead of extensions */
+  languageOverrides?: Record<string, string>
+}
+
+export interface ProjectRoot {
+  directory: string
+  rootType: string
 }
 
 export interface Project {
@@ -100,16 +182,53 @@ export interface Project {
   config: ProjectConfig
   files: Map<string, TreeNode>
   nodes: Map<string, TreeNode[]>
+  /** Toolchain named by the root's manifest (npm, rust, go, python, java), or unknown without one */
+  rootType?: string
   isMonorepo?: boolean
   subProjects?: Project[]
+  /** Files the last walk left out because they were too large or binary */
+  skippedFiles?: SkippedFile[]
+  /** Files and directories the last walk left out for matching a `.gitignore` rule */
+  gitIgnoredCount?: number
+  /** Directories the last walk did not enter for lying deeper than `maxDepth` */
+  depthSkipped?: string[]
+  /** Symlinks the last walk did not follow for pointing outside the project root */
+  outsideRootLinks?: string[]
+  /** Files the last parse found but could not index because the parser threw */
+  parseFailures?: string[]
 }
 
+export type SyntaxScope = 'code' | 'comment' | 'string'
+
+export type TestScope = 'include' | 'exclude' | 'only'
+
+export type SearchSort = 'relevance' | 'path' | 'recency'
+
+/** `range` drops results covering the same span, `line` folds results on the same line into one */
+export type SearchDedup = 'range' | 'line' | 'none'
+
 export interface SearchOptions {
   maxResults?: number
   fuzzyThreshold?: number
   exactMatch?: boolean
+  /** Name matching only: the query must match whole identifier words, so `add` no longer finds `address` */
+  wholeWord?: boolean
+  /** Name matching only: camelCase and snake_case segments count as words, so `User` finds `getUser` */
+  subwordAware?: boolean
+  /** Name and regex matching fold case when set; both are case-sensitive by default */
+  caseInsensitive?: boolean
+  regexFlags?: string
   types?: string[]
   pathPattern?: string
+  scope?: SyntaxScope
+  /** Name and fuzzy matching only: shorter queries are rejected unless exact, whole-word or subword-aware; 0 accepts any */
+  minQueryLength?: number
+  /** Name matching only: search declarations of this kind, such as every `async_fn`, instead of indexed elements */
+  kind?: KindFilter
+  /** Drops matches lying wholly outside these lines; file nodes, which carry no lines, always pass */
+  lineRange?: LineRange
+  /** Receives each match's node in place of collecting results, so counting keeps no list; nothing is then returned */
+  onMatch?: (node: TreeNode) => void
 
   // Content inclusion options
   forceContentInclusion?: boolean
@@ -117,11 +236,52 @@ export interface SearchOptions {
   disableContentInclusion?: boolean
 }
 
+/** 1-based, inclusive line span */
+export interface LineRange {
+  startLine: number
+  endLine: number
+}
+
+export interface NodeText {
+  /** Syntax node type, e.g. `function_declaration` */
+  type: string
+  startLine: number
+  endLine: number
+  text: string
+  /** Set when the text was cut at maxNodeBytes */
+  truncated?: boolean
+}
+
+export interface EnclosingScope {
+  name: string
+  kind: string
+  startLine: number
+  endLine: number
+}
+
 export interface SearchResult {
   node: TreeNode
   score: number
   matches: string[]
   context?: string
+  contextStartLine?: number
+  contextEndLine?: number
+  captureName?: string
+  enclosingScope?: EnclosingScope
+  /** Names of the declarations around the result's start, outermost first (`billing::Invoice::total`) */
+  enclosingPath?: string
+  /** Last commit to touch the result's first line, when blame was requested */
+  blame?: BlameInfo
+  /** Subtrees bound to a structural pattern's metavariables, keyed by name without the `$` */
+  metavariables?: Record<string, MetavariableBinding>
+  /** How relevance sorting scored the result; `total` is `match` plus the adjustments */
+  relevance?: RelevanceScore
+  /** Columns of the matched text on `startLine`, in column order; line deduplication adds the matches it folds in */
+  highlight?: MatchRange[]
+  /** Source of the declaration or top-level statement around the match, when requested */
+  nodeText?: NodeText
+  /** The matched config key, for keyPath searches */
+  keyPath?: KeyPathMatch
 
   // Content inclusion fields
   contentIncluded: boolean
@@ -130,6 +290,100 @@ export interface SearchResult {
   contentLines?: number
 }
 
+/**
+ * A result's position in several conventions. Columns are 0-based: `startColumn`/`endColumn` count
+ * characters (code points), the `utf16` columns count UTF-16 code units as VS Code and LSP do by default.
+ */
+export interface ResultPosition {
+  startLine: number
+  startColumn: number
+  endLine: number
+  endColumn: number
+  utf16Column: number
+  utf16EndColumn: number
+  /** UTF-8 byte offsets into the decoded source, end exclusive; null when the source is not loaded */
+  byteRange: { start: number, end: number } | null
+}
+
+/** 0-based columns on one line, end exclusive */
+export interface MatchRange {
+  start: number
+  end: number
+}
+
+export interface RelevanceScore {
+  total: number
+  match: number
+  definition: number
+  proximity: number
+  path: number
+}
+
+export interface MetavariableBinding {
+  text: string
+  startLine: number
+  endLine: number
+  startColumn: number
+  endColumn: number
+}
+
+export interface KeyPathLocation {
+  line: number
+  column: number
+}
+
+/** A YAML anchor (`&name`) or alias (`*name`) on a key's value */
+export interface KeyPathMarker extends KeyPathLocation {
+  name: string
+}
+
+export interface KeyPathMatch {
+  /** Dotted key path; sequence items appear as their 0-based index, e.g. `servers.0.host` */
+  path: string
+  /** The key's value with any anchor or tag left out; null for a YAML key without a value */
+  value: (MetavariableBinding & { type: string }) | null
+  anchor?: KeyPathMarker
+  /** Alias used as the value, with the anchor it refers to when that is defined earlier in the file */
+  alias?: KeyPathMarker & { anchor?: KeyPathLocation }
+  /** First occurrence of the same key in this mapping, set on each later occurrence */
+  duplicateOf?: KeyPathLocation
+}
+
+export interface BlameInfo {
+  author: string | null
+  /** Abbreviated commit hash */
+  commit: string | null
+  /** Set when the file is untracked or has uncommitted changes, so its lines cannot be attributed */
+  dirty?: true
+}
+
+export interface SearchPage {
+  results: SearchResult[]
+  totalResults: number
+  hasMore: boolean
+}
+
+/** Where a search's files went: left out when indexing, filtered out by the request, or searched */
+export interface SearchFileAudit {
+  /** Every file the walk reached, plus each gitignored directory counted once */
+  considered: number
+  excluded: {
+    gitignore: number
+    tooLarge: number
+    binary: number
+    /** Indexed files outside the requested languages */
+    languageFilter: number
+    /** Indexed files dropped by include, exclude, changedSince or testScope */
+    pathFilter: number
+    /** Candidate files without a syntax tree: no grammar, a disabled language or a parser size cap */
+    unparsed: number
+    /** Directories deeper than `maxDepth`, each counted once since the walk does not enter them */
+    depthLimit: number
+  }
+  /** Candidate files with a syntax tree, the ones actually searched */
+  parsed: number
+}
+
 export interface FindUsageResult {
   node: TreeNode
   context: string
@@ -139,18 +393,376 @@ export interface FindUsageResult {
   endColumn: number
 }
 
+export interface DefinitionResult {
+  name: string
+  qualifiedName?: string
+  kind: string
+  nodeType: string
+  path: string
+  startLine: number
+  endLine: number
+  startColumn: number
+  endColumn: number
+  startByte: number
+  endByte: number
+  context: string
+}
+
+export interface ReferenceResult {
+  path: string
+  startLine: number
+  endLine: number
+  startColumn: number
+  endColumn: number
+  startByte: number
+  endByte: number
+  context: string
+  isDefinition: boolean
+}
+
+export interface CallerResult {
+  path: string
+  startLine: number
+  endLine: number
+  startColumn: number
+  endColumn: number
+  startByte: number
+  endByte: number
+  context: string
+  /** Named function or method making the call; absent for calls at the top level of a file */
+  caller?: EnclosingScope
+}
+
+export interface NodeAtPosition {
+  /** The position looked up, after clamping */
+  line: number
+  column: number
+  /** Set when the requested position was past the end of its line or of the file */
+  clamped: boolean
+  requested?: { line: number, column: number }
+  node: SyntaxNodeInfo
+  /** Parent node types from the innermost out to the root */
+  ancestors: string[]
+}
+
+export interface SyntaxNodeInfo {
+  type: string
+  /** False for punctuation and keyword tokens */
+  named: boolean
+  text: string
+  /** Set when `text` was cut short because the node is very large */
+  textTruncated?: true
+  startLine: number
+  endLine: number
+  startColumn: number
+  endColumn: number
+  startByte: number
+  endByte: number
+}
+
+export interface ImplementationResult {
+  /** The implementing type as written, e.g. `Box<T>` or `MemoryStore` */
+  implementor: string
+  /** The implemented trait or interface as written, e.g. `fmt::Display` or `Store<T>` */
+  trait: string
+  /** The impl or class declares its own type parameters (includes blanket impls) */
+  generic: boolean
+  path: string
+  startLine: number
+  endLine: number
+  startColumn: number
+  endColumn: number
+  context: string
+}
+
+export interface DocumentSymbol {
+  name: string
+  kind: string
+  startLine: number
+  endLine: number
+  decorators?: string[]
+  /** Rust outer attributes such as `derive(Debug, Clone)` */
+  attributes?: string[]
+  /** A C or C++ function declared without a body, such as a header prototype */
+  declarationOnly?: boolean
+  /** Lines of each clause of an Elixir function defined by several pattern-matched clauses */
+  clauses?: LineRange[]
+  children: DocumentSymbol[]
+}
+
+export interface FunctionSignature {
+  /** Qualified like outline names (`Calculator.Add` for a Go method) */
+  name: string
+  kind: string
+  path: string
+  line: number
+  /** The declaration up to its body, with whitespace collapsed */
+  signature: string
+  parameters: SignatureParameter[]
+  /** Null when the declaration states no return type */
+  returnType: string | null
+  /** Null for languages without a notion of visibility */
+  visibility: string | null
+  /** Only on request; declarations without a body have none */
+  bodyHash?: string
+}
+
+export interface SymbolDiff {
+  added: FunctionSignature[]
+  removed: FunctionSignature[]
+  /** Same name in the same file, with a different signature or visibility */
+  changed: Array<{ before: FunctionSignature, after: FunctionSignature }>
+  /** A removed and an added declaration with the same body */
+  renamed: Array<{ before: FunctionSignature, after: FunctionSignature }>
+}
+
+export interface SignatureParameter {
+  /** Null for unnamed parameters, e.g. Go's `func(int)` */
+  name: string | null
+  /** Null when the parameter has no type annotation */
+  type: string | null
+}
+
+export interface CallGraphNode {
+  name: string
+  kind: string
+  path: string
+  startLine: number
+  endLine: number
+}
+
+export interface CallGraphEdge {
+  caller: CallGraphNode
+  callee: CallGraphNode
+  line: number
+  depth: number
+}
+
+export interface ExternalCall {
+  caller: CallGraphNode
+  name: string
+  line: number
+  depth: number
+}
+
+export interface CallGraph {
+  symbol: string
+  functions: CallGraphNode[]
+  callees: CallGraphEdge[]
+  callers: CallGraphEdge[]
+  external: ExternalCall[]
+}
+
+export interface ImportSpecifier {
+  module: string
+  /** Names the import binds; Python's are the names imported from the module, before any `as` */
+  names: string[]
+  line: number
+}
+
+export interface ImportResolution {
+  /** The import path as written */
+  module: string
+  line: number
+  resolved: boolean
+  /** The project file the import loads, when resolved */
+  file?: string
+}
+
+export interface ImportEdge {
+  from: string
+  to: string
+  module: string
+  line: number
+}
+
+export interface ExternalImport {
+  from: string
+  module: string
+  line: number
+}
+
+export interface ImportGraph {
+  files: string[]
+  edges: ImportEdge[]
+  external: ExternalImport[]
+  cycles: string[][]
+}
+
+export interface RenameEdit {
+  path: string
+  startByte: number
+  endByte: number
+  startLine: number
+  startColumn: number
+  endLine: number
+  endColumn: number
+  oldText: string
+  /** The new name, or `old: new` where a shorthand property or field has to keep its key */
+  newText: string
+}
+
+export interface RenameTarget {
+  name: string
+  kind: string
+  path: string
+  startLine: number
+  startColumn: number
+}
+
+export interface RenamePlan {
+  target?: RenameTarget
+  edits: RenameEdit[]
+  files: string[]
+  note?: string
+}
+
+export interface ReplacePlan {
+  edits: RenameEdit[]
+  files: string[]
+  note?: string
+}
+
+export interface DeadSymbol {
+  name: string
+  kind: string
+  path: string
+  startLine: number
+  endLine: number
+  startColumn: number
+  endColumn: number
+  context: string
+  /** `medium` in languages that can reach names through strings (getattr, obj[name]) */
+  confidence: 'high' | 'medium'
+}
+
+export interface TypeRelation {
+  subtype: string
+  supertype: string
+  relation: 'extends' | 'implements'
+  /** File declaring the relation */
+  path: string
+  line: number
+}
+
+export interface TypeHierarchyNode {
+  name: string
+  kind?: string
+  /** How this type relates to its parent in the tree; absent on the root */
+  relation?: TypeRelation['relation']
+  /** Declaration site; absent for types outside the project */
+  path?: string
+  startLine?: number
+  supertypes?: TypeHierarchyNode[]
+  subtypes?: TypeHierarchyNode[]
+}
+
+export interface TypeHierarchy {
+  root: TypeHierarchyNode
+  direction: 'up' | 'down' | 'both'
+  /** Explains an empty hierarchy */
+  note?: string
+}
+
+export interface FunctionComplexity {
+  file: string
+  function: string
+  complexity: number
+  startLine: number
+}
+
+export interface TodoItem {
+  tag: string
+  author?: string
+  message: string
+  path: string
+  startLine: number
+  startColumn: number
+  context: string
+}
+
+export interface SkippedFile {
+  path: string
+  reason: 'too_large' | 'binary'
+  size: number
+}
+
+/** A file whose language was not decided by its extension alone */
+export interface ClassifiedFile {
+  path: string
+  language: string
+  source: Exclude<LanguageSource, 'extension'>
+}
+
 export interface FileChange {
   type: 'created' | 'modified' | 'deleted'
   path: string
   timestamp: number
 }
 
+/** How a file's language was chosen, in the order the sources are tried */
+export type LanguageSource = 'override' | 'extension' | 'shebang' | 'heuristic'
+
+export interface LanguageResolution {
+  language: LanguageConfig
+  source: LanguageSource
+}
+
 export interface LanguageConfig {
   name: string
   extensions: string[]
   parserName: string
+  /** Language this one is a variant of; selecting that language by name selects this one too */
+  dialectOf?: string
+  /** Joins nested scope names in enclosing paths (default: `.`) */
+  scopeSeparator?: string
   functionTypes: string[]
   classTypes: string[]
+  branchTypes: string[]
+  importTypes: string[]
+  definitionKinds: Record<string, string>
+  /** Keywords marking a declaration's modifiers, mapped to the modifier (`suspend` -> `async` in Kotlin) */
+  modifierKeywords?: Record<string, DeclarationModifier>
+}
+
+export type DeclarationModifier = 'async' | 'pub' | 'static'
+
+/** Declarations a `kind` search keeps: one of `kinds`, written with every modifier in `modifiers` */
+export interface KindFilter {
+  kinds: ReadonlySet<string>
+  modifiers: DeclarationModifier[]
+}
+
+export interface LoadedLanguage {
+  name: string
+  extensions: string[]
+  /** Version of the grammar's npm package; null for custom grammars */
+  grammarVersion: string | null
+  custom: boolean
+}
+
+export interface DisabledLanguage {
+  name: string
+  /** Why the tree-sitter runtime rejected the grammar */
+  reason: string
+}
+
+export interface GrammarRegistration {
+  name: string
+  extensions: string[]
+  grammar: TreeSitterLanguage
+  /** Grammar node types mapped to symbol kinds (`function`, `class`, `variable`, ...) */
+  definitionKinds: Record<string, string>
+  /** Node types counted as decision points for complexity */
+  branchTypes?: string[]
+  /** Where the grammar was loaded from, so parse workers can load it too */
+  path?: string
+}
+
+/** Registered grammars and per-root overrides, in a form that can be posted to a worker thread */
+export interface LanguageSetup {
+  grammars: Omit<GrammarRegistration, 'grammar'>[]
+  overrides: [string, Record<string, string>][]
 }
 
 export interface ImportContext {
@@ -168,16 +780,63 @@ export interface ResolutionResult {
 export interface SearchCodeArgs {
   projectId: string
   query: string
+  queryType?: 'name' | 'tree-sitter'
+  mode?: 'name' | 'regex' | 'fuzzy' | 'structural' | 'keyPath'
+  regexFlags?: string
   maxResults?: number
   fuzzyThreshold?: number
   exactMatch?: boolean
+  minQueryLength?: number
+  /** Declaration kind to enumerate, optionally with modifier prefixes (`struct`, `async_fn`, `pub_struct`) */
+  kind?: string
+  /** Limits the search to one file, absolute or relative to the project root */
+  file?: string
+  /** With file, the lines to search; out-of-range values are clamped to the file */
+  startLine?: number
+  endLine?: number
   types?: string[]
   pathPattern?: string
+  ignoreDirs?: string[]
+  useDefaultExcludes?: boolean
+  maxDepth?: number
+  followSymlinks?: boolean
+  allowOutsideRoot?: boolean
+  language?: string | string[]
+  include?: string[]
+  exclude?: string[]
+  offset?: number
+  limit?: number
+  contextBefore?: number
+  contextAfter?: number
+  /** Adds each result's enclosing declaration source as `nodeText`, cut at `maxNodeBytes` */
+  includeNodeText?: boolean
+  maxNodeBytes?: number
+  scope?: SyntaxScope
+  testScope?: TestScope
+  groupBy?: 'file'
+  groupOrder?: 'count' | 'path'
+  /** Stops the search between files once this many milliseconds pass, returning the matches found so far */
+  timeoutMs?: number
 
   // Content inclusion options
   forceContentInclusion?: boolean
   maxContentLines?: number
   disableContentInclusion?: boolean
+  format?: 'json' | 'sarif'
+}
+
+export interface SearchBufferArgs {
+  /** Source text to search, e.g. an unsaved editor buffer */
+  content: string
+  language: string
+  query: string
+  queryType?: 'name' | 'tree-sitter'
+  mode?: 'name' | 'regex' | 'fuzzy' | 'structural' | 'keyPath'
+  regexFlags?: string
+  maxResults?: number
+  exactMatch?: boolean
+  caseInsensitive?: boolean
+  types?: string[]
 }
 
 export interface FindUsageArgs {
@@ -189,6 +848,129 @@ export interface FindUsageArgs {
   pathPattern?: string
 }
 
+export interface FindDefinitionArgs {
+  projectId: string
+  symbol: string
+  file?: string
+  line?: number
+}
+
+export interface FindReferencesArgs {
+  projectId: string
+  symbol: string
+  includeDefinition?: boolean
+  maxResults?: number
+  pathPattern?: string
+}
+
+export interface FindCallersArgs {
+  projectId: string
+  symbol: string
+  maxResults?: number
+  pathPattern?: string
+}
+
+export interface RenameSymbolArgs {
+  projectId: string
+  symbol: string
+  newName: string
+  file?: string
+  line?: number
+}
+
+export interface SearchAndReplaceArgs {
+  projectId: string
+  pattern: string
+  replacement: string
+  regexFlags?: string
+  caseInsensitive?: boolean
+  include?: string[]
+  exclude?: string[]
+  language?: string | string[]
+  apply?: boolean
+}
+
+export interface FindImplementationsArgs {
+  projectId: string
+  symbol: string
+  maxResults?: number
+  pathPattern?: string
+}
+
+export interface ListSymbolsArgs {
+  projectId: string
+  file?: string
+  depth?: number
+  pathPattern?: string
+}
+
+export interface SymbolDiffArgs {
+  projectId: string
+  from: string
+  to?: string
+  pathPattern?: string
+}
+
+export interface ExtractSignaturesArgs {
+  projectId: string
+  file?: string
+  pathPattern?: string
+}
+
+export interface GetNodeAtPositionArgs {
+  projectId: string
+  file: string
+  line: number
+  column: number
+  anonymous?: boolean
+}
+
+export interface CallGraphArgs {
+  projectId: string
+  symbol: string
+  depth?: number
+  pathPattern?: string
+}
+
+export interface ImportGraphArgs {
+  projectId: string
+  file?: string
+  pathPattern?: string
+}
+
+export interface TypeHierarchyArgs {
+  projectId: string
+  symbol: string
+  direction?: 'up' | 'down' | 'both'
+  depth?: number
+}
+
+export interface ComplexityArgs {
+  projectId: string
+  threshold?: number
+  pathPattern?: string
+  maxResults?: number
+}
+
+export interface FindDuplicatesArgs {
+  projectId: string
+  minNodes?: number
+  pathPattern?: string
+  maxResults?: number
+}
+
+export interface FindDeadCodeArgs {
+  projectId: string
+  pathPattern?: string
+  maxResults?: number
+}
+
+export interface FindTodosArgs {
+  projectId: string
+  tags?: string[]
+  pathPattern?: string
+}
+
 export interface AnalyzeCodeArgs {
   projectId: string
   analysisTypes?: string[]
@@ -197,6 +979,7 @@ export interface AnalyzeCodeArgs {
   severity?: 'critical' | 'warning' | 'info'
   pathPattern?: string
   maxResults?: number
+  format?: 'json' | 'sarif'
 }
 
 export interface InitializeProjectArgs {
@@ -213,9 +996,39 @@ export interface ProjectStatusArgs {
   includeStats?: boolean
 }
 
+export interface OpenProjectArgs {
+  directory: string
+  projectId?: string
+  ignoreDirs?: string[]
+  useDefaultExcludes?: boolean
+  maxDepth?: number
+  followSymlinks?: boolean
+  allowOutsideRoot?: boolean
+}
+
+export interface CloseProjectArgs {
+  projectId: string
+}
+
+export interface StatsArgs {
+  projectId?: string
+  directory?: string
+}
+
+/**
+ * One chunk of a streamed tool response: `items` continue the result's `field` array. Chunks are numbered
+ * from 0 across all fields, in the order they are sent.
+ */
+export interface PartialToolResult {
+  tool: string
+  field: string
+  chunk: number
+  items: JsonValue[]
+}
+
 export interface MCPToolRequest {
   params: {
     name: string
-    arguments: SearchCodeArgs | FindUsageArgs | AnalyzeCodeArgs | InitializeProjectArgs | ProjectStatusArgs
+    arguments: SearchCodeArgs | SearchBufferArgs | FindUsageArgs | FindDefinitionArgs | FindReferencesArgs | FindCallersArgs | RenameSymbolArgs | SearchAndReplaceArgs | FindImplementationsArgs | ListSymbolsArgs | ExtractSignaturesArgs | SymbolDiffArgs | GetNodeAtPositionArgs | CallGraphArgs | ImportGraphArgs | TypeHierarchyArgs | ComplexityArgs | FindDuplicatesArgs | FindDeadCodeArgs | FindTodosArgs | AnalyzeCodeArgs | InitializeProjectArgs | ProjectStatusArgs | OpenProjectArgs | CloseProjectArgs | StatsArgs
   }
 }
\ No newline at end of file
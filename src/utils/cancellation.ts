@@ -0,0 +1,97 @@
+/**
+ * Cooperative cancellation - long-running loops check an AbortSignal between files
+ */
+
+import { setImmediate as yieldToEventLoop } from 'timers/promises'
+import { createError, isTreeSitterError, ERROR_CODES, type TreeSitterError } from './errors.js'
+
+// Abort notifications only arrive when the event loop gets a turn, so synchronous work is sliced this finely
+const TIME_SLICE_MS = 10
+
+/**
+ * Work that yields once per file; the return value is the finished result. Resuming it with `next(true)`
+ * asks it to stop early and return what it has so far.
+ */
+export type CancellableSteps<T> = Generator<void, T, boolean | undefined>
+
+export interface DeadlineRun<T> {
+  value: T
+  /** Set when the deadline passed and `value` holds only the steps run until then */
+  timedOut: boolean
+}
+
+export function createCancelledError(): TreeSitterError {
+  return createError(ERROR_CODES.CANCELLED, 'Request was cancelled')
+}
+
+export function throwIfCancelled(signal?: AbortSignal): void {
+  if (signal?.aborted) {
+    throw createCancelledError()
+  }
+}
+
+export function isCancelledError(error: unknown): boolean {
+  return isTreeSitterError(error) && error.code === ERROR_CODES.CANCELLED
+}
+
+/**
+ * Runs steps without interruption
+ */
+export function runToCompletion<T>(steps: CancellableSteps<T>): T {
+  for (;;) {
+    const step = steps.next()
+    if (step.done) return step.value
+  }
+}
+
+/**
+ * Runs steps, yielding to the event loop between files once a time slice is used up and
+ * stopping with a CANCELLED error as soon as `signal` is aborted
+ */
+export async function runCancellable<T>(steps: CancellableSteps<T>, signal?: AbortSignal): Promise<T> {
+  throwIfCancelled(signal)
+  if (!signal) return runToCompletion(steps)
+
+  let sliceStart = performance.now()
+  for (;;) {
+    const step = steps.next()
+    if (step.done) return step.value
+
+    if (performance.now() - sliceStart >= TIME_SLICE_MS) {
+      await yieldToEventLoop()
+      throwIfCancelled(signal)
+      sliceStart = performance.now()
+    }
+  }
+}
+
+/**
+ * Runs steps like runCancellable, but once `deadline` (a `performance.now()` time) passes between two
+ * steps, stops them early and returns their partial result
+ */
+export async function runWithDeadline<T>(steps: CancellableSteps<T>, deadline: number, signal?: AbortSignal): Promise<DeadlineRun<T>> {
+  if (deadline === Infinity) return { value: await runCancellable(steps, signal), timedOut: false }
+  throwIfCancelled(signal)
+
+  let sliceStart = performance.now()
+  for (;;) {
+    const step = steps.next()
+    if (step.done) return { value: step.value, timedOut: false }
+
+    const now = performance.now()
+    if (now >= deadline) return { value: stopEarly(steps), timedOut: true }
+
+    if (now - sliceStart >= TIME_SLICE_MS) {
+      await yieldToEventLoop()
+      throwIfCancelled(signal)
+      sliceStart = performance.now()
+    }
+  }
+}
+
+function stopEarly<T>(steps: CancellableSteps<T>): T {
+  for (;;) {
+    const step = steps.next(true)
+    if (step.done) return step.value
+  }
+}
\ No newline at end of file
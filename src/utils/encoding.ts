@@ -0,0 +1,97 @@
+/**
+ * Source encoding detection - decodes UTF-8 (with or without BOM) and UTF-16 files to strings for parsing
+ */
+
+import { readFileSync } from 'fs'
+import type { SourceEncoding } from '../types/core.js'
+
+export interface DecodedSource {
+  content: string
+  encoding: SourceEncoding
+}
+
+const BOM_LENGTHS: Record<SourceEncoding, number> = {
+  'utf-8': 0,
+  'utf-8-bom': 3,
+  'utf-16le': 2,
+  'utf-16be': 2,
+}
+
+/**
+ * UTF-16 is recognized by its byte order mark, which Windows editors always write; anything else is UTF-8
+ */
+export function detectEncoding(buffer: Buffer): SourceEncoding {
+  if (buffer[0] === 0xEF && buffer[1] === 0xBB && buffer[2] === 0xBF) return 'utf-8-bom'
+  if (buffer[0] === 0xFF && buffer[1] === 0xFE) return 'utf-16le'
+  if (buffer[0] === 0xFE && buffer[1] === 0xFF) return 'utf-16be'
+  return 'utf-8'
+}
+
+/**
+ * Decodes file bytes to a string without the BOM, so it never reaches the parser as a leading character
+ */
+export function decodeSource(buffer: Buffer): DecodedSource {
+  const encoding = detectEncoding(buffer)
+  const body = buffer.subarray(BOM_LENGTHS[encoding])
+
+  switch (encoding) {
+    case 'utf-16le':
+      return { content: body.toString('utf16le'), encoding }
+    case 'utf-16be': {
+      // Node has no big-endian decoder; swap a copy into little-endian, dropping a dangling odd byte
+      const swapped = Buffer.from(body.subarray(0, body.length & ~1)).swap16()
+      return { content: swapped.toString('utf16le'), encoding }
+    }
+    default:
+      return { content: body.toString('utf-8'), encoding }
+  }
+}
+
+/**
+ * Encodes text back into a file's original encoding, restoring its BOM
+ */
+export function encodeSource(content: string, encoding: SourceEncoding = 'utf-8'): Buffer {
+  switch (encoding) {
+    case 'utf-8-bom':
+      return Buffer.concat([Buffer.from([0xEF, 0xBB, 0xBF]), Buffer.from(content, 'utf-8')])
+    case 'utf-16le':
+      return Buffer.concat([Buffer.from([0xFF, 0xFE]), Buffer.from(content, 'utf16le')])
+    case 'utf-16be':
+      return Buffer.concat([Buffer.from([0xFE, 0xFF]), Buffer.from(content, 'utf16le').swap16()])
+    default:
+      return Buffer.from(content, 'utf-8')
+  }
+}
+
+/**
+ * Reads a file whole and closes it before returning. Memory-mapping would not save a copy here: Node has
+ * no mmap, and both the parser and the index need the decoded string, which is kept as the file's content.
+ */
+export function readSourceFile(filePath: string): DecodedSource {
+  return decodeSource(readFileSync(filePath))
+}
+
+/**
+ * A null byte in the first bytes marks a binary file, unless a UTF-16 BOM explains it
+ */
+export function isBinaryContent(buffer: Buffer): boolean {
+  const encoding = detectEncoding(buffer)
+  if (encoding === 'utf-16le' || encoding === 'utf-16be') return false
+  return buffer.includes(0)
+}
+
+/**
+ * Maps an offset into decoded content back to a byte offset into the original file, BOM included
+ */
+export function toSourceByteOffset(content: string, index: number, encoding: SourceEncoding = 'utf-8'): number {
+  return BOM_LENGTHS[encoding] + getEncodedLength(content.slice(0, index), encoding)
+}
+
+/**
+ * Byte length of decoded text in the file's encoding
+ */
+export function getEncodedLength(text: string, encoding: SourceEncoding = 'utf-8'): number {
+  return encoding === 'utf-16le' || encoding === 'utf-16be'
+    ? text.length * 2
+    : Buffer.byteLength(text, 'utf-8')
+}
\ No newline at end of file
@@ -30,6 +30,9 @@ export const ERROR_CODES = {
   PARSE_ERROR: 'PARSE_ERROR',
   FILE_ERROR: 'FILE_ERROR',
   SEARCH_ERROR: 'SEARCH_ERROR',
+  PROJECT_ERROR: 'PROJECT_ERROR',
+  CONFIG_ERROR: 'CONFIG_ERROR',
+  CANCELLED: 'CANCELLED',
 } as const
 
 export type ErrorCode = typeof ERROR_CODES[keyof typeof ERROR_CODES]
@@ -0,0 +1,200 @@
+/**
+ * Minimal TOML reader for config files - key/value pairs, [tables], strings, numbers, booleans and arrays.
+ * Dates, inline tables, multi-line strings and arrays of tables are rejected rather than misread.
+ */
+
+export type TomlValue = string | number | boolean | TomlValue[] | TomlTable
+
+export interface TomlTable {
+  [key: string]: TomlValue
+}
+
+const BARE_KEY = /[A-Za-z0-9_-]/
+const NUMBER = /^[+-]?(?:inf|nan|0x[0-9A-Fa-f_]+|0o[0-7_]+|0b[01_]+|\d[\d_]*(?:\.\d[\d_]*)?(?:[eE][+-]?\d[\d_]*)?)/
+const ESCAPES: Record<string, string> = { 'b': '\b', 't': '\t', 'n': '\n', 'f': '\f', 'r': '\r', '"': '"', '\\': '\\' }
+
+/**
+ * Parses TOML text into nested plain objects; errors name the offending line
+ */
+export function parseToml(content: string): TomlTable {
+  const root: TomlTable = {}
+  const definedTables = new Set<TomlTable>()
+  let table = root
+  let pos = 0
+  let line = 1
+
+  const fail: (message: string) => never = (message) => {
+    throw new Error(`Line ${line}: ${message}`)
+  }
+
+  const skipSpaces = (newlines: boolean) => {
+    while (pos < content.length) {
+      const char = content[pos]!
+      if (char === ' ' || char === '\t' || char === '\r') {
+        pos++
+      }
+      else if (char === '#') {
+        while (pos < content.length && content[pos] !== '\n') pos++
+      }
+      else if (char === '\n' && newlines) {
+        pos++
+        line++
+      }
+      else {
+        break
+      }
+    }
+  }
+
+  const expectLineEnd = () => {
+    skipSpaces(false)
+    if (pos < content.length && content[pos] !== '\n') fail(`Unexpected "${content[pos]}"`)
+  }
+
+  const readString = (quote: string): string => {
+    if (content.startsWith(quote.repeat(3), pos)) fail('Multi-line strings are not supported')
+    pos++
+    let value = ''
+
+    while (content[pos] !== quote) {
+      const char = content[pos]
+      if (char === undefined || char === '\n') fail('Unterminated string')
+
+      if (char === '\\' && quote === '"') {
+        const escape = content[pos + 1]!
+        if (escape === 'u' || escape === 'U') {
+          const length = escape === 'u' ? 4 : 8
+          const hex = content.slice(pos + 2, pos + 2 + length)
+          if (!/^[0-9A-Fa-f]+$/.test(hex) || hex.length !== length) fail(`Invalid unicode escape "\\${escape}${hex}"`)
+          value += String.fromCodePoint(parseInt(hex, 16))
+          pos += 2 + length
+          continue
+        }
+        if (!(escape in ESCAPES)) fail(`Invalid escape "\\${escape}"`)
+        value += ESCAPES[escape]
+        pos += 2
+        continue
+      }
+
+      value += char
+      pos++
+    }
+
+    pos++
+    return value
+  }
+
+  const readKey = (): string => {
+    const char = content[pos]
+    if (char === '"' || char === '\'') return readString(char)
+
+    const start = pos
+    while (pos < content.length && BARE_KEY.test(content[pos]!)) pos++
+    if (pos === start) fail(char === undefined || char === '\n' ? 'Expected a key' : `Unexpected "${char}"`)
+    return content.slice(start, pos)
+  }
+
+  const readKeyPath = (): string[] => {
+    const keys = [readKey()]
+    skipSpaces(false)
+    while (content[pos] === '.') {
+      pos++
+      skipSpaces(false)
+      keys.push(readKey())
+      skipSpaces(false)
+    }
+    return keys
+  }
+
+  const readValue = (): TomlValue => {
+    const char = content[pos]
+
+    if (char === '"' || char === '\'') return readString(char)
+    if (char === '{') fail('Inline tables are not supported')
+
+    if (char === '[') {
+      pos++
+      const items: TomlValue[] = []
+      skipSpaces(true)
+      while (content[pos] !== ']') {
+        items.push(readValue())
+        skipSpaces(true)
+        if (content[pos] === ',') {
+          pos++
+          skipSpaces(true)
+        }
+        else if (content[pos] !== ']') {
+          fail('Expected "," or "]" in array')
+        }
+      }
+      pos++
+      return items
+    }
+
+    for (const [text, value] of [['true', true], ['false', false]] as const) {
+      if (content.startsWith(text, pos) && !BARE_KEY.test(content[pos + text.length] ?? '')) {
+        pos += text.length
+        return value
+      }
+    }
+
+    const match = NUMBER.exec(content.slice(pos))
+    if (!match) return fail(char === undefined || char === '\n' ? 'Expected a value' : `Invalid value starting with "${char}"`)
+    if (/^[-:T]\d/.test(content.slice(pos + match[0].length))) fail('Dates and times are not supported')
+
+    pos += match[0].length
+    return parseNumber(match[0].replace(/_/g, ''))
+  }
+
+  const descend = (start: TomlTable, keys: string[]): TomlTable => {
+    let current = start
+    for (const key of keys) {
+      const next = current[key] ?? {}
+      if (typeof next !== 'object' || Array.isArray(next)) fail(`"${keys.join('.')}" is not a table`)
+      current[key] = next
+      current = next as TomlTable
+    }
+    return current
+  }
+
+  while (pos < content.length) {
+    skipSpaces(true)
+    if (pos >= content.length) break
+
+    if (content[pos] === '[') {
+      if (content[pos + 1] === '[') fail('Arrays of tables are not supported')
+      pos++
+      skipSpaces(false)
+      const keys = readKeyPath()
+      if (content[pos] !== ']') fail('Expected "]" after table name')
+      pos++
+
+      table = descend(root, keys)
+      if (definedTables.has(table)) fail(`Table [${keys.join('.')}] is defined twice`)
+      definedTables.add(table)
+    }
+    else {
+      const keys = readKeyPath()
+      if (content[pos] !== '=') fail(`Expected "=" after "${keys.join('.')}"`)
+      pos++
+      skipSpaces(false)
+
+      const target = descend(table, keys.slice(0, -1))
+      const key = keys[keys.length - 1]!
+      if (key in target) fail(`Duplicate key "${keys.join('.')}"`)
+      target[key] = readValue()
+    }
+
+    expectLineEnd()
+  }
+
+  return root
+}
+
+function parseNumber(text: string): number {
+  const sign = text.startsWith('-') ? -1 : 1
+  const unsigned = text.replace(/^[+-]/, '')
+
+  if (unsigned === 'inf') return sign * Infinity
+  return unsigned === 'nan' ? NaN : sign * Number(unsigned)
+}
\ No newline at end of file